@@ -51,6 +51,22 @@ for %%f in (%SHADER_DIR%\*.frag) do (
     )
 )
 
+echo.
+
+REM Compile all compute shaders
+echo Compiling compute shaders...
+for %%f in (%SHADER_DIR%\*.comp) do (
+    echo   Compiling %%f...
+    %GLSLC% %%f -o %%f.spv
+    if !errorlevel! neq 0 (
+        echo     [FAILED]
+        set /a ERROR_COUNT+=1
+    ) else (
+        echo     [OK]
+        set /a SUCCESS_COUNT+=1
+    )
+)
+
 echo.
 echo ========================================
 echo Compilation Summary
@@ -6,9 +6,34 @@ echo "Compiling shaders..."
 
 glslc shaders/mesh.vert -o shaders/mesh.vert.spv || exit 1
 glslc shaders/mesh.frag -o shaders/mesh.frag.spv || exit 1
+glslc shaders/mesh_instanced.vert -o shaders/mesh_instanced.vert.spv || exit 1
+glslc shaders/shadow.vert -o shaders/shadow.vert.spv || exit 1
+glslc shaders/bloom.vert -o shaders/bloom.vert.spv || exit 1
+glslc shaders/bloom_extract.frag -o shaders/bloom_extract.frag.spv || exit 1
+glslc shaders/bloom_blur.frag -o shaders/bloom_blur.frag.spv || exit 1
+glslc shaders/bloom_composite.frag -o shaders/bloom_composite.frag.spv || exit 1
+glslc shaders/tonemap.vert -o shaders/tonemap.vert.spv || exit 1
+glslc shaders/tonemap.frag -o shaders/tonemap.frag.spv || exit 1
 glslc shaders/skybox.vert -o shaders/skybox.vert.spv || exit 1
 glslc shaders/skybox.frag -o shaders/skybox.frag.spv || exit 1
 glslc shaders/imgui.vert -o shaders/imgui.vert.spv || exit 1
 glslc shaders/imgui.frag -o shaders/imgui.frag.spv || exit 1
+glslc shaders/wireframe_overlay.vert -o shaders/wireframe_overlay.vert.spv || exit 1
+glslc shaders/wireframe_overlay.frag -o shaders/wireframe_overlay.frag.spv || exit 1
+glslc shaders/material_preview.vert -o shaders/material_preview.vert.spv || exit 1
+glslc shaders/material_preview.frag -o shaders/material_preview.frag.spv || exit 1
+glslc shaders/grid.vert -o shaders/grid.vert.spv || exit 1
+glslc shaders/grid.frag -o shaders/grid.frag.spv || exit 1
+glslc shaders/particle.comp -o shaders/particle.comp.spv || exit 1
+glslc shaders/particle.vert -o shaders/particle.vert.spv || exit 1
+glslc shaders/particle.frag -o shaders/particle.frag.spv || exit 1
+glslc shaders/background.vert -o shaders/background.vert.spv || exit 1
+glslc shaders/background.frag -o shaders/background.frag.spv || exit 1
+glslc shaders/thumbnail.vert -o shaders/thumbnail.vert.spv || exit 1
+glslc shaders/thumbnail.frag -o shaders/thumbnail.frag.spv || exit 1
+glslc shaders/fxaa.vert -o shaders/fxaa.vert.spv || exit 1
+glslc shaders/fxaa.frag -o shaders/fxaa.frag.spv || exit 1
+glslc shaders/minimap.vert -o shaders/minimap.vert.spv || exit 1
+glslc shaders/minimap.frag -o shaders/minimap.frag.spv || exit 1
 
 echo "All shaders compiled successfully!"
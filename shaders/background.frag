@@ -0,0 +1,16 @@
+#version 450
+
+// Two-color vertical background gradient (colors are linear, converted on the CPU)
+
+layout(push_constant) uniform PushConstants {
+    vec4 topColor;
+    vec4 bottomColor;
+} push;
+
+layout(location = 0) in float fragHeight;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    outColor = vec4(mix(push.topColor.rgb, push.bottomColor.rgb, clamp(fragHeight, 0.0, 1.0)), 1.0);
+}
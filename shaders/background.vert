@@ -0,0 +1,17 @@
+#version 450
+
+// Background gradient: fullscreen triangle drawn first in the main pass
+
+layout(location = 0) out float fragHeight; // 0 at the top of the screen, 1 at the bottom
+
+void main() {
+    vec2 positions[3] = vec2[](
+        vec2(-1.0, -1.0),
+        vec2( 3.0, -1.0),
+        vec2(-1.0,  3.0)
+    );
+
+    // Vulkan NDC has y = -1 at the top of the screen
+    gl_Position = vec4(positions[gl_VertexIndex], 1.0, 1.0);
+    fragHeight = positions[gl_VertexIndex].y * 0.5 + 0.5;
+}
@@ -0,0 +1,31 @@
+#version 450
+
+// Bloom Separable Gaussian Blur Fragment Shader
+// Run twice per blur pass: horizontal then vertical (same pattern as the SSAO blur)
+
+layout(binding = 0) uniform sampler2D bloomInput;
+
+layout(push_constant) uniform BloomParams {
+    vec2 direction; // (1, 0) = horizontal, (0, 1) = vertical
+    float threshold; // Unused here
+    float intensity; // Unused here
+} params;
+
+layout(location = 0) in vec2 fragTexCoord;
+layout(location = 0) out vec4 outColor;
+
+// 9-tap Gaussian weights (center + 4 on each side)
+const float weights[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec2 texelSize = 1.0 / vec2(textureSize(bloomInput, 0));
+    vec2 step = params.direction * texelSize;
+
+    vec3 result = texture(bloomInput, fragTexCoord).rgb * weights[0];
+    for (int i = 1; i < 5; ++i) {
+        result += texture(bloomInput, fragTexCoord + step * float(i)).rgb * weights[i];
+        result += texture(bloomInput, fragTexCoord - step * float(i)).rgb * weights[i];
+    }
+
+    outColor = vec4(result, 1.0);
+}
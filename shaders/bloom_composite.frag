@@ -0,0 +1,20 @@
+#version 450
+
+// Bloom Composite Fragment Shader
+// Output is blended additively onto the HDR scene color before tonemapping
+
+layout(binding = 0) uniform sampler2D bloomTexture;
+
+layout(push_constant) uniform BloomParams {
+    vec2 direction; // Unused here
+    float threshold; // Unused here
+    float intensity;
+} params;
+
+layout(location = 0) in vec2 fragTexCoord;
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec3 bloom = texture(bloomTexture, fragTexCoord).rgb;
+    outColor = vec4(bloom * params.intensity, 0.0);
+}
@@ -0,0 +1,26 @@
+#version 450
+
+// Bloom Bright-Pass Fragment Shader
+// Keeps only pixels brighter than the threshold, written into the half-res bloom target
+
+layout(binding = 0) uniform sampler2D sceneColor;
+
+layout(push_constant) uniform BloomParams {
+    vec2 direction; // Unused here
+    float threshold;
+    float intensity; // Unused here
+} params;
+
+layout(location = 0) in vec2 fragTexCoord;
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // Bilinear tap at half resolution averages a 2x2 block of the source
+    vec3 color = texture(sceneColor, fragTexCoord).rgb;
+
+    // Scale by how far the brightest channel is above the threshold (keeps hue)
+    float brightness = max(color.r, max(color.g, color.b));
+    float contribution = max(brightness - params.threshold, 0.0) / max(brightness, 0.0001);
+
+    outColor = vec4(color * contribution, 1.0);
+}
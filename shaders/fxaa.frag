@@ -0,0 +1,155 @@
+#version 450
+
+// FXAA Fragment Shader
+// Fast approximate anti-aliasing over the tonemapped image (after Lottes' FXAA 3.11):
+// finds the local edge direction from luma contrast, walks along the edge to both ends
+// and blends across it by how far the pixel sits from the nearer end
+
+layout(binding = 0) uniform sampler2D ldrColor;
+
+layout(push_constant) uniform FxaaParams {
+    vec2 inverseResolution;
+    float quality; // 0..1: lower edge thresholds, more subpixel blending, longer edge search
+    float split;   // Filter only left of this uv.x (1 = whole image)
+} params;
+
+layout(location = 0) in vec2 fragTexCoord;
+layout(location = 0) out vec4 outColor;
+
+// Perceptual luma (the sRGB target is sampled as linear; sqrt approximates the gamma curve FXAA expects)
+float luma(vec3 color) {
+    return sqrt(dot(color, vec3(0.299, 0.587, 0.114)));
+}
+
+float lumaAt(vec2 uv) {
+    return luma(textureLod(ldrColor, uv, 0.0).rgb);
+}
+
+float lumaAt(vec2 uv, ivec2 offset) {
+    return luma(textureLodOffset(ldrColor, uv, 0.0, offset).rgb);
+}
+
+vec3 fxaa(vec2 uv) {
+    vec2 texel = params.inverseResolution;
+    float quality = clamp(params.quality, 0.0, 1.0);
+    float edgeThreshold = mix(0.25, 0.063, quality);
+    float edgeThresholdMin = mix(0.0833, 0.0312, quality);
+    float subpixel = quality;
+    int searchSteps = int(mix(4.0, 12.0, quality));
+
+    vec3 center = textureLod(ldrColor, uv, 0.0).rgb;
+    float lumaM = luma(center);
+    float lumaN = lumaAt(uv, ivec2(0, -1));
+    float lumaS = lumaAt(uv, ivec2(0, 1));
+    float lumaE = lumaAt(uv, ivec2(1, 0));
+    float lumaW = lumaAt(uv, ivec2(-1, 0));
+
+    // Skip pixels without enough local contrast (flat areas and dark noise)
+    float lumaMax = max(lumaM, max(max(lumaN, lumaS), max(lumaE, lumaW)));
+    float lumaMin = min(lumaM, min(min(lumaN, lumaS), min(lumaE, lumaW)));
+    float range = lumaMax - lumaMin;
+    if (range < max(edgeThresholdMin, lumaMax * edgeThreshold)) {
+        return center;
+    }
+
+    float lumaNW = lumaAt(uv, ivec2(-1, -1));
+    float lumaNE = lumaAt(uv, ivec2(1, -1));
+    float lumaSW = lumaAt(uv, ivec2(-1, 1));
+    float lumaSE = lumaAt(uv, ivec2(1, 1));
+
+    // Subpixel blend: how far the center is from its neighbourhood average
+    float lumaAverage = (2.0 * (lumaN + lumaS + lumaE + lumaW) + lumaNW + lumaNE + lumaSW + lumaSE) / 12.0;
+    float subpixelOffset = smoothstep(0.0, 1.0, clamp(abs(lumaAverage - lumaM) / range, 0.0, 1.0));
+    subpixelOffset = subpixelOffset * subpixelOffset * subpixel;
+
+    // Edge orientation from the second derivative in each direction
+    float edgeHorizontal = abs(lumaNW - 2.0 * lumaW + lumaSW)
+        + 2.0 * abs(lumaN - 2.0 * lumaM + lumaS)
+        + abs(lumaNE - 2.0 * lumaE + lumaSE);
+    float edgeVertical = abs(lumaNW - 2.0 * lumaN + lumaNE)
+        + 2.0 * abs(lumaW - 2.0 * lumaM + lumaE)
+        + abs(lumaSW - 2.0 * lumaS + lumaSE);
+    bool isHorizontal = edgeHorizontal >= edgeVertical;
+
+    // Which side of the pixel the edge is on (the steeper gradient)
+    float luma1 = isHorizontal ? lumaN : lumaW;
+    float luma2 = isHorizontal ? lumaS : lumaE;
+    float gradient1 = luma1 - lumaM;
+    float gradient2 = luma2 - lumaM;
+    bool is1Steepest = abs(gradient1) >= abs(gradient2);
+    float gradientScaled = 0.25 * max(abs(gradient1), abs(gradient2));
+
+    float stepLength = isHorizontal ? texel.y : texel.x;
+    float lumaLocalAverage;
+    if (is1Steepest) {
+        stepLength = -stepLength;
+        lumaLocalAverage = 0.5 * (luma1 + lumaM);
+    } else {
+        lumaLocalAverage = 0.5 * (luma2 + lumaM);
+    }
+
+    // Start half a texel onto the edge and walk along it in both directions
+    vec2 edgeUv = uv;
+    if (isHorizontal) {
+        edgeUv.y += stepLength * 0.5;
+    } else {
+        edgeUv.x += stepLength * 0.5;
+    }
+    vec2 offset = isHorizontal ? vec2(texel.x, 0.0) : vec2(0.0, texel.y);
+
+    vec2 uv1 = edgeUv - offset;
+    vec2 uv2 = edgeUv + offset;
+    float lumaEnd1 = lumaAt(uv1) - lumaLocalAverage;
+    float lumaEnd2 = lumaAt(uv2) - lumaLocalAverage;
+    bool reached1 = abs(lumaEnd1) >= gradientScaled;
+    bool reached2 = abs(lumaEnd2) >= gradientScaled;
+
+    for (int i = 1; i < searchSteps && !(reached1 && reached2); i++) {
+        // Longer strides further out cover long edges in few samples
+        float stride = i < 4 ? 1.0 : (i < 8 ? 2.0 : 4.0);
+        if (!reached1) {
+            uv1 -= offset * stride;
+            lumaEnd1 = lumaAt(uv1) - lumaLocalAverage;
+            reached1 = abs(lumaEnd1) >= gradientScaled;
+        }
+        if (!reached2) {
+            uv2 += offset * stride;
+            lumaEnd2 = lumaAt(uv2) - lumaLocalAverage;
+            reached2 = abs(lumaEnd2) >= gradientScaled;
+        }
+    }
+
+    float distance1 = isHorizontal ? (uv.x - uv1.x) : (uv.y - uv1.y);
+    float distance2 = isHorizontal ? (uv2.x - uv.x) : (uv2.y - uv.y);
+    bool isDirection1 = distance1 < distance2;
+    float distanceFinal = min(distance1, distance2);
+    float edgeLength = distance1 + distance2;
+    float pixelOffset = 0.5 - distanceFinal / edgeLength;
+
+    // Only blend if the luma at the nearer end varies the same way as at the center
+    bool isLumaCenterSmaller = lumaM < lumaLocalAverage;
+    bool correctVariation = ((isDirection1 ? lumaEnd1 : lumaEnd2) < 0.0) != isLumaCenterSmaller;
+    float finalOffset = max(correctVariation ? pixelOffset : 0.0, subpixelOffset);
+
+    // The linear sampler does the blend across the edge
+    vec2 finalUv = uv;
+    if (isHorizontal) {
+        finalUv.y += finalOffset * stepLength;
+    } else {
+        finalUv.x += finalOffset * stepLength;
+    }
+    return textureLod(ldrColor, finalUv, 0.0).rgb;
+}
+
+void main() {
+    vec3 color = fragTexCoord.x < params.split
+        ? fxaa(fragTexCoord)
+        : textureLod(ldrColor, fragTexCoord, 0.0).rgb;
+
+    // Divider for the side-by-side comparison
+    if (params.split < 1.0 && abs(fragTexCoord.x - params.split) < params.inverseResolution.x) {
+        color = vec3(1.0, 0.8, 0.2);
+    }
+
+    outColor = vec4(color, 1.0);
+}
@@ -2,6 +2,7 @@
 
 layout(location = 0) in vec3 fragColor;
 layout(location = 1) in float fragHighlight;
+layout(location = 2) in float fragDimmed;
 
 layout(location = 0) out vec4 outColor;
 
@@ -11,5 +12,8 @@ void main() {
     if (fragHighlight > 0.5) {
         color = mix(fragColor, vec3(1.0), 0.5); // Brighten by 50%
     }
+    if (fragDimmed > 0.5) {
+        color = mix(color, vec3(0.25), 0.7); // Locked axis: mostly grey
+    }
     outColor = vec4(color, 1.0);
 }
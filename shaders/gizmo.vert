@@ -7,7 +7,8 @@ layout(binding = 0) uniform UniformBufferObject {
 } ubo;
 
 layout(push_constant) uniform PushConstants {
-    int hoveredAxis; // 0=none, 1=X, 2=Y, 3=Z
+    int hoveredAxis; // 0=none, 1=X, 2=Y, 3=Z, -1/-2/-3 = negative view cube faces
+    int lockedAxes;  // Bit 0/1/2 = X/Y/Z locked (drawn dimmed)
 } push;
 
 layout(location = 0) in vec3 inPosition;
@@ -16,6 +17,7 @@ layout(location = 2) in vec2 inUV;
 
 layout(location = 0) out vec3 fragColor;
 layout(location = 1) out float fragHighlight;
+layout(location = 2) out float fragDimmed;
 
 void main() {
     // Use the normal as color (X=red, Y=green, Z=blue)
@@ -30,8 +32,20 @@ void main() {
         highlight = 1.0;
     } else if (push.hoveredAxis == 3 && baseColor.b > 0.9) { // Z axis (blue)
         highlight = 1.0;
+    } else if (push.hoveredAxis == -1 && inNormal.x < -0.1) { // -X view cube face
+        highlight = 1.0;
+    } else if (push.hoveredAxis == -2 && inNormal.y < -0.1) { // -Y view cube face
+        highlight = 1.0;
+    } else if (push.hoveredAxis == -3 && inNormal.z < -0.1) { // -Z view cube face
+        highlight = 1.0;
     }
     fragHighlight = highlight;
 
+    // Locked axes can't be edited, so they fade back
+    bool locked = ((push.lockedAxes & 1) != 0 && baseColor.r > 0.9)
+        || ((push.lockedAxes & 2) != 0 && baseColor.g > 0.9)
+        || ((push.lockedAxes & 4) != 0 && baseColor.b > 0.9);
+    fragDimmed = locked ? 1.0 : 0.0;
+
     gl_Position = ubo.proj * ubo.view * ubo.model * vec4(inPosition, 1.0);
 }
@@ -0,0 +1,43 @@
+#version 450
+
+layout(push_constant) uniform PushConstants {
+    mat4 viewProj;
+    vec4 origin;
+    vec4 params;
+    vec4 color;
+} push;
+
+layout(location = 0) in vec3 fragRelPos;
+
+layout(location = 0) out vec4 outColor;
+
+// Coverage of lines `spacing` apart: about one pixel wide and anti-aliased using
+// screen-space derivatives. Lines fade out as their cells shrink toward a few
+// pixels, which is where they would otherwise alias into moire.
+float gridCoverage(vec2 coord, float spacing) {
+    vec2 cell = coord / spacing;
+    vec2 width = max(fwidth(cell), vec2(1e-6));
+    vec2 dist = abs(fract(cell - 0.5) - 0.5) / width;
+    float line = 1.0 - min(min(dist.x, dist.y), 1.0);
+    float cellPixels = 1.0 / max(width.x, width.y);
+    return line * smoothstep(2.0, 8.0, cellPixels);
+}
+
+void main() {
+    // Small offset plus small relative position: world grid coordinates without large floats
+    vec2 coord = push.origin.xz + fragRelPos.xz;
+    float spacing = push.params.x;
+
+    float minor = gridCoverage(coord, spacing) * 0.4;
+    float major = gridCoverage(coord, spacing * push.params.y);
+    float coverage = max(minor, major);
+
+    float fadeDistance = push.params.z;
+    float fade = 1.0 - smoothstep(fadeDistance * 0.5, fadeDistance, length(fragRelPos.xz));
+
+    float alpha = coverage * fade * push.color.a;
+    if (alpha <= 0.001) {
+        discard;
+    }
+    outColor = vec4(push.color.rgb, alpha);
+}
@@ -0,0 +1,29 @@
+#version 450
+
+// Editor reference grid: one quad on the Y=0 plane centered under the camera.
+// Positions are camera-relative so the grid stays precise far from the origin.
+
+layout(push_constant) uniform PushConstants {
+    mat4 viewProj;    // Camera-relative view-projection (rotation only, no translation)
+    vec4 origin;      // xz: camera position modulo the major spacing, y: camera height, w: quad half-size
+    vec4 params;      // x: minor spacing, y: minor lines per major line, z: fade distance, w: log depth
+    vec4 color;       // Line color (RGBA)
+} push;
+
+layout(location = 0) out vec3 fragRelPos;
+
+const vec2 CORNERS[6] = vec2[](
+    vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+    vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0)
+);
+
+void main() {
+    vec2 corner = CORNERS[gl_VertexIndex] * push.origin.w;
+    fragRelPos = vec3(corner.x, -push.origin.y, corner.y);
+    gl_Position = push.viewProj * vec4(fragRelPos, 1.0);
+
+    // Logarithmic depth (see mesh.vert)
+    if (push.params.w > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * push.params.w * gl_Position.w;
+    }
+}
@@ -7,6 +7,12 @@ layout(location = 1) in vec4 inColor;
 
 layout(location = 0) out vec4 outColor;
 
+// ImGui colors (including color picker swatches) are sRGB; decode them so the sRGB
+// swapchain's encode shows exactly the picked value (same curve as src/core/color.rs)
+vec3 srgbToLinear(vec3 color) {
+    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+}
+
 void main() {
-    outColor = inColor * texture(fontTexture, inUV);
+    outColor = vec4(srgbToLinear(inColor.rgb), inColor.a) * texture(fontTexture, inUV);
 }
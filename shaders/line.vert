@@ -7,8 +7,14 @@ layout(location = 0) in vec3 inPosition;
 layout(push_constant) uniform PushConstants {
     mat4 viewProj;    // Combined view-projection matrix
     vec4 color;       // Line color (RGBA)
+    float logDepth;   // 0 = standard depth
 } push;
 
 void main() {
     gl_Position = push.viewProj * vec4(inPosition, 1.0);
+
+    // Logarithmic depth (see mesh.vert)
+    if (push.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * push.logDepth * gl_Position.w;
+    }
 }
@@ -0,0 +1,16 @@
+#version 450
+
+// Material editor preview sphere, drawn orthographically looking down -Z
+// (the sphere mesh is already in preview space, so no matrices are needed)
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+
+layout(location = 0) out vec3 fragNormal;
+
+void main() {
+    fragNormal = inNormal;
+
+    // The 0.5 radius sphere fills most of the target; flip Y for Vulkan clip space
+    gl_Position = vec4(inPosition.x / 0.55, -inPosition.y / 0.55, 0.5 - inPosition.z * 0.5, 1.0);
+}
@@ -1,5 +1,21 @@
 #version 450
 
+struct PointLight {
+    vec3 position;
+    float radius;
+    vec3 color;
+    float intensity;
+};
+
+struct DirectionalLight {
+    vec3 direction;
+    float intensity;
+    vec3 color;
+    float _pad;
+};
+
+const uint MAX_DIRECTIONAL_LIGHTS = 3u;
+
 layout(binding = 0) uniform UniformBufferObject {
     mat4 view;
     mat4 proj;
@@ -10,99 +26,117 @@ layout(binding = 0) uniform UniformBufferObject {
     vec3 dirLightShadowColor;
     float starDensity;
     float starBrightness;
-    float pad0;
-    float pad1;
-    float pad2;
+    float ssaoIntensity; // 0 = no AO, 1 = full AO
+    uint ssaoDebug;      // 1 = output the AO term only
+    float logDepth;      // > 0 = logarithmic depth buffer (applied in mesh.vert)
     vec3 nebulaPrimaryColor;
     float nebulaIntensity;
     vec3 nebulaSecondaryColor;
     float backgroundBrightness;
     uint pointLightCount;
     uint ssaoEnabled;
+    uint shadowEnabled;
+    float shadowBias;
+    mat4 lightSpaceMatrix;
+    vec3 fogColor;
+    float fogDensity;
+    uint fogMode;        // 0 = off, 1 = exponential, 2 = linear
+    float fogStart;
+    float fogEnd;
+    uint debugView;      // 0 = off, 1 = normals, 2 = depth, 3 = UVs, 4 = overdraw, 5 = LOD
+    uint dirLightCount;  // Lights in dirLights; [0] mirrors dirLightDirection/Color/Intensity
+    uint _dirPad0;
+    uint _dirPad1;
+    uint _dirPad2;
+    DirectionalLight dirLights[MAX_DIRECTIONAL_LIGHTS];
+    mat4 historyView;    // Camera of the SSR history (last frame)
+    mat4 historyProj;
+    uint ssrEnabled;     // 1 = trace reflections against the history
+    uint ssrMaxSteps;
+    float ssrThickness;  // World units a ray may pass behind the history depth and still hit
+    float _ssrPad;
 } ubo;
 
 // SSAO texture (blurred ambient occlusion)
 layout(binding = 1) uniform sampler2D ssaoTexture;
 
-// Material properties via push constants (after mat4 model at offset 64)
-layout(push_constant) uniform MaterialPushConstants {
-    layout(offset = 64) vec3 albedo;
-    layout(offset = 76) float metallic;
-    layout(offset = 80) float roughness;
-    layout(offset = 84) float ambient_strength;
-    layout(offset = 88) float gi_strength;
-} material;
+// Directional light shadow map (depth rendered from the light's point of view)
+layout(binding = 2) uniform sampler2D shadowMap;
+
+// Point lights; only the first ubo.pointLightCount entries are valid this frame
+layout(std430, binding = 3) readonly buffer PointLightBuffer {
+    PointLight pointLights[];
+} pointLightBuffer;
+
+// Image-based lighting precomputed from the skybox (src/core/environment.rs):
+// irradiance / pi for diffuse, and GGX-prefiltered radiance with roughness 0..1 across the mips
+layout(binding = 4) uniform samplerCube irradianceMap;
+layout(binding = 5) uniform samplerCube prefilteredMap;
+
+// Screen-space reflection history (src/core/ssr.rs): last frame's scene color and depth
+layout(binding = 6) uniform sampler2D historyColor;
+layout(binding = 7) uniform sampler2D historyDepth;
+
+// Material properties, forwarded by the vertex shader from push constants
+// (mesh.vert) or per-instance attributes (mesh_instanced.vert)
+layout(location = 4) flat in vec3 matAlbedo;
+layout(location = 5) flat in vec4 matParams; // metallic, roughness, ambient_strength, gi_strength
+layout(location = 6) flat in uint matTextureFlags;
+layout(location = 7) flat in vec4 matEmissive; // rgb color, a = strength
+layout(location = 9) flat in float matOpacity; // Alpha for the transparent pipeline (ignored when blending is off)
+layout(location = 11) flat in uvec3 matLayer; // Weathering layer, packed by MeshPushConstants::pack_layer
+
+struct MaterialData {
+    vec3 albedo;
+    float metallic;
+    float roughness;
+    float ambient_strength;
+    float gi_strength;
+    uint textureFlags;
+};
+
+// Material textures (set 1), only sampled when the matching texture flag is set
+layout(set = 1, binding = 0) uniform sampler2D albedoTexture;
+layout(set = 1, binding = 1) uniform sampler2D normalTexture;
+layout(set = 1, binding = 2) uniform sampler2D metallicRoughnessTexture;
+
+const uint TEXTURE_ALBEDO = 1u;
+const uint TEXTURE_NORMAL = 2u;
+const uint TEXTURE_METALLIC_ROUGHNESS = 4u;
+const uint TEXTURE_VERTEX_COLOR = 8u; // not a texture: multiply albedo by the vertex color
+const uint TEXTURE_LAYER = 16u;        // not textures: blend in the weathering layer,
+const uint TEXTURE_LAYER_SSAO = 32u;   // with SSAO occlusion in the mask
+const uint TEXTURE_LAYER_VERTEX = 64u; // or vertex-color occlusion in the mask
+
+const uint DEBUG_VIEW_NORMALS = 1u;
+const uint DEBUG_VIEW_DEPTH = 2u;
+const uint DEBUG_VIEW_UVS = 3u;
+const uint DEBUG_VIEW_OVERDRAW = 4u;
+const uint DEBUG_VIEW_LOD = 5u;
 
 layout(location = 0) in vec3 fragPosition;
 layout(location = 1) in vec3 fragNormal;
 layout(location = 2) in vec2 fragUV;
 layout(location = 3) in vec3 viewPos;
+layout(location = 8) in vec3 fragTangent;
+layout(location = 10) in vec3 fragColor; // Vertex color, only used with TEXTURE_VERTEX_COLOR
+layout(location = 12) in vec3 fragLocalPosition; // Object space, so the weathering noise sticks to the mesh
 
 layout(location = 0) out vec4 outColor;
 
 const float PI = 3.14159265359;
 
-// Hash function for procedural skybox (matches skybox shader)
-vec3 hash3(vec3 p) {
-    p = vec3(
-        dot(p, vec3(127.1, 311.7, 74.7)),
-        dot(p, vec3(269.5, 183.3, 246.1)),
-        dot(p, vec3(113.5, 271.9, 124.6))
-    );
-    return fract(sin(p) * 43758.5453123);
-}
-
-// Voronoi noise for star field
-vec4 voronoi(vec3 p, float scale) {
-    vec3 n = floor(p * scale);
-    vec3 f = fract(p * scale);
-
-    float minDist = 10.0;
-    vec3 cellColor = vec3(1.0);
-
-    for (int k = -1; k <= 1; k++) {
-        for (int j = -1; j <= 1; j++) {
-            for (int i = -1; i <= 1; i++) {
-                vec3 neighbor = vec3(float(i), float(j), float(k));
-                vec3 point = hash3(n + neighbor);
-                vec3 diff = neighbor + point - f;
-                float dist = length(diff);
+// Screen-space reflections: rougher surfaces than this only use the environment map,
+// rays reach this many times the camera distance, and hits fade out near screen edges
+const float SSR_MAX_ROUGHNESS = 0.6;
+const float SSR_RAY_LENGTH_SCALE = 1.5;
+const float SSR_EDGE_FADE = 0.1;
+const int SSR_REFINE_STEPS = 5;
 
-                if (dist < minDist) {
-                    minDist = dist;
-                    cellColor = hash3(n + neighbor + vec3(12.34, 56.78, 90.12));
-                }
-            }
-        }
-    }
-
-    return vec4(cellColor, minDist);
-}
-
-// Sample procedural skybox environment (simplified for GI)
-vec3 sampleSkybox(vec3 dir) {
-    // Simplified star field (single layer for performance)
-    float densityScale = 1.0 / max(ubo.starDensity, 0.1);
-    vec4 voronoi1 = voronoi(dir, 40.0 * ubo.starDensity);
-    float star1 = pow(max(0.0, 1.0 - voronoi1.w * (25.0 * densityScale)), 10.0);
-    vec3 stars = vec3(0.0);
-    if (star1 > 0.01) {
-        vec3 starColor = mix(vec3(0.8, 0.9, 1.0), vec3(1.0, 0.9, 0.8), voronoi1.x);
-        stars = starColor * star1 * 2.0 * ubo.starBrightness;
-    }
-
-    // Nebula contribution
-    vec3 spaceColor = vec3(ubo.backgroundBrightness);
-    float nebulaFactor = abs(dir.y) * 0.3 + 0.2;
-    vec3 nebulaColor = mix(
-        ubo.nebulaPrimaryColor,
-        ubo.nebulaSecondaryColor,
-        (dir.x * 0.5 + 0.5)
-    );
-    spaceColor += nebulaColor * nebulaFactor * ubo.nebulaIntensity;
-
-    return spaceColor + stars;
-}
+// Weathering: LayeredMaterial::MAX_NOISE_SCALE, and how strongly occlusion pulls the
+// noise mask toward crevices when it drives the mask
+const float LAYER_MAX_NOISE_SCALE = 64.0;
+const float LAYER_OCCLUSION_WEIGHT = 0.7;
 
 // PBR functions
 float DistributionGGX(vec3 N, vec3 H, float roughness) {
@@ -141,6 +175,21 @@ vec3 fresnelSchlick(float cosTheta, vec3 F0) {
     return F0 + (1.0 - F0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
 }
 
+// Fresnel for ambient light: rough surfaces reflect less at grazing angles
+vec3 fresnelSchlickRoughness(float cosTheta, vec3 F0, float roughness) {
+    return F0 + (max(vec3(1.0 - roughness), F0) - F0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
+}
+
+// Split-sum environment BRDF, analytic fit (Karis) instead of a lookup texture
+vec3 envBRDFApprox(vec3 F0, float roughness, float NdotV) {
+    const vec4 c0 = vec4(-1.0, -0.0275, -0.572, 0.022);
+    const vec4 c1 = vec4(1.0, 0.0425, 1.04, -0.04);
+    vec4 r = roughness * c0 + c1;
+    float a004 = min(r.x * r.x, exp2(-9.28 * NdotV)) * r.x + r.y;
+    vec2 AB = vec2(-1.04, 1.04) * a004 + r.zw;
+    return F0 * AB.x + AB.y;
+}
+
 
 vec3 calculateLight(vec3 N, vec3 V, vec3 L, vec3 lightColor, float lightIntensity, vec3 F0, vec3 albedo, float metallic, float roughness) {
     vec3 H = normalize(V + L);
@@ -163,33 +212,330 @@ vec3 calculateLight(vec3 N, vec3 V, vec3 L, vec3 lightColor, float lightIntensit
     return (kD * albedo / PI + specular) * lightColor * lightIntensity * NdotL;
 }
 
+// Shadow factor for the directional light (0 = fully lit, 1 = fully shadowed)
+// Uses 3x3 PCF filtering with a slope-scaled depth bias
+float calculateShadow(vec3 worldPos, vec3 N, vec3 L) {
+    vec4 lightSpacePos = ubo.lightSpaceMatrix * vec4(worldPos, 1.0);
+    vec3 projCoords = lightSpacePos.xyz / lightSpacePos.w;
+    vec2 shadowUV = projCoords.xy * 0.5 + 0.5;
+
+    // Outside the shadow volume - treat as lit
+    if (projCoords.z > 1.0 || shadowUV.x < 0.0 || shadowUV.x > 1.0 || shadowUV.y < 0.0 || shadowUV.y > 1.0) {
+        return 0.0;
+    }
+
+    // Larger bias on surfaces at grazing angles to the light
+    float bias = max(ubo.shadowBias * (1.0 - dot(N, L)), ubo.shadowBias * 0.1);
+
+    vec2 texelSize = 1.0 / vec2(textureSize(shadowMap, 0));
+    float shadow = 0.0;
+    for (int x = -1; x <= 1; x++) {
+        for (int y = -1; y <= 1; y++) {
+            float closestDepth = texture(shadowMap, shadowUV + vec2(x, y) * texelSize).r;
+            shadow += (projCoords.z - bias > closestDepth) ? 1.0 : 0.0;
+        }
+    }
+
+    return shadow / 9.0;
+}
+
+// Perturb the normal with a tangent-space normal map.
+// Uses the per-vertex tangent; meshes without one (zero tangent) fall back to a
+// tangent frame built from screen-space derivatives.
+vec3 perturbNormal(vec3 N, vec3 worldPos, vec2 uv) {
+    vec3 mapN = texture(normalTexture, uv).xyz * 2.0 - 1.0;
+
+    if (dot(fragTangent, fragTangent) > 1e-8) {
+        // Re-orthogonalize after interpolation
+        vec3 T = normalize(fragTangent - N * dot(N, fragTangent));
+        vec3 B = cross(N, T);
+        return normalize(mat3(T, B, N) * mapN);
+    }
+
+    vec3 dp1 = dFdx(worldPos);
+    vec3 dp2 = dFdy(worldPos);
+    vec2 duv1 = dFdx(uv);
+    vec2 duv2 = dFdy(uv);
+
+    vec3 dp2perp = cross(dp2, N);
+    vec3 dp1perp = cross(N, dp1);
+    vec3 T = dp2perp * duv1.x + dp1perp * duv2.x;
+    vec3 B = dp2perp * duv1.y + dp1perp * duv2.y;
+
+    float invMax = inversesqrt(max(max(dot(T, T), dot(B, B)), 1e-12));
+    mat3 TBN = mat3(T * invMax, B * invMax, N);
+    return normalize(TBN * mapN);
+}
+
+float hash13(vec3 p) {
+    p = fract(p * 0.1031);
+    p += dot(p, p.zyx + 31.32);
+    return fract((p.x + p.y) * p.z);
+}
+
+// Smooth value noise in 0..1
+float valueNoise(vec3 p) {
+    vec3 i = floor(p);
+    vec3 f = fract(p);
+    f = f * f * (3.0 - 2.0 * f);
+    return mix(
+        mix(mix(hash13(i), hash13(i + vec3(1, 0, 0)), f.x),
+            mix(hash13(i + vec3(0, 1, 0)), hash13(i + vec3(1, 1, 0)), f.x), f.y),
+        mix(mix(hash13(i + vec3(0, 0, 1)), hash13(i + vec3(1, 0, 1)), f.x),
+            mix(hash13(i + vec3(0, 1, 1)), hash13(i + vec3(1, 1, 1)), f.x), f.y),
+        f.z);
+}
+
+// Weathering mask in 0..1 (higher = more worn): three octaves of noise, optionally pulled
+// toward occlusion so wear gathers in crevices
+float weatheringMask(uint flags, float noiseScale) {
+    vec3 p = fragLocalPosition * noiseScale;
+    float wear = (valueNoise(p) * 4.0 + valueNoise(p * 2.03) * 2.0 + valueNoise(p * 4.01)) / 7.0;
+
+    float occlusion = -1.0;
+    if ((flags & TEXTURE_LAYER_SSAO) != 0u && ubo.ssaoEnabled != 0u) {
+        occlusion = 1.0 - texture(ssaoTexture, gl_FragCoord.xy / vec2(textureSize(ssaoTexture, 0))).r;
+    } else if ((flags & TEXTURE_LAYER_VERTEX) != 0u) {
+        occlusion = 1.0 - dot(fragColor, vec3(0.2126, 0.7152, 0.0722));
+    }
+    if (occlusion >= 0.0) {
+        wear = mix(wear, occlusion, LAYER_OCCLUSION_WEIGHT);
+    }
+    return wear;
+}
+
+// View-space depth mapped to 0 (near plane) .. 1 (far plane) on a log scale,
+// so both close-up detail and distant objects stay readable
+float debugDepth(vec3 worldPos) {
+    float depth = -(ubo.view * vec4(worldPos, 1.0)).z;
+    // Recover the planes from the projection (perspective_rh / orthographic_rh, 0..1 depth)
+    bool perspective = ubo.proj[2][3] != 0.0;
+    float near = ubo.proj[3][2] / ubo.proj[2][2];
+    float far = perspective
+        ? ubo.proj[3][2] / (ubo.proj[2][2] + 1.0)
+        : (ubo.proj[3][2] - 1.0) / ubo.proj[2][2];
+    near = max(near, 1e-4);
+    return clamp(log(max(depth, near) / near) / log(max(far, near * 2.0) / near), 0.0, 1.0);
+}
+
+// Project a world position into the SSR history. Returns false when it falls outside
+// last frame's view; otherwise uv and the position's view depth in that frame.
+bool projectToHistory(vec3 worldPos, out vec2 uv, out float viewDepth) {
+    vec4 viewSpace = ubo.historyView * vec4(worldPos, 1.0);
+    viewDepth = -viewSpace.z;
+    vec4 clip = ubo.historyProj * viewSpace;
+    if (clip.w <= 0.0) {
+        return false;
+    }
+    vec2 ndc = clip.xy / clip.w;
+    uv = ndc * 0.5 + 0.5;
+    return all(greaterThanEqual(uv, vec2(0.0))) && all(lessThanEqual(uv, vec2(1.0)));
+}
+
+// View depth stored in the history depth buffer at uv (standard or logarithmic depth)
+float historyViewDepth(vec2 uv) {
+    float depth = textureLod(historyDepth, uv, 0.0).r;
+    if (ubo.logDepth > 0.0) {
+        return exp2(depth / ubo.logDepth) - 1.0;
+    }
+    mat4 proj = ubo.historyProj;
+    bool perspective = proj[2][3] != 0.0;
+    return perspective ? proj[3][2] / (depth + proj[2][2]) : (proj[3][2] - depth) / proj[2][2];
+}
+
+// March the reflected ray in world space against last frame's depth. Returns the reflected
+// color with alpha = confidence (0 when the ray leaves the view or hits nothing, so the
+// caller keeps the environment map there).
+vec4 traceScreenSpaceReflection(vec3 worldPos, vec3 N, vec3 R) {
+    float viewDistance = length(worldPos - ubo.viewPos);
+    float stepLength = (viewDistance * SSR_RAY_LENGTH_SCALE + 1.0) / float(ubo.ssrMaxSteps);
+    // Start just off the surface so the ray doesn't hit the pixel it came from
+    vec3 origin = worldPos + N * (0.002 * viewDistance + 0.01);
+    // Per-pixel offset along the first step hides banding between steps
+    float jitter = fract(52.9829189 * fract(dot(gl_FragCoord.xy, vec2(0.06711056, 0.00583715))));
+
+    float previousT = 0.0;
+    for (uint i = 0u; i < ubo.ssrMaxSteps; i++) {
+        float t = (float(i) + jitter) * stepLength;
+        vec2 uv;
+        float rayDepth;
+        if (!projectToHistory(origin + R * t, uv, rayDepth)) {
+            return vec4(0.0);
+        }
+        float behind = rayDepth - historyViewDepth(uv);
+        if (behind > 0.0) {
+            if (behind > max(ubo.ssrThickness, stepLength)) {
+                // Passed behind a surface without touching it (e.g. an object in front of the ray)
+                previousT = t;
+                continue;
+            }
+            // Bisect between the last point in front and this one for the exact crossing
+            float front = previousT;
+            float back = t;
+            for (int j = 0; j < SSR_REFINE_STEPS; j++) {
+                float middle = 0.5 * (front + back);
+                vec2 middleUV;
+                float middleDepth;
+                projectToHistory(origin + R * middle, middleUV, middleDepth);
+                if (middleDepth > historyViewDepth(middleUV)) {
+                    back = middle;
+                } else {
+                    front = middle;
+                }
+            }
+            projectToHistory(origin + R * back, uv, rayDepth);
+
+            vec2 edge = smoothstep(0.0, SSR_EDGE_FADE, uv) * smoothstep(0.0, SSR_EDGE_FADE, 1.0 - uv);
+            float distanceFade = 1.0 - float(i) / float(ubo.ssrMaxSteps);
+            return vec4(textureLod(historyColor, uv, 0.0).rgb, edge.x * edge.y * distanceFade);
+        }
+        previousT = t;
+    }
+    return vec4(0.0);
+}
+
 void main() {
+    // Overdraw heatmap: every fragment adds a little heat (additive pipeline, no depth test)
+    if (ubo.debugView == DEBUG_VIEW_OVERDRAW) {
+        outColor = vec4(0.12, 0.05, 0.02, 1.0);
+        return;
+    }
+
+    MaterialData material = MaterialData(
+        matAlbedo,
+        matParams.x,
+        matParams.y,
+        matParams.z,
+        matParams.w,
+        matTextureFlags
+    );
+
     vec3 N = normalize(fragNormal);
     vec3 V = normalize(viewPos - fragPosition);
 
+    // Material inputs (textures modulate the constant values)
+    vec3 albedo = material.albedo;
+    float metallic = material.metallic;
+    float roughness = material.roughness;
+
+    float opacity = matOpacity;
+
+    if ((material.textureFlags & TEXTURE_ALBEDO) != 0u) {
+        // Albedo images are sRGB-encoded, convert to linear; alpha scales opacity
+        vec4 albedoSample = texture(albedoTexture, fragUV);
+        albedo *= pow(albedoSample.rgb, vec3(2.2));
+        opacity *= albedoSample.a;
+    }
+    if ((material.textureFlags & TEXTURE_VERTEX_COLOR) != 0u) {
+        // Already linear (glTF COLOR_0, OBJ extended vertices)
+        albedo *= fragColor;
+    }
+    if ((material.textureFlags & TEXTURE_METALLIC_ROUGHNESS) != 0u) {
+        // glTF layout: G = roughness, B = metallic
+        vec4 mr = texture(metallicRoughnessTexture, fragUV);
+        roughness *= mr.g;
+        metallic *= mr.b;
+    }
+    if ((material.textureFlags & TEXTURE_NORMAL) != 0u) {
+        N = perturbNormal(N, fragPosition, fragUV);
+    }
+    if ((material.textureFlags & TEXTURE_LAYER) != 0u) {
+        vec4 layerColor = unpackUnorm4x8(matLayer.x);    // albedo, metallic
+        vec2 layerParams = unpackUnorm2x16(matLayer.y);  // roughness, coverage
+        vec2 layerShape = unpackUnorm2x16(matLayer.z);   // blend width, noise scale / max
+        float wear = weatheringMask(material.textureFlags, layerShape.y * LAYER_MAX_NOISE_SCALE);
+
+        // The threshold sweeps past both ends of the mask so 0 and 1 coverage are exact
+        float halfWidth = max(layerShape.x * 0.5, 1e-3);
+        float threshold = mix(1.0 + halfWidth, -halfWidth, layerParams.y);
+        float blend = smoothstep(threshold - halfWidth, threshold + halfWidth, wear);
+
+        albedo = mix(albedo, layerColor.rgb, blend);
+        metallic = mix(metallic, layerColor.a, blend);
+        roughness = mix(roughness, layerParams.x, blend);
+    }
+
+    // Debug views replace the shaded color (after normal mapping so it can be inspected)
+    if (ubo.debugView == DEBUG_VIEW_NORMALS) {
+        outColor = vec4(N * 0.5 + 0.5, 1.0);
+        return;
+    }
+    if (ubo.debugView == DEBUG_VIEW_DEPTH) {
+        outColor = vec4(vec3(debugDepth(fragPosition)), 1.0);
+        return;
+    }
+    if (ubo.debugView == DEBUG_VIEW_UVS) {
+        outColor = vec4(fract(fragUV), 0.0, 1.0);
+        return;
+    }
+    if (ubo.debugView == DEBUG_VIEW_LOD) {
+        // MeshPass swaps in a flat per-level albedo; facing-ratio shading keeps the shape readable
+        outColor = vec4(material.albedo * (0.35 + 0.65 * max(dot(N, V), 0.0)), 1.0);
+        return;
+    }
+
     // Base reflectivity (F0)
     vec3 F0 = vec3(0.04);
-    F0 = mix(F0, material.albedo, material.metallic);
+    F0 = mix(F0, albedo, metallic);
 
     vec3 Lo = vec3(0.0);
 
     // Directional light
     vec3 L = normalize(-ubo.dirLightDirection);
-    Lo += calculateLight(N, V, L, ubo.dirLightColor, ubo.dirLightIntensity, F0, material.albedo, material.metallic, material.roughness);
+    float shadow = 0.0;
+    if (ubo.shadowEnabled != 0u) {
+        shadow = calculateShadow(fragPosition, N, L);
+    }
+    Lo += calculateLight(N, V, L, ubo.dirLightColor, ubo.dirLightIntensity, F0, albedo, metallic, roughness) * (1.0 - shadow);
+
+    // Extra directional lights (fill/rim) - unshadowed
+    for (uint i = 1u; i < min(ubo.dirLightCount, MAX_DIRECTIONAL_LIGHTS); i++) {
+        DirectionalLight light = ubo.dirLights[i];
+        Lo += calculateLight(N, V, normalize(-light.direction), light.color, light.intensity, F0, albedo, metallic, roughness);
+    }
 
-    // TODO: Add point lights (will need separate uniform buffer or storage buffer)
+    // Point lights (inverse-square falloff, windowed to reach zero at the radius).
+    // Every fragment walks the whole list; lights out of range cost one distance test.
+    for (uint i = 0u; i < ubo.pointLightCount; i++) {
+        PointLight light = pointLightBuffer.pointLights[i];
+        vec3 toLight = light.position - fragPosition;
+        float distance = max(length(toLight), 0.0001);
+        if (distance >= light.radius) {
+            continue;
+        }
+
+        float window = clamp(1.0 - pow(distance / light.radius, 4.0), 0.0, 1.0);
+        float attenuation = (window * window) / (distance * distance + 1.0);
+        Lo += calculateLight(N, V, toLight / distance, light.color, light.intensity * attenuation, F0, albedo, metallic, roughness);
+    }
 
-    // Global Illumination: Sample skybox environment based on surface normal
+    // Global Illumination: image-based lighting from the skybox environment maps
     vec3 giColor = vec3(0.0);
     if (material.gi_strength > 0.001) {
-        // Sample skybox in the direction of the surface normal
-        vec3 skyboxSample = sampleSkybox(N);
+        float NdotV = max(dot(N, V), 0.0);
+        vec3 F = fresnelSchlickRoughness(NdotV, F0, roughness);
+        vec3 kD = (vec3(1.0) - F) * (1.0 - metallic);
 
-        // Mix between shadow color and skybox color based on light direction
+        // Diffuse: irradiance around the normal, tinted toward the shadow color away from the light
+        vec3 irradiance = texture(irradianceMap, N).rgb;
         float NdotL = dot(N, normalize(-ubo.dirLightDirection));
-        vec3 indirectLight = mix(ubo.dirLightShadowColor, skyboxSample, max(NdotL * 0.5 + 0.5, 0.0));
+        irradiance = mix(ubo.dirLightShadowColor, irradiance, max(NdotL * 0.5 + 0.5, 0.0));
+
+        // Specular: reflection prefiltered for this roughness
+        vec3 R = reflect(-V, N);
+        float maxLod = float(textureQueryLevels(prefilteredMap) - 1);
+        vec3 prefiltered = textureLod(prefilteredMap, R, roughness * maxLod).rgb;
+
+        // Screen-space reflections replace the environment where the ray finds the scene,
+        // weighted by how metallic and smooth the surface is
+        if (ubo.ssrEnabled != 0u && metallic > 0.0 && roughness < SSR_MAX_ROUGHNESS) {
+            vec4 reflection = traceScreenSpaceReflection(fragPosition, N, R);
+            float smoothness = 1.0 - roughness / SSR_MAX_ROUGHNESS;
+            prefiltered = mix(prefiltered, reflection.rgb, reflection.a * metallic * smoothness);
+        }
+        vec3 specular = prefiltered * envBRDFApprox(F0, roughness, NdotV);
 
-        giColor = indirectLight * material.albedo * material.gi_strength;
+        giColor = (kD * irradiance * albedo + specular) * material.gi_strength;
     }
 
     // Sample SSAO from screen-space coordinates (only if enabled)
@@ -199,18 +545,37 @@ void main() {
         ssaoValue = texture(ssaoTexture, screenUV).r;
     }
 
+    // Debug view for tuning: show the raw blurred AO instead of shading
+    if (ubo.ssaoDebug != 0u) {
+        outColor = vec4(vec3(ssaoValue), 1.0);
+        return;
+    }
+
+    // Blend between no occlusion and full occlusion
+    ssaoValue = mix(1.0, ssaoValue, clamp(ubo.ssaoIntensity, 0.0, 1.0));
+
     // Ambient lighting term (simple constant ambient)
-    vec3 ambient = material.albedo * material.ambient_strength * 0.03;
+    vec3 ambient = albedo * material.ambient_strength * 0.03;
 
     // Apply SSAO to ambient and GI terms (darker crevices get less indirect light)
     ambient *= ssaoValue;
     giColor *= ssaoValue;
 
+    // Linear HDR output - TonemapPass maps it to the swapchain
     vec3 color = ambient + Lo + giColor;
 
-    // HDR tonemapping and gamma correction
-    color = color / (color + vec3(1.0));
-    color = pow(color, vec3(1.0 / 2.2));
+    // Emission is unlit and unoccluded; strong values push past the bloom threshold
+    color += matEmissive.rgb * matEmissive.a;
+
+    // Distance fog. fragPosition and viewPos share a space, so this is the
+    // camera-relative distance even when the scene is rendered relative to the camera
+    if (ubo.fogMode != 0u) {
+        float fogDistance = length(fragPosition - viewPos);
+        float fog = (ubo.fogMode == 1u)
+            ? 1.0 - exp(-ubo.fogDensity * fogDistance)
+            : (fogDistance - ubo.fogStart) / max(ubo.fogEnd - ubo.fogStart, 0.001);
+        color = mix(color, ubo.fogColor, clamp(fog, 0.0, 1.0));
+    }
 
-    outColor = vec4(color, 1.0);
+    outColor = vec4(color, opacity);
 }
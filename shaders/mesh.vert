@@ -1,8 +1,20 @@
 #version 450
 
-// Push constants for per-object model matrix
+// Push constants for per-object model matrix + material properties
 layout(push_constant) uniform PushConstants {
     mat4 model;
+    vec3 albedo;
+    float metallic;
+    float roughness;
+    float ambient_strength;
+    float gi_strength;
+    uint textureFlags;
+    vec3 emissive;
+    float emissive_strength;
+    float opacity;
+    uint layerColor;  // Weathering layer, packed (MeshPushConstants::pack_layer)
+    uint layerParams;
+    uint layerShape;
 } push;
 
 layout(binding = 0) uniform UniformBufferObject {
@@ -12,24 +24,60 @@ layout(binding = 0) uniform UniformBufferObject {
     vec3 dirLightDirection;
     vec3 dirLightColor;
     float dirLightIntensity;
-    uint pointLightCount;
+    vec3 dirLightShadowColor;
+    float starDensity;
+    float starBrightness;
+    float ssaoIntensity;
+    uint ssaoDebug;
+    float logDepth;      // 0 = standard depth
 } ubo;
 
 layout(location = 0) in vec3 inPosition;
 layout(location = 1) in vec3 inNormal;
 layout(location = 2) in vec2 inUV;
+layout(location = 11) in vec3 inTangent; // after the per-instance attributes (3-10)
+layout(location = 12) in vec3 inColor;   // white unless the mesh has vertex colors
 
 layout(location = 0) out vec3 fragPosition;
 layout(location = 1) out vec3 fragNormal;
 layout(location = 2) out vec2 fragUV;
 layout(location = 3) out vec3 viewPos;
+layout(location = 4) flat out vec3 matAlbedo;
+layout(location = 5) flat out vec4 matParams;
+layout(location = 6) flat out uint matTextureFlags;
+layout(location = 7) flat out vec4 matEmissive;
+layout(location = 8) out vec3 fragTangent;
+layout(location = 9) flat out float matOpacity;
+layout(location = 10) out vec3 fragColor;
+layout(location = 11) flat out uvec3 matLayer;
+layout(location = 12) out vec3 fragLocalPosition;
+
+// The depth prepass draws with this shader too, and the shaded pass tests depth with EQUAL
+invariant gl_Position;
 
 void main() {
     vec4 worldPosition = push.model * vec4(inPosition, 1.0);
     fragPosition = worldPosition.xyz;
     fragNormal = mat3(transpose(inverse(push.model))) * inNormal;
     fragUV = inUV;
+    fragColor = inColor;
+    fragLocalPosition = inPosition;
+    fragTangent = mat3(push.model) * inTangent;
     viewPos = ubo.viewPos;
 
+    // Forward material to the fragment shader
+    matAlbedo = push.albedo;
+    matParams = vec4(push.metallic, push.roughness, push.ambient_strength, push.gi_strength);
+    matTextureFlags = push.textureFlags;
+    matEmissive = vec4(push.emissive, push.emissive_strength);
+    matOpacity = push.opacity;
+    matLayer = uvec3(push.layerColor, push.layerParams, push.layerShape);
+
     gl_Position = ubo.proj * ubo.view * worldPosition;
+
+    // Logarithmic depth (Camera::log_depth_coefficient): z is pre-multiplied by w so the
+    // perspective divide leaves log2(1 + view depth) * logDepth, reaching 1 at the far plane
+    if (ubo.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.logDepth * gl_Position.w;
+    }
 }
@@ -0,0 +1,77 @@
+#version 450
+
+// Instanced variant of mesh.vert: model matrix and material come from
+// per-instance attributes (binding 1) instead of push constants
+
+layout(binding = 0) uniform UniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec3 viewPos;
+    vec3 dirLightDirection;
+    vec3 dirLightColor;
+    float dirLightIntensity;
+    vec3 dirLightShadowColor;
+    float starDensity;
+    float starBrightness;
+    float ssaoIntensity;
+    uint ssaoDebug;
+    float logDepth;      // 0 = standard depth
+} ubo;
+
+// Per-vertex attributes (binding 0)
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inUV;
+layout(location = 11) in vec3 inTangent; // after the per-instance attributes (3-10)
+layout(location = 12) in vec3 inColor;   // white unless the mesh has vertex colors
+
+// Per-instance attributes (binding 1), locations 3-6 hold the model matrix columns
+layout(location = 3) in mat4 instModel;
+layout(location = 7) in vec4 instAlbedoMetallic;
+layout(location = 8) in vec3 instRoughnessAmbientGi;
+layout(location = 9) in uint instTextureFlags;
+layout(location = 10) in vec4 instEmissive;
+layout(location = 13) in uvec3 instLayer; // after the per-vertex tangent and color
+
+layout(location = 0) out vec3 fragPosition;
+layout(location = 1) out vec3 fragNormal;
+layout(location = 2) out vec2 fragUV;
+layout(location = 3) out vec3 viewPos;
+layout(location = 4) flat out vec3 matAlbedo;
+layout(location = 5) flat out vec4 matParams;
+layout(location = 6) flat out uint matTextureFlags;
+layout(location = 7) flat out vec4 matEmissive;
+layout(location = 8) out vec3 fragTangent;
+layout(location = 9) flat out float matOpacity;
+layout(location = 10) out vec3 fragColor;
+layout(location = 11) flat out uvec3 matLayer;
+layout(location = 12) out vec3 fragLocalPosition;
+
+// The depth prepass draws with this shader too, and the shaded pass tests depth with EQUAL
+invariant gl_Position;
+
+void main() {
+    vec4 worldPosition = instModel * vec4(inPosition, 1.0);
+    fragPosition = worldPosition.xyz;
+    fragNormal = mat3(transpose(inverse(instModel))) * inNormal;
+    fragUV = inUV;
+    fragColor = inColor;
+    fragLocalPosition = inPosition;
+    fragTangent = mat3(instModel) * inTangent;
+    viewPos = ubo.viewPos;
+
+    // Forward material to the fragment shader
+    matAlbedo = instAlbedoMetallic.xyz;
+    matParams = vec4(instAlbedoMetallic.w, instRoughnessAmbientGi);
+    matTextureFlags = instTextureFlags;
+    matEmissive = instEmissive;
+    matOpacity = 1.0; // Transparent objects are drawn one by one, never instanced
+    matLayer = instLayer;
+
+    gl_Position = ubo.proj * ubo.view * worldPosition;
+
+    // Logarithmic depth (see mesh.vert)
+    if (ubo.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.logDepth * gl_Position.w;
+    }
+}
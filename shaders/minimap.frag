@@ -0,0 +1,18 @@
+#version 450
+
+// Tactical minimap marker: a filled disc with a dark rim
+
+layout(location = 0) in vec2 fragCorner;
+layout(location = 1) in vec3 fragColor;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    float radius = length(fragCorner);
+    if (radius > 1.0) {
+        discard;
+    }
+
+    vec3 color = radius > 0.7 ? fragColor * 0.35 : fragColor;
+    outColor = vec4(color, 1.0);
+}
@@ -0,0 +1,31 @@
+#version 450
+
+// Tactical minimap marker: a screen-aligned quad per instance, seen by the
+// top-down orthographic minimap camera (positions are relative to the map center)
+
+layout(push_constant) uniform PushConstants {
+    mat4 viewProj;
+    vec2 pixelSize;  // One minimap pixel in clip space
+} push;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in float inSize;  // Diameter in pixels
+layout(location = 2) in vec3 inColor;
+
+layout(location = 0) out vec2 fragCorner;
+layout(location = 1) out vec3 fragColor;
+
+const vec2 CORNERS[6] = vec2[](
+    vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+    vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0)
+);
+
+void main() {
+    vec2 corner = CORNERS[gl_VertexIndex];
+    fragCorner = corner;
+    fragColor = inColor;
+
+    vec4 center = push.viewProj * vec4(inPosition, 1.0);
+    // Heights don't matter on the map, so keep every marker inside the depth range
+    gl_Position = vec4(center.xy + corner * push.pixelSize * inSize * 0.5, 0.5, 1.0);
+}
@@ -30,9 +30,11 @@ layout(binding = 0) uniform UniformBufferObject {
     vec3 light_color;
     float light_intensity;
 
-    // Raymarch distance
+    // Raymarch distance and loop bound
     float max_distance;
-    vec3 _padding5;
+    uint steps;
+    float logDepth;  // > 0: the depth buffer holds logarithmic depth (see mesh.vert)
+    float _padding5;
 } ubo;
 
 layout(binding = 1) uniform sampler2D depthTexture;
@@ -186,11 +188,15 @@ void main() {
 
     float min_dist = 0.0, max_dist = 0.0;
 
+    // Fewer steps take proportionally longer strides, so the nebula keeps its depth
+    const float tunedSteps = 56.0;
+    float stride = tunedSteps / float(ubo.steps);
+
     if (RaySphereIntersect(ro, rd, min_dist, max_dist)) {
         t = min_dist * step(t, min_dist);
 
         // Raymarch loop - sample the volumetric nebula
-        for (int i = 0; i < 56; i++) {
+        for (int i = 0; i < int(ubo.steps); i++) {
             vec3 pos = ro + t * rd;
 
             // Check if we've reached geometry depth
@@ -199,7 +205,9 @@ void main() {
             // Transform position back to world space, then project to clip space
             vec3 worldPos = (ubo.model * vec4(pos * ubo.scale, 1.0)).xyz;
             vec4 clipPos = ubo.proj * ubo.view * vec4(worldPos, 1.0);
-            float raymarch_depth = clipPos.z / clipPos.w;
+            float raymarch_depth = ubo.logDepth > 0.0
+                ? log2(max(1e-6, 1.0 + clipPos.w)) * ubo.logDepth
+                : clipPos.z / clipPos.w;
 
             // If we've passed the geometry, stop raymarching
             if (scene_depth < 1.0 && raymarch_depth >= scene_depth) {
@@ -229,14 +237,14 @@ void main() {
                 sum = sum + col * (1.0 - sum.a);
             }
 
-            td += 1.0 / 70.0;
+            td += stride / 70.0;
             d = max(d, 0.04);
 
             #ifdef DITHERING
             d = abs(d) * (0.8 + 0.2 * rand(seed * vec2(float(i))));
             #endif
 
-            t += max(d * 0.1 * max(min(length(ldst), length(ro)), 1.0), 0.02);
+            t += max(d * 0.1 * max(min(length(ldst), length(ro)), 1.0), 0.02) * stride;
         }
 
         sum *= 1.0 / exp(ld * 0.2) * 0.6;
@@ -19,7 +19,7 @@ layout(binding = 0) uniform StarUniformBufferObject {
     float speed_hi;
     float speed_low;
     float zoom;
-    float _padding;
+    float logDepth;
 } ubo;
 
 // 2D hash for noise texture replacement
@@ -5,10 +5,9 @@ layout(location = 0) out vec4 outColor;
 layout(push_constant) uniform PushConstants {
     mat4 model;
     vec4 outlineColor;   // RGB color + alpha
-    float outlineWidth;  // How much to expand the mesh
-    float _padding1;
-    float _padding2;
-    float _padding3;
+    float outlineWidth;  // Silhouette expansion in screen pixels (0 = unexpanded)
+    float _padding;
+    vec2 viewportSize;   // Pixels
 } push;
 
 void main() {
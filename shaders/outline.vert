@@ -7,24 +7,45 @@ layout(location = 2) in vec2 inTexCoord;
 layout(push_constant) uniform PushConstants {
     mat4 model;
     vec4 outlineColor;   // RGB color + alpha
-    float outlineWidth;  // How much to expand the mesh
-    float _padding1;
-    float _padding2;
-    float _padding3;
+    float outlineWidth;  // Silhouette expansion in screen pixels (0 = unexpanded)
+    float _padding;
+    vec2 viewportSize;   // Pixels
 } push;
 
 layout(set = 0, binding = 0) uniform UniformBufferObject {
     mat4 view;
     mat4 proj;
     vec3 viewPos;
-    float time;
+    vec3 dirLightDirection;
+    vec3 dirLightColor;
+    float dirLightIntensity;
+    vec3 dirLightShadowColor;
+    float starDensity;
+    float starBrightness;
+    float ssaoIntensity;
+    uint ssaoDebug;
+    float logDepth;      // 0 = standard depth
     // ... other UBO fields
 } ubo;
 
 void main() {
-    // Expand vertex along normal for outline effect
-    vec3 expandedPosition = inPosition + inNormal * push.outlineWidth;
-
-    vec4 worldPos = push.model * vec4(expandedPosition, 1.0);
+    vec4 worldPos = push.model * vec4(inPosition, 1.0);
     gl_Position = ubo.proj * ubo.view * worldPos;
+
+    // Push the vertex out along its screen-space normal by a fixed number of pixels,
+    // so the outline is equally thick at any distance
+    if (push.outlineWidth > 0.0) {
+        vec3 worldNormal = transpose(inverse(mat3(push.model))) * inNormal;
+        vec2 screenNormal = (ubo.proj * ubo.view * vec4(worldNormal, 0.0)).xy * push.viewportSize;
+        if (dot(screenNormal, screenNormal) > 1e-12) {
+            // Pixels -> NDC (2 units across the viewport), scaled by w to stay in clip space
+            vec2 offset = normalize(screenNormal) * push.outlineWidth * 2.0 / push.viewportSize;
+            gl_Position.xy += offset * gl_Position.w;
+        }
+    }
+
+    // Logarithmic depth (see mesh.vert)
+    if (ubo.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.logDepth * gl_Position.w;
+    }
 }
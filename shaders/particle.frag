@@ -0,0 +1,18 @@
+#version 450
+
+layout(location = 0) in vec2 fragCorner;
+layout(location = 1) in vec4 fragColor;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    // Soft round sprite
+    float falloff = 1.0 - smoothstep(0.0, 1.0, length(fragCorner));
+    float alpha = fragColor.a * falloff * falloff;
+    if (alpha <= 0.001) {
+        discard;
+    }
+
+    // Additive: premultiplied color, HDR values above 1 feed bloom
+    outColor = vec4(fragColor.rgb * alpha, alpha);
+}
@@ -0,0 +1,57 @@
+#version 450
+
+// Camera-facing quads for the GPU particles (6 vertices per particle, no vertex buffer)
+
+struct Particle {
+    vec4 positionAge;       // xyz render-space position, w = age in seconds
+    vec4 velocityLifetime;  // xyz velocity, w = lifetime (0 = dead)
+    vec4 startColor;
+    vec4 endColor;
+    vec4 size;              // x = start size, y = end size
+};
+
+layout(binding = 0) uniform ParticleUniformBufferObject {
+    mat4 view;
+    mat4 proj;
+    vec4 sim;  // z = log depth coefficient (0 = standard depth)
+} ubo;
+
+layout(std430, binding = 1) readonly buffer ParticleBuffer {
+    Particle particles[];
+};
+
+layout(location = 0) out vec2 fragCorner;
+layout(location = 1) out vec4 fragColor;
+
+const vec2 CORNERS[6] = vec2[](
+    vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+    vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0)
+);
+
+void main() {
+    Particle particle = particles[gl_VertexIndex / 6];
+    vec2 corner = CORNERS[gl_VertexIndex % 6];
+    fragCorner = corner;
+
+    // Dead particles collapse outside the clip volume
+    float lifetime = particle.velocityLifetime.w;
+    if (lifetime <= 0.0) {
+        fragColor = vec4(0.0);
+        gl_Position = vec4(0.0, 0.0, 2.0, 1.0);
+        return;
+    }
+
+    // Color and size over life
+    float t = clamp(particle.positionAge.w / lifetime, 0.0, 1.0);
+    fragColor = mix(particle.startColor, particle.endColor, t);
+    float size = mix(particle.size.x, particle.size.y, t);
+
+    vec4 viewPos = ubo.view * vec4(particle.positionAge.xyz, 1.0);
+    viewPos.xy += corner * size;
+    gl_Position = ubo.proj * viewPos;
+
+    // Logarithmic depth (see mesh.vert)
+    if (ubo.sim.z > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.sim.z * gl_Position.w;
+    }
+}
@@ -0,0 +1,16 @@
+#version 450
+
+// Push constants: light-space matrix (ortho * view from light) and per-object model matrix
+layout(push_constant) uniform PushConstants {
+    mat4 lightSpace;
+    mat4 model;
+} push;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+layout(location = 2) in vec2 inUV;
+
+void main() {
+    // Depth-only pass: no fragment shader, depth is written by fixed function
+    gl_Position = push.lightSpace * push.model * vec4(inPosition, 1.0);
+}
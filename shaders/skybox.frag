@@ -13,8 +13,11 @@ layout(binding = 0) uniform UniformBufferObject {
     float nebulaIntensity;
     vec3 nebulaSecondaryColor;
     float backgroundBrightness;
+    uint useCubemap;  // 1 = sample skyboxCube instead of generating stars
 } ubo;
 
+layout(binding = 1) uniform samplerCube skyboxCube;
+
 layout(location = 0) in vec3 fragPosition;
 layout(location = 1) in vec3 fragNormal;
 
@@ -120,6 +123,12 @@ void main() {
     // Normalize direction for star field lookup
     vec3 dir = normalize(fragPosition);
 
+    // Loaded cubemap replaces the procedural sky
+    if (ubo.useCubemap != 0u) {
+        outColor = vec4(texture(skyboxCube, dir).rgb, 1.0);
+        return;
+    }
+
     // Generate star field with configurable parameters
     vec3 stars = starField(dir, ubo.starDensity, ubo.starBrightness);
 
@@ -9,6 +9,7 @@ layout(binding = 0) uniform SSAOUniformBufferObject {
     float ssaoBias;
     float ssaoPower;
     uint ssaoKernelSize;
+    float logDepth;  // > 0: the depth buffer holds logarithmic depth (see mesh.vert)
 } ubo;
 
 layout(binding = 1) uniform sampler2D depthTexture;
@@ -16,12 +17,16 @@ layout(binding = 1) uniform sampler2D depthTexture;
 layout(location = 0) in vec2 fragTexCoord;
 layout(location = 0) out float outAO;
 
-// Linearize depth value
-float linearizeDepth(float depth) {
-    float near = 0.1;  // Must match your camera near plane
-    float far = 100.0; // Must match your camera far plane
-    float z = depth * 2.0 - 1.0; // Back to NDC
-    return (2.0 * near * far) / (far + near - z * (far - near));
+// Sample the depth buffer as standard projection depth, so the reconstruction below works
+// in either depth mode. Logarithmic depth stores log2(1 + w) * logDepth; undo the log to get
+// the view depth w, then apply the projection's z row (clip.z = proj[2][2] * z + proj[3][2], z = -w).
+float sampleDepth(vec2 uv) {
+    float depth = texture(depthTexture, uv).r;
+    if (ubo.logDepth <= 0.0) {
+        return depth;
+    }
+    float w = max(exp2(depth / ubo.logDepth) - 1.0, 1e-6);
+    return (ubo.proj[3][2] - ubo.proj[2][2] * w) / w;
 }
 
 // Reconstruct view-space position from depth using inverse projection
@@ -44,9 +49,9 @@ vec3 reconstructViewPos(vec2 uv, float depth) {
 vec3 reconstructNormal(vec2 uv) {
     vec2 texelSize = 1.0 / textureSize(depthTexture, 0);
 
-    float depth = texture(depthTexture, uv).r;
-    float depthRight = texture(depthTexture, uv + vec2(texelSize.x, 0.0)).r;
-    float depthUp = texture(depthTexture, uv + vec2(0.0, texelSize.y)).r;
+    float depth = sampleDepth(uv);
+    float depthRight = sampleDepth(uv + vec2(texelSize.x, 0.0));
+    float depthUp = sampleDepth(uv + vec2(0.0, texelSize.y));
 
     vec3 pos = reconstructViewPos(uv, depth);
     vec3 posRight = reconstructViewPos(uv + vec2(texelSize.x, 0.0), depthRight);
@@ -78,7 +83,7 @@ vec3 getKernelSample(int index, uint kernelSize) {
 
 void main() {
     vec2 texCoord = fragTexCoord;
-    float depth = texture(depthTexture, texCoord).r;
+    float depth = sampleDepth(texCoord);
 
     // Early out for skybox/far plane
     if (depth >= 0.9999) {
@@ -123,12 +128,12 @@ void main() {
         }
 
         // Reconstruct view-space position at this sample location
-        float sampleDepth = texture(depthTexture, offset.xy).r;
-        if (sampleDepth >= 0.9999) {
+        float occluderDepth = sampleDepth(offset.xy);
+        if (occluderDepth >= 0.9999) {
             continue; // Skip skybox samples
         }
 
-        vec3 reconstructedPos = reconstructViewPos(offset.xy, sampleDepth);
+        vec3 reconstructedPos = reconstructViewPos(offset.xy, occluderDepth);
         vec3 sampledNormal = reconstructNormal(offset.xy);
 
         // Check if sampled normal is similar to our normal (facing same direction)
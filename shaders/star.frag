@@ -19,7 +19,14 @@ layout(binding = 0) uniform StarUniformBufferObject {
     float speed_hi;
     float speed_low;
     float zoom;
-    float _padding;
+    float logDepth;
+    vec2 _fogAlign;
+    vec3 fogColor;
+    float fogDensity;
+    uint fogMode;        // 0 = off, 1 = exponential, 2 = linear
+    float fogStart;
+    float fogEnd;
+    float _fogPad;
 } ubo;
 
 // Hash function for noise
@@ -189,5 +196,13 @@ void main() {
     // Clamp
     finalColor = clamp(finalColor, 0.0, 1.0);
 
+    // Distance fog, matching mesh.frag (fragment-to-camera distance)
+    if (ubo.fogMode != 0u) {
+        float fog = (ubo.fogMode == 1u)
+            ? 1.0 - exp(-ubo.fogDensity * dist)
+            : (dist - ubo.fogStart) / max(ubo.fogEnd - ubo.fogStart, 0.001);
+        finalColor = mix(finalColor, ubo.fogColor, clamp(fog, 0.0, 1.0));
+    }
+
     outColor = vec4(finalColor, 1.0);
 }
@@ -21,7 +21,7 @@ layout(binding = 0) uniform StarUniformBufferObject {
     float speed_hi;
     float speed_low;
     float zoom;
-    float _padding;
+    float logDepth;
 } ubo;
 
 void main() {
@@ -31,4 +31,9 @@ void main() {
     fragTexCoord = inTexCoord;
 
     gl_Position = ubo.proj * ubo.view * worldPos;
+
+    // Logarithmic depth (see mesh.vert)
+    if (ubo.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.logDepth * gl_Position.w;
+    }
 }
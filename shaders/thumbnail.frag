@@ -0,0 +1,80 @@
+#version 450
+
+// Hierarchy thumbnail: the material preview's Cook-Torrance lighting under a fixed
+// studio light, with a depth test instead of the preview's hemisphere discard
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 64) vec3 albedo;
+    float metallic;
+    vec3 lightDirection; // toward the light, in thumbnail view space
+    float roughness;
+    vec3 lightColor;     // color * intensity
+    float ambientStrength;
+    vec3 emissive;
+    float emissiveStrength;
+} push;
+
+layout(location = 0) in vec3 fragNormal;
+
+layout(location = 0) out vec4 outColor;
+
+const float PI = 3.14159265359;
+
+float DistributionGGX(vec3 N, vec3 H, float roughness) {
+    float a = roughness * roughness;
+    float a2 = a * a;
+    float NdotH = max(dot(N, H), 0.0);
+    float denom = (NdotH * NdotH * (a2 - 1.0) + 1.0);
+    return a2 / (PI * denom * denom);
+}
+
+float GeometrySchlickGGX(float NdotV, float roughness) {
+    float r = (roughness + 1.0);
+    float k = (r * r) / 8.0;
+    return NdotV / (NdotV * (1.0 - k) + k);
+}
+
+float GeometrySmith(vec3 N, vec3 V, vec3 L, float roughness) {
+    return GeometrySchlickGGX(max(dot(N, V), 0.0), roughness)
+         * GeometrySchlickGGX(max(dot(N, L), 0.0), roughness);
+}
+
+vec3 fresnelSchlick(float cosTheta, vec3 F0) {
+    return F0 + (1.0 - F0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
+}
+
+void main() {
+    // Two-sided so open meshes (planes) don't show their back faces black
+    vec3 N = normalize(fragNormal);
+    if (N.z < 0.0) {
+        N = -N;
+    }
+
+    vec3 V = vec3(0.0, 0.0, 1.0);
+    vec3 L = normalize(push.lightDirection);
+    vec3 H = normalize(V + L);
+
+    vec3 albedo = push.albedo;
+    float metallic = push.metallic;
+    float roughness = max(push.roughness, 0.04);
+    vec3 F0 = mix(vec3(0.04), albedo, metallic);
+
+    float NDF = DistributionGGX(N, H, roughness);
+    float G = GeometrySmith(N, V, L, roughness);
+    vec3 F = fresnelSchlick(max(dot(H, V), 0.0), F0);
+
+    vec3 specular = (NDF * G * F) / (4.0 * max(dot(N, V), 0.0) * max(dot(N, L), 0.0) + 0.0001);
+    vec3 kD = (vec3(1.0) - F) * (1.0 - metallic);
+    float NdotL = max(dot(N, L), 0.0);
+
+    vec3 color = (kD * albedo / PI + specular) * push.lightColor * NdotL;
+    // A little fill so the unlit side still reads at icon size
+    color += albedo * (0.12 + push.ambientStrength * 0.03);
+    color += push.emissive * push.emissiveStrength;
+
+    // Reinhard so bright highlights don't clip; the target is UNORM and
+    // the sRGB swapchain applies the gamma curve when ImGui draws it
+    color = color / (color + vec3(1.0));
+
+    outColor = vec4(color, 1.0);
+}
@@ -0,0 +1,22 @@
+#version 450
+
+// Hierarchy thumbnail: a mesh drawn orthographically looking down -Z into one atlas cell
+// (the transform centers the mesh, fits it to a 0.5 radius and turns it to a 3/4 view)
+
+layout(push_constant) uniform PushConstants {
+    mat4 transform;
+} push;
+
+layout(location = 0) in vec3 inPosition;
+layout(location = 1) in vec3 inNormal;
+
+layout(location = 0) out vec3 fragNormal;
+
+void main() {
+    vec3 position = (push.transform * vec4(inPosition, 1.0)).xyz;
+    // Uniform scale and rotation only, so the upper 3x3 transforms normals too
+    fragNormal = mat3(push.transform) * inNormal;
+
+    // Same framing as the material preview sphere; flip Y for Vulkan clip space
+    gl_Position = vec4(position.x / 0.55, -position.y / 0.55, 0.5 - position.z * 0.5, 1.0);
+}
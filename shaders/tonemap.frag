@@ -0,0 +1,63 @@
+#version 450
+
+// Tonemap Fragment Shader
+// Maps the HDR scene color into [0, 1]; the sRGB swapchain applies the gamma encode
+
+layout(binding = 0) uniform sampler2D hdrColor;
+
+layout(push_constant) uniform TonemapParams {
+    uint tonemapOperator; // 0 = Reinhard, 1 = ACES, 2 = Uncharted 2, 3 = none (clamp)
+    float exposure;
+} params;
+
+layout(location = 0) in vec2 fragTexCoord;
+layout(location = 0) out vec4 outColor;
+
+vec3 reinhard(vec3 color) {
+    return color / (color + vec3(1.0));
+}
+
+// Narkowicz ACES filmic curve fit
+vec3 aces(vec3 color) {
+    const float a = 2.51;
+    const float b = 0.03;
+    const float c = 2.43;
+    const float d = 0.59;
+    const float e = 0.14;
+    return clamp((color * (a * color + b)) / (color * (c * color + d) + e), 0.0, 1.0);
+}
+
+// Hable's Uncharted 2 filmic curve
+vec3 uncharted2Curve(vec3 x) {
+    const float A = 0.15; // Shoulder strength
+    const float B = 0.50; // Linear strength
+    const float C = 0.10; // Linear angle
+    const float D = 0.20; // Toe strength
+    const float E = 0.02; // Toe numerator
+    const float F = 0.30; // Toe denominator
+    return ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F;
+}
+
+vec3 uncharted2(vec3 color) {
+    const float exposureBias = 2.0;
+    const float whitePoint = 11.2;
+    vec3 curr = uncharted2Curve(color * exposureBias);
+    vec3 whiteScale = vec3(1.0) / uncharted2Curve(vec3(whitePoint));
+    return curr * whiteScale;
+}
+
+void main() {
+    vec3 color = texture(hdrColor, fragTexCoord).rgb * params.exposure;
+
+    if (params.tonemapOperator == 0u) {
+        color = reinhard(color);
+    } else if (params.tonemapOperator == 1u) {
+        color = aces(color);
+    } else if (params.tonemapOperator == 2u) {
+        color = uncharted2(color);
+    } else {
+        color = clamp(color, 0.0, 1.0);
+    }
+
+    outColor = vec4(color, 1.0);
+}
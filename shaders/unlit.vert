@@ -9,6 +9,8 @@ layout(binding = 0) uniform UniformBufferObject {
     mat4 view;
     mat4 proj;
     vec3 viewPos;
+    float time;
+    float logDepth;  // 0 = standard depth
 } ubo;
 
 layout(location = 0) in vec3 inPosition;
@@ -26,4 +28,9 @@ void main() {
     fragUV = inUV;
 
     gl_Position = ubo.proj * ubo.view * worldPosition;
+
+    // Logarithmic depth (see mesh.vert)
+    if (ubo.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.logDepth * gl_Position.w;
+    }
 }
@@ -12,7 +12,12 @@ layout(binding = 0) uniform UniformBufferObject {
     vec3 dirLightDirection;
     vec3 dirLightColor;
     float dirLightIntensity;
-    uint pointLightCount;
+    vec3 dirLightShadowColor;
+    float starDensity;
+    float starBrightness;
+    float ssaoIntensity;
+    uint ssaoDebug;
+    float logDepth;      // 0 = standard depth
 } ubo;
 
 layout(location = 0) in vec3 inPosition;
@@ -26,4 +31,9 @@ void main() {
     fragNormal = mat3(transpose(inverse(push.model))) * inNormal;
 
     gl_Position = ubo.proj * ubo.view * worldPosition;
+
+    // Logarithmic depth (see mesh.vert)
+    if (ubo.logDepth > 0.0) {
+        gl_Position.z = log2(max(1e-6, 1.0 + gl_Position.w)) * ubo.logDepth * gl_Position.w;
+    }
 }
@@ -0,0 +1,13 @@
+#version 450
+
+layout(location = 0) out vec4 outColor;
+
+layout(push_constant) uniform PushConstants {
+    mat4 model;
+    vec4 wireColor;  // RGB color + alpha
+} push;
+
+void main() {
+    // Flat overlay color
+    outColor = push.wireColor;
+}
@@ -0,0 +1,99 @@
+use anyhow::Result;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+
+/// Scene file written by auto-save; its configs go next to it (`SceneData::config_path_for`).
+/// Explicit saves never write here and auto-save never writes anywhere else.
+pub const AUTOSAVE_SCENE_PATH: &str = "config/autosave.scene.json";
+
+/// Set while a background write is in flight, so auto-saves never overlap
+static WRITING: AtomicBool = AtomicBool::new(false);
+
+/// Write `files` (path, contents) on a background thread so editing doesn't stall.
+/// Returns false without writing anything while an earlier write is still running.
+pub fn write_in_background(files: Vec<(String, String)>) -> bool {
+    if WRITING.swap(true, Ordering::AcqRel) {
+        return false;
+    }
+    std::thread::spawn(move || {
+        for (path, contents) in &files {
+            if let Err(e) = write_atomically(Path::new(path), contents) {
+                crate::console::error(format!("Auto-save failed to write {}: {}", path, e));
+            }
+        }
+        WRITING.store(false, Ordering::Release);
+    });
+    true
+}
+
+/// Write to a temporary sibling and rename it over `path`, so a crash mid-write leaves
+/// the previous file intact
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, contents)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// When the auto-save was written, if it is newer than `scene_path` (the explicit save).
+/// A missing scene file counts as older than any auto-save.
+pub fn newer_autosave(scene_path: &str) -> Option<SystemTime> {
+    newer_than(Path::new(AUTOSAVE_SCENE_PATH), Path::new(scene_path))
+}
+
+fn newer_than(autosave_path: &Path, scene_path: &Path) -> Option<SystemTime> {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    let autosave_time = modified(autosave_path)?;
+    match modified(scene_path) {
+        Some(scene_time) if scene_time >= autosave_time => None,
+        _ => Some(autosave_time),
+    }
+}
+
+/// Delete the auto-save scene and its configs (after restoring or discarding it)
+pub fn remove() {
+    let config_path = crate::scene::SceneData::config_path_for(AUTOSAVE_SCENE_PATH);
+    for path in [AUTOSAVE_SCENE_PATH, config_path.as_str()] {
+        if let Err(e) = std::fs::remove_file(path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                crate::console::warn(format!("Failed to delete {}: {}", path, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_only_a_newer_autosave_is_offered() {
+        let dir = std::env::temp_dir().join("tribal_engine_test_autosave");
+        let scene = dir.join("scene.json");
+        let autosave = dir.join("autosave.scene.json");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(newer_than(&autosave, &scene), None);
+        write_atomically(&autosave, "{}").unwrap();
+        assert!(!dir.join("autosave.scene.tmp").exists());
+        // No explicit save yet: the auto-save is all there is
+        assert!(newer_than(&autosave, &scene).is_some());
+
+        write_atomically(&scene, "{}").unwrap();
+        let autosave_time = std::fs::metadata(&autosave).unwrap().modified().unwrap();
+        let set_scene_time = |time: SystemTime| {
+            std::fs::File::options().write(true).open(&scene).unwrap().set_modified(time).unwrap();
+        };
+        set_scene_time(autosave_time - Duration::from_secs(60));
+        assert_eq!(newer_than(&autosave, &scene), Some(autosave_time));
+        set_scene_time(autosave_time + Duration::from_secs(60));
+        assert_eq!(newer_than(&autosave, &scene), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
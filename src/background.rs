@@ -1,6 +1,7 @@
 use ash::vk;
 use glam::{Mat4, Vec3};
 use crate::mesh::Mesh;
+use crate::core::CubemapTexture;
 use crate::game::SkyboxConfig;
 
 /// Manages skybox rendering and related resources
@@ -17,6 +18,9 @@ pub struct SkyboxRenderer {
     pub uniform_buffers_memory: Vec<vk::DeviceMemory>,
     pub descriptor_pool: vk::DescriptorPool,
     pub descriptor_sets: Vec<vk::DescriptorSet>,
+    /// Cube image at binding 1 (a 1x1 black placeholder until one is loaded)
+    pub cubemap: CubemapTexture,
+    pub cubemap_sampler: vk::Sampler,
 }
 
 /// Uniform buffer object for skybox shader (std140 layout)
@@ -35,6 +39,8 @@ pub struct SkyboxUniformBufferObject {
     pub nebula_intensity: f32,       // fills vec3 padding slot
     pub nebula_secondary_color: Vec3,
     pub background_brightness: f32,  // fills vec3 padding slot
+    pub use_cubemap: u32,            // 1 = sample the cubemap instead of generating stars
+    pub _pad1: [u32; 3],
 }
 
 unsafe impl bytemuck::Pod for SkyboxUniformBufferObject {}
@@ -47,6 +53,7 @@ impl SkyboxRenderer {
         proj: Mat4,
         view_pos: Vec3,
         config: &SkyboxConfig,
+        use_cubemap: bool,
     ) -> SkyboxUniformBufferObject {
         SkyboxUniformBufferObject {
             model: Mat4::IDENTITY,
@@ -60,6 +67,8 @@ impl SkyboxRenderer {
             nebula_intensity: config.nebula_intensity,
             nebula_secondary_color: config.nebula_secondary_color,
             background_brightness: config.background_brightness,
+            use_cubemap: use_cubemap as u32,
+            _pad1: [0; 3],
         }
     }
 
@@ -69,6 +78,8 @@ impl SkyboxRenderer {
             device.destroy_buffer(self.uniform_buffers[i], None);
             device.free_memory(self.uniform_buffers_memory[i], None);
         }
+        self.cubemap.destroy(device);
+        device.destroy_sampler(self.cubemap_sampler, None);
         device.destroy_descriptor_pool(self.descriptor_pool, None);
         device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
         device.destroy_pipeline(self.pipeline, None);
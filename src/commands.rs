@@ -0,0 +1,171 @@
+use crate::game::Game;
+use crate::keymap::Action;
+
+/// A named editor command that the command palette (Ctrl+P) can find and run
+pub struct EditorAction {
+    pub name: String,
+    /// Keymap action doing the same thing, shown next to the name as its shortcut
+    pub shortcut: Option<Action>,
+    run: Box<dyn Fn(&mut Game)>,
+}
+
+impl EditorAction {
+    pub fn run(&self, game: &mut Game) {
+        (self.run)(game);
+    }
+}
+
+/// Every command the palette lists. Features add an entry with `register` (or
+/// `register_key_action` for keymap actions) and become searchable by name.
+#[derive(Default)]
+pub struct CommandRegistry {
+    actions: Vec<EditorAction>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, name: impl Into<String>, run: impl Fn(&mut Game) + 'static) {
+        self.actions.push(EditorAction {
+            name: name.into(),
+            shortcut: None,
+            run: Box::new(run),
+        });
+    }
+
+    /// Register a keymap action under its label. Running it queues the action for the
+    /// engine, which handles it like a key press (so screenshots and shader reloads,
+    /// which need the renderer, work from the palette too).
+    pub fn register_key_action(&mut self, action: Action) {
+        self.actions.push(EditorAction {
+            name: action.label().to_string(),
+            shortcut: Some(action),
+            run: Box::new(move |game| game.queued_actions.push(action)),
+        });
+    }
+
+    /// Commands matching `query`, best match first (registration order among equal
+    /// scores). An empty query lists everything.
+    pub fn search(&self, query: &str) -> Vec<&EditorAction> {
+        let mut matches: Vec<(i32, &EditorAction)> = self
+            .actions
+            .iter()
+            .filter_map(|action| fuzzy_score(query, &action.name).map(|score| (score, action)))
+            .collect();
+        // Stable, so ties keep their registration order
+        matches.sort_by_key(|m| std::cmp::Reverse(m.0));
+        matches.into_iter().map(|(_, action)| action).collect()
+    }
+}
+
+/// How well `query` matches `name`: every query character must appear in order
+/// (case-insensitive, spaces in the query are ignored). None when it doesn't match.
+/// Matches at word starts and runs of consecutive characters score higher and gaps
+/// cost a little, so "sv sc" finds "Save Scene" and "scr" puts "Screenshot" first.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let wanted = wanted.to_lowercase().next().unwrap_or(wanted);
+        let index = (next..name.len()).find(|&i| name[i].to_lowercase().next() == Some(wanted))?;
+
+        score += 1;
+        if index == 0 || !name[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match previous {
+            Some(previous) if previous + 1 == index => score += 5,
+            Some(previous) => score -= (index - previous - 1).min(5) as i32,
+            None => {}
+        }
+
+        previous = Some(index);
+        next = index + 1;
+    }
+
+    Some(score)
+}
+
+/// Command palette window state
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    pub query: String,
+    /// Highlighted row of the filtered list (Up/Down move it, Enter runs it)
+    pub selected: usize,
+    /// Set on opening so the search box takes keyboard focus
+    pub focus_query: bool,
+}
+
+impl CommandPalette {
+    /// Open with an empty search, or close if already open
+    pub fn toggle(&mut self) {
+        if self.open {
+            self.open = false;
+        } else {
+            *self = Self {
+                open: true,
+                focus_query: true,
+                ..Default::default()
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(names: &[&str]) -> CommandRegistry {
+        let mut registry = CommandRegistry::new();
+        for name in names {
+            registry.register(*name, |_| {});
+        }
+        registry
+    }
+
+    #[test]
+    fn test_fuzzy_matches_characters_in_order() {
+        assert_eq!(fuzzy_score("", "Save Scene"), Some(0));
+        assert!(fuzzy_score("sv sc", "Save Scene").is_some());
+        assert!(fuzzy_score("SAVE", "Save Scene").is_some());
+        assert!(fuzzy_score("adsph", "Add Primitive: Sphere").is_some());
+        // Out of order or missing characters
+        assert_eq!(fuzzy_score("evas", "Save Scene"), None);
+        assert_eq!(fuzzy_score("save", "Load Scene"), None);
+    }
+
+    #[test]
+    fn test_search_ranks_word_starts_and_runs_first() {
+        let registry = registry(&["Save Scene", "Load Scene", "Screenshot", "Reset Panel Layout"]);
+        assert_eq!(registry.search("").len(), 4);
+
+        let names = |query: &str| -> Vec<String> {
+            registry.search(query).iter().map(|action| action.name.clone()).collect()
+        };
+        assert_eq!(names("scr")[0], "Screenshot");
+        assert_eq!(names("lsc"), vec!["Load Scene".to_string()]);
+        assert_eq!(names("ss")[0], "Save Scene");
+        assert!(names("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_toggle_resets_the_query() {
+        let mut palette = CommandPalette::default();
+        palette.toggle();
+        palette.query.push_str("save");
+        palette.selected = 2;
+        palette.toggle();
+        assert!(!palette.open);
+
+        palette.toggle();
+        assert!(palette.open && palette.focus_query);
+        assert!(palette.query.is_empty());
+        assert_eq!(palette.selected, 0);
+    }
+}
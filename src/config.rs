@@ -4,33 +4,115 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use crate::migration::Migration;
+
+/// Upgrades for older config layouts, oldest first (see `migration::load_versioned`)
+const CONFIG_MIGRATIONS: &[Migration] = &[migrate_config_v1];
+
+/// Layout version written to new config files
+pub const CONFIG_VERSION: u32 = crate::migration::current_version(CONFIG_MIGRATIONS);
+
+/// v1 configs (no `version` field) required every early section and predate many fields:
+/// anything missing takes the current default, everything present is kept as it was
+fn migrate_config_v1(config: &mut serde_json::Value) {
+    if let Ok(defaults) = serde_json::to_value(EngineConfig::default()) {
+        crate::migration::fill_missing_fields(config, &defaults);
+    }
+}
+
+fn config_version() -> u32 {
+    CONFIG_VERSION
+}
+
 /// Complete engine configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngineConfig {
+    /// Layout version, upgraded on load (see `CONFIG_MIGRATIONS`)
+    #[serde(default = "config_version")]
+    pub version: u32,
     pub nebula: NebulaConfigData,
     pub skybox: SkyboxConfigData,
     pub camera: CameraConfigData,
     pub ssao: SSAOConfigData,
     pub star: StarConfigData,
+    #[serde(default)]
+    pub shadow: ShadowConfigData,
+    #[serde(default)]
+    pub bloom: BloomConfigData,
+    #[serde(default)]
+    pub tonemap: TonemapConfigData,
+    #[serde(default)]
+    pub anti_aliasing: AntiAliasingConfigData,
+    #[serde(default)]
+    pub graphics: GraphicsConfigData,
+    #[serde(default)]
+    pub wireframe: WireframeConfigData,
+    #[serde(default)]
+    pub fog: FogConfigData,
+    #[serde(default)]
+    pub background: BackgroundConfigData,
+    #[serde(default)]
+    pub grid: GridConfigData,
+    #[serde(default)]
+    pub particles: ParticleConfigData,
+    #[serde(default)]
+    pub lod: LodConfigData,
+    #[serde(default)]
+    pub budget: SceneBudgetConfigData,
+    #[serde(default)]
+    pub outline: OutlineConfigData,
+    #[serde(default)]
+    pub ssr: SsrConfigData,
+    #[serde(default)]
+    pub gizmo: GizmoConfigData,
+    #[serde(default)]
+    pub keymap: KeymapConfigData,
+    #[serde(default)]
+    pub autosave: AutosaveConfigData,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<WindowConfigData>,
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             nebula: NebulaConfigData::default(),
             skybox: SkyboxConfigData::default(),
             camera: CameraConfigData::default(),
             ssao: SSAOConfigData::default(),
             star: StarConfigData::default(),
+            shadow: ShadowConfigData::default(),
+            bloom: BloomConfigData::default(),
+            tonemap: TonemapConfigData::default(),
+            anti_aliasing: AntiAliasingConfigData::default(),
+            graphics: GraphicsConfigData::default(),
+            wireframe: WireframeConfigData::default(),
+            fog: FogConfigData::default(),
+            background: BackgroundConfigData::default(),
+            grid: GridConfigData::default(),
+            particles: ParticleConfigData::default(),
+            lod: LodConfigData::default(),
+            budget: SceneBudgetConfigData::default(),
+            outline: OutlineConfigData::default(),
+            ssr: SsrConfigData::default(),
+            gizmo: GizmoConfigData::default(),
+            keymap: KeymapConfigData::default(),
+            autosave: AutosaveConfigData::default(),
+            window: None,
         }
     }
 }
 
 impl EngineConfig {
-    /// Load configuration from JSON file
+    /// Load configuration from JSON file, upgrading older layouts
+    /// (the file itself is only rewritten by the next save)
     pub fn load(path: &str) -> Result<Self> {
         let content = fs::read_to_string(path)?;
-        let config: EngineConfig = serde_json::from_str(&content)?;
+        let (config, version) = crate::migration::load_versioned::<EngineConfig>(&content, CONFIG_MIGRATIONS)?;
+        if version < CONFIG_VERSION {
+            crate::console::info(format!("Upgraded config {} from v{} to v{}", path, version, CONFIG_VERSION));
+        }
         Ok(config)
     }
 
@@ -46,14 +128,34 @@ impl EngineConfig {
         Ok(())
     }
 
+    /// Load a scene's configuration, falling back to `fallback` and then to the
+    /// built-in defaults when a file is missing or invalid (nothing is written)
+    pub fn load_or_fallback(path: &str, fallback: &str) -> Self {
+        match Self::load(path) {
+            Ok(config) => config,
+            Err(e) => {
+                crate::console::warn(format!("Config {} not loaded ({}), falling back to {}", path, e, fallback));
+                Self::load(fallback).unwrap_or_default()
+            }
+        }
+    }
+
     /// Load or create default configuration
+    /// Only a missing file is replaced; an unreadable one is left for the user to fix
     pub fn load_or_default(path: &str) -> Self {
-        Self::load(path).unwrap_or_else(|_| {
-            let config = Self::default();
-            // Try to save the default config
-            let _ = config.save(path);
-            config
-        })
+        match Self::load(path) {
+            Ok(config) => config,
+            Err(_) if !Path::new(path).exists() => {
+                let config = Self::default();
+                // Try to save the default config
+                let _ = config.save(path);
+                config
+            }
+            Err(e) => {
+                crate::console::error(format!("Config {} not loaded, using defaults: {}", path, e));
+                Self::default()
+            }
+        }
     }
 }
 
@@ -82,6 +184,14 @@ pub struct NebulaConfigData {
 
     pub light_intensity: f32,
     pub max_distance: f32,
+
+    /// Raymarch steps per pixel (configs saved before this field get the default)
+    #[serde(default = "default_nebula_steps")]
+    pub steps: u32,
+}
+
+fn default_nebula_steps() -> u32 {
+    crate::nebula::DEFAULT_NEBULA_STEPS
 }
 
 impl Default for NebulaConfigData {
@@ -98,6 +208,7 @@ impl Default for NebulaConfigData {
             light_color: Vec3::new(1.0, 0.5, 0.25),
             light_intensity: 1.0 / 30.0,
             max_distance: 10.0,
+            steps: default_nebula_steps(),
         }
     }
 }
@@ -116,6 +227,12 @@ pub struct SkyboxConfigData {
 
     pub nebula_intensity: f32,
     pub background_brightness: f32,
+
+    #[serde(default)]
+    pub mode: crate::game::SkyboxMode,
+    /// Cubemap face directory or equirectangular panorama (used in cubemap mode)
+    #[serde(default)]
+    pub cubemap_path: String,
 }
 
 impl Default for SkyboxConfigData {
@@ -127,6 +244,8 @@ impl Default for SkyboxConfigData {
             nebula_secondary_color: Vec3::new(0.6, 0.3, 0.8),
             nebula_intensity: 1.0,
             background_brightness: 0.0,
+            mode: crate::game::SkyboxMode::Procedural,
+            cubemap_path: String::new(),
         }
     }
 }
@@ -143,6 +262,77 @@ pub struct CameraConfigData {
     pub move_speed: f32,
     pub mouse_sensitivity: f32,
     pub fov: f32,
+
+    #[serde(default = "default_near_plane")]
+    pub near_plane: f32,
+    #[serde(default = "default_far_plane")]
+    pub far_plane: f32,
+    #[serde(default)]
+    pub depth_mode: crate::core::DepthMode,
+
+    #[serde(default)]
+    pub projection: crate::core::ProjectionMode,
+    #[serde(default = "default_ortho_size")]
+    pub ortho_size: f32,
+    #[serde(default)]
+    pub mode: crate::core::CameraMode,
+
+    #[serde(default)]
+    pub collision: bool,
+    #[serde(default = "default_collision_radius")]
+    pub collision_radius: f32,
+
+    #[serde(default = "default_orbit_min_distance")]
+    pub orbit_min_distance: f32,
+    #[serde(default = "default_orbit_max_distance")]
+    pub orbit_max_distance: f32,
+    #[serde(default = "default_orbit_dolly_at_min")]
+    pub orbit_dolly_at_min: bool,
+
+    #[serde(default)]
+    pub bookmarks: Vec<CameraBookmarkData>,
+}
+
+fn default_near_plane() -> f32 {
+    0.1
+}
+
+fn default_far_plane() -> f32 {
+    50_000.0
+}
+
+fn default_ortho_size() -> f32 {
+    10.0
+}
+
+fn default_collision_radius() -> f32 {
+    0.5
+}
+
+fn default_orbit_min_distance() -> f32 {
+    0.5
+}
+
+fn default_orbit_max_distance() -> f32 {
+    10_000.0
+}
+
+fn default_orbit_dolly_at_min() -> bool {
+    true
+}
+
+/// Saved camera viewpoint (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraBookmarkData {
+    pub name: String,
+
+    #[serde(with = "vec3_serde")]
+    pub position: Vec3,
+
+    pub pitch: f32,
+    pub yaw: f32,
+    pub roll: f32,
+    pub fov: f32, // Degrees, like CameraConfigData::fov
 }
 
 impl Default for CameraConfigData {
@@ -155,6 +345,18 @@ impl Default for CameraConfigData {
             move_speed: 5.0,
             mouse_sensitivity: 0.003,
             fov: 70.0,
+            near_plane: default_near_plane(),
+            far_plane: default_far_plane(),
+            depth_mode: crate::core::DepthMode::default(),
+            projection: crate::core::ProjectionMode::Perspective,
+            ortho_size: default_ortho_size(),
+            mode: crate::core::CameraMode::default(),
+            collision: false,
+            collision_radius: default_collision_radius(),
+            orbit_min_distance: default_orbit_min_distance(),
+            orbit_max_distance: default_orbit_max_distance(),
+            orbit_dolly_at_min: default_orbit_dolly_at_min(),
+            bookmarks: Vec::new(),
         }
     }
 }
@@ -167,6 +369,12 @@ pub struct SSAOConfigData {
     pub bias: f32,
     pub power: f32,
     pub kernel_size: u32,
+    #[serde(default = "default_ao_intensity")]
+    pub ao_intensity: f32,
+}
+
+fn default_ao_intensity() -> f32 {
+    1.0
 }
 
 impl Default for SSAOConfigData {
@@ -177,6 +385,7 @@ impl Default for SSAOConfigData {
             bias: 0.1,
             power: 2.0,
             kernel_size: 64,
+            ao_intensity: default_ao_intensity(),
         }
     }
 }
@@ -186,6 +395,7 @@ impl Default for SSAOConfigData {
 pub struct StarConfigData {
     #[serde(with = "vec3_serde")]
     pub color: Vec3,
+    #[serde(default = "default_star_gamma")]
     pub gamma: f32,
     pub exposure: f32,
     pub speed_hi: f32,
@@ -193,11 +403,15 @@ pub struct StarConfigData {
     pub zoom: f32,
 }
 
+fn default_star_gamma() -> f32 {
+    2.2
+}
+
 impl Default for StarConfigData {
     fn default() -> Self {
         Self {
             color: Vec3::new(1.0, 0.9, 0.7),
-            gamma: 2.2,
+            gamma: default_star_gamma(),
             exposure: 40.2,
             speed_hi: 2.0,
             speed_low: 0.5,
@@ -206,6 +420,382 @@ impl Default for StarConfigData {
     }
 }
 
+/// Shadow map configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowConfigData {
+    pub enabled: bool,
+    pub resolution: u32,
+    pub range: f32,
+    pub depth_bias: f32,
+}
+
+impl Default for ShadowConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            resolution: 2048,
+            range: 50.0,
+            depth_bias: 0.005,
+        }
+    }
+}
+
+/// Bloom post-process configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BloomConfigData {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub intensity: f32,
+    pub blur_passes: u32,
+}
+
+impl Default for BloomConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 0.8,
+            intensity: 0.6,
+            blur_passes: 2,
+        }
+    }
+}
+
+/// HDR tonemapping configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TonemapConfigData {
+    pub operator: crate::game::TonemapOperator,
+    pub exposure: f32,
+    /// Picked colors are sRGB and linearized for shaders (false feeds them raw, the old look)
+    #[serde(default = "default_srgb_colors")]
+    pub srgb_colors: bool,
+}
+
+fn default_srgb_colors() -> bool {
+    true
+}
+
+impl Default for TonemapConfigData {
+    fn default() -> Self {
+        Self {
+            operator: crate::game::TonemapOperator::Aces,
+            exposure: 1.0,
+            srgb_colors: default_srgb_colors(),
+        }
+    }
+}
+
+/// Anti-aliasing and texture filtering configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntiAliasingConfigData {
+    /// Configs from before the mode existed used MSAA
+    #[serde(default = "default_anti_aliasing_mode")]
+    pub mode: crate::game::AntiAliasingMode,
+    pub samples: u32,
+    #[serde(default = "default_fxaa_quality")]
+    pub fxaa_quality: f32,
+    #[serde(default = "default_anisotropy")]
+    pub anisotropy: u32,
+}
+
+fn default_anti_aliasing_mode() -> crate::game::AntiAliasingMode {
+    crate::game::AntiAliasingMode::Msaa
+}
+
+fn default_fxaa_quality() -> f32 {
+    0.75
+}
+
+fn default_anisotropy() -> u32 {
+    16
+}
+
+impl Default for AntiAliasingConfigData {
+    fn default() -> Self {
+        Self {
+            mode: default_anti_aliasing_mode(),
+            samples: 4,
+            fxaa_quality: default_fxaa_quality(),
+            anisotropy: default_anisotropy(),
+        }
+    }
+}
+
+/// Present mode and frame limiter configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphicsConfigData {
+    pub present_mode: crate::game::PresentMode,
+    /// 0 = uncapped
+    pub fps_cap: u32,
+    #[serde(default)]
+    pub depth_prepass: bool,
+}
+
+impl Default for GraphicsConfigData {
+    fn default() -> Self {
+        Self {
+            present_mode: crate::game::PresentMode::Mailbox,
+            fps_cap: 120,
+            depth_prepass: false,
+        }
+    }
+}
+
+/// Wireframe overlay configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WireframeConfigData {
+    pub show_all: bool,
+    #[serde(with = "vec3_serde")]
+    pub color: Vec3,
+    #[serde(default)]
+    pub show_bounds: bool,
+}
+
+impl Default for WireframeConfigData {
+    fn default() -> Self {
+        Self {
+            show_all: false,
+            color: Vec3::new(0.0, 1.0, 0.8),
+            show_bounds: false,
+        }
+    }
+}
+
+/// Distance fog configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FogConfigData {
+    pub enabled: bool,
+    pub mode: crate::game::FogMode,
+    #[serde(with = "vec3_serde")]
+    pub color: Vec3,
+    pub density: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Default for FogConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: crate::game::FogMode::Exponential,
+            color: Vec3::new(0.02, 0.03, 0.06),
+            density: 0.01,
+            start: 50.0,
+            end: 300.0,
+        }
+    }
+}
+
+/// Background mode and colors (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundConfigData {
+    pub mode: crate::game::BackgroundMode,
+    #[serde(with = "vec3_serde")]
+    pub color: Vec3,
+    #[serde(with = "vec3_serde")]
+    pub top_color: Vec3,
+    #[serde(with = "vec3_serde")]
+    pub bottom_color: Vec3,
+}
+
+impl Default for BackgroundConfigData {
+    fn default() -> Self {
+        Self {
+            mode: crate::game::BackgroundMode::Skybox,
+            color: Vec3::new(0.1, 0.1, 0.15),
+            top_color: Vec3::new(0.18, 0.22, 0.32),
+            bottom_color: Vec3::new(0.04, 0.04, 0.06),
+        }
+    }
+}
+
+/// Editor ground grid configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridConfigData {
+    pub enabled: bool,
+    pub spacing: f32,
+    pub major_every: u32,
+    #[serde(with = "vec3_serde")]
+    pub color: Vec3,
+    pub fade_distance: f32,
+}
+
+impl Default for GridConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spacing: 1.0,
+            major_every: 10,
+            color: Vec3::new(0.5, 0.55, 0.6),
+            fade_distance: 200.0,
+        }
+    }
+}
+
+/// GPU particle budget configuration (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticleConfigData {
+    pub max_particles: u32,
+    pub drag: f32,
+}
+
+impl Default for ParticleConfigData {
+    fn default() -> Self {
+        Self {
+            max_particles: 16_384,
+            drag: 0.5,
+        }
+    }
+}
+
+/// Mesh level-of-detail switch distances (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LodConfigData {
+    pub enabled: bool,
+    pub distances: [f32; 2],
+}
+
+impl Default for LodConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            distances: [150.0, 500.0],
+        }
+    }
+}
+
+/// Scene content budgets for the Scene Statistics panel (serializable, 0 = no limit)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneBudgetConfigData {
+    pub max_objects: u32,
+    pub max_triangles: u32,
+    pub max_mesh_memory_mb: u32,
+    pub max_point_lights: u32,
+}
+
+impl Default for SceneBudgetConfigData {
+    fn default() -> Self {
+        Self {
+            max_objects: 1_000,
+            max_triangles: 2_000_000,
+            max_mesh_memory_mb: 512,
+            max_point_lights: 64,
+        }
+    }
+}
+
+/// Selection/hover outline look (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlineConfigData {
+    #[serde(with = "vec3_serde")]
+    pub selected_color: Vec3,
+    #[serde(with = "vec3_serde")]
+    pub hovered_color: Vec3,
+    pub width: f32,
+}
+
+impl Default for OutlineConfigData {
+    fn default() -> Self {
+        Self {
+            selected_color: Vec3::new(0.2, 1.0, 0.2),
+            hovered_color: Vec3::new(1.0, 1.0, 0.0),
+            width: 3.0,
+        }
+    }
+}
+
+/// Screen-space reflection settings (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsrConfigData {
+    pub enabled: bool,
+    pub max_steps: u32,
+    pub thickness: f32,
+}
+
+impl Default for SsrConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_steps: 32,
+            thickness: 0.5,
+        }
+    }
+}
+
+/// Transform gizmo size (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GizmoConfigData {
+    pub screen_size: f32,
+    #[serde(default)]
+    pub constant_pixel_size: bool,
+}
+
+impl Default for GizmoConfigData {
+    fn default() -> Self {
+        Self {
+            screen_size: 1.0,
+            constant_pixel_size: false,
+        }
+    }
+}
+
+/// Keyboard shortcuts by action id, e.g. "copy": "Ctrl+C" (serializable).
+/// An empty string unbinds the action; missing actions keep their default key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeymapConfigData {
+    pub bindings: std::collections::BTreeMap<String, String>,
+}
+
+/// Auto-save settings (serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutosaveConfigData {
+    pub enabled: bool,
+    pub interval_secs: u32,
+}
+
+impl Default for AutosaveConfigData {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 120,
+        }
+    }
+}
+
+/// Smallest window size restored from a config (keeps a bad value from hiding the window)
+const MIN_WINDOW_SIZE: (u32, u32) = (320, 240);
+
+/// Last window rect in physical pixels (serializable); saved by the engine on exit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowConfigData {
+    pub width: u32,
+    pub height: u32,
+    /// Outer position (None on platforms that don't report one)
+    #[serde(default)]
+    pub x: Option<i32>,
+    #[serde(default)]
+    pub y: Option<i32>,
+}
+
+impl WindowConfigData {
+    /// Fit the rect inside a monitor (position and size in physical pixels), so a
+    /// window saved on a larger or since-disconnected display still opens on screen
+    pub fn clamped_to_monitor(&self, monitor_position: (i32, i32), monitor_size: (u32, u32)) -> Self {
+        let width = self.width.clamp(MIN_WINDOW_SIZE.0, monitor_size.0.max(MIN_WINDOW_SIZE.0));
+        let height = self.height.clamp(MIN_WINDOW_SIZE.1, monitor_size.1.max(MIN_WINDOW_SIZE.1));
+        let clamp_axis = |value: Option<i32>, start: i32, monitor_extent: u32, extent: u32| {
+            let max = start + monitor_extent.saturating_sub(extent) as i32;
+            value.map(|value| value.clamp(start, max))
+        };
+
+        Self {
+            width,
+            height,
+            x: clamp_axis(self.x, monitor_position.0, monitor_size.0, width),
+            y: clamp_axis(self.y, monitor_position.1, monitor_size.1, height),
+        }
+    }
+}
+
 /// Custom serialization for Vec3
 mod vec3_serde {
     use glam::Vec3;
@@ -263,4 +853,366 @@ mod tests {
         // Cleanup
         let _ = fs::remove_file(path);
     }
+
+    #[test]
+    fn test_v1_config_migrates_field_by_field() {
+        // An unversioned (v1) config with sections and fields missing, as hand-edited or
+        // saved by an early build: it used to fail to load and fall back to defaults
+        let v1 = r#"{
+            "nebula": { "zoom": 0.00001, "density": 3.5 },
+            "camera": {
+                "position": { "x": -13.0, "y": 5.5, "z": -10.0 },
+                "pitch": -0.1, "yaw": -2.4, "roll": 0.0,
+                "move_speed": 8.0, "fov": 60.0
+            },
+            "skybox": { "star_density": 4.0 }
+        }"#;
+        let path = std::env::temp_dir().join("tribal_engine_test_v1.config.json");
+        fs::write(&path, v1).unwrap();
+        let config = EngineConfig::load(path.to_str().unwrap()).unwrap();
+        let _ = fs::remove_file(&path);
+
+        // Everything in the file is kept...
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.nebula.zoom, 0.00001);
+        assert_eq!(config.nebula.density, 3.5);
+        assert_eq!(config.camera.position, Vec3::new(-13.0, 5.5, -10.0));
+        assert_eq!((config.camera.move_speed, config.camera.fov), (8.0, 60.0));
+        assert_eq!(config.skybox.star_density, 4.0);
+
+        // ...and everything missing takes the current default
+        let defaults = EngineConfig::default();
+        assert_eq!(config.nebula.scale, defaults.nebula.scale);
+        assert_eq!(config.nebula.steps, defaults.nebula.steps);
+        assert_eq!(config.camera.mouse_sensitivity, defaults.camera.mouse_sensitivity);
+        assert_eq!(config.ssao.kernel_size, defaults.ssao.kernel_size);
+        assert_eq!(config.star.exposure, defaults.star.exposure);
+        assert_eq!(config.shadow.resolution, defaults.shadow.resolution);
+
+        // Saving writes the current version
+        let saved = serde_json::to_value(&config).unwrap();
+        assert_eq!(saved["version"], CONFIG_VERSION);
+    }
+
+    #[test]
+    fn test_missing_scene_config_falls_back() {
+        let mut shared = EngineConfig::default();
+        shared.nebula.zoom = 0.5;
+        let fallback = "test_fallback_config.json";
+        shared.save(fallback).unwrap();
+
+        let loaded = EngineConfig::load_or_fallback("test_missing_scene.config.json", fallback);
+        assert_eq!(loaded.nebula.zoom, 0.5);
+
+        // Both missing: built-in defaults
+        let _ = fs::remove_file(fallback);
+        let loaded = EngineConfig::load_or_fallback("test_missing_scene.config.json", fallback);
+        assert_eq!(loaded.nebula.zoom, EngineConfig::default().nebula.zoom);
+    }
+
+    #[test]
+    fn test_ssao_config_without_intensity_uses_full_ao() {
+        let json = r#"{"enabled": true, "radius": 1.0, "bias": 0.1, "power": 2.0, "kernel_size": 64}"#;
+        let ssao: SSAOConfigData = serde_json::from_str(json).unwrap();
+        assert_eq!(ssao.ao_intensity, 1.0);
+    }
+
+    #[test]
+    fn test_anti_aliasing_config_without_mode_keeps_msaa() {
+        let json = r#"{"samples": 8, "anisotropy": 4}"#;
+        let data: AntiAliasingConfigData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.mode, crate::game::AntiAliasingMode::Msaa);
+        assert_eq!(data.fxaa_quality, default_fxaa_quality());
+
+        let config = crate::game::AntiAliasingConfig::from(data);
+        assert_eq!(config.msaa_samples(), 8);
+        assert!(!config.fxaa_enabled());
+
+        // FXAA replaces MSAA, so the main pass drops to one sample
+        let config = crate::game::AntiAliasingConfig {
+            mode: crate::game::AntiAliasingMode::Fxaa,
+            ..config
+        };
+        assert_eq!(config.msaa_samples(), 1);
+        assert!(config.fxaa_enabled());
+    }
+
+    #[test]
+    fn test_star_config_defaults_gamma_and_clamps_exposure() {
+        let json = r#"{"color": {"x": 1.0, "y": 0.9, "z": 0.7}, "exposure": 5000.0, "speed_hi": 2.0, "speed_low": 0.5, "zoom": 0.5}"#;
+        let data: StarConfigData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.gamma, 2.2);
+
+        let star = crate::game::StarConfig::from(data);
+        assert_eq!(star.exposure, crate::game::MAX_STAR_EXPOSURE);
+    }
+
+    #[test]
+    fn test_tonemap_config_defaults_to_srgb_colors() {
+        // Configs saved before the color policy existed get sRGB-correct colors
+        let data: TonemapConfigData = serde_json::from_str(r#"{"operator": "Clamp", "exposure": 1.0}"#).unwrap();
+        assert!(data.srgb_colors);
+
+        let tonemap = crate::game::TonemapConfig::from(data);
+        assert_eq!(tonemap.operator.shader_index(), 3);
+        assert!(tonemap.shader_color(Vec3::splat(0.5)).abs_diff_eq(Vec3::splat(0.214), 1e-3));
+        let raw = crate::game::TonemapConfig { srgb_colors: false, ..tonemap };
+        assert_eq!(raw.shader_color(Vec3::splat(0.5)), Vec3::splat(0.5));
+    }
+
+    #[test]
+    fn test_nebula_steps_default_and_clamp() {
+        // Configs saved before the step count existed get the tuned default
+        let mut json = serde_json::to_value(NebulaConfigData::default()).unwrap();
+        json.as_object_mut().unwrap().remove("steps");
+        let mut data: NebulaConfigData = serde_json::from_value(json).unwrap();
+        assert_eq!(data.steps, crate::nebula::DEFAULT_NEBULA_STEPS);
+
+        data.steps = 100_000;
+        let nebula = crate::nebula::NebulaConfig::from(data);
+        assert_eq!(nebula.steps, crate::nebula::MAX_NEBULA_STEPS);
+        assert_eq!(NebulaConfigData::from(&nebula).steps, crate::nebula::MAX_NEBULA_STEPS);
+    }
+
+    #[test]
+    fn test_camera_mode_and_speed_round_trip() {
+        // Configs saved before camera modes existed load as fly mode
+        let json = r#"{"position": {"x": 0.0, "y": 0.0, "z": 5.0}, "pitch": 0.0, "yaw": 0.0, "roll": 0.0,
+            "move_speed": 12.0, "mouse_sensitivity": 0.003, "fov": 70.0}"#;
+        let data: CameraConfigData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.mode, crate::core::CameraMode::Fly);
+        assert!(!data.collision);
+        assert_eq!((data.orbit_min_distance, data.orbit_max_distance), (0.5, 10_000.0));
+
+        let mut camera = crate::core::Camera::from(data);
+        assert_eq!(camera.move_speed(), 12.0);
+        camera.set_mode(crate::core::CameraMode::Orbit);
+        camera.set_collision_enabled(true);
+        camera.set_collision_radius(1.5);
+
+        let saved = CameraConfigData::from(&camera);
+        assert_eq!(saved.mode, crate::core::CameraMode::Orbit);
+        assert_eq!(saved.move_speed, 12.0);
+        assert!(saved.collision);
+        assert_eq!(saved.collision_radius, 1.5);
+    }
+
+    #[test]
+    fn test_orbit_zoom_stays_within_limits() {
+        let mut camera = crate::core::Camera::new(Vec3::new(0.0, 0.0, 10.0));
+        camera.set_orbit_distance_limits(2.0, 20.0);
+
+        // Each notch scales the distance, so zooming in then out returns to the start
+        camera.zoom_orbit(Vec3::ZERO, 1.0);
+        assert!(camera.position().z < 10.0 && camera.position().z > 2.0);
+        camera.zoom_orbit(Vec3::ZERO, -1.0);
+        assert!((camera.position().z - 10.0).abs() < 1e-4);
+
+        camera.zoom_orbit(Vec3::ZERO, -100.0);
+        assert!((camera.position().z - 20.0).abs() < 1e-3);
+
+        // At the minimum the camera stops, or keeps flying forward (-Z) when dollying is on
+        camera.set_orbit_dolly_at_min(false);
+        camera.zoom_orbit(Vec3::ZERO, 100.0);
+        assert!((camera.position().z - 2.0).abs() < 1e-3);
+        camera.zoom_orbit(Vec3::ZERO, 1.0);
+        assert!((camera.position().z - 2.0).abs() < 1e-3);
+        camera.set_orbit_dolly_at_min(true);
+        camera.zoom_orbit(Vec3::ZERO, 1.0);
+        assert!(camera.position().z < 2.0);
+
+        // Limits never cross
+        camera.set_orbit_distance_limits(50.0, 5.0);
+        assert_eq!(camera.orbit_distance_limits(), (50.0, 50.0));
+    }
+
+    #[test]
+    fn test_clip_planes_and_log_depth() {
+        // Configs saved before the clip planes were configurable keep the old planes
+        let json = r#"{"position": {"x": 0.0, "y": 0.0, "z": 5.0}, "pitch": 0.0, "yaw": 0.0, "roll": 0.0,
+            "move_speed": 5.0, "mouse_sensitivity": 0.003, "fov": 70.0}"#;
+        let data: CameraConfigData = serde_json::from_str(json).unwrap();
+        assert_eq!((data.near_plane, data.far_plane), (0.1, 50_000.0));
+        assert_eq!(data.depth_mode, crate::core::DepthMode::Standard);
+
+        let mut camera = crate::core::Camera::from(data);
+        assert_eq!(camera.log_depth_coefficient(), 0.0);
+
+        // The far plane never collapses onto the near plane
+        camera.set_clip_planes(10.0, 5.0);
+        assert_eq!((camera.near_plane(), camera.far_plane()), (10.0, 20.0));
+
+        // Log depth maps the far plane to depth 1, and is off in orthographic projection
+        camera.set_clip_planes(0.01, 1.0e8);
+        camera.set_depth_mode(crate::core::DepthMode::Logarithmic);
+        let coefficient = camera.log_depth_coefficient();
+        assert!(((1.0e8_f32 + 1.0).log2() * coefficient - 1.0).abs() < 1e-6);
+        camera.set_projection_mode(crate::core::ProjectionMode::Orthographic);
+        assert_eq!(camera.log_depth_coefficient(), 0.0);
+
+        let saved = CameraConfigData::from(&camera);
+        assert_eq!((saved.near_plane, saved.far_plane), (0.01, 1.0e8));
+        assert_eq!(saved.depth_mode, crate::core::DepthMode::Logarithmic);
+    }
+
+    #[test]
+    fn test_orbit_target_carries_camera_along() {
+        let mut camera = crate::core::Camera::new(Vec3::new(1_000.0, 0.0, 10.0));
+
+        // Tracking without a pivot does nothing
+        camera.track_orbit_target(glam::DVec3::new(5.0, 5.0, 5.0));
+        assert_eq!(camera.position(), Vec3::new(1_000.0, 0.0, 10.0));
+
+        // The camera keeps its offset as the pivot moves
+        camera.set_orbit_target(glam::DVec3::new(1_000.0, 0.0, 0.0));
+        camera.track_orbit_target(glam::DVec3::new(1_050.0, 20.0, 0.0));
+        assert_eq!(camera.position(), Vec3::new(1_050.0, 20.0, 10.0));
+
+        camera.clear_orbit_target();
+        assert_eq!(camera.orbit_target(), None);
+    }
+
+    #[test]
+    fn test_lod_distances_pick_levels_in_order() {
+        // Thresholds saved out of order are pulled back into ascending order
+        let lod: crate::game::LodConfig = LodConfigData { enabled: true, distances: [300.0, 100.0] }.into();
+        assert_eq!(lod.distances, [300.0, 300.0]);
+
+        let lod: crate::game::LodConfig = LodConfigData { enabled: true, distances: [100.0, 400.0] }.into();
+        assert_eq!(lod.level_for_distance(50.0), 0);
+        assert_eq!(lod.level_for_distance(100.0), 1);
+        assert_eq!(lod.level_for_distance(1000.0), 2);
+
+        let disabled: crate::game::LodConfig = LodConfigData { enabled: false, ..Default::default() }.into();
+        assert_eq!(disabled.level_for_distance(1e6), 0);
+    }
+
+    #[test]
+    fn test_scene_budgets_flag_only_set_limits() {
+        use crate::game::SceneBudgetConfig;
+
+        assert!(SceneBudgetConfig::exceeds(65, 64));
+        assert!(!SceneBudgetConfig::exceeds(64, 64));
+        assert!(!SceneBudgetConfig::exceeds(u64::MAX, 0)); // 0 = no limit
+
+        // The point light budget can't promise more lights than the shader draws
+        let budget: SceneBudgetConfig = SceneBudgetConfigData { max_point_lights: 10_000, ..Default::default() }.into();
+        assert_eq!(budget.max_point_lights, crate::core::lighting::MAX_POINT_LIGHTS as u32);
+    }
+
+    #[test]
+    fn test_outline_width_clamps_to_pixel_range() {
+        let outline: crate::game::OutlineConfig = OutlineConfigData { width: 100.0, ..Default::default() }.into();
+        assert_eq!(outline.width, crate::game::OutlineConfig::MAX_WIDTH);
+        let outline: crate::game::OutlineConfig = OutlineConfigData { width: 0.0, ..Default::default() }.into();
+        assert_eq!(outline.width, crate::game::OutlineConfig::MIN_WIDTH);
+    }
+
+    #[test]
+    fn test_gizmo_scale_modes() {
+        use crate::core::ProjectionMode;
+        use crate::game::GizmoConfig;
+
+        let mut camera = crate::core::Camera::new(Vec3::ZERO); // Looking down -Z
+        let ahead = Vec3::new(0.0, 0.0, -10.0);
+        let viewport_height = 1000.0;
+
+        // Default: a share of the distance, scaled by the size multiplier
+        let relative: GizmoConfig = GizmoConfigData { screen_size: 2.0, constant_pixel_size: false }.into();
+        assert!((relative.scale_at(&camera, ahead, viewport_height) - 3.0).abs() < 1e-4);
+
+        // Constant pixel size: the same pixel length whatever the FOV
+        let constant: GizmoConfig = GizmoConfigData { screen_size: 1.0, constant_pixel_size: true }.into();
+        for fov in [30f32, 60.0, 90.0] {
+            camera.set_fov(fov.to_radians());
+            let pixels = constant.scale_at(&camera, ahead, viewport_height) / camera.world_units_per_pixel(ahead, viewport_height);
+            assert!((pixels - GizmoConfig::REFERENCE_PIXELS).abs() < 1e-2);
+        }
+
+        // Orthographic ignores distance
+        camera.set_projection_mode(ProjectionMode::Orthographic);
+        let near = constant.scale_at(&camera, Vec3::new(0.0, 0.0, -1.0), viewport_height);
+        let far = constant.scale_at(&camera, Vec3::new(0.0, 0.0, -1000.0), viewport_height);
+        assert!((near - far).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gizmo_stays_bounded_next_to_the_camera() {
+        use crate::game::GizmoConfig;
+
+        let camera = crate::core::Camera::new(Vec3::ZERO);
+        let viewport_height = 600.0;
+        let config: GizmoConfig = GizmoConfigData { screen_size: 100.0, constant_pixel_size: false }.into();
+        assert_eq!(config.screen_size, GizmoConfig::MAX_SCREEN_SIZE);
+
+        // Off to the side and barely in front: the distance is large but the depth is tiny
+        for point in [Vec3::new(5.0, 0.0, -0.05), Vec3::new(0.0, 0.0, -0.001), Vec3::new(1.0, 0.0, 1.0)] {
+            let pixels = config.scale_at(&camera, point, viewport_height) / camera.world_units_per_pixel(point, viewport_height);
+            assert!(pixels <= GizmoConfig::MAX_VIEWPORT_FRACTION * viewport_height + 1e-2);
+            assert!(pixels > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_ssr_settings_clamp_to_supported_range() {
+        use crate::game::SsrConfig;
+        let ssr: SsrConfig = SsrConfigData { enabled: true, max_steps: 0, thickness: -1.0 }.into();
+        assert_eq!((ssr.max_steps, ssr.thickness), (SsrConfig::MIN_STEPS, SsrConfig::MIN_THICKNESS));
+        let ssr: SsrConfig = SsrConfigData { enabled: false, max_steps: 10_000, thickness: 1e6 }.into();
+        assert_eq!((ssr.max_steps, ssr.thickness), (SsrConfig::MAX_STEPS, SsrConfig::MAX_THICKNESS));
+        assert!(!ssr.enabled);
+    }
+
+    #[test]
+    fn test_autosave_interval_clamps_and_defaults() {
+        // Configs saved before auto-save existed turn it on at the default interval
+        let mut json = serde_json::to_value(EngineConfig::default()).unwrap();
+        json.as_object_mut().unwrap().remove("autosave");
+        let config: EngineConfig = serde_json::from_value(json).unwrap();
+        assert!(config.autosave.enabled);
+        assert_eq!(config.autosave.interval_secs, 120);
+
+        use crate::game::AutosaveConfig;
+        let autosave: AutosaveConfig = AutosaveConfigData { enabled: true, interval_secs: 0 }.into();
+        assert_eq!(autosave.interval_secs, AutosaveConfig::MIN_INTERVAL_SECS);
+        let autosave: AutosaveConfig = AutosaveConfigData { enabled: true, interval_secs: u32::MAX }.into();
+        assert_eq!(autosave.interval_secs, AutosaveConfig::MAX_INTERVAL_SECS);
+    }
+
+    #[test]
+    fn test_background_defaults_to_skybox() {
+        // Configs saved before the background setting keep drawing the skybox
+        let mut json = serde_json::to_value(EngineConfig::default()).unwrap();
+        json.as_object_mut().unwrap().remove("background");
+        let config: EngineConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.background.mode, crate::game::BackgroundMode::Skybox);
+
+        // The default solid color is the old hardcoded clear color, linearized
+        let clear = crate::core::color::srgb_to_linear_rgb(config.background.color);
+        assert!(clear.abs_diff_eq(Vec3::new(0.01, 0.01, 0.02), 1e-3), "{:?}", clear);
+    }
+
+    #[test]
+    fn test_skybox_mode_defaults_to_procedural() {
+        let json = r#"{"star_density": 2.0, "star_brightness": 3.0, "nebula_primary_color": {"x": 0.1, "y": 0.2, "z": 0.4},
+            "nebula_secondary_color": {"x": 0.6, "y": 0.3, "z": 0.8}, "nebula_intensity": 1.0, "background_brightness": 0.0}"#;
+        let data: SkyboxConfigData = serde_json::from_str(json).unwrap();
+        assert_eq!(data.mode, crate::game::SkyboxMode::Procedural);
+        assert!(data.cubemap_path.is_empty());
+    }
+
+    #[test]
+    fn test_window_rect_clamps_to_monitor() {
+        // Saved on a 4K display, restored on a 1080p one to its right
+        let saved = WindowConfigData { width: 3000, height: 2000, x: Some(3500), y: Some(-50) };
+        let restored = saved.clamped_to_monitor((1920, 0), (1920, 1080));
+        assert_eq!(restored, WindowConfigData { width: 1920, height: 1080, x: Some(1920), y: Some(0) });
+
+        // A rect that already fits is untouched, and a missing position stays missing
+        let fits = WindowConfigData { width: 1600, height: 900, x: Some(100), y: Some(50) };
+        assert_eq!(fits.clamped_to_monitor((0, 0), (1920, 1080)), fits);
+        let unplaced = WindowConfigData { width: 1600, height: 900, x: None, y: None };
+        let unplaced = unplaced.clamped_to_monitor((0, 0), (1280, 720));
+        assert_eq!((unplaced.width, unplaced.height, unplaced.x), (1280, 720, None));
+    }
 }
@@ -0,0 +1,220 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// Maximum number of entries kept before the oldest are dropped
+const MAX_CONSOLE_ENTRIES: usize = 1000;
+
+/// Messages logged with the free functions below, waiting for the console to collect them
+/// Lets code without access to the Game (renderer, loaders, the Vulkan debug callback) log
+static PENDING: Mutex<VecDeque<ConsoleEntry>> = Mutex::new(VecDeque::new());
+
+/// Reference point for entry timestamps
+static START: OnceLock<Instant> = OnceLock::new();
+
+/// Seconds since the first message was logged (or the console was created)
+fn timestamp() -> f32 {
+    START.get_or_init(Instant::now).elapsed().as_secs_f32()
+}
+
+/// Severity of a console entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    /// Text color used by the console window
+    pub fn color(&self) -> [f32; 4] {
+        match self {
+            LogLevel::Info => [0.8, 0.8, 0.8, 1.0],
+            LogLevel::Warning => [1.0, 0.8, 0.2, 1.0],
+            LogLevel::Error => [1.0, 0.35, 0.35, 1.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsoleEntry {
+    pub level: LogLevel,
+    pub message: String,
+    /// Seconds since startup
+    pub time: f32,
+}
+
+impl ConsoleEntry {
+    fn new(level: LogLevel, message: String) -> Self {
+        Self { level, message, time: timestamp() }
+    }
+
+    /// Timestamp as minutes:seconds.milliseconds since startup
+    pub fn time_label(&self) -> String {
+        let minutes = (self.time / 60.0) as u32;
+        format!("{:02}:{:06.3}", minutes, self.time - minutes as f32 * 60.0)
+    }
+}
+
+/// Echo a message to stdout/stderr
+fn echo(level: LogLevel, message: &str) {
+    match level {
+        LogLevel::Info => println!("{}", message),
+        LogLevel::Warning => println!("Warning: {}", message),
+        LogLevel::Error => eprintln!("Error: {}", message),
+    }
+}
+
+/// Push onto a bounded buffer, dropping the oldest entry when full
+fn push_bounded(entries: &mut VecDeque<ConsoleEntry>, entry: ConsoleEntry) {
+    if entries.len() == MAX_CONSOLE_ENTRIES {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+/// Log from anywhere; the message shows up in the console on the next UI frame
+pub fn log(level: LogLevel, message: impl Into<String>) {
+    let message = message.into();
+    echo(level, &message);
+    if let Ok(mut pending) = PENDING.lock() {
+        push_bounded(&mut pending, ConsoleEntry::new(level, message));
+    }
+}
+
+pub fn info(message: impl Into<String>) {
+    log(LogLevel::Info, message);
+}
+
+pub fn warn(message: impl Into<String>) {
+    log(LogLevel::Warning, message);
+}
+
+pub fn error(message: impl Into<String>) {
+    log(LogLevel::Error, message);
+}
+
+/// In-engine log window (toggled with the backtick key)
+///
+/// Entries are echoed to stdout/stderr as well. Logging an error opens the window.
+pub struct Console {
+    entries: VecDeque<ConsoleEntry>,
+    pub open: bool,
+    /// Only entries containing this text are shown (case-insensitive)
+    pub filter: String,
+    /// Keep the newest entry in view
+    pub auto_scroll: bool,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        START.get_or_init(Instant::now);
+        Self {
+            entries: VecDeque::new(),
+            open: false,
+            filter: String::new(),
+            auto_scroll: true,
+        }
+    }
+
+    pub fn log(&mut self, level: LogLevel, message: impl Into<String>) {
+        let message = message.into();
+        echo(level, &message);
+        self.push(ConsoleEntry::new(level, message));
+    }
+
+    fn push(&mut self, entry: ConsoleEntry) {
+        if entry.level == LogLevel::Error {
+            self.open = true;
+        }
+        push_bounded(&mut self.entries, entry);
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.log(LogLevel::Info, message);
+    }
+
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.log(LogLevel::Warning, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.log(LogLevel::Error, message);
+    }
+
+    /// Move messages logged with the free functions into this console
+    pub fn collect_pending(&mut self) {
+        let pending = match PENDING.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return,
+        };
+        for entry in pending {
+            self.push(entry);
+        }
+    }
+
+    /// Entries from oldest to newest
+    pub fn entries(&self) -> impl Iterator<Item = &ConsoleEntry> {
+        self.entries.iter()
+    }
+
+    /// Entries matching the filter text, oldest to newest
+    pub fn filtered_entries(&self) -> impl Iterator<Item = &ConsoleEntry> {
+        let filter = self.filter.to_lowercase();
+        self.entries
+            .iter()
+            .filter(move |entry| filter.is_empty() || entry.message.to_lowercase().contains(&filter))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_open_console_and_entries_are_capped() {
+        let mut console = Console::new();
+        console.info("Shaders reloaded");
+        assert!(!console.open);
+
+        console.error("mesh.frag:12: error: 'colour' : undeclared identifier");
+        assert!(console.open);
+
+        for i in 0..MAX_CONSOLE_ENTRIES {
+            console.info(format!("entry {}", i));
+        }
+        assert_eq!(console.entries().count(), MAX_CONSOLE_ENTRIES);
+        assert_eq!(console.entries().next().unwrap().message, "entry 0");
+    }
+
+    #[test]
+    fn test_global_messages_are_collected_and_filtered() {
+        let mut console = Console::new();
+        warn("Config engine_config.json not loaded");
+        error("Failed to load mesh models/ship.obj");
+        console.collect_pending();
+
+        // Other tests may log concurrently, so only look for our own messages
+        assert!(console.open);
+        console.filter = "MESH".to_string();
+        let matches: Vec<_> = console.filtered_entries().collect();
+        assert!(matches.iter().any(|entry| entry.message == "Failed to load mesh models/ship.obj"));
+        assert!(matches.iter().all(|entry| entry.message.to_lowercase().contains("mesh")));
+    }
+
+    #[test]
+    fn test_time_label() {
+        let entry = ConsoleEntry { level: LogLevel::Info, message: String::new(), time: 125.5 };
+        assert_eq!(entry.time_label(), "02:05.500");
+    }
+}
@@ -1,9 +1,133 @@
-use glam::{Mat4, Quat, Vec3, Vec4};
+use glam::{DVec3, Mat4, Quat, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+
+/// Duration of the animated move to a bookmark, in seconds
+const BOOKMARK_TRANSITION_DURATION: f32 = 0.5;
+
+/// Camera projection type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ProjectionMode {
+    #[default]
+    Perspective,
+    /// Parallel projection for top-down tactical views
+    Orthographic,
+}
+
+impl ProjectionMode {
+    pub const ALL: [ProjectionMode; 2] = [ProjectionMode::Perspective, ProjectionMode::Orthographic];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectionMode::Perspective => "Perspective",
+            ProjectionMode::Orthographic => "Orthographic",
+        }
+    }
+}
+
+/// How mouse and keyboard input drive the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CameraMode {
+    /// Right/middle-drag orbits the selection, the wheel dollies toward it, Q/E roll
+    Orbit,
+    /// WASD + Q/E move relative to the view, right-drag looks around, the wheel scales speed
+    #[default]
+    Fly,
+}
+
+impl CameraMode {
+    pub const ALL: [CameraMode; 2] = [CameraMode::Orbit, CameraMode::Fly];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CameraMode::Orbit => "Orbit",
+            CameraMode::Fly => "Fly",
+        }
+    }
+}
+
+/// How scene depth is stored in the depth buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DepthMode {
+    /// Hardware perspective depth: very precise near the camera, coarse far away, so
+    /// distant surfaces z-fight when the far plane is pushed out to system scale
+    #[default]
+    Standard,
+    /// The vertex shaders write log2(1 + view depth), giving even relative precision from
+    /// the near plane to the far plane. Depth is still interpolated linearly across each
+    /// triangle, so very large triangles close to the camera can sort or clip slightly
+    /// wrong (writing depth per fragment would fix that but disables early depth tests).
+    /// Perspective only; orthographic depth is already linear.
+    Logarithmic,
+}
+
+impl DepthMode {
+    pub const ALL: [DepthMode; 2] = [DepthMode::Standard, DepthMode::Logarithmic];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DepthMode::Standard => "Standard",
+            DepthMode::Logarithmic => "Logarithmic",
+        }
+    }
+}
+
+/// Range the clip planes can be set to
+pub const MIN_NEAR_PLANE: f32 = 0.001;
+pub const MAX_FAR_PLANE: f32 = 1.0e9;
+
+/// Slowest and fastest fly speeds in units per second
+pub const MIN_MOVE_SPEED: f32 = 0.1;
+pub const MAX_MOVE_SPEED: f32 = 100_000.0;
+
+/// Speed multiplier per mouse-wheel notch in fly mode
+const MOVE_SPEED_WHEEL_FACTOR: f32 = 1.2;
+
+/// Orbit distance multiplier per mouse-wheel notch in orbit mode (multiplicative, so
+/// zoom is fast when far and fine when close)
+const ORBIT_ZOOM_WHEEL_FACTOR: f32 = 1.15;
+
+/// Range the orbit zoom limits can be set to
+pub const MIN_ORBIT_DISTANCE: f32 = 0.01;
+pub const MAX_ORBIT_DISTANCE: f32 = 1_000_000.0;
+
+/// Smallest and largest clearance kept between the camera and scene surfaces
+pub const MIN_COLLISION_RADIUS: f32 = 0.01;
+pub const MAX_COLLISION_RADIUS: f32 = 10.0;
+
+/// Named camera viewpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub position: Vec3,
+    pub pitch: f32,
+    pub yaw: f32,
+    pub roll: f32,
+    /// Field of view in radians
+    pub fov: f32,
+}
+
+impl CameraBookmark {
+    fn rotation(&self) -> Quat {
+        Quat::from_euler(glam::EulerRot::YXZ, self.yaw, self.pitch, self.roll)
+    }
+}
+
+/// In-progress animated move to a bookmark
+#[derive(Debug, Clone)]
+struct CameraTransition {
+    start_position: DVec3,
+    start_rotation: Quat,
+    start_fov: f32,
+    target_position: DVec3,
+    target_rotation: Quat,
+    target_fov: f32,
+    progress: f32, // 0.0 to 1.0
+}
 
 /// Free-flying camera with 6 degrees of freedom
 pub struct Camera {
-    /// Camera position in world space
-    position: Vec3,
+    /// Camera position in world space (64-bit, so flying far from the origin stays smooth)
+    position: DVec3,
     /// Camera rotation (pitch, yaw, roll in radians)
     pitch: f32,
     yaw: f32,
@@ -14,37 +138,93 @@ pub struct Camera {
     near_plane: f32,
     /// Far clipping plane distance
     far_plane: f32,
+    /// Standard or logarithmic depth buffer
+    depth_mode: DepthMode,
+    /// Perspective or orthographic projection
+    projection: ProjectionMode,
+    /// Half the visible height in world units (orthographic only)
+    ortho_size: f32,
+    /// Saved viewpoints (persisted with the camera config)
+    bookmarks: Vec<CameraBookmark>,
+    /// Active move to a bookmark, cancelled by manual camera input
+    transition: Option<CameraTransition>,
+    /// Orbit or fly controls
+    mode: CameraMode,
+    /// Fly speed in units per second (before the Shift boost)
+    move_speed: f32,
+    /// Stop the camera at scene surfaces instead of passing through meshes
+    collision: bool,
+    /// Clearance kept between the camera and the surface it stops at
+    collision_radius: f32,
+    /// Closest and farthest the orbit wheel zoom goes from its target
+    orbit_min_distance: f32,
+    orbit_max_distance: f32,
+    /// Zooming in at the closest distance flies forward instead of stopping
+    orbit_dolly_at_min: bool,
+    /// Pivot orbiting and zoom center on instead of the selection (None = free orbit)
+    orbit_target: Option<DVec3>,
 }
 
 impl Camera {
     /// Create a new camera at the given position with default projection settings
     pub fn new(position: Vec3) -> Self {
         Self {
-            position,
+            position: position.as_dvec3(),
             pitch: 0.0,
             yaw: 0.0,
             roll: 0.0,
             fov: 45.0_f32.to_radians(),
             near_plane: 0.1,
             far_plane: 50000.0,  // Balanced far plane for both near precision and distant objects
+            depth_mode: DepthMode::Standard,
+            projection: ProjectionMode::Perspective,
+            ortho_size: 10.0,
+            bookmarks: Vec::new(),
+            transition: None,
+            mode: CameraMode::default(),
+            move_speed: 5.0,
+            collision: false,
+            collision_radius: 0.5,
+            orbit_min_distance: 0.5,
+            orbit_max_distance: 10_000.0,
+            orbit_dolly_at_min: true,
+            orbit_target: None,
         }
     }
     
     /// Create a camera with custom projection parameters
     pub fn with_projection(position: Vec3, fov: f32, near_plane: f32, far_plane: f32) -> Self {
         Self {
-            position,
+            position: position.as_dvec3(),
             pitch: 0.0,
             yaw: 0.0,
             roll: 0.0,
             fov,
             near_plane,
             far_plane,
+            depth_mode: DepthMode::Standard,
+            projection: ProjectionMode::Perspective,
+            ortho_size: 10.0,
+            bookmarks: Vec::new(),
+            transition: None,
+            mode: CameraMode::default(),
+            move_speed: 5.0,
+            collision: false,
+            collision_radius: 0.5,
+            orbit_min_distance: 0.5,
+            orbit_max_distance: 10_000.0,
+            orbit_dolly_at_min: true,
+            orbit_target: None,
         }
     }
     
     /// Get the camera's position
     pub fn position(&self) -> Vec3 {
+        self.position.as_vec3()
+    }
+
+    /// Get the camera's full-precision world position
+    pub fn world_position(&self) -> DVec3 {
         self.position
     }
     
@@ -53,19 +233,34 @@ impl Camera {
         Quat::from_euler(glam::EulerRot::YXZ, self.yaw, self.pitch, self.roll)
     }
     
-    /// Get the view matrix for rendering
+    /// Get the view matrix in world space (used by picking and the gizmos)
     pub fn view_matrix(&self) -> Mat4 {
+        self.view_matrix_relative_to(DVec3::ZERO)
+    }
+
+    /// Get the view matrix for coordinates relative to `origin` (camera-relative rendering)
+    /// The camera's offset from the origin is taken in 64-bit, so it stays small and exact
+    pub fn view_matrix_relative_to(&self, origin: DVec3) -> Mat4 {
         let rotation = self.rotation();
+        let eye = (self.position - origin).as_vec3();
         let forward = rotation * Vec3::NEG_Z;
-        let target = self.position + forward;
         let up = rotation * Vec3::Y;
-        
-        Mat4::look_at_rh(self.position, target, up)
+
+        Mat4::look_at_rh(eye, eye + forward, up)
     }
     
     /// Get the projection matrix for rendering (near-range for regular objects)
     pub fn projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
-        let mut proj = Mat4::perspective_rh(self.fov, aspect_ratio, self.near_plane, self.far_plane);
+        let mut proj = match self.projection {
+            ProjectionMode::Perspective => {
+                Mat4::perspective_rh(self.fov, aspect_ratio, self.near_plane, self.far_plane)
+            }
+            ProjectionMode::Orthographic => {
+                let half_height = self.ortho_size;
+                let half_width = half_height * aspect_ratio;
+                Mat4::orthographic_rh(-half_width, half_width, -half_height, half_height, self.near_plane, self.far_plane)
+            }
+        };
         // Flip Y for Vulkan coordinate system
         proj.y_axis.y *= -1.0;
         proj
@@ -85,6 +280,71 @@ impl Camera {
         proj
     }
 
+    /// Get the projection mode
+    pub fn projection_mode(&self) -> ProjectionMode {
+        self.projection
+    }
+
+    /// Set the projection mode without moving the camera
+    pub fn set_projection_mode(&mut self, mode: ProjectionMode) {
+        self.projection = mode;
+    }
+
+    /// Get the orthographic half-height in world units
+    pub fn ortho_size(&self) -> f32 {
+        self.ortho_size
+    }
+
+    /// Set the orthographic half-height in world units
+    pub fn set_ortho_size(&mut self, size: f32) {
+        self.ortho_size = size.max(0.01);
+    }
+
+    /// Switch between perspective and orthographic while keeping `target` framed.
+    /// The target is projected onto the view axis, so only its depth is used.
+    pub fn toggle_projection(&mut self, target: Vec3) {
+        let forward = self.rotation() * Vec3::NEG_Z;
+        let half_fov_tan = (self.fov * 0.5).tan();
+        let distance = (target - self.position()).dot(forward).max(self.near_plane);
+
+        match self.projection {
+            ProjectionMode::Perspective => {
+                // Match the visible height at the target depth
+                self.set_ortho_size(distance * half_fov_tan);
+                self.projection = ProjectionMode::Orthographic;
+            }
+            ProjectionMode::Orthographic => {
+                // Back off so the perspective frustum covers the same height at the target
+                self.position += (forward * (distance - self.ortho_size / half_fov_tan)).as_dvec3();
+                self.projection = ProjectionMode::Perspective;
+            }
+        }
+        self.cancel_transition();
+    }
+
+    /// World units per unit of screen height at `point` (used to keep gizmos a constant size)
+    pub fn screen_scale_at(&self, point: Vec3) -> f32 {
+        match self.projection {
+            ProjectionMode::Perspective => (point.as_dvec3() - self.position).length() as f32,
+            ProjectionMode::Orthographic => self.ortho_size,
+        }
+    }
+
+    /// World units covered by one pixel at `point` for a viewport `viewport_height` pixels tall.
+    /// Perspective uses the depth along the view axis (at least the near plane, so points
+    /// beside or behind the camera stay finite); orthographic is the same everywhere
+    pub fn world_units_per_pixel(&self, point: Vec3, viewport_height: f32) -> f32 {
+        let half_height = match self.projection {
+            ProjectionMode::Perspective => {
+                let forward = (self.rotation() * Vec3::NEG_Z).as_dvec3();
+                let depth = ((point.as_dvec3() - self.position).dot(forward) as f32).max(self.near_plane);
+                depth * (self.fov * 0.5).tan()
+            }
+            ProjectionMode::Orthographic => self.ortho_size,
+        };
+        2.0 * half_height / viewport_height.max(1.0)
+    }
+
     /// Get field of view in radians
     pub fn fov(&self) -> f32 {
         self.fov
@@ -107,12 +367,36 @@ impl Camera {
     
     /// Set near clipping plane distance
     pub fn set_near_plane(&mut self, near: f32) {
-        self.near_plane = near;
+        self.set_clip_planes(near, self.far_plane);
     }
     
     /// Set far clipping plane distance
     pub fn set_far_plane(&mut self, far: f32) {
-        self.far_plane = far;
+        self.set_clip_planes(self.near_plane, far);
+    }
+
+    /// Set both clip planes (clamped to the allowed range, far kept at least twice near)
+    pub fn set_clip_planes(&mut self, near: f32, far: f32) {
+        self.near_plane = near.clamp(MIN_NEAR_PLANE, MAX_FAR_PLANE * 0.5);
+        self.far_plane = far.clamp(self.near_plane * 2.0, MAX_FAR_PLANE);
+    }
+
+    /// Get the depth buffer mode
+    pub fn depth_mode(&self) -> DepthMode {
+        self.depth_mode
+    }
+
+    pub fn set_depth_mode(&mut self, mode: DepthMode) {
+        self.depth_mode = mode;
+    }
+
+    /// Scale the shaders apply to log2(1 + view depth) so the far plane lands at depth 1,
+    /// or 0 when depth is standard (always the case for orthographic projection)
+    pub fn log_depth_coefficient(&self) -> f32 {
+        match (self.depth_mode, self.projection) {
+            (DepthMode::Logarithmic, ProjectionMode::Perspective) => 1.0 / (self.far_plane + 1.0).log2(),
+            _ => 0.0,
+        }
     }
     
     /// Set all projection parameters at once
@@ -124,25 +408,37 @@ impl Camera {
     
     /// Move the camera forward/backward in the direction it's facing
     pub fn move_forward(&mut self, amount: f32) {
+        self.cancel_transition();
         let rotation = self.rotation();
         let forward = rotation * Vec3::NEG_Z;
-        self.position += forward * amount;
+        self.position += (forward * amount).as_dvec3();
     }
     
     /// Move the camera right/left (strafe)
     pub fn move_right(&mut self, amount: f32) {
+        self.cancel_transition();
         let rotation = self.rotation();
         let right = rotation * Vec3::X;
-        self.position += right * amount;
+        self.position += (right * amount).as_dvec3();
     }
     
+    /// Move the camera along its own up axis (respects pitch and roll)
+    pub fn move_up_local(&mut self, amount: f32) {
+        self.cancel_transition();
+        let up = self.rotation() * Vec3::Y;
+        self.position += (up * amount).as_dvec3();
+    }
+
     /// Move the camera up/down in world space
     pub fn move_up(&mut self, amount: f32) {
-        self.position.y += amount;
+        self.cancel_transition();
+        self.position.y += amount as f64;
     }
     
     /// Rotate the camera (pitch and yaw) respecting current roll
     pub fn rotate(&mut self, pitch_delta: f32, yaw_delta: f32) {
+        self.cancel_transition();
+
         // Get current camera rotation as quaternion
         let current_rotation = self.rotation();
         
@@ -168,11 +464,17 @@ impl Camera {
     
     /// Roll the camera
     pub fn roll(&mut self, amount: f32) {
+        self.cancel_transition();
         self.roll += amount;
     }
     
     /// Set the camera position
     pub fn set_position(&mut self, position: Vec3) {
+        self.position = position.as_dvec3();
+    }
+
+    /// Set the camera position at full precision
+    pub fn set_world_position(&mut self, position: DVec3) {
         self.position = position;
     }
     
@@ -183,6 +485,220 @@ impl Camera {
         self.roll = roll;
     }
 
+    /// Place the camera exactly (used by track playback); cancels any bookmark transition
+    pub fn set_pose(&mut self, position: Vec3, rotation: Quat, fov: f32) {
+        self.cancel_transition();
+        self.position = position.as_dvec3();
+        self.fov = fov;
+        let (yaw, pitch, roll) = rotation.to_euler(glam::EulerRot::YXZ);
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.roll = roll;
+    }
+
+    /// Get the control mode
+    pub fn mode(&self) -> CameraMode {
+        self.mode
+    }
+
+    /// Set the control mode without moving the camera
+    pub fn set_mode(&mut self, mode: CameraMode) {
+        self.mode = mode;
+    }
+
+    /// Get the fly speed in units per second
+    pub fn move_speed(&self) -> f32 {
+        self.move_speed
+    }
+
+    /// Set the fly speed in units per second
+    pub fn set_move_speed(&mut self, speed: f32) {
+        self.move_speed = speed.clamp(MIN_MOVE_SPEED, MAX_MOVE_SPEED);
+    }
+
+    /// Whether camera moves stop at scene surfaces
+    pub fn collision_enabled(&self) -> bool {
+        self.collision
+    }
+
+    pub fn set_collision_enabled(&mut self, enabled: bool) {
+        self.collision = enabled;
+    }
+
+    /// Clearance kept between the camera and surfaces when collision is on
+    pub fn collision_radius(&self) -> f32 {
+        self.collision_radius
+    }
+
+    pub fn set_collision_radius(&mut self, radius: f32) {
+        self.collision_radius = radius.clamp(MIN_COLLISION_RADIUS, MAX_COLLISION_RADIUS);
+    }
+
+    /// Closest and farthest orbit zoom distances
+    pub fn orbit_distance_limits(&self) -> (f32, f32) {
+        (self.orbit_min_distance, self.orbit_max_distance)
+    }
+
+    /// Set the orbit zoom limits (clamped to the allowed range, max never below min)
+    pub fn set_orbit_distance_limits(&mut self, min: f32, max: f32) {
+        self.orbit_min_distance = min.clamp(MIN_ORBIT_DISTANCE, MAX_ORBIT_DISTANCE);
+        self.orbit_max_distance = max.clamp(self.orbit_min_distance, MAX_ORBIT_DISTANCE);
+    }
+
+    /// Whether zooming in at the closest orbit distance keeps flying forward
+    pub fn orbit_dolly_at_min(&self) -> bool {
+        self.orbit_dolly_at_min
+    }
+
+    pub fn set_orbit_dolly_at_min(&mut self, enabled: bool) {
+        self.orbit_dolly_at_min = enabled;
+    }
+
+    /// Fixed orbit pivot, if one is set
+    pub fn orbit_target(&self) -> Option<DVec3> {
+        self.orbit_target
+    }
+
+    /// Orbit around `target` instead of the selection until `clear_orbit_target`
+    pub fn set_orbit_target(&mut self, target: DVec3) {
+        self.orbit_target = Some(target);
+    }
+
+    /// Return to free orbit (around the selection)
+    pub fn clear_orbit_target(&mut self) {
+        self.orbit_target = None;
+    }
+
+    /// Move the orbit pivot to `target`, carrying the camera along so it keeps its offset.
+    /// The move is worked out in f64, so a pivot crossing the system at speed doesn't drift.
+    pub fn track_orbit_target(&mut self, target: DVec3) {
+        if let Some(previous) = self.orbit_target.replace(target) {
+            let offset = self.position - previous;
+            self.position = target + offset;
+        }
+    }
+
+    /// Orbit-mode wheel zoom: scale the distance to `target` by a constant factor per notch
+    /// (positive = closer), kept within the orbit limits. A camera already outside the limits
+    /// is never pushed away from where it is; zooming in at the minimum dollies forward if enabled.
+    pub fn zoom_orbit(&mut self, target: Vec3, wheel_steps: f32) {
+        let offset = (self.position - target.as_dvec3()).as_vec3();
+        let distance = offset.length();
+        if distance <= f32::EPSILON || wheel_steps == 0.0 {
+            return;
+        }
+
+        let scale = ORBIT_ZOOM_WHEEL_FACTOR.powf(wheel_steps);
+        let new_distance = if wheel_steps > 0.0 {
+            if self.orbit_dolly_at_min && distance <= self.orbit_min_distance * 1.001 {
+                // Keep approaching at the pace of the closest zoom step
+                let forward = self.rotation() * Vec3::NEG_Z;
+                self.position += (forward * (self.orbit_min_distance - self.orbit_min_distance / scale)).as_dvec3();
+                return;
+            }
+            (distance / scale).max(self.orbit_min_distance.min(distance))
+        } else {
+            (distance / scale).min(self.orbit_max_distance.max(distance))
+        };
+        self.position = target.as_dvec3() + (offset * (new_distance / distance)).as_dvec3();
+    }
+
+    /// Scale the fly speed by a number of mouse-wheel notches (positive = faster).
+    /// Multiplicative so the wheel is as useful at 0.5 units/s as at 5000.
+    pub fn scale_move_speed(&mut self, wheel_steps: f32) {
+        self.set_move_speed(self.move_speed * MOVE_SPEED_WHEEL_FACTOR.powf(wheel_steps));
+    }
+
+    /// Saved viewpoints in creation order
+    pub fn bookmarks(&self) -> &[CameraBookmark] {
+        &self.bookmarks
+    }
+
+    /// Save the current viewpoint under `name`, replacing a bookmark with the same name
+    pub fn save_bookmark(&mut self, name: &str) {
+        let bookmark = CameraBookmark {
+            name: name.to_string(),
+            position: self.position(),
+            pitch: self.pitch,
+            yaw: self.yaw,
+            roll: self.roll,
+            fov: self.fov,
+        };
+
+        match self.bookmarks.iter_mut().find(|b| b.name == name) {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+    }
+
+    /// Remove a bookmark by name
+    pub fn remove_bookmark(&mut self, name: &str) {
+        self.bookmarks.retain(|b| b.name != name);
+    }
+
+    /// Start a smooth move to the named bookmark. Returns false if it doesn't exist.
+    pub fn goto_bookmark(&mut self, name: &str) -> bool {
+        match self.bookmarks.iter().position(|b| b.name == name) {
+            Some(index) => self.goto_bookmark_index(index),
+            None => false,
+        }
+    }
+
+    /// Start a smooth move to the bookmark at `index`. Returns false if out of range.
+    pub fn goto_bookmark_index(&mut self, index: usize) -> bool {
+        let Some(bookmark) = self.bookmarks.get(index) else {
+            return false;
+        };
+
+        self.transition = Some(CameraTransition {
+            start_position: self.position,
+            start_rotation: self.rotation(),
+            start_fov: self.fov,
+            target_position: bookmark.position.as_dvec3(),
+            target_rotation: bookmark.rotation(),
+            target_fov: bookmark.fov,
+            progress: 0.0,
+        });
+        true
+    }
+
+    /// Whether a bookmark transition is in progress
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Stop the bookmark transition where it is
+    pub fn cancel_transition(&mut self) {
+        self.transition = None;
+    }
+
+    /// Advance the bookmark transition (ease-in-out position, slerp rotation)
+    pub fn update(&mut self, delta_time: f32) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+
+        transition.progress = (transition.progress + delta_time / BOOKMARK_TRANSITION_DURATION).min(1.0);
+        let t = transition.progress;
+        let eased_t = if t < 0.5 {
+            4.0 * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        };
+
+        self.position = transition.start_position.lerp(transition.target_position, eased_t as f64);
+        self.fov = transition.start_fov + (transition.target_fov - transition.start_fov) * eased_t;
+        let rotation = transition.start_rotation.slerp(transition.target_rotation, eased_t);
+        let (yaw, pitch, roll) = rotation.to_euler(glam::EulerRot::YXZ);
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.roll = roll;
+
+        if t >= 1.0 {
+            self.transition = None;
+        }
+    }
+
     /// Convert screen coordinates to a ray in world space for picking
     pub fn screen_to_ray(&self, screen_x: f32, screen_y: f32, viewport_width: f32, viewport_height: f32) -> (glam::DVec3, glam::DVec3) {
         // Convert screen coordinates to NDC (-1 to 1)
@@ -198,6 +714,14 @@ impl Camera {
         let inv_proj = proj.inverse();
         let inv_view = view.inverse();
 
+        if self.projection == ProjectionMode::Orthographic {
+            // Parallel rays: origin slides across the view plane, direction is the view axis
+            let view_point = inv_proj * Vec4::new(ndc_x, ndc_y, 0.0, 1.0);
+            let origin = inv_view * Vec4::new(view_point.x, view_point.y, 0.0, 1.0);
+            let direction = (inv_view * Vec4::new(0.0, 0.0, -1.0, 0.0)).truncate().normalize();
+            return (origin.truncate().as_dvec3(), direction.as_dvec3());
+        }
+
         // Convert NDC to view space
         let clip = Vec4::new(ndc_x, ndc_y, -1.0, 1.0);
         let eye_vec = inv_proj * clip;
@@ -208,7 +732,7 @@ impl Camera {
         let ray_dir = world.normalize();
 
         // Ray origin is camera position
-        let ray_origin = self.position.as_dvec3();
+        let ray_origin = self.position;
         let ray_dir_dvec3 = ray_dir.as_dvec3().normalize();
 
         (ray_origin, ray_dir_dvec3)
@@ -227,6 +751,17 @@ impl From<crate::config::CameraConfigData> for Camera {
         let mut camera = Self::new(data.position);
         camera.set_rotation(data.pitch, data.yaw, data.roll);
         camera.set_fov(data.fov.to_radians());
+        camera.set_clip_planes(data.near_plane, data.far_plane);
+        camera.depth_mode = data.depth_mode;
+        camera.projection = data.projection;
+        camera.set_ortho_size(data.ortho_size);
+        camera.mode = data.mode;
+        camera.set_move_speed(data.move_speed);
+        camera.collision = data.collision;
+        camera.set_collision_radius(data.collision_radius);
+        camera.set_orbit_distance_limits(data.orbit_min_distance, data.orbit_max_distance);
+        camera.orbit_dolly_at_min = data.orbit_dolly_at_min;
+        camera.bookmarks = data.bookmarks.into_iter().map(Into::into).collect();
         camera
     }
 }
@@ -234,13 +769,51 @@ impl From<crate::config::CameraConfigData> for Camera {
 impl From<&Camera> for crate::config::CameraConfigData {
     fn from(camera: &Camera) -> Self {
         Self {
-            position: camera.position,
+            position: camera.position(),
             pitch: camera.pitch,
             yaw: camera.yaw,
             roll: camera.roll,
-            move_speed: 5.0, // Default, would need to be stored in Camera if configurable
+            move_speed: camera.move_speed,
             mouse_sensitivity: 0.003, // Default
             fov: camera.fov.to_degrees(),
+            near_plane: camera.near_plane,
+            far_plane: camera.far_plane,
+            depth_mode: camera.depth_mode,
+            projection: camera.projection,
+            ortho_size: camera.ortho_size,
+            mode: camera.mode,
+            collision: camera.collision,
+            collision_radius: camera.collision_radius,
+            orbit_min_distance: camera.orbit_min_distance,
+            orbit_max_distance: camera.orbit_max_distance,
+            orbit_dolly_at_min: camera.orbit_dolly_at_min,
+            bookmarks: camera.bookmarks.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::config::CameraBookmarkData> for CameraBookmark {
+    fn from(data: crate::config::CameraBookmarkData) -> Self {
+        Self {
+            name: data.name,
+            position: data.position,
+            pitch: data.pitch,
+            yaw: data.yaw,
+            roll: data.roll,
+            fov: data.fov.to_radians(),
+        }
+    }
+}
+
+impl From<&CameraBookmark> for crate::config::CameraBookmarkData {
+    fn from(bookmark: &CameraBookmark) -> Self {
+        Self {
+            name: bookmark.name.clone(),
+            position: bookmark.position,
+            pitch: bookmark.pitch,
+            yaw: bookmark.yaw,
+            roll: bookmark.roll,
+            fov: bookmark.fov.to_degrees(),
         }
     }
 }
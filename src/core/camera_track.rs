@@ -0,0 +1,203 @@
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Seconds between a new keyframe and the previous last one
+pub const DEFAULT_KEYFRAME_SPACING: f32 = 2.0;
+
+/// Shortest gap kept between neighbouring keyframes, in seconds
+const MIN_KEYFRAME_GAP: f32 = 0.01;
+
+/// Camera pose at a point in time on a track
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraKeyframe {
+    pub position: Vec3,
+    pub rotation: Quat,
+    /// Field of view in degrees
+    pub fov: f32,
+    /// Seconds from the start of the track
+    pub time: f32,
+}
+
+/// Recorded camera flythrough (persisted in the scene file)
+///
+/// Keyframes are kept sorted by time. Playback follows a Catmull-Rom spline through
+/// the positions and slerps between rotations, so a handful of keyframes gives a
+/// smooth path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraTrack {
+    pub name: String,
+    #[serde(default)]
+    keyframes: Vec<CameraKeyframe>,
+}
+
+impl CameraTrack {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// Keyframes in playback order
+    pub fn keyframes(&self) -> &[CameraKeyframe] {
+        &self.keyframes
+    }
+
+    /// Time of the last keyframe
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |keyframe| keyframe.time)
+    }
+
+    /// Insert a keyframe at its time (after any keyframe with the same time)
+    pub fn add_keyframe(&mut self, keyframe: CameraKeyframe) -> usize {
+        let index = self.keyframes.partition_point(|existing| existing.time <= keyframe.time);
+        self.keyframes.insert(index, keyframe);
+        index
+    }
+
+    pub fn remove_keyframe(&mut self, index: usize) {
+        if index < self.keyframes.len() {
+            self.keyframes.remove(index);
+        }
+    }
+
+    /// Swap the poses of two keyframes; the times stay in place so the order changes
+    pub fn swap_keyframes(&mut self, a: usize, b: usize) {
+        if a >= self.keyframes.len() || b >= self.keyframes.len() {
+            return;
+        }
+        let (time_a, time_b) = (self.keyframes[a].time, self.keyframes[b].time);
+        self.keyframes.swap(a, b);
+        self.keyframes[a].time = time_a;
+        self.keyframes[b].time = time_b;
+    }
+
+    /// Move a keyframe in time, clamped between its neighbours so the order is kept
+    pub fn set_keyframe_time(&mut self, index: usize, time: f32) {
+        if index >= self.keyframes.len() {
+            return;
+        }
+        let min = if index > 0 { self.keyframes[index - 1].time + MIN_KEYFRAME_GAP } else { 0.0 };
+        let max = self.keyframes.get(index + 1).map_or(f32::MAX, |next| next.time - MIN_KEYFRAME_GAP);
+        self.keyframes[index].time = time.clamp(min, max.max(min));
+    }
+
+    /// Interpolated pose at `time` (clamped to the track), or None for an empty track
+    pub fn sample(&self, time: f32) -> Option<CameraKeyframe> {
+        let last = self.keyframes.len().checked_sub(1)?;
+        let time = time.clamp(0.0, self.duration());
+
+        // Segment containing `time`: keyframes[i] .. keyframes[i + 1]
+        let i = self.keyframes.partition_point(|keyframe| keyframe.time <= time).saturating_sub(1).min(last);
+        if i == last {
+            let mut keyframe = self.keyframes[last];
+            keyframe.time = time;
+            return Some(keyframe);
+        }
+
+        let k1 = &self.keyframes[i];
+        let k2 = &self.keyframes[i + 1];
+        let span = k2.time - k1.time;
+        let t = if span > 0.0 { (time - k1.time) / span } else { 1.0 };
+
+        Some(CameraKeyframe {
+            position: self.spline_position(i, t),
+            rotation: k1.rotation.slerp(k2.rotation, t),
+            fov: k1.fov + (k2.fov - k1.fov) * t,
+            time,
+        })
+    }
+
+    /// Catmull-Rom position between keyframes `i` and `i + 1` at local `t`
+    /// Tangents are finite differences over time, so unevenly spaced keyframes
+    /// keep a steady speed through each keyframe.
+    fn spline_position(&self, i: usize, t: f32) -> Vec3 {
+        let p1 = &self.keyframes[i];
+        let p2 = &self.keyframes[i + 1];
+        let span = p2.time - p1.time;
+
+        let tangent = |index: usize| {
+            let prev = &self.keyframes[index.saturating_sub(1)];
+            let next = &self.keyframes[(index + 1).min(self.keyframes.len() - 1)];
+            let dt = next.time - prev.time;
+            if dt > 0.0 {
+                (next.position - prev.position) / dt * span
+            } else {
+                Vec3::ZERO
+            }
+        };
+        let m1 = tangent(i);
+        let m2 = tangent(i + 1);
+
+        // Cubic Hermite basis
+        let t2 = t * t;
+        let t3 = t2 * t;
+        p1.position * (2.0 * t3 - 3.0 * t2 + 1.0)
+            + m1 * (t3 - 2.0 * t2 + t)
+            + p2.position * (-2.0 * t3 + 3.0 * t2)
+            + m2 * (t3 - t2)
+    }
+}
+
+/// Position of an in-progress track playback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraTrackPlayhead {
+    /// Index into the scene's camera tracks
+    pub track: usize,
+    /// Seconds since the start of the track
+    pub time: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(x: f32, time: f32) -> CameraKeyframe {
+        CameraKeyframe {
+            position: Vec3::new(x, 0.0, 0.0),
+            rotation: Quat::IDENTITY,
+            fov: 45.0,
+            time,
+        }
+    }
+
+    #[test]
+    fn test_sample_passes_through_keyframes() {
+        let mut track = CameraTrack::new("Flyby");
+        assert!(track.sample(0.0).is_none());
+
+        track.add_keyframe(keyframe(10.0, 4.0));
+        track.add_keyframe(keyframe(0.0, 0.0));
+        track.add_keyframe(keyframe(5.0, 2.0));
+        assert_eq!(track.duration(), 4.0);
+
+        for keyframe in track.keyframes().to_vec() {
+            let sample = track.sample(keyframe.time).unwrap();
+            assert!((sample.position - keyframe.position).length() < 1e-4);
+        }
+
+        // Evenly spaced points on a line stay on the line, at constant speed
+        let mid = track.sample(1.0).unwrap();
+        assert!((mid.position - Vec3::new(2.5, 0.0, 0.0)).length() < 1e-4);
+
+        // Clamped past either end
+        assert_eq!(track.sample(-1.0).unwrap().position, Vec3::ZERO);
+        assert_eq!(track.sample(99.0).unwrap().position, Vec3::new(10.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reorder_keeps_times() {
+        let mut track = CameraTrack::new("Flyby");
+        track.add_keyframe(keyframe(0.0, 0.0));
+        track.add_keyframe(keyframe(1.0, 2.0));
+        track.swap_keyframes(0, 1);
+
+        assert_eq!(track.keyframes()[0].position.x, 1.0);
+        assert_eq!(track.keyframes()[0].time, 0.0);
+        assert_eq!(track.keyframes()[1].time, 2.0);
+
+        // Times can't pass a neighbour
+        track.set_keyframe_time(0, 5.0);
+        assert!(track.keyframes()[0].time < track.keyframes()[1].time);
+    }
+}
@@ -0,0 +1,46 @@
+//! sRGB to linear conversion
+//!
+//! Color policy: every color picked in the UI or stored in a config or scene is sRGB
+//! (what the picker swatch shows). It is converted to linear where it enters a uniform
+//! buffer or push constant, shaders light in linear space, and the sRGB swapchain applies
+//! the final encode. imgui.frag decodes UI colors the same way so swatches match the scene.
+
+use glam::Vec3;
+
+/// Decode one sRGB channel (0-1) to linear light (IEC 61966-2-1 piecewise curve)
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Decode an sRGB color. Channels above 1 (HDR tints) follow the same curve.
+pub fn srgb_to_linear_rgb(color: Vec3) -> Vec3 {
+    Vec3::new(srgb_to_linear(color.x), srgb_to_linear(color.y), srgb_to_linear(color.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srgb_to_linear_reference_values() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert!((srgb_to_linear(1.0) - 1.0).abs() < 1e-6);
+        // Mid-gray in sRGB is about 21.4% linear light; ~0.735 sRGB is 50% linear
+        assert!((srgb_to_linear(0.5) - 0.214).abs() < 1e-3);
+        assert!((srgb_to_linear(0.7354) - 0.5).abs() < 1e-3);
+
+        // The linear toe and the power curve meet without a jump
+        let mut previous = 0.0;
+        for i in 1..=255 {
+            let value = srgb_to_linear(i as f32 / 255.0);
+            assert!(value > previous, "{}", i);
+            previous = value;
+        }
+        let color = srgb_to_linear_rgb(Vec3::new(0.0, 0.5, 1.0));
+        assert!(color.abs_diff_eq(Vec3::new(0.0, srgb_to_linear(0.5), 1.0), 1e-6));
+    }
+}
@@ -0,0 +1,570 @@
+use anyhow::{anyhow, bail, Context, Result};
+use ash::vk;
+use glam::{Vec3, Vec4};
+use std::path::Path;
+
+use crate::core::color::srgb_to_linear;
+use crate::core::ResourceManager;
+
+/// File names (without extension) of the six faces, in Vulkan layer order (+X, -X, +Y, -Y, +Z, -Z)
+pub const FACE_NAMES: [&str; 6] = ["px", "nx", "py", "ny", "pz", "nz"];
+
+/// Extensions tried for each face file
+const FACE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "tga", "hdr"];
+
+/// Largest face size produced from an equirectangular panorama
+const MAX_EQUIRECT_FACE_SIZE: u32 = 2048;
+
+/// Half-float RGBA: filterable on every Vulkan device and keeps HDR values
+const CUBEMAP_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+
+/// Six square faces of linear RGBA pixels, in Vulkan layer order
+pub struct CubemapFaces {
+    pub size: u32,
+    /// One `size * size` image per face, rows top to bottom
+    pub faces: Vec<Vec<[f32; 4]>>,
+}
+
+impl CubemapFaces {
+    /// Load a cubemap from a directory of face images (`px`, `nx`, `py`, `ny`, `pz`, `nz`)
+    /// or from a single equirectangular panorama (`.hdr`, `.png`, ...)
+    pub fn load(path: &str) -> Result<Self> {
+        let path = Path::new(path);
+        if path.is_dir() {
+            Self::load_faces(path)
+        } else {
+            let (width, height, pixels) = load_linear(path)?;
+            Ok(Self::from_equirect(width, height, &pixels))
+        }
+    }
+
+    /// 1x1 cubemap of a single color (bound while no cubemap is loaded)
+    pub fn solid(color: [f32; 4]) -> Self {
+        Self {
+            size: 1,
+            faces: vec![vec![color]; 6],
+        }
+    }
+
+    fn load_faces(dir: &Path) -> Result<Self> {
+        let mut size = 0;
+        let mut faces = Vec::with_capacity(FACE_NAMES.len());
+
+        for name in FACE_NAMES {
+            let file = FACE_EXTENSIONS
+                .iter()
+                .map(|ext| dir.join(format!("{}.{}", name, ext)))
+                .find(|file| file.is_file())
+                .ok_or_else(|| anyhow!("missing face '{}' in {}", name, dir.display()))?;
+
+            let (width, height, pixels) = load_linear(&file)?;
+            if width != height {
+                bail!("face {} is {}x{}, faces must be square", file.display(), width, height);
+            }
+            if size != 0 && width != size {
+                bail!("face {} is {}x{}, other faces are {}x{}", file.display(), width, height, size, size);
+            }
+            size = width;
+            faces.push(pixels);
+        }
+
+        Ok(Self { size, faces })
+    }
+
+    /// Resample a longitude/latitude panorama onto six faces
+    pub fn from_equirect(width: u32, height: u32, pixels: &[[f32; 4]]) -> Self {
+        let size = (height / 2).clamp(1, MAX_EQUIRECT_FACE_SIZE);
+        Self::from_fn(size, |direction| sample_equirect(width, height, pixels, direction))
+    }
+
+    /// Fill every texel from its (normalized) direction
+    pub fn from_fn(size: u32, mut texel: impl FnMut(Vec3) -> [f32; 4]) -> Self {
+        let faces = (0..FACE_NAMES.len())
+            .map(|face| {
+                let mut texels = Vec::with_capacity((size * size) as usize);
+                for y in 0..size {
+                    for x in 0..size {
+                        let (u, v) = texel_uv(size, x, y);
+                        texels.push(texel(face_direction(face, u, v)));
+                    }
+                }
+                texels
+            })
+            .collect();
+
+        Self { size, faces }
+    }
+
+    /// Direction and solid angle of every texel, face by face
+    pub fn texels(&self) -> impl Iterator<Item = (Vec3, f32, [f32; 4])> + '_ {
+        let size = self.size;
+        self.faces.iter().enumerate().flat_map(move |(face, texels)| {
+            texels.iter().enumerate().map(move |(i, &color)| {
+                let (u, v) = texel_uv(size, i as u32 % size, i as u32 / size);
+                (face_direction(face, u, v), texel_solid_angle(size, u, v), color)
+            })
+        })
+    }
+
+    /// Bilinear lookup in the direction `direction` (filtered within a face)
+    pub fn sample(&self, direction: Vec3) -> Vec4 {
+        let (face, u, v) = direction_to_face(direction);
+        let size = self.size;
+        let fx = ((u * 0.5 + 0.5) * size as f32 - 0.5).clamp(0.0, (size - 1) as f32);
+        let fy = ((v * 0.5 + 0.5) * size as f32 - 0.5).clamp(0.0, (size - 1) as f32);
+        let (tx, ty) = (fx - fx.floor(), fy - fy.floor());
+        let (x0, y0) = (fx.floor() as u32, fy.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(size - 1), (y0 + 1).min(size - 1));
+
+        let texels = &self.faces[face];
+        let texel = |x: u32, y: u32| Vec4::from_array(texels[(y * size + x) as usize]);
+        let top = texel(x0, y0).lerp(texel(x1, y0), tx);
+        let bottom = texel(x0, y1).lerp(texel(x1, y1), tx);
+        top.lerp(bottom, ty)
+    }
+
+    /// Box-filter down to at most `size` texels per side (unchanged if already that small)
+    pub fn downsample(&self, size: u32) -> Self {
+        let factor = (self.size / size.max(1)).max(1);
+        if factor == 1 {
+            return Self { size: self.size, faces: self.faces.clone() };
+        }
+
+        let new_size = self.size / factor;
+        let weight = 1.0 / (factor * factor) as f32;
+        let faces = self
+            .faces
+            .iter()
+            .map(|texels| {
+                let mut out = Vec::with_capacity((new_size * new_size) as usize);
+                for y in 0..new_size {
+                    for x in 0..new_size {
+                        let mut sum = Vec4::ZERO;
+                        for sy in 0..factor {
+                            for sx in 0..factor {
+                                let index = (y * factor + sy) * self.size + x * factor + sx;
+                                sum += Vec4::from_array(texels[index as usize]);
+                            }
+                        }
+                        out.push((sum * weight).to_array());
+                    }
+                }
+                out
+            })
+            .collect();
+
+        Self { size: new_size, faces }
+    }
+}
+
+/// Cube image on the GPU (skybox background and environment lighting)
+pub struct CubemapTexture {
+    pub image: vk::Image,
+    pub memory: vk::DeviceMemory,
+    pub view: vk::ImageView,
+}
+
+impl CubemapTexture {
+    /// Upload six faces into a device-local cube image
+    pub unsafe fn upload(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        queue: vk::Queue,
+        faces: &CubemapFaces,
+    ) -> Result<Self> {
+        Self::upload_mips(instance, physical_device, device, command_pool, queue, std::slice::from_ref(faces))
+    }
+
+    /// Upload a mip chain (`levels[0]` is the full size, each next level half as large)
+    pub unsafe fn upload_mips(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        queue: vk::Queue,
+        levels: &[CubemapFaces],
+    ) -> Result<Self> {
+        let size = levels[0].size;
+        let mip_levels = levels.len() as u32;
+        let layer_count = FACE_NAMES.len() as u32;
+
+        // Level by level, faces back to back within a level
+        let mut halves: Vec<u16> = Vec::new();
+        let mut level_offsets = Vec::with_capacity(levels.len());
+        for level in levels {
+            level_offsets.push((halves.len() * std::mem::size_of::<u16>()) as vk::DeviceSize);
+            halves.extend(level.faces.iter().flatten().flat_map(|texel| texel.map(f32_to_f16)));
+        }
+        let bytes: &[u8] = bytemuck::cast_slice(&halves);
+        let buffer_size = bytes.len() as vk::DeviceSize;
+
+        // 1. Copy the faces into a staging buffer
+        let (staging_buffer, staging_memory) = ResourceManager::create_buffer(
+            instance,
+            physical_device,
+            device,
+            buffer_size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        let data = device.map_memory(staging_memory, 0, buffer_size, vk::MemoryMapFlags::empty())?;
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, bytes.len());
+        device.unmap_memory(staging_memory);
+
+        // 2. Create the cube-compatible image
+        let image_info = vk::ImageCreateInfo::default()
+            .flags(vk::ImageCreateFlags::CUBE_COMPATIBLE)
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D { width: size, height: size, depth: 1 })
+            .mip_levels(mip_levels)
+            .array_layers(layer_count)
+            .format(CUBEMAP_FORMAT)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let image = device.create_image(&image_info, None)?;
+
+        let mem_requirements = device.get_image_memory_requirements(image);
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(ResourceManager::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        let memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, memory, 0)?;
+
+        // 3. Transition, copy all faces and make the image shader-readable
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: mip_levels,
+            base_array_layer: 0,
+            layer_count,
+        };
+
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(command_pool)
+            .command_buffer_count(1);
+        let command_buffer = device.allocate_command_buffers(&alloc_info)?[0];
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device.begin_command_buffer(command_buffer, &begin_info)?;
+
+        let to_transfer = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource_range)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_transfer],
+        );
+
+        let regions: Vec<_> = levels
+            .iter()
+            .zip(&level_offsets)
+            .enumerate()
+            .map(|(mip, (level, &offset))| {
+                vk::BufferImageCopy::default()
+                    .buffer_offset(offset)
+                    .buffer_row_length(0)
+                    .buffer_image_height(0)
+                    .image_subresource(vk::ImageSubresourceLayers {
+                        aspect_mask: vk::ImageAspectFlags::COLOR,
+                        mip_level: mip as u32,
+                        base_array_layer: 0,
+                        layer_count,
+                    })
+                    .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+                    .image_extent(vk::Extent3D { width: level.size, height: level.size, depth: 1 })
+            })
+            .collect();
+
+        device.cmd_copy_buffer_to_image(
+            command_buffer,
+            staging_buffer,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &regions,
+        );
+
+        let to_shader_read = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource_range)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_shader_read],
+        );
+
+        device.end_command_buffer(command_buffer)?;
+
+        let command_buffers = [command_buffer];
+        let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+        device.queue_submit(queue, &[submit_info], vk::Fence::null())?;
+        device.queue_wait_idle(queue)?;
+        device.free_command_buffers(command_pool, &command_buffers);
+
+        device.destroy_buffer(staging_buffer, None);
+        device.free_memory(staging_memory, None);
+
+        // 4. Create the cube view
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::CUBE)
+            .format(CUBEMAP_FORMAT)
+            .subresource_range(subresource_range);
+
+        let view = device.create_image_view(&view_info, None)?;
+
+        Ok(Self { image, memory, view })
+    }
+
+    pub unsafe fn destroy(&self, device: &ash::Device) {
+        device.destroy_image_view(self.view, None);
+        device.destroy_image(self.image, None);
+        device.free_memory(self.memory, None);
+    }
+}
+
+/// Linear, edge-clamped sampler for cube images with `mip_levels` levels
+pub unsafe fn create_sampler(device: &ash::Device, mip_levels: u32) -> Result<vk::Sampler> {
+    let sampler_info = vk::SamplerCreateInfo::default()
+        .mag_filter(vk::Filter::LINEAR)
+        .min_filter(vk::Filter::LINEAR)
+        .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+        .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+        .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+        .anisotropy_enable(false)
+        .max_anisotropy(1.0)
+        .border_color(vk::BorderColor::INT_OPAQUE_BLACK)
+        .unnormalized_coordinates(false)
+        .compare_enable(false)
+        .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+        .min_lod(0.0)
+        .max_lod(mip_levels.saturating_sub(1) as f32);
+
+    Ok(device.create_sampler(&sampler_info, None)?)
+}
+
+/// Decode an image into linear RGBA (8/16-bit images are treated as sRGB, float images as linear)
+fn load_linear(path: &Path) -> Result<(u32, u32, Vec<[f32; 4]>)> {
+    let image = image::open(path).with_context(|| format!("failed to load {}", path.display()))?;
+    let is_linear = matches!(
+        image,
+        image::DynamicImage::ImageRgb32F(_) | image::DynamicImage::ImageRgba32F(_)
+    );
+
+    let rgba = image.to_rgba32f();
+    let (width, height) = rgba.dimensions();
+    let pixels = rgba
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            if is_linear {
+                [r, g, b, a]
+            } else {
+                [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+            }
+        })
+        .collect();
+
+    Ok((width, height, pixels))
+}
+
+/// Center of texel (x, y) in face coordinates [-1, 1]
+fn texel_uv(size: u32, x: u32, y: u32) -> (f32, f32) {
+    (
+        (x as f32 + 0.5) / size as f32 * 2.0 - 1.0,
+        (y as f32 + 0.5) / size as f32 * 2.0 - 1.0,
+    )
+}
+
+/// Exact area of the texel centered at (u, v) projected onto the unit sphere
+fn texel_solid_angle(size: u32, u: f32, v: f32) -> f32 {
+    // Solid angle of the face rectangle from its center to (x, y)
+    let area = |x: f32, y: f32| (x * y).atan2((x * x + y * y + 1.0).sqrt());
+    let half = 1.0 / size as f32;
+    let (u0, u1, v0, v1) = (u - half, u + half, v - half, v + half);
+    area(u0, v0) - area(u0, v1) - area(u1, v0) + area(u1, v1)
+}
+
+/// Face and face coordinates (u, v) in [-1, 1] hit by `direction` (inverse of `face_direction`)
+fn direction_to_face(direction: Vec3) -> (usize, f32, f32) {
+    let abs = direction.abs();
+    if abs.x >= abs.y && abs.x >= abs.z {
+        let ma = abs.x.max(f32::EPSILON);
+        if direction.x > 0.0 {
+            (0, -direction.z / ma, -direction.y / ma)
+        } else {
+            (1, direction.z / ma, -direction.y / ma)
+        }
+    } else if abs.y >= abs.z {
+        let ma = abs.y.max(f32::EPSILON);
+        if direction.y > 0.0 {
+            (2, direction.x / ma, direction.z / ma)
+        } else {
+            (3, direction.x / ma, -direction.z / ma)
+        }
+    } else {
+        let ma = abs.z.max(f32::EPSILON);
+        if direction.z > 0.0 {
+            (4, direction.x / ma, -direction.y / ma)
+        } else {
+            (5, -direction.x / ma, -direction.y / ma)
+        }
+    }
+}
+
+/// Direction through face texel (u, v) in [-1, 1], per the Vulkan cube face table
+fn face_direction(face: usize, u: f32, v: f32) -> Vec3 {
+    let direction = match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    };
+    direction.normalize()
+}
+
+/// Bilinear panorama lookup; -Z is the center of the image and +Y the top row
+fn sample_equirect(width: u32, height: u32, pixels: &[[f32; 4]], direction: Vec3) -> [f32; 4] {
+    let u = 0.5 + direction.x.atan2(-direction.z) / std::f32::consts::TAU;
+    let v = direction.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI;
+
+    let fx = u * width as f32 - 0.5;
+    let fy = (v * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+    let (tx, ty) = (fx - fx.floor(), fy - fy.floor());
+
+    // Longitude wraps around, latitude clamps at the poles
+    let x0 = (fx.floor() as i64).rem_euclid(width as i64) as u32;
+    let x1 = (x0 + 1) % width;
+    let y0 = fy.floor() as u32;
+    let y1 = (y0 + 1).min(height - 1);
+
+    let texel = |x: u32, y: u32| Vec4::from_array(pixels[(y * width + x) as usize]);
+    let top = texel(x0, y0).lerp(texel(x1, y0), tx);
+    let bottom = texel(x0, y1).lerp(texel(x1, y1), tx);
+    top.lerp(bottom, ty).to_array()
+}
+
+/// IEEE half-float bits (truncating; values past the half range clamp to its largest finite value)
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Infinity stays infinity, NaN stays NaN
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        return sign | 0x7bff;
+    }
+    if exponent <= 0 {
+        if exponent < -10 {
+            return sign;
+        }
+        // Subnormal half: shift the implicit leading one into the mantissa
+        let mantissa = (mantissa | 0x80_0000) >> (1 - exponent);
+        return sign | (mantissa >> 13) as u16;
+    }
+
+    sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_to_f16() {
+        assert_eq!(f32_to_f16(0.0), 0x0000);
+        assert_eq!(f32_to_f16(1.0), 0x3c00);
+        assert_eq!(f32_to_f16(0.5), 0x3800);
+        assert_eq!(f32_to_f16(-2.0), 0xc000);
+        assert_eq!(f32_to_f16(65504.0), 0x7bff);
+        // Bright HDR values (e.g. a sun) clamp instead of turning into infinity
+        assert_eq!(f32_to_f16(1.0e6), 0x7bff);
+        // Smallest half subnormal
+        assert_eq!(f32_to_f16(2.0f32.powi(-24)), 0x0001);
+    }
+
+    #[test]
+    fn test_equirect_top_and_bottom() {
+        // 8x4 panorama: upper half red, lower half blue
+        let (red, blue) = ([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]);
+        let pixels: Vec<[f32; 4]> = (0..4)
+            .flat_map(|y| std::iter::repeat_n(if y < 2 { red } else { blue }, 8))
+            .collect();
+
+        let cubemap = CubemapFaces::from_equirect(8, 4, &pixels);
+        assert_eq!(cubemap.size, 2);
+        assert_eq!(cubemap.faces.len(), 6);
+        assert!(cubemap.faces[2].iter().all(|texel| *texel == red));
+        assert!(cubemap.faces[3].iter().all(|texel| *texel == blue));
+    }
+
+    #[test]
+    fn test_face_centers() {
+        let axes = [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::NEG_Y, Vec3::Z, Vec3::NEG_Z];
+        for (face, axis) in axes.into_iter().enumerate() {
+            assert!((face_direction(face, 0.0, 0.0) - axis).length() < 1e-6);
+        }
+        assert!(CubemapFaces::load("does/not/exist.hdr").is_err());
+    }
+
+    #[test]
+    fn test_sample_round_trips_face_texels() {
+        let cubemap = CubemapFaces::from_fn(4, |direction| [direction.x, direction.y, direction.z, 1.0]);
+        for (direction, _, color) in cubemap.texels() {
+            assert!((cubemap.sample(direction) - Vec4::from_array(color)).length() < 1e-5);
+        }
+
+        // The texels tile the sphere
+        let total: f32 = cubemap.texels().map(|(_, solid_angle, _)| solid_angle).sum();
+        assert!((total - 4.0 * std::f32::consts::PI).abs() < 1e-3);
+
+        let half = cubemap.downsample(2);
+        assert_eq!(half.size, 2);
+        assert_eq!(cubemap.downsample(8).size, 4);
+    }
+}
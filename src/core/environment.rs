@@ -0,0 +1,328 @@
+use anyhow::Result;
+use ash::vk;
+use glam::{Vec3, Vec4};
+
+use crate::core::{CubemapFaces, CubemapTexture};
+use crate::game::{SkyboxConfig, SkyboxMode};
+
+/// Face size the sky is captured at (and the prefiltered map's sharpest level)
+const ENVIRONMENT_SIZE: u32 = 64;
+
+/// Face size of the diffuse irradiance map (it's very smooth)
+const IRRADIANCE_SIZE: u32 = 16;
+
+/// Prefiltered levels from roughness 0 (64x64) to roughness 1 (4x4); mesh.frag reads the count
+/// back with textureQueryLevels
+pub const PREFILTER_MIP_LEVELS: u32 = 5;
+
+/// GGX samples per prefiltered texel
+const PREFILTER_SAMPLES: u32 = 64;
+
+/// Skybox settings that change the captured environment (star settings don't: stars are
+/// single pixels at capture resolution and average out)
+#[derive(Debug, Clone, PartialEq)]
+struct EnvironmentKey {
+    mode: SkyboxMode,
+    cubemap_path: String,
+    nebula_primary_color: Vec3,
+    nebula_secondary_color: Vec3,
+    nebula_intensity: f32,
+    background_brightness: f32,
+}
+
+impl From<&SkyboxConfig> for EnvironmentKey {
+    fn from(config: &SkyboxConfig) -> Self {
+        Self {
+            mode: config.mode,
+            cubemap_path: config.cubemap_path.trim().to_string(),
+            nebula_primary_color: config.nebula_primary_color,
+            nebula_secondary_color: config.nebula_secondary_color,
+            nebula_intensity: config.nebula_intensity,
+            background_brightness: config.background_brightness,
+        }
+    }
+}
+
+/// Image-based lighting precomputed on the CPU from a sky cubemap
+pub struct EnvironmentMaps {
+    /// Cosine-weighted irradiance divided by pi, so diffuse = albedo * irradiance
+    pub irradiance: CubemapFaces,
+    /// GGX-prefiltered radiance, one level per roughness step
+    pub prefiltered: Vec<CubemapFaces>,
+}
+
+impl EnvironmentMaps {
+    pub fn from_sky(sky: &CubemapFaces) -> Self {
+        let sky = sky.downsample(ENVIRONMENT_SIZE);
+
+        // Irradiance through 9 spherical harmonics (exact enough for a Lambertian lobe)
+        let sh = project_sh9(&sky);
+        let irradiance = CubemapFaces::from_fn(IRRADIANCE_SIZE, |normal| {
+            (irradiance_sh9(&sh, normal) / std::f32::consts::PI).max(Vec3::ZERO).extend(1.0).to_array()
+        });
+
+        let prefiltered = (0..PREFILTER_MIP_LEVELS)
+            .map(|level| {
+                let size = (sky.size >> level).max(1);
+                let roughness = level as f32 / (PREFILTER_MIP_LEVELS - 1) as f32;
+                // Lower-resolution source for rougher levels keeps the few samples from aliasing
+                let source = sky.downsample(size);
+                if level == 0 {
+                    source
+                } else {
+                    CubemapFaces::from_fn(size, |direction| prefilter_ggx(&source, direction, roughness))
+                }
+            })
+            .collect();
+
+        Self { irradiance, prefiltered }
+    }
+}
+
+/// Procedural sky (background plus nebula tint, as in skybox.frag) captured into a cubemap
+pub fn capture_procedural_sky(config: &SkyboxConfig) -> CubemapFaces {
+    CubemapFaces::from_fn(ENVIRONMENT_SIZE, |direction| {
+        let nebula_factor = direction.y.abs() * 0.3 + 0.2;
+        let nebula_color = config
+            .nebula_primary_color
+            .lerp(config.nebula_secondary_color, direction.x * 0.5 + 0.5);
+        let color = Vec3::splat(config.background_brightness)
+            + nebula_color * nebula_factor * config.nebula_intensity;
+        color.extend(1.0).to_array()
+    })
+}
+
+/// GPU irradiance and prefiltered environment cubemaps (bindings 4 and 5 of the mesh descriptor set)
+///
+/// Recomputed lazily by `update` when the skybox settings that affect it change.
+pub struct EnvironmentLighting {
+    irradiance: CubemapTexture,
+    prefiltered: CubemapTexture,
+    sampler: vk::Sampler,
+    key: Option<EnvironmentKey>,
+}
+
+impl EnvironmentLighting {
+    /// Black placeholder maps (no environment light) until the first `update`
+    pub unsafe fn new(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        queue: vk::Queue,
+    ) -> Result<Self> {
+        let black = CubemapFaces::solid([0.0, 0.0, 0.0, 1.0]);
+        Ok(Self {
+            irradiance: CubemapTexture::upload(instance, physical_device, device, command_pool, queue, &black)?,
+            prefiltered: CubemapTexture::upload(instance, physical_device, device, command_pool, queue, &black)?,
+            sampler: crate::core::cubemap::create_sampler(device, PREFILTER_MIP_LEVELS)?,
+            key: None,
+        })
+    }
+
+    /// Recompute the maps if the skybox changed since the last call
+    /// Returns true when the images were replaced and the descriptors must be rewritten.
+    pub unsafe fn update(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        queue: vk::Queue,
+        config: &SkyboxConfig,
+    ) -> Result<bool> {
+        let key = EnvironmentKey::from(config);
+        if self.key.as_ref() == Some(&key) {
+            return Ok(false);
+        }
+        self.key = Some(key);
+
+        // Same source as the skybox pass: the cubemap if it loads, else the procedural sky
+        // (the skybox pass reports load failures)
+        let sky = match config.mode {
+            SkyboxMode::Cubemap if !config.cubemap_path.trim().is_empty() => {
+                CubemapFaces::load(config.cubemap_path.trim()).unwrap_or_else(|_| capture_procedural_sky(config))
+            }
+            _ => capture_procedural_sky(config),
+        };
+        let maps = EnvironmentMaps::from_sky(&sky);
+
+        let irradiance = CubemapTexture::upload(instance, physical_device, device, command_pool, queue, &maps.irradiance)?;
+        let prefiltered = CubemapTexture::upload_mips(instance, physical_device, device, command_pool, queue, &maps.prefiltered)?;
+
+        // The old maps may still be referenced by frames in flight
+        device.device_wait_idle()?;
+        self.irradiance.destroy(device);
+        self.prefiltered.destroy(device);
+        self.irradiance = irradiance;
+        self.prefiltered = prefiltered;
+
+        Ok(true)
+    }
+
+    /// Point bindings 4 (irradiance) and 5 (prefiltered environment) of `descriptor_sets` at the maps
+    pub unsafe fn write_descriptors(&self, device: &ash::Device, descriptor_sets: &[vk::DescriptorSet]) {
+        let irradiance_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(self.irradiance.view)
+            .sampler(self.sampler);
+        let prefiltered_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(self.prefiltered.view)
+            .sampler(self.sampler);
+
+        for &descriptor_set in descriptor_sets {
+            let descriptor_writes = [
+                vk::WriteDescriptorSet::default()
+                    .dst_set(descriptor_set)
+                    .dst_binding(4)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&irradiance_info)),
+                vk::WriteDescriptorSet::default()
+                    .dst_set(descriptor_set)
+                    .dst_binding(5)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&prefiltered_info)),
+            ];
+
+            device.update_descriptor_sets(&descriptor_writes, &[]);
+        }
+    }
+
+    pub unsafe fn cleanup(&self, device: &ash::Device) {
+        self.irradiance.destroy(device);
+        self.prefiltered.destroy(device);
+        device.destroy_sampler(self.sampler, None);
+    }
+}
+
+/// Project a cubemap's radiance onto the first 9 real spherical harmonics
+fn project_sh9(sky: &CubemapFaces) -> [Vec3; 9] {
+    let mut coefficients = [Vec3::ZERO; 9];
+    for (direction, solid_angle, color) in sky.texels() {
+        let radiance = Vec3::new(color[0], color[1], color[2]) * solid_angle;
+        for (coefficient, basis) in coefficients.iter_mut().zip(sh9_basis(direction)) {
+            *coefficient += radiance * basis;
+        }
+    }
+    coefficients
+}
+
+/// Irradiance at `normal` from SH9 radiance (Ramamoorthi & Hanrahan cosine-lobe convolution)
+fn irradiance_sh9(coefficients: &[Vec3; 9], normal: Vec3) -> Vec3 {
+    use std::f32::consts::PI;
+    const BAND_SCALE: [f32; 3] = [PI, 2.0 * PI / 3.0, PI / 4.0];
+
+    sh9_basis(normal)
+        .iter()
+        .zip(coefficients)
+        .enumerate()
+        .map(|(i, (basis, coefficient))| {
+            let band = match i {
+                0 => 0,
+                1..=3 => 1,
+                _ => 2,
+            };
+            *coefficient * *basis * BAND_SCALE[band]
+        })
+        .sum()
+}
+
+fn sh9_basis(d: Vec3) -> [f32; 9] {
+    [
+        0.282095,
+        0.488603 * d.y,
+        0.488603 * d.z,
+        0.488603 * d.x,
+        1.092548 * d.x * d.y,
+        1.092548 * d.y * d.z,
+        0.315392 * (3.0 * d.z * d.z - 1.0),
+        1.092548 * d.x * d.z,
+        0.546274 * (d.x * d.x - d.y * d.y),
+    ]
+}
+
+/// Radiance around `normal` convolved with the GGX lobe of `roughness` (view = normal)
+fn prefilter_ggx(source: &CubemapFaces, normal: Vec3, roughness: f32) -> [f32; 4] {
+    let alpha = roughness * roughness;
+    let (tangent, bitangent) = normal.any_orthonormal_pair();
+
+    let mut sum = Vec4::ZERO;
+    let mut weight = 0.0;
+    for i in 0..PREFILTER_SAMPLES {
+        let (xi_x, xi_y) = hammersley(i, PREFILTER_SAMPLES);
+
+        // GGX half vector around the normal
+        let phi = std::f32::consts::TAU * xi_x;
+        let cos_theta = ((1.0 - xi_y) / (1.0 + (alpha * alpha - 1.0) * xi_y)).sqrt();
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let half = (tangent * (phi.cos() * sin_theta) + bitangent * (phi.sin() * sin_theta) + normal * cos_theta)
+            .normalize();
+
+        let light = 2.0 * normal.dot(half) * half - normal;
+        let n_dot_l = normal.dot(light);
+        if n_dot_l > 0.0 {
+            sum += source.sample(light) * n_dot_l;
+            weight += n_dot_l;
+        }
+    }
+
+    if weight > 0.0 {
+        (sum / weight).to_array()
+    } else {
+        source.sample(normal).to_array()
+    }
+}
+
+/// Low-discrepancy point `i` of `count` in [0, 1)^2
+fn hammersley(i: u32, count: u32) -> (f32, f32) {
+    (i as f32 / count as f32, (i.reverse_bits() as f64 / 4_294_967_296.0) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_sky_lights_evenly() {
+        // Under a uniform sky every normal and roughness sees the sky color
+        let sky = CubemapFaces::from_fn(16, |_| [0.5, 0.25, 1.0, 1.0]);
+        let maps = EnvironmentMaps::from_sky(&sky);
+
+        for (_, _, color) in maps.irradiance.texels() {
+            assert!((Vec3::from_slice(&color) - Vec3::new(0.5, 0.25, 1.0)).length() < 0.02);
+        }
+        for level in &maps.prefiltered {
+            for (_, _, color) in level.texels() {
+                assert!((Vec3::from_slice(&color) - Vec3::new(0.5, 0.25, 1.0)).length() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_irradiance_follows_bright_hemisphere() {
+        // Lit from above only: up-facing normals get the most light, down-facing almost none
+        let sky = CubemapFaces::from_fn(32, |direction| if direction.y > 0.0 { [1.0; 4] } else { [0.0, 0.0, 0.0, 1.0] });
+        let maps = EnvironmentMaps::from_sky(&sky);
+
+        let up = maps.irradiance.sample(Vec3::Y);
+        let side = maps.irradiance.sample(Vec3::X);
+        let down = maps.irradiance.sample(Vec3::NEG_Y);
+        assert!(up.x > 0.9 && up.x < 1.1);
+        assert!((side.x - 0.5).abs() < 0.1);
+        assert!(down.x < 0.1);
+        assert_eq!(maps.prefiltered.len(), PREFILTER_MIP_LEVELS as usize);
+    }
+
+    #[test]
+    fn test_procedural_capture_key_ignores_stars() {
+        let mut config = SkyboxConfig::default();
+        let key = EnvironmentKey::from(&config);
+        config.star_density += 1.0;
+        assert_eq!(EnvironmentKey::from(&config), key);
+        config.nebula_intensity += 1.0;
+        assert_ne!(EnvironmentKey::from(&config), key);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::core::DrawStats;
+
+/// Number of frames kept for the frame-time graph
+pub const FRAME_HISTORY_LEN: usize = 120;
+
+/// GPU vertex + index buffer memory held by loaded meshes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeshMemory {
+    /// Imported meshes, LOD levels included
+    pub custom_meshes: usize,
+    pub custom_bytes: u64,
+    /// Cube and built-in primitives shared by every scene
+    pub builtin_bytes: u64,
+}
+
+/// Rolling frame timings and last-frame draw statistics for the stats overlay
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    /// Ring buffer of frame times in milliseconds
+    frame_times: [f32; FRAME_HISTORY_LEN],
+    /// Next slot to write in the ring buffer
+    next: usize,
+    /// Number of valid samples (saturates at FRAME_HISTORY_LEN)
+    count: usize,
+    /// Draw calls and triangles recorded last frame
+    pub draw_stats: DrawStats,
+    /// Triangles in all shown scene meshes, culled or not
+    pub scene_triangles: u64,
+    /// Mesh buffer memory (for the Scene Statistics panel)
+    pub mesh_memory: MeshMemory,
+    /// CPU recording time per pass last frame (pass name, milliseconds)
+    pub pass_timings: Vec<(String, f32)>,
+}
+
+impl FrameStats {
+    pub fn new() -> Self {
+        Self {
+            frame_times: [0.0; FRAME_HISTORY_LEN],
+            next: 0,
+            count: 0,
+            draw_stats: DrawStats::default(),
+            scene_triangles: 0,
+            mesh_memory: MeshMemory::default(),
+            pass_timings: Vec::new(),
+        }
+    }
+
+    /// Record the duration of a frame in milliseconds
+    pub fn push_frame_time(&mut self, frame_time_ms: f32) {
+        self.frame_times[self.next] = frame_time_ms;
+        self.next = (self.next + 1) % FRAME_HISTORY_LEN;
+        self.count = (self.count + 1).min(FRAME_HISTORY_LEN);
+    }
+
+    /// Frame times oldest first (for plotting)
+    pub fn frame_times(&self) -> Vec<f32> {
+        let start = (self.next + FRAME_HISTORY_LEN - self.count) % FRAME_HISTORY_LEN;
+        (0..self.count)
+            .map(|i| self.frame_times[(start + i) % FRAME_HISTORY_LEN])
+            .collect()
+    }
+
+    /// Most recent frame time in milliseconds
+    pub fn latest_frame_time(&self) -> f32 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.frame_times[(self.next + FRAME_HISTORY_LEN - 1) % FRAME_HISTORY_LEN]
+    }
+
+    /// Average frame time over the history in milliseconds
+    pub fn average_frame_time(&self) -> f32 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.frame_times().iter().sum::<f32>() / self.count as f32
+    }
+
+    /// Frames per second based on the average frame time
+    pub fn fps(&self) -> f32 {
+        let average = self.average_frame_time();
+        if average > 0.0 {
+            1000.0 / average
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_times_oldest_first() {
+        let mut stats = FrameStats::new();
+        stats.push_frame_time(1.0);
+        stats.push_frame_time(2.0);
+        stats.push_frame_time(3.0);
+
+        assert_eq!(stats.frame_times(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(stats.latest_frame_time(), 3.0);
+        assert_eq!(stats.average_frame_time(), 2.0);
+    }
+
+    #[test]
+    fn test_ring_buffer_wraps() {
+        let mut stats = FrameStats::new();
+        for i in 0..FRAME_HISTORY_LEN + 10 {
+            stats.push_frame_time(i as f32);
+        }
+
+        let times = stats.frame_times();
+        assert_eq!(times.len(), FRAME_HISTORY_LEN);
+        assert_eq!(times[0], 10.0);
+        assert_eq!(*times.last().unwrap(), (FRAME_HISTORY_LEN + 9) as f32);
+        assert_eq!(stats.latest_frame_time(), (FRAME_HISTORY_LEN + 9) as f32);
+    }
+}
@@ -0,0 +1,82 @@
+use glam::{Mat4, Vec3, Vec4};
+
+/// View frustum as six planes (left, right, bottom, top, near, far) pointing inwards
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extract frustum planes from a combined view-projection matrix (Vulkan 0..1 depth)
+    pub fn from_matrix(view_proj: Mat4) -> Self {
+        let row0 = view_proj.row(0);
+        let row1 = view_proj.row(1);
+        let row2 = view_proj.row(2);
+        let row3 = view_proj.row(3);
+
+        let planes = [
+            row3 + row0, // Left
+            row3 - row0, // Right
+            row3 + row1, // Bottom (top after Vulkan Y flip, order doesn't matter)
+            row3 - row1, // Top
+            row2,        // Near (depth >= 0)
+            row3 - row2, // Far
+        ]
+        .map(|plane| plane / plane.truncate().length());
+
+        Self { planes }
+    }
+
+    /// Whether an axis-aligned box is at least partially inside the frustum
+    pub fn intersects_aabb(&self, min: Vec3, max: Vec3) -> bool {
+        self.planes.iter().all(|plane| {
+            // Test the box corner furthest along the plane normal
+            let normal = plane.truncate();
+            let positive = Vec3::select(normal.cmpge(Vec3::ZERO), max, min);
+            normal.dot(positive) + plane.w >= 0.0
+        })
+    }
+}
+
+/// Transform a local-space AABB by a model matrix, returning the enclosing world-space AABB
+pub fn transform_aabb(model: Mat4, min: Vec3, max: Vec3) -> (Vec3, Vec3) {
+    let center = (min + max) * 0.5;
+    let extents = (max - min) * 0.5;
+
+    let world_center = model.transform_point3(center);
+    let world_extents = model.x_axis.truncate().abs() * extents.x
+        + model.y_axis.truncate().abs() * extents.y
+        + model.z_axis.truncate().abs() * extents.z;
+
+    (world_center - world_extents, world_center + world_extents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_frustum() -> Frustum {
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let proj = Mat4::perspective_rh(70f32.to_radians(), 1.0, 0.1, 100.0);
+        Frustum::from_matrix(proj * view)
+    }
+
+    #[test]
+    fn test_aabb_in_front_is_visible() {
+        let frustum = test_frustum();
+        assert!(frustum.intersects_aabb(Vec3::new(-1.0, -1.0, -11.0), Vec3::new(1.0, 1.0, -9.0)));
+        // Straddling the left plane still counts as visible
+        assert!(frustum.intersects_aabb(Vec3::new(-20.0, -1.0, -11.0), Vec3::new(-5.0, 1.0, -9.0)));
+    }
+
+    #[test]
+    fn test_aabb_outside_is_culled() {
+        let frustum = test_frustum();
+        // Behind the camera
+        assert!(!frustum.intersects_aabb(Vec3::new(-1.0, -1.0, 9.0), Vec3::new(1.0, 1.0, 11.0)));
+        // Beyond the far plane
+        assert!(!frustum.intersects_aabb(Vec3::new(-1.0, -1.0, -210.0), Vec3::new(1.0, 1.0, -200.0)));
+        // Far off to the side
+        assert!(!frustum.intersects_aabb(Vec3::new(50.0, -1.0, -11.0), Vec3::new(60.0, 1.0, -9.0)));
+    }
+}
@@ -20,11 +20,78 @@ impl Default for DirectionalLight {
     }
 }
 
+/// Most directional lights shaded at once (key/fill/rim); the first one casts shadows
+pub const MAX_DIRECTIONAL_LIGHTS: usize = 3;
+
+/// Capacity of the per-frame point light storage buffer; lights past it are ignored.
+/// mesh.frag sizes its loop from `point_light_count`, so raising this only grows the
+/// buffer (32 bytes per light).
+pub const MAX_POINT_LIGHTS: usize = 256;
+
+/// Editable directional light properties stored on a scene object (direction comes from its rotation)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DirectionalLightSettings {
+    pub color: Vec3,
+    pub intensity: f32,
+}
+
+impl DirectionalLightSettings {
+    /// Combine with a direction and the scene's shadow/ambient color to get a renderable light
+    pub fn to_light(self, direction: Vec3, shadow_color: Vec3) -> DirectionalLight {
+        DirectionalLight {
+            direction,
+            color: self.color,
+            intensity: self.intensity,
+            shadow_color,
+        }
+    }
+}
+
+impl Default for DirectionalLightSettings {
+    fn default() -> Self {
+        Self {
+            color: Vec3::new(1.0, 1.0, 1.0),
+            intensity: 1.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PointLight {
     pub position: Vec3,
     pub color: Vec3,
     pub intensity: f32,
+    pub radius: f32, // Distance at which the light fades to zero
+}
+
+/// Editable point light properties stored on a scene object (position comes from its transform)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PointLightSettings {
+    pub color: Vec3,
+    pub intensity: f32,
+    pub radius: f32,
+}
+
+impl PointLightSettings {
+    /// Combine with a world position to get a renderable light
+    pub fn to_light(self, position: Vec3) -> PointLight {
+        PointLight {
+            position,
+            color: self.color,
+            intensity: self.intensity,
+            radius: self.radius,
+        }
+    }
+}
+
+impl Default for PointLightSettings {
+    fn default() -> Self {
+        Self {
+            color: Vec3::new(1.0, 0.9, 0.8),
+            intensity: 5.0,
+            radius: 10.0,
+        }
+    }
 }
 
 pub struct LightingData {
@@ -1,16 +1,34 @@
 pub mod vulkan_context;
 pub mod resource_manager;
 pub mod camera;
+pub mod camera_track;
 pub mod swapchain;
 pub mod renderer;
 pub mod lighting;
 pub mod render_pass;
 pub mod passes;
+pub mod texture;
+pub mod color;
+pub mod cubemap;
+pub mod environment;
+pub mod ssr;
+pub mod frustum;
+pub mod frame_stats;
+pub mod shader;
+pub mod pipeline_cache;
 
 pub use vulkan_context::VulkanContext;
 pub use resource_manager::ResourceManager;
-pub use camera::Camera;
+pub use camera::{Camera, CameraMode, DepthMode, ProjectionMode};
+pub use camera_track::{CameraKeyframe, CameraTrack, CameraTrackPlayhead};
 pub use swapchain::SwapchainManager;
 pub use renderer::VulkanRenderer;
-pub use lighting::{DirectionalLight, PointLight};
-pub use render_pass::{RenderPass, RenderContext, RenderPassRegistry};
+pub use lighting::{DirectionalLight, PointLight, PointLightSettings};
+pub use render_pass::{RenderPass, RenderContext, RenderPassRegistry, DrawStats, DEPTH_FORMAT};
+pub use texture::TextureCache;
+pub use cubemap::{CubemapFaces, CubemapTexture};
+pub use environment::EnvironmentLighting;
+pub use ssr::ReflectionHistory;
+pub use frustum::Frustum;
+pub use frame_stats::{FrameStats, MeshMemory};
+pub use pipeline_cache::PipelineCache;
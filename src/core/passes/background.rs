@@ -0,0 +1,257 @@
+use ash::vk;
+use anyhow::Result;
+use glam::Vec4;
+
+use crate::core::RenderPass;
+use crate::game::{BackgroundMode, Game};
+
+/// Push constants for the background gradient (layout matches background.frag)
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct BackgroundPushConstants {
+    pub top_color: Vec4,
+    pub bottom_color: Vec4,
+}
+
+unsafe impl bytemuck::Pod for BackgroundPushConstants {}
+unsafe impl bytemuck::Zeroable for BackgroundPushConstants {}
+
+/// Two-color vertical gradient behind the scene (`BackgroundMode::Gradient` only)
+///
+/// A fullscreen triangle drawn first in the main pass, without depth test or write,
+/// so everything after it draws on top. The solid color mode needs no pass: the
+/// renderer clears the HDR target to that color.
+pub struct BackgroundPass {
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+}
+
+impl BackgroundPass {
+    pub fn new() -> Self {
+        Self {
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+        }
+    }
+}
+
+impl RenderPass for BackgroundPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, extent, render_pass, ctx.msaa_samples)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() {
+                return Ok(());
+            }
+
+            let background = &game.background_config;
+            let push_data = BackgroundPushConstants {
+                top_color: game.tonemap_config.shader_color(background.top_color).extend(1.0),
+                bottom_color: game.tonemap_config.shader_color(background.bottom_color).extend(1.0),
+            };
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+
+            // Fullscreen triangle, corners generated in background.vert
+            ctx.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Destroy old pipeline
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            // Create new pipeline
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, extent, render_pass, ctx.msaa_samples)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            Ok(())
+        }
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+        }
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+    }
+
+    fn name(&self) -> &str {
+        "Background"
+    }
+
+    fn should_render(&self, game: &Game) -> bool {
+        game.background_config.mode == BackgroundMode::Gradient
+    }
+}
+
+impl BackgroundPass {
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        extent: vk::Extent2D,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let vert_shader_code = crate::core::shader::load_spirv("background.vert.spv", include_bytes!("../../../shaders/background.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("background.frag.spv", include_bytes!("../../../shaders/background.frag.spv"));
+
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // No vertex buffers: the triangle comes from gl_VertexIndex
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+
+        let scissor = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent,
+        };
+
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewports(std::slice::from_ref(&viewport))
+            .scissors(std::slice::from_ref(&scissor));
+
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(samples);
+
+        // Drawn before anything else and leaves the cleared depth untouched
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(false)
+            .depth_write_enable(false)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(false);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        // Push constants (no descriptor sets)
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<BackgroundPushConstants>() as u32);
+
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(
+                cache.handle(),
+                std::slice::from_ref(&pipeline_info),
+                None,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to create background pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+}
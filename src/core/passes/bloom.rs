@@ -0,0 +1,812 @@
+use ash::vk;
+use anyhow::Result;
+
+use crate::core::RenderPass;
+use crate::game::Game;
+
+/// HDR format for the half-res bloom targets (bright pass can exceed 1.0 before blur)
+const BLOOM_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+
+/// Push constants shared by the extract, blur and composite shaders
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct BloomPushConstants {
+    pub direction: [f32; 2],
+    pub threshold: f32,
+    pub intensity: f32,
+}
+
+/// Half-resolution color target used for bright-pass extraction and blur ping-pong
+struct BloomTarget {
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+    view: vk::ImageView,
+    framebuffer: vk::Framebuffer,
+    descriptor_set: vk::DescriptorSet,
+}
+
+impl BloomTarget {
+    fn null() -> Self {
+        Self {
+            image: vk::Image::null(),
+            memory: vk::DeviceMemory::null(),
+            view: vk::ImageView::null(),
+            framebuffer: vk::Framebuffer::null(),
+            descriptor_set: vk::DescriptorSet::null(),
+        }
+    }
+}
+
+/// Bloom post-process pass
+///
+/// Runs after the main render pass: extracts pixels above a brightness threshold from
+/// the HDR scene color into a half-res target, blurs it with a separable Gaussian
+/// (horizontal + vertical, `blur_passes` times) and additively composites the result
+/// back onto the HDR scene color before tonemapping. Like the shadow pass it owns its
+/// own Vulkan render passes, so the renderer records it directly instead of using the
+/// RenderPassRegistry.
+pub struct BloomPass {
+    enabled: bool,
+    threshold: f32,
+    intensity: f32,
+    blur_passes: u32,
+
+    color_format: vk::Format,
+    extent: vk::Extent2D,
+
+    // Scene color image this frame's bloom reads from and composites onto
+    image_index: usize,
+
+    // Render passes: half-res offscreen (extract/blur) and scene color composite
+    offscreen_render_pass: vk::RenderPass,
+    composite_render_pass: vk::RenderPass,
+
+    // Ping-pong targets (extract -> 0, horizontal 0 -> 1, vertical 1 -> 0)
+    targets: [BloomTarget; 2],
+
+    // Scene color images as bloom sources (one descriptor set per image)
+    source_descriptor_sets: Vec<vk::DescriptorSet>,
+    // Scene color images as composite targets (one framebuffer per image)
+    composite_framebuffers: Vec<vk::Framebuffer>,
+
+    sampler: vk::Sampler,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    pipeline_layout: vk::PipelineLayout,
+    extract_pipeline: vk::Pipeline,
+    blur_pipeline: vk::Pipeline,
+    composite_pipeline: vk::Pipeline,
+}
+
+impl BloomPass {
+    pub fn new(color_format: vk::Format) -> Self {
+        let defaults = crate::game::BloomConfig::default();
+        Self {
+            enabled: defaults.enabled,
+            threshold: defaults.threshold,
+            intensity: defaults.intensity,
+            blur_passes: defaults.blur_passes,
+            color_format,
+            extent: vk::Extent2D { width: 1, height: 1 },
+            image_index: 0,
+            offscreen_render_pass: vk::RenderPass::null(),
+            composite_render_pass: vk::RenderPass::null(),
+            targets: [BloomTarget::null(), BloomTarget::null()],
+            source_descriptor_sets: Vec::new(),
+            composite_framebuffers: Vec::new(),
+            sampler: vk::Sampler::null(),
+            descriptor_set_layout: vk::DescriptorSetLayout::null(),
+            descriptor_pool: vk::DescriptorPool::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            extract_pipeline: vk::Pipeline::null(),
+            blur_pipeline: vk::Pipeline::null(),
+            composite_pipeline: vk::Pipeline::null(),
+        }
+    }
+
+    /// Select the scene color image to composite onto this frame
+    pub fn set_target(&mut self, image_index: usize) {
+        self.image_index = image_index;
+    }
+
+    /// (Re)create the half-res targets and source descriptors for the current scene color targets
+    /// Caller must make sure the GPU is idle
+    pub fn resize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        source_image_views: &[vk::ImageView],
+    ) -> Result<()> {
+        unsafe {
+            self.destroy_targets(ctx.device);
+
+            self.extent = vk::Extent2D {
+                width: (ctx.extent.width / 2).max(1),
+                height: (ctx.extent.height / 2).max(1),
+            };
+
+            // 1. Descriptor pool: one set per source image + one per bloom target
+            let set_count = (source_image_views.len() + self.targets.len()) as u32;
+            let pool_size = vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(set_count);
+            let pool_info = vk::DescriptorPoolCreateInfo::default()
+                .pool_sizes(std::slice::from_ref(&pool_size))
+                .max_sets(set_count);
+            self.descriptor_pool = ctx.device.create_descriptor_pool(&pool_info, None)?;
+
+            // 2. Half-res ping-pong targets
+            let mut targets = [BloomTarget::null(), BloomTarget::null()];
+            for target in targets.iter_mut() {
+                let (image, memory, view) = Self::create_target_image(ctx.instance, ctx.physical_device, ctx.device, self.extent)?;
+
+                let attachments = [view];
+                let framebuffer_info = vk::FramebufferCreateInfo::default()
+                    .render_pass(self.offscreen_render_pass)
+                    .attachments(&attachments)
+                    .width(self.extent.width)
+                    .height(self.extent.height)
+                    .layers(1);
+                let framebuffer = ctx.device.create_framebuffer(&framebuffer_info, None)?;
+
+                let descriptor_set = self.allocate_descriptor_set(ctx.device, view)?;
+
+                *target = BloomTarget {
+                    image,
+                    memory,
+                    view,
+                    framebuffer,
+                    descriptor_set,
+                };
+            }
+            self.targets = targets;
+
+            // 3. Scene color images as bright-pass sources
+            self.source_descriptor_sets = source_image_views
+                .iter()
+                .map(|&view| self.allocate_descriptor_set(ctx.device, view))
+                .collect::<Result<Vec<_>>>()?;
+
+            // 4. Scene color images as full-res composite targets
+            self.composite_framebuffers = source_image_views
+                .iter()
+                .map(|&view| {
+                    let attachments = [view];
+                    let framebuffer_info = vk::FramebufferCreateInfo::default()
+                        .render_pass(self.composite_render_pass)
+                        .attachments(&attachments)
+                        .width(ctx.extent.width)
+                        .height(ctx.extent.height)
+                        .layers(1);
+                    Ok(ctx.device.create_framebuffer(&framebuffer_info, None)?)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(())
+        }
+    }
+
+    unsafe fn destroy_targets(&mut self, device: &ash::Device) {
+        for framebuffer in self.composite_framebuffers.drain(..) {
+            device.destroy_framebuffer(framebuffer, None);
+        }
+        for target in self.targets.iter_mut() {
+            if target.framebuffer != vk::Framebuffer::null() {
+                device.destroy_framebuffer(target.framebuffer, None);
+            }
+            if target.view != vk::ImageView::null() {
+                device.destroy_image_view(target.view, None);
+            }
+            if target.image != vk::Image::null() {
+                device.destroy_image(target.image, None);
+                device.free_memory(target.memory, None);
+            }
+            *target = BloomTarget::null();
+        }
+
+        // Destroying the pool frees every set allocated from it
+        if self.descriptor_pool != vk::DescriptorPool::null() {
+            device.destroy_descriptor_pool(self.descriptor_pool, None);
+            self.descriptor_pool = vk::DescriptorPool::null();
+        }
+        self.source_descriptor_sets.clear();
+    }
+
+    unsafe fn allocate_descriptor_set(&self, device: &ash::Device, view: vk::ImageView) -> Result<vk::DescriptorSet> {
+        let layouts = [self.descriptor_set_layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(self.descriptor_pool)
+            .set_layouts(&layouts);
+        let descriptor_set = device.allocate_descriptor_sets(&alloc_info)?[0];
+
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(view)
+            .sampler(self.sampler);
+
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(descriptor_set)
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(std::slice::from_ref(&image_info));
+        device.update_descriptor_sets(std::slice::from_ref(&write), &[]);
+
+        Ok(descriptor_set)
+    }
+
+    unsafe fn create_target_image(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        extent: vk::Extent2D,
+    ) -> Result<(vk::Image, vk::DeviceMemory, vk::ImageView)> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: extent.width,
+                height: extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(BLOOM_FORMAT)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        let image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(image);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        let image_memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, image_memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(BLOOM_FORMAT)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+
+        let image_view = device.create_image_view(&view_info, None)?;
+
+        Ok((image, image_memory, image_view))
+    }
+
+    unsafe fn find_memory_type(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        type_filter: u32,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<u32> {
+        let mem_properties = instance.get_physical_device_memory_properties(physical_device);
+
+        for i in 0..mem_properties.memory_type_count {
+            if (type_filter & (1 << i)) != 0
+                && mem_properties.memory_types[i as usize].property_flags.contains(properties)
+            {
+                return Ok(i);
+            }
+        }
+
+        Err(anyhow::anyhow!("Failed to find suitable memory type"))
+    }
+
+    unsafe fn create_offscreen_render_pass(device: &ash::Device) -> Result<vk::RenderPass> {
+        // Every draw covers the full target, so the previous contents are never loaded
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(BLOOM_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        let dependencies = [
+            // Wait for the previous blur step to finish reading before overwriting
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE),
+            // Make the result visible to the next step's fragment shader
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_composite_render_pass(device: &ash::Device, format: vk::Format) -> Result<vk::RenderPass> {
+        // The scene color arrives shader-readable from the main pass and is left that way for tonemapping
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(format)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::LOAD)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        let dependencies = [
+            // Bright pass must finish sampling the scene color before blending onto it
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(
+                    vk::AccessFlags::COLOR_ATTACHMENT_READ
+                    | vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                ),
+            // Make the composited result visible to the tonemap pass
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // Linear filtering does the 2x2 downsample in the bright pass and smooths the upsample
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_TRANSPARENT_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_descriptor_set_layout(device: &ash::Device) -> Result<vk::DescriptorSetLayout> {
+        let input_binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .bindings(std::slice::from_ref(&input_binding));
+
+        Ok(device.create_descriptor_set_layout(&layout_info, None)?)
+    }
+
+    unsafe fn create_pipeline_layout(device: &ash::Device, descriptor_set_layout: vk::DescriptorSetLayout) -> Result<vk::PipelineLayout> {
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<BloomPushConstants>() as u32);
+
+        let layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(&layouts)
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+
+        Ok(device.create_pipeline_layout(&pipeline_layout_info, None)?)
+    }
+
+    /// Fullscreen-triangle pipeline; `additive` blends ONE + ONE onto the target
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+        pipeline_layout: vk::PipelineLayout,
+        frag_shader_code: &[u8],
+        additive: bool,
+    ) -> Result<vk::Pipeline> {
+        use std::ffi::CString;
+
+        let vert_shader_code = crate::core::shader::load_spirv("bloom.vert.spv", include_bytes!("../../../shaders/bloom.vert.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // No vertex input - fullscreen triangle is generated in the vertex shader
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // Viewport/scissor are dynamic so resizing only recreates the targets
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+            .dynamic_states(&dynamic_states);
+
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(false)
+            .depth_write_enable(false);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(additive)
+            .src_color_blend_factor(vk::BlendFactor::ONE)
+            .dst_color_blend_factor(vk::BlendFactor::ONE)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ZERO)
+            .dst_alpha_blend_factor(vk::BlendFactor::ONE)
+            .alpha_blend_op(vk::BlendOp::ADD);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .dynamic_state(&dynamic_state)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create bloom pipeline: {:?}", e.1))?;
+
+        Ok(pipelines[0])
+    }
+
+    /// Create the extract, blur and composite pipelines from the current shaders
+    unsafe fn create_pipelines(&mut self, device: &ash::Device, cache: &crate::core::PipelineCache) -> Result<()> {
+        use crate::core::shader::load_spirv;
+
+        self.extract_pipeline = Self::create_pipeline(
+            device,
+            cache,
+            self.offscreen_render_pass,
+            self.pipeline_layout,
+            &load_spirv("bloom_extract.frag.spv", include_bytes!("../../../shaders/bloom_extract.frag.spv")),
+            false,
+        )?;
+        self.blur_pipeline = Self::create_pipeline(
+            device,
+            cache,
+            self.offscreen_render_pass,
+            self.pipeline_layout,
+            &load_spirv("bloom_blur.frag.spv", include_bytes!("../../../shaders/bloom_blur.frag.spv")),
+            false,
+        )?;
+        self.composite_pipeline = Self::create_pipeline(
+            device,
+            cache,
+            self.composite_render_pass,
+            self.pipeline_layout,
+            &load_spirv("bloom_composite.frag.spv", include_bytes!("../../../shaders/bloom_composite.frag.spv")),
+            true,
+        )?;
+
+        Ok(())
+    }
+
+    /// Record one fullscreen draw into `framebuffer`, sampling `input`
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn draw_fullscreen(
+        &self,
+        device: &ash::Device,
+        command_buffer: vk::CommandBuffer,
+        render_pass: vk::RenderPass,
+        framebuffer: vk::Framebuffer,
+        extent: vk::Extent2D,
+        pipeline: vk::Pipeline,
+        input: vk::DescriptorSet,
+        push_data: BloomPushConstants,
+    ) {
+        let render_area = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent,
+        };
+
+        let render_pass_info = vk::RenderPassBeginInfo::default()
+            .render_pass(render_pass)
+            .framebuffer(framebuffer)
+            .render_area(render_area);
+
+        device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+
+        device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+        device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+        device.cmd_set_scissor(command_buffer, 0, &[render_area]);
+
+        device.cmd_bind_descriptor_sets(
+            command_buffer,
+            vk::PipelineBindPoint::GRAPHICS,
+            self.pipeline_layout,
+            0,
+            &[input],
+            &[],
+        );
+
+        device.cmd_push_constants(
+            command_buffer,
+            self.pipeline_layout,
+            vk::ShaderStageFlags::FRAGMENT,
+            0,
+            bytemuck::bytes_of(&push_data),
+        );
+
+        // Draw fullscreen triangle (no vertex buffer needed)
+        device.cmd_draw(command_buffer, 3, 1, 0, 0);
+
+        device.cmd_end_render_pass(command_buffer);
+    }
+}
+
+impl RenderPass for BloomPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Targets depend on the window size and are created by `resize`
+            self.offscreen_render_pass = Self::create_offscreen_render_pass(ctx.device)?;
+            self.composite_render_pass = Self::create_composite_render_pass(ctx.device, self.color_format)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+            self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
+            self.pipeline_layout = Self::create_pipeline_layout(ctx.device, self.descriptor_set_layout)?;
+
+            self.create_pipelines(ctx.device, ctx.pipeline_cache)?;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        let config = &game.bloom_config;
+        self.enabled = config.enabled;
+        self.threshold = config.threshold;
+        self.intensity = config.intensity;
+        self.blur_passes = config.blur_passes;
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if !self.enabled
+                || self.composite_pipeline == vk::Pipeline::null()
+                || self.image_index >= self.source_descriptor_sets.len()
+            {
+                return Ok(());
+            }
+
+            let params = BloomPushConstants {
+                direction: [0.0, 0.0],
+                threshold: self.threshold,
+                intensity: self.intensity,
+            };
+
+            // 1. Bright pass: scene color -> target 0 (half res)
+            //    (the main render pass leaves it shader-readable)
+            self.draw_fullscreen(
+                ctx.device,
+                command_buffer,
+                self.offscreen_render_pass,
+                self.targets[0].framebuffer,
+                self.extent,
+                self.extract_pipeline,
+                self.source_descriptor_sets[self.image_index],
+                params,
+            );
+
+            // 2. Separable blur: horizontal (0 -> 1) then vertical (1 -> 0), repeated
+            for _ in 0..self.blur_passes.max(1) {
+                self.draw_fullscreen(
+                    ctx.device,
+                    command_buffer,
+                    self.offscreen_render_pass,
+                    self.targets[1].framebuffer,
+                    self.extent,
+                    self.blur_pipeline,
+                    self.targets[0].descriptor_set,
+                    BloomPushConstants { direction: [1.0, 0.0], ..params },
+                );
+                self.draw_fullscreen(
+                    ctx.device,
+                    command_buffer,
+                    self.offscreen_render_pass,
+                    self.targets[0].framebuffer,
+                    self.extent,
+                    self.blur_pipeline,
+                    self.targets[1].descriptor_set,
+                    BloomPushConstants { direction: [0.0, 1.0], ..params },
+                );
+            }
+
+            // 3. Additive composite onto the scene color (leaves it shader-readable for tonemapping)
+            self.draw_fullscreen(
+                ctx.device,
+                command_buffer,
+                self.composite_render_pass,
+                self.composite_framebuffers[self.image_index],
+                ctx.extent,
+                self.composite_pipeline,
+                self.targets[0].descriptor_set,
+                params,
+            );
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Sources reference the scene color targets, so the renderer rebuilds them via `resize`
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            for pipeline in [self.extract_pipeline, self.blur_pipeline, self.composite_pipeline] {
+                if pipeline != vk::Pipeline::null() {
+                    ctx.device.destroy_pipeline(pipeline, None);
+                }
+            }
+            self.create_pipelines(ctx.device, ctx.pipeline_cache)
+        }
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            self.destroy_targets(device);
+
+            for pipeline in [self.extract_pipeline, self.blur_pipeline, self.composite_pipeline] {
+                if pipeline != vk::Pipeline::null() {
+                    device.destroy_pipeline(pipeline, None);
+                }
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
+                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+            }
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            if self.offscreen_render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.offscreen_render_pass, None);
+            }
+            if self.composite_render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.composite_render_pass, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Bloom"
+    }
+}
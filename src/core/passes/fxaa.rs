@@ -0,0 +1,626 @@
+use ash::vk;
+use anyhow::Result;
+
+use crate::core::RenderPass;
+use crate::game::Game;
+
+/// Push constants for the FXAA shader
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct FxaaPushConstants {
+    pub inverse_resolution: [f32; 2],
+    pub quality: f32,
+    /// Filter only left of this horizontal texture coordinate (1.0 = whole image)
+    pub split: f32,
+}
+
+/// Tonemapped color target FXAA samples from (one per swapchain image)
+struct FxaaSource {
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+    view: vk::ImageView,
+    framebuffer: vk::Framebuffer,
+    descriptor_set: vk::DescriptorSet,
+}
+
+/// FXAA post-process pass
+///
+/// Cheap alternative to MSAA: with FXAA selected the tonemap pass writes into an
+/// intermediate color target owned here (swapchain format, so tonemap's pipeline stays
+/// compatible) and this pass filters it onto the swapchain in one fullscreen draw.
+/// In split mode only the left half is filtered, for side-by-side comparisons. Like
+/// tonemap it owns its render passes and is recorded by the renderer directly.
+pub struct FxaaPass {
+    quality: f32,
+    split: bool,
+
+    swapchain_format: vk::Format,
+
+    // Source this frame reads from and the swapchain framebuffer it writes to
+    image_index: usize,
+    target_framebuffer: vk::Framebuffer,
+
+    // Render passes: tonemap -> intermediate target, and intermediate -> swapchain
+    source_render_pass: vk::RenderPass,
+    render_pass: vk::RenderPass,
+
+    sources: Vec<FxaaSource>,
+    extent: vk::Extent2D,
+
+    sampler: vk::Sampler,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+}
+
+impl FxaaPass {
+    pub fn new(swapchain_format: vk::Format) -> Self {
+        let defaults = crate::game::AntiAliasingConfig::default();
+        Self {
+            quality: defaults.fxaa_quality,
+            split: defaults.fxaa_split,
+            swapchain_format,
+            image_index: 0,
+            target_framebuffer: vk::Framebuffer::null(),
+            source_render_pass: vk::RenderPass::null(),
+            render_pass: vk::RenderPass::null(),
+            sources: Vec::new(),
+            extent: vk::Extent2D { width: 1, height: 1 },
+            sampler: vk::Sampler::null(),
+            descriptor_set_layout: vk::DescriptorSetLayout::null(),
+            descriptor_pool: vk::DescriptorPool::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+        }
+    }
+
+    /// Render pass the tonemap pass uses to write into the intermediate targets
+    pub fn source_render_pass(&self) -> vk::RenderPass {
+        self.source_render_pass
+    }
+
+    /// Intermediate target framebuffer for a swapchain image (null before `resize`)
+    pub fn source_framebuffer(&self, image_index: usize) -> vk::Framebuffer {
+        self.sources
+            .get(image_index)
+            .map_or(vk::Framebuffer::null(), |source| source.framebuffer)
+    }
+
+    /// Select the source and the swapchain framebuffer to write this frame
+    pub fn set_target(&mut self, image_index: usize, framebuffer: vk::Framebuffer) {
+        self.image_index = image_index;
+        self.target_framebuffer = framebuffer;
+    }
+
+    /// (Re)create one intermediate target per swapchain image at the current extent
+    /// Caller must make sure the GPU is idle
+    pub fn resize(&mut self, ctx: &crate::core::RenderContext, count: usize) -> Result<()> {
+        unsafe {
+            self.destroy_sources(ctx.device);
+            self.extent = ctx.extent;
+
+            let set_count = count.max(1) as u32;
+            let pool_size = vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(set_count);
+            let pool_info = vk::DescriptorPoolCreateInfo::default()
+                .pool_sizes(std::slice::from_ref(&pool_size))
+                .max_sets(set_count);
+            self.descriptor_pool = ctx.device.create_descriptor_pool(&pool_info, None)?;
+
+            for _ in 0..count {
+                let (image, memory, view) = self.create_source_image(ctx.instance, ctx.physical_device, ctx.device)?;
+
+                let attachments = [view];
+                let framebuffer_info = vk::FramebufferCreateInfo::default()
+                    .render_pass(self.source_render_pass)
+                    .attachments(&attachments)
+                    .width(self.extent.width)
+                    .height(self.extent.height)
+                    .layers(1);
+                let framebuffer = ctx.device.create_framebuffer(&framebuffer_info, None)?;
+
+                let descriptor_set = self.allocate_descriptor_set(ctx.device, view)?;
+
+                self.sources.push(FxaaSource {
+                    image,
+                    memory,
+                    view,
+                    framebuffer,
+                    descriptor_set,
+                });
+            }
+
+            Ok(())
+        }
+    }
+
+    unsafe fn destroy_sources(&mut self, device: &ash::Device) {
+        for source in self.sources.drain(..) {
+            device.destroy_framebuffer(source.framebuffer, None);
+            device.destroy_image_view(source.view, None);
+            device.destroy_image(source.image, None);
+            device.free_memory(source.memory, None);
+        }
+
+        // Destroying the pool frees every set allocated from it
+        if self.descriptor_pool != vk::DescriptorPool::null() {
+            device.destroy_descriptor_pool(self.descriptor_pool, None);
+            self.descriptor_pool = vk::DescriptorPool::null();
+        }
+    }
+
+    unsafe fn allocate_descriptor_set(&self, device: &ash::Device, view: vk::ImageView) -> Result<vk::DescriptorSet> {
+        let layouts = [self.descriptor_set_layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(self.descriptor_pool)
+            .set_layouts(&layouts);
+        let descriptor_set = device.allocate_descriptor_sets(&alloc_info)?[0];
+
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(view)
+            .sampler(self.sampler);
+
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(descriptor_set)
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(std::slice::from_ref(&image_info));
+        device.update_descriptor_sets(std::slice::from_ref(&write), &[]);
+
+        Ok(descriptor_set)
+    }
+
+    unsafe fn create_source_image(
+        &self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> Result<(vk::Image, vk::DeviceMemory, vk::ImageView)> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: self.extent.width,
+                height: self.extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(self.swapchain_format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        let image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(image);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        let image_memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, image_memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(self.swapchain_format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+
+        let image_view = device.create_image_view(&view_info, None)?;
+
+        Ok((image, image_memory, image_view))
+    }
+
+    unsafe fn find_memory_type(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        type_filter: u32,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<u32> {
+        let mem_properties = instance.get_physical_device_memory_properties(physical_device);
+
+        for i in 0..mem_properties.memory_type_count {
+            if (type_filter & (1 << i)) != 0
+                && mem_properties.memory_types[i as usize].property_flags.contains(properties)
+            {
+                return Ok(i);
+            }
+        }
+
+        Err(anyhow::anyhow!("Failed to find suitable memory type"))
+    }
+
+    unsafe fn create_source_render_pass(device: &ash::Device, format: vk::Format) -> Result<vk::RenderPass> {
+        // Tonemap overwrites every pixel and leaves the target shader-readable for FXAA
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(format)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        let dependencies = [
+            // An earlier FXAA draw must finish sampling the target before tonemap overwrites it
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE),
+            // Make the tonemapped image visible to the FXAA pass
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_render_pass(device: &ash::Device, format: vk::Format) -> Result<vk::RenderPass> {
+        // Every pixel is overwritten, so the previous swapchain contents are never loaded
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(format)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        // Wait for the acquired swapchain image (the source is made visible by the tonemap pass)
+        let dependency = vk::SubpassDependency::default()
+            .src_subpass(vk::SUBPASS_EXTERNAL)
+            .dst_subpass(0)
+            .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE);
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(std::slice::from_ref(&dependency));
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // Linear filtering does the blend across edges at sub-texel offsets
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_TRANSPARENT_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_descriptor_set_layout(device: &ash::Device) -> Result<vk::DescriptorSetLayout> {
+        let input_binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .bindings(std::slice::from_ref(&input_binding));
+
+        Ok(device.create_descriptor_set_layout(&layout_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+        descriptor_set_layout: vk::DescriptorSetLayout,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<FxaaPushConstants>() as u32);
+
+        let layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(&layouts)
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let vert_shader_code = crate::core::shader::load_spirv("fxaa.vert.spv", include_bytes!("../../../shaders/fxaa.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("fxaa.frag.spv", include_bytes!("../../../shaders/fxaa.frag.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // No vertex input - fullscreen triangle is generated in the vertex shader
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // Viewport/scissor are dynamic so resizing only rebuilds the targets
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+            .dynamic_states(&dynamic_states);
+
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(false);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .color_blend_state(&color_blending)
+            .dynamic_state(&dynamic_state)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create FXAA pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+}
+
+impl RenderPass for FxaaPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Intermediate targets depend on the window size and are created by `resize`
+            self.source_render_pass = Self::create_source_render_pass(ctx.device, self.swapchain_format)?;
+            self.render_pass = Self::create_render_pass(ctx.device, self.swapchain_format)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+            self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass, self.descriptor_set_layout)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        self.quality = game.anti_aliasing_config.fxaa_quality;
+        self.split = game.anti_aliasing_config.fxaa_split;
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() || self.image_index >= self.sources.len() {
+                return Ok(());
+            }
+
+            let render_area = vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent: ctx.extent,
+            };
+
+            let render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.render_pass)
+                .framebuffer(self.target_framebuffer)
+                .render_area(render_area);
+
+            ctx.device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+
+            let viewport = vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: ctx.extent.width as f32,
+                height: ctx.extent.height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+            ctx.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            ctx.device.cmd_set_scissor(command_buffer, 0, &[render_area]);
+
+            ctx.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline_layout,
+                0,
+                &[self.sources[self.image_index].descriptor_set],
+                &[],
+            );
+
+            let push_data = FxaaPushConstants {
+                inverse_resolution: [
+                    1.0 / self.extent.width.max(1) as f32,
+                    1.0 / self.extent.height.max(1) as f32,
+                ],
+                quality: self.quality,
+                split: if self.split { 0.5 } else { 1.0 },
+            };
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+
+            // Draw fullscreen triangle (no vertex buffer needed)
+            ctx.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+
+            ctx.device.cmd_end_render_pass(command_buffer);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Intermediate targets follow the swapchain size, so the renderer rebuilds them via `resize`
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass, self.descriptor_set_layout)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            self.destroy_sources(device);
+
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
+                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+            }
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            if self.render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.render_pass, None);
+            }
+            if self.source_render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.source_render_pass, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "FXAA"
+    }
+}
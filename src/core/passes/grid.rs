@@ -0,0 +1,295 @@
+use ash::vk;
+use anyhow::Result;
+use glam::{Mat4, Vec4};
+
+use crate::core::RenderPass;
+use crate::game::Game;
+
+/// Push constants for the ground grid (layout matches grid.vert/grid.frag)
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct GridPushConstants {
+    pub view_proj: Mat4, // Camera-relative (rotation only)
+    pub origin: Vec4,    // xz: camera position modulo the major spacing, y: camera height, w: quad half-size
+    pub params: Vec4,    // x: minor spacing, y: minor lines per major line, z: fade distance, w: log depth
+    pub color: Vec4,
+}
+
+unsafe impl bytemuck::Pod for GridPushConstants {}
+unsafe impl bytemuck::Zeroable for GridPushConstants {}
+
+/// Opacity of the grid's major lines (minor lines are fainter)
+const GRID_ALPHA: f32 = 0.8;
+
+/// Editor reference grid on the Y=0 plane (edit mode only)
+///
+/// A single quad under the camera; grid.frag finds the lines analytically with
+/// screen-space derivatives, so they stay one pixel wide and anti-aliased at any
+/// distance. Everything is camera-relative: the CPU wraps the camera position
+/// into one major cell in 64-bit math, so the shader never sees large coordinates.
+pub struct GridPass {
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+}
+
+impl GridPass {
+    pub fn new() -> Self {
+        Self {
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+        }
+    }
+
+    /// Push constants for the current camera and grid settings
+    fn push_constants(game: &Game, aspect_ratio: f32) -> GridPushConstants {
+        let grid = &game.grid_config;
+        let camera_position = game.camera.world_position();
+
+        // Drop the view translation: the grid is drawn relative to the camera
+        let mut view = game.camera.view_matrix();
+        view.w_axis = Vec4::W;
+        let view_proj = game.camera.projection_matrix(aspect_ratio) * view;
+
+        // Wrapping by a whole major cell keeps every line (minor and major) in place
+        let major_spacing = grid.spacing as f64 * grid.major_every as f64;
+        let offset_x = camera_position.x.rem_euclid(major_spacing) as f32;
+        let offset_z = camera_position.z.rem_euclid(major_spacing) as f32;
+
+        GridPushConstants {
+            view_proj,
+            origin: Vec4::new(offset_x, camera_position.y as f32, offset_z, grid.fade_distance),
+            params: Vec4::new(
+                grid.spacing,
+                grid.major_every as f32,
+                grid.fade_distance,
+                game.camera.log_depth_coefficient(),
+            ),
+            color: game.tonemap_config.shader_color(grid.color).extend(GRID_ALPHA),
+        }
+    }
+}
+
+impl RenderPass for GridPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, extent, render_pass, ctx.msaa_samples)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() {
+                return Ok(());
+            }
+
+            let push_data = Self::push_constants(game, ctx.extent.width as f32 / ctx.extent.height as f32);
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+
+            // Two triangles, corners generated in grid.vert
+            ctx.device.cmd_draw(command_buffer, 6, 1, 0, 0);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Destroy old pipeline
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            // Create new pipeline
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, extent, render_pass, ctx.msaa_samples)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            Ok(())
+        }
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+        }
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+    }
+
+    fn name(&self) -> &str {
+        "Grid"
+    }
+
+    fn should_render(&self, game: &Game) -> bool {
+        game.is_grid_visible()
+    }
+}
+
+impl GridPass {
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        extent: vk::Extent2D,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let vert_shader_code = crate::core::shader::load_spirv("grid.vert.spv", include_bytes!("../../../shaders/grid.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("grid.frag.spv", include_bytes!("../../../shaders/grid.frag.spv"));
+
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // No vertex buffers: the quad comes from gl_VertexIndex
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+
+        let scissor = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent,
+        };
+
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewports(std::slice::from_ref(&viewport))
+            .scissors(std::slice::from_ref(&scissor));
+
+        // Visible from above and below
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(samples);
+
+        // Depth tested so objects hide the grid behind them, but never written
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(false)
+            .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+            .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ONE)
+            .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+            .alpha_blend_op(vk::BlendOp::ADD);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        // Push constants (no descriptor sets)
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<GridPushConstants>() as u32);
+
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(
+                cache.handle(),
+                std::slice::from_ref(&pipeline_info),
+                None,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to create grid pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+}
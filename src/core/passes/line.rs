@@ -11,12 +11,17 @@ use anyhow::Result;
 use glam::{Mat4, Vec3, Vec4};
 use std::ffi::CStr;
 
+use crate::ecs::components::MovementCurve;
+
 /// Push constants for line rendering
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct LinePushConstants {
     pub view_proj: Mat4,
     pub color: Vec4,
+    /// `Camera::log_depth_coefficient` (0 = standard depth)
+    pub log_depth: f32,
+    pub _padding: [f32; 3],
 }
 
 unsafe impl bytemuck::Pod for LinePushConstants {}
@@ -26,6 +31,7 @@ pub struct LinePass {
     descriptor_set_layout: vk::DescriptorSetLayout,
     pipeline_layout: vk::PipelineLayout,
     pipeline: vk::Pipeline,
+    overlay_pipeline: vk::Pipeline, // No depth test: drawn on top of the scene
 
     // Dynamic line vertex buffer (updated per frame)
     vertex_buffer: vk::Buffer,
@@ -35,6 +41,7 @@ pub struct LinePass {
     // Cached data for rendering (updated in update() phase)
     cached_vertices: Vec<Vec3>,
     cached_draw_commands: Vec<(usize, usize, Vec4)>, // (vertex_offset, vertex_count, color)
+    cached_overlay_commands: Vec<(usize, usize, Vec4)>, // Same, drawn with the overlay pipeline
 }
 
 impl LinePass {
@@ -43,11 +50,13 @@ impl LinePass {
             descriptor_set_layout: vk::DescriptorSetLayout::null(),
             pipeline_layout: vk::PipelineLayout::null(),
             pipeline: vk::Pipeline::null(),
+            overlay_pipeline: vk::Pipeline::null(),
             vertex_buffer: vk::Buffer::null(),
             vertex_buffer_memory: vk::DeviceMemory::null(),
             vertex_buffer_capacity: capacity,
             cached_vertices: Vec::new(),
             cached_draw_commands: Vec::new(),
+            cached_overlay_commands: Vec::new(),
         }
     }
 
@@ -150,16 +159,19 @@ impl LinePass {
 
     unsafe fn create_pipeline(
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         render_pass: vk::RenderPass,
         pipeline_layout: vk::PipelineLayout,
         extent: vk::Extent2D,
+        samples: vk::SampleCountFlags,
+        depth_test: bool,
     ) -> Result<vk::Pipeline> {
         // Load shaders
-        let vert_code = include_bytes!("../../../shaders/line.vert.spv");
-        let frag_code = include_bytes!("../../../shaders/line.frag.spv");
+        let vert_code = crate::core::shader::load_spirv("line.vert.spv", include_bytes!("../../../shaders/line.vert.spv"));
+        let frag_code = crate::core::shader::load_spirv("line.frag.spv", include_bytes!("../../../shaders/line.frag.spv"));
 
-        let vert_module = Self::create_shader_module(device, vert_code)?;
-        let frag_module = Self::create_shader_module(device, frag_code)?;
+        let vert_module = cache.shader_module(device, &vert_code)?;
+        let frag_module = cache.shader_module(device, &frag_code)?;
 
         let entry_point = CStr::from_bytes_with_nul_unchecked(b"main\0");
 
@@ -232,11 +244,11 @@ impl LinePass {
         // Multisampling
         let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+            .rasterization_samples(samples);
 
         // Depth testing
         let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
-            .depth_test_enable(true)
+            .depth_test_enable(depth_test)
             .depth_write_enable(false)  // Don't write to depth buffer
             .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL)
             .depth_bounds_test_enable(false)
@@ -267,24 +279,16 @@ impl LinePass {
             .subpass(0);
 
         let pipelines = device.create_graphics_pipelines(
-            vk::PipelineCache::null(),
+            cache.handle(),
             &[pipeline_info],
             None,
         ).map_err(|(_, e)| e)?;
 
         // Clean up shader modules
-        device.destroy_shader_module(vert_module, None);
-        device.destroy_shader_module(frag_module, None);
 
         Ok(pipelines[0])
     }
 
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
-        let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
-        let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
-        Ok(device.create_shader_module(&create_info, None)?)
-    }
-
     unsafe fn create_vertex_buffer(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
@@ -348,7 +352,8 @@ impl crate::core::RenderPass for LinePass {
         unsafe {
             self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
             self.pipeline_layout = Self::create_pipeline_layout(ctx.device, self.descriptor_set_layout)?;
-            self.pipeline = Self::create_pipeline(ctx.device, render_pass, self.pipeline_layout, extent)?;
+            self.pipeline = Self::create_pipeline(ctx.device, ctx.pipeline_cache, render_pass, self.pipeline_layout, extent, ctx.msaa_samples, true)?;
+            self.overlay_pipeline = Self::create_pipeline(ctx.device, ctx.pipeline_cache, render_pass, self.pipeline_layout, extent, ctx.msaa_samples, false)?;
 
             let (vertex_buffer, vertex_buffer_memory) = Self::create_vertex_buffer(
                 ctx.instance,
@@ -370,24 +375,206 @@ impl crate::core::RenderPass for LinePass {
         _frame_index: usize,
         game: &crate::game::Game,
     ) -> Result<()> {
-        // Only update in play mode when hologram is active
-        if game.game_manager.mode != crate::game_manager::GameMode::Play {
-            self.cached_vertices.clear();
-            self.cached_draw_commands.clear();
-            return Ok(());
+        // Collect all line vertices
+        let mut all_vertices = Vec::new();
+        let mut draw_commands = Vec::new();
+        let mut overlay_commands = Vec::new();
+
+        // Path, arcs and cursors only show in play mode when hologram is active
+        if Self::showing_play_lines(game) {
+            Self::collect_play_lines(game, &mut all_vertices, &mut draw_commands);
+        }
+
+        // Measurement ruler (edit mode), drawn over the scene since its ends often sit inside objects
+        if game.game_manager.is_editing() {
+            if let Some((start, end)) = game.ruler.segment() {
+                let (start, end) = (start.as_vec3(), end.as_vec3());
+                let start_offset = all_vertices.len();
+                all_vertices.push(start);
+                all_vertices.push(end);
+
+                // Small marker at each end, a constant size on screen
+                for point in [start, end] {
+                    let radius = game.camera.screen_scale_at(point) * 0.01;
+                    all_vertices.extend(Self::generate_wireframe_sphere(point, radius, 6, 6));
+                }
+
+                overlay_commands.push((
+                    start_offset,
+                    all_vertices.len() - start_offset,
+                    Vec4::new(0.2, 0.9, 1.0, 1.0), // Cyan ruler
+                ));
+            }
+        }
+
+        // Lines are collected in world space and drawn in render space (see `Game::render_matrix`)
+        for vertex in all_vertices.iter_mut() {
+            *vertex = game.render_position(*vertex);
+        }
+
+        // Update vertex buffer with collected data
+        if !all_vertices.is_empty() {
+            unsafe {
+                self.update_lines(ctx.device, &all_vertices)?;
+            }
         }
 
-        if game.hologram_ship_position.is_none() {
-            self.cached_vertices.clear();
-            self.cached_draw_commands.clear();
+        // Cache for rendering
+        self.cached_vertices = all_vertices;
+        self.cached_draw_commands = draw_commands;
+        self.cached_overlay_commands = overlay_commands;
+
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        game: &crate::game::Game,
+    ) -> Result<()> {
+        // Only render if we have cached data
+        if self.cached_vertices.is_empty()
+            || (self.cached_draw_commands.is_empty() && self.cached_overlay_commands.is_empty())
+        {
             return Ok(());
         }
 
-        // Collect all line vertices
-        let mut all_vertices = Vec::new();
-        let mut draw_commands = Vec::new();
+        unsafe {
+            // Bind vertex buffer
+            ctx.device.cmd_bind_vertex_buffers(
+                command_buffer,
+                0,
+                &[self.vertex_buffer],
+                &[0],
+            );
+
+            let view_proj = game.camera.projection_matrix(ctx.extent.width as f32 / ctx.extent.height as f32)
+                * game.get_view_matrix();
+            let log_depth = game.camera.log_depth_coefficient();
+
+            // Depth-tested lines first, then the overlay lines on top
+            for (pipeline, commands) in [
+                (self.pipeline, &self.cached_draw_commands),
+                (self.overlay_pipeline, &self.cached_overlay_commands),
+            ] {
+                if commands.is_empty() {
+                    continue;
+                }
+
+                ctx.device.cmd_bind_pipeline(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    pipeline,
+                );
+
+                // Execute all cached draw commands
+                for &(vertex_offset, vertex_count, color) in commands {
+                    let push_constants = LinePushConstants {
+                        view_proj,
+                        color,
+                        log_depth,
+                        _padding: [0.0; 3],
+                    };
+
+                    let push_constants_bytes = bytemuck::bytes_of(&push_constants);
+                    ctx.device.cmd_push_constants(
+                        command_buffer,
+                        self.pipeline_layout,
+                        vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+                        0,
+                        push_constants_bytes,
+                    );
+
+                    ctx.device.cmd_draw(
+                        command_buffer,
+                        vertex_count as u32,
+                        1,
+                        vertex_offset as u32,
+                        0,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Destroy old pipelines
+            for pipeline in [self.pipeline, self.overlay_pipeline] {
+                if pipeline != vk::Pipeline::null() {
+                    ctx.device.destroy_pipeline(pipeline, None);
+                }
+            }
+
+            // Recreate pipelines with new extent
+            self.pipeline = Self::create_pipeline(ctx.device, ctx.pipeline_cache, render_pass, self.pipeline_layout, extent, ctx.msaa_samples, true)?;
+            self.overlay_pipeline = Self::create_pipeline(ctx.device, ctx.pipeline_cache, render_pass, self.pipeline_layout, extent, ctx.msaa_samples, false)?;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            if self.vertex_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.vertex_buffer, None);
+            }
+            if self.vertex_buffer_memory != vk::DeviceMemory::null() {
+                device.free_memory(self.vertex_buffer_memory, None);
+            }
+            for pipeline in [self.pipeline, self.overlay_pipeline] {
+                if pipeline != vk::Pipeline::null() {
+                    device.destroy_pipeline(pipeline, None);
+                }
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
+                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+            }
+        }
+        self.vertex_buffer = vk::Buffer::null();
+        self.vertex_buffer_memory = vk::DeviceMemory::null();
+        self.pipeline = vk::Pipeline::null();
+        self.overlay_pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+        self.descriptor_set_layout = vk::DescriptorSetLayout::null();
+    }
+
+    fn name(&self) -> &str {
+        "LinePass"
+    }
 
-        // 1. Collect bezier curve vertices
+    fn should_render(&self, game: &crate::game::Game) -> bool {
+        // Play mode when hologram exists, or a ruler measurement in edit mode
+        Self::showing_play_lines(game)
+            || (game.game_manager.is_editing() && game.ruler.segment().is_some())
+    }
+}
+
+impl LinePass {
+    /// Whether the play-mode lines (movement path, turn arc, pick sphere, camera cursor) are shown
+    fn showing_play_lines(game: &crate::game::Game) -> bool {
+        game.game_manager.mode == crate::game_manager::GameMode::Play
+            && game.hologram_ship_position.is_some()
+    }
+
+    /// Collect the play-mode lines into the shared vertex list
+    fn collect_play_lines(
+        game: &crate::game::Game,
+        all_vertices: &mut Vec<Vec3>,
+        draw_commands: &mut Vec<(usize, usize, Vec4)>,
+    ) {
+        // 1. Collect bezier curve vertices (planned path of the ship being moved)
         if let Some(hologram_pos) = game.hologram_ship_position {
             if let Some(fed_entity) = game.fed_cruiser_entity {
                 if let Ok(mut query) = game.ecs_world.world.query_one::<(&crate::ecs::components::Position, &crate::ecs::components::Ship)>(fed_entity) {
@@ -397,27 +584,74 @@ impl crate::core::RenderPass for LinePass {
                         // Use the control point from ship (calculated for car-like arc)
                         let control_point = ship.control_point;
 
-                        // Generate bezier curve points
+                        // Color the path by whether the curved move fits this turn's movement
+                        // budget and the unit's remaining action points
+                        let curve = MovementCurve::new(ship_pos, hologram_pos, control_point);
+                        let max_range = ship.max_movement_range;
+                        let affordable = match (game.active_unit(), game.planned_move_cost()) {
+                            (Some(unit), Some(cost)) => unit.can_afford(cost),
+                            _ => true,
+                        };
+                        let within_budget = curve.arc_length() <= max_range as f64
+                            && ship.is_position_valid(hologram_pos)
+                            && affordable;
+                        let path_color = if within_budget {
+                            Vec4::new(0.0, 1.0, 0.4, 1.0) // Green - reachable this turn
+                        } else {
+                            Vec4::new(1.0, 0.3, 0.1, 1.0) // Red - over budget
+                        };
+
+                        // Generate bezier curve points (one segment per collision sample)
                         let curve_points = Self::generate_bezier_curve(
                             ship_pos.as_vec3(),
                             control_point.as_vec3(),
                             hologram_pos.as_vec3(),
-                            32,  // 32 segments for smooth curve
+                            crate::movement::collision::PATH_SEGMENTS,
                         );
 
-                        // Convert to line segments
+                        // Segments where the ship would run into another unit are drawn red on their own
+                        let blocked_segments = game
+                            .planned_path_collision()
+                            .map(|collision| collision.blocked_segments)
+                            .unwrap_or_default();
+                        let is_blocked = |i: usize| blocked_segments.get(i).copied().unwrap_or(false);
+
+                        let blocked_offset = all_vertices.len();
+                        for i in (0..curve_points.len() - 1).filter(|&i| is_blocked(i)) {
+                            all_vertices.push(curve_points[i]);
+                            all_vertices.push(curve_points[i + 1]);
+                        }
+                        let blocked_count = all_vertices.len() - blocked_offset;
+                        if blocked_count > 0 {
+                            draw_commands.push((
+                                blocked_offset,
+                                blocked_count,
+                                Vec4::new(1.0, 0.0, 0.0, 1.0), // Red - path blocked by another unit
+                            ));
+                        }
+
+                        // Convert the clear part to line segments
                         let start_offset = all_vertices.len();
-                        for i in 0..curve_points.len() - 1 {
+                        for i in (0..curve_points.len() - 1).filter(|&i| !is_blocked(i)) {
                             all_vertices.push(curve_points[i]);
                             all_vertices.push(curve_points[i + 1]);
                         }
+
+                        // Tick marks every quarter of the per-turn range; the long tick marks
+                        // where a full turn's movement runs out
+                        all_vertices.extend(Self::generate_path_ticks(
+                            &curve_points,
+                            max_range / 4.0,
+                            4,
+                            max_range * 0.02,
+                        ));
                         let vertex_count = all_vertices.len() - start_offset;
 
                         if vertex_count > 0 {
                             draw_commands.push((
                                 start_offset,
                                 vertex_count,
-                                Vec4::new(0.0, 1.0, 1.0, 1.0), // Cyan
+                                path_color,
                             ));
                         }
                     }
@@ -498,130 +732,47 @@ impl crate::core::RenderPass for LinePass {
                 ));
             }
         }
-
-        // Update vertex buffer with collected data
-        if !all_vertices.is_empty() {
-            unsafe {
-                self.update_lines(ctx.device, &all_vertices)?;
-            }
-        }
-
-        // Cache for rendering
-        self.cached_vertices = all_vertices;
-        self.cached_draw_commands = draw_commands;
-
-        Ok(())
     }
 
-    fn render(
-        &mut self,
-        ctx: &crate::core::RenderContext,
-        command_buffer: vk::CommandBuffer,
-        _frame_index: usize,
-        game: &crate::game::Game,
-    ) -> Result<()> {
-        // Only render if we have cached data
-        if self.cached_vertices.is_empty() || self.cached_draw_commands.is_empty() {
-            return Ok(());
+    /// Generate tick marks along a polyline every `spacing` units of distance
+    /// Every `major_every`-th tick is twice as long. Ticks lie flat in the XZ plane,
+    /// perpendicular to the path. Returns line segments (pairs of vertices)
+    fn generate_path_ticks(points: &[Vec3], spacing: f32, major_every: usize, half_length: f32) -> Vec<Vec3> {
+        let mut vertices = Vec::new();
+        if spacing <= 0.0 {
+            return vertices;
         }
 
-        unsafe {
-            ctx.device.cmd_bind_pipeline(
-                command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
-                self.pipeline,
-            );
-
-            // Bind vertex buffer
-            ctx.device.cmd_bind_vertex_buffers(
-                command_buffer,
-                0,
-                &[self.vertex_buffer],
-                &[0],
-            );
-
-            let view_proj = game.camera.projection_matrix(ctx.extent.width as f32 / ctx.extent.height as f32)
-                * game.camera.view_matrix();
+        let mut travelled = 0.0;
+        let mut next_tick = spacing;
+        let mut tick_index = 1;
 
-            // Execute all cached draw commands
-            for &(vertex_offset, vertex_count, color) in &self.cached_draw_commands {
-                let push_constants = LinePushConstants {
-                    view_proj,
-                    color,
-                };
-
-                let push_constants_bytes = bytemuck::bytes_of(&push_constants);
-                ctx.device.cmd_push_constants(
-                    command_buffer,
-                    self.pipeline_layout,
-                    vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
-                    0,
-                    push_constants_bytes,
-                );
-
-                ctx.device.cmd_draw(
-                    command_buffer,
-                    vertex_count as u32,
-                    1,
-                    vertex_offset as u32,
-                    0,
-                );
+        for segment in points.windows(2) {
+            let (a, b) = (segment[0], segment[1]);
+            let length = a.distance(b);
+            if length <= f32::EPSILON {
+                continue;
             }
-        }
-
-        Ok(())
-    }
 
-    fn recreate_swapchain(
-        &mut self,
-        ctx: &crate::core::RenderContext,
-        render_pass: vk::RenderPass,
-        extent: vk::Extent2D,
-    ) -> Result<()> {
-        unsafe {
-            // Destroy old pipeline
-            if self.pipeline != vk::Pipeline::null() {
-                ctx.device.destroy_pipeline(self.pipeline, None);
-            }
+            let direction = (b - a) / length;
+            let side = direction.cross(Vec3::Y).try_normalize().unwrap_or(Vec3::X);
 
-            // Recreate pipeline with new extent
-            self.pipeline = Self::create_pipeline(ctx.device, render_pass, self.pipeline_layout, extent)?;
-        }
-        Ok(())
-    }
+            while next_tick <= travelled + length {
+                let point = a + direction * (next_tick - travelled);
+                let size = if tick_index % major_every == 0 { half_length * 2.0 } else { half_length };
+                vertices.push(point - side * size);
+                vertices.push(point + side * size);
 
-    fn cleanup(&mut self, device: &ash::Device) {
-        unsafe {
-            if self.vertex_buffer != vk::Buffer::null() {
-                device.destroy_buffer(self.vertex_buffer, None);
-            }
-            if self.vertex_buffer_memory != vk::DeviceMemory::null() {
-                device.free_memory(self.vertex_buffer_memory, None);
-            }
-            if self.pipeline != vk::Pipeline::null() {
-                device.destroy_pipeline(self.pipeline, None);
-            }
-            if self.pipeline_layout != vk::PipelineLayout::null() {
-                device.destroy_pipeline_layout(self.pipeline_layout, None);
-            }
-            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
-                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+                tick_index += 1;
+                next_tick += spacing;
             }
-        }
-    }
 
-    fn name(&self) -> &str {
-        "LinePass"
-    }
+            travelled += length;
+        }
 
-    fn should_render(&self, game: &crate::game::Game) -> bool {
-        // Only render in play mode when hologram exists
-        game.game_manager.mode == crate::game_manager::GameMode::Play
-            && game.hologram_ship_position.is_some()
+        vertices
     }
-}
 
-impl LinePass {
     /// Generate wireframe sphere for debug visualization
     /// Returns line segments (pairs of vertices)
     fn generate_wireframe_sphere(center: Vec3, radius: f32, lat_segments: usize, lon_segments: usize) -> Vec<Vec3> {
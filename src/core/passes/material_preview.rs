@@ -0,0 +1,521 @@
+use ash::vk;
+use anyhow::Result;
+use glam::{Mat4, Vec3};
+
+use crate::core::RenderPass;
+use crate::game::Game;
+use crate::mesh::PrimitiveShape;
+
+/// Width and height of the preview texture in pixels
+pub const PREVIEW_SIZE: u32 = 128;
+
+/// Color format of the preview texture (sampled by the ImGui pipeline)
+const PREVIEW_FORMAT: vk::Format = vk::Format::R8G8B8A8_UNORM;
+
+/// Push constants for the material preview shader
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MaterialPreviewPushConstants {
+    pub albedo: Vec3,
+    pub metallic: f32,
+    pub light_direction: Vec3,
+    pub roughness: f32,
+    pub light_color: Vec3,
+    pub ambient_strength: f32,
+    pub emissive: Vec3,
+    pub emissive_strength: f32,
+}
+
+/// Material editor preview pass
+///
+/// Draws the built-in sphere primitive with the material being edited into a small
+/// offscreen texture that the Material Editor shows as an ImGui image. Like the shadow
+/// pass it owns its Vulkan render pass and is recorded before the main render pass.
+/// The texture is only redrawn while the editor is open and something changed.
+pub struct MaterialPreviewPass {
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    color_image: vk::Image,
+    color_image_memory: vk::DeviceMemory,
+    color_image_view: vk::ImageView,
+    sampler: vk::Sampler,
+
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+
+    // Parameters the texture currently shows (None = needs a redraw)
+    last_push: Option<MaterialPreviewPushConstants>,
+}
+
+impl MaterialPreviewPass {
+    pub fn new() -> Self {
+        Self {
+            render_pass: vk::RenderPass::null(),
+            framebuffer: vk::Framebuffer::null(),
+            color_image: vk::Image::null(),
+            color_image_memory: vk::DeviceMemory::null(),
+            color_image_view: vk::ImageView::null(),
+            sampler: vk::Sampler::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            last_push: None,
+        }
+    }
+
+    /// Preview texture view (registered with the ImGui renderer)
+    pub fn image_view(&self) -> vk::ImageView {
+        self.color_image_view
+    }
+
+    /// Sampler for the preview texture
+    pub fn sampler(&self) -> vk::Sampler {
+        self.sampler
+    }
+
+    /// Rotation from world space into the preview camera's view space
+    /// The camera orbits the sphere at `yaw`/`pitch` (radians) under the scene's directional light
+    pub fn preview_view(yaw: f32, pitch: f32) -> Mat4 {
+        let pitch = pitch.clamp(-1.4, 1.4);
+        let eye = Vec3::new(pitch.cos() * yaw.sin(), pitch.sin(), pitch.cos() * yaw.cos());
+        Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y)
+    }
+
+    fn push_constants(game: &Game) -> MaterialPreviewPushConstants {
+        let material = &game.material;
+        let light = game.primary_directional_light();
+
+        // The shader wants the direction toward the light, in the camera's view space
+        let to_light = -light.direction;
+        let view = Self::preview_view(game.material_preview_yaw, game.material_preview_pitch);
+        let color = |srgb: Vec3| game.tonemap_config.shader_color(srgb);
+
+        MaterialPreviewPushConstants {
+            albedo: color(material.albedo),
+            metallic: material.metallic,
+            light_direction: view.transform_vector3(to_light).normalize_or_zero(),
+            roughness: material.roughness,
+            light_color: color(light.color) * light.intensity,
+            ambient_strength: material.ambient_strength,
+            emissive: color(material.emissive),
+            emissive_strength: material.emissive_strength,
+        }
+    }
+
+    unsafe fn create_target(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> Result<()> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: PREVIEW_SIZE,
+                height: PREVIEW_SIZE,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(PREVIEW_FORMAT)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        self.color_image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(self.color_image);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        self.color_image_memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(self.color_image, self.color_image_memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(self.color_image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(PREVIEW_FORMAT)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+        self.color_image_view = device.create_image_view(&view_info, None)?;
+
+        let attachments = [self.color_image_view];
+        let framebuffer_info = vk::FramebufferCreateInfo::default()
+            .render_pass(self.render_pass)
+            .attachments(&attachments)
+            .width(PREVIEW_SIZE)
+            .height(PREVIEW_SIZE)
+            .layers(1);
+        self.framebuffer = device.create_framebuffer(&framebuffer_info, None)?;
+
+        Ok(())
+    }
+
+    unsafe fn create_render_pass(device: &ash::Device) -> Result<vk::RenderPass> {
+        // Single color attachment, left in a shader-readable layout for the ImGui pipeline
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(PREVIEW_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        let dependencies = [
+            // Wait for last frame's ImGui draw to stop sampling the texture
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE),
+            // Make the preview visible to the ImGui fragment shader
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // ImGui may draw the image at a different size than the texture
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_TRANSPARENT_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<MaterialPreviewPushConstants>() as u32);
+
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let vert_shader_code = crate::core::shader::load_spirv("material_preview.vert.spv", include_bytes!("../../../shaders/material_preview.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("material_preview.frag.spv", include_bytes!("../../../shaders/material_preview.frag.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // Position and normal only (the preview has no textures, so UVs are unused)
+        let binding_description = crate::mesh::Vertex::get_binding_description();
+        let attribute_descriptions = crate::mesh::Vertex::get_attribute_descriptions();
+
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+            .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+            .vertex_attribute_descriptions(&attribute_descriptions[..2]);
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: PREVIEW_SIZE as f32,
+            height: PREVIEW_SIZE as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+
+        let scissor = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent: vk::Extent2D {
+                width: PREVIEW_SIZE,
+                height: PREVIEW_SIZE,
+            },
+        };
+
+        // The target never resizes, so the viewport is baked into the pipeline
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewports(std::slice::from_ref(&viewport))
+            .scissors(std::slice::from_ref(&scissor));
+
+        // No culling or depth test - the fragment shader discards the far hemisphere instead
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(false);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .color_blend_state(&color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create material preview pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+
+    unsafe fn find_memory_type(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        type_filter: u32,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<u32> {
+        let mem_properties = instance.get_physical_device_memory_properties(physical_device);
+
+        for i in 0..mem_properties.memory_type_count {
+            if (type_filter & (1 << i)) != 0
+                && mem_properties.memory_types[i as usize].property_flags.contains(properties)
+            {
+                return Ok(i);
+            }
+        }
+
+        Err(anyhow::anyhow!("Failed to find suitable memory type"))
+    }
+}
+
+impl RenderPass for MaterialPreviewPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Preview uses its own color-only render pass at a fixed size
+            self.render_pass = Self::create_render_pass(ctx.device)?;
+            self.create_target(ctx.instance, ctx.physical_device, ctx.device)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        // Parameters are read in `render`, after the UI has applied this frame's slider edits
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() || !game.material_editor_open {
+                return Ok(());
+            }
+
+            let push_data = Self::push_constants(game);
+            if self.last_push == Some(push_data) {
+                return Ok(()); // Texture already shows these parameters
+            }
+
+            let Some((mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) =
+                ctx.primitive_meshes.and_then(|meshes| meshes.get(&PrimitiveShape::Sphere))
+            else {
+                return Ok(());
+            };
+
+            // Match the ImGui window background so the sphere sits on the panel
+            let clear_values = [vk::ClearValue {
+                color: vk::ClearColorValue {
+                    float32: [0.06, 0.06, 0.06, 1.0],
+                },
+            }];
+
+            let render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.render_pass)
+                .framebuffer(self.framebuffer)
+                .render_area(vk::Rect2D {
+                    offset: vk::Offset2D { x: 0, y: 0 },
+                    extent: vk::Extent2D {
+                        width: PREVIEW_SIZE,
+                        height: PREVIEW_SIZE,
+                    },
+                })
+                .clear_values(&clear_values);
+
+            ctx.device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[*vertex_buffer], &[0]);
+            ctx.device.cmd_bind_index_buffer(command_buffer, *index_buffer, 0, vk::IndexType::UINT32);
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+            ctx.device.cmd_draw_indexed(command_buffer, mesh.indices.len() as u32, 1, 0, 0, 0);
+
+            ctx.device.cmd_end_render_pass(command_buffer);
+            self.last_push = Some(push_data);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Preview size is independent of the swapchain
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            // Redraw with the reloaded shaders
+            self.last_push = None;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            if self.framebuffer != vk::Framebuffer::null() {
+                device.destroy_framebuffer(self.framebuffer, None);
+            }
+            if self.color_image_view != vk::ImageView::null() {
+                device.destroy_image_view(self.color_image_view, None);
+            }
+            if self.color_image != vk::Image::null() {
+                device.destroy_image(self.color_image, None);
+                device.free_memory(self.color_image_memory, None);
+            }
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.render_pass, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Material Preview"
+    }
+}
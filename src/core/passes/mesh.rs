@@ -1,11 +1,14 @@
 use ash::vk;
 use anyhow::Result;
+use std::collections::HashMap;
 use std::ffi::CString;
 use glam::{Mat4, Vec3};
 
-use crate::core::RenderPass;
-use crate::mesh::{Mesh, Vertex};
-use crate::game::Game;
+use crate::core::{DrawStats, Frustum, RenderPass};
+use crate::core::frustum::transform_aabb;
+use crate::mesh::{Mesh, PrimitiveShape, Vertex};
+use crate::game::{DebugView, Game};
+use crate::material::{LayeredMaterial, MaterialProperties, WeatheringMask};
 
 /// Uniform buffer object shared across all mesh rendering
 #[repr(C)]
@@ -22,16 +25,18 @@ struct UniformBufferObject {
     dir_light_shadow_color: Vec3,
     star_density: f32,
     star_brightness: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
+    ssao_intensity: f32,
+    ssao_debug: u32,
+    log_depth: f32,
     nebula_primary_color: Vec3,
     nebula_intensity: f32,
     nebula_secondary_color: Vec3,
     background_brightness: f32,
     point_light_count: u32,
     ssao_enabled: u32,
-    _padding3: [u32; 2],
+    shadow_enabled: u32,
+    shadow_bias: f32,
+    light_space_matrix: Mat4,
 }
 
 /// Push constants for mesh rendering (model matrix + material properties)
@@ -44,7 +49,169 @@ pub struct MeshPushConstants {
     pub roughness: f32,
     pub ambient_strength: f32,
     pub gi_strength: f32,
-    pub _padding: f32,
+    /// Bitmask of bound material textures (see `core::texture::TEXTURE_FLAG_*`)
+    pub texture_flags: u32,
+    pub emissive: Vec3,
+    pub emissive_strength: f32,
+    /// Alpha written by mesh.frag (only blended by the transparent pipeline)
+    pub opacity: f32,
+    /// Weathering layer packed to fit the 128 push constant bytes every GPU has (see `pack_layer`)
+    pub layer: [u32; 3],
+}
+
+/// Per-instance data for batched draws (same layout as the push constants)
+pub type MeshInstanceData = MeshPushConstants;
+
+impl MeshPushConstants {
+    /// `srgb_colors` linearizes the material's picked (sRGB) albedo and emissive colors
+    pub fn new(model: Mat4, material: &MaterialProperties, texture_flags: u32, srgb_colors: bool) -> Self {
+        let color = |srgb: Vec3| if srgb_colors { crate::core::color::srgb_to_linear_rgb(srgb) } else { srgb };
+        let (layer_flags, layer) = match &material.layer {
+            Some(layer) => (Self::layer_flags(layer.mask), Self::pack_layer(layer, color(layer.overlay.albedo))),
+            None => (0, [0; 3]),
+        };
+        Self {
+            model,
+            albedo: color(material.albedo),
+            metallic: material.metallic,
+            roughness: material.roughness,
+            ambient_strength: material.ambient_strength,
+            gi_strength: material.gi_strength,
+            texture_flags: texture_flags | layer_flags,
+            emissive: color(material.emissive),
+            emissive_strength: material.emissive_strength,
+            // Opaque materials always write full alpha
+            opacity: if material.transparent { material.opacity } else { 1.0 },
+            layer,
+        }
+    }
+
+    fn layer_flags(mask: WeatheringMask) -> u32 {
+        use crate::core::texture::{TEXTURE_FLAG_LAYER, TEXTURE_FLAG_LAYER_SSAO, TEXTURE_FLAG_LAYER_VERTEX};
+        match mask {
+            WeatheringMask::Noise => TEXTURE_FLAG_LAYER,
+            WeatheringMask::AmbientOcclusion => TEXTURE_FLAG_LAYER | TEXTURE_FLAG_LAYER_SSAO,
+            WeatheringMask::VertexOcclusion => TEXTURE_FLAG_LAYER | TEXTURE_FLAG_LAYER_VERTEX,
+        }
+    }
+
+    /// Layout read by mesh.frag with GLSL's unpack functions:
+    /// [unorm4x8(albedo, metallic), unorm2x16(roughness, coverage), unorm2x16(blend width, noise scale / max)]
+    fn pack_layer(layer: &LayeredMaterial, overlay_albedo: Vec3) -> [u32; 3] {
+        let unorm8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u32;
+        let unorm16 = |value: f32| (value.clamp(0.0, 1.0) * 65535.0).round() as u32;
+        let overlay = &layer.overlay;
+        [
+            unorm8(overlay_albedo.x)
+                | unorm8(overlay_albedo.y) << 8
+                | unorm8(overlay_albedo.z) << 16
+                | unorm8(overlay.metallic) << 24,
+            unorm16(overlay.roughness) | unorm16(layer.coverage) << 16,
+            unorm16(layer.blend_width) | unorm16(layer.noise_scale / LayeredMaterial::MAX_NOISE_SCALE) << 16,
+        ]
+    }
+
+    /// Instance buffer binding (binding 1, advanced per instance)
+    pub fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::default()
+            .binding(1)
+            .stride(std::mem::size_of::<MeshInstanceData>() as u32)
+            .input_rate(vk::VertexInputRate::INSTANCE)
+    }
+
+    /// Instance attributes for mesh_instanced.vert (locations 3-10, and 13 after the vertex attributes)
+    pub fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 9] {
+        let vec4 = std::mem::size_of::<glam::Vec4>() as u32;
+        [
+            // Model matrix columns
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(3)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(0),
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(4)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(vec4),
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(5)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(vec4 * 2),
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(6)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(vec4 * 3),
+            // Albedo + metallic
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(7)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(vec4 * 4),
+            // Roughness, ambient strength, GI strength
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(8)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(vec4 * 5),
+            // Texture flags
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(9)
+                .format(vk::Format::R32_UINT)
+                .offset(vec4 * 5 + 12),
+            // Emissive color + strength
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(10)
+                .format(vk::Format::R32G32B32A32_SFLOAT)
+                .offset(vec4 * 6),
+            // Packed weathering layer (after opacity, which instances don't use)
+            vk::VertexInputAttributeDescription::default()
+                .binding(1)
+                .location(13)
+                .format(vk::Format::R32G32B32_UINT)
+                .offset(vec4 * 7 + 4),
+        ]
+    }
+}
+
+/// Mesh pipelines used with the depth prepass (created by the renderer, same layout as the mesh pipeline)
+/// Both passes run the same vertex shaders, so the shaded pass's depth matches the prepass exactly.
+#[derive(Debug, Clone, Copy)]
+pub struct DepthPrepassPipelines {
+    /// Depth-only, no fragment shader
+    pub depth: vk::Pipeline,
+    pub depth_instanced: vk::Pipeline,
+    /// Shaded with `CompareOp::EQUAL` and no depth writes: only the visible surface runs the fragment shader
+    pub shade: vk::Pipeline,
+    pub shade_instanced: vk::Pipeline,
+    /// Overdraw debug view with `CompareOp::EQUAL`, counting the fragments actually shaded
+    pub overdraw: vk::Pipeline,
+}
+
+impl DepthPrepassPipelines {
+    pub unsafe fn destroy(&self, device: &ash::Device) {
+        for pipeline in [self.depth, self.depth_instanced, self.shade, self.shade_instanced, self.overdraw] {
+            device.destroy_pipeline(pipeline, None);
+        }
+    }
+}
+
+/// Opaque draws grouped by (mesh, material), with their first instance when drawn instanced
+type Batch<'a, 'm> = (MeshKey<'a>, &'m MaterialProperties, std::ops::Range<usize>, Option<u32>);
+
+/// Flat albedo per LOD level in the LOD debug view (full detail, LOD 1, LOD 2)
+const LOD_DEBUG_COLORS: [Vec3; 3] = [Vec3::new(0.9, 0.9, 0.9), Vec3::new(1.0, 0.8, 0.1), Vec3::new(1.0, 0.2, 0.1)];
+
+/// Which vertex/index buffers a draw uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MeshKey<'a> {
+    Cube,
+    Primitive(PrimitiveShape),
+    Custom(&'a str),
 }
 
 pub struct MeshPass {
@@ -59,12 +226,36 @@ pub struct MeshPass {
     pipeline: vk::Pipeline,
     pipeline_layout: vk::PipelineLayout,
     descriptor_sets: Vec<vk::DescriptorSet>,
+
+    // Frustum culling
+    cube_bounds: (Vec3, Vec3),
+    mesh_bounds: HashMap<String, (Vec3, Vec3)>,
+    primitive_bounds: HashMap<PrimitiveShape, (Vec3, Vec3)>,
+    draw_stats: DrawStats,
+
+    // Instancing: pipeline borrowed from renderer, per-frame instance buffers grown on demand
+    instanced_pipeline: vk::Pipeline,
+    instance_buffers: Vec<vk::Buffer>,
+    instance_buffers_memory: Vec<vk::DeviceMemory>,
+    instance_capacity: Vec<usize>,
+
+    // Transparent materials: blended pipeline borrowed from renderer (no depth writes)
+    transparent_pipeline: vk::Pipeline,
+    // Overdraw debug view: additive pipeline without depth test
+    overdraw_pipeline: vk::Pipeline,
+    // Depth prepass variants (None if the renderer didn't create them)
+    prepass_pipelines: Option<DepthPrepassPipelines>,
+
+    // Color policy for this frame's draws (`TonemapConfig::srgb_colors`)
+    srgb_colors: bool,
 }
 
 impl MeshPass {
-    pub fn new() -> Self {
+    pub fn new(max_frames_in_flight: usize) -> Self {
+        let cube_mesh = Mesh::create_cube();
+        let cube_bounds = cube_mesh.calculate_bounds();
         Self {
-            cube_mesh: Mesh::create_cube(),
+            cube_mesh,
             cube_vertex_buffer: vk::Buffer::null(),
             cube_vertex_buffer_memory: vk::DeviceMemory::null(),
             cube_index_buffer: vk::Buffer::null(),
@@ -72,9 +263,89 @@ impl MeshPass {
             pipeline: vk::Pipeline::null(),
             pipeline_layout: vk::PipelineLayout::null(),
             descriptor_sets: Vec::new(),
+            cube_bounds,
+            mesh_bounds: HashMap::new(),
+            primitive_bounds: HashMap::new(),
+            draw_stats: DrawStats::default(),
+            instanced_pipeline: vk::Pipeline::null(),
+            instance_buffers: vec![vk::Buffer::null(); max_frames_in_flight],
+            instance_buffers_memory: vec![vk::DeviceMemory::null(); max_frames_in_flight],
+            instance_capacity: vec![0; max_frames_in_flight],
+            transparent_pipeline: vk::Pipeline::null(),
+            overdraw_pipeline: vk::Pipeline::null(),
+            prepass_pipelines: None,
+            srgb_colors: true,
         }
     }
 
+    /// Copy instance data into this frame's instance buffer, growing it if needed.
+    /// Safe to reallocate: the renderer waits on this frame's fence before recording.
+    unsafe fn upload_instances(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        frame_index: usize,
+        instances: &[MeshInstanceData],
+    ) -> Result<()> {
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        if instances.len() > self.instance_capacity[frame_index] {
+            if self.instance_buffers[frame_index] != vk::Buffer::null() {
+                ctx.device.destroy_buffer(self.instance_buffers[frame_index], None);
+                ctx.device.free_memory(self.instance_buffers_memory[frame_index], None);
+            }
+
+            let capacity = instances.len().next_power_of_two().max(256);
+            let (buffer, memory) = Self::create_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                (capacity * std::mem::size_of::<MeshInstanceData>()) as vk::DeviceSize,
+                vk::BufferUsageFlags::VERTEX_BUFFER,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )?;
+            self.instance_buffers[frame_index] = buffer;
+            self.instance_buffers_memory[frame_index] = memory;
+            self.instance_capacity[frame_index] = capacity;
+        }
+
+        let bytes: &[u8] = bytemuck::cast_slice(instances);
+        let data = ctx.device.map_memory(
+            self.instance_buffers_memory[frame_index],
+            0,
+            bytes.len() as vk::DeviceSize,
+            vk::MemoryMapFlags::empty(),
+        )?;
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, bytes.len());
+        ctx.device.unmap_memory(self.instance_buffers_memory[frame_index]);
+
+        Ok(())
+    }
+
+    /// Build the camera frustum in render space (relative to the camera origin)
+    /// so culling stays precise far from the world origin
+    fn render_space_frustum(game: &Game, extent: vk::Extent2D) -> Frustum {
+        let aspect = extent.width as f32 / extent.height.max(1) as f32;
+        let proj = game.camera.projection_matrix(aspect);
+        Frustum::from_matrix(proj * game.get_view_matrix())
+    }
+
+    /// Test a local-space AABB under a render-space model matrix against the frustum
+    fn is_visible(frustum: &Frustum, model: Mat4, bounds: (Vec3, Vec3)) -> bool {
+        let (min, max) = transform_aabb(model, bounds.0, bounds.1);
+        frustum.intersects_aabb(min, max)
+    }
+
+    /// Material to draw with: the object's own, or the LOD debug view's flat color for `level`
+    fn lod_material<'a>(
+        debug_materials: Option<&'a [MaterialProperties; 3]>,
+        level: usize,
+        material: &'a MaterialProperties,
+    ) -> &'a MaterialProperties {
+        debug_materials.map_or(material, |materials| &materials[level])
+    }
+
     /// Set pipeline resources from renderer (called during initialization)
     pub fn set_pipeline_resources(
         &mut self,
@@ -87,7 +358,7 @@ impl MeshPass {
         self.descriptor_sets = descriptor_sets;
     }
 
-    unsafe fn create_vertex_buffer(
+    pub(crate) unsafe fn create_vertex_buffer(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
         device: &ash::Device,
@@ -133,7 +404,7 @@ impl MeshPass {
         Ok((vertex_buffer, vertex_memory))
     }
 
-    unsafe fn create_index_buffer(
+    pub(crate) unsafe fn create_index_buffer(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
         device: &ash::Device,
@@ -178,6 +449,120 @@ impl MeshPass {
         Ok((index_buffer, index_memory))
     }
 
+    /// Vertex buffer, index buffer and index count for a mesh, if it is loaded
+    fn mesh_buffers(&self, ctx: &crate::core::RenderContext, mesh_key: &MeshKey) -> Option<(vk::Buffer, vk::Buffer, u32)> {
+        match mesh_key {
+            MeshKey::Cube => Some((self.cube_vertex_buffer, self.cube_index_buffer, self.cube_mesh.indices.len() as u32)),
+            MeshKey::Primitive(shape) => ctx.primitive_meshes.and_then(|meshes| meshes.get(shape))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (*vertex_buffer, *index_buffer, mesh.indices.len() as u32)),
+            MeshKey::Custom(path) => ctx.custom_meshes.and_then(|meshes| meshes.get(*path))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (*vertex_buffer, *index_buffer, mesh.indices.len() as u32)),
+        }
+    }
+
+    /// Bind a material's texture set (set = 1) if it differs from the bound one, returning its texture flags
+    unsafe fn bind_material_textures(
+        &self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        material: &MaterialProperties,
+        bound_set: &mut vk::DescriptorSet,
+    ) -> u32 {
+        let Some(textures) = ctx.textures else {
+            return 0;
+        };
+
+        let (texture_set, texture_flags) = textures.material_binding(material);
+        if texture_set != *bound_set {
+            ctx.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline_layout,
+                1,
+                &[texture_set],
+                &[],
+            );
+            *bound_set = texture_set;
+        }
+        texture_flags
+    }
+
+    /// Record the opaque batches with `pipelines` (single, instanced)
+    /// Material textures are bound only when `bound_texture_set` is given (the depth-only
+    /// prepass doesn't sample them).
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn record_batches(
+        &self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        frame_index: usize,
+        draws: &[(MeshKey, Mat4, &MaterialProperties)],
+        batches: &[Batch],
+        pipelines: (vk::Pipeline, vk::Pipeline),
+        mut bound_texture_set: Option<&mut vk::DescriptorSet>,
+        stats: &mut DrawStats,
+    ) {
+        let mut bound_pipeline = vk::Pipeline::null();
+
+        for (mesh_key, material, range, first_instance) in batches.iter() {
+            let Some((vertex_buffer, index_buffer, index_count)) = self.mesh_buffers(ctx, mesh_key) else {
+                continue;
+            };
+
+            let pipeline = if first_instance.is_some() { pipelines.1 } else { pipelines.0 };
+            if pipeline != bound_pipeline {
+                ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline);
+                bound_pipeline = pipeline;
+            }
+
+            let texture_flags = match bound_texture_set.as_deref_mut() {
+                Some(bound_set) => self.bind_material_textures(ctx, command_buffer, material, bound_set),
+                None => 0,
+            };
+            ctx.device.cmd_bind_index_buffer(command_buffer, index_buffer, 0, vk::IndexType::UINT32);
+
+            match first_instance {
+                Some(first_instance) => {
+                    // One draw for the whole batch, model + material come from the instance buffer
+                    let vertex_buffers = [vertex_buffer, self.instance_buffers[frame_index]];
+                    let offsets = [0, 0];
+                    ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &offsets);
+                    ctx.device.cmd_draw_indexed(command_buffer, index_count, range.len() as u32, 0, 0, *first_instance);
+                    stats.record_draw(index_count, range.len() as u32);
+                }
+                None => {
+                    let vertex_buffers = [vertex_buffer];
+                    let offsets = [0];
+                    ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &offsets);
+                    for (_, model_matrix, _) in draws[range.clone()].iter() {
+                        self.push_material(ctx, command_buffer, *model_matrix, material, texture_flags);
+                        ctx.device.cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
+                        stats.record_draw(index_count, 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Push model matrix and material properties for the next draw
+    unsafe fn push_material(
+        &self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        model: Mat4,
+        material: &MaterialProperties,
+        texture_flags: u32,
+    ) {
+        let push_data = MeshPushConstants::new(model, material, texture_flags, self.srgb_colors);
+        ctx.device.cmd_push_constants(
+            command_buffer,
+            self.pipeline_layout,
+            vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+            0,
+            bytemuck::bytes_of(&push_data),
+        );
+    }
+
     unsafe fn create_buffer(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
@@ -280,6 +665,10 @@ impl RenderPass for MeshPass {
                 self.pipeline_layout = pipeline_layout;
                 self.descriptor_sets = descriptor_sets.to_vec();
             }
+            self.instanced_pipeline = ctx.mesh_instanced_pipeline.unwrap_or(vk::Pipeline::null());
+            self.transparent_pipeline = ctx.mesh_transparent_pipeline.unwrap_or(vk::Pipeline::null());
+            self.overdraw_pipeline = ctx.mesh_overdraw_pipeline.unwrap_or(vk::Pipeline::null());
+            self.prepass_pipelines = ctx.mesh_prepass_pipelines;
 
             // Create cube mesh buffers
             let (cube_vb, cube_vb_mem) = Self::create_vertex_buffer(
@@ -330,14 +719,7 @@ impl RenderPass for MeshPass {
                 return Ok(()); // Not initialized yet
             }
 
-            // Bind graphics pipeline
-            ctx.device.cmd_bind_pipeline(
-                command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
-                self.pipeline,
-            );
-
-            // Bind descriptor set
+            // Bind descriptor set (shared by the single and instanced pipelines)
             ctx.device.cmd_bind_descriptor_sets(
                 command_buffer,
                 vk::PipelineBindPoint::GRAPHICS,
@@ -347,80 +729,176 @@ impl RenderPass for MeshPass {
                 &[],
             );
 
-            // 1. Render cubes
-            let visible_cubes = game.get_visible_cubes();
-            if !visible_cubes.is_empty() {
-                // Bind cube buffers once for all cubes
-                let vertex_buffers = [self.cube_vertex_buffer];
-                let offsets = [0];
-                ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &offsets);
-                ctx.device.cmd_bind_index_buffer(command_buffer, self.cube_index_buffer, 0, vk::IndexType::UINT32);
-
-                let indices_per_cube = self.cube_mesh.indices.len() as u32;
-
-                // Render each cube with push constants
-                for model_matrix in visible_cubes.iter() {
-                    let push_data = MeshPushConstants {
-                        model: *model_matrix,
-                        albedo: game.material.albedo,
-                        metallic: game.material.metallic,
-                        roughness: game.material.roughness,
-                        ambient_strength: game.material.ambient_strength,
-                        gi_strength: game.material.gi_strength,
-                        _padding: 0.0,
-                    };
-                    let push_constants = bytemuck::bytes_of(&push_data);
-                    ctx.device.cmd_push_constants(
-                        command_buffer,
-                        self.pipeline_layout,
-                        vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
-                        0,
-                        push_constants,
-                    );
-
-                    ctx.device.cmd_draw_indexed(command_buffer, indices_per_cube, 1, 0, 0, 0);
+            let frustum = Self::render_space_frustum(game, ctx.extent);
+            let mut stats = DrawStats::default();
+            self.srgb_colors = game.tonemap_config.srgb_colors;
+
+            // The LOD debug view swaps every material for a flat color per level
+            let lod_debug_materials = (game.debug_view == DebugView::Lod)
+                .then(|| LOD_DEBUG_COLORS.map(|albedo| MaterialProperties { albedo, ..Default::default() }));
+
+            // 1. Gather visible objects that survive frustum culling
+            // (model matrices are moved into render space first, see `Game::render_matrix`)
+            let mut draws: Vec<(MeshKey, Mat4, &MaterialProperties)> = Vec::new();
+
+            for (model_matrix, material) in game.get_visible_cubes_with_materials() {
+                let model_matrix = game.render_matrix(model_matrix);
+                if Self::is_visible(&frustum, model_matrix, self.cube_bounds) {
+                    let material = Self::lod_material(lod_debug_materials.as_ref(), 0, material);
+                    draws.push((MeshKey::Cube, model_matrix, material));
+                } else {
+                    stats.culled += 1;
+                }
+            }
+
+            // Built-in primitives share buffers created by the renderer
+            if let Some(primitive_meshes) = ctx.primitive_meshes {
+                for (shape, model_matrix, material) in game.get_visible_primitives_with_materials() {
+                    if let Some((mesh, ..)) = primitive_meshes.get(&shape) {
+                        let bounds = *self
+                            .primitive_bounds
+                            .entry(shape)
+                            .or_insert_with(|| mesh.calculate_bounds());
+                        let model_matrix = game.render_matrix(model_matrix);
+                        if Self::is_visible(&frustum, model_matrix, bounds) {
+                            let material = Self::lod_material(lod_debug_materials.as_ref(), 0, material);
+                            draws.push((MeshKey::Primitive(shape), model_matrix, material));
+                        } else {
+                            stats.culled += 1;
+                        }
+                    }
                 }
             }
 
-            // 2. Render custom meshes (loaded by renderer)
-            let visible_meshes = game.get_visible_meshes();
-            if !visible_meshes.is_empty() {
-                if let Some(custom_meshes) = ctx.custom_meshes {
-                    for (mesh_path, model_matrix) in visible_meshes.iter() {
-                        if let Some((mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) = custom_meshes.get(mesh_path) {
-                            // Bind this mesh's buffers
-                            let vertex_buffers = [*vertex_buffer];
-                            let offsets = [0];
-                            ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &offsets);
-                            ctx.device.cmd_bind_index_buffer(command_buffer, *index_buffer, 0, vk::IndexType::UINT32);
-
-                            // Push constants
-                            let push_data = MeshPushConstants {
-                                model: *model_matrix,
-                                albedo: game.material.albedo,
-                                metallic: game.material.metallic,
-                                roughness: game.material.roughness,
-                                ambient_strength: game.material.ambient_strength,
-                                gi_strength: game.material.gi_strength,
-                                _padding: 0.0,
-                            };
-                            let push_constants = bytemuck::bytes_of(&push_data);
-                            ctx.device.cmd_push_constants(
-                                command_buffer,
-                                self.pipeline_layout,
-                                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
-                                0,
-                                push_constants,
-                            );
-
-                            ctx.device.cmd_draw_indexed(command_buffer, mesh.indices.len() as u32, 1, 0, 0, 0);
+            // Custom meshes are loaded by the renderer, along with simplified LOD levels for heavy ones
+            let visible_meshes = game.get_visible_meshes_with_materials();
+            let camera_position = game.get_camera_position();
+            if let Some(custom_meshes) = ctx.custom_meshes {
+                for (mesh_path, model_matrix, material) in visible_meshes.iter() {
+                    if let Some((mesh, ..)) = custom_meshes.get(mesh_path) {
+                        let bounds = *self
+                            .mesh_bounds
+                            .entry(mesh_path.clone())
+                            .or_insert_with(|| mesh.calculate_bounds());
+                        let model_matrix = game.render_matrix(*model_matrix);
+                        if Self::is_visible(&frustum, model_matrix, bounds) {
+                            // Coarsest generated level up to the one wanted at this distance
+                            let distance = model_matrix.w_axis.truncate().distance(camera_position);
+                            let (level, key) = (1..=game.lod_config.level_for_distance(distance))
+                                .rev()
+                                .find_map(|level| {
+                                    custom_meshes
+                                        .get_key_value(&crate::mesh::lod_key(mesh_path, level))
+                                        .map(|(key, _)| (level, key.as_str()))
+                                })
+                                .unwrap_or((0, mesh_path.as_str()));
+                            let material = Self::lod_material(lod_debug_materials.as_ref(), level, material);
+                            draws.push((MeshKey::Custom(key), model_matrix, material));
+                        } else {
+                            stats.culled += 1;
                         }
                     }
                 }
             }
+            stats.drawn = draws.len() as u32;
+
+            // Transparent materials are held back for a blended pass after everything opaque
+            let (mut transparent_draws, mut draws): (Vec<_>, Vec<_>) = draws
+                .into_iter()
+                .partition(|(_, _, material)| material.transparent && self.transparent_pipeline != vk::Pipeline::null());
+
+            // 2. Sort by (mesh, material) and group into batches
+            //    Objects using the same library material share the same reference
+            draws.sort_by(|a, b| {
+                a.0.cmp(&b.0).then_with(|| {
+                    (a.2 as *const MaterialProperties).cmp(&(b.2 as *const MaterialProperties))
+                })
+            });
+
+            let mut batches: Vec<Batch> = Vec::new();
+            for (i, (mesh_key, _, material)) in draws.iter().enumerate() {
+                match batches.last_mut() {
+                    Some((key, batch_material, range, _)) if key == mesh_key && std::ptr::eq(*batch_material, *material) => {
+                        range.end = i + 1;
+                    }
+                    _ => batches.push((*mesh_key, *material, i..i + 1, None)),
+                }
+            }
+
+            // The overdraw debug view draws everything one by one with its additive pipeline
+            let overdraw = game.debug_view == DebugView::Overdraw && self.overdraw_pipeline != vk::Pipeline::null();
+
+            // 3. Upload per-instance data for batches with more than one object
+            //    (unique objects keep the push-constant path)
+            let mut instances: Vec<MeshInstanceData> = Vec::new();
+            for (_, material, range, first_instance) in batches.iter_mut() {
+                if range.len() > 1 && self.instanced_pipeline != vk::Pipeline::null() && !overdraw {
+                    *first_instance = Some(instances.len() as u32);
+                    let texture_flags = ctx.textures
+                        .map(|textures| textures.material_binding(material).1)
+                        .unwrap_or(0);
+                    instances.extend(draws[range.clone()].iter().map(|(_, model_matrix, _)| {
+                        MeshInstanceData::new(*model_matrix, material, texture_flags, self.srgb_colors)
+                    }));
+                }
+            }
+            self.upload_instances(ctx, frame_index, &instances)?;
+
+            // Material texture set (set = 1) currently bound
+            let mut bound_texture_set = vk::DescriptorSet::null();
+
+            // 4. Record draws, after laying down depth first when the prepass is on
+            let prepass = self.prepass_pipelines.filter(|_| game.graphics_config.depth_prepass);
+            let (pipeline, instanced_pipeline) = match prepass {
+                Some(prepass) => {
+                    let pipelines = (prepass.depth, prepass.depth_instanced);
+                    self.record_batches(ctx, command_buffer, frame_index, &draws, &batches, pipelines, None, &mut stats);
+                    if overdraw {
+                        (prepass.overdraw, prepass.overdraw)
+                    } else {
+                        (prepass.shade, prepass.shade_instanced)
+                    }
+                }
+                None if overdraw => (self.overdraw_pipeline, self.overdraw_pipeline),
+                None => (self.pipeline, self.instanced_pipeline),
+            };
+            self.record_batches(
+                ctx,
+                command_buffer,
+                frame_index,
+                &draws,
+                &batches,
+                (pipeline, instanced_pipeline),
+                Some(&mut bound_texture_set),
+                &mut stats,
+            );
+
+            // 5. Transparent objects, back to front (render space keeps distances small and precise)
+            if !transparent_draws.is_empty() {
+                let camera_position = game.get_camera_position();
+                let distance = |model: &Mat4| model.w_axis.truncate().distance_squared(camera_position);
+                transparent_draws.sort_by(|a, b| distance(&b.1).total_cmp(&distance(&a.1)));
+
+                let pipeline = if overdraw { self.overdraw_pipeline } else { self.transparent_pipeline };
+                ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline);
+                for (mesh_key, model_matrix, material) in transparent_draws.iter() {
+                    let Some((vertex_buffer, index_buffer, index_count)) = self.mesh_buffers(ctx, mesh_key) else {
+                        continue;
+                    };
+
+                    let texture_flags = self.bind_material_textures(ctx, command_buffer, material, &mut bound_texture_set);
+                    ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer], &[0]);
+                    ctx.device.cmd_bind_index_buffer(command_buffer, index_buffer, 0, vk::IndexType::UINT32);
+                    self.push_material(ctx, command_buffer, *model_matrix, material, texture_flags);
+                    ctx.device.cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
+                    stats.record_draw(index_count, 1);
+                }
+            }
 
             // Note: Spheres (stars) are rendered by the renderer using the star shader pipeline, not here
 
+            self.draw_stats = stats;
+
             Ok(())
         }
     }
@@ -438,6 +916,10 @@ impl RenderPass for MeshPass {
             self.pipeline_layout = pipeline_layout;
             self.descriptor_sets = descriptor_sets.to_vec();
         }
+        self.instanced_pipeline = ctx.mesh_instanced_pipeline.unwrap_or(vk::Pipeline::null());
+        self.transparent_pipeline = ctx.mesh_transparent_pipeline.unwrap_or(vk::Pipeline::null());
+        self.overdraw_pipeline = ctx.mesh_overdraw_pipeline.unwrap_or(vk::Pipeline::null());
+        self.prepass_pipelines = ctx.mesh_prepass_pipelines;
         Ok(())
     }
 
@@ -452,13 +934,31 @@ impl RenderPass for MeshPass {
                 device.destroy_buffer(self.cube_index_buffer, None);
                 device.free_memory(self.cube_index_buffer_memory, None);
             }
+            for (buffer, memory) in self.instance_buffers.iter().zip(self.instance_buffers_memory.iter()) {
+                if *buffer != vk::Buffer::null() {
+                    device.destroy_buffer(*buffer, None);
+                    device.free_memory(*memory, None);
+                }
+            }
 
             // Custom meshes are owned and cleaned up by renderer
             // Spheres (stars) are owned and rendered by the renderer with the star shader
+            // Pipelines come from the renderer's RenderContext and are destroyed there
         }
+        self.cube_vertex_buffer = vk::Buffer::null();
+        self.cube_vertex_buffer_memory = vk::DeviceMemory::null();
+        self.cube_index_buffer = vk::Buffer::null();
+        self.cube_index_buffer_memory = vk::DeviceMemory::null();
+        self.instance_buffers.fill(vk::Buffer::null());
+        self.instance_buffers_memory.fill(vk::DeviceMemory::null());
+        self.instance_capacity.fill(0);
     }
 
     fn name(&self) -> &str {
         "Mesh"
     }
+
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
 }
@@ -0,0 +1,515 @@
+use ash::vk;
+use anyhow::Result;
+use glam::{Mat4, Vec2};
+
+use crate::core::{RenderPass, ResourceManager};
+use crate::game::Game;
+use crate::minimap::{MinimapMarker, MinimapView, MAX_MARKERS, MINIMAP_SIZE};
+
+/// Color format of the minimap texture (sampled by the ImGui pipeline)
+const MINIMAP_FORMAT: vk::Format = vk::Format::R8G8B8A8_UNORM;
+
+/// Push constants for the minimap marker shader
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MinimapPushConstants {
+    pub view_proj: Mat4,
+    /// Size of one minimap pixel in clip space (markers are sized in pixels)
+    pub pixel_size: Vec2,
+    pub _padding: Vec2,
+}
+
+/// Tactical minimap pass
+///
+/// Draws units and scene content as dots, seen by the top-down orthographic camera of
+/// `MinimapView`, into an offscreen texture that the Minimap window shows as an ImGui
+/// image. No meshes are drawn: each marker is an instanced quad the fragment shader
+/// rounds into a disc. Like the material preview it owns its Vulkan render pass and is
+/// recorded before the main render pass, and only while the window is open.
+pub struct MinimapPass {
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    color_image: vk::Image,
+    color_image_memory: vk::DeviceMemory,
+    color_image_view: vk::ImageView,
+    sampler: vk::Sampler,
+
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+
+    // Per-frame marker instance buffers (host visible, `MAX_MARKERS` each)
+    max_frames_in_flight: usize,
+    marker_buffers: Vec<vk::Buffer>,
+    marker_buffers_memory: Vec<vk::DeviceMemory>,
+}
+
+impl MinimapPass {
+    pub fn new(max_frames_in_flight: usize) -> Self {
+        Self {
+            render_pass: vk::RenderPass::null(),
+            framebuffer: vk::Framebuffer::null(),
+            color_image: vk::Image::null(),
+            color_image_memory: vk::DeviceMemory::null(),
+            color_image_view: vk::ImageView::null(),
+            sampler: vk::Sampler::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            max_frames_in_flight,
+            marker_buffers: Vec::new(),
+            marker_buffers_memory: Vec::new(),
+        }
+    }
+
+    /// Minimap texture view (registered with the ImGui renderer)
+    pub fn image_view(&self) -> vk::ImageView {
+        self.color_image_view
+    }
+
+    /// Sampler for the minimap texture
+    pub fn sampler(&self) -> vk::Sampler {
+        self.sampler
+    }
+
+    unsafe fn create_target(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> Result<()> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: MINIMAP_SIZE,
+                height: MINIMAP_SIZE,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(MINIMAP_FORMAT)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        self.color_image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(self.color_image);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(ResourceManager::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        self.color_image_memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(self.color_image, self.color_image_memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(self.color_image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(MINIMAP_FORMAT)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+        self.color_image_view = device.create_image_view(&view_info, None)?;
+
+        let attachments = [self.color_image_view];
+        let framebuffer_info = vk::FramebufferCreateInfo::default()
+            .render_pass(self.render_pass)
+            .attachments(&attachments)
+            .width(MINIMAP_SIZE)
+            .height(MINIMAP_SIZE)
+            .layers(1);
+        self.framebuffer = device.create_framebuffer(&framebuffer_info, None)?;
+
+        Ok(())
+    }
+
+    unsafe fn create_render_pass(device: &ash::Device) -> Result<vk::RenderPass> {
+        // Single color attachment, left in a shader-readable layout for the ImGui pipeline
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(MINIMAP_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        let dependencies = [
+            // Wait for last frame's ImGui draw to stop sampling the texture
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE),
+            // Make the minimap visible to the ImGui fragment shader
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // ImGui may draw the image at a different size than the texture
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_TRANSPARENT_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::VERTEX)
+            .offset(0)
+            .size(std::mem::size_of::<MinimapPushConstants>() as u32);
+
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let vert_shader_code = crate::core::shader::load_spirv("minimap.vert.spv", include_bytes!("../../../shaders/minimap.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("minimap.frag.spv", include_bytes!("../../../shaders/minimap.frag.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // One marker per instance; the quad corners come from gl_VertexIndex
+        let binding_description = vk::VertexInputBindingDescription::default()
+            .binding(0)
+            .stride(std::mem::size_of::<MinimapMarker>() as u32)
+            .input_rate(vk::VertexInputRate::INSTANCE);
+
+        let attribute_descriptions = [
+            // position
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(0)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(0),
+            // size
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(1)
+                .format(vk::Format::R32_SFLOAT)
+                .offset(12),
+            // color
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(2)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(16),
+        ];
+
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+            .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+            .vertex_attribute_descriptions(&attribute_descriptions);
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: MINIMAP_SIZE as f32,
+            height: MINIMAP_SIZE as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+
+        let scissor = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent: vk::Extent2D {
+                width: MINIMAP_SIZE,
+                height: MINIMAP_SIZE,
+            },
+        };
+
+        // The target never resizes, so the viewport is baked into the pipeline
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewports(std::slice::from_ref(&viewport))
+            .scissors(std::slice::from_ref(&scissor));
+
+        // No depth test - markers are drawn in order, so the camera marker ends up on top
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(false);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .color_blend_state(&color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create minimap pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+
+    /// Copy this frame's markers into its instance buffer
+    unsafe fn upload_markers(&self, device: &ash::Device, frame_index: usize, markers: &[MinimapMarker]) -> Result<()> {
+        let memory = self.marker_buffers_memory[frame_index];
+        let bytes: &[u8] = bytemuck::cast_slice(markers);
+        let data = device.map_memory(memory, 0, bytes.len() as vk::DeviceSize, vk::MemoryMapFlags::empty())?;
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, bytes.len());
+        device.unmap_memory(memory);
+        Ok(())
+    }
+}
+
+impl RenderPass for MinimapPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Minimap uses its own color-only render pass at a fixed size
+            self.render_pass = Self::create_render_pass(ctx.device)?;
+            self.create_target(ctx.instance, ctx.physical_device, ctx.device)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            for _ in 0..self.max_frames_in_flight {
+                let (buffer, memory) = ResourceManager::create_buffer(
+                    ctx.instance,
+                    ctx.physical_device,
+                    ctx.device,
+                    (MAX_MARKERS * std::mem::size_of::<MinimapMarker>()) as vk::DeviceSize,
+                    vk::BufferUsageFlags::VERTEX_BUFFER,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                )?;
+                self.marker_buffers.push(buffer);
+                self.marker_buffers_memory.push(memory);
+            }
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        // Markers are gathered in `render`, after this frame's minimap click has moved the camera
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() || !game.show_minimap {
+                return Ok(());
+            }
+
+            let view = MinimapView::for_game(game);
+            let markers = crate::minimap::markers(game, &view);
+            self.upload_markers(ctx.device, frame_index, &markers)?;
+
+            let push_data = MinimapPushConstants {
+                view_proj: view.view_projection(),
+                pixel_size: Vec2::splat(2.0 / MINIMAP_SIZE as f32),
+                _padding: Vec2::ZERO,
+            };
+
+            // Dark navy backdrop so the markers read against the panel
+            let clear_values = [vk::ClearValue {
+                color: vk::ClearColorValue {
+                    float32: [0.03, 0.04, 0.07, 1.0],
+                },
+            }];
+
+            let render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.render_pass)
+                .framebuffer(self.framebuffer)
+                .render_area(vk::Rect2D {
+                    offset: vk::Offset2D { x: 0, y: 0 },
+                    extent: vk::Extent2D {
+                        width: MINIMAP_SIZE,
+                        height: MINIMAP_SIZE,
+                    },
+                })
+                .clear_values(&clear_values);
+
+            ctx.device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[self.marker_buffers[frame_index]], &[0]);
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::VERTEX,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+            ctx.device.cmd_draw(command_buffer, 6, markers.len() as u32, 0, 0);
+
+            ctx.device.cmd_end_render_pass(command_buffer);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Minimap size is independent of the swapchain
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            for (buffer, memory) in self.marker_buffers.drain(..).zip(self.marker_buffers_memory.drain(..)) {
+                device.destroy_buffer(buffer, None);
+                device.free_memory(memory, None);
+            }
+            if self.framebuffer != vk::Framebuffer::null() {
+                device.destroy_framebuffer(self.framebuffer, None);
+            }
+            if self.color_image_view != vk::ImageView::null() {
+                device.destroy_image_view(self.color_image_view, None);
+            }
+            if self.color_image != vk::Image::null() {
+                device.destroy_image(self.color_image, None);
+                device.free_memory(self.color_image_memory, None);
+            }
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.render_pass, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Minimap"
+    }
+}
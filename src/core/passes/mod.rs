@@ -2,18 +2,40 @@
 ///
 /// Each file in this module is a self-contained rendering system
 
+pub mod background;
 pub mod skybox;
 pub mod nebula;
 pub mod mesh;
 pub mod star;
 pub mod outline;
+pub mod wireframe;
 pub mod line;
 pub mod unlit;
+pub mod shadow;
+pub mod bloom;
+pub mod tonemap;
+pub mod fxaa;
+pub mod material_preview;
+pub mod grid;
+pub mod particle;
+pub mod thumbnail;
+pub mod minimap;
 
+pub use background::BackgroundPass;
 pub use skybox::SkyboxPass;
 pub use nebula::NebulaPass;
 pub use mesh::MeshPass;
 pub use star::StarPass;
 pub use outline::OutlinePass;
+pub use wireframe::WireframePass;
 pub use line::LinePass;
 pub use unlit::UnlitPass;
+pub use shadow::ShadowPass;
+pub use bloom::BloomPass;
+pub use tonemap::TonemapPass;
+pub use fxaa::FxaaPass;
+pub use material_preview::MaterialPreviewPass;
+pub use grid::GridPass;
+pub use particle::ParticlePass;
+pub use thumbnail::ThumbnailPass;
+pub use minimap::MinimapPass;
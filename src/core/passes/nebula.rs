@@ -1,11 +1,13 @@
 /// Nebula render pass plugin
 ///
 /// Renders volumetric nebula as fullscreen effect
+/// Each nebula object in the scene is one fullscreen draw, farthest first so
+/// the alpha blending composites nearer volumes over farther ones.
 
 use ash::vk;
 use anyhow::Result;
 use glam::Vec2;
-use crate::nebula::{NebulaRenderer, NebulaUniformBufferObject};
+use crate::nebula::{NebulaRenderer, NebulaUniformBufferObject, MAX_NEBULAE};
 use crate::core::{RenderPass, RenderContext};
 use std::ffi::CString;
 
@@ -13,20 +15,23 @@ const MAX_FRAMES_IN_FLIGHT: usize = 2;
 
 pub struct NebulaPass {
     renderer: Option<NebulaRenderer>,
+    /// Nebula volumes written to the UBO this frame
+    draw_count: usize,
 }
 
 impl NebulaPass {
     pub fn new() -> Self {
         Self {
             renderer: None,
+            draw_count: 0,
         }
     }
 
     unsafe fn create_descriptor_set_layout(device: &ash::Device) -> Result<vk::DescriptorSetLayout> {
-        // Binding 0: Uniform buffer
+        // Binding 0: Uniform buffer (dynamic offset selects the nebula being drawn)
         let ubo_binding = vk::DescriptorSetLayoutBinding::default()
             .binding(0)
-            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
             .descriptor_count(1)
             .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT);
 
@@ -46,15 +51,17 @@ impl NebulaPass {
 
     unsafe fn create_pipeline(
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         extent: vk::Extent2D,
         render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
         descriptor_set_layout: vk::DescriptorSetLayout,
     ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
-        let vert_shader_code = include_bytes!("../../../shaders/nebula.vert.spv");
-        let frag_shader_code = include_bytes!("../../../shaders/nebula.frag.spv");
+        let vert_shader_code = crate::core::shader::load_spirv("nebula.vert.spv", include_bytes!("../../../shaders/nebula.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("nebula.frag.spv", include_bytes!("../../../shaders/nebula.frag.spv"));
 
-        let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-        let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
         let entry_point = CString::new("main")?;
 
@@ -106,7 +113,7 @@ impl NebulaPass {
 
         let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+            .rasterization_samples(samples);
 
         // Depth test but don't write - nebula renders after skybox
         let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
@@ -149,29 +156,32 @@ impl NebulaPass {
             .subpass(0);
 
         let pipelines = device.create_graphics_pipelines(
-            vk::PipelineCache::null(),
+            cache.handle(),
             std::slice::from_ref(&pipeline_info),
             None,
         ).map_err(|e| anyhow::anyhow!("Failed to create nebula pipeline: {:?}", e.1))?;
 
-        device.destroy_shader_module(vert_shader_module, None);
-        device.destroy_shader_module(frag_shader_module, None);
-
         Ok((pipeline_layout, pipelines[0]))
     }
 
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
-        let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
-        let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
-        Ok(device.create_shader_module(&create_info, None)?)
+    /// UBO size rounded up so every per-nebula slot is a valid dynamic offset
+    unsafe fn ubo_stride(instance: &ash::Instance, physical_device: vk::PhysicalDevice) -> vk::DeviceSize {
+        let alignment = instance
+            .get_physical_device_properties(physical_device)
+            .limits
+            .min_uniform_buffer_offset_alignment
+            .max(1);
+        let size = std::mem::size_of::<NebulaUniformBufferObject>() as vk::DeviceSize;
+        size.div_ceil(alignment) * alignment
     }
 
     unsafe fn create_uniform_buffers(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
         device: &ash::Device,
+        ubo_stride: vk::DeviceSize,
     ) -> Result<(Vec<vk::Buffer>, Vec<vk::DeviceMemory>)> {
-        let buffer_size = std::mem::size_of::<NebulaUniformBufferObject>() as vk::DeviceSize;
+        let buffer_size = ubo_stride * MAX_NEBULAE as vk::DeviceSize;
 
         let mut uniform_buffers = Vec::new();
         let mut uniform_buffers_memory = Vec::new();
@@ -249,7 +259,7 @@ impl NebulaPass {
     unsafe fn create_descriptor_pool(device: &ash::Device) -> Result<vk::DescriptorPool> {
         let pool_sizes = [
             vk::DescriptorPoolSize::default()
-                .ty(vk::DescriptorType::UNIFORM_BUFFER)
+                .ty(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
                 .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
             vk::DescriptorPoolSize::default()
                 .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
@@ -280,7 +290,7 @@ impl NebulaPass {
         let descriptor_sets = device.allocate_descriptor_sets(&alloc_info)?;
 
         for i in 0..MAX_FRAMES_IN_FLIGHT {
-            // UBO descriptor
+            // UBO descriptor (one slot; the dynamic offset picks the nebula)
             let buffer_info = vk::DescriptorBufferInfo::default()
                 .buffer(uniform_buffers[i])
                 .offset(0)
@@ -300,7 +310,7 @@ impl NebulaPass {
                     .dst_set(descriptor_sets[i])
                     .dst_binding(0)
                     .dst_array_element(0)
-                    .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                    .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC)
                     .buffer_info(&buffer_infos),
                 vk::WriteDescriptorSet::default()
                     .dst_set(descriptor_sets[i])
@@ -315,6 +325,31 @@ impl NebulaPass {
 
         Ok(descriptor_sets)
     }
+
+    /// Point the depth sampler binding at the current depth image (after a resize)
+    unsafe fn update_depth_descriptors(
+        device: &ash::Device,
+        descriptor_sets: &[vk::DescriptorSet],
+        depth_image_view: vk::ImageView,
+        depth_sampler: vk::Sampler,
+    ) {
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL)
+            .image_view(depth_image_view)
+            .sampler(depth_sampler);
+        let image_infos = [image_info];
+
+        for &descriptor_set in descriptor_sets {
+            let descriptor_write = vk::WriteDescriptorSet::default()
+                .dst_set(descriptor_set)
+                .dst_binding(1)
+                .dst_array_element(0)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .image_info(&image_infos);
+
+            device.update_descriptor_sets(&[descriptor_write], &[]);
+        }
+    }
 }
 
 impl RenderPass for NebulaPass {
@@ -328,14 +363,18 @@ impl RenderPass for NebulaPass {
             let descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
             let (pipeline_layout, pipeline) = Self::create_pipeline(
                 ctx.device,
+                ctx.pipeline_cache,
                 extent,
                 render_pass,
+                ctx.msaa_samples,
                 descriptor_set_layout,
             )?;
+            let ubo_stride = Self::ubo_stride(ctx.instance, ctx.physical_device);
             let (uniform_buffers, uniform_buffers_memory) = Self::create_uniform_buffers(
                 ctx.instance,
                 ctx.physical_device,
                 ctx.device,
+                ubo_stride,
             )?;
             let descriptor_pool = Self::create_descriptor_pool(ctx.device)?;
 
@@ -362,6 +401,7 @@ impl RenderPass for NebulaPass {
                 uniform_buffers_memory,
                 descriptor_pool,
                 descriptor_sets,
+                ubo_stride,
             });
 
             Ok(())
@@ -370,41 +410,53 @@ impl RenderPass for NebulaPass {
 
     fn update(&mut self, ctx: &RenderContext, frame_index: usize, game: &crate::game::Game) -> Result<()> {
         unsafe {
+            self.draw_count = 0;
             if let Some(renderer) = &self.renderer {
                 if renderer.descriptor_sets.is_empty() {
                     // Descriptor sets not created yet (need depth resources)
                     return Ok(());
                 }
 
+                // Back to front; past the limit, drop the farthest volumes
+                let mut nebulae = game.get_nebula_instances();
+                if nebulae.len() > MAX_NEBULAE {
+                    nebulae.drain(..nebulae.len() - MAX_NEBULAE);
+                }
+                if nebulae.is_empty() {
+                    return Ok(());
+                }
+
                 let view = game.get_view_matrix();
                 let aspect = ctx.extent.width as f32 / ctx.extent.height as f32;
                 let proj = game.camera.projection_matrix(aspect);
-                let view_pos = game.camera.position();
+                let view_pos = game.get_camera_position();
                 let resolution = Vec2::new(ctx.extent.width as f32, ctx.extent.height as f32);
 
-                // Get nebula transform from ECS
-                let nebula_transform = game.get_nebula_model_matrix();
-
-                let ubo = NebulaRenderer::create_ubo(
-                    game.get_time(),
-                    resolution,
-                    Vec2::ZERO, // Mouse position (not used currently)
-                    view,
-                    proj,
-                    view_pos,
-                    &game.nebula_config,
-                    nebula_transform,
-                );
-
                 let data = ctx.device.map_memory(
                     renderer.uniform_buffers_memory[frame_index],
                     0,
-                    std::mem::size_of::<NebulaUniformBufferObject>() as vk::DeviceSize,
+                    renderer.ubo_stride * nebulae.len() as vk::DeviceSize,
                     vk::MemoryMapFlags::empty(),
-                )?;
+                )? as *mut u8;
+
+                for (i, (model, config)) in nebulae.iter().enumerate() {
+                    let mut ubo = NebulaRenderer::create_ubo(
+                        game.get_time(),
+                        resolution,
+                        Vec2::ZERO, // Mouse position (not used currently)
+                        view,
+                        proj,
+                        view_pos,
+                        config,
+                        game.render_matrix(*model),
+                    );
+                    ubo.log_depth = game.camera.log_depth_coefficient();
+                    let slot = data.add(i * renderer.ubo_stride as usize) as *mut NebulaUniformBufferObject;
+                    std::ptr::copy_nonoverlapping(&ubo, slot, 1);
+                }
 
-                std::ptr::copy_nonoverlapping(&ubo, data as *mut NebulaUniformBufferObject, 1);
                 ctx.device.unmap_memory(renderer.uniform_buffers_memory[frame_index]);
+                self.draw_count = nebulae.len();
             }
 
             Ok(())
@@ -431,17 +483,20 @@ impl RenderPass for NebulaPass {
                     renderer.pipeline,
                 );
 
-                ctx.device.cmd_bind_descriptor_sets(
-                    command_buffer,
-                    vk::PipelineBindPoint::GRAPHICS,
-                    renderer.pipeline_layout,
-                    0,
-                    &[renderer.descriptor_sets[frame_index]],
-                    &[],
-                );
-
-                // Draw fullscreen triangle (no vertex buffer needed)
-                ctx.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+                for i in 0..self.draw_count {
+                    let offset = (i as vk::DeviceSize * renderer.ubo_stride) as u32;
+                    ctx.device.cmd_bind_descriptor_sets(
+                        command_buffer,
+                        vk::PipelineBindPoint::GRAPHICS,
+                        renderer.pipeline_layout,
+                        0,
+                        &[renderer.descriptor_sets[frame_index]],
+                        &[offset],
+                    );
+
+                    // Draw fullscreen triangle (no vertex buffer needed)
+                    ctx.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+                }
             }
 
             Ok(())
@@ -461,13 +516,26 @@ impl RenderPass for NebulaPass {
 
                 let (pipeline_layout, pipeline) = Self::create_pipeline(
                     ctx.device,
+                    ctx.pipeline_cache,
                     extent,
                     render_pass,
+                    ctx.msaa_samples,
                     renderer.descriptor_set_layout,
                 )?;
 
                 renderer.pipeline_layout = pipeline_layout;
                 renderer.pipeline = pipeline;
+
+                // The depth image is recreated with the swapchain
+                if let (Some(depth_image_view), Some(depth_sampler)) =
+                    (ctx.depth_image_view, ctx.depth_sampler) {
+                    Self::update_depth_descriptors(
+                        ctx.device,
+                        &renderer.descriptor_sets,
+                        depth_image_view,
+                        depth_sampler,
+                    );
+                }
             }
 
             Ok(())
@@ -476,7 +544,7 @@ impl RenderPass for NebulaPass {
 
     fn cleanup(&mut self, device: &ash::Device) {
         unsafe {
-            if let Some(renderer) = &self.renderer {
+            if let Some(renderer) = self.renderer.take() {
                 renderer.cleanup(device);
             }
         }
@@ -487,7 +555,7 @@ impl RenderPass for NebulaPass {
     }
 
     fn should_render(&self, _game: &crate::game::Game) -> bool {
-        // Always render nebula for now - will add visibility check when integrating
+        // Scenes without visible nebulae record no draws
         true
     }
 }
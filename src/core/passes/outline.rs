@@ -1,9 +1,15 @@
 use ash::vk;
 use anyhow::Result;
-use glam::{Mat4, Vec4};
+use glam::{Mat4, Vec2, Vec4};
 
-use crate::core::RenderPass;
+use crate::core::passes::MeshPass;
+use crate::core::{DrawStats, RenderPass};
 use crate::game::Game;
+use crate::mesh::Mesh;
+use crate::scene::ObjectType;
+
+/// Stencil value written under outlined objects
+const OUTLINE_STENCIL_REF: u32 = 1;
 
 /// Push constants for outline rendering
 #[repr(C)]
@@ -11,20 +17,33 @@ use crate::game::Game;
 pub struct OutlinePushConstants {
     pub model: Mat4,
     pub outline_color: Vec4,  // RGB color + alpha
-    pub outline_width: f32,   // How much to expand the mesh (typical: 0.01-0.05)
-    pub _padding1: f32,
-    pub _padding2: f32,
-    pub _padding3: f32,
+    pub outline_width: f32,   // Silhouette expansion in screen pixels (0 = unexpanded)
+    pub _padding: f32,
+    pub viewport_size: Vec2,  // Pixels, to turn the width into clip-space offsets
 }
 
 unsafe impl bytemuck::Pod for OutlinePushConstants {}
 unsafe impl bytemuck::Zeroable for OutlinePushConstants {}
 
+/// Stencil-based outline for selected and hovered objects (colors and width from `OutlineConfig`)
+///
+/// Outlined objects are first drawn into the stencil buffer only, then an
+/// expanded silhouette is drawn where the stencil isn't set. The silhouette
+/// is pushed out along the screen-space normal by a fixed number of pixels and
+/// depth tested, so occluded parts of the outline stay hidden.
 pub struct OutlinePass {
     descriptor_set_layout: vk::DescriptorSetLayout,
     pipeline_layout: vk::PipelineLayout,
+    stencil_pipeline: vk::Pipeline,
     pipeline: vk::Pipeline,
     descriptor_sets: Vec<vk::DescriptorSet>,
+    // Cube buffers (cubes are drawn from MeshPass-owned buffers, so the outline keeps its own copy)
+    cube_mesh: Mesh,
+    cube_vertex_buffer: vk::Buffer,
+    cube_vertex_buffer_memory: vk::DeviceMemory,
+    cube_index_buffer: vk::Buffer,
+    cube_index_buffer_memory: vk::DeviceMemory,
+    draw_stats: DrawStats,
 }
 
 impl OutlinePass {
@@ -32,8 +51,77 @@ impl OutlinePass {
         Self {
             descriptor_set_layout: vk::DescriptorSetLayout::null(),
             pipeline_layout: vk::PipelineLayout::null(),
+            stencil_pipeline: vk::Pipeline::null(),
             pipeline: vk::Pipeline::null(),
             descriptor_sets: Vec::new(),
+            cube_mesh: Mesh::create_cube(),
+            cube_vertex_buffer: vk::Buffer::null(),
+            cube_vertex_buffer_memory: vk::DeviceMemory::null(),
+            cube_index_buffer: vk::Buffer::null(),
+            cube_index_buffer_memory: vk::DeviceMemory::null(),
+            draw_stats: DrawStats::default(),
+        }
+    }
+
+    /// Vertex buffer, index buffer and index count for an outlined object
+    fn mesh_buffers(
+        &self,
+        ctx: &crate::core::RenderContext,
+        object_type: &ObjectType,
+    ) -> Option<(vk::Buffer, vk::Buffer, u32)> {
+        match object_type {
+            ObjectType::Cube => Some((self.cube_vertex_buffer, self.cube_index_buffer, self.cube_mesh.indices.len() as u32)),
+            ObjectType::Primitive(shape) => ctx
+                .primitive_meshes
+                .and_then(|meshes| meshes.get(shape))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (*vertex_buffer, *index_buffer, mesh.indices.len() as u32)),
+            ObjectType::Mesh(path) | ObjectType::Unlit(path) => ctx
+                .custom_meshes
+                .and_then(|meshes| meshes.get(path))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (*vertex_buffer, *index_buffer, mesh.indices.len() as u32)),
+            _ => None,
+        }
+    }
+
+    /// Draw every outlined object with the given pipeline
+    unsafe fn draw_objects(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        pipeline: vk::Pipeline,
+        game: &Game,
+        outlined_objects: &[(ObjectType, Mat4, Vec4, f32)],
+        expand: bool,
+    ) {
+        ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline);
+
+        for (object_type, model_matrix, outline_color, outline_width) in outlined_objects.iter() {
+            let Some((vertex_buffer, index_buffer, index_count)) = self.mesh_buffers(ctx, object_type) else {
+                continue;
+            };
+
+            // Bind mesh buffers
+            ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer], &[0]);
+            ctx.device.cmd_bind_index_buffer(command_buffer, index_buffer, 0, vk::IndexType::UINT32);
+
+            // Push constants (the stencil mask uses the unexpanded mesh)
+            let push_data = OutlinePushConstants {
+                model: game.render_matrix(*model_matrix),
+                outline_color: *outline_color,
+                outline_width: if expand { *outline_width } else { 0.0 },
+                _padding: 0.0,
+                viewport_size: Vec2::new(ctx.extent.width.max(1) as f32, ctx.extent.height.max(1) as f32),
+            };
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+
+            ctx.device.cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
+            self.draw_stats.record_draw(index_count, 1);
         }
     }
 }
@@ -54,16 +142,42 @@ impl RenderPass for OutlinePass {
             // Create descriptor set layout
             self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
 
-            // Create pipeline
-            let (pipeline_layout, pipeline) = Self::create_pipeline(
+            // Create stencil mask + outline pipelines
+            let (pipeline_layout, stencil_pipeline, pipeline) = Self::create_pipelines(
                 ctx.device,
+                ctx.pipeline_cache,
                 extent,
                 render_pass,
+                ctx.msaa_samples,
                 self.descriptor_set_layout,
             )?;
             self.pipeline_layout = pipeline_layout;
+            self.stencil_pipeline = stencil_pipeline;
             self.pipeline = pipeline;
 
+            // Create cube mesh buffers
+            let (cube_vb, cube_vb_mem) = MeshPass::create_vertex_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &self.cube_mesh.vertices,
+            )?;
+            self.cube_vertex_buffer = cube_vb;
+            self.cube_vertex_buffer_memory = cube_vb_mem;
+
+            let (cube_ib, cube_ib_mem) = MeshPass::create_index_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &self.cube_mesh.indices,
+            )?;
+            self.cube_index_buffer = cube_ib;
+            self.cube_index_buffer_memory = cube_ib_mem;
+
             Ok(())
         }
     }
@@ -85,24 +199,18 @@ impl RenderPass for OutlinePass {
         game: &Game,
     ) -> Result<()> {
         unsafe {
+            self.draw_stats = DrawStats::default();
             if self.pipeline == vk::Pipeline::null() {
                 return Ok(());
             }
 
-            // Get outlined objects from game (selected or highlighted objects)
+            // Get outlined objects from game (selected and hovered objects, edit mode only)
             let outlined_objects = game.get_outlined_objects();
             if outlined_objects.is_empty() {
                 return Ok(());
             }
 
-            // Bind outline pipeline
-            ctx.device.cmd_bind_pipeline(
-                command_buffer,
-                vk::PipelineBindPoint::GRAPHICS,
-                self.pipeline,
-            );
-
-            // Bind descriptor set
+            // Bind descriptor set (shared by both pipelines)
             ctx.device.cmd_bind_descriptor_sets(
                 command_buffer,
                 vk::PipelineBindPoint::GRAPHICS,
@@ -112,38 +220,11 @@ impl RenderPass for OutlinePass {
                 &[],
             );
 
-            // Render each outlined object
-            if let Some(custom_meshes) = ctx.custom_meshes {
-                for (mesh_path, model_matrix, outline_color, outline_width) in outlined_objects.iter() {
-                    if let Some((mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) = custom_meshes.get(mesh_path) {
-                        // Bind mesh buffers
-                        let vertex_buffers = [*vertex_buffer];
-                        let offsets = [0_u64];
-                        ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &vertex_buffers, &offsets);
-                        ctx.device.cmd_bind_index_buffer(command_buffer, *index_buffer, 0, vk::IndexType::UINT32);
-
-                        // Push constants
-                        let push_data = OutlinePushConstants {
-                            model: *model_matrix,
-                            outline_color: *outline_color,
-                            outline_width: *outline_width,
-                            _padding1: 0.0,
-                            _padding2: 0.0,
-                            _padding3: 0.0,
-                        };
-                        let push_constants = bytemuck::bytes_of(&push_data);
-                        ctx.device.cmd_push_constants(
-                            command_buffer,
-                            self.pipeline_layout,
-                            vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
-                            0,
-                            push_constants,
-                        );
-
-                        ctx.device.cmd_draw_indexed(command_buffer, mesh.indices.len() as u32, 1, 0, 0, 0);
-                    }
-                }
-            }
+            // 1. Mark the outlined objects in the stencil buffer
+            self.draw_objects(ctx, command_buffer, self.stencil_pipeline, game, &outlined_objects, false);
+
+            // 2. Draw the expanded silhouettes where the stencil isn't set
+            self.draw_objects(ctx, command_buffer, self.pipeline, game, &outlined_objects, true);
 
             Ok(())
         }
@@ -156,20 +237,24 @@ impl RenderPass for OutlinePass {
         extent: vk::Extent2D,
     ) -> Result<()> {
         unsafe {
-            // Destroy old pipeline
+            // Destroy old pipelines
             if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.stencil_pipeline, None);
                 ctx.device.destroy_pipeline(self.pipeline, None);
                 ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
             }
 
-            // Create new pipeline
-            let (pipeline_layout, pipeline) = Self::create_pipeline(
+            // Create new pipelines
+            let (pipeline_layout, stencil_pipeline, pipeline) = Self::create_pipelines(
                 ctx.device,
+                ctx.pipeline_cache,
                 extent,
                 render_pass,
+                ctx.msaa_samples,
                 self.descriptor_set_layout,
             )?;
             self.pipeline_layout = pipeline_layout;
+            self.stencil_pipeline = stencil_pipeline;
             self.pipeline = pipeline;
 
             Ok(())
@@ -178,6 +263,17 @@ impl RenderPass for OutlinePass {
 
     fn cleanup(&mut self, device: &ash::Device) {
         unsafe {
+            if self.cube_vertex_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.cube_vertex_buffer, None);
+                device.free_memory(self.cube_vertex_buffer_memory, None);
+            }
+            if self.cube_index_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.cube_index_buffer, None);
+                device.free_memory(self.cube_index_buffer_memory, None);
+            }
+            if self.stencil_pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.stencil_pipeline, None);
+            }
             if self.pipeline != vk::Pipeline::null() {
                 device.destroy_pipeline(self.pipeline, None);
             }
@@ -188,11 +284,23 @@ impl RenderPass for OutlinePass {
                 device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
             }
         }
+        self.cube_vertex_buffer = vk::Buffer::null();
+        self.cube_vertex_buffer_memory = vk::DeviceMemory::null();
+        self.cube_index_buffer = vk::Buffer::null();
+        self.cube_index_buffer_memory = vk::DeviceMemory::null();
+        self.stencil_pipeline = vk::Pipeline::null();
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+        self.descriptor_set_layout = vk::DescriptorSetLayout::null();
     }
 
     fn name(&self) -> &str {
         "Outline"
     }
+
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
 }
 
 impl OutlinePass {
@@ -209,19 +317,22 @@ impl OutlinePass {
         Ok(device.create_descriptor_set_layout(&layout_info, None)?)
     }
 
-    unsafe fn create_pipeline(
+    /// Create the stencil mask pipeline and the outline pipeline (sharing one layout)
+    unsafe fn create_pipelines(
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         extent: vk::Extent2D,
         render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
         descriptor_set_layout: vk::DescriptorSetLayout,
-    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline, vk::Pipeline)> {
         use std::ffi::CString;
 
-        let vert_shader_code = include_bytes!("../../../shaders/outline.vert.spv");
-        let frag_shader_code = include_bytes!("../../../shaders/outline.frag.spv");
+        let vert_shader_code = crate::core::shader::load_spirv("outline.vert.spv", include_bytes!("../../../shaders/outline.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("outline.frag.spv", include_bytes!("../../../shaders/outline.frag.spv"));
 
-        let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-        let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
         let entry_point = CString::new("main")?;
 
@@ -292,19 +403,59 @@ impl OutlinePass {
             .rasterizer_discard_enable(false)
             .polygon_mode(vk::PolygonMode::FILL)
             .line_width(1.0)
-            .cull_mode(vk::CullModeFlags::FRONT) // Cull front faces to show only outline
+            .cull_mode(vk::CullModeFlags::BACK)
             .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
             .depth_bias_enable(false);
 
         let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
-
-        // Depth testing to avoid z-fighting
-        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .rasterization_samples(samples);
+
+        // Stencil mask: mark the whole silhouette, ignoring depth so the
+        // mask never z-fights with the mesh pass
+        let mark_stencil = vk::StencilOpState::default()
+            .fail_op(vk::StencilOp::KEEP)
+            .pass_op(vk::StencilOp::REPLACE)
+            .depth_fail_op(vk::StencilOp::KEEP)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .compare_mask(0xFF)
+            .write_mask(0xFF)
+            .reference(OUTLINE_STENCIL_REF);
+
+        let stencil_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(false)
+            .depth_write_enable(false)
+            .stencil_test_enable(true)
+            .front(mark_stencil)
+            .back(mark_stencil);
+
+        // Outline: only outside the marked silhouette, depth tested so
+        // occluded parts of the outline stay hidden
+        let outside_stencil = vk::StencilOpState::default()
+            .fail_op(vk::StencilOp::KEEP)
+            .pass_op(vk::StencilOp::KEEP)
+            .depth_fail_op(vk::StencilOp::KEEP)
+            .compare_op(vk::CompareOp::NOT_EQUAL)
+            .compare_mask(0xFF)
+            .write_mask(0x00)
+            .reference(OUTLINE_STENCIL_REF);
+
+        let outline_depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
             .depth_test_enable(true)
             .depth_write_enable(false) // Don't write depth for outline
-            .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL); // Allow outline to show behind object
+            .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL)
+            .stencil_test_enable(true)
+            .front(outside_stencil)
+            .back(outside_stencil);
+
+        // The stencil mask writes no color
+        let stencil_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::empty())
+            .blend_enable(false);
+
+        let stencil_color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&stencil_blend_attachment));
 
         // No blending needed for solid outline
         let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
@@ -328,36 +479,40 @@ impl OutlinePass {
 
         let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
 
-        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+        let stencil_pipeline_info = vk::GraphicsPipelineCreateInfo::default()
             .stages(&shader_stages)
             .vertex_input_state(&vertex_input_info)
             .input_assembly_state(&input_assembly)
             .viewport_state(&viewport_state)
             .rasterization_state(&rasterizer)
             .multisample_state(&multisampling)
-            .depth_stencil_state(&depth_stencil)
+            .depth_stencil_state(&stencil_depth_stencil)
+            .color_blend_state(&stencil_color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let outline_pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&outline_depth_stencil)
             .color_blend_state(&color_blending)
             .layout(pipeline_layout)
             .render_pass(render_pass)
             .subpass(0);
 
         let pipelines = device
-            .create_graphics_pipelines(vk::PipelineCache::null(), std::slice::from_ref(&pipeline_info), None)
-            .map_err(|e| anyhow::anyhow!("Failed to create outline pipeline: {:?}", e.1))?;
-
-        device.destroy_shader_module(vert_shader_module, None);
-        device.destroy_shader_module(frag_shader_module, None);
-
-        Ok((pipeline_layout, pipelines[0]))
-    }
-
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
-        let shader_module_create_info = vk::ShaderModuleCreateInfo {
-            code_size: code.len(),
-            p_code: code.as_ptr() as *const u32,
-            ..Default::default()
-        };
-
-        Ok(device.create_shader_module(&shader_module_create_info, None)?)
+            .create_graphics_pipelines(
+                cache.handle(),
+                &[stencil_pipeline_info, outline_pipeline_info],
+                None,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to create outline pipelines: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0], pipelines[1]))
     }
 }
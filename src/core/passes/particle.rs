@@ -0,0 +1,668 @@
+//! GPU particle pass (engine exhaust)
+//!
+//! Particles live in a storage buffer ring. `simulate` records a compute dispatch
+//! that spawns and ages them, and must run outside any render pass; `render` then
+//! draws them as additive camera-facing quads inside the main render pass.
+
+use ash::vk;
+use anyhow::Result;
+use glam::{DVec3, Mat4};
+
+use crate::core::{DrawStats, RenderPass, ResourceManager};
+use crate::game::Game;
+use crate::particles::{ParticleSpawner, MAX_EMITTERS};
+
+/// Compute shader workgroup size (matches `local_size_x` in particle.comp)
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Longest simulation step; a hitch or a long pause doesn't fling particles across the scene
+const MAX_DELTA_TIME: f32 = 0.1;
+
+/// One particle in the storage buffer (std430, matches `Particle` in the particle shaders)
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct GpuParticle {
+    position_age: [f32; 4],
+    velocity_lifetime: [f32; 4],
+    start_color: [f32; 4],
+    end_color: [f32; 4],
+    size: [f32; 4],
+}
+
+/// One emitter's spawns for this frame (std140, matches `Emitter` in particle.comp)
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+struct GpuEmitter {
+    position: [f32; 4],
+    direction: [f32; 4],
+    velocity: [f32; 4],
+    start_color: [f32; 4],
+    end_color: [f32; 4],
+    size: [f32; 4],
+    spawn: [u32; 4],
+}
+
+/// Shared by the compute and render stages
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct ParticleUniformBufferObject {
+    view: Mat4,
+    proj: Mat4,
+    // x = delta time, y = drag per second, z = log depth coefficient
+    sim: [f32; 4],
+    // x = emitter count, y = particle capacity, z = random seed
+    counts: [u32; 4],
+    // xyz = camera origin rebase since the last dispatch (live particles are moved by it)
+    shift: [f32; 4],
+    emitters: [GpuEmitter; MAX_EMITTERS],
+}
+
+pub struct ParticlePass {
+    /// Slots in the particle ring
+    capacity: u32,
+    particle_buffer: vk::Buffer,
+    particle_buffer_memory: vk::DeviceMemory,
+    /// Zero the ring (all particles dead) before the next dispatch
+    needs_clear: bool,
+
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    compute_pipeline_layout: vk::PipelineLayout,
+    compute_pipeline: vk::Pipeline,
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+    uniform_buffers: Vec<vk::Buffer>,
+    uniform_buffers_memory: Vec<vk::DeviceMemory>,
+    descriptor_pool: vk::DescriptorPool,
+    descriptor_sets: Vec<vk::DescriptorSet>,
+
+    spawner: ParticleSpawner,
+    /// Simulation clock at the last update (None until the first frame)
+    last_time: Option<f32>,
+    /// Camera origin the live particles' render-space positions are relative to
+    last_origin: DVec3,
+    frame_seed: u32,
+    /// Particles may still be alive (skip the dispatch and draw once every emitter is gone long enough)
+    active_until: f32,
+
+    max_frames_in_flight: usize,
+    draw_stats: DrawStats,
+}
+
+impl ParticlePass {
+    pub fn new(max_frames_in_flight: usize, capacity: u32) -> Self {
+        Self {
+            capacity,
+            particle_buffer: vk::Buffer::null(),
+            particle_buffer_memory: vk::DeviceMemory::null(),
+            needs_clear: true,
+            descriptor_set_layout: vk::DescriptorSetLayout::null(),
+            compute_pipeline_layout: vk::PipelineLayout::null(),
+            compute_pipeline: vk::Pipeline::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            uniform_buffers: Vec::new(),
+            uniform_buffers_memory: Vec::new(),
+            descriptor_pool: vk::DescriptorPool::null(),
+            descriptor_sets: Vec::new(),
+            spawner: ParticleSpawner::new(),
+            last_time: None,
+            last_origin: DVec3::ZERO,
+            frame_seed: 0,
+            active_until: f32::NEG_INFINITY,
+            max_frames_in_flight,
+            draw_stats: DrawStats::default(),
+        }
+    }
+
+    /// Current particle budget
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Reallocate the particle ring for a new budget (existing particles are dropped)
+    /// The device must be idle
+    pub fn resize(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        capacity: u32,
+    ) -> Result<()> {
+        unsafe {
+            self.destroy_particle_buffer(device);
+
+            self.capacity = capacity;
+            self.create_particle_buffer(instance, physical_device, device)?;
+            self.write_descriptor_sets(device);
+
+            crate::console::info(format!("Particle budget resized to {}", capacity));
+            Ok(())
+        }
+    }
+
+    /// Record the compute dispatch that spawns and ages this frame's particles
+    /// Must be called outside a render pass, before `render`
+    pub fn simulate(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        if !self.is_active(game) || self.compute_pipeline == vk::Pipeline::null() {
+            return Ok(());
+        }
+
+        unsafe {
+            let particle_buffer = self.particle_buffer;
+            let buffer_barrier = |src_stage, src_access, dst_stage, dst_access| {
+                let barrier = vk::BufferMemoryBarrier::default()
+                    .src_access_mask(src_access)
+                    .dst_access_mask(dst_access)
+                    .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                    .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                    .buffer(particle_buffer)
+                    .offset(0)
+                    .size(vk::WHOLE_SIZE);
+                ctx.device.cmd_pipeline_barrier(
+                    command_buffer,
+                    src_stage,
+                    dst_stage,
+                    vk::DependencyFlags::empty(),
+                    &[],
+                    std::slice::from_ref(&barrier),
+                    &[],
+                );
+            };
+
+            if self.needs_clear {
+                ctx.device.cmd_fill_buffer(command_buffer, self.particle_buffer, 0, vk::WHOLE_SIZE, 0);
+                buffer_barrier(
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::AccessFlags::TRANSFER_WRITE,
+                    vk::PipelineStageFlags::COMPUTE_SHADER,
+                    vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE,
+                );
+                self.needs_clear = false;
+            } else {
+                // The previous frame's draw reads the ring this dispatch rewrites
+                buffer_barrier(
+                    vk::PipelineStageFlags::VERTEX_SHADER,
+                    vk::AccessFlags::SHADER_READ,
+                    vk::PipelineStageFlags::COMPUTE_SHADER,
+                    vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE,
+                );
+            }
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::COMPUTE, self.compute_pipeline);
+            ctx.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::COMPUTE,
+                self.compute_pipeline_layout,
+                0,
+                &[self.descriptor_sets[frame_index]],
+                &[],
+            );
+            ctx.device.cmd_dispatch(command_buffer, self.capacity.div_ceil(WORKGROUP_SIZE), 1, 1);
+
+            buffer_barrier(
+                vk::PipelineStageFlags::COMPUTE_SHADER,
+                vk::AccessFlags::SHADER_WRITE,
+                vk::PipelineStageFlags::VERTEX_SHADER,
+                vk::AccessFlags::SHADER_READ,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Anything to simulate or draw: emitters in the scene, or particles that may still be alive
+    fn is_active(&self, game: &Game) -> bool {
+        self.capacity > 0 && game.get_time() <= self.active_until
+    }
+
+    unsafe fn create_particle_buffer(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> Result<()> {
+        // Keep a valid (if tiny) buffer bound when the budget is zero
+        let size = std::mem::size_of::<GpuParticle>() as vk::DeviceSize * self.capacity.max(1) as vk::DeviceSize;
+        let (buffer, memory) = ResourceManager::create_buffer(
+            instance,
+            physical_device,
+            device,
+            size,
+            vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::TRANSFER_DST,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        )?;
+        self.particle_buffer = buffer;
+        self.particle_buffer_memory = memory;
+        self.needs_clear = true;
+        Ok(())
+    }
+
+    unsafe fn destroy_particle_buffer(&mut self, device: &ash::Device) {
+        if self.particle_buffer != vk::Buffer::null() {
+            device.destroy_buffer(self.particle_buffer, None);
+            device.free_memory(self.particle_buffer_memory, None);
+            self.particle_buffer = vk::Buffer::null();
+            self.particle_buffer_memory = vk::DeviceMemory::null();
+        }
+    }
+
+    unsafe fn write_descriptor_sets(&self, device: &ash::Device) {
+        for (set, uniform_buffer) in self.descriptor_sets.iter().zip(&self.uniform_buffers) {
+            let uniform_info = vk::DescriptorBufferInfo::default()
+                .buffer(*uniform_buffer)
+                .offset(0)
+                .range(std::mem::size_of::<ParticleUniformBufferObject>() as vk::DeviceSize);
+            let particle_info = vk::DescriptorBufferInfo::default()
+                .buffer(self.particle_buffer)
+                .offset(0)
+                .range(vk::WHOLE_SIZE);
+
+            let writes = [
+                vk::WriteDescriptorSet::default()
+                    .dst_set(*set)
+                    .dst_binding(0)
+                    .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                    .buffer_info(std::slice::from_ref(&uniform_info)),
+                vk::WriteDescriptorSet::default()
+                    .dst_set(*set)
+                    .dst_binding(1)
+                    .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                    .buffer_info(std::slice::from_ref(&particle_info)),
+            ];
+            device.update_descriptor_sets(&writes, &[]);
+        }
+    }
+
+    unsafe fn destroy_pipelines(&mut self, device: &ash::Device) {
+        for pipeline in [&mut self.compute_pipeline, &mut self.pipeline] {
+            if *pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(*pipeline, None);
+                *pipeline = vk::Pipeline::null();
+            }
+        }
+        for layout in [&mut self.compute_pipeline_layout, &mut self.pipeline_layout] {
+            if *layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(*layout, None);
+                *layout = vk::PipelineLayout::null();
+            }
+        }
+    }
+
+    unsafe fn create_pipelines(&mut self, ctx: &crate::core::RenderContext, render_pass: vk::RenderPass, extent: vk::Extent2D) -> Result<()> {
+        let (compute_pipeline_layout, compute_pipeline) = Self::create_compute_pipeline(ctx.device, ctx.pipeline_cache, self.descriptor_set_layout)?;
+        let (pipeline_layout, pipeline) =
+            match Self::create_render_pipeline(ctx.device, ctx.pipeline_cache, extent, render_pass, ctx.msaa_samples, self.descriptor_set_layout) {
+                Ok(created) => created,
+                Err(e) => {
+                    ctx.device.destroy_pipeline(compute_pipeline, None);
+                    ctx.device.destroy_pipeline_layout(compute_pipeline_layout, None);
+                    return Err(e);
+                }
+            };
+
+        self.destroy_pipelines(ctx.device);
+        self.compute_pipeline_layout = compute_pipeline_layout;
+        self.compute_pipeline = compute_pipeline;
+        self.pipeline_layout = pipeline_layout;
+        self.pipeline = pipeline;
+        Ok(())
+    }
+}
+
+impl RenderPass for ParticlePass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
+            self.create_pipelines(ctx, render_pass, extent)?;
+            self.create_particle_buffer(ctx.instance, ctx.physical_device, ctx.device)?;
+
+            for _ in 0..self.max_frames_in_flight {
+                let (buffer, memory) = ResourceManager::create_buffer(
+                    ctx.instance,
+                    ctx.physical_device,
+                    ctx.device,
+                    std::mem::size_of::<ParticleUniformBufferObject>() as vk::DeviceSize,
+                    vk::BufferUsageFlags::UNIFORM_BUFFER,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                )?;
+                self.uniform_buffers.push(buffer);
+                self.uniform_buffers_memory.push(memory);
+            }
+
+            self.descriptor_pool = Self::create_descriptor_pool(ctx.device, self.max_frames_in_flight)?;
+            let layouts = vec![self.descriptor_set_layout; self.max_frames_in_flight];
+            let alloc_info = vk::DescriptorSetAllocateInfo::default()
+                .descriptor_pool(self.descriptor_pool)
+                .set_layouts(&layouts);
+            self.descriptor_sets = ctx.device.allocate_descriptor_sets(&alloc_info)?;
+            self.write_descriptor_sets(ctx.device);
+
+            Ok(())
+        }
+    }
+
+    fn update(&mut self, ctx: &crate::core::RenderContext, frame_index: usize, game: &Game) -> Result<()> {
+        // Step on the simulation clock, so particles freeze while the game is paused
+        let time = game.get_time();
+        let delta_time = self.last_time.map_or(0.0, |last| (time - last).clamp(0.0, MAX_DELTA_TIME));
+        self.last_time = Some(time);
+
+        // Particles live in render space; a camera origin rebase shifts them along with everything else
+        let origin = game.ecs_world.camera_origin;
+        let shift = (origin - self.last_origin).as_vec3();
+        self.last_origin = origin;
+
+        let emitters = game.get_particle_emitters();
+        if let Some(longest_lifetime) = emitters.iter().map(|emitter| emitter.settings.lifetime).reduce(f32::max) {
+            // Keep simulating until the last particle of a removed emitter has died
+            self.active_until = self.active_until.max(time + longest_lifetime.max(0.0) + MAX_DELTA_TIME);
+        }
+        if !self.is_active(game) {
+            return Ok(());
+        }
+
+        let batches = self.spawner.plan(&emitters, delta_time, self.capacity);
+        let mut gpu_emitters = [GpuEmitter::default(); MAX_EMITTERS];
+        for ((slot, emitter), batch) in gpu_emitters.iter_mut().zip(&emitters).zip(&batches) {
+            let settings = &emitter.settings;
+            *slot = GpuEmitter {
+                position: game.render_position(emitter.position).extend(settings.speed).to_array(),
+                direction: emitter.direction.extend(settings.spread.clamp(0.0, 180.0).to_radians().cos()).to_array(),
+                velocity: batch.velocity.extend(settings.lifetime).to_array(),
+                start_color: settings.start_color.extend(settings.start_alpha).to_array(),
+                end_color: settings.end_color.extend(settings.end_alpha).to_array(),
+                size: [settings.start_size, settings.end_size, settings.inherit_velocity, 0.0],
+                spawn: [batch.first, batch.count, 0, 0],
+            };
+        }
+
+        self.frame_seed = self.frame_seed.wrapping_add(1);
+        let aspect = ctx.extent.width as f32 / ctx.extent.height as f32;
+        let ubo = ParticleUniformBufferObject {
+            view: game.get_view_matrix(),
+            proj: game.camera.projection_matrix(aspect),
+            sim: [delta_time, game.particle_config.drag, game.camera.log_depth_coefficient(), 0.0],
+            counts: [batches.len() as u32, self.capacity, self.frame_seed, 0],
+            shift: shift.extend(0.0).to_array(),
+            emitters: gpu_emitters,
+        };
+
+        unsafe {
+            let data = ctx.device.map_memory(
+                self.uniform_buffers_memory[frame_index],
+                0,
+                std::mem::size_of::<ParticleUniformBufferObject>() as vk::DeviceSize,
+                vk::MemoryMapFlags::empty(),
+            )?;
+            std::ptr::copy_nonoverlapping(&ubo, data as *mut ParticleUniformBufferObject, 1);
+            ctx.device.unmap_memory(self.uniform_buffers_memory[frame_index]);
+        }
+
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        self.draw_stats = DrawStats::default();
+        if !self.is_active(game) || self.pipeline == vk::Pipeline::null() {
+            return Ok(());
+        }
+
+        unsafe {
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            ctx.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline_layout,
+                0,
+                &[self.descriptor_sets[frame_index]],
+                &[],
+            );
+            // Every slot is drawn; dead particles are collapsed by the vertex shader
+            ctx.device.cmd_draw(command_buffer, self.capacity * 6, 1, 0, 0);
+        }
+        self.draw_stats.record_draw(self.capacity * 6, 1);
+
+        Ok(())
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe { self.create_pipelines(ctx, render_pass, extent) }
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            self.destroy_pipelines(device);
+            self.destroy_particle_buffer(device);
+
+            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
+                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+            }
+            if self.descriptor_pool != vk::DescriptorPool::null() {
+                device.destroy_descriptor_pool(self.descriptor_pool, None);
+            }
+            for (buffer, memory) in self.uniform_buffers.iter().zip(&self.uniform_buffers_memory) {
+                device.destroy_buffer(*buffer, None);
+                device.free_memory(*memory, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Particles"
+    }
+
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
+}
+
+// Static helper methods for resource creation
+impl ParticlePass {
+    unsafe fn create_descriptor_set_layout(device: &ash::Device) -> Result<vk::DescriptorSetLayout> {
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(0)
+                .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE | vk::ShaderStageFlags::VERTEX),
+            vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE | vk::ShaderStageFlags::VERTEX),
+        ];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+
+        Ok(device.create_descriptor_set_layout(&layout_info, None)?)
+    }
+
+    unsafe fn create_descriptor_pool(device: &ash::Device, max_frames_in_flight: usize) -> Result<vk::DescriptorPool> {
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::UNIFORM_BUFFER)
+                .descriptor_count(max_frames_in_flight as u32),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(max_frames_in_flight as u32),
+        ];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(max_frames_in_flight as u32);
+
+        Ok(device.create_descriptor_pool(&pool_info, None)?)
+    }
+
+    unsafe fn create_compute_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        descriptor_set_layout: vk::DescriptorSetLayout,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let shader_code = crate::core::shader::load_spirv("particle.comp.spv", include_bytes!("../../../shaders/particle.comp.spv"));
+        let shader_module = cache.shader_module(device, &shader_code)?;
+        let entry_point = CString::new("main")?;
+
+        let stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(shader_module)
+            .name(&entry_point);
+
+        let set_layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default().set_layouts(&set_layouts);
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let pipeline_info = vk::ComputePipelineCreateInfo::default()
+            .stage(stage_info)
+            .layout(pipeline_layout);
+
+        let pipelines = device.create_compute_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None);
+
+        match pipelines {
+            Ok(pipelines) => Ok((pipeline_layout, pipelines[0])),
+            Err((_, e)) => {
+                device.destroy_pipeline_layout(pipeline_layout, None);
+                Err(anyhow::anyhow!("Failed to create particle compute pipeline: {:?}", e))
+            }
+        }
+    }
+
+    unsafe fn create_render_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        extent: vk::Extent2D,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+        descriptor_set_layout: vk::DescriptorSetLayout,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let vert_shader_code = crate::core::shader::load_spirv("particle.vert.spv", include_bytes!("../../../shaders/particle.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("particle.frag.spv", include_bytes!("../../../shaders/particle.frag.spv"));
+
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let shader_stages = [
+            vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::VERTEX)
+                .module(vert_shader_module)
+                .name(&entry_point),
+            vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(frag_shader_module)
+                .name(&entry_point),
+        ];
+
+        // Quads are built from gl_VertexIndex and the particle buffer
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+
+        let scissor = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent,
+        };
+
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewports(std::slice::from_ref(&viewport))
+            .scissors(std::slice::from_ref(&scissor));
+
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(samples);
+
+        // Hidden behind geometry, but additive particles don't occlude each other
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(false)
+            .depth_compare_op(vk::CompareOp::LESS);
+
+        // Additive blending; the scene's alpha is left untouched
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::ONE)
+            .dst_color_blend_factor(vk::BlendFactor::ONE)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ZERO)
+            .dst_alpha_blend_factor(vk::BlendFactor::ONE)
+            .alpha_blend_op(vk::BlendOp::ADD);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let set_layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default().set_layouts(&set_layouts);
+
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device.create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None);
+
+        match pipelines {
+            Ok(pipelines) => Ok((pipeline_layout, pipelines[0])),
+            Err((_, e)) => {
+                device.destroy_pipeline_layout(pipeline_layout, None);
+                Err(anyhow::anyhow!("Failed to create particle pipeline: {:?}", e))
+            }
+        }
+    }
+}
@@ -0,0 +1,770 @@
+use ash::vk;
+use anyhow::Result;
+use glam::{Mat4, Vec3};
+
+use crate::core::{DrawStats, RenderPass};
+use crate::mesh::Mesh;
+use crate::game::Game;
+
+/// Depth format used for the shadow map
+const SHADOW_MAP_FORMAT: vk::Format = vk::Format::D32_SFLOAT;
+
+/// Push constants for shadow rendering (light-space matrix + model matrix)
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ShadowPushConstants {
+    pub light_space: Mat4,
+    pub model: Mat4,
+}
+
+/// Directional light shadow map pass (single cascade)
+///
+/// Renders cubes and custom meshes into a depth texture from the light's point of view.
+/// This pass owns its own Vulkan render pass, so the renderer records it before the
+/// main render pass begins instead of registering it with the RenderPassRegistry.
+pub struct ShadowPass {
+    resolution: u32,
+    enabled: bool,
+    light_space_matrix: Mat4,
+
+    // Shadow map target
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    depth_image: vk::Image,
+    depth_image_memory: vk::DeviceMemory,
+    depth_image_view: vk::ImageView,
+    sampler: vk::Sampler,
+
+    // Depth-only pipeline
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+
+    // Built-in cube mesh (custom meshes come from the renderer)
+    cube_mesh: Mesh,
+    cube_vertex_buffer: vk::Buffer,
+    cube_vertex_buffer_memory: vk::DeviceMemory,
+    cube_index_buffer: vk::Buffer,
+    cube_index_buffer_memory: vk::DeviceMemory,
+
+    draw_stats: DrawStats,
+}
+
+impl ShadowPass {
+    pub fn new(resolution: u32) -> Self {
+        Self {
+            resolution,
+            enabled: true,
+            light_space_matrix: Mat4::IDENTITY,
+            render_pass: vk::RenderPass::null(),
+            framebuffer: vk::Framebuffer::null(),
+            depth_image: vk::Image::null(),
+            depth_image_memory: vk::DeviceMemory::null(),
+            depth_image_view: vk::ImageView::null(),
+            sampler: vk::Sampler::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            cube_mesh: Mesh::create_cube(),
+            cube_vertex_buffer: vk::Buffer::null(),
+            cube_vertex_buffer_memory: vk::DeviceMemory::null(),
+            cube_index_buffer: vk::Buffer::null(),
+            cube_index_buffer_memory: vk::DeviceMemory::null(),
+            draw_stats: DrawStats::default(),
+        }
+    }
+
+    /// Current shadow map size in texels
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    /// Shadow map image view (sampled by mesh.frag)
+    pub fn image_view(&self) -> vk::ImageView {
+        self.depth_image_view
+    }
+
+    /// Sampler for the shadow map
+    pub fn sampler(&self) -> vk::Sampler {
+        self.sampler
+    }
+
+    /// Light-space matrix computed in the last update
+    pub fn light_space_matrix(&self) -> Mat4 {
+        self.light_space_matrix
+    }
+
+    /// Build an orthographic light-space matrix covering `range` units around `center`
+    pub fn compute_light_space_matrix(light_direction: Vec3, center: Vec3, range: f32) -> Mat4 {
+        let direction = light_direction.try_normalize().unwrap_or(Vec3::NEG_Y);
+        let range = range.max(0.1);
+
+        // Avoid a degenerate look_at when the light points straight up/down
+        let up = if direction.dot(Vec3::Y).abs() > 0.99 { Vec3::Z } else { Vec3::Y };
+
+        // Pull the light back so casters behind the camera still land in the map
+        let eye = center - direction * range * 2.0;
+        let view = Mat4::look_at_rh(eye, center, up);
+        let proj = Mat4::orthographic_rh(-range, range, -range, range, 0.0, range * 4.0);
+
+        proj * view
+    }
+
+    /// Recreate the shadow map at a new resolution
+    /// Caller must make sure the GPU is idle and rewrite descriptors that reference the old view
+    pub fn resize(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        resolution: u32,
+    ) -> Result<()> {
+        unsafe {
+            self.destroy_target(device);
+
+            self.resolution = resolution;
+            self.create_target(instance, physical_device, device)?;
+
+            println!("Shadow map resized to {}x{}", resolution, resolution);
+            Ok(())
+        }
+    }
+
+    unsafe fn create_target(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+    ) -> Result<()> {
+        let (image, memory, view) = Self::create_depth_resources(instance, physical_device, device, self.resolution)?;
+        self.depth_image = image;
+        self.depth_image_memory = memory;
+        self.depth_image_view = view;
+
+        let attachments = [self.depth_image_view];
+        let framebuffer_info = vk::FramebufferCreateInfo::default()
+            .render_pass(self.render_pass)
+            .attachments(&attachments)
+            .width(self.resolution)
+            .height(self.resolution)
+            .layers(1);
+        self.framebuffer = device.create_framebuffer(&framebuffer_info, None)?;
+
+        Ok(())
+    }
+
+    unsafe fn destroy_target(&mut self, device: &ash::Device) {
+        if self.framebuffer != vk::Framebuffer::null() {
+            device.destroy_framebuffer(self.framebuffer, None);
+            self.framebuffer = vk::Framebuffer::null();
+        }
+        if self.depth_image_view != vk::ImageView::null() {
+            device.destroy_image_view(self.depth_image_view, None);
+            self.depth_image_view = vk::ImageView::null();
+        }
+        if self.depth_image != vk::Image::null() {
+            device.destroy_image(self.depth_image, None);
+            device.free_memory(self.depth_image_memory, None);
+            self.depth_image = vk::Image::null();
+            self.depth_image_memory = vk::DeviceMemory::null();
+        }
+    }
+
+    unsafe fn create_depth_resources(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        resolution: u32,
+    ) -> Result<(vk::Image, vk::DeviceMemory, vk::ImageView)> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: resolution,
+                height: resolution,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(SHADOW_MAP_FORMAT)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        let image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(image);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        let image_memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, image_memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(SHADOW_MAP_FORMAT)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::DEPTH,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+
+        let image_view = device.create_image_view(&view_info, None)?;
+
+        Ok((image, image_memory, image_view))
+    }
+
+    unsafe fn create_render_pass(device: &ash::Device) -> Result<vk::RenderPass> {
+        // Single depth attachment, left in a shader-readable layout for mesh.frag
+        let depth_attachment = vk::AttachmentDescription::default()
+            .format(SHADOW_MAP_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL);
+
+        let depth_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .depth_stencil_attachment(&depth_attachment_ref);
+
+        let dependencies = [
+            // Wait for last frame's fragment shader reads before writing depth
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS)
+                .dst_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE),
+            // Make depth writes visible to the main pass fragment shader
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::LATE_FRAGMENT_TESTS)
+                .src_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&depth_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // Clamp to a white border so everything outside the map reads as lit
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::NEAREST)
+            .min_filter(vk::Filter::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_BORDER)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_BORDER)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_BORDER)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_OPAQUE_WHITE)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let vert_shader_code = crate::core::shader::load_spirv("shadow.vert.spv", include_bytes!("../../../shaders/shadow.vert.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        // Depth-only: no fragment stage
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info];
+
+        let binding_description = crate::mesh::Vertex::get_binding_description();
+        let attribute_descriptions = crate::mesh::Vertex::get_attribute_descriptions();
+
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+            .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+            .vertex_attribute_descriptions(&attribute_descriptions);
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // Viewport/scissor are dynamic so the shadow map can be resized without a new pipeline
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+            .dynamic_states(&dynamic_states);
+
+        // No culling - light-space winding differs from the camera and thin meshes need both faces
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(true)
+            .depth_compare_op(vk::CompareOp::LESS);
+
+        // No color attachments
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false);
+
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::VERTEX)
+            .offset(0)
+            .size(std::mem::size_of::<ShadowPushConstants>() as u32);
+
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .dynamic_state(&dynamic_state)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create shadow pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+
+    unsafe fn create_device_local_buffer<T: Copy>(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        graphics_queue: vk::Queue,
+        data: &[T],
+        usage: vk::BufferUsageFlags,
+    ) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+        let buffer_size = std::mem::size_of_val(data) as vk::DeviceSize;
+
+        // Create staging buffer
+        let (staging_buffer, staging_memory) = Self::create_buffer(
+            instance,
+            physical_device,
+            device,
+            buffer_size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        // Copy data to staging buffer
+        let mapped = device.map_memory(staging_memory, 0, buffer_size, vk::MemoryMapFlags::empty())?;
+        std::ptr::copy_nonoverlapping(data.as_ptr(), mapped as *mut T, data.len());
+        device.unmap_memory(staging_memory);
+
+        // Create device local buffer
+        let (buffer, memory) = Self::create_buffer(
+            instance,
+            physical_device,
+            device,
+            buffer_size,
+            vk::BufferUsageFlags::TRANSFER_DST | usage,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        )?;
+
+        // Copy from staging to device local buffer
+        Self::copy_buffer(device, command_pool, graphics_queue, staging_buffer, buffer, buffer_size)?;
+
+        // Cleanup staging buffer
+        device.destroy_buffer(staging_buffer, None);
+        device.free_memory(staging_memory, None);
+
+        Ok((buffer, memory))
+    }
+
+    unsafe fn create_buffer(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        size: vk::DeviceSize,
+        usage: vk::BufferUsageFlags,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(size)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let buffer = device.create_buffer(&buffer_info, None)?;
+        let mem_requirements = device.get_buffer_memory_requirements(buffer);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                properties,
+            )?);
+
+        let buffer_memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_buffer_memory(buffer, buffer_memory, 0)?;
+
+        Ok((buffer, buffer_memory))
+    }
+
+    unsafe fn find_memory_type(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        type_filter: u32,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<u32> {
+        let mem_properties = instance.get_physical_device_memory_properties(physical_device);
+
+        for i in 0..mem_properties.memory_type_count {
+            if (type_filter & (1 << i)) != 0
+                && mem_properties.memory_types[i as usize].property_flags.contains(properties)
+            {
+                return Ok(i);
+            }
+        }
+
+        Err(anyhow::anyhow!("Failed to find suitable memory type"))
+    }
+
+    unsafe fn copy_buffer(
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        graphics_queue: vk::Queue,
+        src_buffer: vk::Buffer,
+        dst_buffer: vk::Buffer,
+        size: vk::DeviceSize,
+    ) -> Result<()> {
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(command_pool)
+            .command_buffer_count(1);
+
+        let command_buffers = device.allocate_command_buffers(&alloc_info)?;
+        let command_buffer = command_buffers[0];
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        device.begin_command_buffer(command_buffer, &begin_info)?;
+
+        let copy_region = vk::BufferCopy::default().size(size);
+        device.cmd_copy_buffer(command_buffer, src_buffer, dst_buffer, &[copy_region]);
+
+        device.end_command_buffer(command_buffer)?;
+
+        let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+
+        device.queue_submit(graphics_queue, &[submit_info], vk::Fence::null())?;
+        device.queue_wait_idle(graphics_queue)?;
+
+        device.free_command_buffers(command_pool, &command_buffers);
+
+        Ok(())
+    }
+
+    unsafe fn draw(
+        &self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        model: Mat4,
+        index_count: u32,
+        stats: &mut DrawStats,
+    ) {
+        let push_data = ShadowPushConstants {
+            light_space: self.light_space_matrix,
+            model,
+        };
+        ctx.device.cmd_push_constants(
+            command_buffer,
+            self.pipeline_layout,
+            vk::ShaderStageFlags::VERTEX,
+            0,
+            bytemuck::bytes_of(&push_data),
+        );
+        ctx.device.cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
+        stats.record_draw(index_count, 1);
+    }
+}
+
+impl RenderPass for ShadowPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Shadow pass uses its own depth-only render pass at its own resolution
+            self.render_pass = Self::create_render_pass(ctx.device)?;
+            self.create_target(ctx.instance, ctx.physical_device, ctx.device)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            // Create cube mesh buffers
+            let (cube_vb, cube_vb_mem) = Self::create_device_local_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &self.cube_mesh.vertices,
+                vk::BufferUsageFlags::VERTEX_BUFFER,
+            )?;
+            self.cube_vertex_buffer = cube_vb;
+            self.cube_vertex_buffer_memory = cube_vb_mem;
+
+            let (cube_ib, cube_ib_mem) = Self::create_device_local_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &self.cube_mesh.indices,
+                vk::BufferUsageFlags::INDEX_BUFFER,
+            )?;
+            self.cube_index_buffer = cube_ib;
+            self.cube_index_buffer_memory = cube_ib_mem;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        self.enabled = game.shadow_config.enabled;
+
+        // Single cascade centered on the camera
+        self.light_space_matrix = Self::compute_light_space_matrix(
+            game.get_directional_light_direction(),
+            game.get_camera_position(),
+            game.shadow_config.range,
+        );
+
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() {
+                return Ok(()); // Not initialized yet
+            }
+
+            let extent = vk::Extent2D {
+                width: self.resolution,
+                height: self.resolution,
+            };
+
+            // Always begin the pass so the map is cleared and transitioned to a readable layout,
+            // even when shadows are disabled
+            let clear_values = [vk::ClearValue {
+                depth_stencil: vk::ClearDepthStencilValue {
+                    depth: 1.0,
+                    stencil: 0,
+                },
+            }];
+
+            let render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.render_pass)
+                .framebuffer(self.framebuffer)
+                .render_area(vk::Rect2D {
+                    offset: vk::Offset2D { x: 0, y: 0 },
+                    extent,
+                })
+                .clear_values(&clear_values);
+
+            ctx.device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+
+            let mut stats = DrawStats::default();
+            if self.enabled {
+                ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+
+                let viewport = vk::Viewport {
+                    x: 0.0,
+                    y: 0.0,
+                    width: self.resolution as f32,
+                    height: self.resolution as f32,
+                    min_depth: 0.0,
+                    max_depth: 1.0,
+                };
+                ctx.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+
+                let scissor = vk::Rect2D {
+                    offset: vk::Offset2D { x: 0, y: 0 },
+                    extent,
+                };
+                ctx.device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+                // 1. Cubes
+                let visible_cubes = game.get_visible_cubes();
+                if !visible_cubes.is_empty() {
+                    ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[self.cube_vertex_buffer], &[0]);
+                    ctx.device.cmd_bind_index_buffer(command_buffer, self.cube_index_buffer, 0, vk::IndexType::UINT32);
+
+                    let indices_per_cube = self.cube_mesh.indices.len() as u32;
+                    for model_matrix in visible_cubes.iter() {
+                        self.draw(ctx, command_buffer, game.render_matrix(*model_matrix), indices_per_cube, &mut stats);
+                    }
+                }
+
+                // 2. Built-in primitives (shared buffers owned by renderer)
+                if let Some(primitive_meshes) = ctx.primitive_meshes {
+                    for (shape, model_matrix, _) in game.get_visible_primitives_with_materials().iter() {
+                        if let Some((mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) = primitive_meshes.get(shape) {
+                            ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[*vertex_buffer], &[0]);
+                            ctx.device.cmd_bind_index_buffer(command_buffer, *index_buffer, 0, vk::IndexType::UINT32);
+                            self.draw(ctx, command_buffer, game.render_matrix(*model_matrix), mesh.indices.len() as u32, &mut stats);
+                        }
+                    }
+                }
+
+                // 3. Custom meshes (loaded by renderer)
+                if let Some(custom_meshes) = ctx.custom_meshes {
+                    for (mesh_path, model_matrix) in game.get_visible_meshes().iter() {
+                        if let Some((mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) = custom_meshes.get(mesh_path) {
+                            ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[*vertex_buffer], &[0]);
+                            ctx.device.cmd_bind_index_buffer(command_buffer, *index_buffer, 0, vk::IndexType::UINT32);
+                            self.draw(ctx, command_buffer, game.render_matrix(*model_matrix), mesh.indices.len() as u32, &mut stats);
+                        }
+                    }
+                }
+            }
+
+            ctx.device.cmd_end_render_pass(command_buffer);
+            self.draw_stats = stats;
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Shadow map size is independent of the swapchain
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            self.destroy_target(device);
+
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.render_pass, None);
+            }
+            if self.cube_vertex_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.cube_vertex_buffer, None);
+                device.free_memory(self.cube_vertex_buffer_memory, None);
+            }
+            if self.cube_index_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.cube_index_buffer, None);
+                device.free_memory(self.cube_index_buffer_memory, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Shadow"
+    }
+
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
+}
@@ -1,11 +1,12 @@
 /// Skybox render pass plugin
 ///
-/// Renders the starry background skybox
+/// Renders the starry background skybox, or a loaded cubemap in `SkyboxMode::Cubemap`
 
 use ash::vk;
 use anyhow::Result;
 use crate::background::{SkyboxRenderer, SkyboxUniformBufferObject};
-use crate::core::{RenderPass, RenderContext};
+use crate::core::{CubemapFaces, CubemapTexture, RenderPass, RenderContext};
+use crate::game::SkyboxMode;
 use crate::mesh::Mesh;
 use std::ffi::CString;
 
@@ -13,12 +14,18 @@ const MAX_FRAMES_IN_FLIGHT: usize = 2;
 
 pub struct SkyboxPass {
     renderer: Option<SkyboxRenderer>,
+    /// Cubemap path last requested by the config (None in procedural mode)
+    cubemap_request: Option<String>,
+    /// True once the requested cubemap is loaded; false falls back to procedural stars
+    cubemap_active: bool,
 }
 
 impl SkyboxPass {
     pub fn new() -> Self {
         Self {
             renderer: None,
+            cubemap_request: None,
+            cubemap_active: false,
         }
     }
 
@@ -29,7 +36,13 @@ impl SkyboxPass {
             .descriptor_count(1)
             .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT);
 
-        let bindings = [ubo_binding];
+        let cubemap_binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(1)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+        let bindings = [ubo_binding, cubemap_binding];
         let create_info = vk::DescriptorSetLayoutCreateInfo::default()
             .bindings(&bindings);
 
@@ -38,15 +51,17 @@ impl SkyboxPass {
 
     unsafe fn create_pipeline(
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         extent: vk::Extent2D,
         render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
         descriptor_set_layout: vk::DescriptorSetLayout,
     ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
-        let vert_shader_code = include_bytes!("../../../shaders/skybox.vert.spv");
-        let frag_shader_code = include_bytes!("../../../shaders/skybox.frag.spv");
+        let vert_shader_code = crate::core::shader::load_spirv("skybox.vert.spv", include_bytes!("../../../shaders/skybox.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("skybox.frag.spv", include_bytes!("../../../shaders/skybox.frag.spv"));
 
-        let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-        let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
         let entry_point = CString::new("main")?;
 
@@ -102,7 +117,7 @@ impl SkyboxPass {
 
         let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+            .rasterization_samples(samples);
 
         let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
             .depth_test_enable(true)
@@ -137,23 +152,14 @@ impl SkyboxPass {
             .subpass(0);
 
         let pipelines = device.create_graphics_pipelines(
-            vk::PipelineCache::null(),
+            cache.handle(),
             std::slice::from_ref(&pipeline_info),
             None,
         ).map_err(|e| anyhow::anyhow!("Failed to create skybox pipeline: {:?}", e.1))?;
 
-        device.destroy_shader_module(vert_shader_module, None);
-        device.destroy_shader_module(frag_shader_module, None);
-
         Ok((pipeline_layout, pipelines[0]))
     }
 
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
-        let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
-        let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
-        Ok(device.create_shader_module(&create_info, None)?)
-    }
-
     unsafe fn create_uniform_buffers(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
@@ -235,11 +241,14 @@ impl SkyboxPass {
     }
 
     unsafe fn create_descriptor_pool(device: &ash::Device) -> Result<vk::DescriptorPool> {
-        let pool_size = vk::DescriptorPoolSize::default()
-            .ty(vk::DescriptorType::UNIFORM_BUFFER)
-            .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32);
-
-        let pool_sizes = [pool_size];
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::UNIFORM_BUFFER)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(MAX_FRAMES_IN_FLIGHT as u32),
+        ];
 
         let pool_info = vk::DescriptorPoolCreateInfo::default()
             .pool_sizes(&pool_sizes)
@@ -284,6 +293,64 @@ impl SkyboxPass {
 
         Ok(descriptor_sets)
     }
+
+    /// Point binding 1 of every frame's descriptor set at the current cubemap
+    unsafe fn write_cubemap_descriptors(device: &ash::Device, renderer: &SkyboxRenderer) {
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(renderer.cubemap.view)
+            .sampler(renderer.cubemap_sampler);
+        let image_infos = [image_info];
+
+        let descriptor_writes: Vec<_> = renderer
+            .descriptor_sets
+            .iter()
+            .map(|&set| {
+                vk::WriteDescriptorSet::default()
+                    .dst_set(set)
+                    .dst_binding(1)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(&image_infos)
+            })
+            .collect();
+
+        device.update_descriptor_sets(&descriptor_writes, &[]);
+    }
+
+    /// Load the cubemap at `path` and bind it; on failure keep drawing procedural stars
+    unsafe fn load_cubemap(&mut self, ctx: &RenderContext, path: &str) -> Result<()> {
+        let Some(renderer) = self.renderer.as_mut() else {
+            return Ok(());
+        };
+
+        let faces = match CubemapFaces::load(path) {
+            Ok(faces) => faces,
+            Err(err) => {
+                crate::console::warn(format!("Skybox cubemap '{}' failed to load ({:#}), using procedural stars", path, err));
+                return Ok(());
+            }
+        };
+
+        let cubemap = CubemapTexture::upload(
+            ctx.instance,
+            ctx.physical_device,
+            ctx.device,
+            ctx.command_pool,
+            ctx.graphics_queue,
+            &faces,
+        )?;
+
+        // The old cubemap may still be referenced by frames in flight
+        ctx.device.device_wait_idle()?;
+        renderer.cubemap.destroy(ctx.device);
+        renderer.cubemap = cubemap;
+        Self::write_cubemap_descriptors(ctx.device, renderer);
+
+        self.cubemap_active = true;
+        crate::console::info(format!("Skybox cubemap loaded: {} ({}x{} per face)", path, faces.size, faces.size));
+        Ok(())
+    }
 }
 
 impl RenderPass for SkyboxPass {
@@ -351,8 +418,10 @@ impl RenderPass for SkyboxPass {
             // Create pipeline
             let (pipeline_layout, pipeline) = Self::create_pipeline(
                 ctx.device,
+                ctx.pipeline_cache,
                 extent,
                 render_pass,
+                ctx.msaa_samples,
                 descriptor_set_layout,
             )?;
 
@@ -372,7 +441,18 @@ impl RenderPass for SkyboxPass {
                 &uniform_buffers,
             )?;
 
-            self.renderer = Some(SkyboxRenderer {
+            // Black placeholder until a cubemap is requested
+            let cubemap = CubemapTexture::upload(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &CubemapFaces::solid([0.0, 0.0, 0.0, 1.0]),
+            )?;
+            let cubemap_sampler = crate::core::cubemap::create_sampler(ctx.device, 1)?;
+
+            let renderer = SkyboxRenderer {
                 mesh,
                 vertex_buffer,
                 vertex_buffer_memory,
@@ -385,7 +465,15 @@ impl RenderPass for SkyboxPass {
                 uniform_buffers_memory,
                 descriptor_pool,
                 descriptor_sets,
-            });
+                cubemap,
+                cubemap_sampler,
+            };
+            Self::write_cubemap_descriptors(ctx.device, &renderer);
+            self.renderer = Some(renderer);
+
+            // Reload the configured cubemap (if any) on the next update
+            self.cubemap_request = None;
+            self.cubemap_active = false;
 
             Ok(())
         }
@@ -393,13 +481,24 @@ impl RenderPass for SkyboxPass {
 
     fn update(&mut self, ctx: &RenderContext, frame_index: usize, game: &crate::game::Game) -> Result<()> {
         unsafe {
+            // (Re)load the cubemap when cubemap mode is entered or its path changes
+            let config = &game.skybox_config;
+            let request = (config.mode == SkyboxMode::Cubemap).then(|| config.cubemap_path.trim().to_string());
+            if request != self.cubemap_request {
+                self.cubemap_request = request.clone();
+                self.cubemap_active = false;
+                if let Some(path) = request.filter(|path| !path.is_empty()) {
+                    self.load_cubemap(ctx, &path)?;
+                }
+            }
+
             if let Some(renderer) = &self.renderer {
                 let view = game.get_view_matrix();
                 let aspect = ctx.extent.width as f32 / ctx.extent.height as f32;
                 let proj = game.camera.projection_matrix(aspect);
-                let view_pos = game.camera.position();
+                let view_pos = game.get_camera_position();
 
-                let ubo = SkyboxRenderer::create_ubo(view, proj, view_pos, &game.skybox_config);
+                let ubo = SkyboxRenderer::create_ubo(view, proj, view_pos, &game.skybox_config, self.cubemap_active);
 
                 let data = ctx.device.map_memory(
                     renderer.uniform_buffers_memory[frame_index],
@@ -474,8 +573,10 @@ impl RenderPass for SkyboxPass {
                 // Recreate pipeline with new extent
                 let (pipeline_layout, pipeline) = Self::create_pipeline(
                     ctx.device,
+                    ctx.pipeline_cache,
                     extent,
                     render_pass,
+                    ctx.msaa_samples,
                     renderer.descriptor_set_layout,
                 )?;
 
@@ -489,7 +590,7 @@ impl RenderPass for SkyboxPass {
 
     fn cleanup(&mut self, device: &ash::Device) {
         unsafe {
-            if let Some(renderer) = &self.renderer {
+            if let Some(renderer) = self.renderer.take() {
                 renderer.cleanup(device);
             }
         }
@@ -499,8 +600,7 @@ impl RenderPass for SkyboxPass {
         "Skybox"
     }
 
-    fn should_render(&self, _game: &crate::game::Game) -> bool {
-        // Always render skybox
-        true
+    fn should_render(&self, game: &crate::game::Game) -> bool {
+        game.draws_skybox()
     }
 }
@@ -2,7 +2,7 @@ use ash::vk;
 use anyhow::Result;
 use glam::{Mat4, Vec3};
 
-use crate::core::RenderPass;
+use crate::core::{DrawStats, RenderPass};
 use crate::mesh::Mesh;
 use crate::game::Game;
 
@@ -22,7 +22,15 @@ struct StarUniformBufferObject {
     speed_hi: f32,
     speed_low: f32,
     zoom: f32,
-    _padding: f32,
+    log_depth: f32,
+    // std140 starts the following vec3 on a 16-byte boundary
+    _fog_align: [f32; 2],
+    fog_color: Vec3,
+    fog_density: f32,
+    fog_mode: u32,
+    fog_start: f32,
+    fog_end: f32,
+    _fog_pad: f32,
 }
 
 pub struct StarPass {
@@ -43,6 +51,7 @@ pub struct StarPass {
     descriptor_sets: Vec<vk::DescriptorSet>,
 
     max_frames_in_flight: usize,
+    draw_stats: DrawStats,
 }
 
 impl StarPass {
@@ -61,6 +70,7 @@ impl StarPass {
             descriptor_pool: vk::DescriptorPool::null(),
             descriptor_sets: Vec::new(),
             max_frames_in_flight,
+            draw_stats: DrawStats::default(),
         }
     }
 
@@ -238,7 +248,7 @@ impl StarPass {
 
     unsafe fn update_uniform_buffer(
         &self,
-        device: &ash::Device,
+        ctx: &crate::core::RenderContext,
         frame_index: usize,
         game: &Game,
         model: Mat4,
@@ -247,7 +257,7 @@ impl StarPass {
         let view = game.get_view_matrix();
         let view_pos = game.get_camera_position();
 
-        let aspect = 1920.0 / 1080.0; // Will be provided via context in future
+        let aspect = ctx.extent.width as f32 / ctx.extent.height as f32;
         let proj = game.camera.projection_matrix(aspect);
 
         let star_color = game.star_config.color;
@@ -271,17 +281,24 @@ impl StarPass {
             speed_hi,
             speed_low,
             zoom,
-            _padding: 0.0,
+            log_depth: game.camera.log_depth_coefficient(),
+            _fog_align: [0.0; 2],
+            fog_color: game.tonemap_config.shader_color(game.fog_config.color),
+            fog_density: game.fog_config.density,
+            fog_mode: game.fog_config.shader_mode(),
+            fog_start: game.fog_config.start,
+            fog_end: game.fog_config.end,
+            _fog_pad: 0.0,
         };
 
-        let data = device.map_memory(
+        let data = ctx.device.map_memory(
             self.uniform_buffers_memory[frame_index],
             0,
             std::mem::size_of::<StarUniformBufferObject>() as vk::DeviceSize,
             vk::MemoryMapFlags::empty(),
         )?;
         std::ptr::copy_nonoverlapping(&ubo, data as *mut StarUniformBufferObject, 1);
-        device.unmap_memory(self.uniform_buffers_memory[frame_index]);
+        ctx.device.unmap_memory(self.uniform_buffers_memory[frame_index]);
 
         Ok(())
     }
@@ -324,8 +341,10 @@ impl RenderPass for StarPass {
             // Create pipeline
             let (pipeline_layout, pipeline) = Self::create_pipeline(
                 ctx.device,
+                ctx.pipeline_cache,
                 extent,
                 render_pass,
+                ctx.msaa_samples,
                 self.descriptor_set_layout,
             )?;
             self.pipeline_layout = pipeline_layout;
@@ -373,6 +392,7 @@ impl RenderPass for StarPass {
         game: &Game,
     ) -> Result<()> {
         unsafe {
+            self.draw_stats = DrawStats::default();
             if self.pipeline == vk::Pipeline::null() {
                 return Ok(());
             }
@@ -400,7 +420,7 @@ impl RenderPass for StarPass {
             // Render each sphere
             for model_matrix in visible_spheres.iter() {
                 // Update uniform buffer for this star
-                self.update_uniform_buffer(ctx.device, frame_index, game, *model_matrix)?;
+                self.update_uniform_buffer(ctx, frame_index, game, game.render_matrix(*model_matrix))?;
 
                 // Bind descriptor set
                 ctx.device.cmd_bind_descriptor_sets(
@@ -414,6 +434,7 @@ impl RenderPass for StarPass {
 
                 // Draw star
                 ctx.device.cmd_draw_indexed(command_buffer, indices_per_sphere, 1, 0, 0, 0);
+                self.draw_stats.record_draw(indices_per_sphere, 1);
             }
 
             Ok(())
@@ -436,8 +457,10 @@ impl RenderPass for StarPass {
             // Create new pipeline with new extent
             let (pipeline_layout, pipeline) = Self::create_pipeline(
                 ctx.device,
+                ctx.pipeline_cache,
                 extent,
                 render_pass,
+                ctx.msaa_samples,
                 self.descriptor_set_layout,
             )?;
             self.pipeline_layout = pipeline_layout;
@@ -481,11 +504,27 @@ impl RenderPass for StarPass {
                 device.free_memory(self.uniform_buffers_memory[i], None);
             }
         }
+        self.sphere_vertex_buffer = vk::Buffer::null();
+        self.sphere_vertex_buffer_memory = vk::DeviceMemory::null();
+        self.sphere_index_buffer = vk::Buffer::null();
+        self.sphere_index_buffer_memory = vk::DeviceMemory::null();
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+        self.descriptor_set_layout = vk::DescriptorSetLayout::null();
+        // Destroying the pool freed the sets
+        self.descriptor_pool = vk::DescriptorPool::null();
+        self.descriptor_sets.clear();
+        self.uniform_buffers.clear();
+        self.uniform_buffers_memory.clear();
     }
 
     fn name(&self) -> &str {
         "Star"
     }
+
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
 }
 
 // Static helper methods for resource creation
@@ -505,17 +544,19 @@ impl StarPass {
 
     unsafe fn create_pipeline(
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         extent: vk::Extent2D,
         render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
         descriptor_set_layout: vk::DescriptorSetLayout,
     ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
         use std::ffi::CString;
 
-        let vert_shader_code = include_bytes!("../../../shaders/star.vert.spv");
-        let frag_shader_code = include_bytes!("../../../shaders/star.frag.spv");
+        let vert_shader_code = crate::core::shader::load_spirv("star.vert.spv", include_bytes!("../../../shaders/star.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("star.frag.spv", include_bytes!("../../../shaders/star.frag.spv"));
 
-        let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-        let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
         let entry_point = CString::new("main")?;
 
@@ -592,7 +633,7 @@ impl StarPass {
 
         let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+            .rasterization_samples(samples);
 
         let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
             .depth_test_enable(true)
@@ -633,25 +674,12 @@ impl StarPass {
             .subpass(0);
 
         let pipelines = device
-            .create_graphics_pipelines(vk::PipelineCache::null(), std::slice::from_ref(&pipeline_info), None)
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
             .map_err(|e| anyhow::anyhow!("Failed to create star pipeline: {:?}", e.1))?;
 
-        device.destroy_shader_module(vert_shader_module, None);
-        device.destroy_shader_module(frag_shader_module, None);
-
         Ok((pipeline_layout, pipelines[0]))
     }
 
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
-        let shader_module_create_info = vk::ShaderModuleCreateInfo {
-            code_size: code.len(),
-            p_code: code.as_ptr() as *const u32,
-            ..Default::default()
-        };
-
-        Ok(device.create_shader_module(&shader_module_create_info, None)?)
-    }
-
     unsafe fn create_uniform_buffers(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
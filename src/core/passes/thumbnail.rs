@@ -0,0 +1,932 @@
+use ash::vk;
+use anyhow::Result;
+use glam::{Mat3, Mat4, Vec3};
+
+use crate::core::passes::material_preview::MaterialPreviewPushConstants;
+use crate::core::passes::MaterialPreviewPass;
+use crate::core::RenderPass;
+use crate::game::Game;
+use crate::material::MaterialProperties;
+use crate::mesh::{Mesh, PrimitiveShape};
+use crate::scene::ObjectType;
+
+/// Width and height of one thumbnail in pixels (drawn smaller in the hierarchy)
+pub const THUMBNAIL_SIZE: u32 = 32;
+
+/// Thumbnails per atlas row (the atlas is square)
+const ATLAS_COLUMNS: u32 = 8;
+
+/// Most thumbnails held at once; meshes past this show their type icon instead
+pub const THUMBNAIL_CAPACITY: usize = (ATLAS_COLUMNS * ATLAS_COLUMNS) as usize;
+
+const ATLAS_SIZE: u32 = THUMBNAIL_SIZE * ATLAS_COLUMNS;
+
+/// Color format of the atlas (sampled by the ImGui pipeline)
+const ATLAS_FORMAT: vk::Format = vk::Format::R8G8B8A8_UNORM;
+
+/// Depth format for the atlas (16 bits is plenty at this size and always supported)
+const ATLAS_DEPTH_FORMAT: vk::Format = vk::Format::D16_UNORM;
+
+/// 3/4 view every thumbnail is drawn from (radians, as in the material preview)
+const VIEW_YAW: f32 = 0.6;
+const VIEW_PITCH: f32 = 0.45;
+
+/// Fixed studio light, so thumbnails don't change with the scene's lighting
+const LIGHT_DIRECTION: Vec3 = Vec3::new(-0.4, 0.7, 0.6); // Toward the light, in view space
+const LIGHT_INTENSITY: f32 = 2.5;
+
+/// The mesh a thumbnail shows; objects sharing a mesh share its thumbnail
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ThumbnailKey {
+    Cube,
+    Primitive(PrimitiveShape),
+    Mesh(String),
+}
+
+impl ThumbnailKey {
+    /// Thumbnail for an object type (None for types shown with an icon)
+    pub fn for_object(object_type: &ObjectType) -> Option<Self> {
+        match object_type {
+            ObjectType::Cube => Some(Self::Cube),
+            ObjectType::Primitive(shape) => Some(Self::Primitive(*shape)),
+            ObjectType::Mesh(path) | ObjectType::Unlit(path) => Some(Self::Mesh(path.clone())),
+            _ => None,
+        }
+    }
+}
+
+struct AtlasCell {
+    key: ThumbnailKey,
+    /// Material parameters the cell is (or is about to be) drawn with
+    material: MaterialPreviewPushConstants,
+    drawn: bool,
+}
+
+/// Which atlas cell holds each mesh's thumbnail (read by the hierarchy)
+/// Cells are reused as meshes leave the scene, so memory stays fixed.
+pub struct ThumbnailAtlas {
+    cells: Vec<Option<AtlasCell>>,
+    texture_id: Option<imgui::TextureId>,
+}
+
+impl ThumbnailAtlas {
+    fn new() -> Self {
+        Self {
+            cells: (0..THUMBNAIL_CAPACITY).map(|_| None).collect(),
+            texture_id: None,
+        }
+    }
+
+    /// ImGui texture and UV rectangle of a drawn thumbnail
+    pub fn image(&self, key: &ThumbnailKey) -> Option<(imgui::TextureId, [f32; 2], [f32; 2])> {
+        let texture_id = self.texture_id?;
+        let index = self.cells.iter().position(|cell| {
+            cell.as_ref().is_some_and(|cell| cell.drawn && &cell.key == key)
+        })?;
+
+        let cell_uv = 1.0 / ATLAS_COLUMNS as f32;
+        let column = (index as u32 % ATLAS_COLUMNS) as f32;
+        let row = (index as u32 / ATLAS_COLUMNS) as f32;
+        Some((texture_id, [column * cell_uv, row * cell_uv], [(column + 1.0) * cell_uv, (row + 1.0) * cell_uv]))
+    }
+
+    /// Give each wanted mesh a cell, freeing cells of meshes no longer wanted.
+    /// Returns the cells to draw: new ones and ones whose material changed.
+    fn sync(&mut self, wanted: &[(ThumbnailKey, MaterialPreviewPushConstants)]) -> Vec<usize> {
+        for cell in &mut self.cells {
+            if cell.as_ref().is_some_and(|cell| !wanted.iter().any(|(key, _)| key == &cell.key)) {
+                *cell = None;
+            }
+        }
+
+        let mut dirty = Vec::new();
+        for (key, material) in wanted {
+            let existing = self.cells.iter().position(|cell| cell.as_ref().is_some_and(|cell| &cell.key == key));
+            match existing {
+                Some(index) => {
+                    if let Some(cell) = self.cells[index].as_mut() {
+                        if cell.material != *material || !cell.drawn {
+                            cell.material = *material;
+                            dirty.push(index);
+                        }
+                    }
+                }
+                None => {
+                    // Full atlas: the rest keep their type icon
+                    let Some(index) = self.cells.iter().position(Option::is_none) else {
+                        break;
+                    };
+                    self.cells[index] = Some(AtlasCell { key: key.clone(), material: *material, drawn: false });
+                    dirty.push(index);
+                }
+            }
+        }
+        dirty
+    }
+
+    fn mark_drawn(&mut self, index: usize) {
+        if let Some(cell) = self.cells[index].as_mut() {
+            cell.drawn = true;
+        }
+    }
+
+    /// Draw a mesh's thumbnail again on the next sync (its geometry changed)
+    fn redraw(&mut self, key: &ThumbnailKey) {
+        for cell in self.cells.iter_mut().flatten() {
+            if &cell.key == key {
+                cell.drawn = false;
+            }
+        }
+    }
+
+    /// Forget every thumbnail so they're all drawn again
+    fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| *cell = None);
+    }
+}
+
+/// Hierarchy thumbnail pass
+///
+/// Draws each mesh in the scene (cube, primitives and custom meshes) once into its own
+/// cell of a small offscreen atlas, lit like the material preview with the material of
+/// the first object using it. The atlas is a single ImGui texture; a cell is only
+/// redrawn when that material changes, and cells of meshes that left the scene are reused.
+pub struct ThumbnailPass {
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    color_image: vk::Image,
+    color_image_memory: vk::DeviceMemory,
+    color_image_view: vk::ImageView,
+    depth_image: vk::Image,
+    depth_image_memory: vk::DeviceMemory,
+    depth_image_view: vk::ImageView,
+    sampler: vk::Sampler,
+
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+
+    // The cube isn't one of the renderer's shared primitives
+    cube_mesh: Mesh,
+    cube_vertex_buffer: vk::Buffer,
+    cube_vertex_buffer_memory: vk::DeviceMemory,
+    cube_index_buffer: vk::Buffer,
+    cube_index_buffer_memory: vk::DeviceMemory,
+
+    atlas: ThumbnailAtlas,
+}
+
+impl ThumbnailPass {
+    pub fn new() -> Self {
+        Self {
+            render_pass: vk::RenderPass::null(),
+            framebuffer: vk::Framebuffer::null(),
+            color_image: vk::Image::null(),
+            color_image_memory: vk::DeviceMemory::null(),
+            color_image_view: vk::ImageView::null(),
+            depth_image: vk::Image::null(),
+            depth_image_memory: vk::DeviceMemory::null(),
+            depth_image_view: vk::ImageView::null(),
+            sampler: vk::Sampler::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            cube_mesh: Mesh::create_cube(),
+            cube_vertex_buffer: vk::Buffer::null(),
+            cube_vertex_buffer_memory: vk::DeviceMemory::null(),
+            cube_index_buffer: vk::Buffer::null(),
+            cube_index_buffer_memory: vk::DeviceMemory::null(),
+            atlas: ThumbnailAtlas::new(),
+        }
+    }
+
+    /// Atlas texture view (registered with the ImGui renderer)
+    pub fn image_view(&self) -> vk::ImageView {
+        self.color_image_view
+    }
+
+    /// Sampler for the atlas texture
+    pub fn sampler(&self) -> vk::Sampler {
+        self.sampler
+    }
+
+    /// Set the ImGui texture id the atlas was registered as
+    pub fn set_texture_id(&mut self, texture_id: imgui::TextureId) {
+        self.atlas.texture_id = Some(texture_id);
+    }
+
+    /// Thumbnails drawn so far, for the hierarchy
+    /// Redraw a mesh's thumbnail, e.g. after its vertex buffer was replaced
+    pub fn redraw(&mut self, key: &ThumbnailKey) {
+        self.atlas.redraw(key);
+    }
+
+    pub fn atlas(&self) -> &ThumbnailAtlas {
+        &self.atlas
+    }
+
+    /// Material parameters for a thumbnail (the material preview's, under the studio light)
+    fn material_constants(game: &Game, material: &MaterialProperties) -> MaterialPreviewPushConstants {
+        let color = |srgb: Vec3| game.tonemap_config.shader_color(srgb);
+        MaterialPreviewPushConstants {
+            albedo: color(material.albedo),
+            metallic: material.metallic,
+            light_direction: LIGHT_DIRECTION.normalize(),
+            roughness: material.roughness,
+            light_color: Vec3::splat(LIGHT_INTENSITY),
+            ambient_strength: material.ambient_strength,
+            emissive: color(material.emissive),
+            emissive_strength: material.emissive_strength,
+        }
+    }
+
+    /// Center a mesh, fit its bounding sphere to a 0.5 radius and turn it to the 3/4 view
+    fn mesh_transform(bounds: (Vec3, Vec3)) -> Mat4 {
+        let (min, max) = bounds;
+        let radius = ((max - min).length() * 0.5).max(1e-4);
+        let view = Mat4::from_mat3(Mat3::from_mat4(MaterialPreviewPass::preview_view(VIEW_YAW, VIEW_PITCH)));
+        view * Mat4::from_scale(Vec3::splat(0.5 / radius)) * Mat4::from_translation(-(min + max) * 0.5)
+    }
+
+    /// Mesh data and buffers for a thumbnail, if the mesh is loaded
+    fn mesh_buffers<'a>(
+        &'a self,
+        ctx: &crate::core::RenderContext<'a>,
+        key: &ThumbnailKey,
+    ) -> Option<(&'a Mesh, vk::Buffer, vk::Buffer)> {
+        match key {
+            ThumbnailKey::Cube => Some((&self.cube_mesh, self.cube_vertex_buffer, self.cube_index_buffer)),
+            ThumbnailKey::Primitive(shape) => ctx
+                .primitive_meshes
+                .and_then(|meshes| meshes.get(shape))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (mesh, *vertex_buffer, *index_buffer)),
+            ThumbnailKey::Mesh(path) => ctx
+                .custom_meshes
+                .and_then(|meshes| meshes.get(path))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (mesh, *vertex_buffer, *index_buffer)),
+        }
+    }
+
+    unsafe fn create_image(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        format: vk::Format,
+        usage: vk::ImageUsageFlags,
+        aspect_mask: vk::ImageAspectFlags,
+    ) -> Result<(vk::Image, vk::DeviceMemory, vk::ImageView)> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        let image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(image);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        let memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+        let view = device.create_image_view(&view_info, None)?;
+
+        Ok((image, memory, view))
+    }
+
+    unsafe fn create_target(&mut self, ctx: &crate::core::RenderContext) -> Result<()> {
+        let (image, memory, view) = Self::create_image(
+            ctx.instance,
+            ctx.physical_device,
+            ctx.device,
+            ATLAS_FORMAT,
+            vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST,
+            vk::ImageAspectFlags::COLOR,
+        )?;
+        self.color_image = image;
+        self.color_image_memory = memory;
+        self.color_image_view = view;
+
+        let (image, memory, view) = Self::create_image(
+            ctx.instance,
+            ctx.physical_device,
+            ctx.device,
+            ATLAS_DEPTH_FORMAT,
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+            vk::ImageAspectFlags::DEPTH,
+        )?;
+        self.depth_image = image;
+        self.depth_image_memory = memory;
+        self.depth_image_view = view;
+
+        let attachments = [self.color_image_view, self.depth_image_view];
+        let framebuffer_info = vk::FramebufferCreateInfo::default()
+            .render_pass(self.render_pass)
+            .attachments(&attachments)
+            .width(ATLAS_SIZE)
+            .height(ATLAS_SIZE)
+            .layers(1);
+        self.framebuffer = ctx.device.create_framebuffer(&framebuffer_info, None)?;
+
+        Self::clear_atlas(ctx, self.color_image)
+    }
+
+    /// Clear the atlas to transparent and leave it shader-readable, so the render pass can
+    /// load it and draw only the cells that changed
+    unsafe fn clear_atlas(ctx: &crate::core::RenderContext, image: vk::Image) -> Result<()> {
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(ctx.command_pool)
+            .command_buffer_count(1);
+
+        let command_buffers = ctx.device.allocate_command_buffers(&alloc_info)?;
+        let command_buffer = command_buffers[0];
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+
+        ctx.device.begin_command_buffer(command_buffer, &begin_info)?;
+
+        let range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let to_transfer = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(range)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+        ctx.device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_transfer],
+        );
+
+        let transparent = vk::ClearColorValue { float32: [0.0; 4] };
+        ctx.device.cmd_clear_color_image(
+            command_buffer,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &transparent,
+            &[range],
+        );
+
+        let to_shader_read = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(range)
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ);
+        ctx.device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_shader_read],
+        );
+
+        ctx.device.end_command_buffer(command_buffer)?;
+
+        let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+        ctx.device.queue_submit(ctx.graphics_queue, &[submit_info], vk::Fence::null())?;
+        ctx.device.queue_wait_idle(ctx.graphics_queue)?;
+
+        ctx.device.free_command_buffers(ctx.command_pool, &command_buffers);
+
+        Ok(())
+    }
+
+    unsafe fn create_render_pass(device: &ash::Device) -> Result<vk::RenderPass> {
+        // The atlas keeps its other cells, so it is loaded and stays shader-readable
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(ATLAS_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::LOAD)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+        let depth_attachment = vk::AttachmentDescription::default()
+            .format(ATLAS_DEPTH_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let depth_attachment_ref = vk::AttachmentReference::default()
+            .attachment(1)
+            .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref))
+            .depth_stencil_attachment(&depth_attachment_ref);
+
+        let dependencies = [
+            // Wait for last frame's ImGui draw to stop sampling the atlas (and for the last depth use)
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS)
+                .src_access_mask(vk::AccessFlags::SHADER_READ | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS)
+                .dst_access_mask(
+                    vk::AccessFlags::COLOR_ATTACHMENT_READ
+                        | vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+                        | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+                ),
+            // Make the new thumbnails visible to the ImGui fragment shader
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let attachments = [color_attachment, depth_attachment];
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(&attachments)
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // Thumbnails are drawn smaller than they're rendered; clamping keeps
+        // neighbouring cells from bleeding in at the edges
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_TRANSPARENT_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        // Mesh transform for the vertex shader, then the material preview's constants
+        let push_constant_ranges = [
+            vk::PushConstantRange::default()
+                .stage_flags(vk::ShaderStageFlags::VERTEX)
+                .offset(0)
+                .size(std::mem::size_of::<Mat4>() as u32),
+            vk::PushConstantRange::default()
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+                .offset(std::mem::size_of::<Mat4>() as u32)
+                .size(std::mem::size_of::<MaterialPreviewPushConstants>() as u32),
+        ];
+
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .push_constant_ranges(&push_constant_ranges);
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let vert_shader_code = crate::core::shader::load_spirv("thumbnail.vert.spv", include_bytes!("../../../shaders/thumbnail.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("thumbnail.frag.spv", include_bytes!("../../../shaders/thumbnail.frag.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // Position and normal only (thumbnails have no textures, so UVs are unused)
+        let binding_description = crate::mesh::Vertex::get_binding_description();
+        let attribute_descriptions = crate::mesh::Vertex::get_attribute_descriptions();
+
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+            .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+            .vertex_attribute_descriptions(&attribute_descriptions[..2]);
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // Viewport and scissor select the atlas cell being drawn
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+            .dynamic_states(&dynamic_states);
+
+        // No culling: imported meshes don't all agree on winding
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(true)
+            .depth_compare_op(vk::CompareOp::LESS)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(false);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .dynamic_state(&dynamic_state)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create thumbnail pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+
+    /// Host-visible buffer holding `data` (the cube is tiny, so no staging copy)
+    unsafe fn create_mesh_buffer<T: bytemuck::Pod>(
+        ctx: &crate::core::RenderContext,
+        data: &[T],
+        usage: vk::BufferUsageFlags,
+    ) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let buffer_info = vk::BufferCreateInfo::default()
+            .size(bytes.len() as vk::DeviceSize)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let buffer = ctx.device.create_buffer(&buffer_info, None)?;
+        let mem_requirements = ctx.device.get_buffer_memory_requirements(buffer);
+
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                ctx.instance,
+                ctx.physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )?);
+
+        let memory = ctx.device.allocate_memory(&alloc_info, None)?;
+        ctx.device.bind_buffer_memory(buffer, memory, 0)?;
+
+        let mapped = ctx.device.map_memory(memory, 0, bytes.len() as vk::DeviceSize, vk::MemoryMapFlags::empty())?;
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), mapped as *mut u8, bytes.len());
+        ctx.device.unmap_memory(memory);
+
+        Ok((buffer, memory))
+    }
+
+    unsafe fn find_memory_type(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        type_filter: u32,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<u32> {
+        let mem_properties = instance.get_physical_device_memory_properties(physical_device);
+
+        for i in 0..mem_properties.memory_type_count {
+            if (type_filter & (1 << i)) != 0
+                && mem_properties.memory_types[i as usize].property_flags.contains(properties)
+            {
+                return Ok(i);
+            }
+        }
+
+        Err(anyhow::anyhow!("Failed to find suitable memory type"))
+    }
+}
+
+impl RenderPass for ThumbnailPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Thumbnails use their own render pass at a fixed size
+            self.render_pass = Self::create_render_pass(ctx.device)?;
+            self.create_target(ctx)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            let (vertex_buffer, vertex_memory) =
+                Self::create_mesh_buffer(ctx, &self.cube_mesh.vertices, vk::BufferUsageFlags::VERTEX_BUFFER)?;
+            self.cube_vertex_buffer = vertex_buffer;
+            self.cube_vertex_buffer_memory = vertex_memory;
+
+            let (index_buffer, index_memory) =
+                Self::create_mesh_buffer(ctx, &self.cube_mesh.indices, vk::BufferUsageFlags::INDEX_BUFFER)?;
+            self.cube_index_buffer = index_buffer;
+            self.cube_index_buffer_memory = index_memory;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        // Materials are read in `render`, after the UI has applied this frame's edits
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null() {
+                return Ok(());
+            }
+
+            // One thumbnail per loaded mesh, with the material of the first object using it
+            let unlit_material = MaterialProperties::default();
+            let mut wanted: Vec<(ThumbnailKey, MaterialPreviewPushConstants)> = Vec::new();
+            for obj in game.scene.objects_sorted() {
+                let Some(key) = ThumbnailKey::for_object(&obj.object_type) else {
+                    continue;
+                };
+                if wanted.iter().any(|(wanted_key, _)| wanted_key == &key) || self.mesh_buffers(ctx, &key).is_none() {
+                    continue;
+                }
+                let material = if obj.object_type.takes_material() { game.object_material(obj) } else { &unlit_material };
+                wanted.push((key, Self::material_constants(game, material)));
+            }
+
+            let dirty = self.atlas.sync(&wanted);
+            if dirty.is_empty() {
+                return Ok(()); // Every thumbnail is up to date
+            }
+
+            let clear_values = [
+                vk::ClearValue { color: vk::ClearColorValue { float32: [0.0; 4] } },
+                vk::ClearValue { depth_stencil: vk::ClearDepthStencilValue { depth: 1.0, stencil: 0 } },
+            ];
+            let render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.render_pass)
+                .framebuffer(self.framebuffer)
+                .render_area(vk::Rect2D {
+                    offset: vk::Offset2D { x: 0, y: 0 },
+                    extent: vk::Extent2D {
+                        width: ATLAS_SIZE,
+                        height: ATLAS_SIZE,
+                    },
+                })
+                .clear_values(&clear_values);
+
+            ctx.device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+
+            for index in dirty {
+                let Some(cell) = self.atlas.cells[index].as_ref() else {
+                    continue;
+                };
+                let Some((mesh, vertex_buffer, index_buffer)) = self.mesh_buffers(ctx, &cell.key) else {
+                    continue;
+                };
+
+                let cell_rect = vk::Rect2D {
+                    offset: vk::Offset2D {
+                        x: ((index as u32 % ATLAS_COLUMNS) * THUMBNAIL_SIZE) as i32,
+                        y: ((index as u32 / ATLAS_COLUMNS) * THUMBNAIL_SIZE) as i32,
+                    },
+                    extent: vk::Extent2D {
+                        width: THUMBNAIL_SIZE,
+                        height: THUMBNAIL_SIZE,
+                    },
+                };
+                let viewport = vk::Viewport {
+                    x: cell_rect.offset.x as f32,
+                    y: cell_rect.offset.y as f32,
+                    width: THUMBNAIL_SIZE as f32,
+                    height: THUMBNAIL_SIZE as f32,
+                    min_depth: 0.0,
+                    max_depth: 1.0,
+                };
+                ctx.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+                ctx.device.cmd_set_scissor(command_buffer, 0, &[cell_rect]);
+
+                // Wipe the cell's previous thumbnail (the rest of the atlas is kept)
+                ctx.device.cmd_clear_attachments(
+                    command_buffer,
+                    &[vk::ClearAttachment {
+                        aspect_mask: vk::ImageAspectFlags::COLOR,
+                        color_attachment: 0,
+                        clear_value: clear_values[0],
+                    }],
+                    &[vk::ClearRect { rect: cell_rect, base_array_layer: 0, layer_count: 1 }],
+                );
+
+                let transform = Self::mesh_transform(mesh.calculate_bounds());
+                ctx.device.cmd_push_constants(
+                    command_buffer,
+                    self.pipeline_layout,
+                    vk::ShaderStageFlags::VERTEX,
+                    0,
+                    bytemuck::bytes_of(&transform),
+                );
+                ctx.device.cmd_push_constants(
+                    command_buffer,
+                    self.pipeline_layout,
+                    vk::ShaderStageFlags::FRAGMENT,
+                    std::mem::size_of::<Mat4>() as u32,
+                    bytemuck::bytes_of(&cell.material),
+                );
+                ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer], &[0]);
+                ctx.device.cmd_bind_index_buffer(command_buffer, index_buffer, 0, vk::IndexType::UINT32);
+                ctx.device.cmd_draw_indexed(command_buffer, mesh.indices.len() as u32, 1, 0, 0, 0);
+
+                self.atlas.mark_drawn(index);
+            }
+
+            ctx.device.cmd_end_render_pass(command_buffer);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Atlas size is independent of the swapchain
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            // Redraw every thumbnail with the reloaded shaders
+            self.atlas.clear();
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            if self.framebuffer != vk::Framebuffer::null() {
+                device.destroy_framebuffer(self.framebuffer, None);
+            }
+            for (view, image, memory) in [
+                (self.color_image_view, self.color_image, self.color_image_memory),
+                (self.depth_image_view, self.depth_image, self.depth_image_memory),
+            ] {
+                if view != vk::ImageView::null() {
+                    device.destroy_image_view(view, None);
+                }
+                if image != vk::Image::null() {
+                    device.destroy_image(image, None);
+                    device.free_memory(memory, None);
+                }
+            }
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            for (buffer, memory) in [
+                (self.cube_vertex_buffer, self.cube_vertex_buffer_memory),
+                (self.cube_index_buffer, self.cube_index_buffer_memory),
+            ] {
+                if buffer != vk::Buffer::null() {
+                    device.destroy_buffer(buffer, None);
+                    device.free_memory(memory, None);
+                }
+            }
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.render_pass, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Thumbnails"
+    }
+}
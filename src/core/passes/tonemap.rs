@@ -0,0 +1,464 @@
+use ash::vk;
+use anyhow::Result;
+
+use crate::core::RenderPass;
+use crate::game::{Game, TonemapOperator};
+
+/// Format of the intermediate scene color target the main render pass draws into
+pub const HDR_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+
+/// Push constants for the tonemap shader
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct TonemapPushConstants {
+    pub operator: u32,
+    pub exposure: f32,
+}
+
+/// Tonemap pass
+///
+/// Final fullscreen pass that maps the HDR scene color to the swapchain using the
+/// selected operator and exposure. The swapchain is sRGB, so the hardware applies the
+/// gamma encode on write. Like bloom it owns its Vulkan render pass and is recorded by
+/// the renderer directly; ImGui is drawn afterwards with a compatible overlay pass.
+/// With FXAA on it writes to the FXAA pass's intermediate target instead, whose render
+/// pass has the same swapchain-format attachment so the one pipeline serves both.
+pub struct TonemapPass {
+    operator: TonemapOperator,
+    exposure: f32,
+
+    swapchain_format: vk::Format,
+
+    // HDR image this frame reads from and the framebuffer (and its render pass) it writes to
+    image_index: usize,
+    target_render_pass: vk::RenderPass,
+    target_framebuffer: vk::Framebuffer,
+
+    render_pass: vk::RenderPass,
+
+    // HDR scene color targets as sources (one descriptor set per swapchain image)
+    source_descriptor_sets: Vec<vk::DescriptorSet>,
+
+    sampler: vk::Sampler,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+}
+
+impl TonemapPass {
+    pub fn new(swapchain_format: vk::Format) -> Self {
+        let defaults = crate::game::TonemapConfig::default();
+        Self {
+            operator: defaults.operator,
+            exposure: defaults.exposure,
+            swapchain_format,
+            image_index: 0,
+            target_render_pass: vk::RenderPass::null(),
+            target_framebuffer: vk::Framebuffer::null(),
+            render_pass: vk::RenderPass::null(),
+            source_descriptor_sets: Vec::new(),
+            sampler: vk::Sampler::null(),
+            descriptor_set_layout: vk::DescriptorSetLayout::null(),
+            descriptor_pool: vk::DescriptorPool::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+        }
+    }
+
+    /// Select the HDR source and the swapchain framebuffer to write this frame
+    pub fn set_target(&mut self, image_index: usize, framebuffer: vk::Framebuffer) {
+        self.image_index = image_index;
+        self.target_render_pass = self.render_pass;
+        self.target_framebuffer = framebuffer;
+    }
+
+    /// Select the HDR source and write to an intermediate target instead of the swapchain
+    /// `render_pass` must have a single swapchain-format color attachment
+    pub fn set_intermediate_target(&mut self, image_index: usize, render_pass: vk::RenderPass, framebuffer: vk::Framebuffer) {
+        self.image_index = image_index;
+        self.target_render_pass = render_pass;
+        self.target_framebuffer = framebuffer;
+    }
+
+    /// (Re)create the source descriptors for the current HDR targets
+    /// Caller must make sure the GPU is idle
+    pub fn resize(&mut self, ctx: &crate::core::RenderContext, hdr_image_views: &[vk::ImageView]) -> Result<()> {
+        unsafe {
+            self.destroy_sources(ctx.device);
+
+            let set_count = hdr_image_views.len().max(1) as u32;
+            let pool_size = vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(set_count);
+            let pool_info = vk::DescriptorPoolCreateInfo::default()
+                .pool_sizes(std::slice::from_ref(&pool_size))
+                .max_sets(set_count);
+            self.descriptor_pool = ctx.device.create_descriptor_pool(&pool_info, None)?;
+
+            self.source_descriptor_sets = hdr_image_views
+                .iter()
+                .map(|&view| self.allocate_descriptor_set(ctx.device, view))
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(())
+        }
+    }
+
+    unsafe fn destroy_sources(&mut self, device: &ash::Device) {
+        // Destroying the pool frees every set allocated from it
+        if self.descriptor_pool != vk::DescriptorPool::null() {
+            device.destroy_descriptor_pool(self.descriptor_pool, None);
+            self.descriptor_pool = vk::DescriptorPool::null();
+        }
+        self.source_descriptor_sets.clear();
+    }
+
+    unsafe fn allocate_descriptor_set(&self, device: &ash::Device, view: vk::ImageView) -> Result<vk::DescriptorSet> {
+        let layouts = [self.descriptor_set_layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(self.descriptor_pool)
+            .set_layouts(&layouts);
+        let descriptor_set = device.allocate_descriptor_sets(&alloc_info)?[0];
+
+        let image_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(view)
+            .sampler(self.sampler);
+
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(descriptor_set)
+            .dst_binding(0)
+            .dst_array_element(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(std::slice::from_ref(&image_info));
+        device.update_descriptor_sets(std::slice::from_ref(&write), &[]);
+
+        Ok(descriptor_set)
+    }
+
+    unsafe fn create_render_pass(device: &ash::Device, format: vk::Format) -> Result<vk::RenderPass> {
+        // Every pixel is overwritten, so the previous swapchain contents are never loaded
+        let color_attachment = vk::AttachmentDescription::default()
+            .format(format)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        // Wait for the acquired swapchain image (the HDR source is made visible by the passes writing it)
+        let dependency = vk::SubpassDependency::default()
+            .src_subpass(vk::SUBPASS_EXTERNAL)
+            .dst_subpass(0)
+            .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE);
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(std::slice::from_ref(&dependency));
+
+        Ok(device.create_render_pass(&create_info, None)?)
+    }
+
+    unsafe fn create_sampler(device: &ash::Device) -> Result<vk::Sampler> {
+        // Source and target are the same size, so nearest sampling reads texels exactly
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::NEAREST)
+            .min_filter(vk::Filter::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .anisotropy_enable(false)
+            .max_anisotropy(1.0)
+            .border_color(vk::BorderColor::FLOAT_TRANSPARENT_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .compare_op(vk::CompareOp::ALWAYS)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .mip_lod_bias(0.0)
+            .min_lod(0.0)
+            .max_lod(0.0);
+
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    unsafe fn create_descriptor_set_layout(device: &ash::Device) -> Result<vk::DescriptorSetLayout> {
+        let input_binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .bindings(std::slice::from_ref(&input_binding));
+
+        Ok(device.create_descriptor_set_layout(&layout_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+        descriptor_set_layout: vk::DescriptorSetLayout,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<TonemapPushConstants>() as u32);
+
+        let layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(&layouts)
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let vert_shader_code = crate::core::shader::load_spirv("tonemap.vert.spv", include_bytes!("../../../shaders/tonemap.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("tonemap.frag.spv", include_bytes!("../../../shaders/tonemap.frag.spv"));
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // No vertex input - fullscreen triangle is generated in the vertex shader
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        // Viewport/scissor are dynamic so resizing only rebuilds the descriptors
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+            .dynamic_states(&dynamic_states);
+
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(false);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .color_blend_state(&color_blending)
+            .dynamic_state(&dynamic_state)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(cache.handle(), std::slice::from_ref(&pipeline_info), None)
+            .map_err(|e| anyhow::anyhow!("Failed to create tonemap pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+}
+
+impl RenderPass for TonemapPass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Source descriptors depend on the HDR targets and are created by `resize`
+            self.render_pass = Self::create_render_pass(ctx.device, self.swapchain_format)?;
+            self.sampler = Self::create_sampler(ctx.device)?;
+            self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass, self.descriptor_set_layout)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        self.operator = game.tonemap_config.operator;
+        self.exposure = game.tonemap_config.exposure;
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline == vk::Pipeline::null()
+                || self.target_render_pass == vk::RenderPass::null()
+                || self.image_index >= self.source_descriptor_sets.len()
+            {
+                return Ok(());
+            }
+
+            let render_area = vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent: ctx.extent,
+            };
+
+            let render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.target_render_pass)
+                .framebuffer(self.target_framebuffer)
+                .render_area(render_area);
+
+            ctx.device.cmd_begin_render_pass(command_buffer, &render_pass_info, vk::SubpassContents::INLINE);
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+
+            let viewport = vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: ctx.extent.width as f32,
+                height: ctx.extent.height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+            ctx.device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+            ctx.device.cmd_set_scissor(command_buffer, 0, &[render_area]);
+
+            ctx.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline_layout,
+                0,
+                &[self.source_descriptor_sets[self.image_index]],
+                &[],
+            );
+
+            let push_data = TonemapPushConstants {
+                operator: self.operator.shader_index(),
+                exposure: self.exposure,
+            };
+            ctx.device.cmd_push_constants(
+                command_buffer,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytemuck::bytes_of(&push_data),
+            );
+
+            // Draw fullscreen triangle (no vertex buffer needed)
+            ctx.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+
+            ctx.device.cmd_end_render_pass(command_buffer);
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        // Sources reference the HDR targets, so the renderer rebuilds them via `resize`
+        Ok(())
+    }
+
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        _render_pass: vk::RenderPass,
+        _extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            let (pipeline_layout, pipeline) = Self::create_pipeline(ctx.device, ctx.pipeline_cache, self.render_pass, self.descriptor_set_layout)?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+        }
+        Ok(())
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            self.destroy_sources(device);
+
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
+                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+            }
+            if self.sampler != vk::Sampler::null() {
+                device.destroy_sampler(self.sampler, None);
+            }
+            if self.render_pass != vk::RenderPass::null() {
+                device.destroy_render_pass(self.render_pass, None);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Tonemap"
+    }
+}
@@ -2,7 +2,7 @@ use ash::vk;
 use anyhow::Result;
 use glam::{Mat4, Vec3, Vec4};
 
-use crate::core::{RenderPass, RenderContext};
+use crate::core::{DrawStats, RenderPass, RenderContext};
 use crate::mesh::Mesh;
 use crate::game::Game;
 
@@ -14,6 +14,9 @@ struct UniformBufferObject {
     proj: Mat4,
     view_pos: Vec3,
     time: f32,
+    /// `Camera::log_depth_coefficient` (0 = standard depth)
+    log_depth: f32,
+    _padding: [f32; 3],
 }
 
 /// Push constants for hologram rendering (model matrix + hologram parameters)
@@ -39,6 +42,7 @@ pub struct UnlitPass {
     descriptor_pool: vk::DescriptorPool,
     uniform_buffers: Vec<vk::Buffer>,
     uniform_buffers_memory: Vec<vk::DeviceMemory>,
+    draw_stats: DrawStats,
 }
 
 impl UnlitPass {
@@ -52,15 +56,10 @@ impl UnlitPass {
             descriptor_pool: vk::DescriptorPool::null(),
             uniform_buffers: Vec::new(),
             uniform_buffers_memory: Vec::new(),
+            draw_stats: DrawStats::default(),
         }
     }
 
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
-        let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
-        let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
-        Ok(device.create_shader_module(&create_info, None)?)
-    }
-
     unsafe fn create_buffer(
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
@@ -112,6 +111,110 @@ impl UnlitPass {
 
         anyhow::bail!("Failed to find suitable memory type")
     }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        render_pass: vk::RenderPass,
+        pipeline_layout: vk::PipelineLayout,
+        samples: vk::SampleCountFlags,
+    ) -> Result<vk::Pipeline> {
+        // Load shaders
+        let vert_shader_code = std::fs::read("shaders/unlit.vert.spv")?;
+        let frag_shader_code = std::fs::read("shaders/unlit.frag.spv")?;
+
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = std::ffi::CStr::from_bytes_with_nul(b"main\0").unwrap();
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // Vertex input
+        let binding_description = crate::mesh::Vertex::get_binding_description();
+        let attribute_descriptions = crate::mesh::Vertex::get_attribute_descriptions();
+
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+            .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+            .vertex_attribute_descriptions(&attribute_descriptions);
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::BACK)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(samples);
+
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(false) // Don't write to depth for transparent holograms
+            .depth_compare_op(vk::CompareOp::LESS)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
+        // Enable alpha blending for hologram transparency
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+            .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ONE)
+            .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+            .alpha_blend_op(vk::BlendOp::ADD);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
+            .dynamic_states(&dynamic_states);
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .dynamic_state(&dynamic_state)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipeline = device
+            .create_graphics_pipelines(cache.handle(), &[pipeline_info], None)
+            .map_err(|(_, e)| e)?[0];
+
+        Ok(pipeline)
+    }
 }
 
 impl RenderPass for UnlitPass {
@@ -119,6 +222,10 @@ impl RenderPass for UnlitPass {
         "Unlit"
     }
 
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
+
     fn initialize(
         &mut self,
         ctx: &RenderContext,
@@ -202,102 +309,7 @@ impl RenderPass for UnlitPass {
 
             self.pipeline_layout = ctx.device.create_pipeline_layout(&pipeline_layout_info, None)?;
 
-            // Load shaders
-            let vert_shader_code = std::fs::read("shaders/unlit.vert.spv")?;
-            let frag_shader_code = std::fs::read("shaders/unlit.frag.spv")?;
-
-            let vert_shader_module = Self::create_shader_module(ctx.device, &vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(ctx.device, &frag_shader_code)?;
-
-            let entry_point = std::ffi::CStr::from_bytes_with_nul(b"main\0").unwrap();
-
-            let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
-                .stage(vk::ShaderStageFlags::VERTEX)
-                .module(vert_shader_module)
-                .name(entry_point);
-
-            let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
-                .stage(vk::ShaderStageFlags::FRAGMENT)
-                .module(frag_shader_module)
-                .name(entry_point);
-
-            let shader_stages = [vert_stage_info, frag_stage_info];
-
-            // Vertex input
-            let binding_description = crate::mesh::Vertex::get_binding_description();
-            let attribute_descriptions = crate::mesh::Vertex::get_attribute_descriptions();
-
-            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
-                .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
-                .vertex_attribute_descriptions(&attribute_descriptions);
-
-            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
-                .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
-                .primitive_restart_enable(false);
-
-            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
-                .viewport_count(1)
-                .scissor_count(1);
-
-            let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
-                .depth_clamp_enable(false)
-                .rasterizer_discard_enable(false)
-                .polygon_mode(vk::PolygonMode::FILL)
-                .line_width(1.0)
-                .cull_mode(vk::CullModeFlags::BACK)
-                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
-                .depth_bias_enable(false);
-
-            let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
-                .sample_shading_enable(false)
-                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
-
-            let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
-                .depth_test_enable(true)
-                .depth_write_enable(false) // Don't write to depth for transparent holograms
-                .depth_compare_op(vk::CompareOp::LESS)
-                .depth_bounds_test_enable(false)
-                .stencil_test_enable(false);
-
-            // Enable alpha blending for hologram transparency
-            let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
-                .color_write_mask(vk::ColorComponentFlags::RGBA)
-                .blend_enable(true)
-                .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
-                .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
-                .color_blend_op(vk::BlendOp::ADD)
-                .src_alpha_blend_factor(vk::BlendFactor::ONE)
-                .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
-                .alpha_blend_op(vk::BlendOp::ADD);
-
-            let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
-                .logic_op_enable(false)
-                .attachments(std::slice::from_ref(&color_blend_attachment));
-
-            let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
-            let dynamic_state = vk::PipelineDynamicStateCreateInfo::default()
-                .dynamic_states(&dynamic_states);
-
-            let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
-                .stages(&shader_stages)
-                .vertex_input_state(&vertex_input_info)
-                .input_assembly_state(&input_assembly)
-                .viewport_state(&viewport_state)
-                .rasterization_state(&rasterizer)
-                .multisample_state(&multisampling)
-                .depth_stencil_state(&depth_stencil)
-                .color_blend_state(&color_blending)
-                .dynamic_state(&dynamic_state)
-                .layout(self.pipeline_layout)
-                .render_pass(render_pass)
-                .subpass(0);
-
-            self.pipeline = ctx.device
-                .create_graphics_pipelines(vk::PipelineCache::null(), &[pipeline_info], None)
-                .map_err(|(_, e)| e)?[0];
-
-            ctx.device.destroy_shader_module(vert_shader_module, None);
-            ctx.device.destroy_shader_module(frag_shader_module, None);
+            self.pipeline = Self::create_pipeline(ctx.device, ctx.pipeline_cache, render_pass, self.pipeline_layout, ctx.msaa_samples)?;
 
             Ok(())
         }
@@ -308,10 +320,12 @@ impl RenderPass for UnlitPass {
             // Update uniform buffer with camera data and time
             let aspect_ratio = ctx.extent.width as f32 / ctx.extent.height as f32;
             let ubo = UniformBufferObject {
-                view: game.camera.view_matrix(),
+                view: game.get_view_matrix(),
                 proj: game.camera.projection_matrix(aspect_ratio),
-                view_pos: game.camera.position(),
+                view_pos: game.get_camera_position(),
                 time: game.time(),
+                log_depth: game.camera.log_depth_coefficient(),
+                _padding: [0.0; 3],
             };
 
             let data = ctx.device.map_memory(
@@ -335,6 +349,7 @@ impl RenderPass for UnlitPass {
         game: &Game,
     ) -> Result<()> {
         unsafe {
+            self.draw_stats = DrawStats::default();
             ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
 
             // Bind descriptor set
@@ -349,7 +364,7 @@ impl RenderPass for UnlitPass {
 
             // Render unlit objects from scene
             for obj in game.scene.objects_sorted() {
-                if !obj.visible {
+                if !game.scene.is_shown(obj) {
                     continue;
                 }
 
@@ -357,11 +372,7 @@ impl RenderPass for UnlitPass {
                     // Get mesh from custom_meshes
                     if let Some(custom_meshes) = ctx.custom_meshes {
                         if let Some((_mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) = custom_meshes.get(mesh_path) {
-                            let model_matrix = glam::Mat4::from_scale_rotation_translation(
-                                obj.transform.scale,
-                                obj.transform.rotation,
-                                obj.transform.position,
-                            );
+                            let model_matrix = game.render_matrix(game.scene.world_matrix(obj.id));
 
                             // Cyan holographic color with transparency
                             let color = glam::Vec4::new(0.0, 0.8, 1.0, 0.6);
@@ -407,6 +418,7 @@ impl RenderPass for UnlitPass {
                                     0,
                                     0,
                                 );
+                                self.draw_stats.record_draw(mesh.indices.len() as u32, 1);
                             }
                         }
                     }
@@ -424,7 +436,7 @@ impl RenderPass for UnlitPass {
                                     let mesh_path = "content/models/Fed_cruiser_ship.obj";
                                     if let Some((_mesh, vertex_buffer, _vertex_memory, index_buffer, _index_memory)) = custom_meshes.get(mesh_path) {
                                         // Create model matrix for hologram at planned position
-                                        let position = hologram_pos.as_vec3();
+                                        let position = game.ecs_world.world_to_camera_relative(hologram_pos);
                                         let rotation_quat = glam::Quat::from_xyzw(
                                             rotation.0.x as f32,
                                             rotation.0.y as f32,
@@ -487,6 +499,7 @@ impl RenderPass for UnlitPass {
                                                 0,
                                                 0,
                                             );
+                                            self.draw_stats.record_draw(mesh.indices.len() as u32, 1);
                                         }
                                     }
                                 }
@@ -502,25 +515,38 @@ impl RenderPass for UnlitPass {
 
     fn recreate_swapchain(
         &mut self,
-        _ctx: &RenderContext,
-        _render_pass: vk::RenderPass,
+        ctx: &RenderContext,
+        render_pass: vk::RenderPass,
         _extent: vk::Extent2D,
     ) -> Result<()> {
+        unsafe {
+            // Viewport is dynamic; only the render pass (MSAA level) can change
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+            }
+            self.pipeline = Self::create_pipeline(ctx.device, ctx.pipeline_cache, render_pass, self.pipeline_layout, ctx.msaa_samples)?;
+        }
         Ok(())
     }
 
     fn cleanup(&mut self, device: &ash::Device) {
         unsafe {
+            // Destroying null handles is a no-op, so this is safe before initialize and when repeated
             device.destroy_pipeline(self.pipeline, None);
             device.destroy_pipeline_layout(self.pipeline_layout, None);
             device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
             device.destroy_descriptor_pool(self.descriptor_pool, None);
-            for buffer in &self.uniform_buffers {
-                device.destroy_buffer(*buffer, None);
+            for buffer in self.uniform_buffers.drain(..) {
+                device.destroy_buffer(buffer, None);
             }
-            for memory in &self.uniform_buffers_memory {
-                device.free_memory(*memory, None);
+            for memory in self.uniform_buffers_memory.drain(..) {
+                device.free_memory(memory, None);
             }
         }
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+        self.descriptor_set_layout = vk::DescriptorSetLayout::null();
+        self.descriptor_pool = vk::DescriptorPool::null();
+        self.descriptor_sets.clear();
     }
 }
@@ -0,0 +1,438 @@
+use ash::vk;
+use anyhow::Result;
+use glam::{Mat4, Vec4};
+
+use crate::core::passes::MeshPass;
+use crate::core::{DrawStats, RenderPass};
+use crate::game::Game;
+use crate::mesh::Mesh;
+use crate::scene::ObjectType;
+
+/// Push constants for wireframe overlay rendering
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct WireframePushConstants {
+    pub model: Mat4,
+    pub color: Vec4, // RGB color + alpha
+}
+
+unsafe impl bytemuck::Pod for WireframePushConstants {}
+unsafe impl bytemuck::Zeroable for WireframePushConstants {}
+
+/// Wireframe overlay for objects flagged `wireframe` (or every mesh with "Wireframe All")
+///
+/// Meshes are redrawn in line mode after the shaded passes. Back faces are
+/// culled and lines are depth tested with a small bias toward the camera, so
+/// only the visible edges show up on top of the surface.
+pub struct WireframePass {
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    pipeline_layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+    descriptor_sets: Vec<vk::DescriptorSet>,
+    // Cube buffers (cubes are drawn from MeshPass-owned buffers, so the overlay keeps its own copy)
+    cube_mesh: Mesh,
+    cube_vertex_buffer: vk::Buffer,
+    cube_vertex_buffer_memory: vk::DeviceMemory,
+    cube_index_buffer: vk::Buffer,
+    cube_index_buffer_memory: vk::DeviceMemory,
+    draw_stats: DrawStats,
+}
+
+impl WireframePass {
+    pub fn new() -> Self {
+        Self {
+            descriptor_set_layout: vk::DescriptorSetLayout::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            descriptor_sets: Vec::new(),
+            cube_mesh: Mesh::create_cube(),
+            cube_vertex_buffer: vk::Buffer::null(),
+            cube_vertex_buffer_memory: vk::DeviceMemory::null(),
+            cube_index_buffer: vk::Buffer::null(),
+            cube_index_buffer_memory: vk::DeviceMemory::null(),
+            draw_stats: DrawStats::default(),
+        }
+    }
+
+    /// Vertex buffer, index buffer and index count for an overlaid object
+    fn mesh_buffers(
+        &self,
+        ctx: &crate::core::RenderContext,
+        object_type: &ObjectType,
+    ) -> Option<(vk::Buffer, vk::Buffer, u32)> {
+        match object_type {
+            ObjectType::Cube => Some((self.cube_vertex_buffer, self.cube_index_buffer, self.cube_mesh.indices.len() as u32)),
+            ObjectType::Primitive(shape) => ctx
+                .primitive_meshes
+                .and_then(|meshes| meshes.get(shape))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (*vertex_buffer, *index_buffer, mesh.indices.len() as u32)),
+            ObjectType::Mesh(path) | ObjectType::Unlit(path) => ctx
+                .custom_meshes
+                .and_then(|meshes| meshes.get(path))
+                .map(|(mesh, vertex_buffer, _, index_buffer, _)| (*vertex_buffer, *index_buffer, mesh.indices.len() as u32)),
+            _ => None,
+        }
+    }
+}
+
+impl RenderPass for WireframePass {
+    fn initialize(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Get shared descriptor sets from context
+            if let (Some(mesh_descriptor_sets),) = (ctx.mesh_descriptor_sets,) {
+                self.descriptor_sets = mesh_descriptor_sets.to_vec();
+            }
+
+            // Create descriptor set layout
+            self.descriptor_set_layout = Self::create_descriptor_set_layout(ctx.device)?;
+
+            // Create line-mode pipeline
+            let (pipeline_layout, pipeline) = Self::create_pipeline(
+                ctx.device,
+                ctx.pipeline_cache,
+                extent,
+                render_pass,
+                ctx.msaa_samples,
+                self.descriptor_set_layout,
+            )?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            // Create cube mesh buffers
+            let (cube_vb, cube_vb_mem) = MeshPass::create_vertex_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &self.cube_mesh.vertices,
+            )?;
+            self.cube_vertex_buffer = cube_vb;
+            self.cube_vertex_buffer_memory = cube_vb_mem;
+
+            let (cube_ib, cube_ib_mem) = MeshPass::create_index_buffer(
+                ctx.instance,
+                ctx.physical_device,
+                ctx.device,
+                ctx.command_pool,
+                ctx.graphics_queue,
+                &self.cube_mesh.indices,
+            )?;
+            self.cube_index_buffer = cube_ib;
+            self.cube_index_buffer_memory = cube_ib_mem;
+
+            Ok(())
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &crate::core::RenderContext,
+        _frame_index: usize,
+        _game: &Game,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        command_buffer: vk::CommandBuffer,
+        frame_index: usize,
+        game: &Game,
+    ) -> Result<()> {
+        unsafe {
+            self.draw_stats = DrawStats::default();
+            if self.pipeline == vk::Pipeline::null() {
+                return Ok(());
+            }
+
+            // Get flagged objects from game (or every mesh with "Wireframe All")
+            let wireframe_objects = game.get_wireframe_objects();
+            if wireframe_objects.is_empty() {
+                return Ok(());
+            }
+
+            ctx.device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            ctx.device.cmd_bind_descriptor_sets(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline_layout,
+                0,
+                &[self.descriptor_sets[frame_index]],
+                &[],
+            );
+
+            let color = game.tonemap_config.shader_color(game.wireframe_config.color).extend(1.0);
+
+            for (object_type, model_matrix) in wireframe_objects.iter() {
+                let Some((vertex_buffer, index_buffer, index_count)) = self.mesh_buffers(ctx, object_type) else {
+                    continue;
+                };
+
+                // Bind mesh buffers
+                ctx.device.cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer], &[0]);
+                ctx.device.cmd_bind_index_buffer(command_buffer, index_buffer, 0, vk::IndexType::UINT32);
+
+                let push_data = WireframePushConstants {
+                    model: game.render_matrix(*model_matrix),
+                    color,
+                };
+                ctx.device.cmd_push_constants(
+                    command_buffer,
+                    self.pipeline_layout,
+                    vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+                    0,
+                    bytemuck::bytes_of(&push_data),
+                );
+
+                ctx.device.cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
+                self.draw_stats.record_draw(index_count, 1);
+            }
+
+            Ok(())
+        }
+    }
+
+    fn recreate_swapchain(
+        &mut self,
+        ctx: &crate::core::RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        unsafe {
+            // Destroy old pipeline
+            if self.pipeline != vk::Pipeline::null() {
+                ctx.device.destroy_pipeline(self.pipeline, None);
+                ctx.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+
+            // Create new pipeline
+            let (pipeline_layout, pipeline) = Self::create_pipeline(
+                ctx.device,
+                ctx.pipeline_cache,
+                extent,
+                render_pass,
+                ctx.msaa_samples,
+                self.descriptor_set_layout,
+            )?;
+            self.pipeline_layout = pipeline_layout;
+            self.pipeline = pipeline;
+
+            Ok(())
+        }
+    }
+
+    fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            if self.cube_vertex_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.cube_vertex_buffer, None);
+                device.free_memory(self.cube_vertex_buffer_memory, None);
+            }
+            if self.cube_index_buffer != vk::Buffer::null() {
+                device.destroy_buffer(self.cube_index_buffer, None);
+                device.free_memory(self.cube_index_buffer_memory, None);
+            }
+            if self.pipeline != vk::Pipeline::null() {
+                device.destroy_pipeline(self.pipeline, None);
+            }
+            if self.pipeline_layout != vk::PipelineLayout::null() {
+                device.destroy_pipeline_layout(self.pipeline_layout, None);
+            }
+            if self.descriptor_set_layout != vk::DescriptorSetLayout::null() {
+                device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+            }
+        }
+        self.cube_vertex_buffer = vk::Buffer::null();
+        self.cube_vertex_buffer_memory = vk::DeviceMemory::null();
+        self.cube_index_buffer = vk::Buffer::null();
+        self.cube_index_buffer_memory = vk::DeviceMemory::null();
+        self.pipeline = vk::Pipeline::null();
+        self.pipeline_layout = vk::PipelineLayout::null();
+        self.descriptor_set_layout = vk::DescriptorSetLayout::null();
+    }
+
+    fn name(&self) -> &str {
+        "Wireframe"
+    }
+
+    fn draw_stats(&self) -> Option<DrawStats> {
+        Some(self.draw_stats)
+    }
+}
+
+impl WireframePass {
+    unsafe fn create_descriptor_set_layout(device: &ash::Device) -> Result<vk::DescriptorSetLayout> {
+        let ubo_binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT);
+
+        let bindings = [ubo_binding];
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings);
+
+        Ok(device.create_descriptor_set_layout(&layout_info, None)?)
+    }
+
+    unsafe fn create_pipeline(
+        device: &ash::Device,
+        cache: &crate::core::PipelineCache,
+        extent: vk::Extent2D,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+        descriptor_set_layout: vk::DescriptorSetLayout,
+    ) -> Result<(vk::PipelineLayout, vk::Pipeline)> {
+        use std::ffi::CString;
+
+        let vert_shader_code = crate::core::shader::load_spirv("wireframe_overlay.vert.spv", include_bytes!("../../../shaders/wireframe_overlay.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("wireframe_overlay.frag.spv", include_bytes!("../../../shaders/wireframe_overlay.frag.spv"));
+
+        let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+        let entry_point = CString::new("main")?;
+
+        let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+        let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+        let shader_stages = [vert_stage_info, frag_stage_info];
+
+        // Vertex input
+        let binding_description = vk::VertexInputBindingDescription::default()
+            .binding(0)
+            .stride(std::mem::size_of::<crate::mesh::Vertex>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX);
+
+        let attribute_descriptions = [
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(0)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(0),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(1)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(12),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(2)
+                .format(vk::Format::R32G32_SFLOAT)
+                .offset(24),
+        ];
+
+        let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+            .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+            .vertex_attribute_descriptions(&attribute_descriptions);
+
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+
+        let scissor = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent,
+        };
+
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewports(std::slice::from_ref(&viewport))
+            .scissors(std::slice::from_ref(&scissor));
+
+        // Line mode with back faces culled; the negative bias pulls edges in front
+        // of the shaded surface they were drawn from so they don't z-fight
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::LINE)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::BACK)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(true)
+            .depth_bias_constant_factor(-1.0)
+            .depth_bias_slope_factor(-1.0);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(samples);
+
+        // Depth tested so edges behind other geometry stay hidden
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(false)
+            .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL)
+            .depth_bounds_test_enable(false)
+            .stencil_test_enable(false);
+
+        let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+            .color_write_mask(vk::ColorComponentFlags::RGBA)
+            .blend_enable(true)
+            .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+            .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+            .color_blend_op(vk::BlendOp::ADD)
+            .src_alpha_blend_factor(vk::BlendFactor::ONE)
+            .dst_alpha_blend_factor(vk::BlendFactor::ZERO)
+            .alpha_blend_op(vk::BlendOp::ADD);
+
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+            .logic_op_enable(false)
+            .attachments(std::slice::from_ref(&color_blend_attachment));
+
+        // Push constants
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(std::mem::size_of::<WireframePushConstants>() as u32);
+
+        let set_layouts = [descriptor_set_layout];
+        let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(&set_layouts)
+            .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+
+        let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+        let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+            .stages(&shader_stages)
+            .vertex_input_state(&vertex_input_info)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterizer)
+            .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blending)
+            .layout(pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipelines = device
+            .create_graphics_pipelines(
+                cache.handle(),
+                std::slice::from_ref(&pipeline_info),
+                None,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to create wireframe overlay pipeline: {:?}", e.1))?;
+
+        Ok((pipeline_layout, pipelines[0]))
+    }
+}
@@ -0,0 +1,149 @@
+//! Pipeline and shader module caching
+//!
+//! The renderer owns one `PipelineCache` and every pipeline is created with its `handle()`,
+//! so rebuilding pipelines on resize (their viewport is baked in) and on later launches
+//! reuses the driver's compiled code instead of compiling it again. The cache blob is
+//! loaded from `CACHE_PATH` at startup and written back by `destroy`.
+//!
+//! Shader modules are cached as well, keyed by their SPIR-V, so a resize doesn't rebuild
+//! them. Pipelines don't reference their modules after creation, so the cache owns them
+//! and `clear_shader_modules` can drop them at any time (e.g. before a shader hot-reload).
+//!
+//! Descriptor sets need no cache of their own: the renderer allocates its main sets once and
+//! only rewrites them on resize. The small per-image pools of the post-processing passes
+//! are still rebuilt with their targets.
+
+use anyhow::Result;
+use ash::vk;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Where the driver's pipeline cache blob is kept between runs
+pub const CACHE_PATH: &str = "config/pipeline_cache.bin";
+
+/// Size of the `VkPipelineCacheHeaderVersionOne` header that starts every cache blob
+const HEADER_SIZE: usize = 32;
+
+pub struct PipelineCache {
+    handle: vk::PipelineCache,
+    /// Behind a `RefCell` so passes can create modules through the shared `RenderContext`
+    shader_modules: RefCell<HashMap<u64, vk::ShaderModule>>,
+}
+
+impl PipelineCache {
+    /// Create the pipeline cache, seeded from `CACHE_PATH` when that blob was written by
+    /// this same driver and GPU (anything else starts an empty cache)
+    pub unsafe fn new(device: &ash::Device, properties: &vk::PhysicalDeviceProperties) -> Result<Self> {
+        let initial_data = match std::fs::read(CACHE_PATH) {
+            Ok(data) if header_matches(&data, properties) => data,
+            Ok(_) => {
+                crate::console::info(format!("Ignoring {} (written by another driver or GPU)", CACHE_PATH));
+                Vec::new()
+            }
+            Err(_) => Vec::new(),
+        };
+
+        let create_info = vk::PipelineCacheCreateInfo::default().initial_data(&initial_data);
+        Ok(Self {
+            handle: device.create_pipeline_cache(&create_info, None)?,
+            shader_modules: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// The pipeline cache to pass to `create_graphics_pipelines`/`create_compute_pipelines`
+    pub fn handle(&self) -> vk::PipelineCache {
+        self.handle
+    }
+
+    /// A shader module for `code`, created once and reused until `clear_shader_modules`
+    /// The cache owns the module: callers must not destroy it.
+    pub unsafe fn shader_module(&self, device: &ash::Device, code: &[u8]) -> Result<vk::ShaderModule> {
+        let key = spirv_key(code);
+        if let Some(module) = self.shader_modules.borrow().get(&key) {
+            return Ok(*module);
+        }
+
+        let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
+        let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
+        let module = device.create_shader_module(&create_info, None)?;
+        self.shader_modules.borrow_mut().insert(key, module);
+        Ok(module)
+    }
+
+    /// Destroy every cached shader module (pipelines already created are unaffected)
+    pub unsafe fn clear_shader_modules(&self, device: &ash::Device) {
+        for (_, module) in self.shader_modules.borrow_mut().drain() {
+            device.destroy_shader_module(module, None);
+        }
+    }
+
+    /// Write the cache blob to `CACHE_PATH` and destroy the cache and shader modules
+    /// Call once, before the device is destroyed.
+    pub unsafe fn destroy(&mut self, device: &ash::Device) {
+        self.clear_shader_modules(device);
+        match device.get_pipeline_cache_data(self.handle) {
+            Ok(data) => {
+                let written = std::path::Path::new(CACHE_PATH)
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|_| std::fs::write(CACHE_PATH, &data));
+                if let Err(e) = written {
+                    crate::console::warn(format!("Failed to save {}: {}", CACHE_PATH, e));
+                }
+            }
+            Err(e) => crate::console::warn(format!("Failed to read the pipeline cache: {}", e)),
+        }
+        device.destroy_pipeline_cache(self.handle, None);
+        self.handle = vk::PipelineCache::null();
+    }
+}
+
+fn spirv_key(code: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    code.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether a cache blob was written by the same driver and GPU: drivers are meant to
+/// reject foreign data themselves, but not all of them do so safely
+fn header_matches(data: &[u8], properties: &vk::PhysicalDeviceProperties) -> bool {
+    if data.len() < HEADER_SIZE {
+        return false;
+    }
+    let word = |offset: usize| u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]);
+    word(0) as usize >= HEADER_SIZE
+        && word(4) == vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+        && word(8) == properties.vendor_id
+        && word(12) == properties.device_id
+        && data[16..HEADER_SIZE] == properties.pipeline_cache_uuid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_header_must_match_the_device() {
+        let properties = vk::PhysicalDeviceProperties {
+            vendor_id: 0x10de,
+            device_id: 0x2484,
+            pipeline_cache_uuid: [7; vk::UUID_SIZE],
+            ..Default::default()
+        };
+        let mut blob = Vec::new();
+        for word in [HEADER_SIZE as u32, 1, 0x10de, 0x2484] {
+            blob.extend_from_slice(&word.to_le_bytes());
+        }
+        blob.extend_from_slice(&[7; vk::UUID_SIZE]);
+        blob.extend_from_slice(b"driver data");
+        assert!(header_matches(&blob, &properties));
+
+        assert!(!header_matches(&blob[..HEADER_SIZE - 1], &properties));
+        let other_gpu = vk::PhysicalDeviceProperties { device_id: 0x2504, ..properties };
+        assert!(!header_matches(&blob, &other_gpu));
+        let other_driver = vk::PhysicalDeviceProperties { pipeline_cache_uuid: [8; vk::UUID_SIZE], ..properties };
+        assert!(!header_matches(&blob, &other_driver));
+    }
+}
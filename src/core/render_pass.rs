@@ -6,7 +6,10 @@
 use ash::vk;
 use anyhow::Result;
 use std::collections::HashMap;
-use crate::mesh::Mesh;
+use crate::mesh::{Mesh, PrimitiveShape};
+
+/// Depth/stencil format of the main scene depth buffer (stencil is used for selection outlines)
+pub const DEPTH_FORMAT: vk::Format = vk::Format::D32_SFLOAT_S8_UINT;
 
 /// Context provided to each render pass during initialization and rendering
 pub struct RenderContext<'a> {
@@ -16,14 +19,52 @@ pub struct RenderContext<'a> {
     pub command_pool: vk::CommandPool,
     pub graphics_queue: vk::Queue,
     pub extent: vk::Extent2D,
+    // Shared pipeline cache and shader modules (see `core::pipeline_cache`)
+    pub pipeline_cache: &'a crate::core::PipelineCache,
+    // MSAA sample count of the main render pass (pipelines drawn in it must match)
+    pub msaa_samples: vk::SampleCountFlags,
     // Optional resources that some passes need
     pub depth_image_view: Option<vk::ImageView>,
     pub depth_sampler: Option<vk::Sampler>,
     // Shared mesh rendering resources (for MeshPass)
     pub mesh_pipeline: Option<vk::Pipeline>,
+    pub mesh_instanced_pipeline: Option<vk::Pipeline>,
+    pub mesh_transparent_pipeline: Option<vk::Pipeline>,
+    pub mesh_overdraw_pipeline: Option<vk::Pipeline>,
+    pub mesh_prepass_pipelines: Option<crate::core::passes::mesh::DepthPrepassPipelines>,
     pub mesh_pipeline_layout: Option<vk::PipelineLayout>,
     pub mesh_descriptor_sets: Option<&'a [vk::DescriptorSet]>,
     pub custom_meshes: Option<&'a HashMap<String, (Mesh, vk::Buffer, vk::DeviceMemory, vk::Buffer, vk::DeviceMemory)>>,
+    // Built-in primitive meshes (created once by the renderer, same layout as custom_meshes)
+    pub primitive_meshes: Option<&'a HashMap<PrimitiveShape, (Mesh, vk::Buffer, vk::DeviceMemory, vk::Buffer, vk::DeviceMemory)>>,
+    // Material texture cache (set = 1 of the mesh pipeline)
+    pub textures: Option<&'a crate::core::TextureCache>,
+}
+
+/// Per-frame draw statistics reported by passes that draw meshes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DrawStats {
+    pub drawn: u32,
+    pub culled: u32,
+    pub draw_calls: u32,
+    pub triangles: u64,
+}
+
+impl DrawStats {
+    /// Record one indexed triangle-list draw
+    pub fn record_draw(&mut self, index_count: u32, instance_count: u32) {
+        self.draw_calls += 1;
+        self.triangles += (index_count / 3) as u64 * instance_count as u64;
+    }
+}
+
+impl std::ops::AddAssign for DrawStats {
+    fn add_assign(&mut self, other: Self) {
+        self.drawn += other.drawn;
+        self.culled += other.culled;
+        self.draw_calls += other.draw_calls;
+        self.triangles += other.triangles;
+    }
 }
 
 /// Render pass trait - each rendering system implements this
@@ -56,7 +97,20 @@ pub trait RenderPass {
         extent: vk::Extent2D,
     ) -> Result<()>;
 
-    /// Cleanup resources
+    /// Rebuild pipelines from the current shaders (shader hot-reload)
+    /// Defaults to `recreate_swapchain`, which already rebuilds the pipeline for most passes
+    fn recreate_pipeline(
+        &mut self,
+        ctx: &RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        self.recreate_swapchain(ctx, render_pass, extent)
+    }
+
+    /// Destroy every Vulkan object the pass created and reset its handles to null, so a
+    /// repeated call is harmless. Pipelines and buffers borrowed from the `RenderContext`
+    /// belong to the renderer and are left alone.
     fn cleanup(&mut self, device: &ash::Device);
 
     /// Get pass name for debugging
@@ -67,25 +121,117 @@ pub trait RenderPass {
         let _ = game;
         true
     }
+
+    /// Draw statistics from the last recorded frame (None if the pass doesn't track them)
+    fn draw_stats(&self) -> Option<DrawStats> {
+        None
+    }
 }
 
 /// Registry of render passes - executed in order
 pub struct RenderPassRegistry {
     passes: Vec<Box<dyn RenderPass>>,
+    /// Runtime enable flags keyed by lowercase pass name
+    /// Disabled passes keep their GPU resources so re-enabling is instant
+    enabled: HashMap<String, bool>,
+    /// Time each pass's render call when set (only while the stats overlay is open)
+    profiling: bool,
+    /// CPU time spent recording each pass last frame (pass name, milliseconds)
+    timings: Vec<(String, f32)>,
 }
 
 impl RenderPassRegistry {
     pub fn new() -> Self {
         Self {
             passes: Vec::new(),
+            enabled: HashMap::new(),
+            profiling: false,
+            timings: Vec::new(),
         }
     }
 
-    /// Register a new render pass
+    /// Register a new render pass (enabled by default)
     pub fn register(&mut self, pass: Box<dyn RenderPass>) {
+        self.enabled.insert(pass.name().to_lowercase(), true);
         self.passes.push(pass);
     }
 
+    /// Enable or disable a pass by name (case-insensitive, e.g. "nebula")
+    /// Returns false if no pass with that name is registered
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.enabled.get_mut(&name.to_lowercase()) {
+            Some(flag) => {
+                *flag = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if a pass is enabled (unknown passes count as enabled, like the passes the
+    /// renderer records outside the registry)
+    pub fn is_enabled(&self, name: &str) -> bool {
+        Self::flag(&self.enabled, name)
+    }
+
+    /// Get the names of all registered passes in execution order
+    pub fn pass_names(&self) -> Vec<String> {
+        self.passes.iter().map(|pass| pass.name().to_string()).collect()
+    }
+
+    /// Sync enable flags with a UI-facing list of (pass name, enabled)
+    /// Entries in the list are applied to the registry; passes missing from the
+    /// list are appended with their current state so the UI can display them
+    pub fn sync_enabled(&mut self, states: &mut Vec<(String, bool)>) {
+        for name in self.pass_names() {
+            match states.iter().find(|(state_name, _)| *state_name == name) {
+                Some((_, enabled)) => {
+                    let enabled = *enabled;
+                    self.set_enabled(&name, enabled);
+                }
+                None => {
+                    let enabled = self.is_enabled(&name);
+                    states.push((name, enabled));
+                }
+            }
+        }
+    }
+
+    /// Draw statistics of a pass by name (case-insensitive)
+    pub fn draw_stats(&self, name: &str) -> Option<DrawStats> {
+        self.passes
+            .iter()
+            .find(|pass| pass.name().eq_ignore_ascii_case(name))
+            .and_then(|pass| pass.draw_stats())
+    }
+
+    /// Sum of the draw statistics of all enabled passes
+    pub fn total_draw_stats(&self) -> DrawStats {
+        let mut total = DrawStats::default();
+        for pass in &self.passes {
+            if !Self::flag(&self.enabled, pass.name()) {
+                continue;
+            }
+            if let Some(stats) = pass.draw_stats() {
+                total += stats;
+            }
+        }
+        total
+    }
+
+    /// Enable or disable per-pass CPU timing of render calls
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+        if !profiling {
+            self.timings.clear();
+        }
+    }
+
+    /// CPU recording time of each pass rendered last frame (pass name, milliseconds)
+    pub fn pass_timings(&self) -> &[(String, f32)] {
+        &self.timings
+    }
+
     /// Initialize all passes
     pub fn initialize_all(
         &mut self,
@@ -108,6 +254,9 @@ impl RenderPassRegistry {
         game: &crate::game::Game,
     ) -> Result<()> {
         for pass in &mut self.passes {
+            if !Self::flag(&self.enabled, pass.name()) {
+                continue;
+            }
             if pass.should_render(game) {
                 pass.update(ctx, frame_index, game)?;
             }
@@ -123,15 +272,31 @@ impl RenderPassRegistry {
         frame_index: usize,
         game: &crate::game::Game,
     ) -> Result<()> {
+        self.timings.clear();
         for pass in &mut self.passes {
+            if !Self::flag(&self.enabled, pass.name()) {
+                continue;
+            }
             if pass.should_render(game) {
-                pass.render(ctx, command_buffer, frame_index, game)?;
+                if self.profiling {
+                    let start = std::time::Instant::now();
+                    pass.render(ctx, command_buffer, frame_index, game)?;
+                    let elapsed_ms = start.elapsed().as_secs_f32() * 1000.0;
+                    self.timings.push((pass.name().to_string(), elapsed_ms));
+                } else {
+                    pass.render(ctx, command_buffer, frame_index, game)?;
+                }
             }
         }
         Ok(())
     }
 
-    /// Recreate swapchain for all passes
+    /// Look up an enable flag without borrowing the whole registry
+    fn flag(enabled: &HashMap<String, bool>, name: &str) -> bool {
+        enabled.get(&name.to_lowercase()).copied().unwrap_or(true)
+    }
+
+    /// Recreate swapchain for all passes (including disabled ones, so they stay valid)
     pub fn recreate_swapchain_all(
         &mut self,
         ctx: &RenderContext,
@@ -144,7 +309,27 @@ impl RenderPassRegistry {
         Ok(())
     }
 
-    /// Cleanup all passes
+    /// Rebuild the pipelines of all passes after a shader reload
+    /// A pass that fails is skipped so the others still pick up their new shaders
+    /// Returns (pass name, error) for each failure
+    pub fn recreate_pipelines_all(
+        &mut self,
+        ctx: &RenderContext,
+        render_pass: vk::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Vec<(String, String)> {
+        let mut failures = Vec::new();
+        for pass in &mut self.passes {
+            if let Err(e) = pass.recreate_pipeline(ctx, render_pass, extent) {
+                failures.push((pass.name().to_string(), e.to_string()));
+            }
+        }
+        failures
+    }
+
+    /// Destroy the Vulkan objects of every pass. Called from the renderer's `Drop` after
+    /// the device is idle and before it is destroyed; each pass resets its handles, so a
+    /// second call does nothing.
     pub fn cleanup_all(&mut self, device: &ash::Device) {
         for pass in &mut self.passes {
             println!("Cleaning up render pass: {}", pass.name());
@@ -152,3 +337,76 @@ impl RenderPassRegistry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pass that records nothing, for exercising the registry's bookkeeping
+    struct StubPass(&'static str);
+
+    impl RenderPass for StubPass {
+        fn initialize(&mut self, _ctx: &RenderContext, _render_pass: vk::RenderPass, _extent: vk::Extent2D) -> Result<()> {
+            Ok(())
+        }
+
+        fn update(&mut self, _ctx: &RenderContext, _frame_index: usize, _game: &crate::game::Game) -> Result<()> {
+            Ok(())
+        }
+
+        fn render(
+            &mut self,
+            _ctx: &RenderContext,
+            _command_buffer: vk::CommandBuffer,
+            _frame_index: usize,
+            _game: &crate::game::Game,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn recreate_swapchain(&mut self, _ctx: &RenderContext, _render_pass: vk::RenderPass, _extent: vk::Extent2D) -> Result<()> {
+            Ok(())
+        }
+
+        fn cleanup(&mut self, _device: &ash::Device) {}
+
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    fn registry() -> RenderPassRegistry {
+        let mut registry = RenderPassRegistry::new();
+        registry.register(Box::new(StubPass("Skybox")));
+        registry.register(Box::new(StubPass("Nebula")));
+        registry
+    }
+
+    #[test]
+    fn test_set_enabled_by_name() {
+        let mut registry = registry();
+        assert!(registry.is_enabled("Skybox"));
+
+        assert!(registry.set_enabled("NEBULA", false));
+        assert!(!registry.is_enabled("nebula"));
+        assert!(registry.is_enabled("skybox"));
+
+        // Unknown passes can't be toggled and count as enabled
+        assert!(!registry.set_enabled("Bloom", false));
+        assert!(registry.is_enabled("Bloom"));
+    }
+
+    #[test]
+    fn test_sync_enabled_applies_and_lists_passes() {
+        let mut registry = registry();
+        let mut states = vec![("Nebula".to_string(), false), ("Removed".to_string(), true)];
+        registry.sync_enabled(&mut states);
+
+        assert!(!registry.is_enabled("Nebula"));
+        assert!(registry.is_enabled("Skybox"));
+        assert_eq!(
+            states,
+            vec![("Nebula".to_string(), false), ("Removed".to_string(), true), ("Skybox".to_string(), true)],
+        );
+    }
+}
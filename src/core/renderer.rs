@@ -2,18 +2,73 @@ use ash::{vk, Entry};
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use std::ffi::{CStr, CString};
 use winit::window::Window;
-use glam::{Mat4, Quat, Vec2, Vec3};
+use glam::{DVec3, Mat4, Vec2, Vec3, Vec4};
 use imgui::Context;
 
-use crate::mesh::{Mesh, Vertex};
+use crate::mesh::{Mesh, MeshStats, Vertex};
 use crate::material::MaterialProperties;
-use crate::core::lighting::{DirectionalLight, PointLight};
+use crate::core::lighting::{MAX_DIRECTIONAL_LIGHTS, MAX_POINT_LIGHTS};
 use crate::imgui_renderer::ImGuiRenderer;
 use crate::ui::UiManager;
 use crate::gizmo::GizmoMesh;
+use crate::core::RenderPass;
+use crate::core::passes::tonemap::HDR_FORMAT;
+use crate::core::DEPTH_FORMAT;
 
 const MAX_FRAMES_IN_FLIGHT: usize = 2;
 
+/// Output format of headless renderers, the format a window's swapchain prefers
+const HEADLESS_FORMAT: vk::Format = vk::Format::B8G8R8A8_SRGB;
+
+/// The window a renderer presents to: its surface, present queue and swapchain.
+/// Headless renderers have none and draw into offscreen images in place of swapchain images
+struct WindowSurface {
+    window: Window,
+    surface: vk::SurfaceKHR,
+    surface_loader: ash::khr::surface::Instance,
+    present_queue: vk::Queue,
+    swapchain: vk::SwapchainKHR,
+}
+
+/// Multisampled color + depth/stencil targets of the main pass (null when MSAA is off)
+struct MsaaTargets {
+    color_image: vk::Image,
+    color_memory: vk::DeviceMemory,
+    color_view: vk::ImageView,
+    depth_image: vk::Image,
+    depth_memory: vk::DeviceMemory,
+    depth_view: vk::ImageView,
+}
+
+impl MsaaTargets {
+    fn null() -> Self {
+        Self {
+            color_image: vk::Image::null(),
+            color_memory: vk::DeviceMemory::null(),
+            color_view: vk::ImageView::null(),
+            depth_image: vk::Image::null(),
+            depth_memory: vk::DeviceMemory::null(),
+            depth_view: vk::ImageView::null(),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.color_view != vk::ImageView::null()
+    }
+
+    unsafe fn destroy(&mut self, device: &ash::Device) {
+        if self.is_enabled() {
+            device.destroy_image_view(self.color_view, None);
+            device.destroy_image(self.color_image, None);
+            device.free_memory(self.color_memory, None);
+            device.destroy_image_view(self.depth_view, None);
+            device.destroy_image(self.depth_image, None);
+            device.free_memory(self.depth_memory, None);
+        }
+        *self = Self::null();
+    }
+}
+
 /// Push constants for mesh rendering (model matrix + material properties)
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -24,6 +79,11 @@ struct MeshPushConstants {
     roughness: f32,              // 4 bytes
     ambient_strength: f32,       // 4 bytes
     gi_strength: f32,            // 4 bytes
+    texture_flags: u32,          // 4 bytes
+    emissive: glam::Vec3,        // 12 bytes
+    emissive_strength: f32,      // 4 bytes
+    opacity: f32,                // 4 bytes
+    layer: [u32; 3],             // 12 bytes (packed weathering layer)
 }
 
 unsafe impl bytemuck::Pod for MeshPushConstants {}
@@ -58,7 +118,14 @@ struct StarUniformBufferObject {
     speed_hi: f32,
     speed_low: f32,
     zoom: f32,
-    _padding: f32,
+    log_depth: f32,
+    _fog_align: [f32; 2],
+    fog_color: Vec3,
+    fog_density: f32,
+    fog_mode: u32,
+    fog_start: f32,
+    fog_end: f32,
+    _fog_pad: f32,
 }
 
 unsafe impl bytemuck::Pod for StarUniformBufferObject {}
@@ -68,23 +135,38 @@ pub struct VulkanRenderer {
     _entry: Entry,
     instance: ash::Instance,
     debug_utils: Option<(ash::ext::debug_utils::Instance, vk::DebugUtilsMessengerEXT)>,
-    surface: vk::SurfaceKHR,
-    surface_loader: ash::khr::surface::Instance,
+    // None for headless renderers
+    window_surface: Option<WindowSurface>,
     physical_device: vk::PhysicalDevice,
     device: ash::Device,
     graphics_queue: vk::Queue,
-    present_queue: vk::Queue,
-    swapchain: vk::SwapchainKHR,
+    // Shared by every pipeline; destroyed (and saved to disk) in Drop
+    pipeline_cache: crate::core::PipelineCache,
     swapchain_loader: ash::khr::swapchain::Device,
+    // Swapchain images, or the offscreen output images of a headless renderer
     swapchain_images: Vec<vk::Image>,
+    // Memory of the headless output images (empty when presenting to a window)
+    offscreen_images_memory: Vec<vk::DeviceMemory>,
     swapchain_image_views: Vec<vk::ImageView>,
     swapchain_format: vk::Format,
     swapchain_extent: vk::Extent2D,
+    // HDR scene color targets the main render pass draws into (one per swapchain image)
+    hdr_images: Vec<vk::Image>,
+    hdr_images_memory: Vec<vk::DeviceMemory>,
+    hdr_image_views: Vec<vk::ImageView>,
     render_pass: vk::RenderPass,
+    // Loads the swapchain image to draw ImGui after tonemapping
+    overlay_render_pass: vk::RenderPass,
+    // Color-only swapchain framebuffers (tonemap + ImGui overlay)
+    swapchain_framebuffers: Vec<vk::Framebuffer>,
     descriptor_set_layout: vk::DescriptorSetLayout,
     pipeline_layout: vk::PipelineLayout,
     graphics_pipeline: vk::Pipeline,
     wireframe_pipeline: vk::Pipeline,  // Wireframe rendering pipeline
+    instanced_pipeline: vk::Pipeline,  // Instanced mesh pipeline (batched identical objects)
+    transparent_pipeline: vk::Pipeline,  // Alpha-blended mesh pipeline (transparent materials)
+    overdraw_pipeline: vk::Pipeline,  // Additive, depth-test-free mesh pipeline (overdraw debug view)
+    depth_prepass_pipelines: crate::core::passes::mesh::DepthPrepassPipelines,  // Depth-only + EQUAL-tested mesh pipelines
     // Gizmo - store all three mesh types
     gizmo_translate_mesh: Mesh,
     gizmo_rotate_mesh: Mesh,
@@ -101,6 +183,16 @@ pub struct VulkanRenderer {
     gizmo_uniform_buffers: Vec<vk::Buffer>,
     gizmo_uniform_buffers_memory: Vec<vk::DeviceMemory>,
     gizmo_descriptor_sets: Vec<vk::DescriptorSet>,
+    // View cube (drawn with the gizmo pipeline, own uniform buffers for its corner projection)
+    view_cube_mesh: Mesh,
+    view_cube_vertex_buffer: vk::Buffer,
+    view_cube_vertex_buffer_memory: vk::DeviceMemory,
+    view_cube_index_buffer: vk::Buffer,
+    view_cube_index_buffer_memory: vk::DeviceMemory,
+    view_cube_uniform_buffers: Vec<vk::Buffer>,
+    view_cube_uniform_buffers_memory: Vec<vk::DeviceMemory>,
+    view_cube_descriptor_pool: vk::DescriptorPool,
+    view_cube_descriptor_sets: Vec<vk::DescriptorSet>,
     framebuffers: Vec<vk::Framebuffer>,
     command_pool: vk::CommandPool,
     command_buffers: Vec<vk::CommandBuffer>,
@@ -113,7 +205,8 @@ pub struct VulkanRenderer {
     fps_frame_count: u64,
     last_time: std::time::Instant,
     last_frame_time: std::time::Instant,
-    window: Window,
+    // Rolling frame times and draw stats for the F3 overlay
+    frame_stats: crate::core::FrameStats,
     // Mesh registry for cube objects
     cube_mesh: Mesh,
     cube_vertex_buffer: vk::Buffer,
@@ -122,6 +215,8 @@ pub struct VulkanRenderer {
     cube_index_buffer_memory: vk::DeviceMemory,
     // Custom mesh storage (path -> (mesh, vertex_buffer, index_buffer, memories))
     custom_meshes: std::collections::HashMap<String, (Mesh, vk::Buffer, vk::DeviceMemory, vk::Buffer, vk::DeviceMemory)>,
+    // Built-in primitive meshes (sphere, plane, cylinder, torus), created once at init
+    primitive_meshes: std::collections::HashMap<crate::mesh::PrimitiveShape, (Mesh, vk::Buffer, vk::DeviceMemory, vk::Buffer, vk::DeviceMemory)>,
     // Directional light visualization
     dir_light_mesh: Mesh,
     dir_light_vertex_buffer: vk::Buffer,
@@ -136,12 +231,26 @@ pub struct VulkanRenderer {
     index_buffer_memory: vk::DeviceMemory,
     uniform_buffers: Vec<vk::Buffer>,
     uniform_buffers_memory: Vec<vk::DeviceMemory>,
+    /// Per-frame point light storage buffers (binding 3 of the main descriptor set)
+    point_light_buffers: Vec<vk::Buffer>,
+    point_light_buffers_memory: Vec<vk::DeviceMemory>,
     descriptor_pool: vk::DescriptorPool,
     descriptor_sets: Vec<vk::DescriptorSet>,
     depth_image: vk::Image,
     depth_image_memory: vk::DeviceMemory,
     depth_image_view: vk::ImageView,
     depth_sampler: vk::Sampler,
+    // MSAA: active sample count, the level requested by the game config and the GPU maximum
+    msaa_samples: vk::SampleCountFlags,
+    msaa_requested: u32,
+    max_msaa_samples: vk::SampleCountFlags,
+    msaa_targets: MsaaTargets,
+    // Anisotropic filtering: level requested by the game config and the GPU maximum (1.0 = unsupported)
+    anisotropy_requested: u32,
+    max_anisotropy: f32,
+    // Present mode requested by the game config and the one the swapchain actually uses
+    present_mode_requested: crate::game::PresentMode,
+    present_mode: vk::PresentModeKHR,
     // SSAO resources
     ssao_image: vk::Image,
     ssao_image_memory: vk::DeviceMemory,
@@ -150,6 +259,8 @@ pub struct VulkanRenderer {
     ssao_blur_image_memory: vk::DeviceMemory,
     ssao_blur_image_view: vk::ImageView,
     ssao_sampler: vk::Sampler,
+    // SSAO targets keep the size they were created at (their pipelines bake this viewport)
+    ssao_extent: vk::Extent2D,
     ssao_render_pass: vk::RenderPass,
     ssao_framebuffer: vk::Framebuffer,
     ssao_descriptor_set_layout: vk::DescriptorSetLayout,
@@ -174,14 +285,51 @@ pub struct VulkanRenderer {
     ssao_blur_horizontal_framebuffer: vk::Framebuffer,
     ssao_blur_horizontal_descriptor_pool: vk::DescriptorPool,
     ssao_blur_horizontal_descriptor_sets: Vec<vk::DescriptorSet>,
-    directional_light: DirectionalLight,
-    point_lights: Vec<PointLight>,
     // ImGui
     imgui_context: Context,
     imgui_renderer: ImGuiRenderer,
     imgui_platform: imgui_winit_support::WinitPlatform,
     // Render pass plugin system
     render_passes: crate::core::RenderPassRegistry,
+    // Directional light shadow map (recorded before the main render pass)
+    shadow_pass: crate::core::passes::ShadowPass,
+    // Image-based lighting from the skybox (bindings 4/5 of the mesh descriptor set)
+    environment: crate::core::EnvironmentLighting,
+    // Last frame's scene color/depth for screen-space reflections
+    reflection_history: crate::core::ReflectionHistory,
+    // Camera origin the history was rendered with (a rebase makes it unusable)
+    history_origin: DVec3,
+    // GPU particles (compute dispatch before the main render pass, drawn at its end)
+    particle_pass: crate::core::passes::ParticlePass,
+    // Bloom post-process (recorded after the main render pass)
+    bloom_pass: crate::core::passes::BloomPass,
+    // HDR -> swapchain tonemapping (recorded last, before ImGui)
+    tonemap_pass: crate::core::passes::TonemapPass,
+    // FXAA over the tonemapped image (tonemap writes to its intermediate target when enabled)
+    fxaa_pass: crate::core::passes::FxaaPass,
+    // Material editor preview sphere (offscreen, shown as an ImGui image)
+    material_preview_pass: crate::core::passes::MaterialPreviewPass,
+    material_preview_texture: imgui::TextureId,
+    // Tactical minimap from a top-down camera (offscreen, shown as an ImGui image)
+    minimap_pass: crate::core::passes::MinimapPass,
+    minimap_texture: imgui::TextureId,
+    // Scene hierarchy mesh thumbnails (offscreen atlas, shown as ImGui images)
+    thumbnail_pass: crate::core::passes::ThumbnailPass,
+    // Material textures, keyed by path (parallel to custom_meshes)
+    texture_cache: crate::core::TextureCache,
+    // Screenshot requested for the next frame (output path)
+    screenshot_request: Option<String>,
+    // Readback buffer for a screenshot recorded into the current frame
+    screenshot_capture: Option<ScreenshotCapture>,
+}
+
+/// Host-visible readback of one swapchain image
+struct ScreenshotCapture {
+    path: String,
+    buffer: vk::Buffer,
+    memory: vk::DeviceMemory,
+    extent: vk::Extent2D,
+    format: vk::Format,
 }
 
 #[repr(C)]
@@ -198,16 +346,44 @@ struct UniformBufferObject {
     dir_light_shadow_color: Vec3,
     star_density: f32,
     star_brightness: f32,
-    _pad0: f32,
-    _pad1: f32,
-    _pad2: f32,
+    ssao_intensity: f32,
+    ssao_debug: u32,
+    /// `Camera::log_depth_coefficient` (0 = standard depth)
+    log_depth: f32,
     nebula_primary_color: Vec3,
     nebula_intensity: f32,
     nebula_secondary_color: Vec3,
     background_brightness: f32,
     point_light_count: u32,
     ssao_enabled: u32,
-    _padding3: [u32; 2],
+    shadow_enabled: u32,
+    shadow_bias: f32,
+    light_space_matrix: Mat4,
+    fog_color: Vec3,
+    fog_density: f32,
+    fog_mode: u32,
+    fog_start: f32,
+    fog_end: f32,
+    debug_view: u32,
+    dir_light_count: u32,
+    _dir_light_pad: [u32; 3],
+    dir_lights: [DirectionalLightData; MAX_DIRECTIONAL_LIGHTS],
+    /// Camera of the SSR history (last frame's color and depth)
+    history_view: Mat4,
+    history_proj: Mat4,
+    /// 1 when SSR is on and the history holds a frame
+    ssr_enabled: u32,
+    ssr_max_steps: u32,
+    ssr_thickness: f32,
+    _ssr_pad: f32,
+}
+
+/// Push constants for `wireframe_pipeline` (a prefix of the mesh pipeline's push range)
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct WireframeColorPushConstants {
+    model: Mat4,
+    color: Vec4,
 }
 
 #[repr(C)]
@@ -218,11 +394,22 @@ struct GizmoUniformBufferObject {
     proj: Mat4,
 }
 
+/// One entry of the UBO's directional light array (std140, 32 bytes)
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct DirectionalLightData {
+    direction: Vec3,
+    intensity: f32,
+    color: Vec3,
+    _pad: f32,
+}
+
+/// One entry of the point light storage buffer (std430, 32 bytes)
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct PointLightData {
     position: Vec3,
-    _padding: f32,
+    radius: f32,
     color: Vec3,
     intensity: f32,
 }
@@ -235,13 +422,46 @@ struct SSAOUniformBufferObject {
     ssao_bias: f32,         // 4 bytes, offset 68
     ssao_power: f32,        // 4 bytes, offset 72
     ssao_kernel_size: u32,  // 4 bytes, offset 76
-    // Total: 80 bytes (needs padding to 16-byte alignment)
+    log_depth: f32,         // 4 bytes, offset 80 (0 = standard depth)
+    _padding: [f32; 3],     // Total: 96 bytes
 }
 
-const MAX_POINT_LIGHTS: usize = 4;
-
 impl VulkanRenderer {
     pub fn new(window: Window) -> anyhow::Result<Self> {
+        Self::create(Some(window), vk::Extent2D::default())
+    }
+
+    /// Create a renderer without a window or swapchain. Frames render into a `width`x`height`
+    /// offscreen image and are read back with `capture_frame`. Any GPU with the required
+    /// features is accepted (discrete preferred), including software rasterizers
+    pub fn new_headless(width: u32, height: u32) -> anyhow::Result<Self> {
+        if width == 0 || height == 0 {
+            anyhow::bail!("Invalid headless size {}x{}", width, height);
+        }
+        Self::create(None, vk::Extent2D { width, height })
+    }
+
+    /// Whether `new_headless` can find a device here: a Vulkan loader and a capable GPU.
+    /// Lets tools and tests skip cleanly on machines without Vulkan
+    pub fn headless_device_available() -> bool {
+        unsafe {
+            let Ok(entry) = Entry::load() else {
+                return false;
+            };
+            let app_info = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_2);
+            let create_info = vk::InstanceCreateInfo::default().application_info(&app_info);
+            let Ok(instance) = entry.create_instance(&create_info, None) else {
+                return false;
+            };
+            let available = Self::pick_physical_device(&instance, None).is_ok();
+            instance.destroy_instance(None);
+            available
+        }
+    }
+
+    /// Shared by `new` and `new_headless`: everything that needs the window (instance surface
+    /// extensions, surface, present queue, swapchain, ImGui platform) is skipped without one
+    fn create(window: Option<Window>, headless_extent: vk::Extent2D) -> anyhow::Result<Self> {
         unsafe {
             let entry = Entry::load()?;
             
@@ -255,20 +475,24 @@ impl VulkanRenderer {
             .engine_version(vk::make_api_version(0, 1, 0, 0))
             .api_version(vk::API_VERSION_1_2);
             
-            let extension_names = ash_window::enumerate_required_extensions(
-                window.display_handle()?.as_raw()
-            )?;
-            
-            let mut extensions: Vec<*const i8> = extension_names.to_vec();
+            // Headless renderers need no surface extensions
+            let mut extensions: Vec<*const i8> = match &window {
+                Some(window) => ash_window::enumerate_required_extensions(window.display_handle()?.as_raw())?.to_vec(),
+                None => Vec::new(),
+            };
             
             #[cfg(debug_assertions)]
             extensions.push(ash::ext::debug_utils::NAME.as_ptr());
             
+            // Validation is enabled when the layer is installed (test machines often lack it)
             #[cfg(debug_assertions)]
             let layer_names = [CString::new("VK_LAYER_KHRONOS_validation")?];
             #[cfg(debug_assertions)]
+            let available_layers = entry.enumerate_instance_layer_properties()?;
+            #[cfg(debug_assertions)]
             let layer_names_raw: Vec<*const i8> = layer_names
             .iter()
+            .filter(|name| available_layers.iter().any(|layer| CStr::from_ptr(layer.layer_name.as_ptr()) == name.as_c_str()))
             .map(|name| name.as_ptr())
             .collect();
             
@@ -287,51 +511,109 @@ impl VulkanRenderer {
             let debug_utils = Self::setup_debug_messenger(&entry, &instance)?;
             
             // Create surface
-            let surface = ash_window::create_surface(
-                &entry,
-                &instance,
-                window.display_handle()?.as_raw(),
-                window.window_handle()?.as_raw(),
-                None,
-            )?;
-            let surface_loader = ash::khr::surface::Instance::new(&entry, &instance);
+            let surface = match &window {
+                Some(window) => Some((
+                    ash::khr::surface::Instance::new(&entry, &instance),
+                    ash_window::create_surface(
+                        &entry,
+                        &instance,
+                        window.display_handle()?.as_raw(),
+                        window.window_handle()?.as_raw(),
+                        None,
+                    )?,
+                )),
+                None => None,
+            };
+            let surface_ref = surface.as_ref().map(|(loader, surface)| (loader, *surface));
             
             // Pick physical device
-            let physical_device = Self::pick_physical_device(&instance, &surface_loader, surface)?;
+            let physical_device = Self::pick_physical_device(&instance, surface_ref)?;
             
             // Create logical device
             let (device, graphics_queue, present_queue) =
-            Self::create_logical_device(&instance, physical_device, &surface_loader, surface)?;
+            Self::create_logical_device(&instance, physical_device, surface_ref)?;
+            let pipeline_cache = crate::core::PipelineCache::new(&device, &instance.get_physical_device_properties(physical_device))?;
             
-            // Create swapchain
+            // Create swapchain (default present mode; the game's config is applied on the first frame)
             let swapchain_loader = ash::khr::swapchain::Device::new(&instance, &device);
-            let (swapchain, swapchain_images, swapchain_format, swapchain_extent) =
-            Self::create_swapchain(
-                &window,
+            let present_mode_requested = crate::game::GraphicsConfig::default().present_mode;
+            let (swapchain, swapchain_images, swapchain_image_views, offscreen_images_memory, swapchain_format, swapchain_extent, present_mode) =
+            match (&window, surface_ref) {
+                (Some(window), Some((surface_loader, surface))) => {
+                    let (swapchain, images, format, extent, present_mode) = Self::create_swapchain(
+                        window,
+                        &instance,
+                        physical_device,
+                        &device,
+                        surface_loader,
+                        surface,
+                        &swapchain_loader,
+                        Self::present_mode_khr(present_mode_requested),
+                    )?;
+                    let views = Self::create_image_views(&device, &images, format)?;
+                    (swapchain, images, views, Vec::new(), format, extent, present_mode)
+                }
+                _ => {
+                    // A single offscreen image stands in for the swapchain
+                    let (image, memory, view) = Self::create_msaa_image(
+                        &instance,
+                        physical_device,
+                        &device,
+                        headless_extent,
+                        vk::SampleCountFlags::TYPE_1,
+                        HEADLESS_FORMAT,
+                        vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+                        vk::ImageAspectFlags::COLOR,
+                    )?;
+                    (vk::SwapchainKHR::null(), vec![image], vec![view], vec![memory], HEADLESS_FORMAT, headless_extent, vk::PresentModeKHR::FIFO)
+                }
+            };
+            
+            // Create HDR scene color targets
+            let (hdr_images, hdr_images_memory, hdr_image_views) = Self::create_hdr_resources(
                 &instance,
                 physical_device,
                 &device,
-                &surface_loader,
-                surface,
-                &swapchain_loader,
+                swapchain_extent,
+                swapchain_images.len(),
             )?;
-            
-            // Create image views
-            let swapchain_image_views =
-            Self::create_image_views(&device, &swapchain_images, swapchain_format)?;
-            
-            // Create render pass
-            let render_pass = Self::create_render_pass(&device, swapchain_format)?;
+
+            // Start at the default MSAA level; the game's config is applied on the first frame
+            let max_msaa_samples = Self::max_msaa_samples(&instance, physical_device);
+            let msaa_requested = crate::game::AntiAliasingConfig::default().msaa_samples();
+            let msaa_samples = Self::clamp_msaa_samples(msaa_requested, max_msaa_samples);
+
+            // Textures start unfiltered; the configured anisotropy is applied on the first frame
+            let max_anisotropy = Self::max_sampler_anisotropy(&instance, physical_device);
+
+            // Create render pass (scene renders to HDR, the overlay pass draws ImGui on the swapchain)
+            let render_pass = Self::create_render_pass(&device, HDR_FORMAT, msaa_samples)?;
+            let overlay_render_pass = Self::create_overlay_render_pass(&device, swapchain_format)?;
             
             // Create descriptor set layout
             let descriptor_set_layout = Self::create_descriptor_set_layout(&device)?;
+
+            // Create material texture cache (its set layout is set 1 of the mesh pipeline)
+            let mut texture_cache = crate::core::TextureCache::new(&device)?;
             
             // Create graphics pipeline
             let (pipeline_layout, graphics_pipeline) =
-            Self::create_graphics_pipeline(&device, swapchain_extent, render_pass, descriptor_set_layout)?;
+            Self::create_graphics_pipeline(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, descriptor_set_layout, texture_cache.set_layout())?;
 
             // Create wireframe pipeline (reuses same pipeline layout)
-            let wireframe_pipeline = Self::create_wireframe_pipeline(&device, swapchain_extent, render_pass, pipeline_layout)?;
+            let wireframe_pipeline = Self::create_wireframe_pipeline(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, pipeline_layout)?;
+
+            // Create instanced mesh pipeline (reuses same pipeline layout)
+            let instanced_pipeline = Self::create_instanced_pipeline(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, pipeline_layout)?;
+
+            // Create transparent mesh pipeline (reuses same pipeline layout)
+            let transparent_pipeline = Self::create_transparent_pipeline(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, pipeline_layout)?;
+
+            // Create overdraw debug pipeline (reuses same pipeline layout)
+            let overdraw_pipeline = Self::create_overdraw_pipeline(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, pipeline_layout)?;
+
+            // Create depth prepass pipelines (reuse same pipeline layout)
+            let depth_prepass_pipelines = Self::create_depth_prepass_pipelines(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, pipeline_layout)?;
 
             // Create depth resources
             let (depth_image, depth_image_memory, depth_image_view) = Self::create_depth_resources(
@@ -341,6 +623,15 @@ impl VulkanRenderer {
                 swapchain_extent,
             )?;
 
+            // Create multisampled targets (resolved into the HDR and depth images)
+            let msaa_targets = Self::create_msaa_resources(
+                &instance,
+                physical_device,
+                &device,
+                swapchain_extent,
+                msaa_samples,
+            )?;
+
             // Create depth sampler for nebula
             let depth_sampler = Self::create_depth_sampler(&device)?;
 
@@ -397,12 +688,14 @@ impl VulkanRenderer {
 
             let (ssao_pipeline_layout, ssao_pipeline) = Self::create_ssao_pipeline(
                 &device,
+                &pipeline_cache,
                 swapchain_extent,
                 ssao_render_pass,
                 ssao_descriptor_set_layout,
             )?;
             let (ssao_blur_pipeline_layout, ssao_blur_pipeline) = Self::create_ssao_blur_pipeline(
                 &device,
+                &pipeline_cache,
                 swapchain_extent,
                 ssao_blur_render_pass,
                 ssao_blur_descriptor_set_layout,
@@ -457,14 +750,84 @@ impl VulkanRenderer {
             // Create framebuffers
             let framebuffers = Self::create_framebuffers(
                 &device,
-                &swapchain_image_views,
+                &hdr_image_views,
                 depth_image_view,
+                &msaa_targets,
                 render_pass,
                 swapchain_extent,
             )?;
+            let swapchain_framebuffers = Self::create_swapchain_framebuffers(
+                &device,
+                &swapchain_image_views,
+                overlay_render_pass,
+                swapchain_extent,
+            )?;
             
             // Create command pool
-            let command_pool = Self::create_command_pool(&instance, physical_device, &device, &surface_loader, surface)?;
+            let command_pool = Self::create_command_pool(&instance, physical_device, &device, surface_ref)?;
+
+            // Upload the fallback white texture used by untextured materials
+            texture_cache.initialize(&instance, physical_device, &device, command_pool, graphics_queue)?;
+
+            // Create shadow map pass (its depth texture is bound in the main descriptor set)
+            let mut shadow_pass = crate::core::passes::ShadowPass::new(crate::game::ShadowConfig::default().resolution);
+            let shadow_ctx = crate::core::RenderContext {
+                device: &device,
+                instance: &instance,
+                physical_device,
+                command_pool,
+                graphics_queue,
+                extent: swapchain_extent,
+                pipeline_cache: &pipeline_cache,
+                msaa_samples,
+                depth_image_view: Some(depth_image_view),
+                depth_sampler: Some(depth_sampler),
+                mesh_pipeline: None,
+                mesh_instanced_pipeline: None,
+                mesh_transparent_pipeline: None,
+                mesh_overdraw_pipeline: None,
+                mesh_prepass_pipelines: None,
+                mesh_pipeline_layout: None,
+                mesh_descriptor_sets: None,
+                custom_meshes: None,
+                primitive_meshes: None,
+                textures: None,
+            };
+            shadow_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+
+            // Create particle pass (its compute stage runs outside the main render pass)
+            let mut particle_pass = crate::core::passes::ParticlePass::new(
+                MAX_FRAMES_IN_FLIGHT,
+                crate::game::ParticleConfig::default().max_particles,
+            );
+            particle_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+
+            // Create bloom pass (samples the HDR scene color after the main pass)
+            let mut bloom_pass = crate::core::passes::BloomPass::new(HDR_FORMAT);
+            bloom_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+            bloom_pass.resize(&shadow_ctx, &hdr_image_views)?;
+
+            // Create tonemap pass (HDR scene color -> swapchain)
+            let mut tonemap_pass = crate::core::passes::TonemapPass::new(swapchain_format);
+            tonemap_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+            tonemap_pass.resize(&shadow_ctx, &hdr_image_views)?;
+
+            // Create FXAA pass (intermediate tonemapped target -> swapchain)
+            let mut fxaa_pass = crate::core::passes::FxaaPass::new(swapchain_format);
+            fxaa_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+            fxaa_pass.resize(&shadow_ctx, hdr_image_views.len())?;
+
+            // Create material preview pass (its texture is registered with ImGui below)
+            let mut material_preview_pass = crate::core::passes::MaterialPreviewPass::new();
+            material_preview_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+
+            // Create minimap pass (its texture is registered with ImGui below)
+            let mut minimap_pass = crate::core::passes::MinimapPass::new(MAX_FRAMES_IN_FLIGHT);
+            minimap_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
+
+            // Create thumbnail pass (its atlas is registered with ImGui below)
+            let mut thumbnail_pass = crate::core::passes::ThumbnailPass::new();
+            thumbnail_pass.initialize(&shadow_ctx, render_pass, swapchain_extent)?;
             
             // Create cube mesh (will be used for all cube objects)
             let cube_mesh = Mesh::create_cube();
@@ -489,6 +852,32 @@ impl VulkanRenderer {
                 &cube_mesh.indices,
             )?;
 
+            // Create shared buffers for the built-in primitives (drawn like cubes)
+            let mut primitive_meshes = std::collections::HashMap::new();
+            for shape in crate::mesh::PrimitiveShape::ALL {
+                let primitive_mesh = Mesh::create_primitive(shape);
+                let (primitive_vertex_buffer, primitive_vertex_memory) = Self::create_vertex_buffer(
+                    &instance,
+                    physical_device,
+                    &device,
+                    command_pool,
+                    graphics_queue,
+                    &primitive_mesh.vertices,
+                )?;
+                let (primitive_index_buffer, primitive_index_memory) = Self::create_index_buffer(
+                    &instance,
+                    physical_device,
+                    &device,
+                    command_pool,
+                    graphics_queue,
+                    &primitive_mesh.indices,
+                )?;
+                primitive_meshes.insert(
+                    shape,
+                    (primitive_mesh, primitive_vertex_buffer, primitive_vertex_memory, primitive_index_buffer, primitive_index_memory),
+                );
+            }
+
             // Legacy: keep old mesh references for compatibility
             let mesh = cube_mesh.clone();
             let vertex_buffer = cube_vertex_buffer;
@@ -503,7 +892,22 @@ impl VulkanRenderer {
                 &device,
                 MAX_FRAMES_IN_FLIGHT,
             )?;
+            let (point_light_buffers, point_light_buffers_memory) = Self::create_point_light_buffers(
+                &instance,
+                physical_device,
+                &device,
+                MAX_FRAMES_IN_FLIGHT,
+            )?;
             
+            // Environment lighting starts black and is computed from the skybox on the first frame
+            let environment = crate::core::EnvironmentLighting::new(
+                &instance,
+                physical_device,
+                &device,
+                command_pool,
+                graphics_queue,
+            )?;
+
             // Create descriptor pool and sets
             let descriptor_pool = Self::create_descriptor_pool(&device, MAX_FRAMES_IN_FLIGHT)?;
             let descriptor_sets = Self::create_descriptor_sets(
@@ -511,8 +915,11 @@ impl VulkanRenderer {
                 descriptor_pool,
                 descriptor_set_layout,
                 &uniform_buffers,
+                &point_light_buffers,
                 ssao_blur_image_view,
                 ssao_sampler,
+                shadow_pass.image_view(),
+                shadow_pass.sampler(),
                 MAX_FRAMES_IN_FLIGHT,
             )?;
             // Create all three gizmo meshes
@@ -547,6 +954,8 @@ impl VulkanRenderer {
                 position: Vec3::ZERO,
                 normal: Vec3::Y,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             };
             let mut temp_vertices = gizmo_translate_mesh.vertices.clone();
             temp_vertices.resize(max_vertices, zero_vertex);
@@ -603,7 +1012,7 @@ impl VulkanRenderer {
 
             let gizmo_descriptor_set_layout = Self::create_descriptor_set_layout(&device)?;
             let (gizmo_pipeline_layout, gizmo_pipeline) =
-            Self::create_gizmo_pipeline(&device, swapchain_extent, render_pass, gizmo_descriptor_set_layout)?;
+            Self::create_gizmo_pipeline(&device, &pipeline_cache, swapchain_extent, render_pass, msaa_samples, gizmo_descriptor_set_layout)?;
 
             let (gizmo_uniform_buffers, gizmo_uniform_buffers_memory) = Self::create_gizmo_uniform_buffers(
                 &instance,
@@ -618,11 +1027,64 @@ impl VulkanRenderer {
                 gizmo_descriptor_pool,
                 gizmo_descriptor_set_layout,
                 &gizmo_uniform_buffers,
+                &point_light_buffers,
+                ssao_blur_image_view,
+                ssao_sampler,
+                shadow_pass.image_view(),
+                shadow_pass.sampler(),
+                MAX_FRAMES_IN_FLIGHT,
+            )?;
+
+            // Create view cube mesh and its per-frame uniform buffers (shares the gizmo pipeline)
+            let (view_cube_vertices, view_cube_indices) = crate::view_cube::ViewCube::generate_mesh();
+            let view_cube_mesh = Mesh {
+                vertices: view_cube_vertices,
+                indices: view_cube_indices,
+            };
+            let (view_cube_vertex_buffer, view_cube_vertex_buffer_memory) = Self::create_vertex_buffer(
+                &instance,
+                physical_device,
+                &device,
+                command_pool,
+                graphics_queue,
+                &view_cube_mesh.vertices,
+            )?;
+            let (view_cube_index_buffer, view_cube_index_buffer_memory) = Self::create_index_buffer(
+                &instance,
+                physical_device,
+                &device,
+                command_pool,
+                graphics_queue,
+                &view_cube_mesh.indices,
+            )?;
+
+            let (view_cube_uniform_buffers, view_cube_uniform_buffers_memory) = Self::create_gizmo_uniform_buffers(
+                &instance,
+                physical_device,
+                &device,
+                MAX_FRAMES_IN_FLIGHT,
+            )?;
+
+            let view_cube_descriptor_pool = Self::create_descriptor_pool(&device, MAX_FRAMES_IN_FLIGHT)?;
+            let view_cube_descriptor_sets = Self::create_descriptor_sets(
+                &device,
+                view_cube_descriptor_pool,
+                gizmo_descriptor_set_layout,
+                &view_cube_uniform_buffers,
+                &point_light_buffers,
                 ssao_blur_image_view,
                 ssao_sampler,
+                shadow_pass.image_view(),
+                shadow_pass.sampler(),
                 MAX_FRAMES_IN_FLIGHT,
             )?;
 
+            let reflection_history = crate::core::ReflectionHistory::new(&instance, physical_device, &device, swapchain_extent)?;
+
+            for sets in [&descriptor_sets, &gizmo_descriptor_sets, &view_cube_descriptor_sets] {
+                environment.write_descriptors(&device, sets);
+                reflection_history.write_descriptors(&device, sets);
+            }
 
             // Create command buffers
             let command_buffers = Self::create_command_buffers(&device, command_pool, MAX_FRAMES_IN_FLIGHT)?;
@@ -631,40 +1093,23 @@ impl VulkanRenderer {
             let (image_available_semaphores, render_finished_semaphores, in_flight_fences) =
             Self::create_sync_objects(&device, MAX_FRAMES_IN_FLIGHT)?;
             
-            // Initialize lighting
-            let directional_light = DirectionalLight {
-                direction: Vec3::new(-0.3, -1.0, -0.3).normalize(),
-                color: Vec3::new(1.0, 0.95, 0.9),
-                intensity: 1.0,
-                shadow_color: Vec3::new(0.1, 0.1, 0.15),
-            };
-            
-            let point_lights = vec![
-            PointLight {
-                position: Vec3::new(2.0, 2.0, 2.0),
-                color: Vec3::new(1.0, 0.3, 0.3),
-                intensity: 5.0,
-            },
-            PointLight {
-                position: Vec3::new(-2.0, 2.0, -2.0),
-                color: Vec3::new(0.3, 0.3, 1.0),
-                intensity: 5.0,
-            },
-            ];
-            
             // Initialize images_in_flight with null fences
             let images_in_flight = vec![vk::Fence::null(); swapchain_images.len()];
             
             // Initialize ImGui
             let mut imgui_context = Context::create();
-            imgui_context.set_ini_filename(None);
+            // Panel positions and sizes persist between sessions (headless runs leave them alone)
+            let ini_path = window.as_ref().map(|_| std::path::PathBuf::from(crate::ui::IMGUI_INI_PATH));
+            imgui_context.set_ini_filename(ini_path);
             
             let mut imgui_platform = imgui_winit_support::WinitPlatform::init(&mut imgui_context);
-            imgui_platform.attach_window(
-                imgui_context.io_mut(),
-                &window,
-                imgui_winit_support::HiDpiMode::Default,
-            );
+            if let Some(window) = &window {
+                imgui_platform.attach_window(
+                    imgui_context.io_mut(),
+                    window,
+                    imgui_winit_support::HiDpiMode::Default,
+                );
+            }
 
             // Create directional light visualization mesh
             let dir_light_mesh = Mesh::create_directional_light_viz();
@@ -693,26 +1138,46 @@ impl VulkanRenderer {
                 }),
             }]);
             
-            let imgui_renderer = ImGuiRenderer::new(
+            let mut imgui_renderer = ImGuiRenderer::new(
                 &mut imgui_context,
                 &device,
+                &pipeline_cache,
                 &instance,
                 physical_device,
-                render_pass,
+                overlay_render_pass,
                 command_pool,
                 graphics_queue,
                 swapchain_extent,
             )?;
+            let material_preview_texture = imgui_renderer.register_texture(
+                &device,
+                material_preview_pass.image_view(),
+                material_preview_pass.sampler(),
+            )?;
+            let minimap_texture = imgui_renderer.register_texture(
+                &device,
+                minimap_pass.image_view(),
+                minimap_pass.sampler(),
+            )?;
+            let thumbnail_texture = imgui_renderer.register_texture(
+                &device,
+                thumbnail_pass.image_view(),
+                thumbnail_pass.sampler(),
+            )?;
+            thumbnail_pass.set_texture_id(thumbnail_texture);
 
             // Initialize render pass plugin system
             let mut render_passes = crate::core::RenderPassRegistry::new();
 
             // Register passes
+            render_passes.register(Box::new(crate::core::passes::BackgroundPass::new()));
             render_passes.register(Box::new(crate::core::passes::SkyboxPass::new()));
             render_passes.register(Box::new(crate::core::passes::NebulaPass::new()));
-            render_passes.register(Box::new(crate::core::passes::MeshPass::new()));
+            render_passes.register(Box::new(crate::core::passes::MeshPass::new(MAX_FRAMES_IN_FLIGHT)));
             render_passes.register(Box::new(crate::core::passes::StarPass::new(MAX_FRAMES_IN_FLIGHT)));
             render_passes.register(Box::new(crate::core::passes::UnlitPass::new()));
+            render_passes.register(Box::new(crate::core::passes::GridPass::new()));
+            render_passes.register(Box::new(crate::core::passes::WireframePass::new()));
             render_passes.register(Box::new(crate::core::passes::OutlinePass::new()));
             render_passes.register(Box::new(crate::core::passes::LinePass::new(10000))); // 10k vertex capacity
 
@@ -724,12 +1189,20 @@ impl VulkanRenderer {
                 command_pool,
                 graphics_queue,
                 extent: swapchain_extent,
+                pipeline_cache: &pipeline_cache,
+                msaa_samples,
                 depth_image_view: Some(depth_image_view),
                 depth_sampler: Some(depth_sampler),
                 mesh_pipeline: Some(graphics_pipeline),
+                mesh_instanced_pipeline: Some(instanced_pipeline),
+                mesh_transparent_pipeline: Some(transparent_pipeline),
+                mesh_overdraw_pipeline: Some(overdraw_pipeline),
+                mesh_prepass_pipelines: Some(depth_prepass_pipelines),
                 mesh_pipeline_layout: Some(pipeline_layout),
                 mesh_descriptor_sets: Some(&descriptor_sets),
                 custom_meshes: None,  // No meshes loaded yet at initialization
+                primitive_meshes: Some(&primitive_meshes),
+                textures: Some(&texture_cache),
             };
             render_passes.initialize_all(&ctx, render_pass, swapchain_extent)?;
 
@@ -737,23 +1210,40 @@ impl VulkanRenderer {
                 _entry: entry,
                 instance,
                 debug_utils,
-                surface,
-                surface_loader,
+                window_surface: match (window, surface) {
+                    (Some(window), Some((surface_loader, surface))) => Some(WindowSurface {
+                        window,
+                        surface,
+                        surface_loader,
+                        present_queue,
+                        swapchain,
+                    }),
+                    _ => None,
+                },
                 physical_device,
                 device,
                 graphics_queue,
-                present_queue,
-                swapchain,
+                pipeline_cache,
                 swapchain_loader,
                 swapchain_images,
+                offscreen_images_memory,
                 swapchain_image_views,
                 swapchain_format,
                 swapchain_extent,
+                hdr_images,
+                hdr_images_memory,
+                hdr_image_views,
                 render_pass,
+                overlay_render_pass,
+                swapchain_framebuffers,
                 descriptor_set_layout,
                 pipeline_layout,
                 graphics_pipeline,
                 wireframe_pipeline,
+                instanced_pipeline,
+                transparent_pipeline,
+                overdraw_pipeline,
+                depth_prepass_pipelines,
                 gizmo_translate_mesh,
                 gizmo_rotate_mesh,
                 gizmo_scale_mesh,
@@ -769,6 +1259,15 @@ impl VulkanRenderer {
                 gizmo_uniform_buffers,
                 gizmo_uniform_buffers_memory,
                 gizmo_descriptor_sets,
+                view_cube_mesh,
+                view_cube_vertex_buffer,
+                view_cube_vertex_buffer_memory,
+                view_cube_index_buffer,
+                view_cube_index_buffer_memory,
+                view_cube_uniform_buffers,
+                view_cube_uniform_buffers_memory,
+                view_cube_descriptor_pool,
+                view_cube_descriptor_sets,
                 framebuffers,
                 command_pool,
                 command_buffers,
@@ -781,13 +1280,14 @@ impl VulkanRenderer {
                 fps_frame_count: 0,
                 last_time: std::time::Instant::now(),
                 last_frame_time: std::time::Instant::now(),
-                window,
+                frame_stats: crate::core::FrameStats::new(),
                 cube_mesh,
                 cube_vertex_buffer,
                 cube_vertex_buffer_memory,
                 cube_index_buffer,
                 cube_index_buffer_memory,
                 custom_meshes: std::collections::HashMap::new(),
+                primitive_meshes,
                 dir_light_mesh,
                 dir_light_vertex_buffer,
                 dir_light_vertex_buffer_memory,
@@ -800,12 +1300,22 @@ impl VulkanRenderer {
                 index_buffer_memory,
                 uniform_buffers,
                 uniform_buffers_memory,
+                point_light_buffers,
+                point_light_buffers_memory,
                 descriptor_pool,
                 descriptor_sets,
                 depth_image,
                 depth_image_memory,
                 depth_image_view,
                 depth_sampler,
+                msaa_samples,
+                msaa_requested,
+                max_msaa_samples,
+                msaa_targets,
+                anisotropy_requested: 1,
+                max_anisotropy,
+                present_mode_requested,
+                present_mode,
                 ssao_image,
                 ssao_image_memory,
                 ssao_image_view,
@@ -813,6 +1323,7 @@ impl VulkanRenderer {
                 ssao_blur_image_memory,
                 ssao_blur_image_view,
                 ssao_sampler,
+                ssao_extent: swapchain_extent,
                 ssao_render_pass,
                 ssao_framebuffer,
                 ssao_descriptor_set_layout,
@@ -836,12 +1347,26 @@ impl VulkanRenderer {
                 ssao_blur_horizontal_framebuffer,
                 ssao_blur_horizontal_descriptor_pool,
                 ssao_blur_horizontal_descriptor_sets,
-                directional_light,
-                point_lights,
                 imgui_context,
                 imgui_renderer,
                 imgui_platform,
                 render_passes,
+                shadow_pass,
+                environment,
+                reflection_history,
+                history_origin: DVec3::ZERO,
+                particle_pass,
+                bloom_pass,
+                tonemap_pass,
+                fxaa_pass,
+                material_preview_pass,
+                material_preview_texture,
+                minimap_pass,
+                minimap_texture,
+                thumbnail_pass,
+                texture_cache,
+                screenshot_request: None,
+                screenshot_capture: None,
             })
         }
     }
@@ -875,15 +1400,19 @@ impl VulkanRenderer {
         Ok(None)
     }
     
+    /// With a surface only discrete GPUs that can present to it qualify. Headless
+    /// renderers take any capable device, discrete first
     unsafe fn pick_physical_device(
         instance: &ash::Instance,
-        surface_loader: &ash::khr::surface::Instance,
-        surface: vk::SurfaceKHR,
+        surface: Option<(&ash::khr::surface::Instance, vk::SurfaceKHR)>,
     ) -> anyhow::Result<vk::PhysicalDevice> {
-        let devices = instance.enumerate_physical_devices()?;
+        let mut devices = instance.enumerate_physical_devices()?;
+        devices.sort_by_key(|&device| {
+            instance.get_physical_device_properties(device).device_type != vk::PhysicalDeviceType::DISCRETE_GPU
+        });
         
         for device in devices {
-            if Self::is_device_suitable(instance, device, surface_loader, surface)? {
+            if Self::is_device_suitable(instance, device, surface)? {
                 return Ok(device);
             }
         }
@@ -894,36 +1423,38 @@ impl VulkanRenderer {
     unsafe fn is_device_suitable(
         instance: &ash::Instance,
         device: vk::PhysicalDevice,
-        surface_loader: &ash::khr::surface::Instance,
-        surface: vk::SurfaceKHR,
+        surface: Option<(&ash::khr::surface::Instance, vk::SurfaceKHR)>,
     ) -> anyhow::Result<bool> {
         let props = instance.get_physical_device_properties(device);
         let features = instance.get_physical_device_features(device);
         
-        let queue_families = Self::find_queue_families(instance, device, surface_loader, surface)?;
+        let queue_families = Self::find_queue_families(instance, device, surface)?;
         
         let extensions_supported = Self::check_device_extension_support(instance, device)?;
         
-        let swapchain_adequate = if extensions_supported {
-            let formats = surface_loader.get_physical_device_surface_formats(device, surface)?;
-            let present_modes = surface_loader.get_physical_device_surface_present_modes(device, surface)?;
-            !formats.is_empty() && !present_modes.is_empty()
-        } else {
-            false
+        let swapchain_adequate = match surface {
+            Some((surface_loader, surface)) if extensions_supported => {
+                let formats = surface_loader.get_physical_device_surface_formats(device, surface)?;
+                let present_modes = surface_loader.get_physical_device_surface_present_modes(device, surface)?;
+                !formats.is_empty() && !present_modes.is_empty()
+            }
+            Some(_) => false,
+            None => true,
         };
         
-        Ok(props.device_type == vk::PhysicalDeviceType::DISCRETE_GPU
+        Ok((props.device_type == vk::PhysicalDeviceType::DISCRETE_GPU || surface.is_none())
             && features.geometry_shader == vk::TRUE
+            && features.fill_mode_non_solid == vk::TRUE
             && queue_families.is_complete()
             && extensions_supported
             && swapchain_adequate)
         }
         
+        /// Without a surface nothing is presented, so the graphics family doubles as the present family
         unsafe fn find_queue_families(
             instance: &ash::Instance,
             device: vk::PhysicalDevice,
-            surface_loader: &ash::khr::surface::Instance,
-            surface: vk::SurfaceKHR,
+            surface: Option<(&ash::khr::surface::Instance, vk::SurfaceKHR)>,
         ) -> anyhow::Result<QueueFamilyIndices> {
             let queue_families = instance.get_physical_device_queue_family_properties(device);
             
@@ -934,11 +1465,14 @@ impl VulkanRenderer {
                     indices.graphics_family = Some(i as u32);
                 }
                 
-                let present_support = surface_loader.get_physical_device_surface_support(
-                    device,
-                    i as u32,
-                    surface,
-                )?;
+                let present_support = match surface {
+                    Some((surface_loader, surface)) => surface_loader.get_physical_device_surface_support(
+                        device,
+                        i as u32,
+                        surface,
+                    )?,
+                    None => indices.graphics_family == Some(i as u32),
+                };
                 
                 if present_support {
                     indices.present_family = Some(i as u32);
@@ -977,10 +1511,9 @@ impl VulkanRenderer {
         unsafe fn create_logical_device(
             instance: &ash::Instance,
             physical_device: vk::PhysicalDevice,
-            surface_loader: &ash::khr::surface::Instance,
-            surface: vk::SurfaceKHR,
+            surface: Option<(&ash::khr::surface::Instance, vk::SurfaceKHR)>,
         ) -> anyhow::Result<(ash::Device, vk::Queue, vk::Queue)> {
-            let indices = Self::find_queue_families(instance, physical_device, surface_loader, surface)?;
+            let indices = Self::find_queue_families(instance, physical_device, surface)?;
             
             let mut unique_queue_families = std::collections::HashSet::new();
             unique_queue_families.insert(indices.graphics_family.unwrap());
@@ -1002,7 +1535,11 @@ impl VulkanRenderer {
             })
             .collect();
             
-            let device_features = vk::PhysicalDeviceFeatures::default();
+            // Line polygon mode is used by the wireframe pipelines, anisotropy by material textures when available
+            let supported_features = instance.get_physical_device_features(physical_device);
+            let device_features = vk::PhysicalDeviceFeatures::default()
+            .fill_mode_non_solid(true)
+            .sampler_anisotropy(supported_features.sampler_anisotropy == vk::TRUE);
             
             let device_extensions = [ash::khr::swapchain::NAME.as_ptr()];
             
@@ -1027,7 +1564,8 @@ impl VulkanRenderer {
             surface_loader: &ash::khr::surface::Instance,
             surface: vk::SurfaceKHR,
             swapchain_loader: &ash::khr::swapchain::Device,
-        ) -> anyhow::Result<(vk::SwapchainKHR, Vec<vk::Image>, vk::Format, vk::Extent2D)> {
+            requested_present_mode: vk::PresentModeKHR,
+        ) -> anyhow::Result<(vk::SwapchainKHR, Vec<vk::Image>, vk::Format, vk::Extent2D, vk::PresentModeKHR)> {
             let capabilities = surface_loader.get_physical_device_surface_capabilities(physical_device, surface)?;
             let formats = surface_loader.get_physical_device_surface_formats(physical_device, surface)?;
             let present_modes = surface_loader.get_physical_device_surface_present_modes(physical_device, surface)?;
@@ -1040,11 +1578,13 @@ impl VulkanRenderer {
             })
             .unwrap_or(&formats[0]);
             
-            let present_mode = present_modes
-            .iter()
-            .copied()
-            .find(|&mode| mode == vk::PresentModeKHR::MAILBOX)
-            .unwrap_or(vk::PresentModeKHR::FIFO);
+            // Transfer source lets screenshots copy the presented image back
+            let mut image_usage = vk::ImageUsageFlags::COLOR_ATTACHMENT;
+            if capabilities.supported_usage_flags.contains(vk::ImageUsageFlags::TRANSFER_SRC) {
+                image_usage |= vk::ImageUsageFlags::TRANSFER_SRC;
+            }
+            
+            let present_mode = Self::choose_present_mode(requested_present_mode, &present_modes);
             
             let extent = {
                 if capabilities.current_extent.width != u32::MAX {
@@ -1072,7 +1612,7 @@ impl VulkanRenderer {
                 },
             );
             
-            let indices = Self::find_queue_families(instance, physical_device, surface_loader, surface)?;
+            let indices = Self::find_queue_families(instance, physical_device, Some((surface_loader, surface)))?;
             let queue_family_indices = [
             indices.graphics_family.unwrap(),
             indices.present_family.unwrap(),
@@ -1092,7 +1632,7 @@ impl VulkanRenderer {
             .image_color_space(surface_format.color_space)
             .image_extent(extent)
             .image_array_layers(1)
-            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+            .image_usage(image_usage)
             .image_sharing_mode(image_sharing_mode)
             .queue_family_indices(&queue_family_indices[..queue_family_index_count as usize])
             .pre_transform(capabilities.current_transform)
@@ -1103,7 +1643,24 @@ impl VulkanRenderer {
             let swapchain = swapchain_loader.create_swapchain(&create_info, None)?;
             let images = swapchain_loader.get_swapchain_images(swapchain)?;
             
-            Ok((swapchain, images, surface_format.format, extent))
+            Ok((swapchain, images, surface_format.format, extent, present_mode))
+        }
+
+        fn present_mode_khr(mode: crate::game::PresentMode) -> vk::PresentModeKHR {
+            match mode {
+                crate::game::PresentMode::Fifo => vk::PresentModeKHR::FIFO,
+                crate::game::PresentMode::Mailbox => vk::PresentModeKHR::MAILBOX,
+                crate::game::PresentMode::Immediate => vk::PresentModeKHR::IMMEDIATE,
+            }
+        }
+
+        /// The requested present mode if the surface supports it, otherwise FIFO (the only mode Vulkan guarantees)
+        fn choose_present_mode(requested: vk::PresentModeKHR, available: &[vk::PresentModeKHR]) -> vk::PresentModeKHR {
+            if available.contains(&requested) {
+                requested
+            } else {
+                vk::PresentModeKHR::FIFO
+            }
         }
         
         unsafe fn create_image_views(
@@ -1137,49 +1694,101 @@ impl VulkanRenderer {
             .collect()
         }
         
+        /// Main scene render pass. With MSAA the scene renders into multisampled color/depth
+        /// targets that resolve into the HDR image and the (sampled) depth image
         unsafe fn create_render_pass(
             device: &ash::Device,
             format: vk::Format,
+            samples: vk::SampleCountFlags,
         ) -> anyhow::Result<vk::RenderPass> {
-            let color_attachment = vk::AttachmentDescription::default()
+            let msaa = samples != vk::SampleCountFlags::TYPE_1;
+
+            let color_attachment = vk::AttachmentDescription2::default()
             .format(format)
-            .samples(vk::SampleCountFlags::TYPE_1)
+            .samples(samples)
             .load_op(vk::AttachmentLoadOp::CLEAR)
-            .store_op(vk::AttachmentStoreOp::STORE)
+            .store_op(if msaa { vk::AttachmentStoreOp::DONT_CARE } else { vk::AttachmentStoreOp::STORE })
             .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
             .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
             .initial_layout(vk::ImageLayout::UNDEFINED)
-            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+            .final_layout(if msaa {
+                vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL
+            } else {
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL // Sampled by bloom/tonemap
+            });
             
-            let depth_attachment = vk::AttachmentDescription::default()
-            .format(vk::Format::D32_SFLOAT)
-            .samples(vk::SampleCountFlags::TYPE_1)
+            let depth_attachment = vk::AttachmentDescription2::default()
+            .format(DEPTH_FORMAT)
+            .samples(samples)
             .load_op(vk::AttachmentLoadOp::CLEAR)
-            .store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .store_op(if msaa { vk::AttachmentStoreOp::DONT_CARE } else { vk::AttachmentStoreOp::STORE }) // Sampled by SSAO
+            .stencil_load_op(vk::AttachmentLoadOp::CLEAR) // Outline pass masks selected objects
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+            // Single-sample resolve targets (only used with MSAA)
+            let color_resolve_attachment = vk::AttachmentDescription2::default()
+            .format(format)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL); // Sampled by bloom/tonemap
+
+            let depth_resolve_attachment = vk::AttachmentDescription2::default()
+            .format(DEPTH_FORMAT)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .store_op(vk::AttachmentStoreOp::STORE) // Sampled by SSAO
             .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
             .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
             .initial_layout(vk::ImageLayout::UNDEFINED)
             .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
             
-            let color_attachment_ref = vk::AttachmentReference::default()
+            let color_attachment_ref = vk::AttachmentReference2::default()
             .attachment(0)
             .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
             
-            let depth_attachment_ref = vk::AttachmentReference::default()
+            let depth_attachment_ref = vk::AttachmentReference2::default()
             .attachment(1)
             .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+            let color_resolve_ref = vk::AttachmentReference2::default()
+            .attachment(2)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+            let depth_resolve_ref = vk::AttachmentReference2::default()
+            .attachment(3)
+            .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+            // Depth keeps sample 0 (always supported, no averaging of depth values)
+            let mut depth_resolve = vk::SubpassDescriptionDepthStencilResolve::default()
+            .depth_resolve_mode(vk::ResolveModeFlags::SAMPLE_ZERO)
+            .stencil_resolve_mode(vk::ResolveModeFlags::SAMPLE_ZERO)
+            .depth_stencil_resolve_attachment(&depth_resolve_ref);
             
-            let subpass = vk::SubpassDescription::default()
+            let mut subpass = vk::SubpassDescription2::default()
             .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
             .color_attachments(std::slice::from_ref(&color_attachment_ref))
             .depth_stencil_attachment(&depth_attachment_ref);
+            if msaa {
+                subpass = subpass
+                .resolve_attachments(std::slice::from_ref(&color_resolve_ref))
+                .push_next(&mut depth_resolve);
+            }
             
-            let dependency = vk::SubpassDependency::default()
+            let dependencies = [
+            // Previous reads of the HDR target (tonemap) must finish before it is cleared
+            vk::SubpassDependency2::default()
             .src_subpass(vk::SUBPASS_EXTERNAL)
             .dst_subpass(0)
             .src_stage_mask(
                 vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
-                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS
+                | vk::PipelineStageFlags::FRAGMENT_SHADER,
             )
             .src_access_mask(vk::AccessFlags::empty())
             .dst_stage_mask(
@@ -1189,36 +1798,279 @@ impl VulkanRenderer {
             .dst_access_mask(
                 vk::AccessFlags::COLOR_ATTACHMENT_WRITE
                 | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
-            );
+            ),
+            // Make the finished scene color (and depth) visible to SSAO/bloom/tonemap
+            // (resolves are COLOR_ATTACHMENT_OUTPUT writes, depth included)
+            vk::SubpassDependency2::default()
+            .src_subpass(0)
+            .dst_subpass(vk::SUBPASS_EXTERNAL)
+            .src_stage_mask(
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+            )
+            .src_access_mask(
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+                | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            )
+            .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+            .dst_access_mask(vk::AccessFlags::SHADER_READ),
+            ];
             
-            let attachments = [color_attachment, depth_attachment];
-            let create_info = vk::RenderPassCreateInfo::default()
+            let attachments = if msaa {
+                vec![color_attachment, depth_attachment, color_resolve_attachment, depth_resolve_attachment]
+            } else {
+                vec![color_attachment, depth_attachment]
+            };
+            let create_info = vk::RenderPassCreateInfo2::default()
             .attachments(&attachments)
             .subpasses(std::slice::from_ref(&subpass))
-            .dependencies(std::slice::from_ref(&dependency));
+            .dependencies(&dependencies);
             
-            Ok(device.create_render_pass(&create_info, None)?)
+            Ok(device.create_render_pass2(&create_info, None)?)
         }
-        
-        unsafe fn create_descriptor_set_layout(device: &ash::Device) -> anyhow::Result<vk::DescriptorSetLayout> {
-            let ubo_binding = vk::DescriptorSetLayoutBinding::default()
-            .binding(0)
-            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
-            .descriptor_count(1)
-            .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT);
 
-            // Binding 1: SSAO texture sampler
-            let ssao_binding = vk::DescriptorSetLayoutBinding::default()
-                .binding(1)
-                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-                .descriptor_count(1)
-                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+        /// Highest MSAA sample count usable for the main pass's color and depth/stencil targets
+        unsafe fn max_msaa_samples(instance: &ash::Instance, physical_device: vk::PhysicalDevice) -> vk::SampleCountFlags {
+            let limits = instance.get_physical_device_properties(physical_device).limits;
+            let supported = limits.framebuffer_color_sample_counts
+                & limits.framebuffer_depth_sample_counts
+                & limits.framebuffer_stencil_sample_counts;
+
+            [
+                vk::SampleCountFlags::TYPE_8,
+                vk::SampleCountFlags::TYPE_4,
+                vk::SampleCountFlags::TYPE_2,
+            ]
+            .into_iter()
+            .find(|&count| supported.contains(count))
+            .unwrap_or(vk::SampleCountFlags::TYPE_1)
+        }
 
-            let bindings = [ubo_binding, ssao_binding];
-            let create_info = vk::DescriptorSetLayoutCreateInfo::default()
-            .bindings(&bindings);
+        /// Highest sampler anisotropy, or 1.0 if the `samplerAnisotropy` feature is missing
+        unsafe fn max_sampler_anisotropy(instance: &ash::Instance, physical_device: vk::PhysicalDevice) -> f32 {
+            if instance.get_physical_device_features(physical_device).sampler_anisotropy != vk::TRUE {
+                return 1.0;
+            }
+            instance.get_physical_device_properties(physical_device).limits.max_sampler_anisotropy.max(1.0)
+        }
 
-            Ok(device.create_descriptor_set_layout(&create_info, None)?)
+        /// Clamp a requested sample count (1/2/4/8) to the supported maximum
+        fn clamp_msaa_samples(requested: u32, max: vk::SampleCountFlags) -> vk::SampleCountFlags {
+            vk::SampleCountFlags::from_raw(requested.clamp(1, max.as_raw()).next_power_of_two())
+        }
+
+        /// Multisampled color + depth targets for the main pass (one set shared by all frames, like depth)
+        unsafe fn create_msaa_resources(
+            instance: &ash::Instance,
+            physical_device: vk::PhysicalDevice,
+            device: &ash::Device,
+            extent: vk::Extent2D,
+            samples: vk::SampleCountFlags,
+        ) -> anyhow::Result<MsaaTargets> {
+            if samples == vk::SampleCountFlags::TYPE_1 {
+                return Ok(MsaaTargets::null());
+            }
+
+            let (color_image, color_memory, color_view) = Self::create_msaa_image(
+                instance,
+                physical_device,
+                device,
+                extent,
+                samples,
+                HDR_FORMAT,
+                vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+                vk::ImageAspectFlags::COLOR,
+            )?;
+            let (depth_image, depth_memory, depth_view) = Self::create_msaa_image(
+                instance,
+                physical_device,
+                device,
+                extent,
+                samples,
+                DEPTH_FORMAT,
+                vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+                vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
+            )?;
+
+            Ok(MsaaTargets {
+                color_image,
+                color_memory,
+                color_view,
+                depth_image,
+                depth_memory,
+                depth_view,
+            })
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        unsafe fn create_msaa_image(
+            instance: &ash::Instance,
+            physical_device: vk::PhysicalDevice,
+            device: &ash::Device,
+            extent: vk::Extent2D,
+            samples: vk::SampleCountFlags,
+            format: vk::Format,
+            usage: vk::ImageUsageFlags,
+            aspect_mask: vk::ImageAspectFlags,
+        ) -> anyhow::Result<(vk::Image, vk::DeviceMemory, vk::ImageView)> {
+            let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: extent.width,
+                height: extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(samples);
+
+            let image = device.create_image(&image_info, None)?;
+            let mem_requirements = device.get_image_memory_requirements(image);
+
+            let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(Self::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+            let memory = device.allocate_memory(&alloc_info, None)?;
+            device.bind_image_memory(image, memory, 0)?;
+
+            let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+
+            let view = device.create_image_view(&view_info, None)?;
+
+            Ok((image, memory, view))
+        }
+        
+        /// Color-only swapchain render pass that keeps the tonemapped image contents,
+        /// used to draw ImGui on top after post-processing (compatible with the tonemap pass)
+        unsafe fn create_overlay_render_pass(
+            device: &ash::Device,
+            format: vk::Format,
+        ) -> anyhow::Result<vk::RenderPass> {
+            let color_attachment = vk::AttachmentDescription::default()
+            .format(format)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .load_op(vk::AttachmentLoadOp::LOAD)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::PRESENT_SRC_KHR)
+            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+            
+            let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+            
+            let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+            
+            // Wait for the tonemap pass to finish writing the image
+            let dependency = vk::SubpassDependency::default()
+            .src_subpass(vk::SUBPASS_EXTERNAL)
+            .dst_subpass(0)
+            .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+            .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .dst_access_mask(
+                vk::AccessFlags::COLOR_ATTACHMENT_READ
+                | vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+            );
+            
+            let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(std::slice::from_ref(&dependency));
+            
+            Ok(device.create_render_pass(&create_info, None)?)
+        }
+        
+        unsafe fn create_descriptor_set_layout(device: &ash::Device) -> anyhow::Result<vk::DescriptorSetLayout> {
+            let ubo_binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT);
+
+            // Binding 1: SSAO texture sampler
+            let ssao_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(1)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+            // Binding 2: Directional light shadow map
+            let shadow_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(2)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+            // Binding 3: Point lights (storage buffer, length given by the UBO's point_light_count)
+            let point_light_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(3)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+            // Bindings 4/5: Environment irradiance and prefiltered specular cubemaps
+            let irradiance_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(4)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+            let prefiltered_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(5)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+            // Bindings 6/7: Last frame's scene color and depth for screen-space reflections
+            let history_color_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(6)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+            let history_depth_binding = vk::DescriptorSetLayoutBinding::default()
+                .binding(7)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT);
+
+            let bindings = [
+                ubo_binding,
+                ssao_binding,
+                shadow_binding,
+                point_light_binding,
+                irradiance_binding,
+                prefiltered_binding,
+                history_color_binding,
+                history_depth_binding,
+            ];
+            let create_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .bindings(&bindings);
+
+            Ok(device.create_descriptor_set_layout(&create_info, None)?)
         }
 
         unsafe fn create_ssao_descriptor_set_layout(device: &ash::Device) -> anyhow::Result<vk::DescriptorSetLayout> {
@@ -1282,16 +2134,19 @@ impl VulkanRenderer {
 
         unsafe fn create_graphics_pipeline(
             device: &ash::Device,
+            cache: &crate::core::PipelineCache,
             extent: vk::Extent2D,
             render_pass: vk::RenderPass,
+            samples: vk::SampleCountFlags,
             descriptor_set_layout: vk::DescriptorSetLayout,
+            material_set_layout: vk::DescriptorSetLayout,
         ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
             // Shader code will be compiled from GLSL
-            let vert_shader_code = include_bytes!("../../shaders/mesh.vert.spv");
-            let frag_shader_code = include_bytes!("../../shaders/mesh.frag.spv");
+            let vert_shader_code = crate::core::shader::load_spirv("mesh.vert.spv", include_bytes!("../../shaders/mesh.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("mesh.frag.spv", include_bytes!("../../shaders/mesh.frag.spv"));
             
-            let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
             
             let entry_point = CString::new("main")?;
             
@@ -1347,7 +2202,7 @@ impl VulkanRenderer {
             
             let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+            .rasterization_samples(samples);
             
             let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
             .depth_test_enable(true)
@@ -1364,7 +2219,8 @@ impl VulkanRenderer {
             .logic_op_enable(false)
             .attachments(std::slice::from_ref(&color_blend_attachment));
 
-            let set_layouts = [descriptor_set_layout];
+            // Set 0: frame resources, set 1: material textures
+            let set_layouts = [descriptor_set_layout, material_set_layout];
 
             // Define push constant range for model matrix + material properties
             let push_constant_range = vk::PushConstantRange::default()
@@ -1392,28 +2248,28 @@ impl VulkanRenderer {
             .subpass(0);
             
             let pipelines = device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                cache.handle(),
                 std::slice::from_ref(&pipeline_info),
                 None,
             ).map_err(|e| anyhow::anyhow!("Failed to create graphics pipeline: {:?}", e.1))?;
             
-            device.destroy_shader_module(vert_shader_module, None);
-            device.destroy_shader_module(frag_shader_module, None);
             
             Ok((pipeline_layout, pipelines[0]))
         }
 
         unsafe fn create_wireframe_pipeline(
             device: &ash::Device,
+            cache: &crate::core::PipelineCache,
             extent: vk::Extent2D,
             render_pass: vk::RenderPass,
+            samples: vk::SampleCountFlags,
             pipeline_layout: vk::PipelineLayout, // Reuse same layout as graphics pipeline
         ) -> anyhow::Result<vk::Pipeline> {
-            let vert_shader_code = include_bytes!("../../shaders/wireframe.vert.spv");
-            let frag_shader_code = include_bytes!("../../shaders/wireframe.frag.spv");
+            let vert_shader_code = crate::core::shader::load_spirv("wireframe.vert.spv", include_bytes!("../../shaders/wireframe.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("wireframe.frag.spv", include_bytes!("../../shaders/wireframe.frag.spv"));
 
-            let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
             let entry_point = CString::new("main")?;
 
@@ -1470,7 +2326,7 @@ impl VulkanRenderer {
 
             let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
                 .sample_shading_enable(false)
-                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+                .rasterization_samples(samples);
 
             // Wireframe should write depth but at a slight offset to avoid z-fighting
             let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
@@ -1508,28 +2364,28 @@ impl VulkanRenderer {
                 .subpass(0);
 
             let pipelines = device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                cache.handle(),
                 std::slice::from_ref(&pipeline_info),
                 None,
             ).map_err(|e| anyhow::anyhow!("Failed to create wireframe pipeline: {:?}", e.1))?;
 
-            device.destroy_shader_module(vert_shader_module, None);
-            device.destroy_shader_module(frag_shader_module, None);
-
             Ok(pipelines[0])
         }
 
-        unsafe fn create_ssao_pipeline(
+        unsafe fn create_instanced_pipeline(
             device: &ash::Device,
+            cache: &crate::core::PipelineCache,
             extent: vk::Extent2D,
-            ssao_render_pass: vk::RenderPass,
-            descriptor_set_layout: vk::DescriptorSetLayout,
-        ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
-            let vert_shader_code = include_bytes!("../../shaders/ssao.vert.spv");
-            let frag_shader_code = include_bytes!("../../shaders/ssao.frag.spv");
+            render_pass: vk::RenderPass,
+            samples: vk::SampleCountFlags,
+            pipeline_layout: vk::PipelineLayout, // Reuse same layout as graphics pipeline
+        ) -> anyhow::Result<vk::Pipeline> {
+            // Instanced vertex shader, shared fragment shader
+            let vert_shader_code = crate::core::shader::load_spirv("mesh_instanced.vert.spv", include_bytes!("../../shaders/mesh_instanced.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("mesh.frag.spv", include_bytes!("../../shaders/mesh.frag.spv"));
 
-            let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
             let entry_point = CString::new("main")?;
 
@@ -1545,8 +2401,19 @@ impl VulkanRenderer {
 
             let shader_stages = [vert_stage_info, frag_stage_info];
 
-            // No vertex input - fullscreen triangle generated in vertex shader
-            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+            // Binding 0: per-vertex data, binding 1: per-instance model matrix + material
+            let binding_descriptions = [
+                Vertex::get_binding_description(),
+                crate::core::passes::mesh::MeshInstanceData::binding_description(),
+            ];
+            let attribute_descriptions: Vec<vk::VertexInputAttributeDescription> = Vertex::get_attribute_descriptions()
+                .into_iter()
+                .chain(crate::core::passes::mesh::MeshInstanceData::attribute_descriptions())
+                .collect();
+
+            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+                .vertex_binding_descriptions(&binding_descriptions)
+                .vertex_attribute_descriptions(&attribute_descriptions);
 
             let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
                 .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
@@ -1570,39 +2437,35 @@ impl VulkanRenderer {
                 .viewports(std::slice::from_ref(&viewport))
                 .scissors(std::slice::from_ref(&scissor));
 
+            // Same fixed-function state as the main mesh pipeline
             let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
                 .depth_clamp_enable(false)
                 .rasterizer_discard_enable(false)
                 .polygon_mode(vk::PolygonMode::FILL)
                 .line_width(1.0)
-                .cull_mode(vk::CullModeFlags::NONE)
+                .cull_mode(vk::CullModeFlags::BACK)
                 .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
                 .depth_bias_enable(false);
 
             let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
                 .sample_shading_enable(false)
-                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+                .rasterization_samples(samples);
 
-            // No depth test for SSAO pass
             let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
-                .depth_test_enable(false)
-                .depth_write_enable(false);
+                .depth_test_enable(true)
+                .depth_write_enable(true)
+                .depth_compare_op(vk::CompareOp::LESS)
+                .depth_bounds_test_enable(false)
+                .stencil_test_enable(false);
 
             let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
-                .color_write_mask(vk::ColorComponentFlags::R) // Only write red channel
+                .color_write_mask(vk::ColorComponentFlags::RGBA)
                 .blend_enable(false);
 
             let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
                 .logic_op_enable(false)
                 .attachments(std::slice::from_ref(&color_blend_attachment));
 
-            let set_layouts = [descriptor_set_layout];
-
-            let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
-                .set_layouts(&set_layouts);
-
-            let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
-
             let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
                 .stages(&shader_stages)
                 .vertex_input_state(&vertex_input_info)
@@ -1613,32 +2476,32 @@ impl VulkanRenderer {
                 .depth_stencil_state(&depth_stencil)
                 .color_blend_state(&color_blending)
                 .layout(pipeline_layout)
-                .render_pass(ssao_render_pass)
+                .render_pass(render_pass)
                 .subpass(0);
 
             let pipelines = device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                cache.handle(),
                 std::slice::from_ref(&pipeline_info),
                 None,
-            ).map_err(|e| anyhow::anyhow!("Failed to create SSAO pipeline: {:?}", e.1))?;
-
-            device.destroy_shader_module(vert_shader_module, None);
-            device.destroy_shader_module(frag_shader_module, None);
+            ).map_err(|e| anyhow::anyhow!("Failed to create instanced mesh pipeline: {:?}", e.1))?;
 
-            Ok((pipeline_layout, pipelines[0]))
+            Ok(pipelines[0])
         }
 
-        unsafe fn create_ssao_blur_pipeline(
+        unsafe fn create_transparent_pipeline(
             device: &ash::Device,
+            cache: &crate::core::PipelineCache,
             extent: vk::Extent2D,
-            blur_render_pass: vk::RenderPass,
-            descriptor_set_layout: vk::DescriptorSetLayout,
-        ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
-            let vert_shader_code = include_bytes!("../../shaders/ssao_blur.vert.spv");
-            let frag_shader_code = include_bytes!("../../shaders/ssao_blur.frag.spv");
+            render_pass: vk::RenderPass,
+            samples: vk::SampleCountFlags,
+            pipeline_layout: vk::PipelineLayout, // Reuse same layout as graphics pipeline
+        ) -> anyhow::Result<vk::Pipeline> {
+            // Same shaders as the main mesh pipeline, blended by the material's opacity
+            let vert_shader_code = crate::core::shader::load_spirv("mesh.vert.spv", include_bytes!("../../shaders/mesh.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("mesh.frag.spv", include_bytes!("../../shaders/mesh.frag.spv"));
 
-            let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
             let entry_point = CString::new("main")?;
 
@@ -1654,8 +2517,12 @@ impl VulkanRenderer {
 
             let shader_stages = [vert_stage_info, frag_stage_info];
 
-            // No vertex input - fullscreen triangle
-            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+            let binding_description = Vertex::get_binding_description();
+            let attribute_descriptions = Vertex::get_attribute_descriptions();
+
+            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+                .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+                .vertex_attribute_descriptions(&attribute_descriptions);
 
             let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
                 .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
@@ -1684,41 +2551,37 @@ impl VulkanRenderer {
                 .rasterizer_discard_enable(false)
                 .polygon_mode(vk::PolygonMode::FILL)
                 .line_width(1.0)
-                .cull_mode(vk::CullModeFlags::NONE)
+                .cull_mode(vk::CullModeFlags::BACK)
                 .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
                 .depth_bias_enable(false);
 
             let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
                 .sample_shading_enable(false)
-                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+                .rasterization_samples(samples);
 
-            // No depth test for blur pass
+            // Tested against opaque depth, but never written so overlapping glass all shows
             let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
-                .depth_test_enable(false)
-                .depth_write_enable(false);
+                .depth_test_enable(true)
+                .depth_write_enable(false)
+                .depth_compare_op(vk::CompareOp::LESS)
+                .depth_bounds_test_enable(false)
+                .stencil_test_enable(false);
 
+            // Standard "over" blending
             let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
-                .color_write_mask(vk::ColorComponentFlags::R)
-                .blend_enable(false);
+                .color_write_mask(vk::ColorComponentFlags::RGBA)
+                .blend_enable(true)
+                .src_color_blend_factor(vk::BlendFactor::SRC_ALPHA)
+                .dst_color_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                .color_blend_op(vk::BlendOp::ADD)
+                .src_alpha_blend_factor(vk::BlendFactor::ONE)
+                .dst_alpha_blend_factor(vk::BlendFactor::ONE_MINUS_SRC_ALPHA)
+                .alpha_blend_op(vk::BlendOp::ADD);
 
             let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
                 .logic_op_enable(false)
                 .attachments(std::slice::from_ref(&color_blend_attachment));
 
-            let set_layouts = [descriptor_set_layout];
-
-            // Add push constant for blur direction (int = 4 bytes)
-            let push_constant_range = vk::PushConstantRange::default()
-                .stage_flags(vk::ShaderStageFlags::FRAGMENT)
-                .offset(0)
-                .size(std::mem::size_of::<i32>() as u32);
-
-            let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
-                .set_layouts(&set_layouts)
-                .push_constant_ranges(std::slice::from_ref(&push_constant_range));
-
-            let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
-
             let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
                 .stages(&shader_stages)
                 .vertex_input_state(&vertex_input_info)
@@ -1729,57 +2592,576 @@ impl VulkanRenderer {
                 .depth_stencil_state(&depth_stencil)
                 .color_blend_state(&color_blending)
                 .layout(pipeline_layout)
-                .render_pass(blur_render_pass)
+                .render_pass(render_pass)
                 .subpass(0);
 
             let pipelines = device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                cache.handle(),
                 std::slice::from_ref(&pipeline_info),
                 None,
-            ).map_err(|e| anyhow::anyhow!("Failed to create SSAO blur pipeline: {:?}", e.1))?;
+            ).map_err(|e| anyhow::anyhow!("Failed to create transparent mesh pipeline: {:?}", e.1))?;
 
-            device.destroy_shader_module(vert_shader_module, None);
-            device.destroy_shader_module(frag_shader_module, None);
-
-            Ok((pipeline_layout, pipelines[0]))
+            Ok(pipelines[0])
         }
 
-        unsafe fn create_shader_module(
-            device: &ash::Device,
-            code: &[u8],
-        ) -> anyhow::Result<vk::ShaderModule> {
-            let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
-            let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
-            Ok(device.create_shader_module(&create_info, None)?)
-        }
-        
-        unsafe fn create_gizmo_pipeline(
+        unsafe fn create_overdraw_pipeline(
             device: &ash::Device,
+            cache: &crate::core::PipelineCache,
             extent: vk::Extent2D,
             render_pass: vk::RenderPass,
-            descriptor_set_layout: vk::DescriptorSetLayout,
-        ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
-            let vert_shader_code = include_bytes!("../../shaders/gizmo.vert.spv");
-            let frag_shader_code = include_bytes!("../../shaders/gizmo.frag.spv");
+            samples: vk::SampleCountFlags,
+            pipeline_layout: vk::PipelineLayout, // Reuse same layout as graphics pipeline
+        ) -> anyhow::Result<vk::Pipeline> {
+            // Same shaders as the main mesh pipeline; mesh.frag outputs a constant heat value in overdraw mode
+            let vert_shader_code = crate::core::shader::load_spirv("mesh.vert.spv", include_bytes!("../../shaders/mesh.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("mesh.frag.spv", include_bytes!("../../shaders/mesh.frag.spv"));
 
-            let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
             let entry_point = CString::new("main")?;
 
             let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
-            .stage(vk::ShaderStageFlags::VERTEX)
-            .module(vert_shader_module)
-            .name(&entry_point);
+                .stage(vk::ShaderStageFlags::VERTEX)
+                .module(vert_shader_module)
+                .name(&entry_point);
 
             let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
-            .stage(vk::ShaderStageFlags::FRAGMENT)
-            .module(frag_shader_module)
-            .name(&entry_point);
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(frag_shader_module)
+                .name(&entry_point);
 
             let shader_stages = [vert_stage_info, frag_stage_info];
 
-            let binding_desc = Vertex::get_binding_description();
+            let binding_description = Vertex::get_binding_description();
+            let attribute_descriptions = Vertex::get_attribute_descriptions();
+
+            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+                .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+                .vertex_attribute_descriptions(&attribute_descriptions);
+
+            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+                .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+                .primitive_restart_enable(false);
+
+            let viewport = vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: extent.width as f32,
+                height: extent.height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+
+            let scissor = vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent,
+            };
+
+            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+                .viewports(std::slice::from_ref(&viewport))
+                .scissors(std::slice::from_ref(&scissor));
+
+            let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+                .depth_clamp_enable(false)
+                .rasterizer_discard_enable(false)
+                .polygon_mode(vk::PolygonMode::FILL)
+                .line_width(1.0)
+                .cull_mode(vk::CullModeFlags::BACK)
+                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+                .depth_bias_enable(false);
+
+            let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+                .sample_shading_enable(false)
+                .rasterization_samples(samples);
+
+            // No depth test: hidden surfaces count too
+            let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+                .depth_test_enable(false)
+                .depth_write_enable(false)
+                .depth_compare_op(vk::CompareOp::LESS)
+                .depth_bounds_test_enable(false)
+                .stencil_test_enable(false);
+
+            // Additive: each layer drawn over a pixel adds its heat
+            let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+                .color_write_mask(vk::ColorComponentFlags::RGBA)
+                .blend_enable(true)
+                .src_color_blend_factor(vk::BlendFactor::ONE)
+                .dst_color_blend_factor(vk::BlendFactor::ONE)
+                .color_blend_op(vk::BlendOp::ADD)
+                .src_alpha_blend_factor(vk::BlendFactor::ONE)
+                .dst_alpha_blend_factor(vk::BlendFactor::ONE)
+                .alpha_blend_op(vk::BlendOp::ADD);
+
+            let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(std::slice::from_ref(&color_blend_attachment));
+
+            let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+                .stages(&shader_stages)
+                .vertex_input_state(&vertex_input_info)
+                .input_assembly_state(&input_assembly)
+                .viewport_state(&viewport_state)
+                .rasterization_state(&rasterizer)
+                .multisample_state(&multisampling)
+                .depth_stencil_state(&depth_stencil)
+                .color_blend_state(&color_blending)
+                .layout(pipeline_layout)
+                .render_pass(render_pass)
+                .subpass(0);
+
+            let pipelines = device.create_graphics_pipelines(
+                cache.handle(),
+                std::slice::from_ref(&pipeline_info),
+                None,
+            ).map_err(|e| anyhow::anyhow!("Failed to create overdraw mesh pipeline: {:?}", e.1))?;
+
+            Ok(pipelines[0])
+        }
+
+        /// Depth prepass variants of the mesh pipelines (reuse the mesh pipeline layout)
+        /// The depth-only pipelines have no fragment stage; the shaded ones keep the main
+        /// pipelines' state but test with EQUAL and leave depth alone.
+        unsafe fn create_depth_prepass_pipelines(
+            device: &ash::Device,
+            cache: &crate::core::PipelineCache,
+            extent: vk::Extent2D,
+            render_pass: vk::RenderPass,
+            samples: vk::SampleCountFlags,
+            pipeline_layout: vk::PipelineLayout,
+        ) -> anyhow::Result<crate::core::passes::mesh::DepthPrepassPipelines> {
+            use crate::core::passes::mesh::MeshInstanceData;
+
+            let vert_shader_code = crate::core::shader::load_spirv("mesh.vert.spv", include_bytes!("../../shaders/mesh.vert.spv"));
+            let instanced_vert_shader_code = crate::core::shader::load_spirv("mesh_instanced.vert.spv", include_bytes!("../../shaders/mesh_instanced.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("mesh.frag.spv", include_bytes!("../../shaders/mesh.frag.spv"));
+
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let instanced_vert_shader_module = cache.shader_module(device, &instanced_vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+            let entry_point = CString::new("main")?;
+            let entry_point = entry_point.as_c_str();
+
+            let stage = move |stage: vk::ShaderStageFlags, module: vk::ShaderModule| {
+                vk::PipelineShaderStageCreateInfo::default()
+                    .stage(stage)
+                    .module(module)
+                    .name(entry_point)
+            };
+            let vert_stage = stage(vk::ShaderStageFlags::VERTEX, vert_shader_module);
+            let instanced_vert_stage = stage(vk::ShaderStageFlags::VERTEX, instanced_vert_shader_module);
+            let frag_stage = stage(vk::ShaderStageFlags::FRAGMENT, frag_shader_module);
+            let depth_stages = [vert_stage];
+            let depth_instanced_stages = [instanced_vert_stage];
+            let shade_stages = [vert_stage, frag_stage];
+            let shade_instanced_stages = [instanced_vert_stage, frag_stage];
+
+            let binding_description = Vertex::get_binding_description();
+            let attribute_descriptions = Vertex::get_attribute_descriptions();
+            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+                .vertex_binding_descriptions(std::slice::from_ref(&binding_description))
+                .vertex_attribute_descriptions(&attribute_descriptions);
+
+            // Binding 0: per-vertex data, binding 1: per-instance model matrix + material
+            let instanced_binding_descriptions = [
+                Vertex::get_binding_description(),
+                MeshInstanceData::binding_description(),
+            ];
+            let instanced_attribute_descriptions: Vec<vk::VertexInputAttributeDescription> = Vertex::get_attribute_descriptions()
+                .into_iter()
+                .chain(MeshInstanceData::attribute_descriptions())
+                .collect();
+            let instanced_vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
+                .vertex_binding_descriptions(&instanced_binding_descriptions)
+                .vertex_attribute_descriptions(&instanced_attribute_descriptions);
+
+            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+                .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+                .primitive_restart_enable(false);
+
+            let viewport = vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: extent.width as f32,
+                height: extent.height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+
+            let scissor = vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent,
+            };
+
+            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+                .viewports(std::slice::from_ref(&viewport))
+                .scissors(std::slice::from_ref(&scissor));
+
+            // Same fixed-function state as the main mesh pipeline, so both passes rasterize identically
+            let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+                .depth_clamp_enable(false)
+                .rasterizer_discard_enable(false)
+                .polygon_mode(vk::PolygonMode::FILL)
+                .line_width(1.0)
+                .cull_mode(vk::CullModeFlags::BACK)
+                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+                .depth_bias_enable(false);
+
+            let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+                .sample_shading_enable(false)
+                .rasterization_samples(samples);
+
+            let depth_write = vk::PipelineDepthStencilStateCreateInfo::default()
+                .depth_test_enable(true)
+                .depth_write_enable(true)
+                .depth_compare_op(vk::CompareOp::LESS)
+                .depth_bounds_test_enable(false)
+                .stencil_test_enable(false);
+
+            // Only the nearest surface (laid down by the prepass) passes
+            let depth_equal = vk::PipelineDepthStencilStateCreateInfo::default()
+                .depth_test_enable(true)
+                .depth_write_enable(false)
+                .depth_compare_op(vk::CompareOp::EQUAL)
+                .depth_bounds_test_enable(false)
+                .stencil_test_enable(false);
+
+            let no_color_attachment = vk::PipelineColorBlendAttachmentState::default()
+                .color_write_mask(vk::ColorComponentFlags::empty())
+                .blend_enable(false);
+            let no_color = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(std::slice::from_ref(&no_color_attachment));
+
+            let opaque_attachment = vk::PipelineColorBlendAttachmentState::default()
+                .color_write_mask(vk::ColorComponentFlags::RGBA)
+                .blend_enable(false);
+            let opaque = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(std::slice::from_ref(&opaque_attachment));
+
+            // Additive heat, as in the overdraw pipeline
+            let additive_attachment = vk::PipelineColorBlendAttachmentState::default()
+                .color_write_mask(vk::ColorComponentFlags::RGBA)
+                .blend_enable(true)
+                .src_color_blend_factor(vk::BlendFactor::ONE)
+                .dst_color_blend_factor(vk::BlendFactor::ONE)
+                .color_blend_op(vk::BlendOp::ADD)
+                .src_alpha_blend_factor(vk::BlendFactor::ONE)
+                .dst_alpha_blend_factor(vk::BlendFactor::ONE)
+                .alpha_blend_op(vk::BlendOp::ADD);
+            let additive = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(std::slice::from_ref(&additive_attachment));
+
+            // Shared state; each variant sets its shaders, vertex input, depth test and blending
+            let base_info = vk::GraphicsPipelineCreateInfo::default()
+                .input_assembly_state(&input_assembly)
+                .viewport_state(&viewport_state)
+                .rasterization_state(&rasterizer)
+                .multisample_state(&multisampling)
+                .layout(pipeline_layout)
+                .render_pass(render_pass)
+                .subpass(0);
+            let pipeline_infos = [
+                base_info
+                    .stages(&depth_stages)
+                    .vertex_input_state(&vertex_input_info)
+                    .depth_stencil_state(&depth_write)
+                    .color_blend_state(&no_color),
+                base_info
+                    .stages(&depth_instanced_stages)
+                    .vertex_input_state(&instanced_vertex_input_info)
+                    .depth_stencil_state(&depth_write)
+                    .color_blend_state(&no_color),
+                base_info
+                    .stages(&shade_stages)
+                    .vertex_input_state(&vertex_input_info)
+                    .depth_stencil_state(&depth_equal)
+                    .color_blend_state(&opaque),
+                base_info
+                    .stages(&shade_instanced_stages)
+                    .vertex_input_state(&instanced_vertex_input_info)
+                    .depth_stencil_state(&depth_equal)
+                    .color_blend_state(&opaque),
+                base_info
+                    .stages(&shade_stages)
+                    .vertex_input_state(&vertex_input_info)
+                    .depth_stencil_state(&depth_equal)
+                    .color_blend_state(&additive),
+            ];
+
+            let pipelines = device
+                .create_graphics_pipelines(cache.handle(), &pipeline_infos, None)
+                .map_err(|(created, e)| {
+                    // Creation can partly succeed; don't leak the pipelines that were built
+                    for pipeline in created.into_iter().filter(|pipeline| *pipeline != vk::Pipeline::null()) {
+                        device.destroy_pipeline(pipeline, None);
+                    }
+                    anyhow::anyhow!("Failed to create depth prepass pipelines: {:?}", e)
+                })?;
+
+            Ok(crate::core::passes::mesh::DepthPrepassPipelines {
+                depth: pipelines[0],
+                depth_instanced: pipelines[1],
+                shade: pipelines[2],
+                shade_instanced: pipelines[3],
+                overdraw: pipelines[4],
+            })
+        }
+
+        unsafe fn create_ssao_pipeline(
+            device: &ash::Device,
+            cache: &crate::core::PipelineCache,
+            extent: vk::Extent2D,
+            ssao_render_pass: vk::RenderPass,
+            descriptor_set_layout: vk::DescriptorSetLayout,
+        ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
+            let vert_shader_code = crate::core::shader::load_spirv("ssao.vert.spv", include_bytes!("../../shaders/ssao.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("ssao.frag.spv", include_bytes!("../../shaders/ssao.frag.spv"));
+
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+            let entry_point = CString::new("main")?;
+
+            let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::VERTEX)
+                .module(vert_shader_module)
+                .name(&entry_point);
+
+            let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(frag_shader_module)
+                .name(&entry_point);
+
+            let shader_stages = [vert_stage_info, frag_stage_info];
+
+            // No vertex input - fullscreen triangle generated in vertex shader
+            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+                .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+                .primitive_restart_enable(false);
+
+            let viewport = vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: extent.width as f32,
+                height: extent.height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+
+            let scissor = vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent,
+            };
+
+            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+                .viewports(std::slice::from_ref(&viewport))
+                .scissors(std::slice::from_ref(&scissor));
+
+            let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+                .depth_clamp_enable(false)
+                .rasterizer_discard_enable(false)
+                .polygon_mode(vk::PolygonMode::FILL)
+                .line_width(1.0)
+                .cull_mode(vk::CullModeFlags::NONE)
+                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+                .depth_bias_enable(false);
+
+            let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+                .sample_shading_enable(false)
+                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+            // No depth test for SSAO pass
+            let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+                .depth_test_enable(false)
+                .depth_write_enable(false);
+
+            let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+                .color_write_mask(vk::ColorComponentFlags::R) // Only write red channel
+                .blend_enable(false);
+
+            let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(std::slice::from_ref(&color_blend_attachment));
+
+            let set_layouts = [descriptor_set_layout];
+
+            let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+                .set_layouts(&set_layouts);
+
+            let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+            let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+                .stages(&shader_stages)
+                .vertex_input_state(&vertex_input_info)
+                .input_assembly_state(&input_assembly)
+                .viewport_state(&viewport_state)
+                .rasterization_state(&rasterizer)
+                .multisample_state(&multisampling)
+                .depth_stencil_state(&depth_stencil)
+                .color_blend_state(&color_blending)
+                .layout(pipeline_layout)
+                .render_pass(ssao_render_pass)
+                .subpass(0);
+
+            let pipelines = device.create_graphics_pipelines(
+                cache.handle(),
+                std::slice::from_ref(&pipeline_info),
+                None,
+            ).map_err(|e| anyhow::anyhow!("Failed to create SSAO pipeline: {:?}", e.1))?;
+
+            Ok((pipeline_layout, pipelines[0]))
+        }
+
+        unsafe fn create_ssao_blur_pipeline(
+            device: &ash::Device,
+            cache: &crate::core::PipelineCache,
+            extent: vk::Extent2D,
+            blur_render_pass: vk::RenderPass,
+            descriptor_set_layout: vk::DescriptorSetLayout,
+        ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
+            let vert_shader_code = crate::core::shader::load_spirv("ssao_blur.vert.spv", include_bytes!("../../shaders/ssao_blur.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("ssao_blur.frag.spv", include_bytes!("../../shaders/ssao_blur.frag.spv"));
+
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+            let entry_point = CString::new("main")?;
+
+            let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::VERTEX)
+                .module(vert_shader_module)
+                .name(&entry_point);
+
+            let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(frag_shader_module)
+                .name(&entry_point);
+
+            let shader_stages = [vert_stage_info, frag_stage_info];
+
+            // No vertex input - fullscreen triangle
+            let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default();
+
+            let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
+                .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+                .primitive_restart_enable(false);
+
+            let viewport = vk::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: extent.width as f32,
+                height: extent.height as f32,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+
+            let scissor = vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent,
+            };
+
+            let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+                .viewports(std::slice::from_ref(&viewport))
+                .scissors(std::slice::from_ref(&scissor));
+
+            let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+                .depth_clamp_enable(false)
+                .rasterizer_discard_enable(false)
+                .polygon_mode(vk::PolygonMode::FILL)
+                .line_width(1.0)
+                .cull_mode(vk::CullModeFlags::NONE)
+                .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+                .depth_bias_enable(false);
+
+            let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+                .sample_shading_enable(false)
+                .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+            // No depth test for blur pass
+            let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+                .depth_test_enable(false)
+                .depth_write_enable(false);
+
+            let color_blend_attachment = vk::PipelineColorBlendAttachmentState::default()
+                .color_write_mask(vk::ColorComponentFlags::R)
+                .blend_enable(false);
+
+            let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
+                .logic_op_enable(false)
+                .attachments(std::slice::from_ref(&color_blend_attachment));
+
+            let set_layouts = [descriptor_set_layout];
+
+            // Add push constant for blur direction (int = 4 bytes)
+            let push_constant_range = vk::PushConstantRange::default()
+                .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+                .offset(0)
+                .size(std::mem::size_of::<i32>() as u32);
+
+            let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
+                .set_layouts(&set_layouts)
+                .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+
+            let pipeline_layout = device.create_pipeline_layout(&pipeline_layout_info, None)?;
+
+            let pipeline_info = vk::GraphicsPipelineCreateInfo::default()
+                .stages(&shader_stages)
+                .vertex_input_state(&vertex_input_info)
+                .input_assembly_state(&input_assembly)
+                .viewport_state(&viewport_state)
+                .rasterization_state(&rasterizer)
+                .multisample_state(&multisampling)
+                .depth_stencil_state(&depth_stencil)
+                .color_blend_state(&color_blending)
+                .layout(pipeline_layout)
+                .render_pass(blur_render_pass)
+                .subpass(0);
+
+            let pipelines = device.create_graphics_pipelines(
+                cache.handle(),
+                std::slice::from_ref(&pipeline_info),
+                None,
+            ).map_err(|e| anyhow::anyhow!("Failed to create SSAO blur pipeline: {:?}", e.1))?;
+
+            Ok((pipeline_layout, pipelines[0]))
+        }
+
+        unsafe fn create_gizmo_pipeline(
+            device: &ash::Device,
+            cache: &crate::core::PipelineCache,
+            extent: vk::Extent2D,
+            render_pass: vk::RenderPass,
+            samples: vk::SampleCountFlags,
+            descriptor_set_layout: vk::DescriptorSetLayout,
+        ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
+            let vert_shader_code = crate::core::shader::load_spirv("gizmo.vert.spv", include_bytes!("../../shaders/gizmo.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("gizmo.frag.spv", include_bytes!("../../shaders/gizmo.frag.spv"));
+
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
+
+            let entry_point = CString::new("main")?;
+
+            let vert_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_shader_module)
+            .name(&entry_point);
+
+            let frag_stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_shader_module)
+            .name(&entry_point);
+
+            let shader_stages = [vert_stage_info, frag_stage_info];
+
+            let binding_desc = Vertex::get_binding_description();
             let attribute_desc = Vertex::get_attribute_descriptions();
 
             let vertex_input_info = vk::PipelineVertexInputStateCreateInfo::default()
@@ -1819,7 +3201,7 @@ impl VulkanRenderer {
 
             let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
-            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+            .rasterization_samples(samples);
 
             // Enable depth test so rotation rings sort correctly, but use ALWAYS to render on top of scene
             let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
@@ -1837,11 +3219,11 @@ impl VulkanRenderer {
 
             let set_layouts = [descriptor_set_layout];
 
-            // Add push constant for hovered axis
+            // Add push constants for the hovered axis and the locked axes mask
             let push_constant_range = vk::PushConstantRange::default()
                 .stage_flags(vk::ShaderStageFlags::VERTEX)
                 .offset(0)
-                .size(std::mem::size_of::<i32>() as u32);
+                .size(std::mem::size_of::<[i32; 2]>() as u32);
 
             let pipeline_layout_info = vk::PipelineLayoutCreateInfo::default()
             .set_layouts(&set_layouts)
@@ -1863,28 +3245,26 @@ impl VulkanRenderer {
             .subpass(0);
 
             let pipelines = device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                cache.handle(),
                 std::slice::from_ref(&pipeline_info),
                 None,
             ).map_err(|e| anyhow::anyhow!("Failed to create gizmo pipeline: {:?}", e.1))?;
 
-            device.destroy_shader_module(vert_shader_module, None);
-            device.destroy_shader_module(frag_shader_module, None);
-
             Ok((pipeline_layout, pipelines[0]))
         }
 
         unsafe fn create_star_pipeline(
             device: &ash::Device,
+            cache: &crate::core::PipelineCache,
             extent: vk::Extent2D,
             render_pass: vk::RenderPass,
             descriptor_set_layout: vk::DescriptorSetLayout,
         ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
-            let vert_shader_code = include_bytes!("../../shaders/star.vert.spv");
-            let frag_shader_code = include_bytes!("../../shaders/star.frag.spv");
+            let vert_shader_code = crate::core::shader::load_spirv("star.vert.spv", include_bytes!("../../shaders/star.vert.spv"));
+            let frag_shader_code = crate::core::shader::load_spirv("star.frag.spv", include_bytes!("../../shaders/star.frag.spv"));
 
-            let vert_shader_module = Self::create_shader_module(device, vert_shader_code)?;
-            let frag_shader_module = Self::create_shader_module(device, frag_shader_code)?;
+            let vert_shader_module = cache.shader_module(device, &vert_shader_code)?;
+            let frag_shader_module = cache.shader_module(device, &frag_shader_code)?;
 
             let entry_point = CString::new("main")?;
 
@@ -2004,14 +3384,11 @@ impl VulkanRenderer {
                 .subpass(0);
 
             let pipelines = device.create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                cache.handle(),
                 std::slice::from_ref(&pipeline_info),
                 None,
             ).map_err(|e| anyhow::anyhow!("Failed to create star pipeline: {:?}", e.1))?;
 
-            device.destroy_shader_module(vert_shader_module, None);
-            device.destroy_shader_module(frag_shader_module, None);
-
             Ok((pipeline_layout, pipelines[0]))
         }
 
@@ -2020,13 +3397,19 @@ impl VulkanRenderer {
             device: &ash::Device,
             image_views: &[vk::ImageView],
             depth_image_view: vk::ImageView,
+            msaa_targets: &MsaaTargets,
             render_pass: vk::RenderPass,
             extent: vk::Extent2D,
         ) -> anyhow::Result<Vec<vk::Framebuffer>> {
             image_views
             .iter()
             .map(|&image_view| {
-                let attachments = [image_view, depth_image_view];
+                // With MSAA the HDR and depth images are the resolve targets
+                let attachments = if msaa_targets.is_enabled() {
+                    vec![msaa_targets.color_view, msaa_targets.depth_view, image_view, depth_image_view]
+                } else {
+                    vec![image_view, depth_image_view]
+                };
                 let create_info = vk::FramebufferCreateInfo::default()
                 .render_pass(render_pass)
                 .attachments(&attachments)
@@ -2038,15 +3421,37 @@ impl VulkanRenderer {
             })
             .collect()
         }
+
+        /// Color-only framebuffers over the swapchain images (tonemap output + ImGui overlay)
+        unsafe fn create_swapchain_framebuffers(
+            device: &ash::Device,
+            image_views: &[vk::ImageView],
+            render_pass: vk::RenderPass,
+            extent: vk::Extent2D,
+        ) -> anyhow::Result<Vec<vk::Framebuffer>> {
+            image_views
+            .iter()
+            .map(|&image_view| {
+                let attachments = [image_view];
+                let create_info = vk::FramebufferCreateInfo::default()
+                .render_pass(render_pass)
+                .attachments(&attachments)
+                .width(extent.width)
+                .height(extent.height)
+                .layers(1);
+                
+                device.create_framebuffer(&create_info, None).map_err(|e| anyhow::anyhow!("Failed to create swapchain framebuffer: {}", e))
+            })
+            .collect()
+        }
         
         unsafe fn create_command_pool(
             instance: &ash::Instance,
             physical_device: vk::PhysicalDevice,
             device: &ash::Device,
-            surface_loader: &ash::khr::surface::Instance,
-            surface: vk::SurfaceKHR,
+            surface: Option<(&ash::khr::surface::Instance, vk::SurfaceKHR)>,
         ) -> anyhow::Result<vk::CommandPool> {
-            let indices = Self::find_queue_families(instance, physical_device, surface_loader, surface)?;
+            let indices = Self::find_queue_families(instance, physical_device, surface)?;
             
             let create_info = vk::CommandPoolCreateInfo::default()
             .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
@@ -2155,13 +3560,48 @@ impl VulkanRenderer {
 
             println!("Loading custom mesh: {}", path);
 
-            // Load mesh from file
-            let mesh = Mesh::from_obj(path)?;
+            // Load mesh from file, picking the importer by extension
+            let extension = std::path::Path::new(path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext.to_ascii_lowercase());
+            let mesh = match extension.as_deref() {
+                Some("gltf") | Some("glb") => Mesh::from_gltf(path)?,
+                _ => Mesh::from_obj(path)?,
+            };
 
             // Calculate bounds before moving mesh
             let bounds = mesh.calculate_bounds();
 
-            // Create vertex buffer
+            // Heavy meshes get simplified levels for distance-based LOD, stored next to the
+            // base mesh. Each level is decimated from the previous one, and generation stops
+            // once the simplifier can't take off at least a tenth of the triangles.
+            let base_triangles = mesh.indices.len() / 3;
+            let mut lods: Vec<Mesh> = Vec::new();
+            if base_triangles >= crate::game::LodConfig::MIN_TRIANGLES {
+                for ratio in crate::game::LodConfig::RATIOS {
+                    let source = lods.last().unwrap_or(&mesh);
+                    let source_triangles = source.indices.len() / 3;
+                    let lod = source.simplify(ratio * base_triangles as f32 / source_triangles as f32);
+                    if lod.indices.len() / 3 * 10 > source_triangles * 9 {
+                        break;
+                    }
+                    lods.push(lod);
+                }
+            }
+            for (i, lod) in lods.into_iter().enumerate() {
+                crate::console::info(format!("  LOD {}: {} -> {} triangles", i + 1, base_triangles, lod.indices.len() / 3));
+                self.upload_custom_mesh(crate::mesh::lod_key(path, i + 1), lod)?;
+            }
+
+            self.upload_custom_mesh(path.to_string(), mesh)?;
+
+            crate::console::info(format!("Custom mesh loaded successfully: {} (bounds: {:?} to {:?})", path, bounds.0, bounds.1));
+            Ok(bounds)
+        }
+
+        /// Create GPU buffers for `mesh` and store it in the custom mesh registry under `key`
+        unsafe fn upload_custom_mesh(&mut self, key: String, mesh: Mesh) -> anyhow::Result<()> {
             let (vertex_buffer, vertex_memory) = Self::create_vertex_buffer(
                 &self.instance,
                 self.physical_device,
@@ -2171,7 +3611,6 @@ impl VulkanRenderer {
                 &mesh.vertices,
             )?;
 
-            // Create index buffer
             let (index_buffer, index_memory) = Self::create_index_buffer(
                 &self.instance,
                 self.physical_device,
@@ -2181,14 +3620,38 @@ impl VulkanRenderer {
                 &mesh.indices,
             )?;
 
-            // Store in registry
-            self.custom_meshes.insert(
-                path.to_string(),
-                (mesh, vertex_buffer, vertex_memory, index_buffer, index_memory),
-            );
+            self.custom_meshes.insert(key, (mesh, vertex_buffer, vertex_memory, index_buffer, index_memory));
+            Ok(())
+        }
 
-            println!("Custom mesh loaded successfully: {} (bounds: {:?} to {:?})", path, bounds.0, bounds.1);
-            Ok(bounds)
+        /// Recompute the normals of a loaded custom mesh and its LOD levels (see
+        /// `Mesh::recompute_normals`) and replace their GPU buffers
+        unsafe fn recompute_custom_mesh_normals(&mut self, path: &str, options: crate::game::NormalRecomputeOptions) -> anyhow::Result<()> {
+            let keys: Vec<String> = (0..)
+                .map(|level| crate::mesh::lod_key(path, level))
+                .take_while(|key| self.custom_meshes.contains_key(key))
+                .collect();
+            if keys.is_empty() {
+                anyhow::bail!("mesh isn't loaded");
+            }
+
+            // Frames in flight may still read the old buffers
+            self.device.device_wait_idle()?;
+            for key in keys {
+                let Some((mut mesh, vertex_buffer, vertex_memory, index_buffer, index_memory)) = self.custom_meshes.remove(&key) else {
+                    continue;
+                };
+                self.device.destroy_buffer(vertex_buffer, None);
+                self.device.free_memory(vertex_memory, None);
+                self.device.destroy_buffer(index_buffer, None);
+                self.device.free_memory(index_memory, None);
+
+                mesh.recompute_normals(options.smooth, options.smoothing_angle.to_radians());
+                self.upload_custom_mesh(key, mesh)?;
+            }
+
+            self.thumbnail_pass.redraw(&crate::core::passes::thumbnail::ThumbnailKey::Mesh(path.to_string()));
+            Ok(())
         }
 
         unsafe fn create_uniform_buffers(
@@ -2217,6 +3680,34 @@ impl VulkanRenderer {
             
             Ok((buffers, memories))
         }
+        /// Host-visible storage buffers holding up to MAX_POINT_LIGHTS lights, one per frame in flight
+        unsafe fn create_point_light_buffers(
+            instance: &ash::Instance,
+            physical_device: vk::PhysicalDevice,
+            device: &ash::Device,
+            count: usize,
+        ) -> anyhow::Result<(Vec<vk::Buffer>, Vec<vk::DeviceMemory>)> {
+            let buffer_size = (MAX_POINT_LIGHTS * std::mem::size_of::<PointLightData>()) as vk::DeviceSize;
+
+            let mut buffers = vec![];
+            let mut memories = vec![];
+
+            for _ in 0..count {
+                let (buffer, memory) = Self::create_buffer(
+                    instance,
+                    physical_device,
+                    device,
+                    buffer_size,
+                    vk::BufferUsageFlags::STORAGE_BUFFER,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                )?;
+                buffers.push(buffer);
+                memories.push(memory);
+            }
+
+            Ok((buffers, memories))
+        }
+
         unsafe fn create_star_uniform_buffers(
             instance: &ash::Instance,
             physical_device: vk::PhysicalDevice,
@@ -2373,8 +3864,12 @@ impl VulkanRenderer {
                 vk::DescriptorPoolSize::default()
                     .ty(vk::DescriptorType::UNIFORM_BUFFER)
                     .descriptor_count(count as u32),
+                // SSAO texture, shadow map, the two environment maps and the SSR history per set
                 vk::DescriptorPoolSize::default()
                     .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .descriptor_count(count as u32 * 6),
+                vk::DescriptorPoolSize::default()
+                    .ty(vk::DescriptorType::STORAGE_BUFFER)
                     .descriptor_count(count as u32),
             ];
 
@@ -2389,8 +3884,11 @@ impl VulkanRenderer {
             pool: vk::DescriptorPool,
             layout: vk::DescriptorSetLayout,
             buffers: &[vk::Buffer],
+            point_light_buffers: &[vk::Buffer],
             ssao_image_view: vk::ImageView,
             ssao_sampler: vk::Sampler,
+            shadow_image_view: vk::ImageView,
+            shadow_sampler: vk::Sampler,
             count: usize,
         ) -> anyhow::Result<Vec<vk::DescriptorSet>> {
             let layouts = vec![layout; count];
@@ -2411,6 +3909,16 @@ impl VulkanRenderer {
                     .image_view(ssao_image_view)
                     .sampler(ssao_sampler);
 
+                let shadow_image_info = vk::DescriptorImageInfo::default()
+                    .image_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL)
+                    .image_view(shadow_image_view)
+                    .sampler(shadow_sampler);
+
+                let point_light_info = vk::DescriptorBufferInfo::default()
+                    .buffer(point_light_buffers[i])
+                    .offset(0)
+                    .range(vk::WHOLE_SIZE);
+
                 let descriptor_writes = [
                     vk::WriteDescriptorSet::default()
                         .dst_set(descriptor_set)
@@ -2424,6 +3932,18 @@ impl VulkanRenderer {
                         .dst_array_element(0)
                         .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
                         .image_info(std::slice::from_ref(&image_info)),
+                    vk::WriteDescriptorSet::default()
+                        .dst_set(descriptor_set)
+                        .dst_binding(2)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                        .image_info(std::slice::from_ref(&shadow_image_info)),
+                    vk::WriteDescriptorSet::default()
+                        .dst_set(descriptor_set)
+                        .dst_binding(3)
+                        .dst_array_element(0)
+                        .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                        .buffer_info(std::slice::from_ref(&point_light_info)),
                 ];
 
                 device.update_descriptor_sets(&descriptor_writes, &[]);
@@ -2431,6 +3951,25 @@ impl VulkanRenderer {
 
             Ok(descriptor_sets)
         }
+
+        /// Point binding 2 of the main and gizmo descriptor sets at the current shadow map
+        unsafe fn update_shadow_descriptors(&self) {
+            let shadow_image_info = vk::DescriptorImageInfo::default()
+                .image_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL)
+                .image_view(self.shadow_pass.image_view())
+                .sampler(self.shadow_pass.sampler());
+
+            for &descriptor_set in self.descriptor_sets.iter().chain(self.gizmo_descriptor_sets.iter()) {
+                let descriptor_write = vk::WriteDescriptorSet::default()
+                    .dst_set(descriptor_set)
+                    .dst_binding(2)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&shadow_image_info));
+
+                self.device.update_descriptor_sets(std::slice::from_ref(&descriptor_write), &[]);
+            }
+        }
         unsafe fn create_star_descriptor_pool(
             device: &ash::Device,
             count: usize,
@@ -2522,7 +4061,7 @@ impl VulkanRenderer {
             device: &ash::Device,
             extent: vk::Extent2D,
         ) -> anyhow::Result<(vk::Image, vk::DeviceMemory, vk::ImageView)> {
-            let format = vk::Format::D32_SFLOAT;
+            let format = DEPTH_FORMAT;
             
             let image_info = vk::ImageCreateInfo::default()
             .image_type(vk::ImageType::TYPE_2D)
@@ -2536,7 +4075,7 @@ impl VulkanRenderer {
             .format(format)
             .tiling(vk::ImageTiling::OPTIMAL)
             .initial_layout(vk::ImageLayout::UNDEFINED)
-            .usage(vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .usage(vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_SRC)
             .sharing_mode(vk::SharingMode::EXCLUSIVE)
             .samples(vk::SampleCountFlags::TYPE_1);
             
@@ -2572,6 +4111,70 @@ impl VulkanRenderer {
             Ok((depth_image, depth_image_memory, depth_image_view))
         }
 
+        /// Create one HDR color target per swapchain image (rendered to, then sampled by post-processing)
+        unsafe fn create_hdr_resources(
+            instance: &ash::Instance,
+            physical_device: vk::PhysicalDevice,
+            device: &ash::Device,
+            extent: vk::Extent2D,
+            count: usize,
+        ) -> anyhow::Result<(Vec<vk::Image>, Vec<vk::DeviceMemory>, Vec<vk::ImageView>)> {
+            let mut images = Vec::with_capacity(count);
+            let mut memories = Vec::with_capacity(count);
+            let mut views = Vec::with_capacity(count);
+
+            for _ in 0..count {
+                let image_info = vk::ImageCreateInfo::default()
+                .image_type(vk::ImageType::TYPE_2D)
+                .extent(vk::Extent3D {
+                    width: extent.width,
+                    height: extent.height,
+                    depth: 1,
+                })
+                .mip_levels(1)
+                .array_layers(1)
+                .format(HDR_FORMAT)
+                .tiling(vk::ImageTiling::OPTIMAL)
+                .initial_layout(vk::ImageLayout::UNDEFINED)
+                .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_SRC)
+                .sharing_mode(vk::SharingMode::EXCLUSIVE)
+                .samples(vk::SampleCountFlags::TYPE_1);
+
+                let image = device.create_image(&image_info, None)?;
+                let mem_requirements = device.get_image_memory_requirements(image);
+
+                let alloc_info = vk::MemoryAllocateInfo::default()
+                .allocation_size(mem_requirements.size)
+                .memory_type_index(Self::find_memory_type(
+                    instance,
+                    physical_device,
+                    mem_requirements.memory_type_bits,
+                    vk::MemoryPropertyFlags::DEVICE_LOCAL,
+                )?);
+
+                let memory = device.allocate_memory(&alloc_info, None)?;
+                device.bind_image_memory(image, memory, 0)?;
+
+                let view_info = vk::ImageViewCreateInfo::default()
+                .image(image)
+                .view_type(vk::ImageViewType::TYPE_2D)
+                .format(HDR_FORMAT)
+                .subresource_range(vk::ImageSubresourceRange {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    base_mip_level: 0,
+                    level_count: 1,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                });
+
+                images.push(image);
+                memories.push(memory);
+                views.push(device.create_image_view(&view_info, None)?);
+            }
+
+            Ok((images, memories, views))
+        }
+
         unsafe fn create_depth_sampler(device: &ash::Device) -> anyhow::Result<vk::Sampler> {
             let sampler_info = vk::SamplerCreateInfo::default()
                 .mag_filter(vk::Filter::NEAREST)
@@ -2862,45 +4465,98 @@ impl VulkanRenderer {
         unsafe fn update_uniform_buffer(&mut self, image_index: usize, game: &crate::game::Game) -> anyhow::Result<()> {
             let view = game.get_view_matrix();
 
+            // Render space jumps when the camera origin rebases; last frame's reflection
+            // history was captured in the old space and can't be reprojected
+            if game.ecs_world.camera_origin != self.history_origin {
+                self.history_origin = game.ecs_world.camera_origin;
+                self.reflection_history.invalidate();
+            }
+
             // CRITICAL: Use the EXACT SAME projection matrix as gizmo and picking!
             let aspect = self.swapchain_extent.width as f32 / self.swapchain_extent.height as f32;
             let proj = game.camera.projection_matrix(aspect);
 
-            // Get light direction from scene object rotation, or use default
-            let dir_light_direction = if let Some(light_id) = game.scene.find_by_type(crate::scene::ObjectType::DirectionalLight) {
-                if let Some(light_obj) = game.scene.get_object(light_id) {
-                    // Light arrow points down -Y, rotate it by the object's rotation
-                    let dir = light_obj.transform.rotation * glam::Vec3::NEG_Y;
-                    dir.normalize()
-                } else {
-                    self.directional_light.direction
-                }
-            } else {
-                self.directional_light.direction
-            };
+            // Directional lights from the scene; the first one also fills the legacy
+            // single-light fields (shadows and ambient use it)
+            // Picked light and fog colors are sRGB; lighting happens in linear space
+            let color = |srgb: Vec3| game.tonemap_config.shader_color(srgb);
+            let directional_lights = game.get_directional_lights();
+            let primary_light = directional_lights[0];
+            let mut dir_lights = [<DirectionalLightData as bytemuck::Zeroable>::zeroed(); MAX_DIRECTIONAL_LIGHTS];
+            for (slot, light) in dir_lights.iter_mut().zip(&directional_lights) {
+                *slot = DirectionalLightData {
+                    direction: light.direction,
+                    intensity: light.intensity,
+                    color: color(light.color),
+                    _pad: 0.0,
+                };
+            }
+
+            // Gather point lights from the scene into this frame's storage buffer
+            // (extras beyond MAX_POINT_LIGHTS are ignored)
+            let scene_point_lights = game.get_point_lights();
+            let point_light_count = scene_point_lights.len().min(MAX_POINT_LIGHTS);
+            if point_light_count > 0 {
+                let point_lights: Vec<PointLightData> = scene_point_lights[..point_light_count]
+                    .iter()
+                    .map(|light| PointLightData {
+                        position: game.render_position(light.position),
+                        radius: light.radius,
+                        color: color(light.color),
+                        intensity: light.intensity,
+                    })
+                    .collect();
+                let bytes: &[u8] = bytemuck::cast_slice(&point_lights);
+                let data = self.device.map_memory(
+                    self.point_light_buffers_memory[image_index],
+                    0,
+                    bytes.len() as vk::DeviceSize,
+                    vk::MemoryMapFlags::empty(),
+                )?;
+                std::ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, bytes.len());
+                self.device.unmap_memory(self.point_light_buffers_memory[image_index]);
+            }
 
+            let (history_view, history_proj) = self.reflection_history.camera();
             let ubo = UniformBufferObject {
                 view,
                 proj,
                 view_pos: game.get_camera_position(),
                 _padding: 0.0,
-                dir_light_direction,
+                dir_light_direction: primary_light.direction,
                 _padding2: 0.0,
-                dir_light_color: game.directional_light.color,
-                dir_light_intensity: game.directional_light.intensity,
-                dir_light_shadow_color: game.directional_light.shadow_color,
+                dir_light_color: color(primary_light.color),
+                dir_light_intensity: primary_light.intensity,
+                dir_light_shadow_color: color(primary_light.shadow_color),
                 star_density: game.skybox_config.star_density,
                 star_brightness: game.skybox_config.star_brightness,
-                _pad0: 0.0,
-                _pad1: 0.0,
-                _pad2: 0.0,
+                ssao_intensity: game.ssao_config.ao_intensity,
+                ssao_debug: if game.ssao_config.show_ao_only { 1 } else { 0 },
+                log_depth: game.camera.log_depth_coefficient(),
                 nebula_primary_color: game.skybox_config.nebula_primary_color,
                 nebula_intensity: game.skybox_config.nebula_intensity,
                 nebula_secondary_color: game.skybox_config.nebula_secondary_color,
                 background_brightness: game.skybox_config.background_brightness,
-                point_light_count: self.point_lights.len().min(MAX_POINT_LIGHTS) as u32,
+                point_light_count: point_light_count as u32,
                 ssao_enabled: if game.ssao_config.enabled { 1 } else { 0 },
-                _padding3: [0; 2],
+                shadow_enabled: if game.shadow_config.enabled { 1 } else { 0 },
+                shadow_bias: game.shadow_config.depth_bias,
+                light_space_matrix: self.shadow_pass.light_space_matrix(),
+                fog_color: color(game.fog_config.color),
+                fog_density: game.fog_config.density,
+                fog_mode: game.fog_config.shader_mode(),
+                fog_start: game.fog_config.start,
+                fog_end: game.fog_config.end,
+                debug_view: game.debug_view.shader_index(),
+                dir_light_count: directional_lights.len().min(MAX_DIRECTIONAL_LIGHTS) as u32,
+                _dir_light_pad: [0; 3],
+                dir_lights,
+                history_view,
+                history_proj,
+                ssr_enabled: (game.ssr_config.enabled && self.reflection_history.is_valid()) as u32,
+                ssr_max_steps: game.ssr_config.max_steps,
+                ssr_thickness: game.ssr_config.thickness,
+                _ssr_pad: 0.0,
             };
             
             let data = self.device.map_memory(
@@ -2915,6 +4571,49 @@ impl VulkanRenderer {
             Ok(())
         }
 
+        /// Mesh drawn for `object_type` (None until a custom mesh is loaded)
+        fn object_mesh(&self, object_type: &crate::scene::ObjectType) -> Option<&Mesh> {
+            use crate::scene::ObjectType;
+            match object_type {
+                ObjectType::Cube => Some(&self.mesh),
+                ObjectType::Primitive(shape) => self.primitive_meshes.get(shape).map(|(mesh, ..)| mesh),
+                ObjectType::Mesh(path) | ObjectType::Unlit(path) => self.custom_meshes.get(path).map(|(mesh, ..)| mesh),
+                _ => None,
+            }
+        }
+
+        /// Local-space AABB of the mesh drawn for `object_type` (None until a custom mesh is loaded)
+        fn object_mesh_bounds(&self, object_type: &crate::scene::ObjectType) -> Option<(Vec3, Vec3)> {
+            self.object_mesh(object_type).map(Mesh::calculate_bounds)
+        }
+
+        /// Vertex/triangle counts and extent of the mesh drawn for `object_type`
+        /// (None for non-mesh objects and custom meshes that haven't loaded yet)
+        pub fn mesh_stats(&self, object_type: &crate::scene::ObjectType) -> Option<MeshStats> {
+            self.object_mesh(object_type).map(Mesh::stats)
+        }
+
+        /// Vertex + index buffer memory of the loaded meshes (sizes of the uploaded data)
+        fn mesh_memory(&self) -> crate::core::MeshMemory {
+            crate::core::MeshMemory {
+                custom_meshes: self.custom_meshes.len(),
+                custom_bytes: self.custom_meshes.values().map(|(mesh, ..)| mesh.buffer_bytes()).sum(),
+                builtin_bytes: self.cube_mesh.buffer_bytes()
+                    + self.primitive_meshes.values().map(|(mesh, ..)| mesh.buffer_bytes()).sum::<u64>(),
+            }
+        }
+
+        /// Triangles in every shown mesh object, whether or not it is culled this frame
+        fn scene_triangle_count(&self, game: &crate::game::Game) -> u64 {
+            game.scene
+                .objects()
+                .values()
+                .filter(|obj| game.scene.is_shown(obj))
+                .filter_map(|obj| self.object_mesh(&obj.object_type))
+                .map(|mesh| (mesh.indices.len() / 3) as u64)
+                .sum()
+        }
+
         unsafe fn update_gizmo_uniform_buffer(&mut self, image_index: usize, game: &crate::game::Game) -> anyhow::Result<()> {
             let view = game.get_view_matrix();
 
@@ -2923,34 +4622,20 @@ impl VulkanRenderer {
             let aspect = self.swapchain_extent.width as f32 / self.swapchain_extent.height as f32;
             let proj = game.camera.projection_matrix(aspect);
 
-            // Gizmo transform based on mode:
-            // - Translate: world-space orientation (easier to use)
-            // - Rotate/Scale: object-space orientation (rotate with object)
+            // Gizmo orientation follows the selected space (world or local);
+            // scale handles always use object-space orientation.
             // Scale the gizmo based on distance from camera to maintain constant screen size
-            let model = if let Some(obj) = game.scene.selected_object() {
-                // Calculate distance from camera to object
-                let distance = (obj.transform.position - game.camera.position()).length();
-                // Scale factor: make gizmo size proportional to distance (0.15 is a tuning factor)
-                let gizmo_scale = distance * 0.15;
-
-                match game.gizmo_state.mode {
-                    crate::gizmo::GizmoMode::Translate => {
-                        // World-space: only position + uniform scale
-                        Mat4::from_scale_rotation_translation(
-                            Vec3::splat(gizmo_scale),
-                            Quat::IDENTITY,
-                            obj.transform.position
-                        )
-                    }
-                    crate::gizmo::GizmoMode::Rotate | crate::gizmo::GizmoMode::Scale => {
-                        // Object-space: position + rotation + uniform scale
-                        Mat4::from_scale_rotation_translation(
-                            Vec3::splat(gizmo_scale),
-                            obj.transform.rotation,
-                            obj.transform.position
-                        )
-                    }
-                }
+            // Multi-selections place the gizmo at the selection centroid.
+            let model = if let Some(pivot) = game.gizmo_pivot() {
+                // Same scale as picking: proportional to distance (or ortho size), or a
+                // fixed pixel size, as set in the Viewport panel
+                let gizmo_scale = game.gizmo_config.scale_at(&game.camera, pivot.position, self.viewport_size().1);
+
+                Mat4::from_scale_rotation_translation(
+                    Vec3::splat(gizmo_scale),
+                    game.gizmo_state.axis_orientation(pivot.rotation),
+                    game.render_position(pivot.position),
+                )
             } else {
                 Mat4::IDENTITY
             };
@@ -2970,6 +4655,23 @@ impl VulkanRenderer {
             std::ptr::copy_nonoverlapping(&ubo, data as *mut GizmoUniformBufferObject, 1);
             self.device.unmap_memory(self.gizmo_uniform_buffers_memory[image_index]);
 
+            // View cube: camera orientation only, projected into its screen corner
+            let (viewport_width, viewport_height) = self.viewport_size();
+            let view_cube_ubo = GizmoUniformBufferObject {
+                model: Mat4::IDENTITY,
+                view: crate::view_cube::ViewCube::view_matrix(game.camera.rotation()),
+                proj: crate::view_cube::ViewCube::projection_matrix(viewport_width, viewport_height),
+            };
+
+            let data = self.device.map_memory(
+                self.view_cube_uniform_buffers_memory[image_index],
+                0,
+                std::mem::size_of::<GizmoUniformBufferObject>() as vk::DeviceSize,
+                vk::MemoryMapFlags::empty(),
+            )?;
+            std::ptr::copy_nonoverlapping(&view_cube_ubo, data as *mut GizmoUniformBufferObject, 1);
+            self.device.unmap_memory(self.view_cube_uniform_buffers_memory[image_index]);
+
             Ok(())
         }
 
@@ -2984,6 +4686,8 @@ impl VulkanRenderer {
                 ssao_bias: game.ssao_config.bias,
                 ssao_power: game.ssao_config.power,
                 ssao_kernel_size: game.ssao_config.kernel_size,
+                log_depth: game.camera.log_depth_coefficient(),
+                _padding: [0.0; 3],
             };
 
             let data = self.device.map_memory(
@@ -3031,10 +4735,10 @@ impl VulkanRenderer {
             Ok(())
         }
 
-        /// Render movement widget for tactical turn-based movement
-        pub fn render(&mut self, game: &mut crate::game::Game) -> anyhow::Result<()> {
-            // Load any new custom meshes
+        /// Upload the meshes and material textures the scene needs that aren't loaded yet
+        fn load_scene_assets(&mut self, game: &mut crate::game::Game) {
             unsafe {
+                // Load any new custom meshes
                 let mesh_objects = game.get_visible_meshes();
                 for (mesh_path, _) in mesh_objects.iter() {
                     if !self.custom_meshes.contains_key(mesh_path) {
@@ -3044,20 +4748,67 @@ impl VulkanRenderer {
                                 game.update_ship_bounds(mesh_path, bounds_min, bounds_max);
                             }
                             Err(e) => {
-                                eprintln!("Failed to load mesh {}: {}", mesh_path, e);
+                                crate::console::error(format!("Failed to load mesh {}: {}", mesh_path, e));
+                            }
+                        }
+                    }
+                }
+
+                // Normals recomputed from the Transform panel (picking gets the rebuilt indices too)
+                if let Some((path, options)) = game.pending_normal_recompute.take() {
+                    match self.recompute_custom_mesh_normals(&path, options) {
+                        Ok(()) => {
+                            if let Some((mesh, ..)) = self.custom_meshes.get(&path) {
+                                game.pick_meshes.insert_custom(&path, mesh);
                             }
+                            crate::console::info(format!("Recomputed normals of {}", path));
                         }
+                        Err(e) => {
+                            crate::console::error(format!("Failed to recompute normals of {}: {}", path, e));
+                        }
+                    }
+                }
+
+                // Share loaded geometry with scene ray casts (picking always uses full detail)
+                for (mesh_path, (mesh, _, _, _, _)) in &self.custom_meshes {
+                    if !mesh_path.contains(crate::mesh::LOD_KEY_SEPARATOR) && !game.pick_meshes.contains_custom(mesh_path) {
+                        game.pick_meshes.insert_custom(mesh_path, mesh);
                     }
                 }
+
+                // Load any new material textures (each path is uploaded once)
+                for material in game.get_visible_materials() {
+                    self.texture_cache.prepare_material(
+                        &self.instance,
+                        self.physical_device,
+                        &self.device,
+                        self.command_pool,
+                        self.graphics_queue,
+                        material,
+                    );
+                }
             }
+        }
 
-            // Frame rate limiting to 120 FPS
-            let target_frame_time = std::time::Duration::from_secs_f64(1.0 / 120.0);
-            let elapsed = self.last_frame_time.elapsed();
-            if elapsed < target_frame_time {
-                std::thread::sleep(target_frame_time - elapsed);
+        /// Render movement widget for tactical turn-based movement
+        pub fn render(&mut self, game: &mut crate::game::Game) -> anyhow::Result<()> {
+            if self.window_surface.is_none() {
+                anyhow::bail!("Headless renderers have no swapchain to present to, use capture_frame");
+            }
+            self.load_scene_assets(game);
+
+            // Frame rate limiting to the configured cap (0 = uncapped)
+            let fps_cap = game.graphics_config.fps_cap;
+            if fps_cap > 0 {
+                let target_frame_time = std::time::Duration::from_secs_f64(1.0 / fps_cap as f64);
+                let elapsed = self.last_frame_time.elapsed();
+                if elapsed < target_frame_time {
+                    std::thread::sleep(target_frame_time - elapsed);
+                }
             }
-            self.last_frame_time = std::time::Instant::now();
+            let now = std::time::Instant::now();
+            self.frame_stats.push_frame_time(now.duration_since(self.last_frame_time).as_secs_f32() * 1000.0);
+            self.last_frame_time = now;
 
             unsafe {
                 self.device.wait_for_fences(
@@ -3065,9 +4816,27 @@ impl VulkanRenderer {
                     true,
                     u64::MAX,
                 )?;
+
+                // Rebuild the main render pass if the requested MSAA level changed
+                // (switching to FXAA or None requests a single sample)
+                let msaa_samples = game.anti_aliasing_config.msaa_samples();
+                if msaa_samples != self.msaa_requested {
+                    self.apply_msaa_samples(msaa_samples)?;
+                }
+
+                // Swap the material texture sampler if the requested anisotropy changed
+                game.max_anisotropy = self.max_anisotropy as u32;
+                if game.anti_aliasing_config.anisotropy != self.anisotropy_requested {
+                    self.apply_anisotropy(game.anti_aliasing_config.anisotropy)?;
+                }
+
+                // Recreate the swapchain if the requested present mode changed
+                if game.graphics_config.present_mode != self.present_mode_requested {
+                    self.apply_present_mode(game)?;
+                }
                 
                 let result = self.swapchain_loader.acquire_next_image(
-                    self.swapchain,
+                    self.surface().swapchain,
                     u64::MAX,
                     self.image_available_semaphores[self.current_frame],
                     vk::Fence::null(),
@@ -3094,9 +4863,45 @@ impl VulkanRenderer {
                 // Mark the image as now being in use by this frame
                 self.images_in_flight[image_index as usize] = self.in_flight_fences[self.current_frame];
                 
-                self.update_uniform_buffer(self.current_frame, game)?;
-                self.update_gizmo_uniform_buffer(self.current_frame, game)?;
-                self.update_ssao_uniform_buffer(self.current_frame, game)?;
+                // Apply render pass toggles from the UI (also publishes pass names to the game)
+                self.render_passes.sync_enabled(&mut game.render_pass_states);
+
+                // Recreate the shadow map if the configured resolution changed
+                if game.shadow_config.resolution != self.shadow_pass.resolution() {
+                    self.device.device_wait_idle()?;
+                    self.shadow_pass.resize(
+                        &self.instance,
+                        self.physical_device,
+                        &self.device,
+                        game.shadow_config.resolution,
+                    )?;
+                    self.update_shadow_descriptors();
+                }
+
+                // Reallocate the particle ring if the configured budget changed
+                if game.particle_config.max_particles != self.particle_pass.capacity() {
+                    self.device.device_wait_idle()?;
+                    self.particle_pass.resize(
+                        &self.instance,
+                        self.physical_device,
+                        &self.device,
+                        game.particle_config.max_particles,
+                    )?;
+                }
+
+                // Recompute image-based lighting when the skybox changed
+                if self.environment.update(
+                    &self.instance,
+                    self.physical_device,
+                    &self.device,
+                    self.command_pool,
+                    self.graphics_queue,
+                    &game.skybox_config,
+                )? {
+                    for sets in [&self.descriptor_sets, &self.gizmo_descriptor_sets, &self.view_cube_descriptor_sets] {
+                        self.environment.write_descriptors(&self.device, sets);
+                    }
+                }
 
                 // Update render passes (plugin system)
                 let ctx = crate::core::RenderContext {
@@ -3106,17 +4911,37 @@ impl VulkanRenderer {
                     command_pool: self.command_pool,
                     graphics_queue: self.graphics_queue,
                     extent: self.swapchain_extent,
+                    pipeline_cache: &self.pipeline_cache,
+                    msaa_samples: self.msaa_samples,
                     depth_image_view: Some(self.depth_image_view),
                     depth_sampler: Some(self.depth_sampler),
                     mesh_pipeline: Some(self.graphics_pipeline),
+                    mesh_instanced_pipeline: Some(self.instanced_pipeline),
+                    mesh_transparent_pipeline: Some(self.transparent_pipeline),
+                    mesh_overdraw_pipeline: Some(self.overdraw_pipeline),
+                    mesh_prepass_pipelines: Some(self.depth_prepass_pipelines),
                     mesh_pipeline_layout: Some(self.pipeline_layout),
                     mesh_descriptor_sets: Some(&self.descriptor_sets),
                     custom_meshes: Some(&self.custom_meshes),
+                    primitive_meshes: Some(&self.primitive_meshes),
+                    textures: Some(&self.texture_cache),
                 };
+                self.shadow_pass.update(&ctx, self.current_frame, game)?;
+                self.particle_pass.update(&ctx, self.current_frame, game)?;
+                self.bloom_pass.update(&ctx, self.current_frame, game)?;
+                self.tonemap_pass.update(&ctx, self.current_frame, game)?;
+                self.fxaa_pass.update(&ctx, self.current_frame, game)?;
+            self.fxaa_pass.update(&ctx, self.current_frame, game)?;
                 self.render_passes.update_all(&ctx, self.current_frame, game)?;
 
+                self.update_uniform_buffer(self.current_frame, game)?;
+                self.update_gizmo_uniform_buffer(self.current_frame, game)?;
+                self.update_ssao_uniform_buffer(self.current_frame, game)?;
+
                 // Prepare ImGui frame
-                self.imgui_platform.prepare_frame(self.imgui_context.io_mut(), &self.window)?;
+                if let Some(window_surface) = &self.window_surface {
+                    self.imgui_platform.prepare_frame(self.imgui_context.io_mut(), &window_surface.window)?;
+                }
                 self.build_ui(game);
                 
                 self.device.reset_fences(&[self.in_flight_fences[self.current_frame]])?;
@@ -3126,7 +4951,26 @@ impl VulkanRenderer {
                     vk::CommandBufferResetFlags::empty(),
                 )?;
                 
+                // Allocate the readback buffer if a screenshot was requested
+                if let Some(path) = self.screenshot_request.take() {
+                    match self.create_screenshot_capture(path) {
+                        Ok(capture) => self.screenshot_capture = Some(capture),
+                        Err(e) => {
+                            crate::console::error(format!("Failed to capture screenshot: {}", e));
+                            game.add_notification(format!("Screenshot failed: {}", e), 3.0);
+                        }
+                    }
+                }
+
+                // Per-pass timing is only collected while the stats overlay is visible
+                self.render_passes.set_profiling(game.show_stats_overlay);
                 self.record_command_buffer(self.command_buffers[self.current_frame], image_index as usize, game)?;
+
+                // Publish culling stats for the UI
+                if let Some(stats) = self.render_passes.draw_stats("Mesh") {
+                    game.mesh_draw_stats = stats;
+                }
+                game.selected_mesh_stats = game.scene.selected_object().and_then(|obj| self.mesh_stats(&obj.object_type));
                 
                 let wait_semaphores = [self.image_available_semaphores[self.current_frame]];
                 let wait_stages = [vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
@@ -3145,14 +4989,14 @@ impl VulkanRenderer {
                     self.in_flight_fences[self.current_frame],
                 )?;
                 
-                let swapchains = [self.swapchain];
+                let swapchains = [self.surface().swapchain];
                 let image_indices = [image_index];
                 let present_info = vk::PresentInfoKHR::default()
                 .wait_semaphores(&signal_semaphores)
                 .swapchains(&swapchains)
                 .image_indices(&image_indices);
                 
-                let result = self.swapchain_loader.queue_present(self.present_queue, &present_info);
+                let result = self.swapchain_loader.queue_present(self.surface().present_queue, &present_info);
                 
                 match result {
                     Ok(true) | Err(vk::Result::ERROR_OUT_OF_DATE_KHR) | Err(vk::Result::SUBOPTIMAL_KHR) => {
@@ -3161,6 +5005,23 @@ impl VulkanRenderer {
                     Err(e) => return Err(anyhow::anyhow!("Failed to present swap chain image: {}", e)),
                     _ => {}
                 }
+
+                // Read back the screenshot once this frame has finished rendering
+                if let Some(capture) = self.screenshot_capture.take() {
+                    self.device.wait_for_fences(&[self.in_flight_fences[self.current_frame]], true, u64::MAX)?;
+                    match self.save_screenshot(&capture) {
+                        Ok(()) => {
+                            println!("Screenshot saved: {}", capture.path);
+                            game.add_notification(format!("Screenshot saved: {}", capture.path), 3.0);
+                        }
+                        Err(e) => {
+                            crate::console::error(format!("Failed to save screenshot {}: {}", capture.path, e));
+                            game.add_notification(format!("Screenshot failed: {}", e), 3.0);
+                        }
+                    }
+                    self.device.destroy_buffer(capture.buffer, None);
+                    self.device.free_memory(capture.memory, None);
+                }
                 
                 self.current_frame = (self.current_frame + 1) % MAX_FRAMES_IN_FLIGHT;
                 self.frame_count += 1;
@@ -3171,7 +5032,7 @@ impl VulkanRenderer {
                 
                 if elapsed.as_secs_f64() >= 0.5 {
                     let fps = self.fps_frame_count as f64 / elapsed.as_secs_f64();
-                    self.window.set_title(&format!("Tribal Engine | FPS: {:.0}", fps));
+                    self.surface().window.set_title(&format!("Tribal Engine | FPS: {:.0}", fps));
                     self.fps_frame_count = 0;
                     self.last_time = now;
                 }
@@ -3179,16 +5040,103 @@ impl VulkanRenderer {
             
             Ok(())
         }
-        
-        unsafe fn record_command_buffer(&mut self, command_buffer: vk::CommandBuffer, image_index: usize, game: &crate::game::Game) -> anyhow::Result<()> {
-            let begin_info = vk::CommandBufferBeginInfo::default();
-            
-            self.device.begin_command_buffer(command_buffer, &begin_info)?;
+        
+        unsafe fn record_command_buffer(&mut self, command_buffer: vk::CommandBuffer, image_index: usize, game: &crate::game::Game) -> anyhow::Result<()> {
+            let begin_info = vk::CommandBufferBeginInfo::default();
+            
+            self.device.begin_command_buffer(command_buffer, &begin_info)?;
+
+            self.record_scene(command_buffer, image_index, game)?;
+
+            // ImGui on top of the tonemapped image (sRGB, unaffected by bloom/exposure)
+            let overlay_render_pass_info = vk::RenderPassBeginInfo::default()
+                .render_pass(self.overlay_render_pass)
+                .framebuffer(self.swapchain_framebuffers[image_index])
+                .render_area(vk::Rect2D {
+                    offset: vk::Offset2D { x: 0, y: 0 },
+                    extent: self.swapchain_extent,
+                });
+
+            self.device.cmd_begin_render_pass(
+                command_buffer,
+                &overlay_render_pass_info,
+                vk::SubpassContents::INLINE,
+            );
+
+            let draw_data = self.imgui_context.render();
+            self.imgui_renderer.render(
+                &self.device,
+                &self.instance,
+                self.physical_device,
+                command_buffer,
+                self.command_pool,
+                self.graphics_queue,
+                draw_data,
+            )?;
+
+            self.device.cmd_end_render_pass(command_buffer);
+
+            if let Some(capture) = &self.screenshot_capture {
+                self.record_screenshot_copy(command_buffer, self.swapchain_images[image_index], capture);
+            }
+
+            self.device.end_command_buffer(command_buffer)?;
+
+            Ok(())
+        }
+
+        /// Record the whole scene (shadows, main pass, SSAO, bloom, tonemap) into
+        /// `swapchain_framebuffers[image_index]`, leaving the image in PRESENT_SRC layout
+        unsafe fn record_scene(&mut self, command_buffer: vk::CommandBuffer, image_index: usize, game: &crate::game::Game) -> anyhow::Result<()> {
+            let ctx = crate::core::RenderContext {
+                device: &self.device,
+                instance: &self.instance,
+                physical_device: self.physical_device,
+                command_pool: self.command_pool,
+                graphics_queue: self.graphics_queue,
+                extent: self.swapchain_extent,
+                pipeline_cache: &self.pipeline_cache,
+                msaa_samples: self.msaa_samples,
+                depth_image_view: Some(self.depth_image_view),
+                depth_sampler: Some(self.depth_sampler),
+                mesh_pipeline: Some(self.graphics_pipeline),
+                mesh_instanced_pipeline: Some(self.instanced_pipeline),
+                mesh_transparent_pipeline: Some(self.transparent_pipeline),
+                mesh_overdraw_pipeline: Some(self.overdraw_pipeline),
+                mesh_prepass_pipelines: Some(self.depth_prepass_pipelines),
+                mesh_pipeline_layout: Some(self.pipeline_layout),
+                mesh_descriptor_sets: Some(&self.descriptor_sets),
+                custom_meshes: Some(&self.custom_meshes),
+                primitive_meshes: Some(&self.primitive_meshes),
+                textures: Some(&self.texture_cache),
+            };
+
+            // 0. Shadow map (own render pass, must finish before the main pass samples it)
+            let shadow_start = std::time::Instant::now();
+            self.shadow_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+            let shadow_ms = shadow_start.elapsed().as_secs_f32() * 1000.0;
+
+            // Material editor preview (own render pass, sampled by ImGui at the end of the frame)
+            self.material_preview_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+
+            // Tactical minimap (own render pass, only while its window is open)
+            self.minimap_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+
+            // Hierarchy thumbnails (own render pass, only when a mesh or its material changed)
+            self.thumbnail_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+
+            // Particle simulation (compute can't be recorded inside a render pass)
+            self.particle_pass.simulate(&ctx, command_buffer, self.current_frame, game)?;
+
+            // SSR history must be in its sampled layout before the main pass binds it
+            self.reflection_history.prepare(&self.device, command_buffer);
             
+            // Solid background color (covered by the gradient or skybox in those modes)
+            let clear_color = game.tonemap_config.shader_color(game.background_config.color);
             let clear_values = [
             vk::ClearValue {
                 color: vk::ClearColorValue {
-                    float32: [0.01, 0.01, 0.02, 1.0],
+                    float32: clear_color.extend(1.0).to_array(),
                 },
             },
             vk::ClearValue {
@@ -3215,22 +5163,11 @@ impl VulkanRenderer {
             );
             
             // 1. Render passes (skybox, nebula, meshes, etc.) - plugin system
-            let ctx = crate::core::RenderContext {
-                device: &self.device,
-                instance: &self.instance,
-                physical_device: self.physical_device,
-                command_pool: self.command_pool,
-                graphics_queue: self.graphics_queue,
-                extent: self.swapchain_extent,
-                depth_image_view: Some(self.depth_image_view),
-                depth_sampler: Some(self.depth_sampler),
-                mesh_pipeline: Some(self.graphics_pipeline),
-                mesh_pipeline_layout: Some(self.pipeline_layout),
-                mesh_descriptor_sets: Some(&self.descriptor_sets),
-                custom_meshes: Some(&self.custom_meshes),
-            };
             self.render_passes.render_all(&ctx, command_buffer, self.current_frame, game)?;
 
+            // 2. Particles, additive over the finished scene
+            self.particle_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+
             // Mesh rendering (cubes, custom meshes) and stars now handled by render pass plugins
 
             // Transition depth image for shader reading
@@ -3241,7 +5178,7 @@ impl VulkanRenderer {
                 .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
                 .image(self.depth_image)
                 .subresource_range(vk::ImageSubresourceRange {
-                    aspect_mask: vk::ImageAspectFlags::DEPTH,
+                    aspect_mask: vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
                     base_mip_level: 0,
                     level_count: 1,
                     base_array_layer: 0,
@@ -3270,7 +5207,7 @@ impl VulkanRenderer {
                 .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
                 .image(self.depth_image)
                 .subresource_range(vk::ImageSubresourceRange {
-                    aspect_mask: vk::ImageAspectFlags::DEPTH,
+                    aspect_mask: vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
                     base_mip_level: 0,
                     level_count: 1,
                     base_array_layer: 0,
@@ -3340,28 +5277,29 @@ impl VulkanRenderer {
                     &[],
                 );
 
-                // Push hovered axis constant (0=none, 1=X, 2=Y, 3=Z)
+                // Push hovered axis constant (0=none, 1=X, 2=Y, 3=Z) and the locked axes to dim
                 let hovered_axis = match game.gizmo_state.hovered_axis {
                     crate::gizmo::GizmoAxis::None => 0i32,
                     crate::gizmo::GizmoAxis::X => 1i32,
                     crate::gizmo::GizmoAxis::Y => 2i32,
                     crate::gizmo::GizmoAxis::Z => 3i32,
                 };
-                let push_constants = hovered_axis.to_le_bytes();
+                let push_constants: [i32; 2] = [hovered_axis, game.gizmo_state.locked_axes_mask()];
+                let push_constants = bytemuck::bytes_of(&push_constants);
                 self.device.cmd_push_constants(
                     command_buffer,
                     self.gizmo_pipeline_layout,
                     vk::ShaderStageFlags::VERTEX,
                     0,
-                    &push_constants,
+                    push_constants,
                 );
 
                 self.device.cmd_draw_indexed(command_buffer, index_count, 1, 0, 0, 0);
             }
 
-            // 5. Render directional light visualization (yellow wireframe) - editor only
+            // 5. Render directional light visualizations (yellow wireframe) - editor only
             if in_edit_mode {
-                if let Some(light_transform) = game.get_directional_light() {
+                for light_transform in game.get_directional_light_transforms() {
                 self.device.cmd_bind_pipeline(
                     command_buffer,
                     vk::PipelineBindPoint::GRAPHICS,
@@ -3382,35 +5320,126 @@ impl VulkanRenderer {
                     &[],
                 );
 
-                // Push light transform matrix
-                let model_array = [light_transform];
-                let push_constants = bytemuck::cast_slice(&model_array);
+                // Push light transform matrix and color
+                let push_constants = WireframeColorPushConstants {
+                    model: game.render_matrix(light_transform),
+                    color: Vec4::new(1.0, 1.0, 0.0, 1.0),
+                };
                 self.device.cmd_push_constants(
                     command_buffer,
                     self.pipeline_layout,
-                    vk::ShaderStageFlags::VERTEX,
+                    vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
                     0,
-                    push_constants,
+                    bytemuck::bytes_of(&push_constants),
                 );
 
                 self.device.cmd_draw_indexed(command_buffer, self.dir_light_mesh.indices.len() as u32, 1, 0, 0, 0);
                 }
             }
 
-            // Render ImGui
-            let draw_data = self.imgui_context.render();
-            self.imgui_renderer.render(
-                &self.device,
-                &self.instance,
-                self.physical_device,
-                command_buffer,
-                self.command_pool,
-                self.graphics_queue,
-                draw_data,
-            )?;
+            // Object bounding boxes ("Show Bounds"): the unit cube stretched over each mesh's
+            // local AABB, then placed by the object's world matrix. Selected objects stand out.
+            let bounds_objects = game.get_bounds_objects();
+            if !bounds_objects.is_empty() {
+                self.device.cmd_bind_pipeline(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.wireframe_pipeline,
+                );
+                self.device.cmd_bind_vertex_buffers(command_buffer, 0, &[self.vertex_buffer], &[0]);
+                self.device.cmd_bind_index_buffer(command_buffer, self.index_buffer, 0, vk::IndexType::UINT32);
+                self.device.cmd_bind_descriptor_sets(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.pipeline_layout,
+                    0,
+                    &[self.descriptor_sets[self.current_frame]],
+                    &[],
+                );
+
+                for (object_type, world_matrix, selected) in &bounds_objects {
+                    let Some((bounds_min, bounds_max)) = self.object_mesh_bounds(object_type) else {
+                        continue;
+                    };
+                    let box_matrix = Mat4::from_scale_rotation_translation(
+                        bounds_max - bounds_min,
+                        glam::Quat::IDENTITY,
+                        (bounds_min + bounds_max) * 0.5,
+                    );
+                    let push_constants = WireframeColorPushConstants {
+                        model: game.render_matrix(*world_matrix * box_matrix),
+                        color: if *selected {
+                            Vec4::new(1.0, 0.55, 0.0, 1.0) // Orange - selected
+                        } else {
+                            Vec4::new(0.3, 0.9, 1.0, 0.6) // Light blue
+                        },
+                    };
+                    self.device.cmd_push_constants(
+                        command_buffer,
+                        self.pipeline_layout,
+                        vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+                        0,
+                        bytemuck::bytes_of(&push_constants),
+                    );
+                    self.device.cmd_draw_indexed(command_buffer, self.mesh.indices.len() as u32, 1, 0, 0, 0);
+                }
+            }
+
+            // 6. Render view cube in the screen corner (gizmo pipeline ignores scene depth) - editor only
+            if game.is_view_cube_visible() {
+                self.device.cmd_bind_pipeline(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.gizmo_pipeline,
+                );
+
+                self.device.cmd_bind_vertex_buffers(command_buffer, 0, &[self.view_cube_vertex_buffer], &[0]);
+                self.device.cmd_bind_index_buffer(command_buffer, self.view_cube_index_buffer, 0, vk::IndexType::UINT32);
+
+                self.device.cmd_bind_descriptor_sets(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.gizmo_pipeline_layout,
+                    0,
+                    &[self.view_cube_descriptor_sets[self.current_frame]],
+                    &[],
+                );
+
+                // Push hovered face (signed axis code, 0 = none); no face is ever dimmed
+                let hovered_face = game.view_cube.hovered_face.map_or(0, |face| face.hover_code());
+                let push_constants: [i32; 2] = [hovered_face, 0];
+                self.device.cmd_push_constants(
+                    command_buffer,
+                    self.gizmo_pipeline_layout,
+                    vk::ShaderStageFlags::VERTEX,
+                    0,
+                    bytemuck::bytes_of(&push_constants),
+                );
+
+                // Only camera-facing faces, so the depth-ignoring pipeline can't draw back faces over them
+                for face in crate::view_cube::ViewCube::visible_faces(game.camera.rotation()) {
+                    self.device.cmd_draw_indexed(command_buffer, 6, 1, face.index() as u32 * 6, 0, 0);
+                }
+            }
 
             self.device.cmd_end_render_pass(command_buffer);
 
+            // Keep this frame's color and depth for the next frame's reflections
+            // (before bloom, so reflected highlights don't bloom twice)
+            if game.ssr_config.enabled {
+                let aspect = self.swapchain_extent.width as f32 / self.swapchain_extent.height as f32;
+                let frame = crate::core::ssr::HistoryCopyInfo {
+                    scene_color: self.hdr_images[image_index],
+                    scene_depth: self.depth_image,
+                    extent: self.swapchain_extent,
+                    view: game.get_view_matrix(),
+                    proj: game.camera.projection_matrix(aspect),
+                };
+                self.reflection_history.record_copy(&self.device, command_buffer, &frame);
+            } else {
+                self.reflection_history.invalidate();
+            }
+
             // SSAO Pass - only if enabled
             if game.ssao_config.enabled {
                 // SSAO Pass - compute ambient occlusion from depth buffer
@@ -3425,7 +5454,7 @@ impl VulkanRenderer {
                     .framebuffer(self.ssao_framebuffer)
                     .render_area(vk::Rect2D {
                         offset: vk::Offset2D { x: 0, y: 0 },
-                        extent: self.swapchain_extent,
+                        extent: self.ssao_extent,
                     })
                     .clear_values(&ssao_clear_values);
 
@@ -3467,7 +5496,7 @@ impl VulkanRenderer {
                     .framebuffer(self.ssao_blur_horizontal_framebuffer)
                     .render_area(vk::Rect2D {
                         offset: vk::Offset2D { x: 0, y: 0 },
-                        extent: self.swapchain_extent,
+                        extent: self.ssao_extent,
                     })
                     .clear_values(&ssao_blur_clear_values);
 
@@ -3508,60 +5537,470 @@ impl VulkanRenderer {
                 // Draw fullscreen triangle
                 self.device.cmd_draw(command_buffer, 3, 1, 0, 0);
 
-                self.device.cmd_end_render_pass(command_buffer);
+                self.device.cmd_end_render_pass(command_buffer);
+
+                // SSAO Blur Pass 2: Vertical blur (intermediate -> final)
+                let ssao_blur_vertical_render_pass_info = vk::RenderPassBeginInfo::default()
+                    .render_pass(self.ssao_blur_render_pass)
+                    .framebuffer(self.ssao_blur_framebuffer)
+                    .render_area(vk::Rect2D {
+                        offset: vk::Offset2D { x: 0, y: 0 },
+                        extent: self.ssao_extent,
+                    })
+                    .clear_values(&ssao_blur_clear_values);
+
+                self.device.cmd_begin_render_pass(
+                    command_buffer,
+                    &ssao_blur_vertical_render_pass_info,
+                    vk::SubpassContents::INLINE,
+                );
+
+                self.device.cmd_bind_pipeline(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.ssao_blur_pipeline,
+                );
+
+                self.device.cmd_bind_descriptor_sets(
+                    command_buffer,
+                    vk::PipelineBindPoint::GRAPHICS,
+                    self.ssao_blur_pipeline_layout,
+                    0,
+                    &[self.ssao_blur_descriptor_sets[self.current_frame]],
+                    &[],
+                );
+
+                // Push constant: direction = 1 (vertical)
+                let vertical_direction: i32 = 1;
+                self.device.cmd_push_constants(
+                    command_buffer,
+                    self.ssao_blur_pipeline_layout,
+                    vk::ShaderStageFlags::FRAGMENT,
+                    0,
+                    std::slice::from_raw_parts(
+                        &vertical_direction as *const i32 as *const u8,
+                        std::mem::size_of::<i32>(),
+                    ),
+                );
+
+                // Draw fullscreen triangle
+                self.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+
+                self.device.cmd_end_render_pass(command_buffer);
+            }
+
+            // Post-processing: bloom onto the HDR scene color, then tonemap to the swapchain
+            let ctx = crate::core::RenderContext {
+                device: &self.device,
+                instance: &self.instance,
+                physical_device: self.physical_device,
+                command_pool: self.command_pool,
+                graphics_queue: self.graphics_queue,
+                extent: self.swapchain_extent,
+                pipeline_cache: &self.pipeline_cache,
+                msaa_samples: self.msaa_samples,
+                depth_image_view: Some(self.depth_image_view),
+                depth_sampler: Some(self.depth_sampler),
+                mesh_pipeline: Some(self.graphics_pipeline),
+                mesh_instanced_pipeline: Some(self.instanced_pipeline),
+                mesh_transparent_pipeline: Some(self.transparent_pipeline),
+                mesh_overdraw_pipeline: Some(self.overdraw_pipeline),
+                mesh_prepass_pipelines: Some(self.depth_prepass_pipelines),
+                mesh_pipeline_layout: Some(self.pipeline_layout),
+                mesh_descriptor_sets: Some(&self.descriptor_sets),
+                custom_meshes: Some(&self.custom_meshes),
+                primitive_meshes: Some(&self.primitive_meshes),
+                textures: Some(&self.texture_cache),
+            };
+            let post_start = std::time::Instant::now();
+            if game.bloom_config.enabled {
+                self.bloom_pass.set_target(image_index);
+                self.bloom_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+            }
+
+            if game.anti_aliasing_config.fxaa_enabled() {
+                self.tonemap_pass.set_intermediate_target(
+                    image_index,
+                    self.fxaa_pass.source_render_pass(),
+                    self.fxaa_pass.source_framebuffer(image_index),
+                );
+                self.tonemap_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+                self.fxaa_pass.set_target(image_index, self.swapchain_framebuffers[image_index]);
+                self.fxaa_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+            } else {
+                self.tonemap_pass.set_target(image_index, self.swapchain_framebuffers[image_index]);
+                self.tonemap_pass.render(&ctx, command_buffer, self.current_frame, game)?;
+            }
+            let post_ms = post_start.elapsed().as_secs_f32() * 1000.0;
+
+            // Collect stats for the overlay (registry timings are only recorded while it's open)
+            if game.show_stats_overlay {
+                let mut draw_stats = self.render_passes.total_draw_stats();
+                if let Some(shadow_stats) = self.shadow_pass.draw_stats() {
+                    draw_stats += shadow_stats;
+                }
+                if let Some(particle_stats) = self.particle_pass.draw_stats() {
+                    draw_stats += particle_stats;
+                }
+                self.frame_stats.draw_stats = draw_stats;
+
+                self.frame_stats.pass_timings.clear();
+                self.frame_stats.pass_timings.push(("Shadow".to_string(), shadow_ms));
+                self.frame_stats.pass_timings.extend(self.render_passes.pass_timings().iter().cloned());
+                self.frame_stats.pass_timings.push(("Bloom + Tonemap + FXAA".to_string(), post_ms));
+            }
+
+            // Static scene content, for the overlay and the Scene Statistics panel (edit mode)
+            if game.show_stats_overlay || game.game_manager.is_editing() {
+                self.frame_stats.scene_triangles = self.scene_triangle_count(game);
+                self.frame_stats.mesh_memory = self.mesh_memory();
+            }
+
+            Ok(())
+        }
+
+        /// Request a PNG screenshot of the next presented frame.
+        /// The file is written after that frame finishes rendering.
+        pub fn capture_screenshot(&mut self, path: &str) {
+            self.screenshot_request = Some(path.to_string());
+        }
+
+        unsafe fn create_screenshot_capture(&self, path: String) -> anyhow::Result<ScreenshotCapture> {
+            let window_surface = self.surface();
+            let capabilities = window_surface.surface_loader.get_physical_device_surface_capabilities(self.physical_device, window_surface.surface)?;
+            if !capabilities.supported_usage_flags.contains(vk::ImageUsageFlags::TRANSFER_SRC) {
+                anyhow::bail!("Swapchain images cannot be copied on this device");
+            }
+
+            self.create_capture_buffer(path, self.swapchain_extent)
+        }
+
+        /// Host-visible readback buffer for an `extent`-sized image in the swapchain format
+        unsafe fn create_capture_buffer(&self, path: String, extent: vk::Extent2D) -> anyhow::Result<ScreenshotCapture> {
+            let size = extent.width as vk::DeviceSize * extent.height as vk::DeviceSize * 4;
+            let (buffer, memory) = Self::create_buffer(
+                &self.instance,
+                self.physical_device,
+                &self.device,
+                size,
+                vk::BufferUsageFlags::TRANSFER_DST,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )?;
+
+            Ok(ScreenshotCapture {
+                path,
+                buffer,
+                memory,
+                extent,
+                format: self.swapchain_format,
+            })
+        }
+
+        /// Copy the finished swapchain image into the capture buffer, leaving it ready to present
+        unsafe fn record_screenshot_copy(&self, command_buffer: vk::CommandBuffer, image: vk::Image, capture: &ScreenshotCapture) {
+            let subresource_range = vk::ImageSubresourceRange::default()
+                .aspect_mask(vk::ImageAspectFlags::COLOR)
+                .base_mip_level(0)
+                .level_count(1)
+                .base_array_layer(0)
+                .layer_count(1);
+
+            // 1. PRESENT_SRC -> TRANSFER_SRC after the overlay pass finished writing
+            let to_transfer = vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::PRESENT_SRC_KHR)
+                .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .image(image)
+                .subresource_range(subresource_range)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_access_mask(vk::AccessFlags::TRANSFER_READ);
+
+            self.device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[to_transfer],
+            );
+
+            // 2. Copy the whole image into the tightly packed buffer
+            let region = vk::BufferImageCopy::default()
+                .buffer_offset(0)
+                .buffer_row_length(0)
+                .buffer_image_height(0)
+                .image_subresource(vk::ImageSubresourceLayers {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    mip_level: 0,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                })
+                .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+                .image_extent(vk::Extent3D {
+                    width: capture.extent.width,
+                    height: capture.extent.height,
+                    depth: 1,
+                });
+
+            self.device.cmd_copy_image_to_buffer(
+                command_buffer,
+                image,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                capture.buffer,
+                &[region],
+            );
+
+            // 3. Back to PRESENT_SRC, and make the buffer visible to the host
+            let to_present = vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .new_layout(vk::ImageLayout::PRESENT_SRC_KHR)
+                .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .image(image)
+                .subresource_range(subresource_range)
+                .src_access_mask(vk::AccessFlags::TRANSFER_READ)
+                .dst_access_mask(vk::AccessFlags::empty());
+
+            let buffer_to_host = vk::BufferMemoryBarrier::default()
+                .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(vk::AccessFlags::HOST_READ)
+                .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .buffer(capture.buffer)
+                .offset(0)
+                .size(vk::WHOLE_SIZE);
+
+            self.device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::BOTTOM_OF_PIPE | vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[buffer_to_host],
+                &[to_present],
+            );
+        }
+
+        /// Read back the captured pixels and write them as PNG
+        unsafe fn save_screenshot(&self, capture: &ScreenshotCapture) -> anyhow::Result<()> {
+            let image = self.read_capture(capture)?;
+            Self::save_png(&capture.path, &image)
+        }
+
+        /// Convert the read-back pixels to RGBA8.
+        /// The bytes are copied raw: an *_SRGB swapchain already stores sRGB-encoded
+        /// values, which is what PNG expects, so only the channel order changes.
+        unsafe fn read_capture(&self, capture: &ScreenshotCapture) -> anyhow::Result<image::RgbaImage> {
+            let swap_red_blue = match capture.format {
+                vk::Format::B8G8R8A8_SRGB | vk::Format::B8G8R8A8_UNORM => true,
+                vk::Format::R8G8B8A8_SRGB | vk::Format::R8G8B8A8_UNORM => false,
+                other => anyhow::bail!("Unsupported swapchain format for screenshots: {:?}", other),
+            };
+
+            let size = capture.extent.width as usize * capture.extent.height as usize * 4;
+            let data_ptr = self.device.map_memory(capture.memory, 0, size as vk::DeviceSize, vk::MemoryMapFlags::empty())? as *const u8;
+            let mut pixels = std::slice::from_raw_parts(data_ptr, size).to_vec();
+            self.device.unmap_memory(capture.memory);
+
+            for pixel in pixels.chunks_exact_mut(4) {
+                if swap_red_blue {
+                    pixel.swap(0, 2);
+                }
+                // Swapchain alpha is meaningless for an opaque window
+                pixel[3] = 255;
+            }
+
+            image::RgbaImage::from_raw(capture.extent.width, capture.extent.height, pixels)
+                .ok_or_else(|| anyhow::anyhow!("Screenshot buffer size mismatch"))
+        }
+
+        /// Write `image` to `path` as PNG, creating missing parent directories
+        fn save_png(path: &str, image: &image::RgbaImage) -> anyhow::Result<()> {
+            if let Some(parent) = std::path::Path::new(path).parent() {
+                if !parent.as_os_str().is_empty() {
+                    std::fs::create_dir_all(parent)?;
+                }
+            }
+
+            image.save_with_format(path, image::ImageFormat::Png)?;
+            Ok(())
+        }
+
+        /// Render one frame of a headless renderer and read it back as RGBA8.
+        /// Loads the scene's meshes and applies the game's MSAA and anisotropy first.
+        /// ImGui is not drawn; edit-mode overlays are, as with `render_to_image`
+        pub fn capture_frame(&mut self, game: &mut crate::game::Game) -> anyhow::Result<image::RgbaImage> {
+            if !self.is_headless() {
+                anyhow::bail!("capture_frame needs a headless renderer, use capture_screenshot or render_to_image");
+            }
+            self.load_scene_assets(game);
+
+            unsafe {
+                self.device.device_wait_idle()?;
+
+                let msaa_samples = game.anti_aliasing_config.msaa_samples();
+                if msaa_samples != self.msaa_requested {
+                    self.apply_msaa_samples(msaa_samples)?;
+                }
+                game.max_anisotropy = self.max_anisotropy as u32;
+                if game.anti_aliasing_config.anisotropy != self.anisotropy_requested {
+                    self.apply_anisotropy(game.anti_aliasing_config.anisotropy)?;
+                }
+
+                let capture = self.create_capture_buffer(String::new(), self.swapchain_extent)?;
+                let result = self.record_captured_frame(self.swapchain_images[0], &capture, game)
+                    .and_then(|()| self.read_capture(&capture));
 
-                // SSAO Blur Pass 2: Vertical blur (intermediate -> final)
-                let ssao_blur_vertical_render_pass_info = vk::RenderPassBeginInfo::default()
-                    .render_pass(self.ssao_blur_render_pass)
-                    .framebuffer(self.ssao_blur_framebuffer)
-                    .render_area(vk::Rect2D {
-                        offset: vk::Offset2D { x: 0, y: 0 },
-                        extent: self.swapchain_extent,
-                    })
-                    .clear_values(&ssao_blur_clear_values);
+                self.device.destroy_buffer(capture.buffer, None);
+                self.device.free_memory(capture.memory, None);
+                result
+            }
+        }
 
-                self.device.cmd_begin_render_pass(
-                    command_buffer,
-                    &ssao_blur_vertical_render_pass_info,
-                    vk::SubpassContents::INLINE,
-                );
+        /// Render one headless frame and write it to `path` as PNG
+        pub fn save_frame(&mut self, path: &str, game: &mut crate::game::Game) -> anyhow::Result<()> {
+            let image = self.capture_frame(game)?;
+            Self::save_png(path, &image)
+        }
+        
+        /// Render the scene at any resolution (e.g. 4K or 8K posters) and save it as PNG.
+        /// The render targets and extent-baked pipelines are rebuilt at the requested size for
+        /// one offscreen frame, then restored to the window size. ImGui is not drawn, but edit-mode
+        /// overlays (gizmo, grid, view cube) are, so use play mode for a clean image.
+        pub fn render_to_image(&mut self, width: u32, height: u32, path: &str, game: &crate::game::Game) -> anyhow::Result<()> {
+            let limits = unsafe { self.instance.get_physical_device_properties(self.physical_device).limits };
+            let max_size = limits.max_image_dimension2_d
+                .min(limits.max_framebuffer_width)
+                .min(limits.max_framebuffer_height);
+            if width == 0 || height == 0 {
+                anyhow::bail!("Invalid image size {}x{}", width, height);
+            }
+            if width > max_size || height > max_size {
+                anyhow::bail!("{}x{} exceeds the GPU's maximum image size of {}", width, height, max_size);
+            }
 
-                self.device.cmd_bind_pipeline(
-                    command_buffer,
-                    vk::PipelineBindPoint::GRAPHICS,
-                    self.ssao_blur_pipeline,
-                );
+            unsafe {
+                self.device.device_wait_idle()?;
+
+                let extent = vk::Extent2D { width, height };
+                let (image, memory, view) = Self::create_msaa_image(
+                    &self.instance,
+                    self.physical_device,
+                    &self.device,
+                    extent,
+                    vk::SampleCountFlags::TYPE_1,
+                    self.swapchain_format,
+                    vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+                    vk::ImageAspectFlags::COLOR,
+                )?;
+                let capture = match self.create_capture_buffer(path.to_string(), extent) {
+                    Ok(capture) => capture,
+                    Err(e) => {
+                        self.device.destroy_image_view(view, None);
+                        self.device.destroy_image(image, None);
+                        self.device.free_memory(memory, None);
+                        return Err(e);
+                    }
+                };
 
-                self.device.cmd_bind_descriptor_sets(
-                    command_buffer,
-                    vk::PipelineBindPoint::GRAPHICS,
-                    self.ssao_blur_pipeline_layout,
-                    0,
-                    &[self.ssao_blur_descriptor_sets[self.current_frame]],
-                    &[],
-                );
+                let window_extent = self.swapchain_extent;
+                let result = self.record_offscreen_frame(extent, image, view, &capture, game)
+                    .and_then(|()| self.save_screenshot(&capture));
 
-                // Push constant: direction = 1 (vertical)
-                let vertical_direction: i32 = 1;
-                self.device.cmd_push_constants(
-                    command_buffer,
-                    self.ssao_blur_pipeline_layout,
-                    vk::ShaderStageFlags::FRAGMENT,
-                    0,
-                    std::slice::from_raw_parts(
-                        &vertical_direction as *const i32 as *const u8,
-                        std::mem::size_of::<i32>(),
-                    ),
-                );
+                // Back to the window-sized targets, even if the offscreen frame failed
+                self.device.device_wait_idle()?;
+                self.destroy_render_targets();
+                let swapchain_image_views = self.swapchain_image_views.clone();
+                let restored = self.create_render_targets(window_extent, &swapchain_image_views);
 
-                // Draw fullscreen triangle
-                self.device.cmd_draw(command_buffer, 3, 1, 0, 0);
+                self.device.destroy_buffer(capture.buffer, None);
+                self.device.free_memory(capture.memory, None);
+                self.device.destroy_image_view(view, None);
+                self.device.destroy_image(image, None);
+                self.device.free_memory(memory, None);
 
-                self.device.cmd_end_render_pass(command_buffer);
+                restored?;
+                result
             }
+        }
+
+        /// Render one frame into `image` at `extent` and copy it into the capture buffer
+        unsafe fn record_offscreen_frame(
+            &mut self,
+            extent: vk::Extent2D,
+            image: vk::Image,
+            view: vk::ImageView,
+            capture: &ScreenshotCapture,
+            game: &crate::game::Game,
+        ) -> anyhow::Result<()> {
+            self.destroy_render_targets();
+            self.create_render_targets(extent, &[view])?;
+            self.record_captured_frame(image, capture, game)
+        }
+
+        /// Render one frame with the current targets into `image` (the output image of
+        /// frame target 0) and copy it into the capture buffer, waiting for the GPU.
+        /// Uses the current frame's command buffer, which is idle between frames
+        unsafe fn record_captured_frame(
+            &mut self,
+            image: vk::Image,
+            capture: &ScreenshotCapture,
+            game: &crate::game::Game,
+        ) -> anyhow::Result<()> {
+            let extent = self.swapchain_extent;
+
+            // Pass state and uniforms for the current aspect ratio
+            let ctx = crate::core::RenderContext {
+                device: &self.device,
+                instance: &self.instance,
+                physical_device: self.physical_device,
+                command_pool: self.command_pool,
+                graphics_queue: self.graphics_queue,
+                extent,
+                pipeline_cache: &self.pipeline_cache,
+                msaa_samples: self.msaa_samples,
+                depth_image_view: Some(self.depth_image_view),
+                depth_sampler: Some(self.depth_sampler),
+                mesh_pipeline: Some(self.graphics_pipeline),
+                mesh_instanced_pipeline: Some(self.instanced_pipeline),
+                mesh_transparent_pipeline: Some(self.transparent_pipeline),
+                mesh_overdraw_pipeline: Some(self.overdraw_pipeline),
+                mesh_prepass_pipelines: Some(self.depth_prepass_pipelines),
+                mesh_pipeline_layout: Some(self.pipeline_layout),
+                mesh_descriptor_sets: Some(&self.descriptor_sets),
+                custom_meshes: Some(&self.custom_meshes),
+                primitive_meshes: Some(&self.primitive_meshes),
+                textures: Some(&self.texture_cache),
+            };
+            self.shadow_pass.update(&ctx, self.current_frame, game)?;
+            self.particle_pass.update(&ctx, self.current_frame, game)?;
+            self.bloom_pass.update(&ctx, self.current_frame, game)?;
+            self.tonemap_pass.update(&ctx, self.current_frame, game)?;
+            self.fxaa_pass.update(&ctx, self.current_frame, game)?;
+            self.render_passes.update_all(&ctx, self.current_frame, game)?;
+
+            self.update_uniform_buffer(self.current_frame, game)?;
+            self.update_gizmo_uniform_buffer(self.current_frame, game)?;
+            self.update_ssao_uniform_buffer(self.current_frame, game)?;
+
+            let command_buffer = self.command_buffers[self.current_frame];
+            self.device.reset_command_buffer(command_buffer, vk::CommandBufferResetFlags::empty())?;
+            let begin_info = vk::CommandBufferBeginInfo::default()
+                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+            self.device.begin_command_buffer(command_buffer, &begin_info)?;
+
+            self.record_scene(command_buffer, 0, game)?;
+            self.record_screenshot_copy(command_buffer, image, capture);
 
             self.device.end_command_buffer(command_buffer)?;
 
+            let submit_info = vk::SubmitInfo::default()
+                .command_buffers(std::slice::from_ref(&command_buffer));
+            self.device.queue_submit(self.graphics_queue, std::slice::from_ref(&submit_info), vk::Fence::null())?;
+            self.device.queue_wait_idle(self.graphics_queue)?;
+
             Ok(())
         }
         
@@ -3570,8 +6009,11 @@ impl VulkanRenderer {
                 // Wait for device to be idle before recreating resources
                 let _ = self.device.device_wait_idle();
                 
-                // Get new window size
-                let size = self.window.inner_size();
+                // Get new window size (headless output images never resize)
+                let Some(window_surface) = &self.window_surface else {
+                    return;
+                };
+                let size = window_surface.window.inner_size();
                 
                 // Don't recreate if minimized (width or height is 0)
                 if size.width == 0 || size.height == 0 {
@@ -3580,13 +6022,24 @@ impl VulkanRenderer {
                 
                 // Recreate swapchain with new size
                 if let Err(e) = self.recreate_swapchain() {
-                    eprintln!("Failed to recreate swapchain on resize: {}", e);
+                    crate::console::error(format!("Failed to recreate swapchain on resize: {}", e));
                 }
             }
         }
         
+        /// The window frames are presented to. Panics for headless renderers
         pub fn window(&self) -> &Window {
-            &self.window
+            &self.surface().window
+        }
+
+        /// The window surface of a windowed renderer (callers only run when presenting)
+        fn surface(&self) -> &WindowSurface {
+            self.window_surface.as_ref().expect("headless renderer has no window surface")
+        }
+
+        /// Whether this renderer draws offscreen without a window (see `new_headless`)
+        pub fn is_headless(&self) -> bool {
+            self.window_surface.is_none()
         }
 
         /// Get the actual render viewport dimensions (swapchain extent)
@@ -3610,79 +6063,159 @@ impl VulkanRenderer {
         pub fn build_ui(&mut self, game: &mut crate::game::Game) {
             let viewport_width = self.swapchain_extent.width as f32;
             let viewport_height = self.swapchain_extent.height as f32;
-            UiManager::build_ui(&mut self.imgui_context, game, viewport_width, viewport_height);
+            UiManager::build_ui(
+                &mut self.imgui_context,
+                game,
+                &self.frame_stats,
+                self.material_preview_texture,
+                self.minimap_texture,
+                self.thumbnail_pass.atlas(),
+                viewport_width,
+                viewport_height,
+            );
         }
         
         unsafe fn recreate_swapchain(&mut self) -> anyhow::Result<()> {
             self.device.device_wait_idle()?;
+
+            // Headless output images keep their size, only the targets built on them are rebuilt
+            if self.is_headless() {
+                self.destroy_render_targets();
+                let output_views = self.swapchain_image_views.clone();
+                self.create_render_targets(self.swapchain_extent, &output_views)?;
+                self.imgui_renderer.recreate_pipeline(&self.device, &self.pipeline_cache, self.overlay_render_pass, self.swapchain_extent)?;
+                return Ok(());
+            }
             
             self.cleanup_swapchain();
             
-            let (swapchain, swapchain_images, swapchain_format, swapchain_extent) =
+            let window_surface = self.surface();
+            let (swapchain, swapchain_images, swapchain_format, swapchain_extent, present_mode) =
             Self::create_swapchain(
-                &self.window,
+                &window_surface.window,
                 &self.instance,
                 self.physical_device,
                 &self.device,
-                &self.surface_loader,
-                self.surface,
+                &window_surface.surface_loader,
+                window_surface.surface,
                 &self.swapchain_loader,
+                Self::present_mode_khr(self.present_mode_requested),
             )?;
             
             let swapchain_image_views =
             Self::create_image_views(&self.device, &swapchain_images, swapchain_format)?;
-            
+
+            if let Some(window_surface) = &mut self.window_surface {
+                window_surface.swapchain = swapchain;
+            }
+            self.present_mode = present_mode;
+            self.swapchain_images = swapchain_images.clone();
+            self.swapchain_format = swapchain_format;
+            self.swapchain_image_views = swapchain_image_views.clone();
+            self.images_in_flight = vec![vk::Fence::null(); swapchain_images.len()];
+
+            self.create_render_targets(swapchain_extent, &swapchain_image_views)?;
+
+            // Recreate ImGui pipeline with new swapchain extent
+            self.imgui_renderer.recreate_pipeline(&self.device, &self.pipeline_cache, self.overlay_render_pass, swapchain_extent)?;
+
+            Ok(())
+        }
+
+        /// Create everything sized to the render extent: depth, MSAA and HDR targets, the
+        /// framebuffers, the pipelines with a baked-in viewport, and the passes' resources.
+        /// `output_views` are the images the tonemap pass writes (one per frame target),
+        /// normally the swapchain images. Sets `swapchain_extent` to `extent`.
+        /// Caller must make sure the GPU is idle and the previous targets are destroyed.
+        unsafe fn create_render_targets(&mut self, extent: vk::Extent2D, output_views: &[vk::ImageView]) -> anyhow::Result<()> {
             let (depth_image, depth_image_memory, depth_image_view) = Self::create_depth_resources(
                 &self.instance,
                 self.physical_device,
                 &self.device,
-                swapchain_extent,
+                extent,
+            )?;
+
+            let msaa_targets = Self::create_msaa_resources(
+                &self.instance,
+                self.physical_device,
+                &self.device,
+                extent,
+                self.msaa_samples,
+            )?;
+            
+            let (hdr_images, hdr_images_memory, hdr_image_views) = Self::create_hdr_resources(
+                &self.instance,
+                self.physical_device,
+                &self.device,
+                extent,
+                output_views.len(),
             )?;
             
             let framebuffers = Self::create_framebuffers(
                 &self.device,
-                &swapchain_image_views,
+                &hdr_image_views,
                 depth_image_view,
+                &msaa_targets,
                 self.render_pass,
-                swapchain_extent,
+                extent,
+            )?;
+            let swapchain_framebuffers = Self::create_swapchain_framebuffers(
+                &self.device,
+                output_views,
+                self.overlay_render_pass,
+                extent,
             )?;
             
             // Recreate main graphics pipeline with new extent
             self.device.destroy_pipeline(self.graphics_pipeline, None);
             self.device.destroy_pipeline(self.wireframe_pipeline, None);
+            self.device.destroy_pipeline(self.instanced_pipeline, None);
+            self.device.destroy_pipeline(self.transparent_pipeline, None);
+            self.device.destroy_pipeline(self.overdraw_pipeline, None);
+            self.depth_prepass_pipelines.destroy(&self.device);
             self.device.destroy_pipeline_layout(self.pipeline_layout, None);
             let (pipeline_layout, graphics_pipeline) =
-            Self::create_graphics_pipeline(&self.device, swapchain_extent, self.render_pass, self.descriptor_set_layout)?;
-            let wireframe_pipeline = Self::create_wireframe_pipeline(&self.device, swapchain_extent, self.render_pass, pipeline_layout)?;
+            Self::create_graphics_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, self.descriptor_set_layout, self.texture_cache.set_layout())?;
+            let wireframe_pipeline = Self::create_wireframe_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout)?;
+            let instanced_pipeline = Self::create_instanced_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout)?;
+            let transparent_pipeline = Self::create_transparent_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout)?;
+            let overdraw_pipeline = Self::create_overdraw_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout)?;
+            let depth_prepass_pipelines = Self::create_depth_prepass_pipelines(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout)?;
             self.pipeline_layout = pipeline_layout;
             self.graphics_pipeline = graphics_pipeline;
             self.wireframe_pipeline = wireframe_pipeline;
+            self.instanced_pipeline = instanced_pipeline;
+            self.transparent_pipeline = transparent_pipeline;
+            self.overdraw_pipeline = overdraw_pipeline;
+            self.depth_prepass_pipelines = depth_prepass_pipelines;
 
             // Recreate gizmo pipeline with new extent
             self.device.destroy_pipeline(self.gizmo_pipeline, None);
             self.device.destroy_pipeline_layout(self.gizmo_pipeline_layout, None);
             let (gizmo_pipeline_layout, gizmo_pipeline) =
-            Self::create_gizmo_pipeline(&self.device, swapchain_extent, self.render_pass, self.gizmo_descriptor_set_layout)?;
+            Self::create_gizmo_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, self.gizmo_descriptor_set_layout)?;
+            self.gizmo_pipeline_layout = gizmo_pipeline_layout;
+            self.gizmo_pipeline = gizmo_pipeline;
 
-            self.swapchain = swapchain;
-            self.swapchain_images = swapchain_images.clone();
-            self.swapchain_format = swapchain_format;
-            self.swapchain_extent = swapchain_extent;
-            self.swapchain_image_views = swapchain_image_views;
+            self.swapchain_extent = extent;
             self.depth_image = depth_image;
             self.depth_image_memory = depth_image_memory;
             self.depth_image_view = depth_image_view;
+            self.msaa_targets = msaa_targets;
+            self.hdr_images = hdr_images;
+            self.hdr_images_memory = hdr_images_memory;
+            self.hdr_image_views = hdr_image_views;
             self.framebuffers = framebuffers;
-            self.images_in_flight = vec![vk::Fence::null(); swapchain_images.len()];
-            
-            // Update pipelines
-            self.pipeline_layout = pipeline_layout;
-            self.graphics_pipeline = graphics_pipeline;
-            self.gizmo_pipeline_layout = gizmo_pipeline_layout;
-            self.gizmo_pipeline = gizmo_pipeline;
+            self.swapchain_framebuffers = swapchain_framebuffers;
 
-            // Recreate ImGui pipeline with new swapchain extent
-            self.imgui_renderer.recreate_pipeline(&self.device, self.render_pass, swapchain_extent)?;
+            // SSAO samples the new (resolved) depth image
+            self.update_depth_descriptors();
+
+            // SSR history follows the scene extent (empty until the next frame is copied)
+            self.reflection_history.resize(&self.instance, self.physical_device, &self.device, extent)?;
+            for sets in [&self.descriptor_sets, &self.gizmo_descriptor_sets, &self.view_cube_descriptor_sets] {
+                self.reflection_history.write_descriptors(&self.device, sets);
+            }
 
             // Update render passes with new pipeline and extent
             let ctx = crate::core::RenderContext {
@@ -3691,33 +6224,308 @@ impl VulkanRenderer {
                 physical_device: self.physical_device,
                 command_pool: self.command_pool,
                 graphics_queue: self.graphics_queue,
-                extent: swapchain_extent,
+                extent,
+                pipeline_cache: &self.pipeline_cache,
+                msaa_samples: self.msaa_samples,
                 depth_image_view: Some(depth_image_view),
                 depth_sampler: Some(self.depth_sampler),
                 mesh_pipeline: Some(graphics_pipeline),
+                mesh_instanced_pipeline: Some(instanced_pipeline),
+                mesh_transparent_pipeline: Some(transparent_pipeline),
+                mesh_overdraw_pipeline: Some(overdraw_pipeline),
+                mesh_prepass_pipelines: Some(depth_prepass_pipelines),
                 mesh_pipeline_layout: Some(pipeline_layout),
                 mesh_descriptor_sets: Some(&self.descriptor_sets),
                 custom_meshes: Some(&self.custom_meshes),
+                primitive_meshes: Some(&self.primitive_meshes),
+                textures: Some(&self.texture_cache),
+            };
+            self.render_passes.recreate_swapchain_all(&ctx, self.render_pass, extent)?;
+            self.particle_pass.recreate_swapchain(&ctx, self.render_pass, extent)?;
+            self.bloom_pass.resize(&ctx, &self.hdr_image_views)?;
+            self.tonemap_pass.resize(&ctx, &self.hdr_image_views)?;
+            self.fxaa_pass.resize(&ctx, self.hdr_image_views.len())?;
+
+            Ok(())
+        }
+        
+        /// Switch the main pass to a new MSAA level (clamped to the GPU maximum)
+        /// Recreates the render pass, then rebuilds targets and pipelines like a resize
+        unsafe fn apply_msaa_samples(&mut self, requested: u32) -> anyhow::Result<()> {
+            self.msaa_requested = requested;
+            let samples = Self::clamp_msaa_samples(requested, self.max_msaa_samples);
+            if samples == self.msaa_samples {
+                return Ok(());
+            }
+
+            self.device.device_wait_idle()?;
+            self.device.destroy_render_pass(self.render_pass, None);
+            self.render_pass = Self::create_render_pass(&self.device, HDR_FORMAT, samples)?;
+            self.msaa_samples = samples;
+            println!("MSAA set to {}x (requested {}x)", samples.as_raw(), requested);
+
+            self.recreate_swapchain()
+        }
+
+        /// Recreate the swapchain with the game's present mode, telling the user if it fell back to FIFO
+        unsafe fn apply_present_mode(&mut self, game: &mut crate::game::Game) -> anyhow::Result<()> {
+            let requested = game.graphics_config.present_mode;
+            self.present_mode_requested = requested;
+            self.recreate_swapchain()?;
+
+            if self.present_mode != Self::present_mode_khr(requested) {
+                game.add_notification(
+                    format!("{} isn't supported by this display, using Vsync (FIFO)", requested.name()),
+                    3.0,
+                );
+            }
+            crate::console::info(format!("Present mode set to {:?} (requested {})", self.present_mode, requested.name()));
+            Ok(())
+        }
+
+        /// Switch material textures to a new anisotropy level (clamped to the GPU maximum)
+        unsafe fn apply_anisotropy(&mut self, requested: u32) -> anyhow::Result<()> {
+            self.anisotropy_requested = requested;
+            let anisotropy = (requested as f32).clamp(1.0, self.max_anisotropy);
+
+            // Material descriptor sets are rewritten, so no frame may still be using them
+            self.device.device_wait_idle()?;
+            self.texture_cache.set_anisotropy(&self.device, anisotropy)?;
+            crate::console::info(format!("Anisotropic filtering set to {}x (requested {}x)", anisotropy, requested));
+            Ok(())
+        }
+
+        /// Recompile changed shaders in `shaders/` and rebuild every pipeline from the new SPIR-V
+        /// Compile errors abort the reload, so the old pipelines stay in use until the shader is fixed
+        pub fn reload_shaders(&mut self) -> crate::core::shader::ShaderCompileReport {
+            let mut report = crate::core::shader::compile_changed(std::path::Path::new(crate::core::shader::SHADER_DIR));
+            if report.has_errors() {
+                return report;
+            }
+
+            unsafe {
+                if let Err(e) = self.device.device_wait_idle() {
+                    report.errors.push(format!("Failed to wait for device idle: {}", e));
+                    return report;
+                }
+                // Modules are keyed by their SPIR-V, so the old ones would only pile up
+                self.pipeline_cache.clear_shader_modules(&self.device);
+
+                if let Err(e) = self.recreate_renderer_pipelines() {
+                    report.errors.push(format!("Renderer pipelines: {}", e));
+                }
+                if let Err(e) = self.imgui_renderer.recreate_pipeline(&self.device, &self.pipeline_cache, self.overlay_render_pass, self.swapchain_extent) {
+                    report.errors.push(format!("ImGui: {}", e));
+                }
+            }
+
+            let ctx = crate::core::RenderContext {
+                device: &self.device,
+                instance: &self.instance,
+                physical_device: self.physical_device,
+                command_pool: self.command_pool,
+                graphics_queue: self.graphics_queue,
+                extent: self.swapchain_extent,
+                pipeline_cache: &self.pipeline_cache,
+                msaa_samples: self.msaa_samples,
+                depth_image_view: Some(self.depth_image_view),
+                depth_sampler: Some(self.depth_sampler),
+                mesh_pipeline: Some(self.graphics_pipeline),
+                mesh_instanced_pipeline: Some(self.instanced_pipeline),
+                mesh_transparent_pipeline: Some(self.transparent_pipeline),
+                mesh_overdraw_pipeline: Some(self.overdraw_pipeline),
+                mesh_prepass_pipelines: Some(self.depth_prepass_pipelines),
+                mesh_pipeline_layout: Some(self.pipeline_layout),
+                mesh_descriptor_sets: Some(&self.descriptor_sets),
+                custom_meshes: Some(&self.custom_meshes),
+                primitive_meshes: Some(&self.primitive_meshes),
+                textures: Some(&self.texture_cache),
+            };
+            let mut failures = self.render_passes.recreate_pipelines_all(&ctx, self.render_pass, self.swapchain_extent);
+            for pass in [
+                &mut self.shadow_pass as &mut dyn RenderPass,
+                &mut self.particle_pass,
+                &mut self.bloom_pass,
+                &mut self.tonemap_pass,
+                &mut self.fxaa_pass,
+                &mut self.material_preview_pass,
+                &mut self.minimap_pass,
+                &mut self.thumbnail_pass,
+            ] {
+                if let Err(e) = pass.recreate_pipeline(&ctx, self.render_pass, self.swapchain_extent) {
+                    failures.push((pass.name().to_string(), e.to_string()));
+                }
+            }
+            for (pass_name, error) in failures {
+                report.errors.push(format!("{}: {}", pass_name, error));
+            }
+
+            report
+        }
+
+        /// Rebuild the pipelines owned directly by the renderer (mesh, wireframe, instanced, transparent, overdraw, depth prepass, gizmo, SSAO)
+        /// New pipelines are created before the old ones are destroyed, so a failure leaves them intact
+        unsafe fn recreate_renderer_pipelines(&mut self) -> anyhow::Result<()> {
+            let extent = self.swapchain_extent;
+
+            let (pipeline_layout, graphics_pipeline) =
+            Self::create_graphics_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, self.descriptor_set_layout, self.texture_cache.set_layout())?;
+            let wireframe_pipeline = match Self::create_wireframe_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout) {
+                Ok(pipeline) => pipeline,
+                Err(e) => {
+                    self.device.destroy_pipeline(graphics_pipeline, None);
+                    self.device.destroy_pipeline_layout(pipeline_layout, None);
+                    return Err(e);
+                }
             };
-            self.render_passes.recreate_swapchain_all(&ctx, self.render_pass, swapchain_extent)?;
+            let instanced_pipeline = match Self::create_instanced_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout) {
+                Ok(pipeline) => pipeline,
+                Err(e) => {
+                    self.device.destroy_pipeline(wireframe_pipeline, None);
+                    self.device.destroy_pipeline(graphics_pipeline, None);
+                    self.device.destroy_pipeline_layout(pipeline_layout, None);
+                    return Err(e);
+                }
+            };
+            let transparent_pipeline = match Self::create_transparent_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout) {
+                Ok(pipeline) => pipeline,
+                Err(e) => {
+                    self.device.destroy_pipeline(instanced_pipeline, None);
+                    self.device.destroy_pipeline(wireframe_pipeline, None);
+                    self.device.destroy_pipeline(graphics_pipeline, None);
+                    self.device.destroy_pipeline_layout(pipeline_layout, None);
+                    return Err(e);
+                }
+            };
+            let overdraw_pipeline = match Self::create_overdraw_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout) {
+                Ok(pipeline) => pipeline,
+                Err(e) => {
+                    self.device.destroy_pipeline(transparent_pipeline, None);
+                    self.device.destroy_pipeline(instanced_pipeline, None);
+                    self.device.destroy_pipeline(wireframe_pipeline, None);
+                    self.device.destroy_pipeline(graphics_pipeline, None);
+                    self.device.destroy_pipeline_layout(pipeline_layout, None);
+                    return Err(e);
+                }
+            };
+            let depth_prepass_pipelines = match Self::create_depth_prepass_pipelines(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, pipeline_layout) {
+                Ok(pipelines) => pipelines,
+                Err(e) => {
+                    self.device.destroy_pipeline(overdraw_pipeline, None);
+                    self.device.destroy_pipeline(transparent_pipeline, None);
+                    self.device.destroy_pipeline(instanced_pipeline, None);
+                    self.device.destroy_pipeline(wireframe_pipeline, None);
+                    self.device.destroy_pipeline(graphics_pipeline, None);
+                    self.device.destroy_pipeline_layout(pipeline_layout, None);
+                    return Err(e);
+                }
+            };
+            self.device.destroy_pipeline(self.graphics_pipeline, None);
+            self.device.destroy_pipeline(self.wireframe_pipeline, None);
+            self.device.destroy_pipeline(self.instanced_pipeline, None);
+            self.device.destroy_pipeline(self.transparent_pipeline, None);
+            self.device.destroy_pipeline(self.overdraw_pipeline, None);
+            self.depth_prepass_pipelines.destroy(&self.device);
+            self.device.destroy_pipeline_layout(self.pipeline_layout, None);
+            self.pipeline_layout = pipeline_layout;
+            self.graphics_pipeline = graphics_pipeline;
+            self.wireframe_pipeline = wireframe_pipeline;
+            self.instanced_pipeline = instanced_pipeline;
+            self.transparent_pipeline = transparent_pipeline;
+            self.overdraw_pipeline = overdraw_pipeline;
+            self.depth_prepass_pipelines = depth_prepass_pipelines;
+
+            let (gizmo_pipeline_layout, gizmo_pipeline) =
+            Self::create_gizmo_pipeline(&self.device, &self.pipeline_cache, extent, self.render_pass, self.msaa_samples, self.gizmo_descriptor_set_layout)?;
+            self.device.destroy_pipeline(self.gizmo_pipeline, None);
+            self.device.destroy_pipeline_layout(self.gizmo_pipeline_layout, None);
+            self.gizmo_pipeline_layout = gizmo_pipeline_layout;
+            self.gizmo_pipeline = gizmo_pipeline;
+
+            let (ssao_pipeline_layout, ssao_pipeline) =
+            Self::create_ssao_pipeline(&self.device, &self.pipeline_cache, self.ssao_extent, self.ssao_render_pass, self.ssao_descriptor_set_layout)?;
+            self.device.destroy_pipeline(self.ssao_pipeline, None);
+            self.device.destroy_pipeline_layout(self.ssao_pipeline_layout, None);
+            self.ssao_pipeline_layout = ssao_pipeline_layout;
+            self.ssao_pipeline = ssao_pipeline;
+
+            let (ssao_blur_pipeline_layout, ssao_blur_pipeline) =
+            Self::create_ssao_blur_pipeline(&self.device, &self.pipeline_cache, self.ssao_extent, self.ssao_blur_render_pass, self.ssao_blur_descriptor_set_layout)?;
+            self.device.destroy_pipeline(self.ssao_blur_pipeline, None);
+            self.device.destroy_pipeline_layout(self.ssao_blur_pipeline_layout, None);
+            self.ssao_blur_pipeline_layout = ssao_blur_pipeline_layout;
+            self.ssao_blur_pipeline = ssao_blur_pipeline;
 
             Ok(())
         }
+
+        /// Point the SSAO and SSAO blur depth bindings at the current depth image
+        unsafe fn update_depth_descriptors(&self) {
+            let depth_image_info = vk::DescriptorImageInfo::default()
+                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .image_view(self.depth_image_view)
+                .sampler(self.depth_sampler);
+
+            let descriptor_sets = self.ssao_descriptor_sets.iter()
+                .chain(self.ssao_blur_descriptor_sets.iter())
+                .chain(self.ssao_blur_horizontal_descriptor_sets.iter());
+            for &descriptor_set in descriptor_sets {
+                let descriptor_write = vk::WriteDescriptorSet::default()
+                    .dst_set(descriptor_set)
+                    .dst_binding(1)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&depth_image_info));
+
+                self.device.update_descriptor_sets(std::slice::from_ref(&descriptor_write), &[]);
+            }
+        }
         
         unsafe fn cleanup_swapchain(&mut self) {
+            self.destroy_render_targets();
+            
+            for &image_view in &self.swapchain_image_views {
+                self.device.destroy_image_view(image_view, None);
+            }
+            
+            match &self.window_surface {
+                Some(window_surface) => self.swapchain_loader.destroy_swapchain(window_surface.swapchain, None),
+                None => {
+                    for (&image, &memory) in self.swapchain_images.iter().zip(&self.offscreen_images_memory) {
+                        self.device.destroy_image(image, None);
+                        self.device.free_memory(memory, None);
+                    }
+                }
+            }
+        }
+
+        /// Destroy the extent-sized targets made by `create_render_targets` (pipelines are kept)
+        /// Handles are reset, so a second call after a failed `create_render_targets` is harmless
+        unsafe fn destroy_render_targets(&mut self) {
             self.device.destroy_image_view(self.depth_image_view, None);
             self.device.destroy_image(self.depth_image, None);
             self.device.free_memory(self.depth_image_memory, None);
+            self.depth_image_view = vk::ImageView::null();
+            self.depth_image = vk::Image::null();
+            self.depth_image_memory = vk::DeviceMemory::null();
+            self.msaa_targets.destroy(&self.device);
             
-            for &framebuffer in &self.framebuffers {
+            for framebuffer in self.framebuffers.drain(..) {
                 self.device.destroy_framebuffer(framebuffer, None);
             }
             
-            for &image_view in &self.swapchain_image_views {
-                self.device.destroy_image_view(image_view, None);
+            for framebuffer in self.swapchain_framebuffers.drain(..) {
+                self.device.destroy_framebuffer(framebuffer, None);
             }
             
-            self.swapchain_loader.destroy_swapchain(self.swapchain, None);
+            for ((image_view, image), memory) in self.hdr_image_views.drain(..)
+                .zip(self.hdr_images.drain(..))
+                .zip(self.hdr_images_memory.drain(..))
+            {
+                self.device.destroy_image_view(image_view, None);
+                self.device.destroy_image(image, None);
+                self.device.free_memory(memory, None);
+            }
         }
     }
     
@@ -3729,6 +6537,41 @@ impl VulkanRenderer {
                 
                 // Cleanup ImGui
                 self.imgui_renderer.cleanup(&self.device);
+
+                // Cleanup the registered passes (background, skybox, nebula, mesh, star, unlit,
+                // grid, wireframe, outline, lines) before the shared resources they reference
+                self.render_passes.cleanup_all(&self.device);
+
+                // Cleanup shadow map pass
+                self.shadow_pass.cleanup(&self.device);
+
+                // Cleanup particle pass
+                self.particle_pass.cleanup(&self.device);
+
+                // Cleanup environment lighting maps
+                self.environment.cleanup(&self.device);
+                self.reflection_history.cleanup(&self.device);
+
+                // Cleanup bloom pass
+                self.bloom_pass.cleanup(&self.device);
+
+                // Cleanup tonemap pass
+                self.tonemap_pass.cleanup(&self.device);
+
+                // Cleanup FXAA pass
+                self.fxaa_pass.cleanup(&self.device);
+
+                // Cleanup material preview pass
+                self.material_preview_pass.cleanup(&self.device);
+
+                // Cleanup minimap pass
+                self.minimap_pass.cleanup(&self.device);
+
+                // Cleanup thumbnail pass
+                self.thumbnail_pass.cleanup(&self.device);
+
+                // Cleanup material textures
+                self.texture_cache.cleanup(&self.device);
                 
                 self.cleanup_swapchain();
                 
@@ -3741,6 +6584,8 @@ impl VulkanRenderer {
                 for i in 0..MAX_FRAMES_IN_FLIGHT {
                     self.device.destroy_buffer(self.uniform_buffers[i], None);
                     self.device.free_memory(self.uniform_buffers_memory[i], None);
+                    self.device.destroy_buffer(self.point_light_buffers[i], None);
+                    self.device.free_memory(self.point_light_buffers_memory[i], None);
                 }
                 
                 self.device.destroy_descriptor_pool(self.descriptor_pool, None);
@@ -3751,6 +6596,15 @@ impl VulkanRenderer {
                     self.device.destroy_buffer(self.gizmo_uniform_buffers[i], None);
                     self.device.free_memory(self.gizmo_uniform_buffers_memory[i], None);
                 }
+                for i in 0..MAX_FRAMES_IN_FLIGHT {
+                    self.device.destroy_buffer(self.view_cube_uniform_buffers[i], None);
+                    self.device.free_memory(self.view_cube_uniform_buffers_memory[i], None);
+                }
+                self.device.destroy_descriptor_pool(self.view_cube_descriptor_pool, None);
+                self.device.destroy_buffer(self.view_cube_index_buffer, None);
+                self.device.free_memory(self.view_cube_index_buffer_memory, None);
+                self.device.destroy_buffer(self.view_cube_vertex_buffer, None);
+                self.device.free_memory(self.view_cube_vertex_buffer_memory, None);
                 self.device.destroy_descriptor_set_layout(self.gizmo_descriptor_set_layout, None);
                 self.device.destroy_pipeline(self.gizmo_pipeline, None);
                 self.device.destroy_pipeline_layout(self.gizmo_pipeline_layout, None);
@@ -3768,6 +6622,14 @@ impl VulkanRenderer {
                     self.device.free_memory(index_memory, None);
                 }
 
+                // Cleanup built-in primitive meshes
+                for (_shape, (_mesh, vertex_buffer, vertex_memory, index_buffer, index_memory)) in self.primitive_meshes.drain() {
+                    self.device.destroy_buffer(vertex_buffer, None);
+                    self.device.free_memory(vertex_memory, None);
+                    self.device.destroy_buffer(index_buffer, None);
+                    self.device.free_memory(index_memory, None);
+                }
+
                 // Cleanup directional light visualization
                 self.device.destroy_buffer(self.dir_light_index_buffer, None);
                 self.device.free_memory(self.dir_light_index_buffer_memory, None);
@@ -3823,21 +6685,29 @@ impl VulkanRenderer {
                     self.device.destroy_fence(self.in_flight_fences[i], None);
                 }
 
-                // Star shader resources now cleaned up by StarPass plugin
+                // Star shader resources were cleaned up with the other registered passes
 
                 self.device.destroy_command_pool(self.command_pool, None);
                 self.device.destroy_pipeline(self.graphics_pipeline, None);
                 self.device.destroy_pipeline(self.wireframe_pipeline, None);
+                self.device.destroy_pipeline(self.instanced_pipeline, None);
+                self.device.destroy_pipeline(self.transparent_pipeline, None);
+                self.device.destroy_pipeline(self.overdraw_pipeline, None);
+                self.depth_prepass_pipelines.destroy(&self.device);
                 self.device.destroy_pipeline_layout(self.pipeline_layout, None);
+                self.device.destroy_render_pass(self.overlay_render_pass, None);
                 self.device.destroy_render_pass(self.render_pass, None);
 
+                self.pipeline_cache.destroy(&self.device);
                 self.device.destroy_device(None);
                 
                 if let Some((debug_utils, messenger)) = self.debug_utils.take() {
                     debug_utils.destroy_debug_utils_messenger(messenger, None);
                 }
                 
-                self.surface_loader.destroy_surface(self.surface, None);
+                if let Some(window_surface) = &self.window_surface {
+                    window_surface.surface_loader.destroy_surface(window_surface.surface, None);
+                }
                 self.instance.destroy_instance(None);
             }
         }
@@ -3870,10 +6740,10 @@ impl VulkanRenderer {
         
         match message_severity {
             vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => {
-                eprintln!("[Vulkan Error {:?}] {}", message_type, message);
+                crate::console::error(format!("[Vulkan {:?}] {}", message_type, message));
             }
             vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => {
-                eprintln!("[Vulkan Warning {:?}] {}", message_type, message);
+                crate::console::warn(format!("[Vulkan {:?}] {}", message_type, message));
             }
             _ => {
                 println!("[Vulkan Info {:?}] {}", message_type, message);
@@ -0,0 +1,149 @@
+//! Runtime SPIR-V loading and recompilation for shader hot-reload
+//!
+//! Pipelines load their shaders through `load_spirv`, which prefers the `.spv`
+//! files in `SHADER_DIR` so a reload picks up freshly compiled code, and falls
+//! back to the copy embedded at build time when the file is missing.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directory shaders are loaded from and compiled in (relative to the working directory)
+pub const SHADER_DIR: &str = "shaders";
+
+/// Result of recompiling the shader sources
+#[derive(Debug, Default)]
+pub struct ShaderCompileReport {
+    /// Sources that were recompiled successfully
+    pub compiled: Vec<String>,
+    /// Compiler output of sources that failed to compile
+    pub errors: Vec<String>,
+    /// False if glslc could not be run (prebuilt `.spv` files are reloaded as-is)
+    pub glslc_found: bool,
+}
+
+impl ShaderCompileReport {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+/// Load a compiled shader (e.g. "mesh.vert.spv") from `SHADER_DIR`,
+/// falling back to the embedded build-time copy
+pub fn load_spirv(file_name: &str, embedded: &[u8]) -> Vec<u8> {
+    load_spirv_from(Path::new(SHADER_DIR), file_name, embedded)
+}
+
+fn load_spirv_from(dir: &Path, file_name: &str, embedded: &[u8]) -> Vec<u8> {
+    match std::fs::read(dir.join(file_name)) {
+        // SPIR-V is a stream of 32-bit words
+        Ok(code) if !code.is_empty() && code.len() % 4 == 0 => code,
+        _ => embedded.to_vec(),
+    }
+}
+
+/// Whether a source needs compiling: its `.spv` is missing or older than the source
+fn needs_compile(source: &Path, spirv: &Path) -> bool {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+
+    match (modified(source), modified(spirv)) {
+        (Some(source_time), Some(spirv_time)) => source_time > spirv_time,
+        _ => true,
+    }
+}
+
+/// Output path of a shader source (`mesh.vert` -> `mesh.vert.spv`)
+fn spirv_path(source: &Path) -> PathBuf {
+    let mut path = source.as_os_str().to_owned();
+    path.push(".spv");
+    PathBuf::from(path)
+}
+
+/// Recompile every `.vert`/`.frag`/`.comp` in `dir` whose SPIR-V is out of date using glslc
+/// Failed sources keep their previous `.spv` and are reported in `errors`
+pub fn compile_changed(dir: &Path) -> ShaderCompileReport {
+    let mut report = ShaderCompileReport {
+        glslc_found: true,
+        ..Default::default()
+    };
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            report.errors.push(format!("Failed to read shader directory {}: {}", dir.display(), e));
+            return report;
+        }
+    };
+
+    let mut sources: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("vert") | Some("frag") | Some("comp")))
+        .collect();
+    sources.sort();
+
+    for source in sources {
+        let spirv = spirv_path(&source);
+        if !needs_compile(&source, &spirv) {
+            continue;
+        }
+
+        // Compile to a temporary file so a failed build never clobbers the working .spv
+        let staging = spirv.with_extension("spv.tmp");
+        let output = match Command::new("glslc").arg(&source).arg("-o").arg(&staging).output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                report.glslc_found = false;
+                return report;
+            }
+            Err(e) => {
+                report.errors.push(format!("{}: failed to run glslc: {}", source.display(), e));
+                continue;
+            }
+        };
+
+        if output.status.success() {
+            if let Err(e) = std::fs::rename(&staging, &spirv) {
+                report.errors.push(format!("{}: failed to write {}: {}", source.display(), spirv.display(), e));
+            } else {
+                report.compiled.push(source.display().to_string());
+            }
+        } else {
+            let _ = std::fs::remove_file(&staging);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            report.errors.push(stderr.trim().to_string());
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_spirv_falls_back_to_embedded() {
+        let dir = std::env::temp_dir().join(format!("tribal_shader_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let embedded = [3u8, 2, 35, 7];
+
+        // Missing file uses the embedded copy
+        assert_eq!(load_spirv_from(&dir, "missing.vert.spv", &embedded), embedded.to_vec());
+
+        // Truncated SPIR-V (not whole words) is rejected
+        std::fs::write(dir.join("broken.vert.spv"), [1u8, 2, 3]).unwrap();
+        assert_eq!(load_spirv_from(&dir, "broken.vert.spv", &embedded), embedded.to_vec());
+
+        // A valid file on disk wins
+        let on_disk = [3u8, 2, 35, 7, 0, 0, 1, 0];
+        std::fs::write(dir.join("fresh.vert.spv"), on_disk).unwrap();
+        assert_eq!(load_spirv_from(&dir, "fresh.vert.spv", &embedded), on_disk.to_vec());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_spirv_path_appends_extension() {
+        assert_eq!(spirv_path(Path::new("shaders/mesh.vert")), PathBuf::from("shaders/mesh.vert.spv"));
+        assert!(needs_compile(Path::new("shaders/does_not_exist.frag"), Path::new("shaders/does_not_exist.frag.spv")));
+    }
+}
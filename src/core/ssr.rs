@@ -0,0 +1,405 @@
+use anyhow::Result;
+use ash::vk;
+use glam::Mat4;
+
+use crate::core::passes::tonemap::HDR_FORMAT;
+use crate::core::{ResourceManager, DEPTH_FORMAT};
+
+/// One history target (color or depth) at the scene's extent
+struct HistoryImage {
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+    view: vk::ImageView,
+}
+
+impl HistoryImage {
+    unsafe fn new(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        extent: vk::Extent2D,
+        format: vk::Format,
+        aspect_mask: vk::ImageAspectFlags,
+    ) -> Result<Self> {
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D {
+                width: extent.width,
+                height: extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .format(format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .samples(vk::SampleCountFlags::TYPE_1);
+
+        let image = device.create_image(&image_info, None)?;
+        let mem_requirements = device.get_image_memory_requirements(image);
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(ResourceManager::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+        let memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, memory, 0)?;
+
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(subresource_range(aspect_mask));
+        let view = device.create_image_view(&view_info, None)?;
+
+        Ok(Self { image, memory, view })
+    }
+
+    unsafe fn destroy(&self, device: &ash::Device) {
+        device.destroy_image_view(self.view, None);
+        device.destroy_image(self.image, None);
+        device.free_memory(self.memory, None);
+    }
+}
+
+fn subresource_range(aspect_mask: vk::ImageAspectFlags) -> vk::ImageSubresourceRange {
+    vk::ImageSubresourceRange {
+        aspect_mask,
+        base_mip_level: 0,
+        level_count: 1,
+        base_array_layer: 0,
+        layer_count: 1,
+    }
+}
+
+fn layout_barrier(
+    image: vk::Image,
+    aspect_mask: vk::ImageAspectFlags,
+    old_layout: vk::ImageLayout,
+    new_layout: vk::ImageLayout,
+    src_access_mask: vk::AccessFlags,
+    dst_access_mask: vk::AccessFlags,
+) -> vk::ImageMemoryBarrier<'static> {
+    vk::ImageMemoryBarrier::default()
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(subresource_range(aspect_mask))
+        .src_access_mask(src_access_mask)
+        .dst_access_mask(dst_access_mask)
+}
+
+/// The finished frame copied into the history by `ReflectionHistory::record_copy`
+pub struct HistoryCopyInfo {
+    /// Resolved HDR scene color
+    pub scene_color: vk::Image,
+    /// Single-sample scene depth
+    pub scene_depth: vk::Image,
+    pub extent: vk::Extent2D,
+    /// Camera the frame was rendered with
+    pub view: Mat4,
+    pub proj: Mat4,
+}
+
+/// Last frame's scene color and depth for screen-space reflections.
+///
+/// The main pass can't sample the targets it is drawing, so mesh.frag marches reflected rays
+/// against copies made after the previous frame's main pass, projecting with that frame's
+/// camera. Rays that leave the history (or miss) fall back to the environment maps.
+pub struct ReflectionHistory {
+    color: HistoryImage,
+    depth: HistoryImage,
+    color_sampler: vk::Sampler,
+    depth_sampler: vk::Sampler,
+    /// Layouts have been set up for sampling (recorded into the first frame)
+    initialized: bool,
+    /// A frame has been copied since the targets were (re)created
+    valid: bool,
+    /// Camera the history was rendered with
+    view: Mat4,
+    proj: Mat4,
+}
+
+impl ReflectionHistory {
+    pub unsafe fn new(
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        extent: vk::Extent2D,
+    ) -> Result<Self> {
+        // Depth must not be filtered (and linear filtering of depth formats is optional)
+        let sampler_info = |filter: vk::Filter| {
+            vk::SamplerCreateInfo::default()
+                .mag_filter(filter)
+                .min_filter(filter)
+                .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+                .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+                .max_lod(0.0)
+        };
+
+        Ok(Self {
+            color: HistoryImage::new(instance, physical_device, device, extent, HDR_FORMAT, vk::ImageAspectFlags::COLOR)?,
+            depth: HistoryImage::new(instance, physical_device, device, extent, DEPTH_FORMAT, vk::ImageAspectFlags::DEPTH)?,
+            color_sampler: device.create_sampler(&sampler_info(vk::Filter::LINEAR), None)?,
+            depth_sampler: device.create_sampler(&sampler_info(vk::Filter::NEAREST), None)?,
+            initialized: false,
+            valid: false,
+            view: Mat4::IDENTITY,
+            proj: Mat4::IDENTITY,
+        })
+    }
+
+    /// Recreate the history at a new scene extent; descriptors must be written again.
+    /// Caller must make sure the GPU is idle.
+    pub unsafe fn resize(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        extent: vk::Extent2D,
+    ) -> Result<()> {
+        self.color.destroy(device);
+        self.depth.destroy(device);
+        self.color = HistoryImage::new(instance, physical_device, device, extent, HDR_FORMAT, vk::ImageAspectFlags::COLOR)?;
+        self.depth = HistoryImage::new(instance, physical_device, device, extent, DEPTH_FORMAT, vk::ImageAspectFlags::DEPTH)?;
+        self.initialized = false;
+        self.valid = false;
+        Ok(())
+    }
+
+    /// Whether mesh.frag may trace against the history this frame
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// View and projection of the frame in the history
+    pub fn camera(&self) -> (Mat4, Mat4) {
+        (self.view, self.proj)
+    }
+
+    /// Forget the history (SSR turned off, so it stops being refreshed)
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    /// Point bindings 6 (history color) and 7 (history depth) of `descriptor_sets` at the history
+    pub unsafe fn write_descriptors(&self, device: &ash::Device, descriptor_sets: &[vk::DescriptorSet]) {
+        let color_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .image_view(self.color.view)
+            .sampler(self.color_sampler);
+        let depth_info = vk::DescriptorImageInfo::default()
+            .image_layout(vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL)
+            .image_view(self.depth.view)
+            .sampler(self.depth_sampler);
+
+        for &descriptor_set in descriptor_sets {
+            let descriptor_writes = [
+                vk::WriteDescriptorSet::default()
+                    .dst_set(descriptor_set)
+                    .dst_binding(6)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&color_info)),
+                vk::WriteDescriptorSet::default()
+                    .dst_set(descriptor_set)
+                    .dst_binding(7)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(&depth_info)),
+            ];
+
+            device.update_descriptor_sets(&descriptor_writes, &[]);
+        }
+    }
+
+    /// Move freshly created history images into their sampled layouts. Record before the
+    /// main pass; does nothing after the first frame.
+    pub unsafe fn prepare(&mut self, device: &ash::Device, command_buffer: vk::CommandBuffer) {
+        if self.initialized {
+            return;
+        }
+        let barriers = [
+            layout_barrier(
+                self.color.image,
+                vk::ImageAspectFlags::COLOR,
+                vk::ImageLayout::UNDEFINED,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::empty(),
+                vk::AccessFlags::SHADER_READ,
+            ),
+            layout_barrier(
+                self.depth.image,
+                vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
+                vk::ImageLayout::UNDEFINED,
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::empty(),
+                vk::AccessFlags::SHADER_READ,
+            ),
+        ];
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &barriers,
+        );
+        self.initialized = true;
+    }
+
+    /// Copy the finished scene into the history for the next frame. Record after the main pass
+    /// ends: `scene_color` in SHADER_READ_ONLY_OPTIMAL and `scene_depth` (single-sample) in
+    /// DEPTH_STENCIL_ATTACHMENT_OPTIMAL, which is where they are left afterwards.
+    pub unsafe fn record_copy(&mut self, device: &ash::Device, command_buffer: vk::CommandBuffer, frame: &HistoryCopyInfo) {
+        let HistoryCopyInfo { scene_color, scene_depth, extent, view, proj } = *frame;
+        let depth_aspect = vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL;
+
+        // Scene targets become copy sources; the history stops being sampled by the main pass
+        let to_transfer = [
+            layout_barrier(
+                scene_color,
+                vk::ImageAspectFlags::COLOR,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+                vk::AccessFlags::TRANSFER_READ,
+            ),
+            layout_barrier(
+                scene_depth,
+                depth_aspect,
+                vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+                vk::AccessFlags::TRANSFER_READ,
+            ),
+            layout_barrier(
+                self.color.image,
+                vk::ImageAspectFlags::COLOR,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::SHADER_READ,
+                vk::AccessFlags::TRANSFER_WRITE,
+            ),
+            layout_barrier(
+                self.depth.image,
+                depth_aspect,
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::AccessFlags::SHADER_READ,
+                vk::AccessFlags::TRANSFER_WRITE,
+            ),
+        ];
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS
+                | vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &to_transfer,
+        );
+
+        let copy_region = |aspect_mask: vk::ImageAspectFlags| {
+            let layers = vk::ImageSubresourceLayers {
+                aspect_mask,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            };
+            vk::ImageCopy::default()
+                .src_subresource(layers)
+                .dst_subresource(layers)
+                .extent(vk::Extent3D {
+                    width: extent.width,
+                    height: extent.height,
+                    depth: 1,
+                })
+        };
+        device.cmd_copy_image(
+            command_buffer,
+            scene_color,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            self.color.image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[copy_region(vk::ImageAspectFlags::COLOR)],
+        );
+        // Stencil (outline masks) isn't needed
+        device.cmd_copy_image(
+            command_buffer,
+            scene_depth,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            self.depth.image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[copy_region(vk::ImageAspectFlags::DEPTH)],
+        );
+
+        // Back to where post-processing (and the next frame's main pass) expect them
+        let to_sampled = [
+            layout_barrier(
+                scene_color,
+                vk::ImageAspectFlags::COLOR,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::TRANSFER_READ,
+                vk::AccessFlags::SHADER_READ,
+            ),
+            layout_barrier(
+                scene_depth,
+                depth_aspect,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+                vk::AccessFlags::TRANSFER_READ,
+                vk::AccessFlags::SHADER_READ,
+            ),
+            layout_barrier(
+                self.color.image,
+                vk::ImageAspectFlags::COLOR,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE,
+                vk::AccessFlags::SHADER_READ,
+            ),
+            layout_barrier(
+                self.depth.image,
+                depth_aspect,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                vk::ImageLayout::DEPTH_STENCIL_READ_ONLY_OPTIMAL,
+                vk::AccessFlags::TRANSFER_WRITE,
+                vk::AccessFlags::SHADER_READ,
+            ),
+        ];
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &to_sampled,
+        );
+
+        self.valid = true;
+        self.view = view;
+        self.proj = proj;
+    }
+
+    pub unsafe fn cleanup(&self, device: &ash::Device) {
+        self.color.destroy(device);
+        self.depth.destroy(device);
+        device.destroy_sampler(self.color_sampler, None);
+        device.destroy_sampler(self.depth_sampler, None);
+    }
+}
@@ -0,0 +1,596 @@
+use ash::vk;
+use std::collections::{HashMap, HashSet};
+
+use crate::core::ResourceManager;
+use crate::material::MaterialProperties;
+
+/// Bits of `MeshPushConstants::texture_flags` telling mesh.frag which texture slots to sample
+pub const TEXTURE_FLAG_ALBEDO: u32 = 1 << 0;
+pub const TEXTURE_FLAG_NORMAL: u32 = 1 << 1;
+pub const TEXTURE_FLAG_METALLIC_ROUGHNESS: u32 = 1 << 2;
+/// Not a texture slot: multiply albedo by the mesh's vertex colors
+pub const TEXTURE_FLAG_VERTEX_COLOR: u32 = 1 << 3;
+/// Not texture slots: blend in the weathering layer packed in `MeshPushConstants::layer`,
+/// with its mask driven by noise alone or also by SSAO / vertex-color occlusion
+pub const TEXTURE_FLAG_LAYER: u32 = 1 << 4;
+pub const TEXTURE_FLAG_LAYER_SSAO: u32 = 1 << 5;
+pub const TEXTURE_FLAG_LAYER_VERTEX: u32 = 1 << 6;
+
+/// Maximum number of distinct material texture combinations (one descriptor set each)
+const MAX_MATERIAL_SETS: u32 = 256;
+
+/// Number of texture slots per material (albedo, normal, metallic/roughness)
+const TEXTURE_SLOTS: usize = 3;
+
+/// Texture paths of a material, keeping only the ones that loaded successfully
+type MaterialTextureKey = [Option<String>; TEXTURE_SLOTS];
+
+/// Device handles needed to upload a texture
+#[derive(Clone, Copy)]
+struct TextureUpload<'a> {
+    instance: &'a ash::Instance,
+    physical_device: vk::PhysicalDevice,
+    device: &'a ash::Device,
+    command_pool: vk::CommandPool,
+    queue: vk::Queue,
+}
+
+/// Sampled 2D texture on the GPU
+#[derive(Clone, Copy)]
+pub struct Texture {
+    pub image: vk::Image,
+    pub memory: vk::DeviceMemory,
+    pub view: vk::ImageView,
+}
+
+/// Material texture registry (path -> GPU texture) so shared textures are only uploaded once.
+/// Also owns the material descriptor sets (set = 1 in the mesh pipeline), one per
+/// distinct texture combination, with unused slots pointing at a 1x1 white texture.
+pub struct TextureCache {
+    textures: HashMap<String, Texture>,
+    // Paths that failed to load (logged once, then rendered with the solid color)
+    failed: HashSet<String>,
+    white: Option<Texture>,
+    sampler: vk::Sampler,
+    // Anisotropy the sampler was created with (1.0 = off)
+    anisotropy: f32,
+    set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    default_set: vk::DescriptorSet,
+    material_sets: HashMap<MaterialTextureKey, vk::DescriptorSet>,
+}
+
+impl TextureCache {
+    /// Create the descriptor set layout, sampler and pool (needed before pipeline creation)
+    pub unsafe fn new(device: &ash::Device) -> anyhow::Result<Self> {
+        let bindings: Vec<vk::DescriptorSetLayoutBinding> = (0..TEXTURE_SLOTS as u32)
+            .map(|binding| {
+                vk::DescriptorSetLayoutBinding::default()
+                    .binding(binding)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .descriptor_count(1)
+                    .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            })
+            .collect();
+
+        let layout_info = vk::DescriptorSetLayoutCreateInfo::default()
+            .bindings(&bindings);
+        let set_layout = device.create_descriptor_set_layout(&layout_info, None)?;
+
+        // Anisotropy starts off; the renderer applies the configured level on the first frame
+        let sampler = Self::create_sampler(device, 1.0)?;
+
+        // +1 for the default (untextured) set
+        let pool_sizes = [vk::DescriptorPoolSize::default()
+            .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .descriptor_count((MAX_MATERIAL_SETS + 1) * TEXTURE_SLOTS as u32)];
+        let pool_info = vk::DescriptorPoolCreateInfo::default()
+            .pool_sizes(&pool_sizes)
+            .max_sets(MAX_MATERIAL_SETS + 1);
+        let descriptor_pool = device.create_descriptor_pool(&pool_info, None)?;
+
+        Ok(Self {
+            textures: HashMap::new(),
+            failed: HashSet::new(),
+            white: None,
+            sampler,
+            anisotropy: 1.0,
+            set_layout,
+            descriptor_pool,
+            default_set: vk::DescriptorSet::null(),
+            material_sets: HashMap::new(),
+        })
+    }
+
+    /// Upload the fallback white texture and build the default (untextured) descriptor set
+    pub unsafe fn initialize(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        queue: vk::Queue,
+    ) -> anyhow::Result<()> {
+        let upload = TextureUpload { instance, physical_device, device, command_pool, queue };
+        let white = Self::upload_rgba(upload, 1, 1, &[255, 255, 255, 255])?;
+        self.white = Some(white);
+        self.default_set = self.create_material_set(device, &[None, None, None])?;
+        Ok(())
+    }
+
+    /// Trilinear, repeating sampler over every mip level with optional anisotropic filtering
+    unsafe fn create_sampler(device: &ash::Device, anisotropy: f32) -> anyhow::Result<vk::Sampler> {
+        let sampler_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::REPEAT)
+            .address_mode_v(vk::SamplerAddressMode::REPEAT)
+            .address_mode_w(vk::SamplerAddressMode::REPEAT)
+            .anisotropy_enable(anisotropy > 1.0)
+            .max_anisotropy(anisotropy.max(1.0))
+            .border_color(vk::BorderColor::INT_OPAQUE_BLACK)
+            .unnormalized_coordinates(false)
+            .compare_enable(false)
+            .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+            .min_lod(0.0)
+            .max_lod(vk::LOD_CLAMP_NONE);
+        Ok(device.create_sampler(&sampler_info, None)?)
+    }
+
+    /// Swap in a sampler with a new anisotropy level and point every material set at it
+    /// The caller must make sure no frame using the old sampler is still in flight
+    pub unsafe fn set_anisotropy(&mut self, device: &ash::Device, anisotropy: f32) -> anyhow::Result<()> {
+        if anisotropy == self.anisotropy {
+            return Ok(());
+        }
+
+        let old_sampler = self.sampler;
+        self.sampler = Self::create_sampler(device, anisotropy)?;
+        self.anisotropy = anisotropy;
+
+        if self.white.is_some() {
+            self.write_material_set(device, self.default_set, &[None, None, None])?;
+            for (key, &set) in &self.material_sets {
+                self.write_material_set(device, set, key)?;
+            }
+        }
+        device.destroy_sampler(old_sampler, None);
+        Ok(())
+    }
+
+    /// Descriptor set layout for material textures (set = 1 of the mesh pipeline)
+    pub fn set_layout(&self) -> vk::DescriptorSetLayout {
+        self.set_layout
+    }
+
+    /// Load any textures referenced by a material and build its descriptor set.
+    /// Missing or unreadable files are logged once and fall back to the solid color.
+    pub unsafe fn prepare_material(
+        &mut self,
+        instance: &ash::Instance,
+        physical_device: vk::PhysicalDevice,
+        device: &ash::Device,
+        command_pool: vk::CommandPool,
+        queue: vk::Queue,
+        material: &MaterialProperties,
+    ) {
+        if !material.has_textures() {
+            return;
+        }
+
+        let upload = TextureUpload { instance, physical_device, device, command_pool, queue };
+        for path in Self::texture_paths(material).into_iter().flatten() {
+            if self.textures.contains_key(path) || self.failed.contains(path) {
+                continue;
+            }
+
+            match Self::load_texture(upload, path) {
+                Ok(texture) => {
+                    crate::console::info(format!("Loaded texture: {}", path));
+                    self.textures.insert(path.to_string(), texture);
+                }
+                Err(e) => {
+                    crate::console::warn(format!("Failed to load texture {}: {} (using solid color)", path, e));
+                    self.failed.insert(path.to_string());
+                }
+            }
+        }
+
+        let key = self.resolve_key(material);
+        if key.iter().all(|slot| slot.is_none()) || self.material_sets.contains_key(&key) {
+            return;
+        }
+
+        if self.material_sets.len() as u32 >= MAX_MATERIAL_SETS {
+            crate::console::warn(format!("Material texture set limit ({}) reached, rendering without textures", MAX_MATERIAL_SETS));
+            return;
+        }
+
+        match self.create_material_set(device, &key) {
+            Ok(set) => {
+                self.material_sets.insert(key, set);
+            }
+            Err(e) => crate::console::error(format!("Failed to create material texture descriptor set: {}", e)),
+        }
+    }
+
+    /// Descriptor set and texture flags to draw a material with.
+    /// Untextured (or not yet prepared) materials get the default set and no texture flags.
+    pub fn material_binding(&self, material: &MaterialProperties) -> (vk::DescriptorSet, u32) {
+        let vertex_color = if material.vertex_colors { TEXTURE_FLAG_VERTEX_COLOR } else { 0 };
+        if !material.has_textures() {
+            return (self.default_set, vertex_color);
+        }
+
+        let key = self.resolve_key(material);
+        match self.material_sets.get(&key) {
+            Some(&set) => {
+                let flags = [TEXTURE_FLAG_ALBEDO, TEXTURE_FLAG_NORMAL, TEXTURE_FLAG_METALLIC_ROUGHNESS]
+                    .iter()
+                    .zip(key.iter())
+                    .filter(|(_, slot)| slot.is_some())
+                    .fold(vertex_color, |flags, (flag, _)| flags | flag);
+                (set, flags)
+            }
+            None => (self.default_set, vertex_color),
+        }
+    }
+
+    pub fn cleanup(&mut self, device: &ash::Device) {
+        unsafe {
+            for (_path, texture) in self.textures.drain() {
+                Self::destroy_texture(device, texture);
+            }
+            if let Some(white) = self.white.take() {
+                Self::destroy_texture(device, white);
+            }
+
+            // Destroying the pool frees all material sets
+            self.material_sets.clear();
+            device.destroy_descriptor_pool(self.descriptor_pool, None);
+            device.destroy_descriptor_set_layout(self.set_layout, None);
+            device.destroy_sampler(self.sampler, None);
+        }
+    }
+
+    fn texture_paths(material: &MaterialProperties) -> [Option<&str>; TEXTURE_SLOTS] {
+        [
+            material.albedo_texture.as_deref(),
+            material.normal_texture.as_deref(),
+            material.metallic_roughness_texture.as_deref(),
+        ]
+    }
+
+    fn resolve_key(&self, material: &MaterialProperties) -> MaterialTextureKey {
+        Self::texture_paths(material).map(|path| {
+            path.filter(|p| self.textures.contains_key(*p))
+                .map(|p| p.to_string())
+        })
+    }
+
+    unsafe fn create_material_set(
+        &self,
+        device: &ash::Device,
+        key: &MaterialTextureKey,
+    ) -> anyhow::Result<vk::DescriptorSet> {
+        let layouts = [self.set_layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::default()
+            .descriptor_pool(self.descriptor_pool)
+            .set_layouts(&layouts);
+        let set = device.allocate_descriptor_sets(&alloc_info)?[0];
+
+        self.write_material_set(device, set, key)?;
+        Ok(set)
+    }
+
+    /// Point a material set's slots at its textures (white for empty slots) and the current sampler
+    unsafe fn write_material_set(
+        &self,
+        device: &ash::Device,
+        set: vk::DescriptorSet,
+        key: &MaterialTextureKey,
+    ) -> anyhow::Result<()> {
+        let white = self.white
+            .ok_or_else(|| anyhow::anyhow!("Texture cache not initialized"))?;
+
+        let image_infos: Vec<vk::DescriptorImageInfo> = key
+            .iter()
+            .map(|slot| {
+                let view = slot
+                    .as_ref()
+                    .and_then(|path| self.textures.get(path))
+                    .map(|texture| texture.view)
+                    .unwrap_or(white.view);
+                vk::DescriptorImageInfo::default()
+                    .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                    .image_view(view)
+                    .sampler(self.sampler)
+            })
+            .collect();
+
+        let descriptor_writes: Vec<vk::WriteDescriptorSet> = image_infos
+            .iter()
+            .enumerate()
+            .map(|(binding, image_info)| {
+                vk::WriteDescriptorSet::default()
+                    .dst_set(set)
+                    .dst_binding(binding as u32)
+                    .dst_array_element(0)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(std::slice::from_ref(image_info))
+            })
+            .collect();
+
+        device.update_descriptor_sets(&descriptor_writes, &[]);
+
+        Ok(())
+    }
+
+    unsafe fn load_texture(upload: TextureUpload, path: &str) -> anyhow::Result<Texture> {
+        let image = image::open(path)?.to_rgba8();
+        let (width, height) = image.dimensions();
+
+        Self::upload_rgba(upload, width, height, image.as_raw())
+    }
+
+    /// Upload RGBA8 pixels into a device-local sampled image (stored as UNORM, shaders linearize albedo)
+    /// The full mip chain is generated on the GPU with linear blits when the format supports it
+    unsafe fn upload_rgba(upload: TextureUpload, width: u32, height: u32, pixels: &[u8]) -> anyhow::Result<Texture> {
+        let TextureUpload { instance, physical_device, device, command_pool, queue } = upload;
+        let format = vk::Format::R8G8B8A8_UNORM;
+        let size = pixels.len() as vk::DeviceSize;
+
+        let format_properties = instance.get_physical_device_format_properties(physical_device, format);
+        let can_blit = format_properties
+            .optimal_tiling_features
+            .contains(vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR);
+        let mip_levels = if can_blit { mip_level_count(width, height) } else { 1 };
+
+        // 1. Copy pixels into a staging buffer
+        let (staging_buffer, staging_memory) = ResourceManager::create_buffer(
+            instance,
+            physical_device,
+            device,
+            size,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        let data = device.map_memory(staging_memory, 0, size, vk::MemoryMapFlags::empty())?;
+        std::ptr::copy_nonoverlapping(pixels.as_ptr(), data as *mut u8, pixels.len());
+        device.unmap_memory(staging_memory);
+
+        // 2. Create the device-local image
+        let image_info = vk::ImageCreateInfo::default()
+            .image_type(vk::ImageType::TYPE_2D)
+            .extent(vk::Extent3D { width, height, depth: 1 })
+            .mip_levels(mip_levels)
+            .array_layers(1)
+            .format(format)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .usage(vk::ImageUsageFlags::TRANSFER_SRC | vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let image = device.create_image(&image_info, None)?;
+
+        let mem_requirements = device.get_image_memory_requirements(image);
+        let alloc_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(mem_requirements.size)
+            .memory_type_index(ResourceManager::find_memory_type(
+                instance,
+                physical_device,
+                mem_requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?);
+
+        let memory = device.allocate_memory(&alloc_info, None)?;
+        device.bind_image_memory(image, memory, 0)?;
+
+        // 3. Transition, copy, downsample each level from the one above and make the image shader-readable
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: mip_levels,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+
+        let alloc_info = vk::CommandBufferAllocateInfo::default()
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_pool(command_pool)
+            .command_buffer_count(1);
+        let command_buffer = device.allocate_command_buffers(&alloc_info)?[0];
+
+        let begin_info = vk::CommandBufferBeginInfo::default()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device.begin_command_buffer(command_buffer, &begin_info)?;
+
+        let to_transfer = vk::ImageMemoryBarrier::default()
+            .old_layout(vk::ImageLayout::UNDEFINED)
+            .new_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .image(image)
+            .subresource_range(subresource_range)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE);
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[to_transfer],
+        );
+
+        let region = vk::BufferImageCopy::default()
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0)
+            .image_subresource(vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            })
+            .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
+            .image_extent(vk::Extent3D { width, height, depth: 1 });
+
+        device.cmd_copy_buffer_to_image(
+            command_buffer,
+            staging_buffer,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        );
+
+        let level_range = |level: u32| vk::ImageSubresourceRange {
+            base_mip_level: level,
+            level_count: 1,
+            ..subresource_range
+        };
+        let level_layers = |level: u32| vk::ImageSubresourceLayers {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            mip_level: level,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let level_extent = |level: u32| {
+            vk::Offset3D {
+                x: (width >> level).max(1) as i32,
+                y: (height >> level).max(1) as i32,
+                z: 1,
+            }
+        };
+
+        for level in 1..mip_levels {
+            // The level above is finished: make it the blit source
+            let to_source = vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .image(image)
+                .subresource_range(level_range(level - 1))
+                .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(vk::AccessFlags::TRANSFER_READ);
+
+            device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                &[to_source],
+            );
+
+            let blit = vk::ImageBlit::default()
+                .src_subresource(level_layers(level - 1))
+                .src_offsets([vk::Offset3D::default(), level_extent(level - 1)])
+                .dst_subresource(level_layers(level))
+                .dst_offsets([vk::Offset3D::default(), level_extent(level)]);
+
+            device.cmd_blit_image(
+                command_buffer,
+                image,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                image,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[blit],
+                vk::Filter::LINEAR,
+            );
+        }
+
+        // Every level but the last was a blit source
+        let mut to_shader_read = Vec::with_capacity(2);
+        if mip_levels > 1 {
+            to_shader_read.push(
+                vk::ImageMemoryBarrier::default()
+                    .old_layout(vk::ImageLayout::TRANSFER_SRC_OPTIMAL)
+                    .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                    .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                    .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                    .image(image)
+                    .subresource_range(vk::ImageSubresourceRange {
+                        level_count: mip_levels - 1,
+                        ..subresource_range
+                    })
+                    .src_access_mask(vk::AccessFlags::TRANSFER_READ)
+                    .dst_access_mask(vk::AccessFlags::SHADER_READ),
+            );
+        }
+        to_shader_read.push(
+            vk::ImageMemoryBarrier::default()
+                .old_layout(vk::ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+                .image(image)
+                .subresource_range(level_range(mip_levels - 1))
+                .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        );
+
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &to_shader_read,
+        );
+
+        device.end_command_buffer(command_buffer)?;
+
+        let command_buffers = [command_buffer];
+        let submit_info = vk::SubmitInfo::default().command_buffers(&command_buffers);
+        device.queue_submit(queue, &[submit_info], vk::Fence::null())?;
+        device.queue_wait_idle(queue)?;
+        device.free_command_buffers(command_pool, &command_buffers);
+
+        device.destroy_buffer(staging_buffer, None);
+        device.free_memory(staging_memory, None);
+
+        // 4. Create the image view
+        let view_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(subresource_range);
+
+        let view = device.create_image_view(&view_info, None)?;
+
+        Ok(Texture { image, memory, view })
+    }
+
+    unsafe fn destroy_texture(device: &ash::Device, texture: Texture) {
+        device.destroy_image_view(texture.view, None);
+        device.destroy_image(texture.image, None);
+        device.free_memory(texture.memory, None);
+    }
+}
+
+/// Number of levels in a full mip chain down to 1x1
+fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mip_level_count() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(2, 1), 2);
+        assert_eq!(mip_level_count(256, 256), 9);
+        assert_eq!(mip_level_count(1024, 300), 11);
+        assert_eq!(mip_level_count(1000, 1000), 10);
+    }
+}
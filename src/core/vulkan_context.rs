@@ -293,10 +293,10 @@ unsafe extern "system" fn vulkan_debug_callback(
 
     match message_severity {
         vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => {
-            eprintln!("[Vulkan Error] {:?}: {:?}", message_type, message);
+            crate::console::error(format!("[Vulkan] {:?}: {:?}", message_type, message));
         }
         vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => {
-            eprintln!("[Vulkan Warning] {:?}: {:?}", message_type, message);
+            crate::console::warn(format!("[Vulkan] {:?}: {:?}", message_type, message));
         }
         _ => {
             println!("[Vulkan Info] {:?}: {:?}", message_type, message);
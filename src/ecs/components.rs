@@ -8,15 +8,15 @@ use serde::{Deserialize, Serialize};
 
 /// 64-bit position component (world space)
 /// Provides ~10^15 meter precision near camera origin
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Position(pub DVec3);
 
 /// 64-bit rotation component (quaternion)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Rotation(pub DQuat);
 
 /// 64-bit scale component
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Scale(pub DVec3);
 
 impl Default for Scale {
@@ -26,15 +26,15 @@ impl Default for Scale {
 }
 
 /// Velocity component (meters per second)
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct Velocity(pub DVec3);
 
 /// Angular velocity component (radians per second)
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct AngularVelocity(pub DVec3);
 
 /// Mass component (kilograms)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Mass(pub f64);
 
 /// Tag component for different entity types
@@ -49,15 +49,25 @@ pub enum EntityType {
     Camera,
 }
 
+/// Physics body marker - entities with one are simulated by Rapier in play mode
+/// Colliders are generated from the entity's bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhysicsBody {
+    /// Moved by the simulation (ships)
+    Dynamic,
+    /// Immovable obstacle (asteroids)
+    Fixed,
+}
+
 /// Visual representation component
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Visual {
     pub mesh_name: String,
     pub material_name: String,
 }
 
 /// Health component for destructible entities
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Health {
     pub current: f32,
     pub max: f32,
@@ -80,13 +90,13 @@ impl Health {
 // Ship component moved below after Star component with tactical movement capabilities
 
 /// Asteroid component
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Asteroid {
     pub radius: f64,  // meters
 }
 
 /// Planet component
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Planet {
     pub name: String,
     pub radius: f64,      // meters
@@ -94,7 +104,7 @@ pub struct Planet {
 }
 
 /// Nebula component (visual effect at massive scale)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Nebula {
     pub scale: f64,           // size in meters (can be 1000x larger now!)
     pub density: f32,
@@ -102,7 +112,7 @@ pub struct Nebula {
 }
 
 /// Star component (procedural sun with limb darkening)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Star {
     pub name: String,
     pub radius: f64,          // meters (e.g., Sun = 695,700,000 m)
@@ -129,7 +139,7 @@ impl Default for Star {
 }
 
 /// Ship component for turn-based tactical movement
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Ship {
     pub name: String,
 
@@ -262,7 +272,7 @@ impl Ship {
 }
 
 /// Movement curve for Bezier-based ship movement
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct MovementCurve {
     pub start_position: DVec3,
     pub end_position: DVec3,
@@ -299,6 +309,15 @@ impl MovementCurve {
             + 2.0 * t * (self.end_position - self.control_point)
     }
 
+    /// The part of the curve from the start up to `t` (de Casteljau split), as its own curve
+    pub fn truncated(&self, t: f64) -> Self {
+        Self {
+            start_position: self.start_position,
+            end_position: self.evaluate(t),
+            control_point: self.start_position.lerp(self.control_point, t),
+        }
+    }
+
     /// Get ending velocity (for next turn's momentum)
     pub fn ending_velocity(&self) -> DVec3 {
         self.end_position - self.control_point
@@ -323,16 +342,16 @@ impl MovementCurve {
 
 /// Parent-child relationship component
 /// Stores the parent entity ID for hierarchical transforms
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Parent(pub hecs::Entity);
 
 /// Children component - tracks all child entities
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Children(pub Vec<hecs::Entity>);
 
 /// Turn-based state component
 /// Tracks orders and state for turn resolution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TurnState {
     pub pending_orders: Vec<Order>,
     pub completed_orders: Vec<Order>,
@@ -340,8 +359,60 @@ pub struct TurnState {
     pub max_action_points: u32,
 }
 
+/// Distance a unit can travel per action point (meters)
+pub const DISTANCE_PER_ACTION_POINT: f64 = 5.0;
+
+/// Action points a unit gets each turn by default (covers a ship's full movement range)
+pub const DEFAULT_ACTION_POINTS: u32 = 4;
+
+/// Turn-based unit component
+/// Action points are spent on movement and restored when the turn ends
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Unit {
+    pub action_points: u32,
+    pub max_action_points: u32,
+    pub faction: String,
+    pub has_moved: bool,            // Has the unit moved this turn?
+}
+
+impl Unit {
+    pub fn new(faction: String, max_action_points: u32) -> Self {
+        Self {
+            action_points: max_action_points,
+            max_action_points,
+            faction,
+            has_moved: false,
+        }
+    }
+
+    /// Action points needed to travel a distance (partial points round up)
+    pub fn move_cost(distance: f64) -> u32 {
+        (distance.max(0.0) / DISTANCE_PER_ACTION_POINT).ceil() as u32
+    }
+
+    pub fn can_afford(&self, cost: u32) -> bool {
+        cost <= self.action_points
+    }
+
+    /// Spend action points on a move, returns false (spending nothing) if over budget
+    pub fn spend(&mut self, cost: u32) -> bool {
+        if !self.can_afford(cost) {
+            return false;
+        }
+        self.action_points -= cost;
+        self.has_moved = true;
+        true
+    }
+
+    /// Restore action points for a new turn
+    pub fn reset(&mut self) {
+        self.action_points = self.max_action_points;
+        self.has_moved = false;
+    }
+}
+
 /// Orders for turn-based gameplay
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Order {
     Move { target_position: DVec3 },
     Attack { target_entity: u64 },  // Entity ID
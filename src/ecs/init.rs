@@ -63,6 +63,7 @@ pub fn create_star_entity(
 pub fn create_ship_entity(
     world: &mut World,
     name: String,
+    faction: String,
     position: DVec3,
     rotation: DQuat,
 ) -> Entity {
@@ -76,6 +77,8 @@ pub fn create_ship_entity(
         Rotation(rotation),
         Scale(DVec3::ONE),
         ship,
+        Unit::new(faction, DEFAULT_ACTION_POINTS),
+        PhysicsBody::Dynamic,
         EntityType::Ship,
         Visual {
             mesh_name: "Fed_cruiser_ship.obj".to_string(),
@@ -100,6 +103,7 @@ pub fn create_asteroid_entity(
         AngularVelocity(DVec3::ZERO),
         Mass(mass),
         Asteroid { radius },
+        PhysicsBody::Fixed,
         EntityType::Asteroid,
         Visual {
             mesh_name: "asteroid".to_string(),
@@ -5,6 +5,7 @@
 /// - Camera-relative rendering (converts to 32-bit at render time)
 /// - Component definitions for space entities
 /// - Integration with Rapier physics
+/// - JSON persistence of all entities and components
 
 pub mod components;
 pub mod physics;
@@ -12,17 +13,24 @@ pub mod spatial;
 pub mod rendering;
 pub mod hierarchy;
 pub mod init;
+pub mod serialization;
 
-use glam::{DVec3, Vec3};
+use glam::{DVec3, Mat4, Vec3};
 use hecs::World;
 
+/// Distance the camera may drift from `camera_origin` before the origin is rebased onto it (meters)
+/// Camera-relative coordinates stay within this plus the view distance, where f32 still
+/// resolves about a millimetre
+pub const ORIGIN_REBASE_DISTANCE: f64 = 10_000.0;
+
 /// The main ECS world containing all entities
 pub struct EcsWorld {
     /// hecs World - stores all entities and components
     pub world: World,
 
     /// Camera origin in world space (64-bit)
-    /// All rendering is done relative to this point
+    /// All rendering is done relative to this point. It follows the camera in jumps
+    /// (see `update_camera_origin`), so it stays put between rebases.
     pub camera_origin: DVec3,
 }
 
@@ -40,6 +48,17 @@ impl EcsWorld {
         self.camera_origin = origin;
     }
 
+    /// Rebase the camera origin onto the camera once it has drifted more than
+    /// `ORIGIN_REBASE_DISTANCE` away. Returns true when the origin moved.
+    pub fn update_camera_origin(&mut self, camera_position: DVec3) -> bool {
+        if camera_position.distance(self.camera_origin) > ORIGIN_REBASE_DISTANCE {
+            self.camera_origin = camera_position;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Convert world position (64-bit) to camera-relative position (32-bit)
     /// This is safe because we're only rendering things close to the camera
     pub fn world_to_camera_relative(&self, world_pos: DVec3) -> Vec3 {
@@ -51,10 +70,35 @@ impl EcsWorld {
         )
     }
 
+    /// Convert a world-space model matrix to a camera-relative one
+    /// (the translation is made relative in 64-bit, rotation and scale are unchanged)
+    pub fn world_matrix_to_camera_relative(&self, model: Mat4) -> Mat4 {
+        let mut relative = model;
+        relative.w_axis = self
+            .world_to_camera_relative(model.w_axis.truncate().as_dvec3())
+            .extend(model.w_axis.w);
+        relative
+    }
+
     /// Get the number of entities
     pub fn entity_count(&self) -> u32 {
         self.world.len() as u32
     }
+
+    /// Save all entities and their registered components to a JSON file
+    pub fn save(&self, path: &str) -> anyhow::Result<()> {
+        serialization::EcsWorldData::from_world(self).save(path)
+    }
+
+    /// Load a world saved with `save`, reconnecting parent/child relationships
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        Ok(serialization::EcsWorldData::load(path)?.into_world())
+    }
+
+    /// Where the world is saved alongside a scene file (`scene.json` -> `scene.ecs.json`)
+    pub fn path_for_scene(scene_path: &str) -> String {
+        std::path::Path::new(scene_path).with_extension("ecs.json").to_string_lossy().into_owned()
+    }
 }
 
 impl Default for EcsWorld {
@@ -62,3 +106,79 @@ impl Default for EcsWorld {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Camera;
+
+    /// View-space position of a world-space point, as the renderer computes it
+    fn view_position(ecs_world: &EcsWorld, camera: &Camera, point: DVec3) -> Vec3 {
+        let view = camera.view_matrix_relative_to(ecs_world.camera_origin);
+        view.transform_point3(ecs_world.world_to_camera_relative(point))
+    }
+
+    #[test]
+    fn test_far_camera_renders_nearby_object_without_jitter() {
+        let mut ecs_world = EcsWorld::new();
+        let mut camera = Camera::default();
+        let start = DVec3::new(1.0e7, 0.0, 1.0e7);
+        camera.set_world_position(start);
+        assert!(ecs_world.update_camera_origin(camera.world_position()));
+
+        // An object 5m ahead; creep the camera sideways by a millimetre a frame
+        let object = start + DVec3::new(0.0, 0.0, -5.0);
+        let step = 1.0e-3;
+        let mut previous = view_position(&ecs_world, &camera, object);
+        assert!((previous - Vec3::new(0.0, 0.0, -5.0)).length() < 1.0e-4);
+        for _ in 0..100 {
+            camera.set_world_position(camera.world_position() + DVec3::new(step, 0.0, 0.0));
+            ecs_world.update_camera_origin(camera.world_position());
+            let current = view_position(&ecs_world, &camera, object);
+
+            // At 1e7, f32 world coordinates would snap by a whole metre
+            assert!((current.x - (previous.x - step as f32)).abs() < 1.0e-5, "jitter: {} -> {}", previous.x, current.x);
+            assert!((current.z - previous.z).abs() < 1.0e-5);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn test_origin_rebases_only_past_threshold() {
+        let mut ecs_world = EcsWorld::new();
+        let mut camera = Camera::default();
+        camera.set_world_position(DVec3::new(1.0e7, 0.0, 0.0));
+        assert!(ecs_world.update_camera_origin(camera.world_position()));
+        let origin = ecs_world.camera_origin;
+
+        camera.set_world_position(origin + DVec3::new(ORIGIN_REBASE_DISTANCE * 0.5, 0.0, 0.0));
+        assert!(!ecs_world.update_camera_origin(camera.world_position()));
+        assert_eq!(ecs_world.camera_origin, origin);
+
+        // A rebase moves the origin but leaves what the camera sees unchanged
+        let object = camera.world_position() + DVec3::new(1.0, 2.0, -5.0);
+        let before = view_position(&ecs_world, &camera, object);
+        camera.set_world_position(origin + DVec3::new(ORIGIN_REBASE_DISTANCE * 1.5, 0.0, 0.0));
+        let object = object + DVec3::new(ORIGIN_REBASE_DISTANCE, 0.0, 0.0);
+        assert!(ecs_world.update_camera_origin(camera.world_position()));
+        assert_eq!(ecs_world.camera_origin, camera.world_position());
+        let after = view_position(&ecs_world, &camera, object);
+        assert!((after - before).length() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_world_matrix_to_camera_relative_keeps_rotation_and_scale() {
+        let mut ecs_world = EcsWorld::new();
+        ecs_world.set_camera_origin(DVec3::new(100.0, 0.0, -50.0));
+        let model = Mat4::from_scale_rotation_translation(
+            Vec3::splat(2.0),
+            glam::Quat::from_rotation_y(0.5),
+            Vec3::new(110.0, 1.0, -50.0),
+        );
+
+        let relative = ecs_world.world_matrix_to_camera_relative(model);
+        assert_eq!(relative.x_axis, model.x_axis);
+        assert_eq!(relative.z_axis, model.z_axis);
+        assert_eq!(relative.w_axis.truncate(), Vec3::new(10.0, 1.0, 0.0));
+    }
+}
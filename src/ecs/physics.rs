@@ -4,11 +4,42 @@
 /// - Deterministic physics simulation (fixed timestep)
 /// - Collision detection for ships, asteroids, projectiles
 /// - Integration with 64-bit coordinate system
+///
+/// Rapier simulates in f32 relative to a physics origin that follows the ECS camera
+/// origin. The entities' DVec3 transforms stay authoritative: bodies are (re)placed
+/// from them and only moving dynamic bodies are written back.
 
 use rapier3d::prelude::*;
 use glam::{DVec3, DQuat, Vec3};
+use hecs::{Entity, World};
 use nalgebra as na;
 
+use crate::ecs::ORIGIN_REBASE_DISTANCE;
+use crate::ecs::components::{Asteroid, Parent, PhysicsBody, Position, Rotation, Scale, Ship};
+
+/// Most fixed steps run in one frame (avoids a spiral of death after a hitch)
+const MAX_STEPS_PER_FRAME: u32 = 4;
+
+/// Collider generated from an entity's bounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColliderShape {
+    /// Box around the mesh bounds (center and half extents in local space)
+    Cuboid { center: Vec3, half_extents: Vec3 },
+    Ball { radius: f32 },
+}
+
+/// Links an ECS entity to its Rapier rigid body
+/// Runtime only - bodies are rebuilt from `PhysicsBody` markers after a load
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigidBodyLink {
+    pub handle: RigidBodyHandle,
+    /// Shape the collider was built with (rebuilt when the bounds change)
+    shape: ColliderShape,
+    /// Transform last exchanged with the body, to detect moves made outside physics
+    synced_position: DVec3,
+    synced_rotation: DQuat,
+}
+
 /// Physics world wrapper
 /// Handles collision detection and deterministic simulation
 pub struct PhysicsWorld {
@@ -47,6 +78,12 @@ pub struct PhysicsWorld {
 
     /// Query pipeline for raycasts
     pub query_pipeline: QueryPipeline,
+
+    /// World position of the simulation's (0, 0, 0) - bodies live relative to it
+    pub origin: DVec3,
+
+    /// Unsimulated time carried over to the next frame (seconds)
+    accumulator: f32,
 }
 
 impl PhysicsWorld {
@@ -70,9 +107,172 @@ impl PhysicsWorld {
             multibody_joint_set: MultibodyJointSet::new(),
             ccd_solver: CCDSolver::new(),
             query_pipeline: QueryPipeline::new(),
+            origin: DVec3::ZERO,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Advance the simulation by one frame and write moving bodies back to the ECS
+    /// `focus` is the camera's 64-bit world position
+    pub fn update(&mut self, world: &mut World, focus: DVec3, delta_time: f32) {
+        // 1. Keep the simulation near the camera so f32 stays precise on long flights
+        if (focus - self.origin).length() > ORIGIN_REBASE_DISTANCE {
+            self.rebase(world, focus);
+        }
+
+        // 2. Match bodies to the ECS (new, changed, moved and removed entities)
+        self.sync_bodies(world);
+
+        // 3. Fixed timestep for determinism
+        let dt = self.integration_params.dt;
+        self.accumulator += delta_time;
+        let mut steps = 0;
+        while self.accumulator >= dt && steps < MAX_STEPS_PER_FRAME {
+            self.step();
+            self.accumulator -= dt;
+            steps += 1;
+        }
+        self.accumulator = self.accumulator.min(dt);
+
+        // 4. Write simulated transforms back into the 64-bit components
+        self.write_back(world);
+    }
+
+    /// Move the physics origin, re-placing every body from its entity's 64-bit transform
+    /// so rebasing never accumulates f32 error into the simulation
+    fn rebase(&mut self, world: &World, new_origin: DVec3) {
+        self.origin = new_origin;
+
+        for (_, (position, rotation, link)) in world.query::<(&Position, &Rotation, &RigidBodyLink)>().iter() {
+            if let Some(body) = self.rigid_body_set.get_mut(link.handle) {
+                body.set_position(dvec3_to_isometry(position.0 - self.origin, rotation.0), false);
+            }
+        }
+    }
+
+    /// Create bodies for new `PhysicsBody` entities, apply changes made outside physics
+    /// and remove bodies whose entity is gone
+    fn sync_bodies(&mut self, world: &mut World) {
+        // 1. Entities that lost their PhysicsBody marker
+        let unmarked: Vec<Entity> = world
+            .query::<(&RigidBodyLink, Option<&PhysicsBody>)>()
+            .iter()
+            .filter(|(_, (_, marker))| marker.is_none())
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in unmarked {
+            if let Ok(link) = world.remove_one::<RigidBodyLink>(entity) {
+                self.remove_body(link.handle);
+            }
+        }
+
+        // 2. Bodies of despawned entities (user data 0 = body not owned by an entity)
+        let orphaned: Vec<RigidBodyHandle> = self
+            .rigid_body_set
+            .iter()
+            .filter(|(_, body)| {
+                body.user_data != 0
+                    && Entity::from_bits(body.user_data as u64).is_none_or(|entity| !world.contains(entity))
+            })
+            .map(|(handle, _)| handle)
+            .collect();
+        for handle in orphaned {
+            self.remove_body(handle);
+        }
+
+        // 3. Create or update bodies (child transforms are local, so only root entities are simulated)
+        let marked: Vec<(Entity, DVec3, DQuat, PhysicsBody, Option<RigidBodyLink>)> = world
+            .query::<(&Position, &Rotation, &PhysicsBody, Option<&RigidBodyLink>, Option<&Parent>)>()
+            .iter()
+            .filter(|(_, (.., parent))| parent.is_none())
+            .map(|(entity, (position, rotation, marker, link, _))| (entity, position.0, rotation.0, *marker, link.copied()))
+            .collect();
+
+        for (entity, position, rotation, marker, link) in marked {
+            let shape = collider_shape(world, entity);
+            let body_type = match marker {
+                PhysicsBody::Dynamic => RigidBodyType::Dynamic,
+                PhysicsBody::Fixed => RigidBodyType::Fixed,
+            };
+
+            let link = match link {
+                None => {
+                    let body = RigidBodyBuilder::new(body_type)
+                        .position(dvec3_to_isometry(position - self.origin, rotation))
+                        .user_data(entity.to_bits().get() as u128)
+                        .build();
+                    let handle = self.rigid_body_set.insert(body);
+                    self.collider_set.insert_with_parent(build_collider(shape), handle, &mut self.rigid_body_set);
+
+                    RigidBodyLink { handle, shape, synced_position: position, synced_rotation: rotation }
+                }
+                Some(mut link) => {
+                    // Collider follows the bounds (custom meshes report theirs after loading)
+                    if link.shape != shape {
+                        let colliders = self
+                            .rigid_body_set
+                            .get(link.handle)
+                            .map(|body| body.colliders().to_vec())
+                            .unwrap_or_default();
+                        for collider in colliders {
+                            self.collider_set.remove(collider, &mut self.island_manager, &mut self.rigid_body_set, true);
+                        }
+                        self.collider_set.insert_with_parent(build_collider(shape), link.handle, &mut self.rigid_body_set);
+                        link.shape = shape;
+                    }
+
+                    if let Some(body) = self.rigid_body_set.get_mut(link.handle) {
+                        if body.body_type() != body_type {
+                            body.set_body_type(body_type, true);
+                        }
+
+                        // Moved by gameplay or the editor - teleport the body
+                        if position != link.synced_position || rotation != link.synced_rotation {
+                            body.set_position(dvec3_to_isometry(position - self.origin, rotation), true);
+                            link.synced_position = position;
+                            link.synced_rotation = rotation;
+                        }
+                    }
+
+                    link
+                }
+            };
+
+            let _ = world.insert_one(entity, link);
+        }
+    }
+
+    /// Copy moving dynamic bodies back into their entities' Position/Rotation
+    /// Resting bodies keep their exact 64-bit transform
+    fn write_back(&self, world: &mut World) {
+        for (_, (position, rotation, link)) in world.query_mut::<(&mut Position, &mut Rotation, &mut RigidBodyLink)>() {
+            let Some(body) = self.rigid_body_set.get(link.handle) else {
+                continue;
+            };
+            let moving = body.linvel().norm_squared() > 0.0 || body.angvel().norm_squared() > 0.0;
+            if !body.is_dynamic() || body.is_sleeping() || !moving {
+                continue;
+            }
+
+            position.0 = self.origin + vector_to_dvec3(body.translation());
+            rotation.0 = unit_quat_to_dquat(body.rotation());
+            link.synced_position = position.0;
+            link.synced_rotation = rotation.0;
         }
     }
 
+    /// Remove a rigid body and its colliders
+    fn remove_body(&mut self, handle: RigidBodyHandle) {
+        self.rigid_body_set.remove(
+            handle,
+            &mut self.island_manager,
+            &mut self.collider_set,
+            &mut self.impulse_joint_set,
+            &mut self.multibody_joint_set,
+            true,
+        );
+    }
+
     /// Step the physics simulation (deterministic)
     pub fn step(&mut self) {
         self.physics_pipeline.step(
@@ -101,9 +301,8 @@ impl PhysicsWorld {
         rotation: DQuat,
         half_extents: Vec3,
     ) -> RigidBodyHandle {
-        // Convert 64-bit position to Rapier's coordinate system
-        // Note: For large-scale battles, we'll use sectors/zones
-        let pos = dvec3_to_isometry(position, rotation);
+        // Convert 64-bit position to Rapier's coordinate system (relative to the physics origin)
+        let pos = dvec3_to_isometry(position - self.origin, rotation);
 
         let rigid_body = RigidBodyBuilder::dynamic()
             .position(pos)
@@ -127,7 +326,7 @@ impl PhysicsWorld {
         position: DVec3,
         radius: f64,
     ) -> RigidBodyHandle {
-        let pos = dvec3_to_isometry(position, DQuat::IDENTITY);
+        let pos = dvec3_to_isometry(position - self.origin, DQuat::IDENTITY);
 
         let rigid_body = RigidBodyBuilder::fixed()
             .position(pos)
@@ -149,7 +348,7 @@ impl PhysicsWorld {
         max_distance: f64,
     ) -> Option<(RigidBodyHandle, f32)> {
         let ray = Ray::new(
-            dvec3_to_point(origin),
+            dvec3_to_point(origin - self.origin),
             dvec3_to_vector(direction.normalize()),
         );
 
@@ -176,6 +375,37 @@ impl Default for PhysicsWorld {
     }
 }
 
+/// Collider for an entity: mesh bounds for ships, radius for asteroids, otherwise a unit cube scaled
+fn collider_shape(world: &World, entity: Entity) -> ColliderShape {
+    let scale = world.get::<&Scale>(entity).map(|scale| scale.0.as_vec3()).unwrap_or(Vec3::ONE);
+
+    if let Ok(asteroid) = world.get::<&Asteroid>(entity) {
+        return ColliderShape::Ball { radius: asteroid.radius as f32 };
+    }
+
+    let (bounds_min, bounds_max) = world
+        .get::<&Ship>(entity)
+        .map(|ship| (ship.bounds_min, ship.bounds_max))
+        .unwrap_or((Vec3::NEG_ONE, Vec3::ONE));
+
+    ColliderShape::Cuboid {
+        center: (bounds_min + bounds_max) * 0.5 * scale,
+        // Degenerate (flat) bounds still need some thickness to collide
+        half_extents: ((bounds_max - bounds_min) * 0.5 * scale).abs().max(Vec3::splat(0.01)),
+    }
+}
+
+fn build_collider(shape: ColliderShape) -> Collider {
+    match shape {
+        ColliderShape::Cuboid { center, half_extents } => {
+            ColliderBuilder::cuboid(half_extents.x, half_extents.y, half_extents.z)
+                .translation(vector![center.x, center.y, center.z])
+                .build()
+        }
+        ColliderShape::Ball { radius } => ColliderBuilder::ball(radius).build(),
+    }
+}
+
 /// Convert DVec3 to Rapier Isometry (position + rotation)
 fn dvec3_to_isometry(pos: DVec3, rot: DQuat) -> Isometry<Real> {
     Isometry::from_parts(
@@ -208,3 +438,60 @@ fn dquat_to_unit_quat(q: DQuat) -> na::UnitQuaternion<Real> {
         q.z as f32,
     ))
 }
+
+/// Convert Rapier Vector back to DVec3
+fn vector_to_dvec3(v: &Vector<Real>) -> DVec3 {
+    DVec3::new(v.x as f64, v.y as f64, v.z as f64)
+}
+
+/// Convert Rapier UnitQuaternion back to DQuat
+fn unit_quat_to_dquat(q: &na::UnitQuaternion<Real>) -> DQuat {
+    DQuat::from_xyzw(q.i as f64, q.j as f64, q.k as f64, q.w as f64).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_body_writes_back_far_from_origin() {
+        let mut physics = PhysicsWorld::new();
+        let mut world = World::new();
+        let start = DVec3::new(1.0e9, 0.0, 0.0);
+        let entity = world.spawn((Position(start), Rotation(DQuat::IDENTITY), PhysicsBody::Dynamic));
+
+        // First update rebases onto the camera and creates the body
+        physics.update(&mut world, start, 0.0);
+        assert_eq!(physics.origin, start);
+        let handle = world.get::<&RigidBodyLink>(entity).unwrap().handle;
+        physics.rigid_body_set.get_mut(handle).unwrap().set_linvel(vector![10.0, 0.0, 0.0], true);
+
+        // Simulated motion survives the trip back into 64-bit coordinates
+        physics.update(&mut world, start, 0.1);
+        let moved = world.get::<&Position>(entity).unwrap().0 - start;
+        let expected = 10.0 * physics.integration_params.dt as f64 * 4.0;
+        assert!((moved.x - expected).abs() < 1.0e-3, "moved {} expected {}", moved.x, expected);
+        assert!(moved.y.abs() < 1.0e-3 && moved.z.abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_external_moves_and_fixed_bodies() {
+        let mut physics = PhysicsWorld::new();
+        let mut world = World::new();
+        let entity = world.spawn((Position(DVec3::ZERO), Rotation(DQuat::IDENTITY), PhysicsBody::Fixed));
+        physics.update(&mut world, DVec3::ZERO, 0.0);
+
+        // Gameplay teleports the entity; the body follows and stays fixed
+        world.get::<&mut Position>(entity).unwrap().0 = DVec3::new(5.0, 0.0, 0.0);
+        physics.update(&mut world, DVec3::ZERO, 0.0);
+        let handle = world.get::<&RigidBodyLink>(entity).unwrap().handle;
+        let body = &physics.rigid_body_set[handle];
+        assert!(body.is_fixed());
+        assert_eq!(body.translation().x, 5.0);
+
+        // Despawning the entity removes its body
+        world.despawn(entity).unwrap();
+        physics.update(&mut world, DVec3::ZERO, 0.0);
+        assert_eq!(physics.rigid_body_set.len(), 0);
+    }
+}
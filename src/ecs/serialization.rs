@@ -0,0 +1,322 @@
+//! ECS world persistence
+//!
+//! hecs isn't serde-aware, so every persisted component is registered explicitly
+//! in `SavedEntity`. Entity references (Parent/Children and attack orders) are
+//! stored as the entity id at save time and remapped to the new entities on load.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use glam::DVec3;
+use hecs::{Entity, EntityBuilder, World};
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::components::*;
+use crate::ecs::EcsWorld;
+
+/// One entity and all of its registered components
+/// Add new component types here (and in `capture`/`spawn`) to persist them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedEntity {
+    /// Entity id at save time, used to reconnect relationships
+    id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<Position>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rotation: Option<Rotation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scale: Option<Scale>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    velocity: Option<Velocity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    angular_velocity: Option<AngularVelocity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mass: Option<Mass>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entity_type: Option<EntityType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    visual: Option<Visual>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    health: Option<Health>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    asteroid: Option<Asteroid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    planet: Option<Planet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nebula: Option<Nebula>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    star: Option<Star>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ship: Option<Ship>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    movement_curve: Option<MovementCurve>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    turn_state: Option<TurnState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unit: Option<Unit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    physics_body: Option<PhysicsBody>,
+    /// Saved id of the parent entity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<u64>,
+    /// Saved ids of the child entities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<u64>>,
+}
+
+/// On-disk representation of an `EcsWorld`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EcsWorldData {
+    #[serde(default)]
+    camera_origin: DVec3,
+    entities: Vec<SavedEntity>,
+}
+
+impl EcsWorldData {
+    /// Capture every entity and its registered components
+    pub fn from_world(ecs_world: &EcsWorld) -> Self {
+        let world = &ecs_world.world;
+        let mut entities: Vec<Entity> = world.query::<()>().iter().map(|(entity, ())| entity).collect();
+        // Sort so the file is stable between saves
+        entities.sort_by_key(|entity| entity.to_bits());
+
+        Self {
+            camera_origin: ecs_world.camera_origin,
+            entities: entities.into_iter().map(|entity| Self::capture(world, entity)).collect(),
+        }
+    }
+
+    /// Rebuild a world, remapping saved entity ids to the newly spawned entities
+    pub fn into_world(self) -> EcsWorld {
+        let mut ecs_world = EcsWorld::new();
+        ecs_world.camera_origin = self.camera_origin;
+
+        // 1. Spawn entities with their plain components
+        let mut id_map: HashMap<u64, Entity> = HashMap::new();
+        for saved in &self.entities {
+            let entity = Self::spawn(&mut ecs_world.world, saved);
+            id_map.insert(saved.id, entity);
+        }
+
+        // 2. Reconnect relationships now that every entity exists
+        for saved in &self.entities {
+            let entity = id_map[&saved.id];
+
+            if let Some(parent) = saved.parent.and_then(|id| id_map.get(&id)) {
+                let _ = ecs_world.world.insert_one(entity, Parent(*parent));
+            }
+
+            if let Some(children) = &saved.children {
+                let children: Vec<Entity> = children.iter().filter_map(|id| id_map.get(id).copied()).collect();
+                let _ = ecs_world.world.insert_one(entity, Children(children));
+            }
+
+            if let Some(turn_state) = &saved.turn_state {
+                let remapped = TurnState {
+                    pending_orders: Self::remap_orders(&turn_state.pending_orders, &id_map),
+                    completed_orders: Self::remap_orders(&turn_state.completed_orders, &id_map),
+                    ..turn_state.clone()
+                };
+                let _ = ecs_world.world.insert_one(entity, remapped);
+            }
+        }
+
+        ecs_world
+    }
+
+    /// Load world data from a JSON file
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let data: EcsWorldData = serde_json::from_str(&content)?;
+        Ok(data)
+    }
+
+    /// Save world data to a JSON file with pretty formatting
+    pub fn save(&self, path: &str) -> Result<()> {
+        // Ensure parent directory exists
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    fn capture(world: &World, entity: Entity) -> SavedEntity {
+        SavedEntity {
+            id: entity.to_bits().get(),
+            position: component(world, entity),
+            rotation: component(world, entity),
+            scale: component(world, entity),
+            velocity: component(world, entity),
+            angular_velocity: component(world, entity),
+            mass: component(world, entity),
+            entity_type: component(world, entity),
+            visual: component(world, entity),
+            health: component(world, entity),
+            asteroid: component(world, entity),
+            planet: component(world, entity),
+            nebula: component(world, entity),
+            star: component(world, entity),
+            ship: component(world, entity),
+            movement_curve: component(world, entity),
+            turn_state: component(world, entity),
+            unit: component(world, entity),
+            physics_body: component(world, entity),
+            parent: component::<Parent>(world, entity).map(|parent| parent.0.to_bits().get()),
+            children: component::<Children>(world, entity)
+                .map(|children| children.0.iter().map(|child| child.to_bits().get()).collect()),
+        }
+    }
+
+    /// Spawn an entity with every component except relationships (added once all entities exist)
+    fn spawn(world: &mut World, saved: &SavedEntity) -> Entity {
+        let mut builder = EntityBuilder::new();
+        add(&mut builder, saved.position);
+        add(&mut builder, saved.rotation);
+        add(&mut builder, saved.scale);
+        add(&mut builder, saved.velocity);
+        add(&mut builder, saved.angular_velocity);
+        add(&mut builder, saved.mass);
+        add(&mut builder, saved.entity_type);
+        add(&mut builder, saved.visual.clone());
+        add(&mut builder, saved.health);
+        add(&mut builder, saved.asteroid.clone());
+        add(&mut builder, saved.planet.clone());
+        add(&mut builder, saved.nebula.clone());
+        add(&mut builder, saved.star.clone());
+        add(&mut builder, saved.ship.clone());
+        add(&mut builder, saved.movement_curve);
+        add(&mut builder, saved.unit.clone());
+        add(&mut builder, saved.physics_body);
+        world.spawn(builder.build())
+    }
+
+    /// Point attack orders at the reloaded entities (unknown targets are kept as-is)
+    fn remap_orders(orders: &[Order], id_map: &HashMap<u64, Entity>) -> Vec<Order> {
+        orders
+            .iter()
+            .map(|order| match order {
+                Order::Attack { target_entity } => Order::Attack {
+                    target_entity: id_map
+                        .get(target_entity)
+                        .map(|entity| entity.to_bits().get())
+                        .unwrap_or(*target_entity),
+                },
+                other => other.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Clone a component off an entity if it has one
+fn component<T: hecs::Component + Clone>(world: &World, entity: Entity) -> Option<T> {
+    world.get::<&T>(entity).ok().map(|component| (*component).clone())
+}
+
+fn add<T: hecs::Component>(builder: &mut EntityBuilder, component: Option<T>) {
+    if let Some(component) = component {
+        builder.add(component);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::DQuat;
+    use crate::ecs::init::{create_asteroid_entity, create_nebula_entity, create_ship_entity, create_star_entity};
+
+    fn find(world: &World, entity_type: EntityType) -> Entity {
+        world
+            .query::<&EntityType>()
+            .iter()
+            .find(|(_, found)| **found == entity_type)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    fn assert_same<T: hecs::Component + Clone + PartialEq + std::fmt::Debug>(
+        a: &World,
+        a_entity: Entity,
+        b: &World,
+        b_entity: Entity,
+    ) {
+        assert_eq!(component::<T>(a, a_entity), component::<T>(b, b_entity));
+    }
+
+    #[test]
+    fn test_world_round_trip() {
+        let mut ecs_world = EcsWorld::new();
+        ecs_world.camera_origin = DVec3::new(1.0e9 + 0.25, -3.5, 7.0e-3);
+
+        let nebula = create_nebula_entity(&mut ecs_world.world, DVec3::new(0.0, 0.0, -10_000.0), 1.0);
+        create_star_entity(&mut ecs_world.world, nebula, 695_700_000.0, DVec3::new(0.0, 0.0, 10_000.0));
+        // Coordinates that lose precision in f32 must survive exactly
+        let ship = create_ship_entity(
+            &mut ecs_world.world,
+            "Test Ship".to_string(),
+            "Player".to_string(),
+            DVec3::new(149_597_870_700.125, 0.1 + 0.2, -1.0e15 / 3.0),
+            DQuat::from_rotation_y(0.3),
+        );
+        let asteroid = create_asteroid_entity(&mut ecs_world.world, DVec3::new(5_000.0, -100.0, 1.0 / 7.0), 120.0);
+        let _ = ecs_world.world.insert_one(ship, Velocity(DVec3::new(0.1, 0.2, 0.3)));
+        let _ = ecs_world.world.insert_one(ship, Health::new(75.0));
+        let _ = ecs_world.world.insert_one(ship, TurnState {
+            pending_orders: vec![Order::Attack { target_entity: asteroid.to_bits().get() }, Order::Wait],
+            completed_orders: Vec::new(),
+            action_points: 2,
+            max_action_points: 3,
+        });
+
+        let path = std::env::temp_dir().join("tribal_engine_test_ecs_world.json");
+        let path = path.to_str().unwrap();
+        ecs_world.save(path).unwrap();
+        let loaded = EcsWorld::load(path).unwrap();
+        let _ = fs::remove_file(path);
+
+        assert_eq!(loaded.entity_count(), ecs_world.entity_count());
+        assert_eq!(loaded.camera_origin, ecs_world.camera_origin);
+
+        let (a, b) = (&ecs_world.world, &loaded.world);
+        for entity_type in [EntityType::Nebula, EntityType::Star, EntityType::Ship, EntityType::Asteroid] {
+            let (a_entity, b_entity) = (find(a, entity_type), find(b, entity_type));
+            assert_same::<Position>(a, a_entity, b, b_entity);
+            assert_same::<Rotation>(a, a_entity, b, b_entity);
+            assert_same::<Scale>(a, a_entity, b, b_entity);
+            assert_same::<Velocity>(a, a_entity, b, b_entity);
+            assert_same::<AngularVelocity>(a, a_entity, b, b_entity);
+            assert_same::<Mass>(a, a_entity, b, b_entity);
+            assert_same::<Visual>(a, a_entity, b, b_entity);
+            assert_same::<Health>(a, a_entity, b, b_entity);
+            assert_same::<Asteroid>(a, a_entity, b, b_entity);
+            assert_same::<Nebula>(a, a_entity, b, b_entity);
+            assert_same::<Star>(a, a_entity, b, b_entity);
+            assert_same::<Ship>(a, a_entity, b, b_entity);
+            assert_same::<Unit>(a, a_entity, b, b_entity);
+            assert_same::<PhysicsBody>(a, a_entity, b, b_entity);
+        }
+
+        // Exact f64 coordinates
+        let original = component::<Position>(a, ship).unwrap().0;
+        let reloaded = component::<Position>(b, find(b, EntityType::Ship)).unwrap().0;
+        assert_eq!(original.x.to_bits(), reloaded.x.to_bits());
+        assert_eq!(original.y.to_bits(), reloaded.y.to_bits());
+        assert_eq!(original.z.to_bits(), reloaded.z.to_bits());
+
+        // Star stays parented to the nebula
+        let (loaded_nebula, loaded_star) = (find(b, EntityType::Nebula), find(b, EntityType::Star));
+        assert_eq!(component::<Parent>(b, loaded_star), Some(Parent(loaded_nebula)));
+        assert_eq!(component::<Children>(b, loaded_nebula), Some(Children(vec![loaded_star])));
+
+        // Attack orders point at the reloaded asteroid
+        let turn_state = component::<TurnState>(b, find(b, EntityType::Ship)).unwrap();
+        let loaded_asteroid = find(b, EntityType::Asteroid);
+        assert_eq!(turn_state.pending_orders[0], Order::Attack { target_entity: loaded_asteroid.to_bits().get() });
+        assert_eq!(turn_state.pending_orders[1], Order::Wait);
+        assert_eq!(turn_state.action_points, 2);
+    }
+}
@@ -1,14 +1,21 @@
 use winit::{
     event::{Event, WindowEvent, KeyEvent, ElementState, DeviceEvent, MouseButton},
     event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    window::{Window, WindowBuilder},
     keyboard::{KeyCode, PhysicalKey},
+    dpi::{PhysicalPosition, PhysicalSize},
 };
+use crate::config::{EngineConfig, WindowConfigData};
 use crate::core::renderer::VulkanRenderer;
+use crate::core::CameraMode;
 use crate::game::Game;
+use crate::keymap::Action;
 use crate::ui::UiManager;
 use std::collections::HashSet;
 
+/// Resolution of posters rendered with Shift+F12 (4K UHD)
+const POSTER_SIZE: (u32, u32) = (3840, 2160);
+
 pub struct Engine {
     event_loop: EventLoop<()>,
     renderer: VulkanRenderer,
@@ -23,7 +30,6 @@ struct GameState {
     right_mouse_pressed: bool,
     left_mouse_pressed: bool,
     middle_mouse_pressed: bool,
-    camera_speed: f32,
     frame_count: u32,
     fps_timer: std::time::Instant,
     current_fps: f32,
@@ -48,9 +54,16 @@ impl Engine {
     pub fn run(mut self) -> anyhow::Result<()> {
         let mut game = Game::new();
 
-        // Load configs and scene from files
-        UiManager::load_all_configs(&mut game);
+        // Load scene and configs from files (the scene names its config file)
         UiManager::load_scene_on_startup(&mut game);
+        UiManager::load_all_configs(&mut game);
+        UiManager::check_for_autosave(&mut game);
+
+        // Reopen the window where it was last closed
+        let config = EngineConfig::load_or_fallback(&game.config_path, crate::scene::LEGACY_CONFIG_PATH);
+        if let Some(saved) = config.window {
+            restore_window_rect(self.renderer.window(), &saved);
+        }
 
         let now = std::time::Instant::now();
         let mut game_state = GameState {
@@ -62,7 +75,6 @@ impl Engine {
             right_mouse_pressed: false,
             left_mouse_pressed: false,
             middle_mouse_pressed: false,
-            camera_speed: 5.0,
             frame_count: 0,
             fps_timer: now,
             current_fps: 0.0,
@@ -84,6 +96,7 @@ impl Engine {
                     event: WindowEvent::CloseRequested,
                     ..
                 } => {
+                    save_window_rect(self.renderer.window(), &game_state.game.config_path);
                     target.exit();
                 }
                 Event::WindowEvent {
@@ -135,11 +148,14 @@ impl Engine {
                                 game_state.left_mouse_pressed = true;
                                 if !self.renderer.imgui_wants_mouse() {
                                     let (viewport_width, viewport_height) = self.renderer.viewport_size();
+                                    let additive = game_state.pressed_keys.contains(&KeyCode::ControlLeft)
+                                        || game_state.pressed_keys.contains(&KeyCode::ControlRight);
                                     game_state.game.handle_mouse_click(
                                         game_state.mouse_position.0 as f32,
                                         game_state.mouse_position.1 as f32,
                                         viewport_width,
                                         viewport_height,
+                                        additive,
                                     );
                                 }
                             }
@@ -211,25 +227,20 @@ impl Engine {
                         ElementState::Pressed => {
                             game_state.pressed_keys.insert(key_code);
 
-                            // Gizmo mode hotkeys (1, 2, 3) - only if not typing in ImGui
-                            if !self.renderer.imgui_wants_keyboard() {
-                                match key_code {
-                                    KeyCode::Digit1 => {
-                                        game_state.game.gizmo_state.mode = crate::gizmo::GizmoMode::Translate;
-                                    }
-                                    KeyCode::Digit2 => {
-                                        game_state.game.gizmo_state.mode = crate::gizmo::GizmoMode::Rotate;
-                                    }
-                                    KeyCode::Digit3 => {
-                                        game_state.game.gizmo_state.mode = crate::gizmo::GizmoMode::Scale;
-                                    }
-                                    KeyCode::Enter => {
-                                        // End turn and execute ship movement in play mode
-                                        if game_state.game.game_manager.mode == crate::game_manager::GameMode::Play {
-                                            game_state.game.execute_ship_movement();
-                                        }
-                                    }
-                                    _ => {}
+                            let ctrl_held = game_state.pressed_keys.contains(&KeyCode::ControlLeft)
+                                || game_state.pressed_keys.contains(&KeyCode::ControlRight);
+                            let shift_held = game_state.pressed_keys.contains(&KeyCode::ShiftLeft)
+                                || game_state.pressed_keys.contains(&KeyCode::ShiftRight);
+
+                            if game_state.game.keymap_capture.is_some() {
+                                // The Keymap panel is waiting for a key to assign
+                                if !crate::keymap::is_modifier(key_code) {
+                                    game_state.game.finish_key_capture(key_code, ctrl_held, shift_held);
+                                }
+                            } else if let Some(action) = game_state.game.keymap.action_for(key_code, ctrl_held, shift_held) {
+                                // Only a few global shortcuts fire while typing in ImGui
+                                if action.works_while_typing() || !self.renderer.imgui_wants_keyboard() {
+                                    handle_action(&mut self.renderer, action, shift_held, &mut game_state);
                                 }
                             }
                         }
@@ -237,6 +248,10 @@ impl Engine {
                             game_state.pressed_keys.remove(&key_code);
                         }
                     }
+
+                    // Holding Ctrl temporarily toggles gizmo snapping
+                    game_state.game.gizmo_state.snap_toggle_held = game_state.pressed_keys.contains(&KeyCode::ControlLeft)
+                        || game_state.pressed_keys.contains(&KeyCode::ControlRight);
                 }
                 Event::WindowEvent {
                     event: WindowEvent::MouseWheel { delta, .. },
@@ -247,8 +262,13 @@ impl Engine {
                         MouseScrollDelta::LineDelta(_x, y) => y,
                         MouseScrollDelta::PixelDelta(pos) => (pos.y / 20.0) as f32,
                     };
-                    game_state.camera_speed = (game_state.camera_speed + scroll_amount).max(0.1).min(50.0);
-                    println!("Camera Speed: {:.1}", game_state.camera_speed);
+                    // Scrolling an ImGui window (or during track playback) shouldn't move the camera
+                    if !self.renderer.imgui_wants_mouse() && !game_state.game.is_camera_track_playing() {
+                        match game_state.game.camera.mode() {
+                            CameraMode::Fly => game_state.game.scale_camera_speed(scroll_amount),
+                            CameraMode::Orbit => game_state.game.dolly_camera(scroll_amount),
+                        }
+                    }
                 }
                 Event::WindowEvent {
                     event: WindowEvent::Resized(_),
@@ -269,9 +289,19 @@ impl Engine {
                     game_state.last_update_time = now;
 
                     // Process input
-                    process_input(&mut game_state, delta_time);
+                    let imgui_wants_mouse = self.renderer.imgui_wants_mouse();
+                    let imgui_wants_keyboard = self.renderer.imgui_wants_keyboard();
+                    process_input(&mut game_state, delta_time, imgui_wants_mouse, imgui_wants_keyboard);
+
+                    // Keymap actions picked in the command palette last frame
+                    for action in std::mem::take(&mut game_state.game.queued_actions) {
+                        handle_action(&mut self.renderer, action, false, &mut game_state);
+                    }
 
                     game_state.game.update(delta_time);
+                    if game_state.game.autosave_due() {
+                        UiManager::autosave(&mut game_state.game);
+                    }
 
                     // Update FPS counter
                     game_state.frame_count += 1;
@@ -301,16 +331,210 @@ impl Engine {
     }
 }
 
-fn process_input(game_state: &mut GameState, delta_time: f32) {
+/// Run a pressed key's action. `shift_held` picks the poster over the screenshot and the larger nudge step.
+fn handle_action(renderer: &mut VulkanRenderer, action: Action, shift_held: bool, game_state: &mut GameState) {
+    use crate::gizmo::{GizmoAxis, GizmoMode};
+    let game = &mut game_state.game;
+    match action {
+        Action::ShowHelp => game.show_keymap_help = !game.show_keymap_help,
+        Action::ToggleStats => game.show_stats_overlay = !game.show_stats_overlay,
+        Action::ToggleMinimap => game.show_minimap = !game.show_minimap,
+        Action::ReloadShaders => {
+            // Recompile changed shaders and rebuild pipelines
+            let report = renderer.reload_shaders();
+            game.report_shader_reload(&report);
+        }
+        Action::Screenshot | Action::Poster => {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            if action == Action::Poster {
+                // Render at POSTER_SIZE, independent of the window
+                let (width, height) = POSTER_SIZE;
+                let path = format!("screenshots/poster_{}.png", timestamp);
+                match renderer.render_to_image(width, height, &path, game) {
+                    Ok(()) => {
+                        println!("Poster saved: {}", path);
+                        game.add_notification(format!("Poster saved: {}", path), 3.0);
+                    }
+                    Err(e) => {
+                        crate::console::error(format!("Failed to render poster: {}", e));
+                        game.add_notification(format!("Poster failed: {}", e), 3.0);
+                    }
+                }
+            } else {
+                // Saved from the next frame
+                let path = format!("screenshots/screenshot_{}.png", timestamp);
+                renderer.capture_screenshot(&path);
+            }
+        }
+        Action::ToggleConsole => game.console.open = !game.console.open,
+        Action::CommandPalette => game.command_palette.toggle(),
+        Action::Cancel => {
+            // Clear the current measurement, stop track playback and release the orbit follow
+            game.ruler.clear();
+            game.stop_camera_track();
+            game.stop_following();
+        }
+        Action::GizmoTranslate => game.gizmo_state.mode = GizmoMode::Translate,
+        Action::GizmoRotate => game.gizmo_state.mode = GizmoMode::Rotate,
+        Action::GizmoScale => game.gizmo_state.mode = GizmoMode::Scale,
+        Action::FrameSelection => {
+            // Frame the selection (or the whole scene)
+            if game.game_manager.is_editing() {
+                game.frame_selected();
+            }
+        }
+        Action::ToggleRuler => {
+            if game.game_manager.is_editing() {
+                game.toggle_ruler();
+            }
+        }
+        Action::Copy | Action::Paste | Action::CopyMaterial | Action::PasteMaterial => {
+            if game.game_manager.is_editing() {
+                match action {
+                    Action::Copy => game.copy_selected(),
+                    Action::CopyMaterial => game.copy_material(),
+                    Action::Paste => {
+                        game.paste();
+                    }
+                    _ => game.paste_material(),
+                }
+            }
+        }
+        Action::NudgeLeft | Action::NudgeRight | Action::NudgeUp | Action::NudgeDown
+        | Action::NudgeRaise | Action::NudgeLower => {
+            // Nudge the selection by the snap increment (Shift = larger step).
+            // Translate: Left/Right/Up/Down move on X/Z, Raise/Lower on Y.
+            // Rotate: Left/Right yaw about Y, Up/Down pitch about X, Raise/Lower roll about Z.
+            if game.game_manager.is_editing() && game.scene.selected_object_id().is_some() {
+                let rotating = game.gizmo_state.mode == GizmoMode::Rotate;
+                let (axis, sign) = match action {
+                    Action::NudgeLeft if rotating => (GizmoAxis::Y, 1.0),
+                    Action::NudgeRight if rotating => (GizmoAxis::Y, -1.0),
+                    Action::NudgeUp if rotating => (GizmoAxis::X, -1.0),
+                    Action::NudgeDown if rotating => (GizmoAxis::X, 1.0),
+                    Action::NudgeRaise if rotating => (GizmoAxis::Z, 1.0),
+                    Action::NudgeLower if rotating => (GizmoAxis::Z, -1.0),
+                    Action::NudgeLeft => (GizmoAxis::X, -1.0),
+                    Action::NudgeRight => (GizmoAxis::X, 1.0),
+                    Action::NudgeUp => (GizmoAxis::Z, -1.0),
+                    Action::NudgeDown => (GizmoAxis::Z, 1.0),
+                    Action::NudgeRaise => (GizmoAxis::Y, 1.0),
+                    _ => (GizmoAxis::Y, -1.0),
+                };
+                game.nudge_selection(axis, sign, shift_held);
+            }
+        }
+        Action::ToggleCameraMode => game.toggle_camera_mode(),
+        Action::ToggleProjection => game.toggle_camera_projection(),
+        Action::Bookmark1 => {
+            game.goto_camera_bookmark(0);
+        }
+        Action::Bookmark2 => {
+            game.goto_camera_bookmark(1);
+        }
+        Action::Bookmark3 => {
+            game.goto_camera_bookmark(2);
+        }
+        Action::Bookmark4 => {
+            game.goto_camera_bookmark(3);
+        }
+        Action::Bookmark5 => {
+            game.goto_camera_bookmark(4);
+        }
+        Action::EndTurn => {
+            // End turn and execute ship movement in play mode
+            if game.game_manager.mode == crate::game_manager::GameMode::Play {
+                game.end_turn();
+            }
+        }
+        // Held actions, polled every frame in process_input
+        Action::MoveForward | Action::MoveBack | Action::MoveLeft | Action::MoveRight
+        | Action::MoveUp | Action::MoveDown
+        | Action::StarDensityDown | Action::StarDensityUp | Action::StarBrightnessDown | Action::StarBrightnessUp
+        | Action::NebulaIntensityDown | Action::NebulaIntensityUp | Action::BackgroundDown | Action::BackgroundUp => {}
+    }
+}
+
+/// Speed multiplier while Shift is held in fly mode
+const FLY_BOOST: f32 = 4.0;
+
+/// Size and place the window from a saved rect, kept on a connected monitor
+fn restore_window_rect(window: &Window, saved: &WindowConfigData) {
+    // Prefer the monitor the saved position lies on, then the one the window opened on
+    let monitor = saved
+        .x
+        .zip(saved.y)
+        .and_then(|(x, y)| {
+            window.available_monitors().find(|monitor| {
+                let (position, size) = (monitor.position(), monitor.size());
+                (position.x..position.x + size.width as i32).contains(&x)
+                    && (position.y..position.y + size.height as i32).contains(&y)
+            })
+        })
+        .or_else(|| window.current_monitor());
+
+    let rect = match monitor {
+        Some(monitor) => {
+            let (position, size) = (monitor.position(), monitor.size());
+            saved.clamped_to_monitor((position.x, position.y), (size.width, size.height))
+        }
+        None => *saved,
+    };
+
+    let _ = window.request_inner_size(PhysicalSize::new(rect.width, rect.height));
+    if let (Some(x), Some(y)) = (rect.x, rect.y) {
+        window.set_outer_position(PhysicalPosition::new(x, y));
+    }
+}
+
+/// Store the window rect in the scene's config file on exit (other sections are left as saved)
+fn save_window_rect(window: &Window, config_path: &str) {
+    // Never replace a missing or unreadable config with defaults just to record the window
+    let Ok(mut config) = EngineConfig::load(config_path) else {
+        return;
+    };
+
+    let size = window.inner_size();
+    if size.width == 0 || size.height == 0 {
+        return; // Minimized; keep the last real size
+    }
+    let position = window.outer_position().ok();
+    config.window = Some(WindowConfigData {
+        width: size.width,
+        height: size.height,
+        x: position.map(|position| position.x),
+        y: position.map(|position| position.y),
+    });
+
+    if let Err(e) = config.save(config_path) {
+        crate::console::error(format!("Failed to save window size: {}", e));
+    }
+}
+
+fn process_input(game_state: &mut GameState, delta_time: f32, imgui_wants_mouse: bool, imgui_wants_keyboard: bool) {
+    let mode = game_state.game.camera.mode();
+
     // Mouse camera controls
     let mouse_sensitivity = 0.002;
 
-    // Right mouse - free camera rotation
+    // A playing camera track owns the camera
+    let track_playing = game_state.game.is_camera_track_playing();
+
+    if imgui_wants_mouse || track_playing {
+        game_state.mouse_delta = (0.0, 0.0);
+    }
+
+    // Right mouse - mouse-look (fly) or orbit around the selection (orbit)
     if game_state.right_mouse_pressed && (game_state.mouse_delta.0 != 0.0 || game_state.mouse_delta.1 != 0.0) {
-        game_state.game.rotate_camera(
-            -(game_state.mouse_delta.1 as f32) * mouse_sensitivity,  // Pitch (vertical)
-            -(game_state.mouse_delta.0 as f32) * mouse_sensitivity,  // Yaw (horizontal)
-        );
+        let pitch_delta = -(game_state.mouse_delta.1 as f32) * mouse_sensitivity;  // Pitch (vertical)
+        let yaw_delta = -(game_state.mouse_delta.0 as f32) * mouse_sensitivity;    // Yaw (horizontal)
+        match mode {
+            CameraMode::Fly => game_state.game.rotate_camera(pitch_delta, yaw_delta),
+            CameraMode::Orbit => game_state.game.orbit_camera_around_selected(pitch_delta, yaw_delta),
+        }
         game_state.mouse_delta = (0.0, 0.0);
     }
 
@@ -323,84 +547,87 @@ fn process_input(game_state: &mut GameState, delta_time: f32) {
         game_state.mouse_delta = (0.0, 0.0);
     }
 
-    // Free camera movement controls
-    let speed = game_state.camera_speed * delta_time;
-
-    // W/S - Forward/Backward (in the direction camera is facing)
-    if game_state.pressed_keys.contains(&KeyCode::KeyW) {
-        game_state.game.move_camera_forward(speed);
-    }
-    if game_state.pressed_keys.contains(&KeyCode::KeyS) {
-        game_state.game.move_camera_forward(-speed);
+    // Keys typed into ImGui widgets must not move the camera
+    if !imgui_wants_keyboard && !track_playing {
+        match mode {
+            CameraMode::Fly => process_fly_keys(game_state, delta_time),
+            CameraMode::Orbit => {
+                // Fly down/up keys roll the orbit camera
+                let keymap = &game_state.game.keymap;
+                let roll = (keymap.is_held(Action::MoveUp, &game_state.pressed_keys) as i32
+                    - keymap.is_held(Action::MoveDown, &game_state.pressed_keys) as i32) as f32;
+                if roll != 0.0 {
+                    game_state.game.roll_camera(roll * 2.0 * delta_time);
+                }
+            }
+        }
     }
 
-    // A/D - Strafe left/right
-    if game_state.pressed_keys.contains(&KeyCode::KeyA) {
-        game_state.game.move_camera_right(-speed);
-    }
-    if game_state.pressed_keys.contains(&KeyCode::KeyD) {
-        game_state.game.move_camera_right(speed);
+    if !imgui_wants_keyboard {
+        process_skybox_keys(game_state, delta_time);
     }
+}
 
-    // Q/E - Roll
-    if game_state.pressed_keys.contains(&KeyCode::KeyQ) {
-        game_state.game.roll_camera(-2.0 * delta_time);
-    }
-    if game_state.pressed_keys.contains(&KeyCode::KeyE) {
-        game_state.game.roll_camera(2.0 * delta_time);
-    }
+/// Held skybox tweaks (unbound by default, assign keys in the Keymap panel)
+fn process_skybox_keys(game_state: &mut GameState, delta_time: f32) {
+    let keymap = &game_state.game.keymap;
+    let keys = &game_state.pressed_keys;
+    let axis = |up: Action, down: Action| {
+        (keymap.is_held(up, keys) as i32 - keymap.is_held(down, keys) as i32) as f32
+    };
+    let density = axis(Action::StarDensityUp, Action::StarDensityDown);
+    let brightness = axis(Action::StarBrightnessUp, Action::StarBrightnessDown);
+    let nebula = axis(Action::NebulaIntensityUp, Action::NebulaIntensityDown);
+    let background = axis(Action::BackgroundUp, Action::BackgroundDown);
 
-    // Skybox tweaking controls
     let config_speed = 0.5 * delta_time;
-
-    // Star density (1/2)
-    if game_state.pressed_keys.contains(&KeyCode::Digit1) {
-        game_state.game.skybox_config.star_density = (game_state.game.skybox_config.star_density - config_speed).max(0.1);
-        println!("Star Density: {:.2}", game_state.game.skybox_config.star_density);
-    }
-    if game_state.pressed_keys.contains(&KeyCode::Digit2) {
-        game_state.game.skybox_config.star_density = (game_state.game.skybox_config.star_density + config_speed).min(2.0);
-        println!("Star Density: {:.2}", game_state.game.skybox_config.star_density);
+    let skybox = &mut game_state.game.skybox_config;
+    if density != 0.0 {
+        skybox.star_density = (skybox.star_density + density * config_speed).clamp(0.1, 2.0);
+        println!("Star Density: {:.2}", skybox.star_density);
     }
-
-    // Star brightness (3/4)
-    if game_state.pressed_keys.contains(&KeyCode::Digit3) {
-        game_state.game.skybox_config.star_brightness = (game_state.game.skybox_config.star_brightness - config_speed).max(0.0);
-        println!("Star Brightness: {:.2}", game_state.game.skybox_config.star_brightness);
+    if brightness != 0.0 {
+        skybox.star_brightness = (skybox.star_brightness + brightness * config_speed).clamp(0.0, 3.0);
+        println!("Star Brightness: {:.2}", skybox.star_brightness);
     }
-    if game_state.pressed_keys.contains(&KeyCode::Digit4) {
-        game_state.game.skybox_config.star_brightness = (game_state.game.skybox_config.star_brightness + config_speed).min(3.0);
-        println!("Star Brightness: {:.2}", game_state.game.skybox_config.star_brightness);
+    if nebula != 0.0 {
+        skybox.nebula_intensity = (skybox.nebula_intensity + nebula * config_speed * 0.5).clamp(0.0, 2.0);
+        println!("Nebula Intensity: {:.2}", skybox.nebula_intensity);
     }
-
-    // Nebula intensity (5/6)
-    if game_state.pressed_keys.contains(&KeyCode::Digit5) {
-        game_state.game.skybox_config.nebula_intensity = (game_state.game.skybox_config.nebula_intensity - config_speed * 0.5).max(0.0);
-        println!("Nebula Intensity: {:.2}", game_state.game.skybox_config.nebula_intensity);
-    }
-    if game_state.pressed_keys.contains(&KeyCode::Digit6) {
-        game_state.game.skybox_config.nebula_intensity = (game_state.game.skybox_config.nebula_intensity + config_speed * 0.5).min(2.0);
-        println!("Nebula Intensity: {:.2}", game_state.game.skybox_config.nebula_intensity);
+    if background != 0.0 {
+        skybox.background_brightness = (skybox.background_brightness + background * config_speed * 0.1).clamp(0.0, 0.5);
+        println!("Background Brightness: {:.2}", skybox.background_brightness);
     }
+}
 
-    // Background brightness (7/8)
-    if game_state.pressed_keys.contains(&KeyCode::Digit7) {
-        game_state.game.skybox_config.background_brightness = (game_state.game.skybox_config.background_brightness - config_speed * 0.1).max(0.0);
-        println!("Background Brightness: {:.2}", game_state.game.skybox_config.background_brightness);
+/// Fly-mode movement relative to the view direction (Shift boosts)
+fn process_fly_keys(game_state: &mut GameState, delta_time: f32) {
+    let keys = &game_state.pressed_keys;
+    let boost = if keys.contains(&KeyCode::ShiftLeft) || keys.contains(&KeyCode::ShiftRight) {
+        FLY_BOOST
+    } else {
+        1.0
+    };
+    let speed = game_state.game.camera.move_speed() * boost * delta_time;
+
+    let keymap = &game_state.game.keymap;
+    let axis = |positive: Action, negative: Action| {
+        (keymap.is_held(positive, keys) as i32 - keymap.is_held(negative, keys) as i32) as f32
+    };
+    let forward = axis(Action::MoveForward, Action::MoveBack);
+    let right = axis(Action::MoveRight, Action::MoveLeft);
+    let up = axis(Action::MoveUp, Action::MoveDown);
+
+    // Forward/Backward (in the direction camera is facing)
+    if forward != 0.0 {
+        game_state.game.move_camera_forward(forward * speed);
     }
-    if game_state.pressed_keys.contains(&KeyCode::Digit8) {
-        game_state.game.skybox_config.background_brightness = (game_state.game.skybox_config.background_brightness + config_speed * 0.1).min(0.5);
-        println!("Background Brightness: {:.2}", game_state.game.skybox_config.background_brightness);
+    // Strafe left/right
+    if right != 0.0 {
+        game_state.game.move_camera_right(right * speed);
     }
-
-    // Print controls help (H key)
-    if game_state.pressed_keys.contains(&KeyCode::KeyH) {
-        println!("\n=== SKYBOX CONTROLS ===");
-        println!("1/2: Star Density (-/+)");
-        println!("3/4: Star Brightness (-/+)");
-        println!("5/6: Nebula Intensity (-/+)");
-        println!("7/8: Background Brightness (-/+)");
-        println!("H: Show this help");
-        println!("=======================\n");
+    // Down/Up along the camera's own up axis
+    if up != 0.0 {
+        game_state.game.move_camera_up(up * speed);
     }
 }
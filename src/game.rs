@@ -1,11 +1,33 @@
 use glam::{Mat4, Quat, Vec3, DVec3, DQuat};
 use crate::nebula::NebulaConfig;
 use crate::core::Camera;
-use crate::scene::{SceneGraph, ObjectType, ObjectId};
+use crate::scene::{SceneGraph, ObjectType, ObjectId, Transform};
 use crate::gizmo::{GizmoState, ObjectPicker};
 use crate::game_manager::GameManager;
 use serde::{Serialize, Deserialize};
 
+/// What the skybox draws behind the scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SkyboxMode {
+    /// Generated star field and nebula tint
+    #[default]
+    Procedural,
+    /// Sampled from a cubemap (six face images or an equirectangular panorama)
+    Cubemap,
+}
+
+impl SkyboxMode {
+    /// All modes, in the order shown in the UI
+    pub const ALL: [SkyboxMode; 2] = [Self::Procedural, Self::Cubemap];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Procedural => "Procedural",
+            Self::Cubemap => "Cubemap",
+        }
+    }
+}
+
 /// Skybox configuration
 #[derive(Clone)]
 pub struct SkyboxConfig {
@@ -21,6 +43,10 @@ pub struct SkyboxConfig {
     pub nebula_intensity: f32,
     /// Background darkness (0.0 = black, 1.0 = lighter)
     pub background_brightness: f32,
+    /// Procedural stars or a loaded cubemap
+    pub mode: SkyboxMode,
+    /// Directory of `px`/`nx`/`py`/`ny`/`pz`/`nz` face images, or an equirectangular panorama file
+    pub cubemap_path: String,
 }
 
 impl From<crate::config::SkyboxConfigData> for SkyboxConfig {
@@ -32,6 +58,8 @@ impl From<crate::config::SkyboxConfigData> for SkyboxConfig {
             nebula_secondary_color: data.nebula_secondary_color,
             nebula_intensity: data.nebula_intensity,
             background_brightness: data.background_brightness,
+            mode: data.mode,
+            cubemap_path: data.cubemap_path,
         }
     }
 }
@@ -45,10 +73,16 @@ impl From<&SkyboxConfig> for crate::config::SkyboxConfigData {
             nebula_secondary_color: config.nebula_secondary_color,
             nebula_intensity: config.nebula_intensity,
             background_brightness: config.background_brightness,
+            mode: config.mode,
+            cubemap_path: config.cubemap_path.clone(),
         }
     }
 }
 
+/// Exposure range of the star shader (the default of 40.2 sits well inside it)
+pub const MIN_STAR_EXPOSURE: f32 = 0.1;
+pub const MAX_STAR_EXPOSURE: f32 = 200.0;
+
 /// Star shader configuration
 #[derive(Clone)]
 pub struct StarConfig {
@@ -84,8 +118,9 @@ impl From<crate::config::StarConfigData> for StarConfig {
     fn from(data: crate::config::StarConfigData) -> Self {
         Self {
             color: data.color,
-            gamma: data.gamma,
-            exposure: data.exposure,
+            // star.frag raises to 1/gamma, so a zero or negative gamma falls back to 2.2
+            gamma: if data.gamma > 0.0 { data.gamma } else { 2.2 },
+            exposure: data.exposure.clamp(MIN_STAR_EXPOSURE, MAX_STAR_EXPOSURE),
             speed_hi: data.speed_hi,
             speed_low: data.speed_low,
             zoom: data.zoom,
@@ -115,6 +150,8 @@ impl From<crate::config::SSAOConfigData> for SSAOConfig {
             bias: data.bias,
             power: data.power,
             kernel_size: data.kernel_size,
+            ao_intensity: data.ao_intensity,
+            show_ao_only: false,
         }
     }
 }
@@ -127,837 +164,3478 @@ impl From<&SSAOConfig> for crate::config::SSAOConfigData {
             bias: config.bias,
             power: config.power,
             kernel_size: config.kernel_size,
+            ao_intensity: config.ao_intensity,
         }
     }
 }
 
-impl Default for SkyboxConfig {
+/// Shadow map configuration (single cascade for the directional light)
+#[derive(Debug, Clone)]
+pub struct ShadowConfig {
+    pub enabled: bool,
+    /// Shadow map size in texels (one of `ShadowConfig::RESOLUTIONS`)
+    pub resolution: u32,
+    /// Half-extent of the light's orthographic box, centered on the camera
+    pub range: f32,
+    /// Depth bias applied when comparing against the shadow map (avoids acne)
+    pub depth_bias: f32,
+}
+
+impl ShadowConfig {
+    /// Supported shadow map resolutions
+    pub const RESOLUTIONS: [u32; 3] = [1024, 2048, 4096];
+}
+
+impl Default for ShadowConfig {
     fn default() -> Self {
         Self {
-            star_density: 2.0,
-            star_brightness: 3.0,
-            nebula_primary_color: Vec3::new(0.1, 0.2, 0.4),
-            nebula_secondary_color: Vec3::new(0.6, 0.3, 0.8),
-            nebula_intensity: 1.0,
-            background_brightness: 0.00,
+            enabled: true,
+            resolution: 2048,
+            range: 50.0,
+            depth_bias: 0.005,
         }
     }
 }
 
-/// Camera focus animation state
-struct CameraFocusAnimation {
-    active: bool,
-    start_position: Vec3,
-    start_pitch: f32,
-    start_yaw: f32,
-    start_roll: f32,
-    target_position: Vec3,
-    target_pitch: f32,
-    target_yaw: f32,
-    target_roll: f32,
-    progress: f32, // 0.0 to 1.0
-    duration: f32, // Total animation duration in seconds
-}
+// Shadow config conversions
+impl From<crate::config::ShadowConfigData> for ShadowConfig {
+    fn from(data: crate::config::ShadowConfigData) -> Self {
+        // Fall back to the default resolution if the file holds an unsupported size
+        let resolution = if Self::RESOLUTIONS.contains(&data.resolution) {
+            data.resolution
+        } else {
+            Self::default().resolution
+        };
 
-impl CameraFocusAnimation {
-    fn new() -> Self {
         Self {
-            active: false,
-            start_position: Vec3::ZERO,
-            start_pitch: 0.0,
-            start_yaw: 0.0,
-            start_roll: 0.0,
-            target_position: Vec3::ZERO,
-            target_pitch: 0.0,
-            target_yaw: 0.0,
-            target_roll: 0.0,
-            progress: 0.0,
-            duration: 0.5, // Half second animation
+            enabled: data.enabled,
+            resolution,
+            range: data.range,
+            depth_bias: data.depth_bias,
         }
     }
 }
 
-/// Notification message for UI
-#[derive(Clone)]
-pub struct Notification {
-    pub message: String,
-    pub time_remaining: f32, // seconds
-}
-
-impl Notification {
-    pub fn new(message: String, duration: f32) -> Self {
+impl From<&ShadowConfig> for crate::config::ShadowConfigData {
+    fn from(config: &ShadowConfig) -> Self {
         Self {
-            message,
-            time_remaining: duration,
+            enabled: config.enabled,
+            resolution: config.resolution,
+            range: config.range,
+            depth_bias: config.depth_bias,
         }
     }
 }
 
-/// SSAO configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SSAOConfig {
+/// Bloom post-process configuration (bright-pass threshold + Gaussian blur)
+#[derive(Debug, Clone)]
+pub struct BloomConfig {
     pub enabled: bool,
-    pub radius: f32,
-    pub bias: f32,
-    pub power: f32,
-    pub kernel_size: u32,
+    /// Brightness above which pixels contribute to bloom
+    pub threshold: f32,
+    /// Strength of the blurred glow added back onto the image
+    pub intensity: f32,
+    /// Number of horizontal + vertical blur iterations (wider, softer glow)
+    pub blur_passes: u32,
 }
 
-impl Default for SSAOConfig {
+impl BloomConfig {
+    /// Upper bound for `blur_passes` (each pass is two half-res fullscreen draws)
+    pub const MAX_BLUR_PASSES: u32 = 8;
+}
+
+impl Default for BloomConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            radius: 1.0,
-            bias: 0.1,
-            power: 2.0,
-            kernel_size: 64,
+            threshold: 0.8,
+            intensity: 0.6,
+            blur_passes: 2,
         }
     }
 }
 
-/// Game state and logic
-pub struct Game {
-    /// Time accumulator for animations
-    time: f32,
-    /// Camera
-    pub camera: Camera,
-    /// Scene graph with all objects (legacy - being migrated to ECS)
-    pub scene: SceneGraph,
-    /// ECS World for space entities (nebula, star, ships, asteroids)
-    pub ecs_world: crate::ecs::EcsWorld,
-    /// Nebula entity ID in ECS
-    pub nebula_entity: Option<hecs::Entity>,
-    /// Star entity ID in ECS
-    pub star_entity: Option<hecs::Entity>,
-    /// Federation Cruiser entity ID in ECS
-    pub fed_cruiser_entity: Option<hecs::Entity>,
-    /// Hologram ship for turn-based movement planning
-    pub hologram_ship_position: Option<DVec3>,
-    /// Is the player currently dragging the hologram ship?
-    pub dragging_hologram: bool,
-    /// Is the mouse hovering over the hologram ship?
-    pub hovering_hologram: bool,
-    /// Hover text to display
-    pub hover_text: Option<String>,
-    /// Gizmo state for 3D manipulation
-    pub gizmo_state: GizmoState,
-    /// Object picker for mouse selection
-    pub object_picker: ObjectPicker,
-    /// Spaceship velocity (for physics simulation)
-    ship_velocity: Vec3,
-    /// Spaceship angular velocity (for physics simulation)
-    ship_angular_velocity: Vec3,
-    /// Movement speed
-    move_speed: f32,
-    /// Rotation speed
-    rotation_speed: f32,
-    /// Skybox configuration
-    pub skybox_config: SkyboxConfig,
-    /// Nebula configuration
-    pub nebula_config: NebulaConfig,
-    /// SSAO configuration
-    pub ssao_config: SSAOConfig,
-    /// Camera focus animation state
-    focus_animation: CameraFocusAnimation,
-    /// Lock camera up vector to world Y axis
-    pub lock_camera_up: bool,
-    /// Scene dirty flag (needs save)
-    pub scene_dirty: bool,
-    /// Config dirty flag (needs save)
-    pub config_dirty: bool,
-    /// Active notifications
-    pub notifications: Vec<Notification>,
-    /// Material properties for mesh rendering
-    pub material: crate::material::MaterialProperties,
-    /// Material library
-    pub material_library: crate::material_library::MaterialLibrary,
-    /// Current material name being edited
-    pub current_material_name: String,
-    /// Material editor visibility
-    pub material_editor_open: bool,
-    /// Directional light settings
-    pub directional_light: crate::core::lighting::DirectionalLight,
-    /// Game Manager - play/pause state and scenario parameters
-    pub game_manager: GameManager,
-    /// Star configuration for shader parameters
-    pub star_config: StarConfig,
-    /// Show camera center cursor (appears when using WASD free camera)
-    pub show_camera_cursor: bool,
-    /// Camera cursor position (where camera is focused)
-    pub camera_cursor_position: DVec3,
+// Bloom config conversions
+impl From<crate::config::BloomConfigData> for BloomConfig {
+    fn from(data: crate::config::BloomConfigData) -> Self {
+        Self {
+            enabled: data.enabled,
+            threshold: data.threshold,
+            intensity: data.intensity,
+            blur_passes: data.blur_passes.clamp(1, Self::MAX_BLUR_PASSES),
+        }
+    }
 }
 
-impl Game {
-    pub fn new() -> Self {
-        let mut scene = SceneGraph::new();
+impl From<&BloomConfig> for crate::config::BloomConfigData {
+    fn from(config: &BloomConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            threshold: config.threshold,
+            intensity: config.intensity,
+            blur_passes: config.blur_passes,
+        }
+    }
+}
 
-        // Add default scene objects (legacy scene graph - for star, nebula, SSAO, Skybox)
-        let star_id = scene.add_object("Star (Sun)".to_string(), ObjectType::Sphere);
-        // Nebula is in ECS, but also in scene graph for transform editing via gizmo
-        let nebula_id = scene.add_object("Nebula".to_string(), ObjectType::Nebula);
-        scene.add_object("Skybox".to_string(), ObjectType::Skybox);
-        scene.add_object("SSAO".to_string(), ObjectType::SSAO);
-        scene.add_object("Game Manager".to_string(), ObjectType::GameManager);
+/// Curve used to map the HDR scene color into display range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TonemapOperator {
+    Reinhard,
+    Aces,
+    Uncharted2,
+    /// No curve: the exposed color is clamped to [0, 1] (exact values for color checks)
+    Clamp,
+}
 
-        // Nebula positioned behind origin for space battles in the foreground
-        let nebula_position = Vec3::new(0.0, 0.0, -10000.0);
-        if let Some(nebula) = scene.get_object_mut(nebula_id) {
-            nebula.transform.position = nebula_position;
-            nebula.transform.scale = Vec3::splat(20000.0); // 1000x scale
+impl TonemapOperator {
+    /// All operators, in the order shown in the UI
+    pub const ALL: [TonemapOperator; 4] = [Self::Reinhard, Self::Aces, Self::Uncharted2, Self::Clamp];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Reinhard => "Reinhard",
+            Self::Aces => "ACES",
+            Self::Uncharted2 => "Uncharted 2",
+            Self::Clamp => "None (Clamp)",
         }
+    }
 
-        // Star at center of nebula - procedural sphere with emissive material
-        if let Some(star) = scene.get_object_mut(star_id) {
-            star.transform.position = nebula_position; // Same as nebula center
-            star.transform.scale = Vec3::splat(50.0); // Large visible star
+    /// Operator index understood by the tonemap shader
+    pub fn shader_index(&self) -> u32 {
+        match self {
+            Self::Reinhard => 0,
+            Self::Aces => 1,
+            Self::Uncharted2 => 2,
+            Self::Clamp => 3,
         }
+    }
+}
 
-        // Initialize ECS world with nebula (1000x scale) and star
-        let mut ecs_world = crate::ecs::EcsWorld::new();
-        let (nebula_entity, star_entity) = crate::ecs::init::init_default_scene(&mut ecs_world.world);
+/// Tonemapping configuration (HDR scene -> swapchain)
+#[derive(Debug, Clone)]
+pub struct TonemapConfig {
+    pub operator: TonemapOperator,
+    /// Linear exposure multiplier applied before the curve
+    pub exposure: f32,
+    /// Treat picked/config colors as sRGB and linearize them before lighting (see
+    /// `core::color`). Off reproduces the old behavior of feeding them to shaders raw.
+    pub srgb_colors: bool,
+}
 
-        // Add Federation Cruiser at origin
-        use glam::{DVec3, DQuat};
-        let fed_cruiser_entity = crate::ecs::init::create_ship_entity(
-            &mut ecs_world.world,
-            "Federation Cruiser".to_string(),
-            DVec3::new(0.0, 0.0, 0.0), // At origin
-            DQuat::IDENTITY,
-        );
+impl Default for TonemapConfig {
+    fn default() -> Self {
+        Self {
+            operator: TonemapOperator::Aces,
+            exposure: 1.0,
+            srgb_colors: true,
+        }
+    }
+}
 
-        let mut game = Self {
-            time: 0.0,
-            camera: Camera::default(),
-            scene,
-            ecs_world,
-            nebula_entity: Some(nebula_entity),
-            star_entity: Some(star_entity),
-            fed_cruiser_entity: Some(fed_cruiser_entity),
-            hologram_ship_position: None,
-            dragging_hologram: false,
-            hovering_hologram: false,
-            hover_text: None,
-            gizmo_state: GizmoState::new(),
-            object_picker: ObjectPicker::new(),
-            ship_velocity: Vec3::ZERO,
-            ship_angular_velocity: Vec3::ZERO,
-            move_speed: 5.0,
-            rotation_speed: 2.0,
-            skybox_config: SkyboxConfig::default(),
-            nebula_config: NebulaConfig::default(),
-            ssao_config: SSAOConfig::default(),
-            focus_animation: CameraFocusAnimation::new(),
-            lock_camera_up: true, // Default to locked (world Y up)
-            scene_dirty: false,
-            config_dirty: false,
-            notifications: Vec::new(),
-            material: crate::material::MaterialProperties::default(),
-            material_library: crate::material_library::MaterialLibrary::default(),
-            current_material_name: "New Material".to_string(),
-            material_editor_open: false,
-            directional_light: crate::core::lighting::DirectionalLight::default(),
-            game_manager: GameManager::default(),
-            star_config: StarConfig::default(),
-            show_camera_cursor: false,
-            camera_cursor_position: DVec3::ZERO,
-        };
+impl TonemapConfig {
+    /// A picked (sRGB) color as shaders should receive it
+    pub fn shader_color(&self, color: Vec3) -> Vec3 {
+        if self.srgb_colors {
+            crate::core::color::srgb_to_linear_rgb(color)
+        } else {
+            color
+        }
+    }
+}
 
-        // Sync nebula transform from scene to ECS
-        game.sync_nebula_transform();
+// Tonemap config conversions
+impl From<crate::config::TonemapConfigData> for TonemapConfig {
+    fn from(data: crate::config::TonemapConfigData) -> Self {
+        Self {
+            operator: data.operator,
+            exposure: data.exposure.max(0.0),
+            srgb_colors: data.srgb_colors,
+        }
+    }
+}
 
-        game
+impl From<&TonemapConfig> for crate::config::TonemapConfigData {
+    fn from(config: &TonemapConfig) -> Self {
+        Self {
+            operator: config.operator,
+            exposure: config.exposure,
+            srgb_colors: config.srgb_colors,
+        }
     }
+}
 
-    /// Handle mouse hover for object picking
-    pub fn handle_mouse_hover(&mut self, mouse_x: f32, mouse_y: f32, viewport_width: f32, viewport_height: f32) {
-        // Reset hover state
-        self.hovering_hologram = false;
-        self.hover_text = None;
+/// Which edge anti-aliasing technique the renderer uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AntiAliasingMode {
+    /// No edge smoothing
+    None,
+    /// Fast approximate AA: one fullscreen post pass over the tonemapped image
+    Fxaa,
+    /// Multisampled main scene pass (costs fill rate and memory per sample)
+    Msaa,
+}
 
-        // In Play mode, check for hologram hover first
-        if self.game_manager.mode == crate::game_manager::GameMode::Play {
-            if let Some(hologram_pos) = self.hologram_ship_position {
-                // Use the proven Ray picking code
+impl AntiAliasingMode {
+    /// All modes, in the order shown in the UI
+    pub const ALL: [AntiAliasingMode; 3] = [Self::None, Self::Fxaa, Self::Msaa];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Fxaa => "FXAA",
+            Self::Msaa => "MSAA",
+        }
+    }
+}
+
+/// Edge anti-aliasing (MSAA or FXAA) and anisotropic filtering for material textures
+#[derive(Debug, Clone)]
+pub struct AntiAliasingConfig {
+    pub mode: AntiAliasingMode,
+    /// Requested MSAA sample count (one of `AntiAliasingConfig::SAMPLE_COUNTS`, 1 = off)
+    /// Only used in MSAA mode; the renderer clamps it to what the GPU supports
+    pub samples: u32,
+    /// FXAA quality in [0, 1]: higher catches fainter edges and searches longer along them
+    pub fxaa_quality: f32,
+    /// Apply FXAA to the left half of the view only, for comparing edges side by side
+    /// (not saved)
+    pub fxaa_split: bool,
+    /// Requested anisotropy level (one of `AntiAliasingConfig::ANISOTROPY_LEVELS`, 1 = off)
+    /// The renderer clamps it to the GPU's `maxSamplerAnisotropy`
+    pub anisotropy: u32,
+}
+
+impl AntiAliasingConfig {
+    /// Selectable MSAA sample counts
+    pub const SAMPLE_COUNTS: [u32; 4] = [1, 2, 4, 8];
+    /// Selectable anisotropic filtering levels
+    pub const ANISOTROPY_LEVELS: [u32; 5] = [1, 2, 4, 8, 16];
+
+    /// Sample count the main render pass should use (1 unless in MSAA mode)
+    pub fn msaa_samples(&self) -> u32 {
+        if self.mode == AntiAliasingMode::Msaa {
+            self.samples
+        } else {
+            1
+        }
+    }
+
+    pub fn fxaa_enabled(&self) -> bool {
+        self.mode == AntiAliasingMode::Fxaa
+    }
+}
+
+impl Default for AntiAliasingConfig {
+    fn default() -> Self {
+        Self {
+            mode: AntiAliasingMode::Msaa,
+            samples: 4,
+            fxaa_quality: 0.75,
+            fxaa_split: false,
+            anisotropy: 16,
+        }
+    }
+}
+
+// Anti-aliasing config conversions
+impl From<crate::config::AntiAliasingConfigData> for AntiAliasingConfig {
+    fn from(data: crate::config::AntiAliasingConfigData) -> Self {
+        // Fall back to the default level if the file holds an unsupported count
+        let samples = if Self::SAMPLE_COUNTS.contains(&data.samples) {
+            data.samples
+        } else {
+            Self::default().samples
+        };
+        let anisotropy = if Self::ANISOTROPY_LEVELS.contains(&data.anisotropy) {
+            data.anisotropy
+        } else {
+            Self::default().anisotropy
+        };
+
+        Self {
+            mode: data.mode,
+            samples,
+            fxaa_quality: data.fxaa_quality.clamp(0.0, 1.0),
+            fxaa_split: false,
+            anisotropy,
+        }
+    }
+}
+
+impl From<&AntiAliasingConfig> for crate::config::AntiAliasingConfigData {
+    fn from(config: &AntiAliasingConfig) -> Self {
+        Self {
+            mode: config.mode,
+            samples: config.samples,
+            fxaa_quality: config.fxaa_quality,
+            anisotropy: config.anisotropy,
+        }
+    }
+}
+
+/// How finished frames are handed to the display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PresentMode {
+    /// Vsync: waits for the display refresh, never tears (always supported)
+    Fifo,
+    /// Vsync without the wait: the newest frame replaces any queued one
+    Mailbox,
+    /// No vsync: presents immediately and may tear
+    Immediate,
+}
+
+impl PresentMode {
+    /// All present modes, in the order shown in the UI
+    pub const ALL: [PresentMode; 3] = [Self::Fifo, Self::Mailbox, Self::Immediate];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fifo => "Vsync (FIFO)",
+            Self::Mailbox => "Mailbox",
+            Self::Immediate => "Immediate (no vsync)",
+        }
+    }
+}
+
+/// Swapchain presentation and frame pacing
+#[derive(Debug, Clone)]
+pub struct GraphicsConfig {
+    /// Requested present mode; the renderer falls back to FIFO if the surface doesn't support it
+    pub present_mode: PresentMode,
+    /// Frame-limiter target in frames per second (0 = uncapped)
+    pub fps_cap: u32,
+    /// Draw opaque meshes depth-only first, so the shaded pass runs each pixel's
+    /// fragment shader once (saves time with heavy overdraw, costs a second vertex pass)
+    pub depth_prepass: bool,
+}
+
+impl GraphicsConfig {
+    /// Highest cap the slider offers
+    pub const MAX_FPS_CAP: u32 = 360;
+}
+
+impl Default for GraphicsConfig {
+    fn default() -> Self {
+        Self {
+            present_mode: PresentMode::Mailbox,
+            fps_cap: 120,
+            depth_prepass: false,
+        }
+    }
+}
+
+// Graphics config conversions
+impl From<crate::config::GraphicsConfigData> for GraphicsConfig {
+    fn from(data: crate::config::GraphicsConfigData) -> Self {
+        Self {
+            present_mode: data.present_mode,
+            fps_cap: data.fps_cap.min(Self::MAX_FPS_CAP),
+            depth_prepass: data.depth_prepass,
+        }
+    }
+}
+
+impl From<&GraphicsConfig> for crate::config::GraphicsConfigData {
+    fn from(config: &GraphicsConfig) -> Self {
+        Self {
+            present_mode: config.present_mode,
+            fps_cap: config.fps_cap,
+            depth_prepass: config.depth_prepass,
+        }
+    }
+}
+
+/// Wireframe overlay drawn on top of shaded meshes
+#[derive(Debug, Clone)]
+pub struct WireframeConfig {
+    /// Overlay every mesh, not just objects with their own wireframe flag
+    pub show_all: bool,
+    /// Line color
+    pub color: Vec3,
+    /// Draw every mesh object's local bounding box (debug picking and culling)
+    pub show_bounds: bool,
+}
+
+impl Default for WireframeConfig {
+    fn default() -> Self {
+        Self {
+            show_all: false,
+            color: Vec3::new(0.0, 1.0, 0.8),
+            show_bounds: false,
+        }
+    }
+}
+
+// Wireframe config conversions
+impl From<crate::config::WireframeConfigData> for WireframeConfig {
+    fn from(data: crate::config::WireframeConfigData) -> Self {
+        Self {
+            show_all: data.show_all,
+            color: data.color,
+            show_bounds: data.show_bounds,
+        }
+    }
+}
+
+impl From<&WireframeConfig> for crate::config::WireframeConfigData {
+    fn from(config: &WireframeConfig) -> Self {
+        Self {
+            show_all: config.show_all,
+            color: config.color,
+            show_bounds: config.show_bounds,
+        }
+    }
+}
+
+/// Editor reference grid on the Y=0 plane (hidden in play mode)
+#[derive(Debug, Clone)]
+pub struct GridConfig {
+    pub enabled: bool,
+    /// World units between minor lines
+    pub spacing: f32,
+    /// Minor cells per major line
+    pub major_every: u32,
+    pub color: Vec3,
+    /// Distance from the camera where the grid has faded out completely
+    pub fade_distance: f32,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            spacing: 1.0,
+            major_every: 10,
+            color: Vec3::new(0.5, 0.55, 0.6),
+            fade_distance: 200.0,
+        }
+    }
+}
+
+// Grid config conversions
+impl From<crate::config::GridConfigData> for GridConfig {
+    fn from(data: crate::config::GridConfigData) -> Self {
+        Self {
+            enabled: data.enabled,
+            spacing: data.spacing.max(0.001),
+            major_every: data.major_every.max(1),
+            color: data.color,
+            fade_distance: data.fade_distance.max(1.0),
+        }
+    }
+}
+
+impl From<&GridConfig> for crate::config::GridConfigData {
+    fn from(config: &GridConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            spacing: config.spacing,
+            major_every: config.major_every,
+            color: config.color,
+            fade_distance: config.fade_distance,
+        }
+    }
+}
+
+/// Colors and thickness of the editor's selection and hover outlines
+#[derive(Debug, Clone)]
+pub struct OutlineConfig {
+    pub selected_color: Vec3,
+    pub hovered_color: Vec3,
+    /// Thickness in screen pixels, so outlines look the same at any distance
+    pub width: f32,
+}
+
+impl OutlineConfig {
+    pub const MIN_WIDTH: f32 = 0.5;
+    pub const MAX_WIDTH: f32 = 16.0;
+}
+
+impl Default for OutlineConfig {
+    fn default() -> Self {
+        Self {
+            selected_color: Vec3::new(0.2, 1.0, 0.2),
+            hovered_color: Vec3::new(1.0, 1.0, 0.0),
+            width: 3.0,
+        }
+    }
+}
+
+// Outline config conversions
+impl From<crate::config::OutlineConfigData> for OutlineConfig {
+    fn from(data: crate::config::OutlineConfigData) -> Self {
+        Self {
+            selected_color: data.selected_color,
+            hovered_color: data.hovered_color,
+            width: data.width.clamp(Self::MIN_WIDTH, Self::MAX_WIDTH),
+        }
+    }
+}
+
+impl From<&OutlineConfig> for crate::config::OutlineConfigData {
+    fn from(config: &OutlineConfig) -> Self {
+        Self {
+            selected_color: config.selected_color,
+            hovered_color: config.hovered_color,
+            width: config.width,
+        }
+    }
+}
+
+/// How the Transform panel's Recompute Normals rebuilds a mesh's normals (`Mesh::recompute_normals`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalRecomputeOptions {
+    /// Average across edges up to `smoothing_angle`, instead of one normal per face
+    pub smooth: bool,
+    /// Largest angle between faces that still get a shared normal, in degrees
+    pub smoothing_angle: f32,
+}
+
+impl Default for NormalRecomputeOptions {
+    fn default() -> Self {
+        Self {
+            smooth: true,
+            smoothing_angle: 60.0,
+        }
+    }
+}
+
+/// Transform gizmo size on screen
+#[derive(Debug, Clone)]
+pub struct GizmoConfig {
+    /// Size multiplier (1.0 = the default look)
+    pub screen_size: f32,
+    /// Keep the arrows a fixed number of pixels long regardless of FOV and window size,
+    /// instead of a fixed share of the distance (or ortho size) to the pivot
+    pub constant_pixel_size: bool,
+}
+
+impl GizmoConfig {
+    pub const MIN_SCREEN_SIZE: f32 = 0.25;
+    pub const MAX_SCREEN_SIZE: f32 = 4.0;
+    /// Arrow length per unit of distance (perspective) or ortho size (orthographic)
+    const DISTANCE_FACTOR: f32 = 0.15;
+    /// Arrow length in pixels at `screen_size` 1.0 with `constant_pixel_size`
+    pub const REFERENCE_PIXELS: f32 = 100.0;
+    /// Longest the arrows may get as a share of the viewport height, so a pivot right next
+    /// to the camera (or a large size on a small window) can't fill the view
+    pub const MAX_VIEWPORT_FRACTION: f32 = 0.4;
+
+    /// World-space scale of the gizmo mesh drawn at `point` (shared by rendering and picking)
+    pub fn scale_at(&self, camera: &Camera, point: Vec3, viewport_height: f32) -> f32 {
+        let units_per_pixel = camera.world_units_per_pixel(point, viewport_height);
+        let scale = if self.constant_pixel_size {
+            Self::REFERENCE_PIXELS * self.screen_size * units_per_pixel
+        } else {
+            camera.screen_scale_at(point) * Self::DISTANCE_FACTOR * self.screen_size
+        };
+        scale.min(Self::MAX_VIEWPORT_FRACTION * viewport_height * units_per_pixel)
+    }
+}
+
+impl Default for GizmoConfig {
+    fn default() -> Self {
+        Self {
+            screen_size: 1.0,
+            constant_pixel_size: false,
+        }
+    }
+}
+
+// Gizmo config conversions
+impl From<crate::config::GizmoConfigData> for GizmoConfig {
+    fn from(data: crate::config::GizmoConfigData) -> Self {
+        Self {
+            screen_size: data.screen_size.clamp(Self::MIN_SCREEN_SIZE, Self::MAX_SCREEN_SIZE),
+            constant_pixel_size: data.constant_pixel_size,
+        }
+    }
+}
+
+impl From<&GizmoConfig> for crate::config::GizmoConfigData {
+    fn from(config: &GizmoConfig) -> Self {
+        Self {
+            screen_size: config.screen_size,
+            constant_pixel_size: config.constant_pixel_size,
+        }
+    }
+}
+
+/// GPU particle budget and simulation settings (emitters are scene objects)
+#[derive(Debug, Clone)]
+pub struct ParticleConfig {
+    /// Size of the particle ring (one of `ParticleConfig::BUDGETS`); once full, new particles replace the oldest
+    pub max_particles: u32,
+    /// Fraction of velocity particles lose per second
+    pub drag: f32,
+}
+
+impl ParticleConfig {
+    /// Selectable particle budgets (80 bytes of GPU memory per particle)
+    pub const BUDGETS: [u32; 4] = [4_096, 16_384, 65_536, 262_144];
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        Self {
+            max_particles: 16_384,
+            drag: 0.5,
+        }
+    }
+}
+
+// Particle config conversions
+impl From<crate::config::ParticleConfigData> for ParticleConfig {
+    fn from(data: crate::config::ParticleConfigData) -> Self {
+        // Fall back to the default budget if the file holds an unsupported size
+        let max_particles = if Self::BUDGETS.contains(&data.max_particles) {
+            data.max_particles
+        } else {
+            Self::default().max_particles
+        };
+
+        Self {
+            max_particles,
+            drag: data.drag.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl From<&ParticleConfig> for crate::config::ParticleConfigData {
+    fn from(config: &ParticleConfig) -> Self {
+        Self {
+            max_particles: config.max_particles,
+            drag: config.drag,
+        }
+    }
+}
+
+/// Distance-based level of detail for imported meshes (simplified levels are generated at load time)
+#[derive(Debug, Clone)]
+pub struct LodConfig {
+    pub enabled: bool,
+    /// Camera distance at which LOD 1 and LOD 2 take over (ascending)
+    pub distances: [f32; 2],
+}
+
+impl LodConfig {
+    /// Triangle count of each generated level as a fraction of the base mesh
+    pub const RATIOS: [f32; 2] = [0.5, 0.2];
+    /// Meshes lighter than this are cheap enough to always draw at full detail
+    pub const MIN_TRIANGLES: usize = 2_000;
+
+    /// Level to draw at `distance` from the camera (0 = base mesh); the renderer falls
+    /// back to a finer level when a mesh didn't get this one
+    pub fn level_for_distance(&self, distance: f32) -> usize {
+        if !self.enabled {
+            return 0;
+        }
+        self.distances.iter().filter(|&&threshold| distance >= threshold).count()
+    }
+}
+
+impl Default for LodConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            distances: [150.0, 500.0],
+        }
+    }
+}
+
+// LOD config conversions
+impl From<crate::config::LodConfigData> for LodConfig {
+    fn from(data: crate::config::LodConfigData) -> Self {
+        let near = data.distances[0].max(0.0);
+        Self {
+            enabled: data.enabled,
+            distances: [near, data.distances[1].max(near)],
+        }
+    }
+}
+
+impl From<&LodConfig> for crate::config::LodConfigData {
+    fn from(config: &LodConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            distances: config.distances,
+        }
+    }
+}
+
+/// Soft limits on scene content, flagged in the Scene Statistics panel (0 = no limit)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneBudgetConfig {
+    pub max_objects: u32,
+    /// Triangles across all shown meshes, before culling
+    pub max_triangles: u32,
+    /// GPU vertex and index buffers of imported meshes (LOD levels included)
+    pub max_mesh_memory_mb: u32,
+    pub max_point_lights: u32,
+}
+
+impl SceneBudgetConfig {
+    /// Whether `value` is over `budget` (a budget of 0 never is)
+    pub fn exceeds(value: u64, budget: u32) -> bool {
+        budget > 0 && value > budget as u64
+    }
+}
+
+impl Default for SceneBudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_objects: 1_000,
+            max_triangles: 2_000_000,
+            max_mesh_memory_mb: 512,
+            max_point_lights: 64,
+        }
+    }
+}
+
+// Scene budget config conversions
+impl From<crate::config::SceneBudgetConfigData> for SceneBudgetConfig {
+    fn from(data: crate::config::SceneBudgetConfigData) -> Self {
+        Self {
+            max_objects: data.max_objects,
+            max_triangles: data.max_triangles,
+            max_mesh_memory_mb: data.max_mesh_memory_mb,
+            // Lights past the shader's capacity aren't drawn, so a higher budget means nothing
+            max_point_lights: data.max_point_lights.min(crate::core::lighting::MAX_POINT_LIGHTS as u32),
+        }
+    }
+}
+
+impl From<&SceneBudgetConfig> for crate::config::SceneBudgetConfigData {
+    fn from(config: &SceneBudgetConfig) -> Self {
+        Self {
+            max_objects: config.max_objects,
+            max_triangles: config.max_triangles,
+            max_mesh_memory_mb: config.max_mesh_memory_mb,
+            max_point_lights: config.max_point_lights,
+        }
+    }
+}
+
+/// Screen-space reflections for metallic surfaces (mesh.frag, src/core/ssr.rs)
+#[derive(Debug, Clone)]
+pub struct SsrConfig {
+    pub enabled: bool,
+    /// Ray-march steps per reflected ray (cost scales with this)
+    pub max_steps: u32,
+    /// How far (world units) a ray may pass behind the depth buffer and still count as a hit
+    pub thickness: f32,
+}
+
+impl SsrConfig {
+    pub const MIN_STEPS: u32 = 8;
+    pub const MAX_STEPS: u32 = 128;
+    pub const MIN_THICKNESS: f32 = 0.01;
+    pub const MAX_THICKNESS: f32 = 10.0;
+}
+
+impl Default for SsrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_steps: 32,
+            thickness: 0.5,
+        }
+    }
+}
+
+// SSR config conversions
+impl From<crate::config::SsrConfigData> for SsrConfig {
+    fn from(data: crate::config::SsrConfigData) -> Self {
+        Self {
+            enabled: data.enabled,
+            max_steps: data.max_steps.clamp(Self::MIN_STEPS, Self::MAX_STEPS),
+            thickness: data.thickness.clamp(Self::MIN_THICKNESS, Self::MAX_THICKNESS),
+        }
+    }
+}
+
+impl From<&SsrConfig> for crate::config::SsrConfigData {
+    fn from(config: &SsrConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            max_steps: config.max_steps,
+            thickness: config.thickness,
+        }
+    }
+}
+
+/// Periodic background save of unsaved edits to `autosave::AUTOSAVE_SCENE_PATH`
+#[derive(Debug, Clone)]
+pub struct AutosaveConfig {
+    pub enabled: bool,
+    /// Seconds between auto-saves while there are unsaved changes
+    pub interval_secs: u32,
+}
+
+impl AutosaveConfig {
+    pub const MIN_INTERVAL_SECS: u32 = 10;
+    pub const MAX_INTERVAL_SECS: u32 = 3600;
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 120,
+        }
+    }
+}
+
+// Autosave config conversions
+impl From<crate::config::AutosaveConfigData> for AutosaveConfig {
+    fn from(data: crate::config::AutosaveConfigData) -> Self {
+        Self {
+            enabled: data.enabled,
+            interval_secs: data.interval_secs.clamp(Self::MIN_INTERVAL_SECS, Self::MAX_INTERVAL_SECS),
+        }
+    }
+}
+
+impl From<&AutosaveConfig> for crate::config::AutosaveConfigData {
+    fn from(config: &AutosaveConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            interval_secs: config.interval_secs,
+        }
+    }
+}
+
+/// How fog density grows with distance from the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FogMode {
+    /// 1 - exp(-density * distance)
+    Exponential,
+    /// Ramps from no fog at `start` to full fog at `end`
+    Linear,
+}
+
+impl FogMode {
+    /// All modes, in the order shown in the UI
+    pub const ALL: [FogMode; 2] = [Self::Exponential, Self::Linear];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Exponential => "Exponential",
+            Self::Linear => "Linear",
+        }
+    }
+
+    /// Mode index understood by mesh.frag and star.frag (0 = fog off)
+    pub fn shader_index(&self) -> u32 {
+        match self {
+            Self::Exponential => 1,
+            Self::Linear => 2,
+        }
+    }
+}
+
+/// Mesh shader debug visualization (replaces the shaded color in mesh.frag)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugView {
+    /// Normal shading
+    #[default]
+    Off,
+    /// World-space normals (after normal mapping) as RGB
+    Normals,
+    /// Linearized view depth as grayscale (log scale, near = black)
+    Depth,
+    /// Texture coordinates as RG
+    Uvs,
+    /// Additive heatmap of how many mesh fragments cover each pixel
+    Overdraw,
+    /// Flat colors by mesh LOD level (white = full detail, yellow = LOD 1, red = LOD 2)
+    Lod,
+}
+
+impl DebugView {
+    /// All views, in the order shown in the UI
+    pub const ALL: [DebugView; 6] = [Self::Off, Self::Normals, Self::Depth, Self::Uvs, Self::Overdraw, Self::Lod];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Normals => "Normals",
+            Self::Depth => "Depth",
+            Self::Uvs => "UVs",
+            Self::Overdraw => "Overdraw",
+            Self::Lod => "LOD Levels",
+        }
+    }
+
+    /// View index understood by mesh.frag (0 = off)
+    pub fn shader_index(&self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Normals => 1,
+            Self::Depth => 2,
+            Self::Uvs => 3,
+            Self::Overdraw => 4,
+            Self::Lod => 5,
+        }
+    }
+}
+
+/// Distance fog blended over meshes and stars (not the skybox, nebula or UI)
+#[derive(Debug, Clone)]
+pub struct FogConfig {
+    pub enabled: bool,
+    pub mode: FogMode,
+    pub color: Vec3,
+    /// Exponential mode falloff per world unit
+    pub density: f32,
+    /// Linear mode distance where fog starts
+    pub start: f32,
+    /// Linear mode distance where fog is fully opaque
+    pub end: f32,
+}
+
+impl FogConfig {
+    /// Mode index for the shaders (0 when fog is disabled)
+    pub fn shader_mode(&self) -> u32 {
+        if self.enabled { self.mode.shader_index() } else { 0 }
+    }
+}
+
+impl Default for FogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: FogMode::Exponential,
+            color: Vec3::new(0.02, 0.03, 0.06),
+            density: 0.01,
+            start: 50.0,
+            end: 300.0,
+        }
+    }
+}
+
+// Fog config conversions
+impl From<crate::config::FogConfigData> for FogConfig {
+    fn from(data: crate::config::FogConfigData) -> Self {
+        Self {
+            enabled: data.enabled,
+            mode: data.mode,
+            color: data.color,
+            density: data.density.max(0.0),
+            start: data.start,
+            end: data.end.max(data.start),
+        }
+    }
+}
+
+impl From<&FogConfig> for crate::config::FogConfigData {
+    fn from(config: &FogConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            mode: config.mode,
+            color: config.color,
+            density: config.density,
+            start: config.start,
+            end: config.end,
+        }
+    }
+}
+
+/// What is drawn behind the scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BackgroundMode {
+    /// Clear to a single color (no extra pass)
+    SolidColor,
+    /// Vertical gradient from the top to the bottom of the screen
+    Gradient,
+    /// The skybox (the solid color shows if the skybox object is hidden)
+    #[default]
+    Skybox,
+}
+
+impl BackgroundMode {
+    /// All modes, in the order shown in the UI
+    pub const ALL: [BackgroundMode; 3] = [Self::SolidColor, Self::Gradient, Self::Skybox];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SolidColor => "Solid Color",
+            Self::Gradient => "Gradient",
+            Self::Skybox => "Skybox",
+        }
+    }
+}
+
+/// Backdrop of the main pass: its clear color, a gradient, or the skybox
+#[derive(Debug, Clone)]
+pub struct BackgroundConfig {
+    pub mode: BackgroundMode,
+    /// Clear color (sRGB, like every picked color)
+    pub color: Vec3,
+    pub top_color: Vec3,
+    pub bottom_color: Vec3,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            mode: BackgroundMode::Skybox,
+            // The old hardcoded clear color (0.01, 0.01, 0.02 linear)
+            color: Vec3::new(0.1, 0.1, 0.15),
+            top_color: Vec3::new(0.18, 0.22, 0.32),
+            bottom_color: Vec3::new(0.04, 0.04, 0.06),
+        }
+    }
+}
+
+// Background config conversions
+impl From<crate::config::BackgroundConfigData> for BackgroundConfig {
+    fn from(data: crate::config::BackgroundConfigData) -> Self {
+        Self {
+            mode: data.mode,
+            color: data.color,
+            top_color: data.top_color,
+            bottom_color: data.bottom_color,
+        }
+    }
+}
+
+impl From<&BackgroundConfig> for crate::config::BackgroundConfigData {
+    fn from(config: &BackgroundConfig) -> Self {
+        Self {
+            mode: config.mode,
+            color: config.color,
+            top_color: config.top_color,
+            bottom_color: config.bottom_color,
+        }
+    }
+}
+
+impl Default for SkyboxConfig {
+    fn default() -> Self {
+        Self {
+            star_density: 2.0,
+            star_brightness: 3.0,
+            nebula_primary_color: Vec3::new(0.1, 0.2, 0.4),
+            nebula_secondary_color: Vec3::new(0.6, 0.3, 0.8),
+            nebula_intensity: 1.0,
+            background_brightness: 0.00,
+            mode: SkyboxMode::Procedural,
+            cubemap_path: String::new(),
+        }
+    }
+}
+
+/// Seconds the camera takes to frame an object
+const FRAME_DURATION: f32 = 0.4;
+
+/// Extra room around framed bounds (1.0 = the bounding sphere touches the view edges)
+const FRAME_MARGIN: f32 = 1.1;
+
+/// How new objects are placed (Object Actions in the hierarchy)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlacementSettings {
+    /// Put new objects on the surface last under the cursor instead of in front of the camera
+    pub snap_to_surface: bool,
+    /// Also tilt them so their up axis follows the surface normal
+    pub align_to_normal: bool,
+}
+
+/// Camera pose to return to after looking through a directional light
+struct LightView {
+    light: ObjectId,
+    position: Vec3,
+    rotation: Quat,
+    fov: f32,
+    /// Camera rotation last copied to the light (the light only follows actual changes)
+    applied_rotation: Quat,
+}
+
+/// Camera focus animation state
+struct CameraFocusAnimation {
+    active: bool,
+    start_position: Vec3,
+    start_pitch: f32,
+    start_yaw: f32,
+    start_roll: f32,
+    target_position: Vec3,
+    target_pitch: f32,
+    target_yaw: f32,
+    target_roll: f32,
+    progress: f32, // 0.0 to 1.0
+    duration: f32, // Total animation duration in seconds
+}
+
+impl CameraFocusAnimation {
+    fn new() -> Self {
+        Self {
+            active: false,
+            start_position: Vec3::ZERO,
+            start_pitch: 0.0,
+            start_yaw: 0.0,
+            start_roll: 0.0,
+            target_position: Vec3::ZERO,
+            target_pitch: 0.0,
+            target_yaw: 0.0,
+            target_roll: 0.0,
+            progress: 0.0,
+            duration: 0.5, // Half second animation
+        }
+    }
+}
+
+/// Notification message for UI
+#[derive(Clone)]
+pub struct Notification {
+    pub message: String,
+    pub time_remaining: f32, // seconds
+}
+
+impl Notification {
+    pub fn new(message: String, duration: f32) -> Self {
+        Self {
+            message,
+            time_remaining: duration,
+        }
+    }
+}
+
+/// SSAO configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SSAOConfig {
+    pub enabled: bool,
+    pub radius: f32,
+    pub bias: f32,
+    pub power: f32,
+    pub kernel_size: u32,
+    /// How strongly AO darkens ambient light (0 = no AO, 1 = full AO)
+    pub ao_intensity: f32,
+    /// Debug view: meshes output the blurred AO term instead of shading (not saved)
+    pub show_ao_only: bool,
+}
+
+impl Default for SSAOConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 1.0,
+            bias: 0.1,
+            power: 2.0,
+            kernel_size: 64,
+            ao_intensity: 1.0,
+            show_ao_only: false,
+        }
+    }
+}
+
+/// Game state and logic
+pub struct Game {
+    /// Time accumulator for animations
+    time: f32,
+    /// Camera
+    pub camera: Camera,
+    /// Scene graph with all objects (legacy - being migrated to ECS)
+    pub scene: SceneGraph,
+    /// ECS World for space entities (nebula, star, ships, asteroids)
+    pub ecs_world: crate::ecs::EcsWorld,
+    /// Rapier simulation for entities with a PhysicsBody (stepped in play mode)
+    pub physics_world: crate::ecs::physics::PhysicsWorld,
+    /// Nebula entity ID in ECS
+    pub nebula_entity: Option<hecs::Entity>,
+    /// Star entity ID in ECS
+    pub star_entity: Option<hecs::Entity>,
+    /// Federation Cruiser entity ID in ECS
+    pub fed_cruiser_entity: Option<hecs::Entity>,
+    /// Hologram ship for turn-based movement planning
+    pub hologram_ship_position: Option<DVec3>,
+    /// Is the player currently dragging the hologram ship?
+    pub dragging_hologram: bool,
+    /// Is the mouse hovering over the hologram ship?
+    pub hovering_hologram: bool,
+    /// Moves blocked by another unit stop at the last safe point instead of being refused
+    pub clamp_blocked_moves: bool,
+    /// Recorded movement actions (replay / async multiplayer)
+    pub movement_events: crate::movement::MovementEventRecorder,
+    /// Turn replay in progress; it moves ship scene objects and puts them back when it stops
+    pub replay: Option<crate::movement::ReplayPlayer>,
+    /// Scene objects the replay drives: (ship ID, object, transform to restore)
+    replay_objects: Vec<(u64, ObjectId, Transform)>,
+    /// Hover text to display
+    pub hover_text: Option<String>,
+    /// Gizmo state for 3D manipulation
+    pub gizmo_state: GizmoState,
+    /// Object picker for mouse selection
+    pub object_picker: ObjectPicker,
+    /// Triangle data for precise ray casts into the scene
+    pub pick_meshes: crate::raycast::PickMeshes,
+    /// Surface under the cursor (edit mode, updated on hover)
+    pub hover_hit: Option<crate::raycast::RayHit>,
+    /// Surface snapping for newly added objects
+    pub placement: PlacementSettings,
+    /// Counts and spacing for the hierarchy's Duplicate Array dialog
+    pub duplicate_array: crate::scene::DuplicateArray,
+    /// Camera orientation widget (edit mode only)
+    pub view_cube: crate::view_cube::ViewCube,
+    /// Distance measurement tool (edit mode only)
+    pub ruler: crate::ruler::Ruler,
+    /// Transforms of every selected object when the current gizmo drag started
+    group_drag_start: Vec<(ObjectId, Transform)>,
+    /// Spaceship velocity (for physics simulation)
+    ship_velocity: Vec3,
+    /// Spaceship angular velocity (for physics simulation)
+    ship_angular_velocity: Vec3,
+    /// Movement speed
+    move_speed: f32,
+    /// Rotation speed
+    rotation_speed: f32,
+    /// Skybox configuration
+    pub skybox_config: SkyboxConfig,
+    /// Nebula configuration
+    pub nebula_config: NebulaConfig,
+    /// SSAO configuration
+    pub ssao_config: SSAOConfig,
+    /// Shadow map configuration for the directional light
+    pub shadow_config: ShadowConfig,
+    /// Bloom post-process configuration
+    pub bloom_config: BloomConfig,
+    /// HDR tonemapping configuration
+    pub tonemap_config: TonemapConfig,
+    /// MSAA configuration for the main scene pass
+    pub anti_aliasing_config: AntiAliasingConfig,
+    pub graphics_config: GraphicsConfig,
+    /// Wireframe overlay configuration
+    pub wireframe_config: WireframeConfig,
+    /// Distance fog configuration
+    pub fog_config: FogConfig,
+    pub background_config: BackgroundConfig,
+    /// Editor ground grid configuration
+    pub grid_config: GridConfig,
+    /// GPU particle budget and drag
+    pub particle_config: ParticleConfig,
+    /// Mesh LOD switch distances
+    pub lod_config: LodConfig,
+    /// Soft limits shown in the Scene Statistics panel
+    pub scene_budget_config: SceneBudgetConfig,
+    /// Screen-space reflection settings
+    pub ssr_config: SsrConfig,
+    /// Selection/hover outline colors and width
+    pub outline_config: OutlineConfig,
+    /// Transform gizmo size
+    pub gizmo_config: GizmoConfig,
+    /// Keyboard shortcuts for editor and camera actions
+    pub keymap: crate::keymap::Keymap,
+    /// Action waiting for its next key press in the Keymap panel
+    pub keymap_capture: Option<crate::keymap::Action>,
+    /// Show the keyboard shortcuts overlay (F1)
+    pub show_keymap_help: bool,
+    /// Show the Keymap panel for remapping shortcuts
+    pub show_keymap_editor: bool,
+    /// Command palette (Ctrl+P) visibility and search
+    pub command_palette: crate::commands::CommandPalette,
+    /// Keymap actions run from the command palette, handled by the engine like key presses
+    pub queued_actions: Vec<crate::keymap::Action>,
+    /// Camera focus animation state
+    focus_animation: CameraFocusAnimation,
+    /// Set while the camera looks through a directional light (its rotation follows the camera)
+    light_view: Option<LightView>,
+    /// Lock camera up vector to world Y axis
+    pub lock_camera_up: bool,
+    /// Scene dirty flag (needs save)
+    pub scene_dirty: bool,
+    /// Config dirty flag (needs save)
+    pub config_dirty: bool,
+    /// Engine config file of the loaded scene (panel saves/loads go here)
+    pub config_path: String,
+    pub autosave_config: AutosaveConfig,
+    /// Last explicit save, auto-save or load (auto-save waits an interval after it)
+    pub last_save_time: std::time::Instant,
+    /// Time of an auto-save newer than the saved scene, found at startup (shows the restore dialog)
+    pub autosave_recovery: Option<std::time::SystemTime>,
+    /// Active notifications
+    pub notifications: Vec<Notification>,
+    /// Log window for shader reload results and other errors
+    pub console: crate::console::Console,
+    /// Material properties for mesh rendering
+    pub material: crate::material::MaterialProperties,
+    /// Material library
+    pub material_library: crate::material_library::MaterialLibrary,
+    /// Current material name being edited
+    pub current_material_name: String,
+    /// Material editor visibility
+    pub material_editor_open: bool,
+    /// Material preview orbit angles in radians (dragging the preview changes them)
+    pub material_preview_yaw: f32,
+    pub material_preview_pitch: f32,
+    /// Scene Hierarchy search text (case-insensitive substring on object names, empty shows all)
+    pub hierarchy_filter: String,
+    /// Tag being typed in the Transform panel's tag editor
+    pub tag_input: String,
+    /// Prefab name typed in the prefab browser (defaults to the selected object's name)
+    pub prefab_name: String,
+    /// Prefabs found in `config/prefabs/` (refreshed on save and from the browser)
+    pub prefab_names: Vec<String>,
+    /// Layer name typed in the Layers panel
+    pub new_layer_name: String,
+    /// Object subtree copied with Ctrl+C, pasted with Ctrl+V
+    pub clipboard: Option<crate::prefab::PrefabData>,
+    /// Material copied with Ctrl+Shift+C (the inner None is the default material)
+    pub material_clipboard: Option<Option<String>>,
+    /// Camera flythroughs saved with the scene
+    pub camera_tracks: Vec<crate::core::CameraTrack>,
+    /// Track shown in the Camera Tracks panel
+    pub selected_camera_track: usize,
+    /// Active track playback (manual camera input is ignored while set)
+    pub camera_track_playhead: Option<crate::core::CameraTrackPlayhead>,
+    /// Directional light settings
+    pub directional_light: crate::core::lighting::DirectionalLight,
+    /// Game Manager - play/pause state and scenario parameters
+    pub game_manager: GameManager,
+    /// Star configuration for shader parameters
+    pub star_config: StarConfig,
+    /// Show camera center cursor (appears when using WASD free camera)
+    pub show_camera_cursor: bool,
+    /// Camera cursor position (where camera is focused)
+    pub camera_cursor_position: DVec3,
+    /// Object the camera orbits and tracks until another is picked or Escape is pressed
+    orbit_follow: Option<ObjectId>,
+    /// Render pass enable toggles (pass name, enabled) - synced with the renderer's registry
+    pub render_pass_states: Vec<(String, bool)>,
+    /// Mesh pass frustum culling stats from the last rendered frame
+    pub mesh_draw_stats: crate::core::DrawStats,
+    /// Geometry of the selected object's mesh, reported by the renderer (None if it has none)
+    pub selected_mesh_stats: Option<crate::mesh::MeshStats>,
+    /// Options for Recompute Normals in the Transform panel
+    pub normal_recompute_options: NormalRecomputeOptions,
+    /// Custom mesh (by path) whose normals the renderer recomputes and re-uploads next frame.
+    /// Only the loaded copy changes, the file on disk is left alone
+    pub pending_normal_recompute: Option<(String, NormalRecomputeOptions)>,
+    /// Highest anisotropic filtering level the GPU supports (1 = unsupported), reported by the renderer
+    pub max_anisotropy: u32,
+    /// Show the frame-time/draw stats overlay (F3)
+    pub show_stats_overlay: bool,
+    /// Show the tactical minimap window (N)
+    pub show_minimap: bool,
+    /// Shader debug visualization for meshes (never saved, always Off at startup)
+    pub debug_view: DebugView,
+}
+
+impl Game {
+    pub fn new() -> Self {
+        let mut scene = SceneGraph::new();
+
+        // Add default scene objects (legacy scene graph - for star, nebula, SSAO, Skybox)
+        let star_id = scene.add_object("Star (Sun)".to_string(), ObjectType::Sphere);
+        // Nebula is in ECS, but also in scene graph for transform editing via gizmo
+        let nebula_id = scene.add_object("Nebula".to_string(), ObjectType::Nebula);
+        scene.add_object("Skybox".to_string(), ObjectType::Skybox);
+        scene.add_object("SSAO".to_string(), ObjectType::SSAO);
+        scene.add_object("Game Manager".to_string(), ObjectType::GameManager);
+
+        // Nebula positioned behind origin for space battles in the foreground
+        let nebula_position = Vec3::new(0.0, 0.0, -10000.0);
+        if let Some(nebula) = scene.get_object_mut(nebula_id) {
+            nebula.transform.position = nebula_position;
+            nebula.transform.scale = Vec3::splat(20000.0); // 1000x scale
+        }
+
+        // Star at center of nebula - procedural sphere with emissive material
+        if let Some(star) = scene.get_object_mut(star_id) {
+            star.transform.position = nebula_position; // Same as nebula center
+            star.transform.scale = Vec3::splat(50.0); // Large visible star
+        }
+
+        // Initialize ECS world with nebula (1000x scale) and star
+        let mut ecs_world = crate::ecs::EcsWorld::new();
+        let (nebula_entity, star_entity) = crate::ecs::init::init_default_scene(&mut ecs_world.world);
+
+        // Add Federation Cruiser at origin, commanded by the player
+        use glam::{DVec3, DQuat};
+        let game_manager = GameManager::default();
+        let fed_cruiser_entity = crate::ecs::init::create_ship_entity(
+            &mut ecs_world.world,
+            "Federation Cruiser".to_string(),
+            game_manager.player_faction.clone(),
+            DVec3::new(0.0, 0.0, 0.0), // At origin
+            DQuat::IDENTITY,
+        );
+
+        let mut game = Self {
+            time: 0.0,
+            camera: Camera::default(),
+            scene,
+            ecs_world,
+            physics_world: crate::ecs::physics::PhysicsWorld::new(),
+            nebula_entity: Some(nebula_entity),
+            star_entity: Some(star_entity),
+            fed_cruiser_entity: Some(fed_cruiser_entity),
+            hologram_ship_position: None,
+            dragging_hologram: false,
+            hovering_hologram: false,
+            clamp_blocked_moves: false,
+            movement_events: crate::movement::MovementEventRecorder::new(0),
+            replay: None,
+            replay_objects: Vec::new(),
+            hover_text: None,
+            gizmo_state: GizmoState::new(),
+            object_picker: ObjectPicker::new(),
+            pick_meshes: crate::raycast::PickMeshes::new(),
+            hover_hit: None,
+            placement: PlacementSettings::default(),
+            duplicate_array: crate::scene::DuplicateArray::default(),
+            view_cube: crate::view_cube::ViewCube::new(),
+            ruler: crate::ruler::Ruler::new(),
+            group_drag_start: Vec::new(),
+            ship_velocity: Vec3::ZERO,
+            ship_angular_velocity: Vec3::ZERO,
+            move_speed: 5.0,
+            rotation_speed: 2.0,
+            skybox_config: SkyboxConfig::default(),
+            nebula_config: NebulaConfig::default(),
+            ssao_config: SSAOConfig::default(),
+            shadow_config: ShadowConfig::default(),
+            bloom_config: BloomConfig::default(),
+            tonemap_config: TonemapConfig::default(),
+            anti_aliasing_config: AntiAliasingConfig::default(),
+            graphics_config: GraphicsConfig::default(),
+            wireframe_config: WireframeConfig::default(),
+            fog_config: FogConfig::default(),
+            background_config: BackgroundConfig::default(),
+            grid_config: GridConfig::default(),
+            particle_config: ParticleConfig::default(),
+            lod_config: LodConfig::default(),
+            scene_budget_config: SceneBudgetConfig::default(),
+            ssr_config: SsrConfig::default(),
+            outline_config: OutlineConfig::default(),
+            gizmo_config: GizmoConfig::default(),
+            keymap: crate::keymap::Keymap::default(),
+            keymap_capture: None,
+            show_keymap_help: false,
+            show_keymap_editor: false,
+            command_palette: crate::commands::CommandPalette::default(),
+            queued_actions: Vec::new(),
+            focus_animation: CameraFocusAnimation::new(),
+            light_view: None,
+            lock_camera_up: true, // Default to locked (world Y up)
+            scene_dirty: false,
+            config_dirty: false,
+            config_path: crate::scene::LEGACY_CONFIG_PATH.to_string(),
+            autosave_config: AutosaveConfig::default(),
+            last_save_time: std::time::Instant::now(),
+            autosave_recovery: None,
+            notifications: Vec::new(),
+            console: crate::console::Console::new(),
+            material: crate::material::MaterialProperties::default(),
+            material_library: crate::material_library::MaterialLibrary::default(),
+            current_material_name: "New Material".to_string(),
+            material_editor_open: false,
+            material_preview_yaw: 0.0,
+            material_preview_pitch: 0.3,
+            hierarchy_filter: String::new(),
+            tag_input: String::new(),
+            prefab_name: String::new(),
+            prefab_names: crate::prefab::PrefabData::list(),
+            new_layer_name: String::new(),
+            clipboard: None,
+            material_clipboard: None,
+            camera_tracks: Vec::new(),
+            selected_camera_track: 0,
+            camera_track_playhead: None,
+            directional_light: crate::core::lighting::DirectionalLight {
+                // Fallback direction when the scene has no light object
+                direction: Vec3::new(-0.3, -1.0, -0.3).normalize(),
+                ..Default::default()
+            },
+            game_manager,
+            star_config: StarConfig::default(),
+            show_camera_cursor: false,
+            camera_cursor_position: DVec3::ZERO,
+            orbit_follow: None,
+            render_pass_states: Vec::new(),
+            mesh_draw_stats: crate::core::DrawStats::default(),
+            selected_mesh_stats: None,
+            normal_recompute_options: NormalRecomputeOptions::default(),
+            pending_normal_recompute: None,
+            max_anisotropy: 1,
+            show_stats_overlay: false,
+            show_minimap: false,
+            debug_view: DebugView::Off,
+        };
+
+        // Sync nebula transform from scene to ECS
+        game.sync_nebula_transform();
+
+        game
+    }
+
+    /// Handle mouse hover for object picking
+    pub fn handle_mouse_hover(&mut self, mouse_x: f32, mouse_y: f32, viewport_width: f32, viewport_height: f32) {
+        // Reset hover state
+        self.hovering_hologram = false;
+        self.hover_text = None;
+        self.hover_hit = None;
+
+        // In Play mode, check for hologram hover first
+        if self.game_manager.mode == crate::game_manager::GameMode::Play {
+            if let Some(hologram_pos) = self.hologram_ship_position {
+                // Use the proven Ray picking code
                 let view = self.camera.view_matrix();
                 let proj = self.camera.projection_matrix(viewport_width / viewport_height);
                 let ray = crate::gizmo::Ray::from_screen(mouse_x, mouse_y, viewport_width, viewport_height, view, proj);
 
-                // Check if hovering over hologram using proven sphere intersection
-                if let Some(fed_entity) = self.fed_cruiser_entity {
-                    if let Ok(mut query) = self.ecs_world.world.query_one::<&crate::ecs::components::Scale>(fed_entity) {
-                        if let Some(scale_comp) = query.get() {
-                            // Use same picking radius as object picker: max scale * 1.5
-                            let scale = glam::Vec3::new(scale_comp.0.x as f32, scale_comp.0.y as f32, scale_comp.0.z as f32);
-                            let radius = scale.x.max(scale.y).max(scale.z) * 1.5;
+                // Check if hovering over hologram using proven sphere intersection
+                if let Some(fed_entity) = self.fed_cruiser_entity {
+                    if let Ok(mut query) = self.ecs_world.world.query_one::<&crate::ecs::components::Scale>(fed_entity) {
+                        if let Some(scale_comp) = query.get() {
+                            // Use same picking radius as object picker: max scale * 1.5
+                            let scale = glam::Vec3::new(scale_comp.0.x as f32, scale_comp.0.y as f32, scale_comp.0.z as f32);
+                            let radius = scale.x.max(scale.y).max(scale.z) * 1.5;
+
+                            if ray.intersects_sphere(hologram_pos.as_vec3(), radius).is_some() {
+                                self.hovering_hologram = true;
+                                self.hover_text = Some("Ship Movement Estimator".to_string());
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // The view cube sits on top of the scene, so it wins the hover
+        self.view_cube.hovered_face = None;
+        if self.is_view_cube_visible() {
+            let rotation = self.camera.rotation();
+            self.view_cube.hovered_face = crate::view_cube::ViewCube::pick_face(mouse_x, mouse_y, viewport_width, viewport_height, rotation);
+            if self.view_cube.hovered_face.is_some() {
+                self.object_picker.hovered_object = None;
+                return;
+            }
+        }
+
+        // The ruler previews its second point under the cursor instead of hovering objects
+        if self.is_ruler_active() {
+            self.ruler.hover_point = crate::ruler::Ruler::pick_point(mouse_x, mouse_y, viewport_width, viewport_height, &self.scene, &self.camera);
+            self.object_picker.hovered_object = None;
+            return;
+        }
+
+        // Check gizmo hover if enabled and object selected (edit mode)
+        if let Some(pivot) = self.gizmo_pivot() {
+            self.gizmo_state.pick_axis(
+                mouse_x,
+                mouse_y,
+                viewport_width,
+                viewport_height,
+                pivot.position,
+                pivot.rotation,
+                self.gizmo_config.scale_at(&self.camera, pivot.position, viewport_height),
+                &self.camera,
+            );
+        } else {
+            // Otherwise check object hover
+            self.object_picker.pick_object(
+                mouse_x,
+                mouse_y,
+                viewport_width,
+                viewport_height,
+                &self.scene,
+                &self.camera,
+            );
+        }
+
+        // Exact surface under the cursor for the hover readout
+        if self.game_manager.is_editing() {
+            self.hover_hit = self.raycast_screen(mouse_x, mouse_y, viewport_width, viewport_height);
+        }
+    }
+
+    /// Closest triangle under a screen position (hit point and normal in world space)
+    pub fn raycast_screen(&self, mouse_x: f32, mouse_y: f32, viewport_width: f32, viewport_height: f32) -> Option<crate::raycast::RayHit> {
+        let view = self.camera.view_matrix();
+        let proj = self.camera.projection_matrix(viewport_width / viewport_height);
+        let ray = crate::gizmo::Ray::from_screen(mouse_x, mouse_y, viewport_width, viewport_height, view, proj);
+        self.scene.raycast(ray.origin.as_dvec3(), ray.direction.as_dvec3(), &self.pick_meshes)
+    }
+
+    /// Handle mouse click for object selection or gizmo drag start.
+    /// `additive` (Ctrl held) adds/removes the picked object from the selection.
+    pub fn handle_mouse_click(&mut self, mouse_x: f32, mouse_y: f32, viewport_width: f32, viewport_height: f32, additive: bool) {
+        // In play mode, check for hologram ship interaction first
+        if self.game_manager.mode == crate::game_manager::GameMode::Play {
+            if self.handle_hologram_click(mouse_x, mouse_y, viewport_width, viewport_height) {
+                return;
+            }
+        }
+
+        // Clicking a view cube face snaps the camera to that axis
+        if self.is_view_cube_visible() {
+            let rotation = self.camera.rotation();
+            if let Some(face) = crate::view_cube::ViewCube::pick_face(mouse_x, mouse_y, viewport_width, viewport_height, rotation) {
+                self.snap_camera_to_face(face);
+                return;
+            }
+        }
+
+        // While measuring, clicks place ruler points instead of selecting
+        if self.is_ruler_active() {
+            if let Some(point) = crate::ruler::Ruler::pick_point(mouse_x, mouse_y, viewport_width, viewport_height, &self.scene, &self.camera) {
+                self.ruler.add_point(point);
+            }
+            return;
+        }
+
+        // Check if clicking on gizmo first
+        if let Some(pivot) = self.gizmo_pivot() {
+            let axis = self.gizmo_state.pick_axis(
+                mouse_x,
+                mouse_y,
+                viewport_width,
+                viewport_height,
+                pivot.position,
+                pivot.rotation,
+                self.gizmo_config.scale_at(&self.camera, pivot.position, viewport_height),
+                &self.camera,
+            );
+
+            if axis != crate::gizmo::GizmoAxis::None {
+                // Locked axes are drawn dimmed and can't be dragged, but still take the click
+                if !self.gizmo_state.is_axis_locked(axis) {
+                    // Start dragging gizmo (remember the start transforms for snapping and group moves)
+                    self.gizmo_state.start_drag(axis, pivot);
+                    self.group_drag_start = self.selection_world_transforms();
+                }
+                return;
+            }
+        }
+
+        // Otherwise try to select an object
+        if let Some(object_id) = self.object_picker.pick_object(
+            mouse_x,
+            mouse_y,
+            viewport_width,
+            viewport_height,
+            &self.scene,
+            &self.camera,
+        ) {
+            if additive {
+                self.scene.toggle_selection(object_id);
+            } else if self.scene.selected_object_id() == Some(object_id) && self.scene.selection_count() == 1 {
+                // If clicking already selected object, focus on it (and keep orbiting it in orbit mode)
+                self.focus_on_object(object_id);
+                if self.camera.mode() == crate::core::CameraMode::Orbit {
+                    self.follow_object(object_id);
+                }
+            } else {
+                self.scene.select_object(object_id);
+            }
+        }
+    }
+
+    /// World transforms of the selected objects that move with the gizmo
+    /// (children of selected objects follow their parent instead of moving twice)
+    fn selection_world_transforms(&self) -> Vec<(ObjectId, Transform)> {
+        self.scene
+            .selected_ids()
+            .iter()
+            .filter(|&&id| self.scene.get_object(id).is_some())
+            .filter(|&&id| !self.scene.selected_ids().iter().any(|&other| self.scene.is_ancestor(other, id)))
+            .map(|&id| (id, self.scene.world_transform(id)))
+            .collect()
+    }
+
+    /// Transform the gizmo is drawn and dragged at: the selected object itself,
+    /// or the selection centroid (oriented like the primary selection) for groups
+    pub fn gizmo_pivot(&self) -> Option<Transform> {
+        if !self.gizmo_state.enabled {
+            return None;
+        }
+
+        let primary = self.scene.world_transform(self.scene.selected_object_id()?);
+        if self.scene.selection_count() <= 1 {
+            return Some(primary);
+        }
+
+        let centroid = self.scene.selection_centroid()?;
+        Some(Transform::new(centroid, primary.rotation, Vec3::ONE))
+    }
+
+    /// Handle mouse drag for gizmo manipulation
+    pub fn handle_mouse_drag(&mut self, old_mouse: (f32, f32), new_mouse: (f32, f32), viewport_width: f32, viewport_height: f32) {
+        // In play mode, check for hologram dragging first
+        if self.game_manager.mode == crate::game_manager::GameMode::Play && self.dragging_hologram {
+            self.handle_hologram_drag(new_mouse.0, new_mouse.1, viewport_width, viewport_height);
+            return;
+        }
+
+        if !self.gizmo_state.using_gizmo {
+            return;
+        }
+
+        // Drags accumulate on the unsnapped pivot transform; snapping is applied on top
+        let mut raw = self.gizmo_state.drag_raw();
+        match self.gizmo_state.mode {
+            crate::gizmo::GizmoMode::Translate => {
+                raw.position = self.gizmo_state.apply_drag_translate(
+                    old_mouse,
+                    new_mouse,
+                    viewport_width,
+                    viewport_height,
+                    raw.position,
+                    raw.rotation,
+                    &self.camera,
+                );
+            }
+            crate::gizmo::GizmoMode::Rotate => {
+                raw.rotation = self.gizmo_state.apply_drag_rotate(
+                    old_mouse,
+                    new_mouse,
+                    viewport_width,
+                    viewport_height,
+                    raw.position,
+                    raw.rotation,
+                    &self.camera,
+                );
+            }
+            crate::gizmo::GizmoMode::Scale => {
+                raw.scale = self.gizmo_state.apply_drag_scale(
+                    old_mouse,
+                    new_mouse,
+                    viewport_width,
+                    viewport_height,
+                    raw.position,
+                    raw.rotation,
+                    raw.scale,
+                    &self.camera,
+                );
+            }
+        }
+
+        let pivot_start = self.gizmo_state.drag_start();
+        let pivot = self.gizmo_state.update_drag(raw);
+
+        let group_start = std::mem::take(&mut self.group_drag_start);
+        self.apply_pivot_change(pivot_start, pivot, &group_start);
+        self.group_drag_start = group_start;
+    }
+
+    /// Nudge the selection one snap increment along/about a gizmo axis (arrow keys in edit mode)
+    pub fn nudge_selection(&mut self, axis: crate::gizmo::GizmoAxis, sign: f32, large: bool) {
+        if self.gizmo_state.using_gizmo {
+            return;
+        }
+        let Some(pivot_start) = self.gizmo_pivot() else {
+            return;
+        };
+
+        let pivot = self.gizmo_state.nudge(pivot_start, axis, sign, large);
+        let group_start = self.selection_world_transforms();
+        self.apply_pivot_change(pivot_start, pivot, &group_start);
+    }
+
+    /// Move the objects in `group_start` (world transforms) by the change from
+    /// `pivot_start` to `pivot`, keeping their arrangement around the pivot
+    fn apply_pivot_change(&mut self, pivot_start: Transform, pivot: Transform, group_start: &[(ObjectId, Transform)]) {
+        // Express the pivot change as a delta and apply it to every selected object
+        let translation = pivot.position - pivot_start.position;
+        let rotation = pivot.rotation * pivot_start.rotation.inverse();
+        let scale_ratio = Vec3::select(
+            pivot_start.scale.cmpeq(Vec3::ZERO),
+            Vec3::ONE,
+            pivot.scale / pivot_start.scale,
+        );
+        let single = group_start.len() == 1;
+
+        let mut transform_changed = false;
+        let mut nebula_moved = false;
+        for &(id, start) in group_start {
+            let Some(obj) = self.scene.get_object(id) else {
+                continue;
+            };
+
+            // Don't allow moving the star - it's always locked to nebula center
+            if obj.object_type == ObjectType::Sphere {
+                continue;
+            }
+            let is_nebula = obj.object_type == ObjectType::Nebula;
+            let old_transform = obj.transform;
+
+            let new_transform = if single {
+                pivot
+            } else {
+                // Offsets from the centroid are scaled in the pivot frame, then rotated about it
+                let local_offset = pivot_start.rotation.inverse() * (start.position - pivot_start.position);
+                let offset = rotation * (pivot_start.rotation * (local_offset * scale_ratio));
+                Transform::new(
+                    pivot_start.position + translation + offset,
+                    (rotation * start.rotation).normalize(),
+                    start.scale * scale_ratio,
+                )
+            };
+
+            // The gizmo works in world space; children store parent-local transforms
+            self.scene.set_world_transform(id, new_transform);
+            let Some(obj) = self.scene.get_object(id) else {
+                continue;
+            };
+
+            if obj.transform.position != old_transform.position
+                || obj.transform.rotation != old_transform.rotation
+                || obj.transform.scale != old_transform.scale
+            {
+                transform_changed = true;
+                nebula_moved |= is_nebula;
+            }
+        }
+
+        // Mark scene dirty if transform changed
+        if transform_changed {
+            self.mark_scene_dirty();
+
+            // If nebula was transformed, sync to ECS entity and update star position
+            if nebula_moved {
+                self.sync_nebula_transform();
+                self.sync_star_to_nebula();
+            }
+        }
+    }
+
+    /// Handle mouse release
+    pub fn handle_mouse_release(&mut self) {
+        // In play mode, stop hologram dragging
+        if self.game_manager.mode == crate::game_manager::GameMode::Play {
+            self.handle_hologram_release();
+        }
+
+        self.gizmo_state.end_drag();
+        self.group_drag_start.clear();
+    }
+    
+    /// Update game logic
+    pub fn update(&mut self, delta_time: f32) {
+        // Play mode runs on the game manager's scaled clock (frozen while paused);
+        // the editor, camera and notifications always run at real time
+        let sim_delta = if self.game_manager.is_playing() {
+            self.game_manager.advance(delta_time)
+        } else {
+            delta_time
+        };
+
+        // Shader animation time follows the simulation
+        self.time += sim_delta;
+
+        // Spin/bob idle motion runs only in play mode, on the same clock. Play mode starts
+        // from a freshly loaded Game (time 0) and exiting reloads one, so it resets cleanly.
+        let animation_time = self.game_manager.is_playing().then_some(self.time);
+        self.scene.set_animation_time(animation_time);
+
+        // Advance camera bookmark transition
+        self.camera.update(delta_time);
+
+        // Drive the camera from the playing track
+        self.update_camera_track_playback(delta_time);
+
+        // Update camera focus animation
+        if self.focus_animation.active {
+            self.focus_animation.progress += delta_time / self.focus_animation.duration;
+
+            if self.focus_animation.progress >= 1.0 {
+                // Animation complete
+                self.focus_animation.progress = 1.0;
+                self.focus_animation.active = false;
+            }
+
+            // Smooth interpolation using ease-in-out cubic
+            let t = self.focus_animation.progress;
+            let eased_t = if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+            };
+
+            // Lerp camera position
+            let position = self.focus_animation.start_position.lerp(self.focus_animation.target_position, eased_t);
+
+            // Lerp camera rotation
+            let pitch = self.focus_animation.start_pitch + (self.focus_animation.target_pitch - self.focus_animation.start_pitch) * eased_t;
+            let yaw = self.focus_animation.start_yaw + (self.focus_animation.target_yaw - self.focus_animation.start_yaw) * eased_t;
+            let roll = self.focus_animation.start_roll + (self.focus_animation.target_roll - self.focus_animation.start_roll) * eased_t;
+
+            self.camera.set_position(position);
+            self.camera.set_rotation(pitch, yaw, roll);
+        }
+
+        // Aim the light being looked through with the camera
+        self.update_light_view();
+
+        // Update cube object if it exists
+        if let Some(cube_id) = self.scene.find_by_type(ObjectType::Cube) {
+            if let Some(cube) = self.scene.get_object_mut(cube_id) {
+                // Apply angular velocity to cube
+                let delta_rotation = Quat::from_euler(
+                    glam::EulerRot::XYZ,
+                    self.ship_angular_velocity.x * sim_delta,
+                    self.ship_angular_velocity.y * sim_delta,
+                    self.ship_angular_velocity.z * sim_delta,
+                );
+                cube.transform.rotation = (cube.transform.rotation * delta_rotation).normalize();
+
+                // Apply velocity with damping
+                cube.transform.position += self.ship_velocity * sim_delta;
+            }
+        }
+
+        // Damping is per simulated frame, so momentum survives a pause
+        if sim_delta > 0.0 {
+            self.ship_velocity *= 0.98; // Air resistance
+            self.ship_angular_velocity *= 0.95; // Angular damping
+        }
+
+        // Turn replay runs at real time, even while the game is paused
+        if let Some(player) = self.replay.as_mut() {
+            player.update(delta_time);
+            self.apply_replay_poses();
+        }
+
+        // Carry the camera along with a followed object (after everything that moves objects)
+        self.update_orbit_follow();
+
+        // Update notifications
+        self.notifications.retain_mut(|notif| {
+            notif.time_remaining -= delta_time;
+            notif.time_remaining > 0.0
+        });
+
+        // Keep the ECS render origin near the camera (camera-relative rendering).
+        // It rebases in jumps once the camera drifts far enough, so render-space coordinates
+        // stay small without shifting every frame. Fly movement is applied before this.
+        self.ecs_world.update_camera_origin(self.camera.world_position());
+
+        // Step physics in play mode and write simulated transforms back to the ECS
+        if self.game_manager.is_playing() && !self.game_manager.is_paused() {
+            self.physics_world.update(&mut self.ecs_world.world, self.camera.world_position(), sim_delta);
+        }
+    }
+
+    /// Replace the ECS world (e.g. with one loaded from disk) and find the entities the
+    /// game keeps handles to again
+    pub fn replace_ecs_world(&mut self, ecs_world: crate::ecs::EcsWorld) {
+        use crate::ecs::components::{EntityType, Ship};
+
+        let find_type = |entity_type: EntityType| {
+            let mut query = ecs_world.world.query::<&EntityType>();
+            let found = query.iter().find(|(_, ty)| **ty == entity_type).map(|(entity, _)| entity);
+            found
+        };
+        self.nebula_entity = find_type(EntityType::Nebula);
+        self.star_entity = find_type(EntityType::Star);
+        self.fed_cruiser_entity = ecs_world
+            .world
+            .query::<&Ship>()
+            .iter()
+            .find(|(_, ship)| ship.name == "Federation Cruiser")
+            .map(|(entity, _)| entity);
+        self.ecs_world = ecs_world;
+    }
+
+    /// Sync the primary (oldest) nebula object's transform to the ECS entity
+    /// Called when the nebula transform is changed via gizmo or loaded from scene
+    pub fn sync_nebula_transform(&mut self) {
+        use glam::{DVec3, DQuat};
+        use crate::ecs::components::{Position, Rotation};
+
+        // Find nebula scene object
+        if let Some(nebula_id) = self.scene.find_by_type(ObjectType::Nebula) {
+            if let Some(nebula_obj) = self.scene.get_object(nebula_id) {
+                if let Some(entity) = self.nebula_entity {
+                    // Update ECS position from scene object
+                    if let Ok(mut pos) = self.ecs_world.world.get::<&mut Position>(entity) {
+                        pos.0 = DVec3::new(
+                            nebula_obj.transform.position.x as f64,
+                            nebula_obj.transform.position.y as f64,
+                            nebula_obj.transform.position.z as f64,
+                        );
+                    }
+
+                    // Update ECS rotation from scene object (quaternion)
+                    if let Ok(mut rot) = self.ecs_world.world.get::<&mut Rotation>(entity) {
+                        let quat = nebula_obj.transform.rotation;
+                        rot.0 = DQuat::from_xyzw(quat.x as f64, quat.y as f64, quat.z as f64, quat.w as f64);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sync star position to the primary nebula's center
+    /// Called whenever the nebula is moved to keep the star at its center
+    pub fn sync_star_to_nebula(&mut self) {
+        // Get nebula position
+        if let Some(nebula_id) = self.scene.find_by_type(ObjectType::Nebula) {
+            if let Some(nebula_obj) = self.scene.get_object(nebula_id) {
+                let nebula_pos = nebula_obj.transform.position;
+
+                // Update star position to match nebula center
+                if let Some(star_id) = self.scene.find_by_type(ObjectType::Sphere) {
+                    if let Some(star_obj) = self.scene.get_object_mut(star_id) {
+                        star_obj.transform.position = nebula_pos;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Visible nebula volumes as (world matrix, settings), farthest from the camera first
+    /// Nebulae without their own settings use the engine-wide default config.
+    pub fn get_nebula_instances(&self) -> Vec<(Mat4, NebulaConfig)> {
+        let camera_position = self.camera.position();
+        let mut nebulae: Vec<(f32, Mat4, NebulaConfig)> = self
+            .scene
+            .objects_sorted()
+            .into_iter()
+            .filter(|obj| obj.object_type == ObjectType::Nebula && self.scene.is_shown(obj))
+            .map(|obj| {
+                let model = self.scene.world_matrix(obj.id);
+                let distance = model.w_axis.truncate().distance_squared(camera_position);
+                (distance, model, obj.nebula.unwrap_or(self.nebula_config))
+            })
+            .collect();
+
+        // Back to front so alpha blending layers nearer volumes over farther ones
+        nebulae.sort_by(|a, b| b.0.total_cmp(&a.0));
+        nebulae.into_iter().map(|(_, model, config)| (model, config)).collect()
+    }
 
-                            if ray.intersects_sphere(hologram_pos.as_vec3(), radius).is_some() {
-                                self.hovering_hologram = true;
-                                self.hover_text = Some("Ship Movement Estimator".to_string());
-                                return;
-                            }
-                        }
-                    }
+    /// Add a notification message
+    pub fn add_notification(&mut self, message: String, duration: f32) {
+        self.notifications.push(Notification::new(message, duration));
+    }
+
+    /// Log the outcome of a shader hot-reload to the console and notify the user
+    pub fn report_shader_reload(&mut self, report: &crate::core::shader::ShaderCompileReport) {
+        if !report.glslc_found {
+            self.console.warn("glslc not found in PATH - reloading prebuilt .spv files");
+        }
+        for source in &report.compiled {
+            self.console.info(format!("Compiled {}", source));
+        }
+        for error in &report.errors {
+            self.console.error(error.clone());
+        }
+
+        if report.has_errors() {
+            self.add_notification("Shader reload failed - see console".to_string(), 3.0);
+        } else {
+            self.console.info("Shaders reloaded");
+            self.add_notification("Shaders reloaded".to_string(), 2.0);
+        }
+    }
+
+    /// Mark scene as dirty (needs save)
+    pub fn mark_scene_dirty(&mut self) {
+        self.scene_dirty = true;
+    }
+
+    /// Assign the material open in the editor to every selected object that can take one,
+    /// as a single scene change. Returns how many objects changed.
+    pub fn apply_material_to_selection(&mut self) -> usize {
+        let changed = self.scene.assign_material_to_selection(&self.current_material_name);
+        if changed > 0 {
+            self.mark_scene_dirty();
+            self.add_notification(
+                format!("Applied '{}' to {} object{}", self.current_material_name, changed, if changed == 1 { "" } else { "s" }),
+                2.0,
+            );
+        }
+        changed
+    }
+
+    /// Assign the key pressed while the Keymap panel was waiting for one. Escape cancels;
+    /// actions that were using the key lose their binding and are reported.
+    pub fn finish_key_capture(&mut self, key: winit::keyboard::KeyCode, ctrl: bool, shift: bool) {
+        use crate::keymap::{key_name, KeyBinding};
+        let Some(action) = self.keymap_capture.take() else {
+            return;
+        };
+        if key == winit::keyboard::KeyCode::Escape && !ctrl && !shift {
+            return;
+        }
+        if key_name(key).is_none() {
+            self.add_notification(format!("{:?} can't be bound", key), 2.0);
+            return;
+        }
+
+        let binding = KeyBinding { key, ctrl, shift };
+        let displaced = self.keymap.bind(action, Some(binding));
+        if displaced.is_empty() {
+            self.add_notification(format!("{} bound to {}", action.label(), binding), 2.0);
+        } else {
+            let names: Vec<&str> = displaced.iter().map(|other| other.label()).collect();
+            self.add_notification(
+                format!("{} bound to {} (unbound from {})", action.label(), binding, names.join(", ")),
+                3.0,
+            );
+        }
+        self.mark_config_dirty();
+    }
+
+    /// Mark config as dirty (needs save)
+    pub fn mark_config_dirty(&mut self) {
+        self.config_dirty = true;
+    }
+
+    /// Check if anything needs saving
+    pub fn is_dirty(&self) -> bool {
+        self.scene_dirty || self.config_dirty
+    }
+
+    /// Whether unsaved editor changes are due for an auto-save (never in play mode,
+    /// whose state is thrown away on exit)
+    pub fn autosave_due(&self) -> bool {
+        self.autosave_config.enabled
+            && self.is_dirty()
+            && self.game_manager.mode == crate::game_manager::GameMode::Edit
+            && self.last_save_time.elapsed().as_secs() >= self.autosave_config.interval_secs as u64
+    }
+
+    /// Get all visible cubes with their model matrices (includes nebula and spheres for picking)
+    pub fn get_visible_cubes(&self) -> Vec<Mat4> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .iter()
+            .filter(|obj| self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter(|obj| matches!(obj.object_type, ObjectType::Cube))
+            .map(|obj| self.scene.world_matrix(obj.id))
+            .collect()
+    }
+
+    /// Get all visible sphere objects (returns model matrix)
+    pub fn get_visible_spheres(&self) -> Vec<Mat4> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .iter()
+            .filter(|obj| self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter(|obj| matches!(obj.object_type, ObjectType::Sphere))
+            .map(|obj| self.scene.world_matrix(obj.id))
+            .collect()
+    }
+
+    /// Get all visible mesh objects (returns path and model matrix)
+    pub fn get_visible_meshes(&self) -> Vec<(String, Mat4)> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .iter()
+            .filter(|obj| self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter_map(|obj| {
+                if let ObjectType::Mesh(path) = &obj.object_type {
+                    Some((path.clone(), self.scene.world_matrix(obj.id)))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Material used to draw an object: its assigned library material, or the editor material.
+    /// The material currently open in the editor uses the live (unsaved) values.
+    pub fn object_material(&self, obj: &crate::scene::SceneObject) -> &crate::material::MaterialProperties {
+        match &obj.material {
+            Some(name) if name == &self.current_material_name => &self.material,
+            Some(name) => self.material_library.get(name).unwrap_or(&self.material),
+            None => &self.material,
+        }
+    }
+
+    /// Get all visible cube objects with their materials
+    pub fn get_visible_cubes_with_materials(&self) -> Vec<(Mat4, &crate::material::MaterialProperties)> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .into_iter()
+            .filter(|obj| self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter(|obj| matches!(obj.object_type, ObjectType::Cube))
+            .map(|obj| (self.scene.world_matrix(obj.id), self.object_material(obj)))
+            .collect()
+    }
+
+    /// Get all visible primitive objects with their materials (shape, model matrix, material)
+    pub fn get_visible_primitives_with_materials(&self) -> Vec<(crate::mesh::PrimitiveShape, Mat4, &crate::material::MaterialProperties)> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .into_iter()
+            .filter(|obj| self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter_map(|obj| {
+                if let ObjectType::Primitive(shape) = obj.object_type {
+                    Some((shape, self.scene.world_matrix(obj.id), self.object_material(obj)))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Get all visible mesh objects with their materials (path, model matrix, material)
+    pub fn get_visible_meshes_with_materials(&self) -> Vec<(String, Mat4, &crate::material::MaterialProperties)> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .into_iter()
+            .filter(|obj| self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter_map(|obj| {
+                if let ObjectType::Mesh(path) = &obj.object_type {
+                    Some((path.clone(), self.scene.world_matrix(obj.id), self.object_material(obj)))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Get the materials of all visible cubes, primitives and meshes (used to upload their textures)
+    pub fn get_visible_materials(&self) -> Vec<&crate::material::MaterialProperties> {
+        let mut materials: Vec<&crate::material::MaterialProperties> = self
+            .get_visible_cubes_with_materials()
+            .into_iter()
+            .map(|(_, material)| material)
+            .chain(self.get_visible_primitives_with_materials().into_iter().map(|(_, _, material)| material))
+            .chain(self.get_visible_meshes_with_materials().into_iter().map(|(_, _, material)| material))
+            .collect();
+        materials.dedup_by(|a, b| std::ptr::eq(*a, *b));
+        materials
+    }
+
+    /// Get outlined objects (selected and hovered, colored by `outline_config`), edit mode only
+    /// Returns: Vec<(object_type, model_matrix, outline_color, outline_width_pixels)>
+    pub fn get_outlined_objects(&self) -> Vec<(ObjectType, Mat4, glam::Vec4, f32)> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+
+        // Outlines are editor feedback only
+        if !in_edit_mode {
+            return Vec::new();
+        }
+
+        let outline = &self.outline_config;
+        let selected_color = self.tonemap_config.shader_color(outline.selected_color).extend(1.0);
+        let hovered_color = self.tonemap_config.shader_color(outline.hovered_color).extend(1.0);
+        // Screen pixels; hover feedback is a little lighter than the selection
+        let selected_width = outline.width;
+        let hovered_width = (outline.width * 0.5).max(OutlineConfig::MIN_WIDTH);
+
+        // Selected objects first, then the hovered object if it isn't already selected
+        let selected = self
+            .scene
+            .selected_ids()
+            .iter()
+            .map(|&id| (id, selected_color, selected_width));
+        let hovered = self
+            .object_picker
+            .hovered_object
+            .filter(|&id| !self.scene.is_selected(id))
+            .map(|id| (id, hovered_color, hovered_width));
+
+        selected
+            .chain(hovered)
+            .filter_map(|(id, color, width)| self.scene.get_object(id).map(|obj| (obj, color, width)))
+            .filter(|(obj, _, _)| self.scene.is_shown(obj))
+            .filter(|(obj, _, _)| {
+                matches!(
+                    obj.object_type,
+                    ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_) | ObjectType::Unlit(_)
+                )
+            })
+            .map(|(obj, color, width)| (obj.object_type.clone(), self.scene.world_matrix(obj.id), color, width))
+            .collect()
+    }
+
+    /// Get mesh objects that need a wireframe overlay (per-object flag or "Wireframe All")
+    pub fn get_wireframe_objects(&self) -> Vec<(ObjectType, Mat4)> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+
+        self.scene
+            .objects()
+            .values()
+            .filter(|obj| self.scene.is_shown(obj) && (obj.wireframe || self.wireframe_config.show_all))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .filter(|obj| {
+                matches!(
+                    obj.object_type,
+                    ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_) | ObjectType::Unlit(_)
+                )
+            })
+            .map(|obj| (obj.object_type.clone(), self.scene.world_matrix(obj.id)))
+            .collect()
+    }
+
+    /// Objects whose bounding boxes are drawn with "Show Bounds": (type, world matrix, selected)
+    pub fn get_bounds_objects(&self) -> Vec<(ObjectType, Mat4, bool)> {
+        if !self.wireframe_config.show_bounds {
+            return Vec::new();
+        }
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+
+        self.scene
+            .objects()
+            .values()
+            .filter(|obj| self.scene.is_shown(obj) && (!obj.editor_only || in_edit_mode))
+            .filter(|obj| {
+                matches!(
+                    obj.object_type,
+                    ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_) | ObjectType::Unlit(_)
+                )
+            })
+            .map(|obj| (obj.object_type.clone(), self.scene.world_matrix(obj.id), self.scene.is_selected(obj.id)))
+            .collect()
+    }
+
+    /// Update ship bounds when mesh is loaded
+    /// This is called from the renderer after loading a mesh
+    pub fn update_ship_bounds(&mut self, mesh_path: &str, bounds_min: Vec3, bounds_max: Vec3) {
+        // Check if this is the Fed Cruiser mesh
+        if mesh_path.contains("Fed_cruiser") {
+            if let Some(fed_entity) = self.fed_cruiser_entity {
+                if let Ok(ship) = self.ecs_world.world.query_one_mut::<&mut crate::ecs::components::Ship>(fed_entity) {
+                    ship.bounds_min = bounds_min;
+                    ship.bounds_max = bounds_max;
+                    // Updated Fed Cruiser bounds
+                }
+            }
+        }
+    }
+
+    /// Check if any nebula is visible
+    pub fn is_nebula_visible(&self) -> bool {
+        self.scene
+            .get_by_type(ObjectType::Nebula)
+            .into_iter()
+            .any(|id| self.scene.get_object(id).is_some_and(|nebula| self.scene.is_shown(nebula)))
+    }
+
+    /// Whether the skybox pass draws: the background is in skybox mode and the
+    /// skybox object (if the scene has one) isn't hidden
+    pub fn draws_skybox(&self) -> bool {
+        self.background_config.mode == BackgroundMode::Skybox
+            && self
+                .scene
+                .find_by_type(ObjectType::Skybox)
+                .and_then(|id| self.scene.get_object(id))
+                .is_none_or(|skybox| self.scene.is_shown(skybox))
+    }
+
+    /// Check if skybox is visible
+    pub fn is_skybox_visible(&self) -> bool {
+        if let Some(skybox_id) = self.scene.find_by_type(ObjectType::Skybox) {
+            if let Some(skybox) = self.scene.get_object(skybox_id) {
+                return self.scene.is_shown(skybox);
+            }
+        }
+        false
+    }
+
+    /// Whether an object name passes the Scene Hierarchy search filter
+    pub fn matches_hierarchy_filter(&self, name: &str) -> bool {
+        let filter = self.hierarchy_filter.trim();
+        filter.is_empty() || name.to_lowercase().contains(&filter.to_lowercase())
+    }
+
+    /// Directional light objects, oldest first (the first one is the shadow caster)
+    fn directional_light_ids(&self) -> Vec<ObjectId> {
+        let mut ids = self.scene.get_by_type(ObjectType::DirectionalLight);
+        ids.sort();
+        ids.truncate(crate::core::lighting::MAX_DIRECTIONAL_LIGHTS);
+        ids
+    }
+
+    /// Lights shaded this frame, up to MAX_DIRECTIONAL_LIGHTS (the first casts shadows)
+    /// Each direction comes from its object's world rotation; with no light objects the
+    /// light settings are used. All lights share the scene's shadow/ambient color.
+    pub fn get_directional_lights(&self) -> Vec<crate::core::lighting::DirectionalLight> {
+        let lights: Vec<_> = self
+            .directional_light_ids()
+            .into_iter()
+            .filter_map(|id| {
+                let obj = self.scene.get_object(id)?;
+                // Light arrow points down -Y, rotate it by the object's rotation
+                let direction = (self.scene.world_transform(id).rotation * Vec3::NEG_Y).normalize();
+                // Lights saved before per-light settings existed use the scene's light settings
+                let settings = obj.directional_light.unwrap_or(crate::core::lighting::DirectionalLightSettings {
+                    color: self.directional_light.color,
+                    intensity: self.directional_light.intensity,
+                });
+                Some(settings.to_light(direction, self.directional_light.shadow_color))
+            })
+            .collect();
+
+        if lights.is_empty() {
+            vec![self.directional_light]
+        } else {
+            lights
+        }
+    }
+
+    /// The shadow-casting (first) directional light
+    pub fn primary_directional_light(&self) -> crate::core::lighting::DirectionalLight {
+        self.get_directional_lights()[0]
+    }
+
+    /// Get directional light direction from the first light object's rotation (falls back to the light settings)
+    pub fn get_directional_light_direction(&self) -> Vec3 {
+        self.primary_directional_light().direction
+    }
+
+    /// Index of a directional light among the shaded lights (0 = shadow caster, None = over the limit)
+    pub fn directional_light_index(&self, id: ObjectId) -> Option<usize> {
+        self.directional_light_ids().iter().position(|&light_id| light_id == id)
+    }
+
+    /// World transforms of the visible directional lights (for the editor arrows)
+    pub fn get_directional_light_transforms(&self) -> Vec<Mat4> {
+        self.scene
+            .get_by_type(ObjectType::DirectionalLight)
+            .into_iter()
+            .filter(|&id| self.scene.get_object(id).is_some_and(|light| self.scene.is_shown(light)))
+            .map(|id| self.scene.world_matrix(id))
+            .collect()
+    }
+
+    /// Add a directional light in front of the camera and select it
+    /// Returns None (with a notification) once MAX_DIRECTIONAL_LIGHTS exist
+    pub fn add_directional_light(&mut self) -> Option<ObjectId> {
+        let count = self.scene.get_by_type(ObjectType::DirectionalLight).len();
+        if count >= crate::core::lighting::MAX_DIRECTIONAL_LIGHTS {
+            self.add_notification(
+                format!("At most {} directional lights are supported", crate::core::lighting::MAX_DIRECTIONAL_LIGHTS),
+                3.0,
+            );
+            return None;
+        }
+
+        let position = self.camera.position() + self.camera.rotation() * Vec3::NEG_Z * 5.0;
+        let id = self.scene.add_object_with_transform(
+            format!("Directional Light {}", count + 1),
+            ObjectType::DirectionalLight,
+            crate::scene::Transform::from_position(position),
+        );
+        if let Some(light) = self.scene.get_object_mut(id) {
+            // Fill light: dimmer than the default key light, and the arrow is editor-only
+            light.editor_only = true;
+            light.directional_light = Some(crate::core::lighting::DirectionalLightSettings {
+                intensity: 0.4,
+                ..Default::default()
+            });
+        }
+        self.scene.select_object(id);
+        self.mark_scene_dirty();
+        Some(id)
+    }
+
+    /// Whether the camera is currently looking through this light
+    pub fn is_looking_through(&self, id: ObjectId) -> bool {
+        self.light_view.as_ref().is_some_and(|view| view.light == id)
+    }
+
+    /// Move the camera onto a directional light, looking down its direction. Until
+    /// `stop_looking_through_light`, turning the camera re-aims the light.
+    pub fn look_through_light(&mut self, id: ObjectId) {
+        if self.scene.get_object(id).map(|obj| &obj.object_type) != Some(&ObjectType::DirectionalLight) {
+            return;
+        }
+        self.focus_animation.active = false;
+
+        // Looking through another light keeps the pose from before the first one
+        let (position, rotation, fov) = match self.light_view.take() {
+            Some(view) => (view.position, view.rotation, view.fov),
+            None => (self.camera.position(), self.camera.rotation(), self.camera.fov()),
+        };
+
+        let light = self.scene.world_transform(id);
+        self.camera.set_pose(light.position, light.rotation * Self::camera_to_light_rotation().inverse(), fov);
+        self.light_view = Some(LightView {
+            light: id,
+            position,
+            rotation,
+            fov,
+            applied_rotation: self.camera.rotation(),
+        });
+    }
+
+    /// Return the camera to where it was before looking through a light
+    pub fn stop_looking_through_light(&mut self) {
+        if let Some(view) = self.light_view.take() {
+            self.camera.set_pose(view.position, view.rotation, view.fov);
+        }
+    }
+
+    /// Point a directional light the way the camera is looking (its position is unchanged)
+    pub fn align_light_to_view(&mut self, id: ObjectId) {
+        if self.scene.get_object(id).map(|obj| &obj.object_type) != Some(&ObjectType::DirectionalLight) {
+            return;
+        }
+        let mut world = self.scene.world_transform(id);
+        world.rotation = (self.camera.rotation() * Self::camera_to_light_rotation()).normalize();
+        self.scene.set_world_transform(id, world);
+        self.mark_scene_dirty();
+    }
+
+    /// Rotation taking the camera's forward (-Z) onto a light arrow's direction (-Y)
+    fn camera_to_light_rotation() -> Quat {
+        Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)
+    }
+
+    /// Copy camera turns to the light being looked through, and stop looking through it
+    /// once it is deleted or deselected
+    fn update_light_view(&mut self) {
+        let Some(view) = self.light_view.as_ref() else {
+            return;
+        };
+        let light = view.light;
+        if self.scene.get_object(light).is_none() || self.scene.selected_object_id() != Some(light) {
+            self.stop_looking_through_light();
+            return;
+        }
+
+        let rotation = self.camera.rotation();
+        if rotation.angle_between(view.applied_rotation) > 1e-4 {
+            self.align_light_to_view(light);
+            if let Some(view) = self.light_view.as_mut() {
+                view.applied_rotation = rotation;
+            }
+        }
+    }
+
+    /// Add a nebula volume in front of the camera and select it
+    /// It starts with the default nebula settings and the size of the existing nebula.
+    pub fn add_nebula(&mut self) -> ObjectId {
+        let count = self.scene.get_by_type(ObjectType::Nebula).len();
+        let scale = self
+            .scene
+            .find_by_type(ObjectType::Nebula)
+            .and_then(|id| self.scene.get_object(id))
+            .map_or(Vec3::splat(20000.0), |nebula| nebula.transform.scale);
+
+        // Far enough ahead that the camera starts outside the volume
+        let position = self.camera.position() + self.camera.rotation() * Vec3::NEG_Z * scale.max_element();
+        let id = self.scene.add_object_with_transform(
+            format!("Nebula {}", count + 1),
+            ObjectType::Nebula,
+            crate::scene::Transform::new(position, Quat::IDENTITY, scale),
+        );
+        if let Some(nebula) = self.scene.get_object_mut(id) {
+            nebula.nebula = Some(self.nebula_config);
+        }
+        self.scene.select_object(id);
+        self.mark_scene_dirty();
+        id
+    }
+
+    /// Get all visible point lights in the scene (position from each object's world transform)
+    pub fn get_point_lights(&self) -> Vec<crate::core::lighting::PointLight> {
+        self.scene
+            .objects_sorted()
+            .into_iter()
+            .filter(|obj| obj.object_type == ObjectType::PointLight && self.scene.is_shown(obj))
+            .map(|obj| {
+                obj.point_light
+                    .unwrap_or_default()
+                    .to_light(self.scene.world_transform(obj.id).position)
+            })
+            .collect()
+    }
+
+    /// Add a new point light in front of the camera and select it
+    pub fn add_point_light(&mut self) -> ObjectId {
+        let position = self.camera.position() + self.camera.rotation() * Vec3::NEG_Z * 5.0;
+        let count = self.scene.get_by_type(ObjectType::PointLight).len();
+        let id = self.scene.add_object_with_transform(
+            format!("Point Light {}", count + 1),
+            ObjectType::PointLight,
+            crate::scene::Transform::from_position(position),
+        );
+        self.snap_new_object_to_surface(id);
+        self.scene.select_object(id);
+        self.mark_scene_dirty();
+        id
+    }
+
+    /// Add an engine exhaust emitter and select it
+    /// With a ship (or any object) selected the emitter is attached to it, sharing its
+    /// orientation so the exhaust leaves out the back; otherwise it's placed in front of the camera
+    pub fn add_particle_emitter(&mut self) -> ObjectId {
+        let ship = self
+            .scene
+            .selected_object()
+            .filter(|obj| !obj.is_singleton())
+            .map(|obj| obj.id);
+        let transform = match ship {
+            Some(ship) => {
+                let world = self.scene.world_transform(ship);
+                crate::scene::Transform {
+                    scale: Vec3::ONE,
+                    ..world
                 }
             }
+            None => crate::scene::Transform::from_position(self.camera.position() + self.camera.rotation() * Vec3::NEG_Z * 5.0),
+        };
+
+        let count = self.scene.get_by_type(ObjectType::ParticleEmitter).len();
+        let id = self.scene.add_object_with_transform(format!("Engine Exhaust {}", count + 1), ObjectType::ParticleEmitter, transform);
+        if let Some(ship) = ship {
+            if let Err(e) = self.scene.set_parent(id, Some(ship)) {
+                crate::console::warn(format!("Couldn't attach exhaust: {}", e));
+            }
         }
+        self.scene.select_object(id);
+        self.mark_scene_dirty();
+        id
+    }
 
-        // Check gizmo hover if enabled and object selected (edit mode)
-        if self.gizmo_state.enabled && self.scene.selected_object().is_some() {
-            let obj = self.scene.selected_object().unwrap();
-            let object_pos = obj.transform.position;
-            let object_rotation = obj.transform.rotation;
-            self.gizmo_state.pick_axis(
-                mouse_x,
-                mouse_y,
-                viewport_width,
-                viewport_height,
-                object_pos,
-                object_rotation,
-                &self.camera,
+    /// Visible particle emitters with their world position and emission direction (local -Z)
+    pub fn get_particle_emitters(&self) -> Vec<crate::particles::EmitterInstance> {
+        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+        self.scene
+            .objects_sorted()
+            .into_iter()
+            .filter(|obj| obj.object_type == ObjectType::ParticleEmitter && self.scene.is_shown(obj))
+            .filter(|obj| !obj.editor_only || in_edit_mode)
+            .map(|obj| {
+                let model = self.scene.world_matrix(obj.id);
+                crate::particles::EmitterInstance {
+                    id: obj.id,
+                    position: model.w_axis.truncate(),
+                    direction: model.transform_vector3(Vec3::NEG_Z).normalize_or_zero(),
+                    settings: obj.particle_emitter.unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
+    /// Add a primitive in front of the camera and select it
+    pub fn add_primitive(&mut self, shape: crate::mesh::PrimitiveShape) -> ObjectId {
+        let position = self.camera.position() + self.camera.rotation() * Vec3::NEG_Z * 5.0;
+        let count = self.scene.get_by_type(ObjectType::Primitive(shape)).len();
+        let id = self.scene.add_object_with_transform(
+            format!("{} {}", shape.name(), count + 1),
+            ObjectType::Primitive(shape),
+            crate::scene::Transform::from_position(position),
+        );
+        self.snap_new_object_to_surface(id);
+        self.scene.select_object(id);
+        self.mark_scene_dirty();
+        id
+    }
+
+    /// Add a chart of emissive swatches with known sRGB colors in front of the camera and
+    /// select it. Each swatch's material picker shows the value it should render as, which
+    /// makes the sRGB color policy (`TonemapConfig::srgb_colors`) checkable by eye.
+    pub fn add_color_chart(&mut self) {
+        const SWATCHES: [(&str, Vec3); 10] = [
+            ("Black", Vec3::new(0.0, 0.0, 0.0)),
+            ("Gray 25%", Vec3::new(0.25, 0.25, 0.25)),
+            ("Gray 50%", Vec3::new(0.5, 0.5, 0.5)),
+            ("Gray 75%", Vec3::new(0.75, 0.75, 0.75)),
+            ("White", Vec3::new(1.0, 1.0, 1.0)),
+            ("Red", Vec3::new(1.0, 0.0, 0.0)),
+            ("Green", Vec3::new(0.0, 1.0, 0.0)),
+            ("Blue", Vec3::new(0.0, 0.0, 1.0)),
+            ("Orange", Vec3::new(1.0, 0.5, 0.0)),
+            ("Sky", Vec3::new(0.4, 0.7, 1.0)),
+        ];
+        const COLUMNS: usize = 5;
+        const SPACING: f32 = 1.2;
+
+        let rotation = self.camera.rotation();
+        let center = self.camera.position() + rotation * Vec3::NEG_Z * 8.0;
+        let mut ids = Vec::with_capacity(SWATCHES.len());
+        for (i, (name, color)) in SWATCHES.iter().enumerate() {
+            // Pure emission: no albedo, ambient or GI, so the swatch shows exactly its color
+            let material_name = format!("Color Chart {}", name);
+            self.material_library.set(
+                material_name.clone(),
+                crate::material::MaterialProperties {
+                    albedo: Vec3::ZERO,
+                    metallic: 0.0,
+                    roughness: 1.0,
+                    ambient_strength: 0.0,
+                    gi_strength: 0.0,
+                    emissive: *color,
+                    emissive_strength: 1.0,
+                    ..Default::default()
+                },
             );
-        } else {
-            // Otherwise check object hover
-            self.object_picker.pick_object(
-                mouse_x,
-                mouse_y,
-                viewport_width,
-                viewport_height,
-                &self.scene,
-                &self.camera,
+
+            let column = (i % COLUMNS) as f32 - (COLUMNS - 1) as f32 * 0.5;
+            let row = 0.5 - (i / COLUMNS) as f32;
+            let position = center + rotation * Vec3::new(column * SPACING, row * SPACING, 0.0);
+            let id = self.scene.add_object_with_transform(
+                format!("Swatch {}", name),
+                ObjectType::Cube,
+                Transform::new(position, rotation, Vec3::new(1.0, 1.0, 0.1)),
             );
+            if let Some(swatch) = self.scene.get_object_mut(id) {
+                swatch.material = Some(material_name);
+            }
+            ids.push(id);
         }
-    }
 
-    /// Handle mouse click for object selection or gizmo drag start
-    pub fn handle_mouse_click(&mut self, mouse_x: f32, mouse_y: f32, viewport_width: f32, viewport_height: f32) {
-        // In play mode, check for hologram ship interaction first
-        if self.game_manager.mode == crate::game_manager::GameMode::Play {
-            if self.handle_hologram_click(mouse_x, mouse_y, viewport_width, viewport_height) {
-                return;
-            }
+        self.scene.deselect();
+        for id in ids {
+            self.scene.toggle_selection(id);
         }
+        self.mark_scene_dirty();
+        self.add_notification(
+            "Color chart added - compare with None (Clamp) tonemapping, exposure 1 and bloom off".to_string(),
+            4.0,
+        );
+    }
 
-        // Check if clicking on gizmo first
-        if self.gizmo_state.enabled && self.scene.selected_object().is_some() {
-            let obj = self.scene.selected_object().unwrap();
-            let object_pos = obj.transform.position;
-            let object_rotation = obj.transform.rotation;
-            let axis = self.gizmo_state.pick_axis(
-                mouse_x,
-                mouse_y,
-                viewport_width,
-                viewport_height,
-                object_pos,
-                object_rotation,
-                &self.camera,
-            );
+    /// Save the selected object and its children as a prefab named `prefab_name`
+    /// (or the object's name when the field is empty)
+    pub fn save_selected_as_prefab(&mut self) {
+        let Some((root, root_name)) = self.scene.selected_object().map(|obj| (obj.id, obj.name.clone())) else {
+            self.add_notification("Select an object to save as a prefab".to_string(), 2.0);
+            return;
+        };
+        let name = match self.prefab_name.trim() {
+            "" => root_name,
+            name => name.to_string(),
+        };
 
-            if axis != crate::gizmo::GizmoAxis::None {
-                // Start dragging gizmo
-                self.gizmo_state.start_drag(axis);
-                return;
+        let path = crate::prefab::PrefabData::path_for(&name);
+        let result = crate::prefab::PrefabData::from_subtree(&self.scene, root, &name)
+            .and_then(|prefab| prefab.save(&path));
+        match result {
+            Ok(()) => {
+                println!("Prefab saved to {}", path);
+                self.prefab_names = crate::prefab::PrefabData::list();
+                self.add_notification(format!("Prefab '{}' saved", name), 2.0);
+            }
+            Err(e) => {
+                self.console.error(format!("Failed to save prefab {}: {}", path, e));
+                self.add_notification(format!("Failed to save prefab: {}", e), 3.0);
             }
         }
+    }
 
-        // Otherwise try to select an object
-        if let Some(object_id) = self.object_picker.pick_object(
-            mouse_x,
-            mouse_y,
-            viewport_width,
-            viewport_height,
-            &self.scene,
-            &self.camera,
-        ) {
-            // If clicking already selected object, focus on it
-            if self.scene.selected_object_id() == Some(object_id) {
-                self.focus_on_object(object_id);
-            } else {
-                self.scene.select_object(object_id);
+    /// Export the scene's visible meshes and lights to `gltf_export::EXPORT_PATH`
+    pub fn export_scene_gltf(&mut self) {
+        let path = crate::gltf_export::EXPORT_PATH;
+        let scene_data = crate::scene::SceneData::from_scene_graph(&self.scene);
+        let result = scene_data.export_gltf(path, |obj| self.object_material(obj).clone());
+        match result {
+            Ok(summary) => {
+                self.console.info(format!("Scene exported to {} ({:?})", path, summary));
+                self.add_notification(
+                    format!("Exported {} meshes and {} lights to {}", summary.meshes, summary.lights, path),
+                    3.0,
+                );
+            }
+            Err(e) => {
+                self.console.error(format!("Failed to export {}: {}", path, e));
+                self.add_notification(format!("Failed to export glTF: {}", e), 3.0);
             }
         }
     }
 
-    /// Handle mouse drag for gizmo manipulation
-    pub fn handle_mouse_drag(&mut self, old_mouse: (f32, f32), new_mouse: (f32, f32), viewport_width: f32, viewport_height: f32) {
-        // In play mode, check for hologram dragging first
-        if self.game_manager.mode == crate::game_manager::GameMode::Play && self.dragging_hologram {
-            self.handle_hologram_drag(new_mouse.0, new_mouse.1, viewport_width, viewport_height);
-            return;
-        }
+    /// Spawn a prefab at the camera target, select its root and mark the scene dirty
+    pub fn instantiate_prefab(&mut self, name: &str) -> Option<ObjectId> {
+        let path = crate::prefab::PrefabData::path_for(name);
+        let prefab = match crate::prefab::PrefabData::load(&path) {
+            Ok(prefab) => prefab,
+            Err(e) => {
+                self.console.error(format!("Failed to load prefab {}: {}", path, e));
+                self.add_notification(format!("Failed to load prefab '{}'", name), 3.0);
+                return None;
+            }
+        };
 
-        if !self.gizmo_state.using_gizmo {
+        let position = self.camera_target();
+        let root = prefab.instantiate(&mut self.scene, position)?;
+        self.snap_new_object_to_surface(root);
+        self.scene.select_object(root);
+        self.mark_scene_dirty();
+        self.add_notification(format!("Spawned prefab '{}'", name), 2.0);
+        Some(root)
+    }
+
+    /// Copy the selected object and its children (material references and transforms included)
+    pub fn copy_selected(&mut self) {
+        let Some((root, name)) = self.scene.selected_object().map(|obj| (obj.id, obj.name.clone())) else {
+            self.add_notification("Select an object to copy".to_string(), 2.0);
             return;
+        };
+
+        match crate::prefab::PrefabData::from_subtree(&self.scene, root, &name) {
+            Ok(data) => {
+                self.clipboard = Some(data);
+                self.add_notification(format!("Copied '{}'", name), 1.5);
+            }
+            Err(e) => self.add_notification(format!("Can't copy: {}", e), 2.0),
         }
+    }
 
-        if let Some(obj) = self.scene.selected_object_mut() {
-            let mut transform_changed = false;
-            let obj_type = obj.object_type.clone(); // Store for later check
+    /// Paste the copied object next to the camera target, select it and mark the scene dirty
+    pub fn paste(&mut self) -> Option<ObjectId> {
+        let Some(mut data) = self.clipboard.clone() else {
+            self.add_notification("Nothing to paste".to_string(), 1.5);
+            return None;
+        };
+        for obj in &mut data.objects {
+            obj.name = format!("{} Copy", obj.name);
+        }
 
-            // Don't allow moving the star - it's always locked to nebula center
-            if obj_type == ObjectType::Sphere {
-                return;
-            }
+        // Offset like Duplicate so a paste onto the copied object stays visible
+        let position = self.camera_target() + Vec3::splat(0.5);
+        let root = data.instantiate(&mut self.scene, position)?;
+        self.scene.select_object(root);
+        self.mark_scene_dirty();
+        Some(root)
+    }
 
-            match self.gizmo_state.mode {
-                crate::gizmo::GizmoMode::Translate => {
-                    let old_pos = obj.transform.position;
-                    let new_pos = self.gizmo_state.apply_drag_translate(
-                        old_mouse,
-                        new_mouse,
-                        viewport_width,
-                        viewport_height,
-                        obj.transform.position,
-                        &self.camera,
-                    );
-                    obj.transform.position = new_pos;
-                    transform_changed = old_pos != new_pos;
-                }
-                crate::gizmo::GizmoMode::Rotate => {
-                    let old_rot = obj.transform.rotation;
-                    let new_rot = self.gizmo_state.apply_drag_rotate(
-                        old_mouse,
-                        new_mouse,
-                        viewport_width,
-                        viewport_height,
-                        obj.transform.position,
-                        obj.transform.rotation,
-                        &self.camera,
-                    );
-                    obj.transform.rotation = new_rot;
-                    transform_changed = old_rot != new_rot;
-                }
-                crate::gizmo::GizmoMode::Scale => {
-                    let old_scale = obj.transform.scale;
-                    let new_scale = self.gizmo_state.apply_drag_scale(
-                        old_mouse,
-                        new_mouse,
-                        viewport_width,
-                        viewport_height,
-                        obj.transform.position,
-                        obj.transform.rotation,
-                        obj.transform.scale,
-                        &self.camera,
-                    );
-                    obj.transform.scale = new_scale;
-                    transform_changed = old_scale != new_scale;
-                }
-            }
+    /// Copy the selected object's material
+    pub fn copy_material(&mut self) {
+        let Some(obj) = self.scene.selected_object().filter(|obj| obj.uses_material()) else {
+            self.add_notification("Select a mesh to copy its material".to_string(), 2.0);
+            return;
+        };
 
-            // End the mutable borrow before calling other methods
-            let _ = obj;
+        let material = obj.material.clone();
+        let label = material.clone().unwrap_or_else(|| "Default".to_string());
+        self.material_clipboard = Some(material);
+        self.add_notification(format!("Copied material '{}'", label), 1.5);
+    }
 
-            // Mark scene dirty if transform changed
-            if transform_changed {
-                self.mark_scene_dirty();
+    /// Assign the copied material to every selected mesh
+    pub fn paste_material(&mut self) {
+        let Some(material) = self.material_clipboard.clone() else {
+            self.add_notification("No material copied".to_string(), 1.5);
+            return;
+        };
 
-                // If nebula was transformed, sync to ECS entity and update star position
-                if obj_type == ObjectType::Nebula {
-                    self.sync_nebula_transform();
-                    self.sync_star_to_nebula();
-                }
+        let mut pasted = 0;
+        for id in self.scene.selected_ids().to_vec() {
+            if let Some(obj) = self.scene.get_object_mut(id).filter(|obj| obj.uses_material()) {
+                obj.material = material.clone();
+                pasted += 1;
             }
         }
+
+        if pasted > 0 {
+            self.mark_scene_dirty();
+            self.add_notification(format!("Pasted material onto {} object(s)", pasted), 1.5);
+        } else {
+            self.add_notification("Select meshes to paste the material onto".to_string(), 2.0);
+        }
     }
 
-    /// Handle mouse release
-    pub fn handle_mouse_release(&mut self) {
-        // In play mode, stop hologram dragging
-        if self.game_manager.mode == crate::game_manager::GameMode::Play {
-            self.handle_hologram_release();
+    /// Get the current model matrix for the cube (first cube for backwards compatibility)
+    pub fn get_cube_model_matrix(&self) -> Mat4 {
+        if let Some(cube_id) = self.scene.find_by_type(ObjectType::Cube) {
+            if let Some(cube) = self.scene.get_object(cube_id) {
+                return self.scene.world_matrix(cube.id);
+            }
         }
+        Mat4::IDENTITY
+    }
 
-        self.gizmo_state.end_drag();
+    /// Check if any cube is visible
+    pub fn is_cube_visible(&self) -> bool {
+        self.scene
+            .get_by_type(ObjectType::Cube)
+            .iter()
+            .any(|&id| {
+                self.scene
+                    .get_object(id)
+                    .map(|obj| self.scene.is_shown(obj))
+                    .unwrap_or(false)
+            })
     }
     
-    /// Update game logic
-    pub fn update(&mut self, delta_time: f32) {
-        self.time += delta_time;
-
-        // Update camera focus animation
-        if self.focus_animation.active {
-            self.focus_animation.progress += delta_time / self.focus_animation.duration;
+    /// Get camera view matrix for rendering (render space, see `render_matrix`)
+    pub fn get_view_matrix(&self) -> Mat4 {
+        self.camera.view_matrix_relative_to(self.ecs_world.camera_origin)
+    }
+    
+    /// Get the current time for shader effects
+    pub fn get_time(&self) -> f32 {
+        self.time
+    }
+    
+    /// Get camera position for shaders (render space)
+    pub fn get_camera_position(&self) -> Vec3 {
+        self.ecs_world.world_to_camera_relative(self.camera.world_position())
+    }
 
-            if self.focus_animation.progress >= 1.0 {
-                // Animation complete
-                self.focus_animation.progress = 1.0;
-                self.focus_animation.active = false;
-            }
+    /// Convert a world-space model matrix to render space: relative to the ECS camera
+    /// origin, which stays near the camera so every coordinate the GPU sees is small.
+    /// Everything uploaded with `get_view_matrix` must go through this (or `render_position`).
+    pub fn render_matrix(&self, model: Mat4) -> Mat4 {
+        self.ecs_world.world_matrix_to_camera_relative(model)
+    }
 
-            // Smooth interpolation using ease-in-out cubic
-            let t = self.focus_animation.progress;
-            let eased_t = if t < 0.5 {
-                4.0 * t * t * t
-            } else {
-                1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
-            };
+    /// Convert a world-space point to render space (see `render_matrix`)
+    pub fn render_position(&self, position: Vec3) -> Vec3 {
+        self.ecs_world.world_to_camera_relative(position.as_dvec3())
+    }
 
-            // Lerp camera position
-            let position = self.focus_animation.start_position.lerp(self.focus_animation.target_position, eased_t);
+    /// Smoothly move the camera to a saved bookmark (stops any focus animation)
+    pub fn goto_camera_bookmark(&mut self, index: usize) -> bool {
+        if self.camera.goto_bookmark_index(index) {
+            self.focus_animation.active = false;
+            true
+        } else {
+            false
+        }
+    }
 
-            // Lerp camera rotation
-            let pitch = self.focus_animation.start_pitch + (self.focus_animation.target_pitch - self.focus_animation.start_pitch) * eased_t;
-            let yaw = self.focus_animation.start_yaw + (self.focus_animation.target_yaw - self.focus_animation.start_yaw) * eased_t;
-            let roll = self.focus_animation.start_roll + (self.focus_animation.target_roll - self.focus_animation.start_roll) * eased_t;
+    /// Save the current view as a new bookmark named "View N"
+    pub fn save_camera_bookmark(&mut self) {
+        let mut number = self.camera.bookmarks().len() + 1;
+        while self.camera.bookmarks().iter().any(|b| b.name == format!("View {}", number)) {
+            number += 1;
+        }
+        self.camera.save_bookmark(&format!("View {}", number));
+        self.mark_config_dirty();
+    }
 
-            self.camera.set_position(position);
-            self.camera.set_rotation(pitch, yaw, roll);
+    /// Append the current view to the selected track (creating a track if there is none)
+    pub fn add_camera_keyframe(&mut self) {
+        if self.camera_tracks.is_empty() {
+            self.camera_tracks.push(crate::core::CameraTrack::new("Track 1"));
+            self.selected_camera_track = 0;
         }
+        let Some(track) = self.camera_tracks.get_mut(self.selected_camera_track) else {
+            return;
+        };
+        let time = if track.keyframes().is_empty() {
+            0.0
+        } else {
+            track.duration() + crate::core::camera_track::DEFAULT_KEYFRAME_SPACING
+        };
+        track.add_keyframe(crate::core::CameraKeyframe {
+            position: self.camera.position(),
+            rotation: self.camera.rotation(),
+            fov: self.camera.fov().to_degrees(),
+            time,
+        });
+        self.mark_scene_dirty();
+    }
 
-        // Update cube object if it exists
-        if let Some(cube_id) = self.scene.find_by_type(ObjectType::Cube) {
-            if let Some(cube) = self.scene.get_object_mut(cube_id) {
-                // Apply angular velocity to cube
-                let delta_rotation = Quat::from_euler(
-                    glam::EulerRot::XYZ,
-                    self.ship_angular_velocity.x * delta_time,
-                    self.ship_angular_velocity.y * delta_time,
-                    self.ship_angular_velocity.z * delta_time,
-                );
-                cube.transform.rotation = (cube.transform.rotation * delta_rotation).normalize();
+    /// Start a new empty track named "Track N" and select it
+    pub fn add_camera_track(&mut self) {
+        let mut number = self.camera_tracks.len() + 1;
+        while self.camera_tracks.iter().any(|track| track.name == format!("Track {}", number)) {
+            number += 1;
+        }
+        self.camera_tracks.push(crate::core::CameraTrack::new(format!("Track {}", number)));
+        self.selected_camera_track = self.camera_tracks.len() - 1;
+        self.mark_scene_dirty();
+    }
 
-                // Apply velocity with damping
-                cube.transform.position += self.ship_velocity * delta_time;
-            }
+    /// Delete the selected track (stops playback)
+    pub fn remove_camera_track(&mut self) {
+        if self.selected_camera_track < self.camera_tracks.len() {
+            self.stop_camera_track();
+            self.camera_tracks.remove(self.selected_camera_track);
+            self.selected_camera_track = self.selected_camera_track.saturating_sub(1);
+            self.mark_scene_dirty();
         }
+    }
 
-        self.ship_velocity *= 0.98; // Air resistance
-        self.ship_angular_velocity *= 0.95; // Angular damping
+    /// Play the selected track from the start. Returns false if it has fewer than two keyframes.
+    pub fn play_camera_track(&mut self) -> bool {
+        let playable = self
+            .camera_tracks
+            .get(self.selected_camera_track)
+            .is_some_and(|track| track.keyframes().len() >= 2);
+        if !playable {
+            self.add_notification("A track needs at least two keyframes to play".to_string(), 2.0);
+            return false;
+        }
 
-        // Update notifications
-        self.notifications.retain_mut(|notif| {
-            notif.time_remaining -= delta_time;
-            notif.time_remaining > 0.0
+        self.focus_animation.active = false;
+        self.stop_following(); // The track drives the camera from here
+        self.camera_track_playhead = Some(crate::core::CameraTrackPlayhead {
+            track: self.selected_camera_track,
+            time: 0.0,
         });
+        self.update_camera_track_playback(0.0);
+        true
     }
 
-    /// Sync nebula scene object transform to ECS entity
-    /// Called when the nebula transform is changed via gizmo or loaded from scene
-    pub fn sync_nebula_transform(&mut self) {
-        use glam::{DVec3, DQuat};
-        use crate::ecs::components::{Position, Rotation};
+    /// Stop playback, leaving the camera where the track put it
+    pub fn stop_camera_track(&mut self) {
+        self.camera_track_playhead = None;
+    }
 
-        // Find nebula scene object
-        if let Some(nebula_id) = self.scene.find_by_type(ObjectType::Nebula) {
-            if let Some(nebula_obj) = self.scene.get_object(nebula_id) {
-                if let Some(entity) = self.nebula_entity {
-                    // Update ECS position from scene object
-                    if let Ok(mut pos) = self.ecs_world.world.get::<&mut Position>(entity) {
-                        pos.0 = DVec3::new(
-                            nebula_obj.transform.position.x as f64,
-                            nebula_obj.transform.position.y as f64,
-                            nebula_obj.transform.position.z as f64,
-                        );
-                    }
+    /// Whether a track is driving the camera
+    pub fn is_camera_track_playing(&self) -> bool {
+        self.camera_track_playhead.is_some()
+    }
 
-                    // Update ECS rotation from scene object (quaternion)
-                    if let Ok(mut rot) = self.ecs_world.world.get::<&mut Rotation>(entity) {
-                        let quat = nebula_obj.transform.rotation;
-                        rot.0 = DQuat::from_xyzw(quat.x as f64, quat.y as f64, quat.z as f64, quat.w as f64);
-                    }
-                }
-            }
-        }
+    /// Playback progress from 0.0 to 1.0
+    pub fn camera_track_progress(&self) -> Option<f32> {
+        let playhead = self.camera_track_playhead?;
+        let duration = self.camera_tracks.get(playhead.track)?.duration();
+        Some(if duration > 0.0 { (playhead.time / duration).min(1.0) } else { 1.0 })
     }
 
-    /// Sync star position to nebula center
-    /// Called whenever the nebula is moved to keep the star at its center
-    pub fn sync_star_to_nebula(&mut self) {
-        // Get nebula position
-        if let Some(nebula_id) = self.scene.find_by_type(ObjectType::Nebula) {
-            if let Some(nebula_obj) = self.scene.get_object(nebula_id) {
-                let nebula_pos = nebula_obj.transform.position;
+    /// Advance the playhead and move the camera to the track's pose; stops at the end
+    fn update_camera_track_playback(&mut self, delta_time: f32) {
+        let Some(playhead) = self.camera_track_playhead.as_mut() else {
+            return;
+        };
+        let Some(track) = self.camera_tracks.get(playhead.track) else {
+            self.camera_track_playhead = None;
+            return;
+        };
 
-                // Update star position to match nebula center
-                if let Some(star_id) = self.scene.find_by_type(ObjectType::Sphere) {
-                    if let Some(star_obj) = self.scene.get_object_mut(star_id) {
-                        star_obj.transform.position = nebula_pos;
-                    }
-                }
-            }
+        playhead.time += delta_time;
+        let finished = playhead.time >= track.duration();
+        if let Some(pose) = track.sample(playhead.time) {
+            self.camera.set_pose(pose.position, pose.rotation, pose.fov.to_radians());
+        }
+        if finished {
+            self.camera_track_playhead = None;
+        }
+    }
+
+    /// Point the camera works around: the followed object, the selected object,
+    /// or the point 10 units ahead
+    fn camera_target(&self) -> Vec3 {
+        if let Some(target) = self.camera.orbit_target() {
+            return target.as_vec3();
+        }
+        match self.scene.selected_object() {
+            Some(obj) => self.scene.world_transform(obj.id).position,
+            None => self.camera.position() + self.camera.rotation() * Vec3::NEG_Z * 10.0,
         }
     }
 
-    /// Get nebula model matrix from ECS entity
-    pub fn get_nebula_model_matrix(&self) -> Mat4 {
-        use crate::ecs::components::{Position, Rotation};
-
-        if let Some(entity) = self.nebula_entity {
-            if let Ok(pos) = self.ecs_world.world.get::<&Position>(entity) {
-                if let Ok(rot) = self.ecs_world.world.get::<&Rotation>(entity) {
-                    // Get scale from scene object (scale isn't in ECS yet)
-                    let scale = if let Some(nebula_id) = self.scene.find_by_type(ObjectType::Nebula) {
-                        if let Some(nebula_obj) = self.scene.get_object(nebula_id) {
-                            nebula_obj.transform.scale
-                        } else {
-                            Vec3::ONE
-                        }
-                    } else {
-                        Vec3::ONE
-                    };
+    /// Object the camera is following, if any
+    pub fn followed_object(&self) -> Option<ObjectId> {
+        self.orbit_follow
+    }
 
-                    // Convert from f64 to f32 for rendering
-                    let position = Vec3::new(pos.0.x as f32, pos.0.y as f32, pos.0.z as f32);
-                    let rotation = Quat::from_xyzw(
-                        rot.0.x as f32,
-                        rot.0.y as f32,
-                        rot.0.z as f32,
-                        rot.0.w as f32,
-                    );
+    /// Orbit `id` as the camera pivot and track it as it moves, until another object
+    /// is picked or `stop_following` (Escape)
+    pub fn follow_object(&mut self, id: ObjectId) {
+        let Some(name) = self.scene.get_object(id).map(|obj| obj.name.clone()) else {
+            return;
+        };
+        self.orbit_follow = Some(id);
+        self.camera.set_orbit_target(self.scene.world_transform(id).position.as_dvec3());
+        if self.camera.mode() != crate::core::CameraMode::Orbit {
+            self.camera.set_mode(crate::core::CameraMode::Orbit);
+            self.mark_config_dirty();
+        }
+        self.add_notification(format!("Orbiting {} (Esc to release)", name), 2.0);
+    }
 
-                    return Mat4::from_scale_rotation_translation(scale, rotation, position);
-                }
-            }
+    /// Focus the selected object and keep orbiting it
+    pub fn follow_selected(&mut self) {
+        if let Some(id) = self.scene.selected_object_id() {
+            self.focus_on_object(id);
+            self.follow_object(id);
         }
+    }
 
-        Mat4::IDENTITY
+    /// Return to free orbit around the selection
+    pub fn stop_following(&mut self) {
+        self.orbit_follow = None;
+        self.camera.clear_orbit_target();
     }
 
-    /// Add a notification message
-    pub fn add_notification(&mut self, message: String, duration: f32) {
-        self.notifications.push(Notification::new(message, duration));
+    /// Keep the camera's offset to the followed object as it moves; selecting a
+    /// different object or deleting the followed one ends the follow
+    fn update_orbit_follow(&mut self) {
+        let Some(id) = self.orbit_follow else {
+            return;
+        };
+        let picked_another = self.scene.selected_object_id().is_some_and(|selected| selected != id);
+        if picked_another || self.scene.get_object(id).is_none() {
+            self.stop_following();
+            return;
+        }
+
+        let target = self.scene.world_transform(id).position.as_dvec3();
+        let previous = self.camera.orbit_target().unwrap_or(target);
+        self.camera.track_orbit_target(target);
+
+        // A focus animation in flight keeps heading for the same spot relative to the object
+        if self.focus_animation.active {
+            let delta = (target - previous).as_vec3();
+            self.focus_animation.start_position += delta;
+            self.focus_animation.target_position += delta;
+        }
     }
 
-    /// Mark scene as dirty (needs save)
-    pub fn mark_scene_dirty(&mut self) {
-        self.scene_dirty = true;
+    /// Flip between perspective and orthographic, keeping the selected object
+    /// (or the point 10 units ahead) framed
+    pub fn toggle_camera_projection(&mut self) {
+        let target = self.camera_target();
+        self.focus_animation.active = false;
+        self.camera.toggle_projection(target);
+        self.mark_config_dirty();
     }
 
-    /// Mark config as dirty (needs save)
-    pub fn mark_config_dirty(&mut self) {
-        self.config_dirty = true;
+    /// Switch between orbit and fly camera controls
+    pub fn toggle_camera_mode(&mut self) {
+        let mode = match self.camera.mode() {
+            crate::core::CameraMode::Orbit => crate::core::CameraMode::Fly,
+            crate::core::CameraMode::Fly => crate::core::CameraMode::Orbit,
+        };
+        self.camera.set_mode(mode);
+        self.mark_config_dirty();
     }
 
-    /// Check if anything needs saving
-    pub fn is_dirty(&self) -> bool {
-        self.scene_dirty || self.config_dirty
+    /// Scale the fly speed by mouse-wheel notches
+    pub fn scale_camera_speed(&mut self, wheel_steps: f32) {
+        self.camera.scale_move_speed(wheel_steps);
+        self.mark_config_dirty();
     }
 
-    /// Get all visible cubes with their model matrices (includes nebula and spheres for picking)
-    pub fn get_visible_cubes(&self) -> Vec<Mat4> {
-        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
-        self.scene
-            .objects_sorted()
-            .iter()
-            .filter(|obj| obj.visible)
-            .filter(|obj| !obj.editor_only || in_edit_mode)
-            .filter(|obj| matches!(obj.object_type, ObjectType::Cube))
-            .map(|obj| obj.transform.model_matrix())
-            .collect()
+    /// Orbit-mode zoom toward the selected object (or the point 10 units ahead),
+    /// within the camera's orbit distance limits
+    pub fn dolly_camera(&mut self, wheel_steps: f32) {
+        let target = self.camera_target();
+        self.focus_animation.active = false;
+        self.camera.cancel_transition();
+        let from = self.camera.world_position();
+        self.camera.zoom_orbit(target, wheel_steps);
+        let excluded = self.orbit_collision_excluded();
+        self.collide_camera(from, &excluded);
     }
 
-    /// Get all visible sphere objects (returns model matrix)
-    pub fn get_visible_spheres(&self) -> Vec<Mat4> {
-        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
-        self.scene
-            .objects_sorted()
-            .iter()
-            .filter(|obj| obj.visible)
-            .filter(|obj| !obj.editor_only || in_edit_mode)
-            .filter(|obj| matches!(obj.object_type, ObjectType::Sphere))
-            .map(|obj| obj.transform.model_matrix())
-            .collect()
+    /// Pull a camera move from `from` back to the first surface in its way
+    /// (camera collision setting; a no-op when it is off or nothing is hit)
+    fn collide_camera(&mut self, from: DVec3, excluded: &[ObjectId]) {
+        if !self.camera.collision_enabled() {
+            return;
+        }
+        let to = self.camera.world_position();
+        let radius = self.camera.collision_radius() as f64;
+        let position = self.scene.sweep_point(from, to, radius, &self.pick_meshes, excluded);
+        self.camera.set_world_position(position);
     }
 
-    /// Get all visible mesh objects (returns path and model matrix)
-    pub fn get_visible_meshes(&self) -> Vec<(String, Mat4)> {
-        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
-        self.scene
-            .objects_sorted()
-            .iter()
-            .filter(|obj| obj.visible)
-            .filter(|obj| !obj.editor_only || in_edit_mode)
-            .filter_map(|obj| {
-                if let ObjectType::Mesh(path) = &obj.object_type {
-                    Some((path.clone(), obj.transform.model_matrix()))
-                } else {
-                    None
-                }
-            })
+    /// The orbit target is the followed or selected object; its own faces never block the camera
+    fn orbit_collision_excluded(&self) -> Vec<ObjectId> {
+        self.orbit_follow
+            .or_else(|| self.scene.selected_object().map(|obj| obj.id))
+            .into_iter()
             .collect()
     }
 
-    /// Get outlined objects (selected or highlighted objects)
-    /// Returns: Vec<(mesh_path, model_matrix, outline_color, outline_width)>
-    pub fn get_outlined_objects(&self) -> Vec<(String, Mat4, glam::Vec4, f32)> {
-        let in_edit_mode = self.game_manager.mode == crate::game_manager::GameMode::Edit;
+    /// Whether viewport clicks place ruler points
+    pub fn is_ruler_active(&self) -> bool {
+        self.ruler.active && self.game_manager.is_editing()
+    }
 
-        // In edit mode, outline the selected object
-        if in_edit_mode {
-            if let Some(selected_obj) = self.scene.selected_object() {
-                if let ObjectType::Mesh(ref mesh_path) = selected_obj.object_type {
-                    if selected_obj.visible {
-                        let model_matrix = selected_obj.transform.model_matrix();
-                        let outline_color = glam::Vec4::new(1.0, 0.5, 0.0, 1.0); // Orange outline
-                        let outline_width = 0.02; // 2cm outline
-                        return vec![(mesh_path.clone(), model_matrix, outline_color, outline_width)];
-                    }
-                }
+    /// Turn the measurement tool on or off; turning it on with exactly two
+    /// objects selected measures between them straight away
+    pub fn toggle_ruler(&mut self) {
+        self.ruler.toggle();
+        if self.ruler.active {
+            if self.scene.selection_count() == 2 {
+                self.measure_selection();
+            } else {
+                self.add_notification("Ruler: click two points".to_string(), 2.0);
             }
         }
+    }
 
-        Vec::new()
+    /// Measure the distance between the centers of the two selected objects
+    pub fn measure_selection(&mut self) {
+        let &[a, b] = self.scene.selected_ids() else {
+            self.add_notification("Select exactly two objects to measure".to_string(), 2.0);
+            return;
+        };
+        let start = self.scene.world_transform(a).position.as_dvec3();
+        let end = self.scene.world_transform(b).position.as_dvec3();
+        self.ruler.active = true;
+        self.ruler.measure(start, end);
     }
 
-    /// Update ship bounds when mesh is loaded
-    /// This is called from the renderer after loading a mesh
-    pub fn update_ship_bounds(&mut self, mesh_path: &str, bounds_min: Vec3, bounds_max: Vec3) {
-        // Check if this is the Fed Cruiser mesh
-        if mesh_path.contains("Fed_cruiser") {
-            if let Some(fed_entity) = self.fed_cruiser_entity {
-                if let Ok(ship) = self.ecs_world.world.query_one_mut::<&mut crate::ecs::components::Ship>(fed_entity) {
-                    ship.bounds_min = bounds_min;
-                    ship.bounds_max = bounds_max;
-                    // Updated Fed Cruiser bounds
-                }
-            }
-        }
+    /// Whether the ground grid is drawn (an editing aid, never shown in play mode)
+    pub fn is_grid_visible(&self) -> bool {
+        self.grid_config.enabled && self.game_manager.is_editing()
     }
 
-    /// Check if nebula is visible
-    pub fn is_nebula_visible(&self) -> bool {
-        if let Some(nebula_id) = self.scene.find_by_type(ObjectType::Nebula) {
-            if let Some(nebula) = self.scene.get_object(nebula_id) {
-                return nebula.visible;
-            }
+    /// Whether the view cube is drawn and clickable
+    pub fn is_view_cube_visible(&self) -> bool {
+        self.view_cube.enabled && self.game_manager.mode == crate::game_manager::GameMode::Edit
+    }
+
+    /// Look at the camera target down a view cube face's axis, keeping the current distance
+    pub fn snap_camera_to_face(&mut self, face: crate::view_cube::ViewCubeFace) {
+        let target = self.camera_target();
+        let distance = (self.camera.position() - target).length().max(1.0);
+        let (pitch, yaw) = face.camera_angles();
+
+        self.focus_animation.active = false;
+        self.camera.cancel_transition();
+        self.camera.set_position(target + face.normal() * distance);
+        self.camera.set_rotation(pitch, yaw, 0.0);
+        self.add_notification(format!("View: {}", face.name()), 1.5);
+    }
+
+    /// Slide the camera over the XZ plane, keeping its height and orientation, until it
+    /// looks at `point` (minimap clicks). A camera that isn't looking down is moved to
+    /// stand over the point instead
+    pub fn pan_camera_to(&mut self, point: DVec3) {
+        let position = self.camera.world_position();
+        let forward = (self.camera.rotation() * Vec3::NEG_Z).as_dvec3();
+        let mut target = DVec3::new(point.x, position.y, point.z);
+        if forward.y < -1e-3 {
+            // Where the view ray meets the plane through `point`, relative to the camera
+            let ahead = forward * ((point.y - position.y) / forward.y);
+            target.x -= ahead.x;
+            target.z -= ahead.z;
         }
-        false
+
+        self.stop_following();
+        self.focus_animation.active = false;
+        self.camera.cancel_transition();
+        self.camera.set_world_position(target);
     }
 
-    /// Check if skybox is visible
-    pub fn is_skybox_visible(&self) -> bool {
-        if let Some(skybox_id) = self.scene.find_by_type(ObjectType::Skybox) {
-            if let Some(skybox) = self.scene.get_object(skybox_id) {
-                return skybox.visible;
+    /// World-space bounding box of an object: its mesh bounds (from `calculate_bounds`)
+    /// through its world matrix, or a box of its picking size for objects without triangles
+    pub fn object_world_bounds(&self, object_id: ObjectId) -> Option<(Vec3, Vec3)> {
+        let obj = self.scene.get_object(object_id)?;
+        match self.pick_meshes.get(&obj.object_type) {
+            Some(mesh) => {
+                let (min, max) = mesh.bounds();
+                Some(crate::core::frustum::transform_aabb(self.scene.world_matrix(object_id), min, max))
+            }
+            None => {
+                let center = self.scene.world_transform(object_id).position;
+                let half_size = Vec3::splat(obj.bounding_box_size().max(0.5) * 0.5);
+                Some((center - half_size, center + half_size))
             }
         }
-        false
     }
 
-    /// Get directional light transform if visible
-    pub fn get_directional_light(&self) -> Option<Mat4> {
-        if let Some(light_id) = self.scene.find_by_type(ObjectType::DirectionalLight) {
-            if let Some(light) = self.scene.get_object(light_id) {
-                if light.visible {
-                    return Some(light.transform.model_matrix());
-                }
+    /// World-space corners of an object's box (mesh bounds, or its picking size for
+    /// objects without triangles), following its rotation unlike `object_world_bounds`
+    fn object_world_corners(&self, object_id: ObjectId) -> Option<[Vec3; 8]> {
+        let obj = self.scene.get_object(object_id)?;
+        let (min, max) = match self.pick_meshes.get(&obj.object_type) {
+            Some(mesh) => mesh.bounds(),
+            None => {
+                let half_size = Vec3::splat(obj.base_bounding_box_size().max(0.5) * 0.5);
+                (-half_size, half_size)
             }
-        }
-        None
+        };
+
+        let model = self.scene.world_matrix(object_id);
+        Some(std::array::from_fn(|i| {
+            let corner = Vec3::select(
+                glam::BVec3::new(i & 1 != 0, i & 2 != 0, i & 4 != 0),
+                max,
+                min,
+            );
+            model.transform_point3(corner)
+        }))
     }
 
-    /// Get the current model matrix for the cube (first cube for backwards compatibility)
-    pub fn get_cube_model_matrix(&self) -> Mat4 {
-        if let Some(cube_id) = self.scene.find_by_type(ObjectType::Cube) {
-            if let Some(cube) = self.scene.get_object(cube_id) {
-                return cube.transform.model_matrix();
+    /// Corners of an object and all of its children
+    fn subtree_world_corners(&self, object_id: ObjectId) -> Vec<Vec3> {
+        self.scene
+            .subtree(object_id)
+            .into_iter()
+            .filter_map(|id| self.object_world_corners(id))
+            .flatten()
+            .collect()
+    }
+
+    /// Drop each selected object (with its children) straight down until its box rests
+    /// on the first mesh below it or on the Y=0 ground plane, whichever is higher
+    pub fn drop_selection_to_ground(&mut self) {
+        let roots: Vec<ObjectId> = self.selection_world_transforms().into_iter().map(|(id, _)| id).collect();
+        let mut dropped = 0;
+        for root in roots {
+            let movable = self.scene.get_object(root).is_some_and(|obj| !obj.is_singleton());
+            if !movable {
+                continue;
+            }
+            if self.drop_to_ground(root) {
+                dropped += 1;
             }
         }
-        Mat4::IDENTITY
+
+        if dropped > 0 {
+            self.mark_scene_dirty();
+        } else {
+            self.add_notification("Nothing below to drop onto".to_string(), 2.0);
+        }
     }
 
-    /// Check if any cube is visible
-    pub fn is_cube_visible(&self) -> bool {
-        self.scene
-            .get_by_type(ObjectType::Cube)
+    /// Returns false if there was no surface below the object
+    fn drop_to_ground(&mut self, root: ObjectId) -> bool {
+        let corners = self.subtree_world_corners(root);
+        let Some((min, max)) = corners
             .iter()
-            .any(|&id| {
-                self.scene
-                    .get_object(id)
-                    .map(|obj| obj.visible)
-                    .unwrap_or(false)
+            .map(|&corner| (corner, corner))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+        else {
+            return false;
+        };
+
+        // Cast down from mid-height (so a half-sunk object still finds what it is sunk
+        // into) through the center and just inside the corners of the footprint
+        let center = (min + max) * 0.5;
+        let inset = (max - min) * 0.45;
+        let footprint = [
+            Vec3::ZERO,
+            Vec3::new(-inset.x, 0.0, -inset.z),
+            Vec3::new(inset.x, 0.0, -inset.z),
+            Vec3::new(-inset.x, 0.0, inset.z),
+            Vec3::new(inset.x, 0.0, inset.z),
+        ];
+        let excluded = self.scene.subtree(root);
+        let mesh_surface = footprint
+            .iter()
+            .filter_map(|&offset| {
+                let origin = (center + offset).as_dvec3();
+                self.scene.raycast_excluding(origin, DVec3::NEG_Y, &self.pick_meshes, &excluded)
             })
+            .map(|hit| hit.point.y)
+            .reduce(f64::max);
+        let ground_plane = (center.y >= 0.0).then_some(0.0);
+
+        let Some(surface) = mesh_surface.into_iter().chain(ground_plane).reduce(f64::max) else {
+            return false;
+        };
+
+        // Move in 64-bit, then back to the scene's f32 transform
+        let mut transform = self.scene.world_transform(root);
+        let offset = surface - min.y as f64;
+        transform.position.y = (transform.position.y as f64 + offset) as f32;
+        self.scene.set_world_transform(root, transform);
+        true
     }
-    
-    /// Get camera view matrix
-    pub fn get_view_matrix(&self) -> Mat4 {
-        self.camera.view_matrix()
+
+    /// Move a just-added object onto the surface last under the cursor, if surface
+    /// snapping is on; its box bottom rests on the hit point (tilted to the normal
+    /// when aligning). Without a surface it stays where it was created.
+    fn snap_new_object_to_surface(&mut self, id: ObjectId) {
+        if !self.placement.snap_to_surface {
+            return;
+        }
+        let Some(hit) = self.hover_hit else {
+            return;
+        };
+
+        let up = if self.placement.align_to_normal { hit.normal } else { Vec3::Y };
+        let mut transform = self.scene.world_transform(id);
+        if self.placement.align_to_normal {
+            transform.rotation = Quat::from_rotation_arc(Vec3::Y, hit.normal) * transform.rotation;
+        }
+
+        // Place the origin on the hit, then lift along `up` by how far the box reaches below it
+        // (the scene stores f32, so the 64-bit hit is converted once here)
+        let hit_point = hit.point.as_vec3();
+        transform.position = hit_point;
+        self.scene.set_world_transform(id, transform);
+        let below = self
+            .subtree_world_corners(id)
+            .into_iter()
+            .map(|corner| (corner - hit_point).dot(up))
+            .fold(0.0_f32, f32::min);
+        transform.position = hit_point - up * below;
+        self.scene.set_world_transform(id, transform);
     }
-    
-    /// Get the current time for shader effects
-    pub fn get_time(&self) -> f32 {
-        self.time
+
+    /// World bounds of each visible piece of scene content (meshes and primitives; no
+    /// lights, settings singletons, nebula or skybox)
+    pub fn shown_object_bounds(&self) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+        self.scene
+            .objects()
+            .values()
+            .filter(|obj| self.scene.is_shown(obj) && self.pick_meshes.get(&obj.object_type).is_some())
+            .filter_map(|obj| self.object_world_bounds(obj.id))
     }
-    
-    /// Get camera position for shaders
-    pub fn get_camera_position(&self) -> Vec3 {
-        self.camera.position()
+
+    /// Combined bounds of the visible scene content
+    fn scene_world_bounds(&self) -> Option<(Vec3, Vec3)> {
+        self.shown_object_bounds()
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+    }
+
+    /// Frame the selection (F key), or the whole scene when nothing is selected
+    pub fn frame_selected(&mut self) {
+        let bounds = if self.scene.selection_count() > 0 {
+            self.scene
+                .selected_ids()
+                .iter()
+                .filter_map(|&id| self.object_world_bounds(id))
+                .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+        } else {
+            self.scene_world_bounds()
+        };
+
+        match bounds {
+            Some((min, max)) => self.frame_bounds(min, max),
+            None => self.add_notification("Nothing to frame".to_string(), 1.5),
+        }
     }
 
     /// Focus camera on a specific object with smooth animation
     pub fn focus_on_object(&mut self, object_id: ObjectId) {
-        // Hide camera cursor when focusing on object
-        self.show_camera_cursor = false;
-
-        if let Some(obj) = self.scene.get_object(object_id) {
-            // Calculate the distance to place camera (at least 2x the bounding box size)
-            let bbox_size = obj.bounding_box_size();
-            let distance = (bbox_size * 2.5).max(5.0); // At least 5 units away
+        if let Some((min, max)) = self.object_world_bounds(object_id) {
+            self.frame_bounds(min, max);
+        }
+    }
 
-            // Calculate target position (camera looking at object from a nice angle)
-            let object_pos = obj.transform.position;
+    /// Animate the camera back along its current view direction until the box's
+    /// bounding sphere fits the field of view (roll is levelled when the up vector is locked)
+    fn frame_bounds(&mut self, min: Vec3, max: Vec3) {
+        self.camera.cancel_transition();
 
-            // Determine the up vector based on lock state
-            let up = if self.lock_camera_up {
-                // Always use world Y up
-                Vec3::Y
-            } else {
-                // Use the object's local up vector
-                obj.transform.rotation * Vec3::Y
-            };
+        // Hide camera cursor when focusing on object
+        self.show_camera_cursor = false;
 
-            // Calculate right vector for positioning camera
-            let target_forward = Vec3::NEG_Z; // Arbitrary initial forward
-            let right = target_forward.cross(up).normalize();
-            let corrected_forward = up.cross(right).normalize();
-
-            // Position camera at 45-degree angle above and to the side, respecting up vector
-            let offset = right * (distance * 0.7) + up * (distance * 0.5) + corrected_forward * (distance * 0.7);
-            let target_camera_pos = object_pos + offset;
-
-            // Calculate rotation using quaternion look_at for proper centering
-            let forward = (object_pos - target_camera_pos).normalize();
-
-            // Build look-at matrix to get proper rotation
-            // We need to construct a rotation that:
-            // 1. Points camera forward (-Z) at the object
-            // 2. Keeps the camera up (Y) aligned with the desired up vector
-            let right = forward.cross(up).normalize();
-            let corrected_up = right.cross(forward).normalize();
-
-            // Create rotation matrix from basis vectors
-            // Camera looks down -Z, so forward maps to -Z axis
-            let rotation_matrix = Mat4::from_cols(
-                right.extend(0.0),
-                corrected_up.extend(0.0),
-                (-forward).extend(0.0),
-                Vec3::ZERO.extend(1.0),
-            );
+        let center = (min + max) * 0.5;
+        let radius = ((max - min).length() * 0.5).max(0.1);
 
-            let target_rotation = Quat::from_mat4(&rotation_matrix);
-
-            // Extract euler angles from the target rotation
-            let (target_yaw, target_pitch, target_roll) = target_rotation.to_euler(glam::EulerRot::YXZ);
-
-            // Get current camera state
-            let current_pos = self.camera.position();
-            let current_rotation = self.camera.rotation();
-            let (current_yaw, current_pitch, current_roll) = current_rotation.to_euler(glam::EulerRot::YXZ);
-
-            // Start the animation
-            self.focus_animation = CameraFocusAnimation {
-                active: true,
-                start_position: current_pos,
-                start_pitch: current_pitch,
-                start_yaw: current_yaw,
-                start_roll: current_roll,
-                target_position: target_camera_pos,
-                target_pitch,
-                target_yaw,
-                target_roll,
-                progress: 0.0,
-                duration: 0.6, // 0.6 seconds for smooth animation
-            };
+        // Distance at which the sphere touches the (vertical) field of view, with a small margin
+        let distance = match self.camera.projection_mode() {
+            crate::core::ProjectionMode::Perspective => radius / (self.camera.fov() * 0.5).sin() * FRAME_MARGIN,
+            crate::core::ProjectionMode::Orthographic => {
+                self.camera.set_ortho_size(radius * FRAME_MARGIN);
+                radius * 2.0
+            }
         }
+        .max(radius + self.camera.near_plane());
+
+        // Keep the view direction; yaw and pitch alone set it (YXZ order)
+        let (current_yaw, current_pitch, current_roll) = self.camera.rotation().to_euler(glam::EulerRot::YXZ);
+        let forward = self.camera.rotation() * Vec3::NEG_Z;
+        let target_roll = if self.lock_camera_up { 0.0 } else { current_roll };
+
+        self.focus_animation = CameraFocusAnimation {
+            active: true,
+            start_position: self.camera.position(),
+            start_pitch: current_pitch,
+            start_yaw: current_yaw,
+            start_roll: current_roll,
+            target_position: center - forward * distance,
+            target_pitch: current_pitch,
+            target_yaw: current_yaw,
+            target_roll,
+            progress: 0.0,
+            duration: FRAME_DURATION,
+        };
     }
 
     /// Reset camera up vector to world Y axis
@@ -997,11 +3675,15 @@ impl Game {
         self.camera.rotate(pitch_delta, yaw_delta);
     }
 
-    /// Orbit camera around the currently selected object
+    /// Orbit camera around the followed object, or else the currently selected one
     pub fn orbit_camera_around_selected(&mut self, pitch_delta: f32, yaw_delta: f32) {
-        // Get the selected object's position
-        let target_pos = if let Some(obj) = self.scene.selected_object() {
-            obj.transform.position
+        self.camera.cancel_transition();
+
+        // Get the pivot object's position
+        let (target_id, target_pos) = if let (Some(id), Some(target)) = (self.orbit_follow, self.camera.orbit_target()) {
+            (id, target.as_vec3())
+        } else if let Some(obj) = self.scene.selected_object() {
+            (obj.id, self.scene.world_transform(obj.id).position)
         } else {
             // If no object selected, just do regular camera rotation
             self.camera.rotate(pitch_delta, yaw_delta);
@@ -1032,8 +3714,14 @@ impl Game {
             distance * pitch.cos() * yaw.sin(),
         );
 
-        // Update camera position and make it look at the target
+        // Update camera position and make it look at the target; with collision on, the
+        // camera is pulled in to the first surface between the target and the new position
         self.camera.set_position(new_camera_pos);
+        self.collide_camera(target_pos.as_dvec3(), &[target_id]);
+        let new_camera_pos = self.camera.position();
+        if new_camera_pos.distance_squared(target_pos) < 1e-8 {
+            return; // Pulled all the way in, no direction to look along
+        }
 
         // Calculate look direction
         let forward = (target_pos - new_camera_pos).normalize();
@@ -1058,13 +3746,17 @@ impl Game {
     }
 
     pub fn move_camera_forward(&mut self, amount: f32) {
+        let from = self.camera.world_position();
         self.camera.move_forward(amount);
+        self.collide_camera(from, &[]);
         self.show_camera_cursor = true;
         self.update_camera_cursor_position();
     }
 
     pub fn move_camera_right(&mut self, amount: f32) {
+        let from = self.camera.world_position();
         self.camera.move_right(amount);
+        self.collide_camera(from, &[]);
         self.show_camera_cursor = true;
         self.update_camera_cursor_position();
     }
@@ -1074,10 +3766,19 @@ impl Game {
         // Place cursor 10 units in front of camera
         let rotation = self.camera.rotation();
         let forward = rotation * Vec3::NEG_Z;
-        let camera_pos = self.camera.position().as_dvec3();
+        let camera_pos = self.camera.world_position();
         self.camera_cursor_position = camera_pos + (forward.as_dvec3() * 10.0);
     }
 
+    /// Move the camera along its own up axis (fly mode Q/E)
+    pub fn move_camera_up(&mut self, amount: f32) {
+        let from = self.camera.world_position();
+        self.camera.move_up_local(amount);
+        self.collide_camera(from, &[]);
+        self.show_camera_cursor = true;
+        self.update_camera_cursor_position();
+    }
+
     pub fn roll_camera(&mut self, amount: f32) {
         self.camera.roll(amount);
     }
@@ -1091,7 +3792,7 @@ impl Game {
     pub fn enter_play_mode(&mut self) {
         // 1. Save current editor state (scene + all configs)
         if let Err(e) = crate::ui::UiManager::save_scene_and_configs(self) {
-            eprintln!("Failed to save editor state: {}", e);
+            self.console.error(format!("Failed to save editor state: {}", e));
             self.add_notification("Failed to save editor state!".to_string(), 3.0);
             return;
         }
@@ -1102,9 +3803,8 @@ impl Game {
         // 3. Replace self with fresh instance (preserve only runtime state)
         *self = fresh_game;
 
-        // 4. Set game mode to Play
-        self.game_manager.mode = crate::game_manager::GameMode::Play;
-        self.game_manager.pause_state = crate::game_manager::PauseState::Running;
+        // 4. Set game mode to Play (turn 1, play clock at zero)
+        self.game_manager.start_play_mode();
 
         // 5. Clear any editor selections and disable gizmo
         self.scene.deselect();
@@ -1138,7 +3838,7 @@ impl Game {
         // Load scene from disk
         crate::ui::UiManager::load_scene_on_startup(&mut game);
 
-        // Load all configs from disk
+        // Load all configs from disk (the scene names its config file)
         crate::ui::UiManager::load_all_configs(&mut game);
 
         // Clear dirty flags since we just loaded from disk
@@ -1346,6 +4046,10 @@ impl Game {
     /// Stop dragging hologram ship
     pub fn handle_hologram_release(&mut self) {
         self.dragging_hologram = false;
+
+        if self.planned_path_collision().is_some_and(|collision| collision.is_blocked()) {
+            self.add_notification("Path blocked".to_string(), 2.0);
+        }
     }
 
     /// Generate line segments for the 90-degree rotation arc boundary
@@ -1417,8 +4121,193 @@ impl Game {
         vertices
     }
 
+    /// The unit currently being commanded (the player's cruiser)
+    pub fn active_unit(&self) -> Option<crate::ecs::components::Unit> {
+        let fed_entity = self.fed_cruiser_entity?;
+        self.ecs_world.world.get::<&crate::ecs::components::Unit>(fed_entity).ok().map(|unit| (*unit).clone())
+    }
+
+    /// Action points needed to fly the planned Bezier path to the hologram
+    pub fn planned_move_cost(&self) -> Option<u32> {
+        let hologram_pos = self.hologram_ship_position?;
+        let fed_entity = self.fed_cruiser_entity?;
+        let mut query = self.ecs_world.world
+            .query_one::<(&crate::ecs::components::Position, &crate::ecs::components::Ship)>(fed_entity)
+            .ok()?;
+        let (position, ship) = query.get()?;
+
+        let curve = crate::ecs::components::MovementCurve::new(position.0, hologram_pos, ship.control_point);
+        Some(crate::ecs::components::Unit::move_cost(curve.arc_length()))
+    }
+
+    /// Check the planned path to the hologram against the bounds of every other ship
+    pub fn planned_path_collision(&self) -> Option<crate::movement::PathCollision> {
+        use crate::ecs::components::{Position, Rotation, Scale, Ship};
+
+        let hologram_pos = self.hologram_ship_position?;
+        let fed_entity = self.fed_cruiser_entity?;
+        let mut query = self.ecs_world.world
+            .query_one::<(&Position, &Rotation, &Scale, &Ship)>(fed_entity)
+            .ok()?;
+        let (position, rotation, scale, ship) = query.get()?;
+
+        let curve = crate::ecs::components::MovementCurve::new(position.0, hologram_pos, ship.control_point);
+        let body = crate::movement::MovingBody {
+            bounds_min: ship.bounds_min,
+            bounds_max: ship.bounds_max,
+            scale: scale.0,
+            start_rotation: ship.turn_start_rotation,
+            end_rotation: rotation.0,
+        };
+
+        let obstacles: Vec<crate::movement::Aabb> = self.ecs_world.world
+            .query::<(&Position, &Rotation, &Scale, &Ship)>()
+            .iter()
+            .filter(|(entity, _)| *entity != fed_entity)
+            .map(|(_, (position, rotation, scale, other))| {
+                crate::movement::Aabb::from_local(other.bounds_min, other.bounds_max, position.0, rotation.0, scale.0)
+            })
+            .collect();
+
+        Some(crate::movement::collision::check_path(&curve, &body, &obstacles))
+    }
+
+    /// Resolve a planned path that runs into another unit
+    /// Clamps the move to the last safe point when enabled, otherwise refuses it (returns false)
+    fn resolve_blocked_path(&mut self) -> bool {
+        let Some(collision) = self.planned_path_collision().filter(|collision| collision.is_blocked()) else {
+            return true;
+        };
+        let (Some(fed_entity), Some(hologram_pos)) = (self.fed_cruiser_entity, self.hologram_ship_position) else {
+            return true;
+        };
+        self.movement_events.set_turn(self.game_manager.current_turn);
+
+        let clamped = if self.clamp_blocked_moves && collision.last_safe_t > 0.0 {
+            self.ecs_world.world
+                .query_one_mut::<(&crate::ecs::components::Position, &mut crate::ecs::components::Ship)>(fed_entity)
+                .ok()
+                .map(|(position, ship)| {
+                    let curve = crate::ecs::components::MovementCurve::new(position.0, hologram_pos, ship.control_point)
+                        .truncated(collision.last_safe_t);
+                    ship.control_point = curve.control_point;
+                    curve.end_position
+                })
+        } else {
+            None
+        };
+
+        self.movement_events.record_movement_blocked(fed_entity, hologram_pos, clamped);
+        match clamped {
+            Some(safe_position) => {
+                self.hologram_ship_position = Some(safe_position);
+                self.add_notification("Path blocked, move stopped short".to_string(), 2.0);
+                true
+            }
+            None => {
+                self.add_notification("Path blocked".to_string(), 3.0);
+                false
+            }
+        }
+    }
+
+    /// Start reviewing the recorded turns from the beginning
+    /// Each moved ship drives the scene mesh object showing its model; the ECS is left untouched
+    pub fn start_replay(&mut self) {
+        let replay = crate::movement::MovementReplay::from_events(self.movement_events.get_all_events());
+        if replay.turn_count() == 0 {
+            self.add_notification("No recorded turns to replay".to_string(), 2.0);
+            return;
+        }
+
+        self.stop_replay();
+        for ship_id in replay.ship_ids() {
+            let Some(mesh_name) = hecs::Entity::from_bits(ship_id)
+                .and_then(|entity| self.ecs_world.world.get::<&crate::ecs::components::Visual>(entity).ok().map(|visual| visual.mesh_name.clone()))
+            else {
+                continue;
+            };
+            let object = self.scene.objects().values().find(|obj| {
+                matches!(&obj.object_type, ObjectType::Mesh(path) if path.ends_with(&mesh_name))
+                    && !self.replay_objects.iter().any(|(_, id, _)| *id == obj.id)
+            });
+            if let Some(obj) = object {
+                self.replay_objects.push((ship_id, obj.id, obj.transform));
+            }
+        }
+
+        self.replay = Some(crate::movement::ReplayPlayer::new(replay));
+        self.apply_replay_poses();
+    }
+
+    /// Stop the replay and put the ships back where they were
+    pub fn stop_replay(&mut self) {
+        self.replay = None;
+        for (_, id, transform) in self.replay_objects.drain(..) {
+            if let Some(obj) = self.scene.get_object_mut(id) {
+                obj.transform = transform;
+            }
+        }
+    }
+
+    /// Move the replayed ships' scene objects to the poses at the playback position
+    fn apply_replay_poses(&mut self) {
+        let Some(player) = &self.replay else {
+            return;
+        };
+        let poses = player.poses();
+        for (ship_id, id, _) in &self.replay_objects {
+            let (Some(pose), Some(obj)) = (poses.get(ship_id), self.scene.get_object_mut(*id)) else {
+                continue;
+            };
+            obj.transform.position = pose.position.as_vec3();
+            let rotation = pose.rotation;
+            obj.transform.rotation = Quat::from_xyzw(rotation.x as f32, rotation.y as f32, rotation.z as f32, rotation.w as f32).normalize();
+        }
+    }
+
+    /// End the turn (Enter / End Turn button): execute the planned move, then restore
+    /// action points and advance to the next turn
+    pub fn end_turn(&mut self) {
+        if !self.game_manager.is_playing() || self.game_manager.is_paused() {
+            return;
+        }
+        if self.replay.is_some() {
+            self.add_notification("Stop the replay to end the turn".to_string(), 2.0);
+            return;
+        }
+
+        // 1. Execute the planned move (a move over budget keeps the turn open)
+        if !self.execute_ship_movement() {
+            return;
+        }
+
+        // 2. Restore action points, advance the turn and check victory
+        let victory = self.game_manager.end_turn(&mut self.ecs_world.world);
+
+        // 3. Ships plan their next move from where they ended up
+        for (_, (position, rotation, ship)) in self.ecs_world.world.query_mut::<(
+            &crate::ecs::components::Position,
+            &crate::ecs::components::Rotation,
+            &mut crate::ecs::components::Ship,
+        )>() {
+            ship.start_turn(position.0, rotation.0);
+        }
+
+        match victory {
+            Some(message) => self.add_notification(format!("Victory! {}", message), 5.0),
+            None => self.add_notification(format!("Turn {}", self.game_manager.current_turn), 2.0),
+        }
+    }
+
     /// Confirm movement and execute ship to hologram position
-    pub fn execute_ship_movement(&mut self) {
+    /// Returns false if the move was blocked because it costs more action points than the unit has left,
+    /// or because its path runs into another unit (and clamping is off)
+    pub fn execute_ship_movement(&mut self) -> bool {
+        if !self.resolve_blocked_path() {
+            return false;
+        }
+
         if let Some(hologram_pos) = self.hologram_ship_position {
             if let Some(fed_entity) = self.fed_cruiser_entity {
                 // Get current position first
@@ -1428,6 +4317,21 @@ impl Game {
                     None
                 };
 
+                // Pay for the move with action points proportional to the path length
+                if let Some(cost) = self.planned_move_cost() {
+                    let remaining = match self.ecs_world.world.get::<&mut crate::ecs::components::Unit>(fed_entity) {
+                        Ok(mut unit) => (!unit.spend(cost)).then_some(unit.action_points),
+                        Err(_) => None,
+                    };
+                    if let Some(remaining) = remaining {
+                        self.add_notification(
+                            format!("Not enough action points ({} needed, {} left)", cost, remaining),
+                            3.0,
+                        );
+                        return false;
+                    }
+                }
+
                 // Update ship's planned position and control point
                 if let Ok(mut query) = self.ecs_world.world.query_one::<&mut crate::ecs::components::Ship>(fed_entity) {
                     if let Some(ship) = query.get() {
@@ -1448,6 +4352,25 @@ impl Game {
                     }
                 }
 
+                // Record the move for turn replay
+                let recorded = self.ecs_world.world
+                    .query_one_mut::<(&crate::ecs::components::Rotation, &crate::ecs::components::Ship)>(fed_entity)
+                    .ok()
+                    .map(|(rotation, ship)| (rotation.0, ship.turn_start_rotation, ship.control_point));
+                if let (Some(start_pos), Some((end_rotation, start_rotation, control_point))) = (current_pos, recorded) {
+                    let curve = crate::ecs::components::MovementCurve::new(start_pos, hologram_pos, control_point);
+                    self.movement_events.set_turn(self.game_manager.current_turn);
+                    self.movement_events.record_movement_confirmed(
+                        fed_entity,
+                        start_pos,
+                        hologram_pos,
+                        control_point,
+                        curve.velocity_at(1.0),
+                        start_rotation,
+                        end_rotation,
+                    );
+                }
+
                 // Clear hologram after movement
                 self.hologram_ship_position = None;
                 self.dragging_hologram = false;
@@ -1455,5 +4378,7 @@ impl Game {
                 self.add_notification("Ship moved!".to_string(), 2.0);
             }
         }
+
+        true
     }
 }
@@ -4,6 +4,19 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::ecs::components::{Health, Unit};
+
+/// Play-mode speed presets offered in the game mode toolbar
+pub const TIME_SCALES: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+/// Slowest allowed play-mode speed
+pub const MIN_TIME_SCALE: f32 = 0.25;
+/// Fastest allowed play-mode speed
+pub const MAX_TIME_SCALE: f32 = 4.0;
+
+fn default_time_scale() -> f32 {
+    1.0
+}
+
 /// Game mode - Edit or Play
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameMode {
@@ -62,11 +75,15 @@ pub struct GameManager {
     /// Turn time limit in seconds (0 = no limit)
     pub turn_time_limit: f32,
 
-    /// Game started timestamp
+    /// Play-mode speed multiplier (slow motion below 1, fast-forward above)
+    #[serde(skip, default = "default_time_scale")]
+    pub time_scale: f32,
+
+    /// Scaled seconds of play so far (stands still while paused)
     #[serde(skip)]
-    pub game_start_time: f32,
+    pub play_time: f32,
 
-    /// Current turn start timestamp
+    /// Play time when the current turn started
     #[serde(skip)]
     pub turn_start_time: f32,
 }
@@ -115,7 +132,8 @@ impl Default for GameManager {
             player_faction: "Player".to_string(),
             ai_factions: vec!["Red Team".to_string(), "Blue Team".to_string()],
             turn_time_limit: 0.0,
-            game_start_time: 0.0,
+            time_scale: default_time_scale(),
+            play_time: 0.0,
             turn_start_time: 0.0,
         }
     }
@@ -128,12 +146,12 @@ impl GameManager {
     }
 
     /// Start play mode - initialize game state
-    pub fn start_play_mode(&mut self, current_time: f32) {
+    pub fn start_play_mode(&mut self) {
         self.mode = GameMode::Play;
         self.pause_state = PauseState::Running;
         self.current_turn = 1;
-        self.game_start_time = current_time;
-        self.turn_start_time = current_time;
+        self.play_time = 0.0;
+        self.turn_start_time = 0.0;
         println!("=== PLAY MODE STARTED ===");
         println!("Scenario: {}", self.scenario_name);
         println!("Turn 1 begins!");
@@ -177,11 +195,28 @@ impl GameManager {
         self.mode == GameMode::Edit
     }
 
+    /// Set the play-mode speed (clamped to MIN_TIME_SCALE..=MAX_TIME_SCALE)
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    /// Advance the play clock by a real frame time and return the simulation step
+    /// The step is scaled by `time_scale`, and zero outside play mode or while paused.
+    pub fn advance(&mut self, delta_time: f32) -> f32 {
+        if self.mode != GameMode::Play || self.is_paused() {
+            return 0.0;
+        }
+
+        let scaled = delta_time * self.time_scale;
+        self.play_time += scaled;
+        scaled
+    }
+
     /// Advance to next turn
-    pub fn next_turn(&mut self, current_time: f32) {
+    pub fn next_turn(&mut self) {
         if self.mode == GameMode::Play && !self.is_paused() {
             self.current_turn += 1;
-            self.turn_start_time = current_time;
+            self.turn_start_time = self.play_time;
             println!("=== Turn {} begins! ===", self.current_turn);
 
             // Check if max turns reached
@@ -191,28 +226,74 @@ impl GameManager {
         }
     }
 
-    /// Get elapsed game time
-    pub fn get_elapsed_time(&self, current_time: f32) -> f32 {
+    /// End the current turn: restore every unit's action points, advance the turn
+    /// and check victory conditions. Returns the victory message if the game was won
+    pub fn end_turn(&mut self, world: &mut hecs::World) -> Option<String> {
+        if self.mode != GameMode::Play || self.is_paused() {
+            return None;
+        }
+
+        // 1. Restore action points for the new turn
+        for (_, unit) in world.query_mut::<&mut Unit>() {
+            unit.reset();
+        }
+
+        // 2. Advance the turn counter
+        self.next_turn();
+
+        // 3. Check victory conditions
+        let victory = self.check_victory(world);
+        if let Some(message) = &victory {
+            println!("=== VICTORY: {} ===", message);
+        }
+        victory
+    }
+
+    /// Get elapsed game time (scaled play time)
+    pub fn get_elapsed_time(&self) -> f32 {
         if self.mode == GameMode::Play {
-            current_time - self.game_start_time
+            self.play_time
         } else {
             0.0
         }
     }
 
-    /// Get elapsed turn time
-    pub fn get_turn_elapsed_time(&self, current_time: f32) -> f32 {
+    /// Get elapsed turn time (scaled play time)
+    pub fn get_turn_elapsed_time(&self) -> f32 {
         if self.mode == GameMode::Play {
-            current_time - self.turn_start_time
+            self.play_time - self.turn_start_time
         } else {
             0.0
         }
     }
 
     /// Check if victory conditions are met
-    pub fn check_victory(&self) -> Option<String> {
-        // TODO: Implement victory checking logic
-        // For now, just return None
+    pub fn check_victory(&self, world: &hecs::World) -> Option<String> {
+        let conditions = &self.victory_conditions;
+
+        // Every AI faction unit destroyed (scenarios without enemy units can't be won this way)
+        if conditions.eliminate_all_enemies {
+            let mut enemies = 0;
+            let mut enemies_alive = 0;
+            for (_, (unit, health)) in world.query::<(&Unit, Option<&Health>)>().iter() {
+                if self.ai_factions.contains(&unit.faction) {
+                    enemies += 1;
+                    if health.is_none_or(|health| health.is_alive()) {
+                        enemies_alive += 1;
+                    }
+                }
+            }
+
+            if enemies > 0 && enemies_alive == 0 {
+                return Some("All enemies eliminated".to_string());
+            }
+        }
+
+        // Survived the required number of turns
+        if conditions.survive_turns > 0 && self.current_turn > conditions.survive_turns {
+            return Some(format!("Survived {} turns", conditions.survive_turns));
+        }
+
         None
     }
 
@@ -222,3 +303,80 @@ impl GameManager {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::DISTANCE_PER_ACTION_POINT;
+
+    fn playing_manager() -> GameManager {
+        let mut manager = GameManager::new();
+        manager.start_play_mode();
+        manager
+    }
+
+    #[test]
+    fn test_end_turn_restores_action_points() {
+        let mut manager = playing_manager();
+        let mut world = hecs::World::new();
+        let mut unit = Unit::new("Player".to_string(), 4);
+        assert!(unit.spend(3));
+        assert!(!unit.spend(2));
+        let entity = world.spawn((unit,));
+
+        assert_eq!(manager.end_turn(&mut world), None);
+        assert_eq!(manager.current_turn, 2);
+
+        let unit = world.get::<&Unit>(entity).unwrap();
+        assert_eq!(unit.action_points, 4);
+        assert!(!unit.has_moved);
+    }
+
+    #[test]
+    fn test_victory_conditions() {
+        let mut manager = playing_manager();
+        manager.victory_conditions.survive_turns = 2;
+        let mut world = hecs::World::new();
+        world.spawn((Unit::new("Player".to_string(), 4),));
+        let enemy = world.spawn((Unit::new("Red Team".to_string(), 4), Health::new(10.0)));
+
+        assert_eq!(manager.end_turn(&mut world), None);
+
+        // Destroying the last enemy wins
+        world.get::<&mut Health>(enemy).unwrap().damage(10.0);
+        assert_eq!(manager.check_victory(&world), Some("All enemies eliminated".to_string()));
+
+        // Otherwise surviving past the turn limit wins
+        manager.victory_conditions.eliminate_all_enemies = false;
+        assert_eq!(manager.end_turn(&mut world), Some("Survived 2 turns".to_string()));
+    }
+
+    #[test]
+    fn test_time_scale_accumulates_play_time() {
+        let mut manager = playing_manager();
+        manager.set_time_scale(0.25);
+        assert_eq!(manager.advance(1.0), 0.25);
+        manager.set_time_scale(10.0);
+        assert_eq!(manager.time_scale, MAX_TIME_SCALE);
+        assert_eq!(manager.advance(0.5), 2.0);
+        assert_eq!(manager.get_elapsed_time(), 2.25);
+
+        // Pausing stops the clock without touching the scale
+        manager.toggle_pause();
+        assert_eq!(manager.advance(1.0), 0.0);
+        assert_eq!(manager.get_elapsed_time(), 2.25);
+        assert_eq!(manager.time_scale, MAX_TIME_SCALE);
+
+        manager.toggle_pause();
+        manager.next_turn();
+        assert_eq!(manager.advance(0.25), 1.0);
+        assert_eq!(manager.get_turn_elapsed_time(), 1.0);
+    }
+
+    #[test]
+    fn test_move_cost_rounds_up() {
+        assert_eq!(Unit::move_cost(0.0), 0);
+        assert_eq!(Unit::move_cost(DISTANCE_PER_ACTION_POINT), 1);
+        assert_eq!(Unit::move_cost(DISTANCE_PER_ACTION_POINT * 1.5), 2);
+    }
+}
@@ -1,5 +1,5 @@
 use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
-use crate::scene::{SceneGraph, ObjectId, ObjectType};
+use crate::scene::{SceneGraph, ObjectId, ObjectType, Transform};
 use crate::core::Camera;
 use crate::mesh::Vertex;
 
@@ -20,29 +20,215 @@ pub enum GizmoAxis {
     Z,
 }
 
+/// Shift-nudge multiplier for translation steps
+const NUDGE_LARGE_TRANSLATE: f32 = 10.0;
+/// Shift-nudge multiplier for rotation steps (15 degrees -> 45 degrees by default)
+const NUDGE_LARGE_ROTATE: f32 = 3.0;
+
+/// Coordinate space the gizmo axes are aligned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoSpace {
+    /// Axes follow the object's rotation
+    Local,
+    /// Axes are aligned to world X/Y/Z
+    World,
+}
+
+impl GizmoSpace {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GizmoSpace::Local => "Local",
+            GizmoSpace::World => "World",
+        }
+    }
+}
+
 /// Gizmo state and interaction
 pub struct GizmoState {
     pub mode: GizmoMode,
+    pub space: GizmoSpace,
     pub enabled: bool,
     pub using_gizmo: bool,
     pub active_axis: GizmoAxis,
     pub hovered_axis: GizmoAxis,
+    /// Snap drags to fixed increments
+    pub snap_enabled: bool,
+    /// Translation increment in world units
+    pub translate_snap: f32,
+    /// Rotation increment in degrees
+    pub rotate_snap: f32,
+    /// Scale increment per axis
+    pub scale_snap: f32,
+    /// Modifier held (Ctrl) - inverts `snap_enabled` while pressed
+    pub snap_toggle_held: bool,
+    /// Per-axis edit locks (X, Y, Z): the locked position, rotation (pitch/yaw/roll)
+    /// and scale components are kept by gizmo drags, nudges and the transform editor
+    pub axis_locks: [bool; 3],
+    /// Object transform when the current drag started
+    drag_start: Transform,
+    /// Unsnapped transform accumulated over the current drag
+    drag_raw: Transform,
 }
 
 impl GizmoState {
     pub fn new() -> Self {
         Self {
             mode: GizmoMode::Translate,
+            space: GizmoSpace::World,
             enabled: true,
             using_gizmo: false,
             active_axis: GizmoAxis::None,
             hovered_axis: GizmoAxis::None,
+            snap_enabled: false,
+            translate_snap: 0.5,
+            rotate_snap: 15.0,
+            scale_snap: 0.1,
+            snap_toggle_held: false,
+            axis_locks: [false; 3],
+            drag_start: Transform::identity(),
+            drag_raw: Transform::identity(),
         }
     }
 
-    pub fn start_drag(&mut self, axis: GizmoAxis) {
+    pub fn start_drag(&mut self, axis: GizmoAxis, transform: Transform) {
         self.active_axis = axis;
         self.using_gizmo = true;
+        self.drag_start = transform;
+        self.drag_raw = transform;
+    }
+
+    /// Orientation of the gizmo axes for an object with the given rotation.
+    /// Scale always uses local axes since scale is stored per local axis.
+    pub fn axis_orientation(&self, object_rotation: Quat) -> Quat {
+        if self.space == GizmoSpace::Local || self.mode == GizmoMode::Scale {
+            object_rotation
+        } else {
+            Quat::IDENTITY
+        }
+    }
+
+    /// Whether snapping applies right now (Ctrl inverts the toggle)
+    pub fn snapping_active(&self) -> bool {
+        self.snap_enabled != self.snap_toggle_held
+    }
+
+    /// Transform at the moment the current drag started
+    pub fn drag_start(&self) -> Transform {
+        self.drag_start
+    }
+
+    /// Unsnapped transform of the current drag, used as input for the next drag step
+    pub fn drag_raw(&self) -> Transform {
+        self.drag_raw
+    }
+
+    /// Whether edits of the `axis` component are locked
+    pub fn is_axis_locked(&self, axis: GizmoAxis) -> bool {
+        match axis {
+            GizmoAxis::X => self.axis_locks[0],
+            GizmoAxis::Y => self.axis_locks[1],
+            GizmoAxis::Z => self.axis_locks[2],
+            GizmoAxis::None => false,
+        }
+    }
+
+    /// Locked axes as a bitmask for the gizmo shader (bit 0 = X, 1 = Y, 2 = Z)
+    pub fn locked_axes_mask(&self) -> i32 {
+        self.axis_locks
+            .iter()
+            .enumerate()
+            .filter(|(_, locked)| **locked)
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// `edited` with its locked components put back to those of `original`
+    pub fn constrain(&self, original: Transform, edited: Transform) -> Transform {
+        if !self.axis_locks.contains(&true) {
+            return edited;
+        }
+        let locks = glam::BVec3::from(self.axis_locks);
+
+        let mut constrained = edited;
+        constrained.position = Vec3::select(locks, original.position, edited.position);
+        constrained.scale = Vec3::select(locks, original.scale, edited.scale);
+        if edited.rotation != original.rotation {
+            let (pitch, yaw, roll) = edited.euler_angles();
+            let (orig_pitch, orig_yaw, orig_roll) = original.euler_angles();
+            constrained.set_euler_rotation(
+                if locks.x { orig_pitch } else { pitch },
+                if locks.y { orig_yaw } else { yaw },
+                if locks.z { orig_roll } else { roll },
+            );
+        }
+        constrained
+    }
+
+    /// Store the new unsnapped drag transform and return the transform to apply to the object.
+    /// Snapping is measured from the drag start so small movements don't jump a full increment.
+    /// Locked axes keep their drag start values.
+    pub fn update_drag(&mut self, raw: Transform) -> Transform {
+        self.drag_raw = raw;
+
+        let snapped = if self.snapping_active() { self.snap_drag(raw) } else { raw };
+        self.constrain(self.drag_start, snapped)
+    }
+
+    /// Snap the current drag to the increments, measured from the drag start
+    fn snap_drag(&self, raw: Transform) -> Transform {
+        let start = self.drag_start;
+        let local_axis = match self.active_axis {
+            GizmoAxis::X => Vec3::X,
+            GizmoAxis::Y => Vec3::Y,
+            GizmoAxis::Z => Vec3::Z,
+            GizmoAxis::None => return raw,
+        };
+        let axis = self.axis_orientation(start.rotation) * local_axis;
+
+        let mut snapped = raw;
+        match self.mode {
+            GizmoMode::Translate => {
+                // Translation only moves along the drag axis, so snap the distance along it
+                let distance = (raw.position - start.position).dot(axis);
+                snapped.position = start.position + axis * snap_value(0.0, distance, self.translate_snap);
+            }
+            GizmoMode::Rotate => {
+                let angle = rotation_angle_about(raw.rotation * start.rotation.inverse(), axis);
+                let snapped_angle = snap_value(0.0, angle, self.rotate_snap.to_radians());
+                snapped.rotation = (Quat::from_axis_angle(axis, snapped_angle) * start.rotation).normalize();
+            }
+            GizmoMode::Scale => {
+                snapped.scale = snap_vec3(start.scale, raw.scale, self.scale_snap);
+            }
+        }
+        snapped
+    }
+
+    /// Keyboard nudge: move along (or rotate about) a gizmo axis by one snap increment.
+    /// `sign` picks the direction, `large` uses a bigger step. Scale mode doesn't nudge.
+    /// Locked axes are left unchanged.
+    pub fn nudge(&self, transform: Transform, axis: GizmoAxis, sign: f32, large: bool) -> Transform {
+        let local_axis = match axis {
+            GizmoAxis::X => Vec3::X,
+            GizmoAxis::Y => Vec3::Y,
+            GizmoAxis::Z => Vec3::Z,
+            GizmoAxis::None => return transform,
+        };
+        let axis = self.axis_orientation(transform.rotation) * local_axis;
+
+        let mut nudged = transform;
+        match self.mode {
+            GizmoMode::Translate => {
+                let step = if large { self.translate_snap * NUDGE_LARGE_TRANSLATE } else { self.translate_snap };
+                nudged.position += axis * step * sign;
+            }
+            GizmoMode::Rotate => {
+                let step = if large { self.rotate_snap * NUDGE_LARGE_ROTATE } else { self.rotate_snap };
+                let rotation = Quat::from_axis_angle(axis, (step * sign).to_radians());
+                nudged.rotation = (rotation * transform.rotation).normalize();
+            }
+            GizmoMode::Scale => {}
+        }
+        self.constrain(transform, nudged)
     }
 
     pub fn end_drag(&mut self) {
@@ -50,7 +236,9 @@ impl GizmoState {
         self.using_gizmo = false;
     }
 
-    /// Check which gizmo arrow/circle/handle is being hovered/clicked
+    /// Check which gizmo arrow/circle/handle is being hovered/clicked.
+    /// `gizmo_scale` must match rendering (`GizmoConfig::scale_at`)
+    #[allow(clippy::too_many_arguments)]
     pub fn pick_axis(
         &mut self,
         mouse_x: f32,
@@ -59,27 +247,29 @@ impl GizmoState {
         viewport_height: f32,
         object_pos: Vec3,
         object_rotation: Quat,
+        gizmo_scale: f32,
         camera: &Camera,
     ) -> GizmoAxis {
         let view = camera.view_matrix();
         let proj = camera.projection_matrix(viewport_width / viewport_height);
         let ray = Ray::from_screen(mouse_x, mouse_y, viewport_width, viewport_height, view, proj);
 
-        // Calculate gizmo scale based on distance from camera (same as rendering)
-        let distance = (object_pos - camera.position()).length();
-        let gizmo_scale = distance * 0.15;
-
         let mut closest_axis = GizmoAxis::None;
         let mut closest_dist = f32::MAX;
 
+        // Axes in the active gizmo space
+        let orientation = self.axis_orientation(object_rotation);
+        let x_axis = orientation * Vec3::X;
+        let y_axis = orientation * Vec3::Y;
+        let z_axis = orientation * Vec3::Z;
+
         match self.mode {
             GizmoMode::Translate => {
-                // Translate uses world-space axes
                 let arrow_length = 1.0 * gizmo_scale;
                 let pick_radius = 0.15 * gizmo_scale;
 
                 // Check X axis (Red)
-                let x_end = object_pos + Vec3::X * arrow_length;
+                let x_end = object_pos + x_axis * arrow_length;
                 if let Some(dist) = ray.intersects_cylinder(object_pos, x_end, pick_radius) {
                     if dist < closest_dist {
                         closest_dist = dist;
@@ -88,7 +278,7 @@ impl GizmoState {
                 }
 
                 // Check Y axis (Green)
-                let y_end = object_pos + Vec3::Y * arrow_length;
+                let y_end = object_pos + y_axis * arrow_length;
                 if let Some(dist) = ray.intersects_cylinder(object_pos, y_end, pick_radius) {
                     if dist < closest_dist {
                         closest_dist = dist;
@@ -97,7 +287,7 @@ impl GizmoState {
                 }
 
                 // Check Z axis (Blue)
-                let z_end = object_pos + Vec3::Z * arrow_length;
+                let z_end = object_pos + z_axis * arrow_length;
                 if let Some(dist) = ray.intersects_cylinder(object_pos, z_end, pick_radius) {
                     if dist < closest_dist {
                         closest_axis = GizmoAxis::Z;
@@ -105,15 +295,9 @@ impl GizmoState {
                 }
             }
             GizmoMode::Rotate | GizmoMode::Scale => {
-                // Rotate and Scale use object-space axes (rotated with object)
                 let length = 1.0 * gizmo_scale;
                 let pick_tolerance = 0.15 * gizmo_scale;
 
-                // Transform axes by object rotation
-                let x_axis = object_rotation * Vec3::X;
-                let y_axis = object_rotation * Vec3::Y;
-                let z_axis = object_rotation * Vec3::Z;
-
                 if self.mode == GizmoMode::Rotate {
                     // Circle picking for rotation
                     if let Some(dist) = ray.intersects_circle(object_pos, x_axis, length, pick_tolerance) {
@@ -177,6 +361,7 @@ impl GizmoState {
         viewport_width: f32,
         viewport_height: f32,
         object_pos: Vec3,
+        object_rotation: Quat,
         camera: &Camera,
     ) -> Vec3 {
         if self.active_axis == GizmoAxis::None {
@@ -190,13 +375,14 @@ impl GizmoState {
         let old_ray = Ray::from_screen(old_mouse.0, old_mouse.1, viewport_width, viewport_height, view, proj);
         let new_ray = Ray::from_screen(new_mouse.0, new_mouse.1, viewport_width, viewport_height, view, proj);
 
-        // Get axis direction in world space
-        let axis_dir = match self.active_axis {
+        // Get axis direction in the active gizmo space
+        let local_axis = match self.active_axis {
             GizmoAxis::X => Vec3::X,
             GizmoAxis::Y => Vec3::Y,
             GizmoAxis::Z => Vec3::Z,
             GizmoAxis::None => return object_pos,
         };
+        let axis_dir = self.axis_orientation(object_rotation) * local_axis;
 
         // Project ray movement onto axis
         let old_point = old_ray.project_onto_axis(object_pos, axis_dir);
@@ -227,14 +413,14 @@ impl GizmoState {
         let old_ray = Ray::from_screen(old_mouse.0, old_mouse.1, viewport_width, viewport_height, view, proj);
         let new_ray = Ray::from_screen(new_mouse.0, new_mouse.1, viewport_width, viewport_height, view, proj);
 
-        // Get rotation axis in object space (transformed by object rotation)
+        // Get rotation axis in the active gizmo space
         let local_axis = match self.active_axis {
             GizmoAxis::X => Vec3::X,
             GizmoAxis::Y => Vec3::Y,
             GizmoAxis::Z => Vec3::Z,
             GizmoAxis::None => return object_rotation,
         };
-        let rotation_axis = object_rotation * local_axis;
+        let rotation_axis = self.axis_orientation(object_rotation) * local_axis;
 
         // Project rays onto the rotation plane
         let old_point = old_ray.project_onto_plane(object_pos, rotation_axis);
@@ -318,6 +504,35 @@ impl Default for GizmoState {
     }
 }
 
+/// Round `value` to the nearest multiple of `step` away from `start`
+fn snap_value(start: f32, value: f32, step: f32) -> f32 {
+    if step <= 0.0 {
+        return value;
+    }
+    start + ((value - start) / step).round() * step
+}
+
+fn snap_vec3(start: Vec3, value: Vec3, step: f32) -> Vec3 {
+    Vec3::new(
+        snap_value(start.x, value.x, step),
+        snap_value(start.y, value.y, step),
+        snap_value(start.z, value.z, step),
+    )
+}
+
+/// Signed angle (radians, -PI..PI) of `rotation` around `axis`
+fn rotation_angle_about(rotation: Quat, axis: Vec3) -> f32 {
+    let (rot_axis, angle) = rotation.to_axis_angle();
+    let signed = if rot_axis.dot(axis) < 0.0 { -angle } else { angle };
+    if signed > std::f32::consts::PI {
+        signed - std::f32::consts::TAU
+    } else if signed < -std::f32::consts::PI {
+        signed + std::f32::consts::TAU
+    } else {
+        signed
+    }
+}
+
 /// Gizmo mesh generator
 pub struct GizmoMesh;
 
@@ -432,6 +647,8 @@ impl GizmoMesh {
                     position: center + tube_offset,
                     normal,
                     uv: Vec2::ZERO,
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
                 });
             }
         }
@@ -488,11 +705,15 @@ impl GizmoMesh {
                 position: start + offset,
                 normal: direction,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
             vertices.push(Vertex {
                 position: line_end + offset,
                 normal: direction,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
         }
 
@@ -529,6 +750,8 @@ impl GizmoMesh {
                 position: *pos,
                 normal: direction,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
         }
 
@@ -590,6 +813,8 @@ impl GizmoMesh {
                 position: start + offset,
                 normal: direction,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
 
             // Top ring
@@ -597,6 +822,8 @@ impl GizmoMesh {
                 position: shaft_end + offset,
                 normal: direction,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
 
             // Triangle 1 of quad
@@ -622,6 +849,8 @@ impl GizmoMesh {
                 position: shaft_end + offset,
                 normal: direction,
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
         }
 
@@ -630,6 +859,8 @@ impl GizmoMesh {
             position: arrow_end,
             normal: direction,
             uv: Vec2::ZERO,
+            tangent: Vec3::ZERO,
+            color: Vec3::ONE,
         });
 
         let tip_idx = vertices.len() as u32 - 1;
@@ -664,16 +895,26 @@ impl Ray {
         let ndc_x = (2.0 * mouse_x) / viewport_width - 1.0;
         let ndc_y = (2.0 * mouse_y) / viewport_height - 1.0; // Changed: removed the flip
 
+        let inv_proj = proj_matrix.inverse();
+        let inv_view = view_matrix.inverse();
+
+        // Orthographic projections have no perspective divide (w row is 0,0,0,1):
+        // rays are parallel to the view axis and start on the view plane under the cursor
+        if proj_matrix.z_axis.w == 0.0 {
+            let view_point = inv_proj * Vec4::new(ndc_x, ndc_y, 0.0, 1.0);
+            let origin = (inv_view * Vec4::new(view_point.x, view_point.y, 0.0, 1.0)).truncate();
+            let direction = (inv_view * Vec4::new(0.0, 0.0, -1.0, 0.0)).truncate().normalize();
+            return Self { origin, direction };
+        }
+
         // Create ray in clip space
         let ray_clip = Vec4::new(ndc_x, ndc_y, -1.0, 1.0);
 
         // Transform to view space
-        let inv_proj = proj_matrix.inverse();
         let ray_view = inv_proj * ray_clip;
         let ray_view = Vec4::new(ray_view.x, ray_view.y, -1.0, 0.0);
 
         // Transform to world space
-        let inv_view = view_matrix.inverse();
         let ray_world = inv_view * ray_view;
 
         let direction = Vec3::new(ray_world.x, ray_world.y, ray_world.z).normalize();
@@ -684,6 +925,11 @@ impl Ray {
 
     /// Test intersection with axis-aligned bounding box
     pub fn intersects_aabb(&self, min: Vec3, max: Vec3) -> bool {
+        self.aabb_distance(min, max).is_some()
+    }
+
+    /// Distance along the ray to where it enters an axis-aligned box (0 if it starts inside)
+    pub fn aabb_distance(&self, min: Vec3, max: Vec3) -> Option<f32> {
         let inv_dir = Vec3::new(
             1.0 / self.direction.x,
             1.0 / self.direction.y,
@@ -700,7 +946,7 @@ impl Ray {
         let tmin = t1.min(t2).max(t3.min(t4)).max(t5.min(t6));
         let tmax = t1.max(t2).min(t3.max(t4)).min(t5.max(t6));
 
-        tmax >= tmin && tmax >= 0.0
+        (tmax >= tmin && tmax >= 0.0).then(|| tmin.max(0.0))
     }
 
     /// Test intersection with sphere
@@ -862,14 +1108,16 @@ impl ObjectPicker {
 
         let mut closest_object: Option<(ObjectId, f32)> = None;
 
-        // Check all objects (skip nebula, skybox, and sphere/star - they're not selectable in viewport)
+        // Check all objects (skip nebula, skybox, and sphere/star - they're not selectable in viewport,
+        // nor objects on hidden or locked layers)
         for obj in scene.objects().values() {
-            if !obj.visible || obj.object_type == ObjectType::Nebula || obj.object_type == ObjectType::Skybox || obj.object_type == ObjectType::Sphere {
+            if !scene.is_selectable(obj) || obj.object_type == ObjectType::Nebula || obj.object_type == ObjectType::Skybox || obj.object_type == ObjectType::Sphere {
                 continue;
             }
 
-            let pos = obj.transform.position;
-            let scale = obj.transform.scale;
+            let world = scene.world_transform(obj.id);
+            let pos = world.position;
+            let scale = world.scale;
 
             // Use a larger bounding sphere for easier picking
             // Radius is the max of the scale components * 1.5 for easier selection
@@ -896,3 +1144,95 @@ impl Default for ObjectPicker {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snap_is_relative_to_drag_start() {
+        // Small movement from an off-grid start stays put
+        assert!((snap_value(0.3, 0.4, 0.5) - 0.3).abs() < 1e-6);
+        assert!((snap_value(0.3, 0.6, 0.5) - 0.8).abs() < 1e-6);
+        assert!((snap_value(0.3, -0.5, 0.5) + 0.7).abs() < 1e-6);
+        // Zero step disables snapping
+        assert_eq!(snap_value(0.3, 0.42, 0.0), 0.42);
+    }
+
+    #[test]
+    fn rotate_snap_rounds_to_increment() {
+        let mut gizmo = GizmoState::new();
+        gizmo.mode = GizmoMode::Rotate;
+        gizmo.snap_enabled = true;
+        gizmo.start_drag(GizmoAxis::Y, Transform::identity());
+
+        let mut raw = Transform::identity();
+        raw.rotation = Quat::from_rotation_y(20f32.to_radians());
+        let snapped = gizmo.update_drag(raw);
+        let angle = rotation_angle_about(snapped.rotation, Vec3::Y);
+        assert!((angle - 15f32.to_radians()).abs() < 1e-4);
+
+        raw.rotation = Quat::from_rotation_y(-5f32.to_radians());
+        let snapped = gizmo.update_drag(raw);
+        assert!(rotation_angle_about(snapped.rotation, Vec3::Y).abs() < 1e-4);
+    }
+
+    #[test]
+    fn nudge_steps_by_snap_increment() {
+        let mut gizmo = GizmoState::new();
+        let start = Transform::new(Vec3::new(1.0, 0.0, 0.0), Quat::from_rotation_y(90f32.to_radians()), Vec3::ONE);
+
+        let moved = gizmo.nudge(start, GizmoAxis::X, -1.0, false);
+        assert!((moved.position - Vec3::new(0.5, 0.0, 0.0)).length() < 1e-5);
+        let moved = gizmo.nudge(start, GizmoAxis::Y, 1.0, true);
+        assert!((moved.position - Vec3::new(1.0, 5.0, 0.0)).length() < 1e-5);
+
+        // Local space follows the object's axes (+X points down -Z after a 90 degree yaw)
+        gizmo.space = GizmoSpace::Local;
+        let moved = gizmo.nudge(start, GizmoAxis::X, 1.0, false);
+        assert!((moved.position - Vec3::new(1.0, 0.0, -0.5)).length() < 1e-5);
+
+        gizmo.mode = GizmoMode::Rotate;
+        gizmo.space = GizmoSpace::World;
+        let rotated = gizmo.nudge(Transform::identity(), GizmoAxis::Y, 1.0, false);
+        assert!((rotation_angle_about(rotated.rotation, Vec3::Y) - 15f32.to_radians()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn locked_axes_keep_their_components() {
+        let mut gizmo = GizmoState::new();
+        gizmo.axis_locks = [true, false, false];
+        let start = Transform::new(Vec3::new(1.0, 2.0, 3.0), Quat::IDENTITY, Vec3::ONE);
+
+        // Local-space drags can move several world components; only the locked one is held
+        gizmo.start_drag(GizmoAxis::X, start);
+        let mut raw = start;
+        raw.position = Vec3::new(4.0, 5.0, 3.0);
+        raw.scale = Vec3::new(2.0, 2.0, 2.0);
+        let dragged = gizmo.update_drag(raw);
+        assert_eq!(dragged.position, Vec3::new(1.0, 5.0, 3.0));
+        assert_eq!(dragged.scale, Vec3::new(1.0, 2.0, 2.0));
+
+        // Nudging along a locked axis does nothing
+        let nudged = gizmo.nudge(start, GizmoAxis::X, 1.0, false);
+        assert_eq!(nudged.position, start.position);
+
+        // Rotation keeps the locked euler angle (pitch is rotation about X)
+        let mut rotated = start;
+        rotated.set_euler_rotation(0.3, 0.5, 0.0);
+        let (pitch, yaw, _) = gizmo.constrain(start, rotated).euler_angles();
+        assert!(pitch.abs() < 1e-5);
+        assert!((yaw - 0.5).abs() < 1e-5);
+        assert_eq!(gizmo.locked_axes_mask(), 1);
+    }
+
+    #[test]
+    fn ctrl_inverts_snap_toggle() {
+        let mut gizmo = GizmoState::new();
+        assert!(!gizmo.snapping_active());
+        gizmo.snap_toggle_held = true;
+        assert!(gizmo.snapping_active());
+        gizmo.snap_enabled = true;
+        assert!(!gizmo.snapping_active());
+    }
+}
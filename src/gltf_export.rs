@@ -0,0 +1,564 @@
+//! Binary glTF (`.glb`) export of a saved scene
+//!
+//! Mesh objects become nodes with embedded geometry and metallic-roughness materials,
+//! lights become `KHR_lights_punctual` lights. Engine-only objects (star, nebula,
+//! skybox, SSAO, game manager) have no glTF equivalent and are left out.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use glam::{Mat4, Quat, Vec3};
+use serde_json::{json, Value};
+
+use crate::material::MaterialProperties;
+use crate::mesh::Mesh;
+use crate::scene::{ObjectId, ObjectType, SceneData, SceneObject};
+
+/// Where the editor writes scene exports
+pub const EXPORT_PATH: &str = "exports/scene.glb";
+
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F534A;
+const CHUNK_BIN: u32 = 0x004E4942;
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const COMPONENT_FLOAT: u32 = 5126;
+const COMPONENT_UNSIGNED_INT: u32 = 5125;
+
+/// Hologram tint the unlit pass draws with
+const UNLIT_COLOR: [f32; 4] = [0.0, 0.8, 1.0, 0.6];
+
+/// What ended up in an exported file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GltfExportSummary {
+    pub meshes: usize,
+    pub lights: usize,
+    pub skipped: usize,
+}
+
+/// Whether an object is written to the export
+fn is_exported(obj: &SceneObject) -> bool {
+    obj.visible
+        && !obj.editor_only
+        && matches!(
+            obj.object_type,
+            ObjectType::Cube
+                | ObjectType::Primitive(_)
+                | ObjectType::Mesh(_)
+                | ObjectType::Unlit(_)
+                | ObjectType::PointLight
+                | ObjectType::DirectionalLight
+        )
+}
+
+/// Geometry shared by every object drawing the same mesh
+fn geometry_key(object_type: &ObjectType) -> Option<String> {
+    match object_type {
+        ObjectType::Cube => Some("Cube".to_string()),
+        ObjectType::Primitive(shape) => Some(shape.name().to_string()),
+        ObjectType::Mesh(path) | ObjectType::Unlit(path) => Some(path.clone()),
+        _ => None,
+    }
+}
+
+fn load_geometry(object_type: &ObjectType) -> anyhow::Result<Mesh> {
+    match object_type {
+        ObjectType::Cube => Ok(Mesh::create_cube()),
+        ObjectType::Primitive(shape) => Ok(Mesh::create_primitive(*shape)),
+        ObjectType::Mesh(path) | ObjectType::Unlit(path) => {
+            let extension = Path::new(path)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            match extension.as_str() {
+                "obj" => Mesh::from_obj(path),
+                "gltf" | "glb" => Mesh::from_gltf(path),
+                _ => anyhow::bail!("unsupported mesh format '{}'", path),
+            }
+        }
+        _ => anyhow::bail!("{:?} has no mesh", object_type),
+    }
+}
+
+/// Accumulates the JSON document and the binary chunk
+#[derive(Default)]
+struct GlbBuilder {
+    bin: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    images: Vec<Value>,
+    textures: Vec<Value>,
+    materials: Vec<Value>,
+    meshes: Vec<Value>,
+    nodes: Vec<Value>,
+    lights: Vec<Value>,
+    extensions_used: Vec<&'static str>,
+    geometry_cache: HashMap<String, Option<Value>>,
+    mesh_cache: HashMap<(String, usize), usize>,
+    material_cache: HashMap<(Option<String>, bool), usize>,
+    texture_cache: HashMap<String, Option<usize>>,
+}
+
+impl GlbBuilder {
+    fn use_extension(&mut self, name: &'static str) {
+        if !self.extensions_used.contains(&name) {
+            self.extensions_used.push(name);
+        }
+    }
+
+    /// Append bytes as a 4-byte aligned buffer view
+    fn push_view(&mut self, bytes: &[u8], target: Option<u32>) -> usize {
+        while !self.bin.len().is_multiple_of(4) {
+            self.bin.push(0);
+        }
+        let mut view = json!({
+            "buffer": 0,
+            "byteOffset": self.bin.len(),
+            "byteLength": bytes.len(),
+        });
+        if let Some(target) = target {
+            view["target"] = json!(target);
+        }
+        self.bin.extend_from_slice(bytes);
+        self.buffer_views.push(view);
+        self.buffer_views.len() - 1
+    }
+
+    fn push_accessor(&mut self, bytes: &[u8], component_type: u32, count: usize, kind: &str, target: u32) -> usize {
+        let view = self.push_view(bytes, Some(target));
+        self.accessors.push(json!({
+            "bufferView": view,
+            "componentType": component_type,
+            "count": count,
+            "type": kind,
+        }));
+        self.accessors.len() - 1
+    }
+
+    /// Mesh primitive attributes and indices for an object's geometry (cached per mesh)
+    fn geometry(&mut self, object_type: &ObjectType) -> Option<Value> {
+        let key = geometry_key(object_type)?;
+        if let Some(cached) = self.geometry_cache.get(&key) {
+            return cached.clone();
+        }
+
+        let primitive = match load_geometry(object_type) {
+            Ok(mesh) if !mesh.indices.is_empty() => {
+                let positions: Vec<Vec3> = mesh.vertices.iter().map(|vertex| vertex.position).collect();
+                let normals: Vec<Vec3> = mesh.vertices.iter().map(|vertex| vertex.normal).collect();
+                let uvs: Vec<glam::Vec2> = mesh.vertices.iter().map(|vertex| vertex.uv).collect();
+                let count = mesh.vertices.len();
+                let (min, max) = mesh.calculate_bounds();
+
+                let position = self.push_accessor(bytemuck::cast_slice(&positions), COMPONENT_FLOAT, count, "VEC3", ARRAY_BUFFER);
+                // glTF requires POSITION bounds
+                self.accessors[position]["min"] = json!(min.to_array());
+                self.accessors[position]["max"] = json!(max.to_array());
+                let normal = self.push_accessor(bytemuck::cast_slice(&normals), COMPONENT_FLOAT, count, "VEC3", ARRAY_BUFFER);
+                let uv = self.push_accessor(bytemuck::cast_slice(&uvs), COMPONENT_FLOAT, count, "VEC2", ARRAY_BUFFER);
+                let indices = self.push_accessor(
+                    bytemuck::cast_slice(&mesh.indices),
+                    COMPONENT_UNSIGNED_INT,
+                    mesh.indices.len(),
+                    "SCALAR",
+                    ELEMENT_ARRAY_BUFFER,
+                );
+
+                let mut attributes = json!({ "POSITION": position, "NORMAL": normal, "TEXCOORD_0": uv });
+                // Only meshes that actually carry vertex colors (all white otherwise)
+                if mesh.vertices.iter().any(|vertex| vertex.color != Vec3::ONE) {
+                    let colors: Vec<Vec3> = mesh.vertices.iter().map(|vertex| vertex.color).collect();
+                    attributes["COLOR_0"] =
+                        json!(self.push_accessor(bytemuck::cast_slice(&colors), COMPONENT_FLOAT, count, "VEC3", ARRAY_BUFFER));
+                }
+
+                Some(json!({
+                    "attributes": attributes,
+                    "indices": indices,
+                }))
+            }
+            Ok(_) => {
+                crate::console::warn(format!("glTF export: '{}' has no triangles, skipping", key));
+                None
+            }
+            Err(e) => {
+                crate::console::warn(format!("glTF export: failed to load '{}': {}", key, e));
+                None
+            }
+        };
+
+        self.geometry_cache.insert(key, primitive.clone());
+        primitive
+    }
+
+    /// Embed an image file as a texture (PNG/JPEG as-is, other formats re-encoded to PNG)
+    fn texture(&mut self, path: &str) -> Option<usize> {
+        if let Some(&cached) = self.texture_cache.get(path) {
+            return cached;
+        }
+
+        let extension = Path::new(path)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let encoded = match extension.as_str() {
+            "png" => std::fs::read(path).map(|bytes| (bytes, "image/png")).map_err(anyhow::Error::from),
+            "jpg" | "jpeg" => std::fs::read(path).map(|bytes| (bytes, "image/jpeg")).map_err(anyhow::Error::from),
+            _ => image::open(path).map_err(anyhow::Error::from).and_then(|image| {
+                let mut bytes = std::io::Cursor::new(Vec::new());
+                image.write_to(&mut bytes, image::ImageFormat::Png)?;
+                Ok((bytes.into_inner(), "image/png"))
+            }),
+        };
+
+        let texture = match encoded {
+            Ok((bytes, mime_type)) => {
+                let view = self.push_view(&bytes, None);
+                self.images.push(json!({ "bufferView": view, "mimeType": mime_type }));
+                self.textures.push(json!({ "source": self.images.len() - 1 }));
+                Some(self.textures.len() - 1)
+            }
+            Err(e) => {
+                crate::console::warn(format!("glTF export: failed to embed texture '{}': {}", path, e));
+                None
+            }
+        };
+
+        self.texture_cache.insert(path.to_string(), texture);
+        texture
+    }
+
+    fn material(&mut self, name: &Option<String>, properties: &MaterialProperties, unlit: bool) -> usize {
+        let key = (name.clone(), unlit);
+        if let Some(&index) = self.material_cache.get(&key) {
+            return index;
+        }
+
+        let material = if unlit {
+            self.use_extension("KHR_materials_unlit");
+            json!({
+                "name": "Unlit",
+                "pbrMetallicRoughness": {
+                    "baseColorFactor": UNLIT_COLOR,
+                    "metallicFactor": 0.0,
+                    "roughnessFactor": 1.0,
+                },
+                "alphaMode": "BLEND",
+                "doubleSided": true,
+                "extensions": { "KHR_materials_unlit": {} },
+            })
+        } else {
+            let alpha = if properties.transparent { properties.opacity } else { 1.0 };
+            let mut pbr = json!({
+                "baseColorFactor": [properties.albedo.x, properties.albedo.y, properties.albedo.z, alpha],
+                "metallicFactor": properties.metallic,
+                "roughnessFactor": properties.roughness,
+            });
+            if let Some(index) = properties.albedo_texture.as_deref().and_then(|path| self.texture(path)) {
+                pbr["baseColorTexture"] = json!({ "index": index });
+            }
+            if let Some(index) = properties.metallic_roughness_texture.as_deref().and_then(|path| self.texture(path)) {
+                pbr["metallicRoughnessTexture"] = json!({ "index": index });
+            }
+
+            let mut material = json!({
+                "name": name.as_deref().unwrap_or("Default"),
+                "pbrMetallicRoughness": pbr,
+            });
+            if let Some(index) = properties.normal_texture.as_deref().and_then(|path| self.texture(path)) {
+                material["normalTexture"] = json!({ "index": index });
+            }
+            if properties.transparent {
+                material["alphaMode"] = json!("BLEND");
+            }
+
+            // glTF clamps emissiveFactor to 1, anything brighter goes through the strength extension
+            if properties.emissive_strength > 0.0 && properties.emissive != Vec3::ZERO {
+                let strength = properties.emissive_strength;
+                let factor = properties.emissive * strength.min(1.0);
+                material["emissiveFactor"] = json!(factor.to_array());
+                if strength > 1.0 {
+                    self.use_extension("KHR_materials_emissive_strength");
+                    material["extensions"] = json!({ "KHR_materials_emissive_strength": { "emissiveStrength": strength } });
+                }
+            }
+            material
+        };
+
+        self.materials.push(material);
+        let index = self.materials.len() - 1;
+        self.material_cache.insert(key, index);
+        index
+    }
+
+    /// glTF mesh for a geometry/material pair
+    fn mesh(&mut self, obj: &SceneObject, properties: &MaterialProperties) -> Option<usize> {
+        let key = geometry_key(&obj.object_type)?;
+        let mut primitive = self.geometry(&obj.object_type)?;
+
+        let unlit = matches!(obj.object_type, ObjectType::Unlit(_));
+        let material = self.material(&obj.material, properties, unlit);
+        if let Some(&index) = self.mesh_cache.get(&(key.clone(), material)) {
+            return Some(index);
+        }
+
+        primitive["material"] = json!(material);
+        self.meshes.push(json!({ "name": key, "primitives": [primitive] }));
+        let index = self.meshes.len() - 1;
+        self.mesh_cache.insert((key, material), index);
+        Some(index)
+    }
+
+    fn push_light(&mut self, light: Value) -> usize {
+        self.use_extension("KHR_lights_punctual");
+        self.lights.push(light);
+        self.lights.len() - 1
+    }
+
+    /// Assemble the GLB file: header, JSON chunk (space padded), BIN chunk (zero padded)
+    fn finish(mut self, roots: Vec<usize>) -> anyhow::Result<Vec<u8>> {
+        while !self.bin.len().is_multiple_of(4) {
+            self.bin.push(0);
+        }
+
+        let mut document = json!({
+            "asset": { "version": "2.0", "generator": "Tribal Engine" },
+            "scene": 0,
+            "scenes": [{ "nodes": roots }],
+            "nodes": self.nodes,
+        });
+        let arrays = [
+            ("meshes", self.meshes),
+            ("materials", self.materials),
+            ("textures", self.textures),
+            ("images", self.images),
+            ("accessors", self.accessors),
+            ("bufferViews", self.buffer_views),
+        ];
+        for (key, values) in arrays {
+            if !values.is_empty() {
+                document[key] = Value::Array(values);
+            }
+        }
+        if !self.bin.is_empty() {
+            document["buffers"] = json!([{ "byteLength": self.bin.len() }]);
+        }
+        if !self.lights.is_empty() {
+            document["extensions"] = json!({ "KHR_lights_punctual": { "lights": self.lights } });
+        }
+        if !self.extensions_used.is_empty() {
+            document["extensionsUsed"] = json!(self.extensions_used);
+        }
+
+        let mut json_chunk = serde_json::to_vec(&document)?;
+        while json_chunk.len() % 4 != 0 {
+            json_chunk.push(b' ');
+        }
+
+        let bin_chunk_size = if self.bin.is_empty() { 0 } else { 8 + self.bin.len() };
+        let total_length = 12 + 8 + json_chunk.len() + bin_chunk_size;
+
+        let mut glb = Vec::with_capacity(total_length);
+        glb.extend_from_slice(GLB_MAGIC);
+        glb.extend_from_slice(&GLB_VERSION.to_le_bytes());
+        glb.extend_from_slice(&(total_length as u32).to_le_bytes());
+        glb.extend_from_slice(&(json_chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&CHUNK_JSON.to_le_bytes());
+        glb.extend_from_slice(&json_chunk);
+        if !self.bin.is_empty() {
+            glb.extend_from_slice(&(self.bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(&CHUNK_BIN.to_le_bytes());
+            glb.extend_from_slice(&self.bin);
+        }
+        Ok(glb)
+    }
+}
+
+fn set_trs(node: &mut Value, matrix: Mat4) {
+    let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+    node["translation"] = json!(translation.to_array());
+    node["rotation"] = json!(rotation.normalize().to_array());
+    node["scale"] = json!(scale.to_array());
+}
+
+fn push_child(node: &mut Value, child: usize) {
+    let children = &mut node["children"];
+    if let Some(children) = children.as_array_mut() {
+        children.push(json!(child));
+    } else {
+        *children = json!([child]);
+    }
+}
+
+/// Build the `.glb` bytes for a scene; `material_for` resolves each mesh object's material
+pub fn build_glb(
+    scene: &SceneData,
+    material_for: impl Fn(&SceneObject) -> MaterialProperties,
+) -> anyhow::Result<(Vec<u8>, GltfExportSummary)> {
+    let by_id: HashMap<ObjectId, &SceneObject> = scene.objects.iter().map(|obj| (obj.id, obj)).collect();
+
+    fn world_matrix<'a>(mut obj: &'a SceneObject, by_id: &HashMap<ObjectId, &'a SceneObject>) -> Mat4 {
+        let mut matrix = obj.transform.model_matrix();
+        while let Some(parent) = obj.parent.and_then(|parent| by_id.get(&parent).copied()) {
+            matrix = parent.transform.model_matrix() * matrix;
+            obj = parent;
+        }
+        matrix
+    }
+
+    let mut builder = GlbBuilder::default();
+    let mut summary = GltfExportSummary::default();
+
+    // One node per exported object, in scene order
+    let mut node_of: HashMap<ObjectId, usize> = HashMap::new();
+    let mut exported: Vec<&SceneObject> = Vec::new();
+    let hidden_layers: Vec<&str> = scene.layers.iter().filter(|layer| !layer.visible).map(|layer| layer.name.as_str()).collect();
+    for obj in &scene.objects {
+        if !is_exported(obj) || hidden_layers.contains(&obj.layer.as_str()) {
+            if !matches!(obj.object_type, ObjectType::SSAO | ObjectType::GameManager) {
+                summary.skipped += 1;
+            }
+            continue;
+        }
+
+        let mut node = json!({ "name": obj.name });
+        match &obj.object_type {
+            ObjectType::PointLight => {
+                let settings = obj.point_light.unwrap_or_default();
+                let light = builder.push_light(json!({
+                    "name": obj.name,
+                    "type": "point",
+                    "color": settings.color.to_array(),
+                    "intensity": settings.intensity,
+                    "range": settings.radius,
+                }));
+                node["extensions"] = json!({ "KHR_lights_punctual": { "light": light } });
+                summary.lights += 1;
+            }
+            ObjectType::DirectionalLight => {
+                // Attached to a child node below, since glTF lights point down -Z
+                summary.lights += 1;
+            }
+            _ => match builder.mesh(obj, &material_for(obj)) {
+                Some(mesh) => {
+                    node["mesh"] = json!(mesh);
+                    summary.meshes += 1;
+                }
+                None => {
+                    summary.skipped += 1;
+                    continue;
+                }
+            },
+        }
+
+        node_of.insert(obj.id, builder.nodes.len());
+        builder.nodes.push(node);
+        exported.push(obj);
+    }
+
+    // Transforms relative to the nearest exported ancestor, so skipped parents still apply
+    let mut roots = Vec::new();
+    for &obj in &exported {
+        let mut ancestor = obj.parent;
+        while let Some(id) = ancestor.filter(|id| !node_of.contains_key(id)) {
+            ancestor = by_id.get(&id).and_then(|parent| parent.parent);
+        }
+
+        let node = node_of[&obj.id];
+        let local = match ancestor {
+            Some(parent) if obj.parent == Some(parent) => obj.transform.model_matrix(),
+            Some(parent) => world_matrix(by_id[&parent], &by_id).inverse() * world_matrix(obj, &by_id),
+            None => world_matrix(obj, &by_id),
+        };
+        set_trs(&mut builder.nodes[node], local);
+
+        match ancestor {
+            Some(parent) => {
+                push_child(&mut builder.nodes[node_of[&parent]], node);
+            }
+            None => roots.push(node),
+        }
+    }
+
+    // Directional lights shine along the object's -Y; glTF lights shine along -Z
+    for obj in exported.iter().filter(|obj| obj.object_type == ObjectType::DirectionalLight) {
+        let settings = obj.directional_light.unwrap_or_default();
+        let light = builder.push_light(json!({
+            "name": obj.name,
+            "type": "directional",
+            "color": settings.color.to_array(),
+            "intensity": settings.intensity,
+        }));
+        let rotation = Quat::from_rotation_arc(Vec3::NEG_Z, Vec3::NEG_Y);
+        builder.nodes.push(json!({
+            "name": format!("{} Light", obj.name),
+            "rotation": rotation.to_array(),
+            "extensions": { "KHR_lights_punctual": { "light": light } },
+        }));
+        let light_node = builder.nodes.len() - 1;
+        push_child(&mut builder.nodes[node_of[&obj.id]], light_node);
+    }
+
+    Ok((builder.finish(roots)?, summary))
+}
+
+/// Write a scene to a `.glb` file
+pub fn export_glb(
+    scene: &SceneData,
+    path: &str,
+    material_for: impl Fn(&SceneObject) -> MaterialProperties,
+) -> anyhow::Result<GltfExportSummary> {
+    let (glb, summary) = build_glb(scene, material_for)?;
+    if let Some(parent) = Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, glb)?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{SceneGraph, Transform};
+
+    fn parse_glb(glb: &[u8]) -> Value {
+        assert_eq!(&glb[0..4], GLB_MAGIC);
+        assert_eq!(u32::from_le_bytes(glb[4..8].try_into().unwrap()), GLB_VERSION);
+        assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+        let json_length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        assert_eq!(u32::from_le_bytes(glb[16..20].try_into().unwrap()), CHUNK_JSON);
+        serde_json::from_slice(&glb[20..20 + json_length]).unwrap()
+    }
+
+    #[test]
+    fn test_export_meshes_and_lights() {
+        let mut scene = SceneGraph::new();
+        let cube = scene.add_object_with_transform("Crate".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(1.0, 2.0, 3.0)));
+        scene.add_object("Sun".to_string(), ObjectType::DirectionalLight);
+        let lamp = scene.add_object("Lamp".to_string(), ObjectType::PointLight);
+        // Parenting keeps the world position, so set the local offset afterwards
+        scene.set_parent(lamp, Some(cube)).unwrap();
+        scene.get_object_mut(lamp).unwrap().transform = Transform::from_position(Vec3::Y);
+        scene.add_object("SSAO".to_string(), ObjectType::SSAO);
+        scene.add_object("Skybox".to_string(), ObjectType::Skybox);
+
+        let data = SceneData::from_scene_graph(&scene);
+        let (glb, summary) = build_glb(&data, |_| MaterialProperties::default()).unwrap();
+        assert_eq!(summary, GltfExportSummary { meshes: 1, lights: 2, skipped: 1 });
+        assert_eq!(glb.len() % 4, 0);
+
+        let document = parse_glb(&glb);
+        assert_eq!(document["extensions"]["KHR_lights_punctual"]["lights"].as_array().unwrap().len(), 2);
+        assert_eq!(document["meshes"].as_array().unwrap().len(), 1);
+
+        // The lamp stays a child of the crate with its local offset
+        let nodes = document["nodes"].as_array().unwrap();
+        let crate_node = nodes.iter().find(|node| node["name"] == "Crate").unwrap();
+        assert_eq!(crate_node["translation"], json!([1.0, 2.0, 3.0]));
+        let lamp_index = nodes.iter().position(|node| node["name"] == "Lamp").unwrap();
+        assert_eq!(crate_node["children"], json!([lamp_index]));
+        assert_eq!(nodes[lamp_index]["translation"], json!([0.0, 1.0, 0.0]));
+    }
+}
@@ -0,0 +1,126 @@
+//! Headless rendering: draw the built-in cube scene without a window and write it to disk.
+//! `tribal-engine --headless <width>x<height> <output.png>` is what the golden-image test
+//! in `tests/headless_render_test.rs` drives.
+
+use glam::Vec3;
+use crate::core::renderer::VulkanRenderer;
+use crate::core::Camera;
+use crate::game::Game;
+use crate::scene::{ObjectType, SceneGraph};
+
+/// Command line flag that selects headless mode
+pub const HEADLESS_FLAG: &str = "--headless";
+
+/// Exit code when no Vulkan device is available, so callers can skip instead of fail
+/// (the automake convention for a skipped test)
+pub const NO_DEVICE_EXIT_CODE: i32 = 77;
+
+/// Where the camera of the cube scene sits, looking at the origin
+const CUBE_CAMERA_POSITION: Vec3 = Vec3::new(3.0, 2.0, 4.0);
+
+/// What to render in headless mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessArgs {
+    pub width: u32,
+    pub height: u32,
+    pub output: String,
+}
+
+impl HeadlessArgs {
+    /// Parse `--headless <width>x<height> <output.png>` from the arguments after the program
+    /// name. None when headless mode wasn't requested
+    pub fn parse(args: &[String]) -> Option<anyhow::Result<Self>> {
+        let position = args.iter().position(|arg| arg == HEADLESS_FLAG)?;
+        Some(Self::parse_values(args.get(position + 1), args.get(position + 2)))
+    }
+
+    fn parse_values(size: Option<&String>, output: Option<&String>) -> anyhow::Result<Self> {
+        let usage = || anyhow::anyhow!("Usage: {} <width>x<height> <output.png>", HEADLESS_FLAG);
+
+        let (Some(size), Some(output)) = (size, output) else {
+            return Err(usage());
+        };
+        let (width, height) = size.split_once('x').ok_or_else(usage)?;
+        let width: u32 = width.parse().map_err(|_| usage())?;
+        let height: u32 = height.parse().map_err(|_| usage())?;
+        if width == 0 || height == 0 {
+            anyhow::bail!("Invalid headless size {}", size);
+        }
+
+        Ok(Self { width, height, output: output.clone() })
+    }
+}
+
+/// A deterministic scene with a single unit cube at the origin: no star, nebula, skybox or
+/// ship, default configs (nothing is loaded from disk) and a fixed camera
+pub fn cube_scene() -> Game {
+    let mut game = Game::new();
+
+    game.scene = SceneGraph::new();
+    game.ecs_world = crate::ecs::EcsWorld::new();
+    game.nebula_entity = None;
+    game.star_entity = None;
+    game.fed_cruiser_entity = None;
+    game.scene.add_object("Cube".to_string(), ObjectType::Cube);
+
+    let forward = -CUBE_CAMERA_POSITION.normalize();
+    game.camera = Camera::new(CUBE_CAMERA_POSITION);
+    game.camera.set_rotation(forward.y.asin(), (-forward.x).atan2(-forward.z), 0.0);
+
+    game
+}
+
+/// Render the cube scene once and save it. Exits with `NO_DEVICE_EXIT_CODE` when this
+/// machine has no Vulkan device, any other failure is returned as an error
+pub fn run(args: &HeadlessArgs) -> anyhow::Result<()> {
+    if !VulkanRenderer::headless_device_available() {
+        eprintln!("No Vulkan device available, skipping headless render");
+        std::process::exit(NO_DEVICE_EXIT_CODE);
+    }
+
+    let mut renderer = VulkanRenderer::new_headless(args.width, args.height)?;
+    let mut game = cube_scene();
+    renderer.save_frame(&args.output, &mut game)?;
+    println!("Saved {}x{} headless frame to {}", args.width, args.height, args.output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parses_size_and_output() {
+        let parsed = HeadlessArgs::parse(&args(&["--headless", "320x240", "out/cube.png"]));
+        assert_eq!(
+            parsed.unwrap().unwrap(),
+            HeadlessArgs { width: 320, height: 240, output: "out/cube.png".to_string() },
+        );
+    }
+
+    #[test]
+    fn test_windowed_runs_are_not_headless() {
+        assert!(HeadlessArgs::parse(&args(&[])).is_none());
+        assert!(HeadlessArgs::parse(&args(&["--verbose"])).is_none());
+    }
+
+    #[test]
+    fn test_malformed_arguments_are_errors() {
+        assert!(HeadlessArgs::parse(&args(&["--headless"])).unwrap().is_err());
+        assert!(HeadlessArgs::parse(&args(&["--headless", "320", "out.png"])).unwrap().is_err());
+        assert!(HeadlessArgs::parse(&args(&["--headless", "0x240", "out.png"])).unwrap().is_err());
+        assert!(HeadlessArgs::parse(&args(&["--headless", "320x240"])).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_cube_scene_camera_looks_at_the_origin() {
+        let game = cube_scene();
+        let forward = game.camera.rotation() * Vec3::NEG_Z;
+        assert!(forward.abs_diff_eq(-CUBE_CAMERA_POSITION.normalize(), 1e-5));
+        assert_eq!(game.scene.objects().len(), 1);
+    }
+}
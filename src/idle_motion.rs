@@ -0,0 +1,97 @@
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::scene::Transform;
+
+/// Continuous rotation about an axis in the object's own space (e.g. a tumbling asteroid)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Spin {
+    pub axis: Vec3,
+    /// Degrees per second (negative spins the other way)
+    pub speed: f32,
+}
+
+impl Default for Spin {
+    fn default() -> Self {
+        Self { axis: Vec3::Y, speed: 15.0 }
+    }
+}
+
+/// Sine-wave offset along an axis in the parent's space (e.g. a floating pickup)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bob {
+    pub axis: Vec3,
+    /// Peak offset from the base position in world units
+    pub amplitude: f32,
+    /// Full cycles per second
+    pub frequency: f32,
+}
+
+impl Default for Bob {
+    fn default() -> Self {
+        Self { axis: Vec3::Y, amplitude: 0.25, frequency: 0.5 }
+    }
+}
+
+/// Layer spin and bob over a base transform at `time` seconds into play mode
+/// The base transform itself is never modified, so nothing needs restoring afterwards.
+/// A zero axis disables that motion.
+pub fn animate(base: Transform, spin: Option<Spin>, bob: Option<Bob>, time: f32) -> Transform {
+    let mut transform = base;
+    if let Some(spin) = spin {
+        let axis = spin.axis.normalize_or_zero();
+        if axis != Vec3::ZERO {
+            let angle = (spin.speed * time).to_radians();
+            transform.rotation = (base.rotation * Quat::from_axis_angle(axis, angle)).normalize();
+        }
+    }
+    if let Some(bob) = bob {
+        let phase = std::f32::consts::TAU * bob.frequency * time;
+        transform.position += bob.axis.normalize_or_zero() * bob.amplitude * phase.sin();
+    }
+    transform
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spin_and_bob_offset_the_base_transform() {
+        let base = Transform::from_position(Vec3::new(1.0, 2.0, 3.0));
+        let spin = Spin { axis: Vec3::Y * 2.0, speed: 90.0 };
+        let bob = Bob { axis: Vec3::Y, amplitude: 0.5, frequency: 0.25 };
+
+        // A quarter of the bob period in: full amplitude, and a 90 degree turn after one second
+        let moved = animate(base, Some(spin), Some(bob), 1.0);
+        assert!(moved.position.abs_diff_eq(Vec3::new(1.0, 2.5, 3.0), 1e-5));
+        assert!(moved.rotation.abs_diff_eq(Quat::from_rotation_y(90f32.to_radians()), 1e-5));
+        assert_eq!(moved.scale, base.scale);
+
+        // At time zero (and with zero axes) the base transform is unchanged
+        assert_eq!(animate(base, Some(spin), Some(bob), 0.0), base);
+        let still = animate(base, Some(Spin { axis: Vec3::ZERO, speed: 90.0 }), Some(Bob { axis: Vec3::ZERO, ..bob }), 1.0);
+        assert_eq!(still, base);
+    }
+
+    #[test]
+    fn test_scene_layers_motion_without_writing_transforms() {
+        use crate::scene::{ObjectType, SceneGraph};
+
+        let mut scene = SceneGraph::new();
+        let pickup = scene.add_object("Pickup".to_string(), ObjectType::Cube);
+        let glow = scene.add_object("Glow".to_string(), ObjectType::Cube);
+        scene.set_parent(glow, Some(pickup)).unwrap();
+        scene.get_object_mut(pickup).unwrap().bob = Some(Bob { axis: Vec3::Y, amplitude: 2.0, frequency: 0.25 });
+
+        // Children ride along with their animated parent
+        scene.set_animation_time(Some(1.0));
+        assert!(scene.world_transform(glow).position.abs_diff_eq(Vec3::new(0.0, 2.0, 0.0), 1e-5));
+        assert_eq!(scene.get_object(pickup).unwrap().transform.position, Vec3::ZERO);
+
+        // Leaving play mode drops the offsets
+        scene.set_animation_time(None);
+        assert_eq!(scene.world_transform(pickup).position, Vec3::ZERO);
+        assert_eq!(scene.world_transform(glow).position, Vec3::ZERO);
+    }
+}
@@ -2,6 +2,12 @@ use ash::vk;
 use imgui::{Context, DrawCmd, DrawCmdParams, DrawData, DrawVert, TextureId};
 use std::mem::size_of;
 
+/// Extra textures (besides the font atlas) that UI code can draw with `ui.image`
+const MAX_USER_TEXTURES: u32 = 4;
+
+/// Texture id of the font atlas; user textures are numbered after it
+const FONT_TEXTURE_ID: usize = 1;
+
 pub struct ImGuiRenderer {
     vertex_buffer: Option<vk::Buffer>,
     vertex_buffer_memory: Option<vk::DeviceMemory>,
@@ -18,12 +24,15 @@ pub struct ImGuiRenderer {
     font_sampler: vk::Sampler,
     descriptor_pool: vk::DescriptorPool,
     descriptor_set: vk::DescriptorSet,
+    // Descriptor sets of registered user textures (texture id = FONT_TEXTURE_ID + 1 + index)
+    user_texture_sets: Vec<vk::DescriptorSet>,
 }
 
 impl ImGuiRenderer {
     pub fn new(
         context: &mut Context,
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         instance: &ash::Instance,
         physical_device: vk::PhysicalDevice,
         render_pass: vk::RenderPass,
@@ -64,12 +73,13 @@ impl ImGuiRenderer {
             // Create pipeline
             let (pipeline_layout, pipeline) = Self::create_pipeline(
                 device,
+                cache,
                 render_pass,
                 descriptor_set_layout,
                 swapchain_extent,
             )?;
 
-            fonts.tex_id = TextureId::from(1);
+            fonts.tex_id = TextureId::from(FONT_TEXTURE_ID);
 
             Ok(Self {
                 vertex_buffer: None,
@@ -87,6 +97,7 @@ impl ImGuiRenderer {
                 font_sampler,
                 descriptor_pool,
                 descriptor_set,
+                user_texture_sets: Vec::new(),
             })
         }
     }
@@ -221,11 +232,11 @@ impl ImGuiRenderer {
     unsafe fn create_descriptor_pool(device: &ash::Device) -> anyhow::Result<vk::DescriptorPool> {
         let pool_size = vk::DescriptorPoolSize::default()
             .ty(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
-            .descriptor_count(1);
+            .descriptor_count(1 + MAX_USER_TEXTURES);
 
         let pool_info = vk::DescriptorPoolCreateInfo::default()
             .pool_sizes(std::slice::from_ref(&pool_size))
-            .max_sets(1);
+            .max_sets(1 + MAX_USER_TEXTURES);
 
         Ok(device.create_descriptor_pool(&pool_info, None)?)
     }
@@ -268,17 +279,18 @@ impl ImGuiRenderer {
 
     unsafe fn create_pipeline(
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         render_pass: vk::RenderPass,
         descriptor_set_layout: vk::DescriptorSetLayout,
         extent: vk::Extent2D,
     ) -> anyhow::Result<(vk::PipelineLayout, vk::Pipeline)> {
         // For now, we'll create a simple placeholder pipeline
         // In a full implementation, you'd need to create proper ImGui shaders
-        let vert_shader_code = include_bytes!("../shaders/imgui.vert.spv");
-        let frag_shader_code = include_bytes!("../shaders/imgui.frag.spv");
+        let vert_shader_code = crate::core::shader::load_spirv("imgui.vert.spv", include_bytes!("../shaders/imgui.vert.spv"));
+        let frag_shader_code = crate::core::shader::load_spirv("imgui.frag.spv", include_bytes!("../shaders/imgui.frag.spv"));
 
-        let vert_module = Self::create_shader_module(device, vert_shader_code)?;
-        let frag_module = Self::create_shader_module(device, frag_shader_code)?;
+        let vert_module = cache.shader_module(device, &vert_shader_code)?;
+        let frag_module = cache.shader_module(device, &frag_shader_code)?;
 
         let entry_name = std::ffi::CString::new("main")?;
 
@@ -394,21 +406,41 @@ impl ImGuiRenderer {
             .subpass(0);
 
         let pipelines = device.create_graphics_pipelines(
-            vk::PipelineCache::null(),
+            cache.handle(),
             std::slice::from_ref(&pipeline_info),
             None,
         ).map_err(|e| anyhow::anyhow!("Failed to create ImGui pipeline: {:?}", e.1))?;
 
-        device.destroy_shader_module(vert_module, None);
-        device.destroy_shader_module(frag_module, None);
-
         Ok((pipeline_layout, pipelines[0]))
     }
 
-    unsafe fn create_shader_module(device: &ash::Device, code: &[u8]) -> anyhow::Result<vk::ShaderModule> {
-        let code_aligned = ash::util::read_spv(&mut std::io::Cursor::new(code))?;
-        let create_info = vk::ShaderModuleCreateInfo::default().code(&code_aligned);
-        Ok(device.create_shader_module(&create_info, None)?)
+    /// Make a sampled image drawable with `ui.image`
+    /// The image must be in SHADER_READ_ONLY_OPTIMAL layout whenever the UI draws it
+    pub unsafe fn register_texture(
+        &mut self,
+        device: &ash::Device,
+        image_view: vk::ImageView,
+        sampler: vk::Sampler,
+    ) -> anyhow::Result<TextureId> {
+        if self.user_texture_sets.len() as u32 >= MAX_USER_TEXTURES {
+            return Err(anyhow::anyhow!("ImGui user texture limit ({}) reached", MAX_USER_TEXTURES));
+        }
+
+        let descriptor_set = Self::allocate_descriptor_set(device, self.descriptor_pool, self.descriptor_set_layout)?;
+        Self::update_descriptor_set(device, descriptor_set, image_view, sampler)?;
+        self.user_texture_sets.push(descriptor_set);
+
+        Ok(TextureId::from(FONT_TEXTURE_ID + self.user_texture_sets.len()))
+    }
+
+    /// Descriptor set for a texture id (unknown ids fall back to the font atlas)
+    fn descriptor_set_for(&self, texture_id: TextureId) -> vk::DescriptorSet {
+        texture_id
+            .id()
+            .checked_sub(FONT_TEXTURE_ID + 1)
+            .and_then(|index| self.user_texture_sets.get(index))
+            .copied()
+            .unwrap_or(self.descriptor_set)
     }
 
     pub unsafe fn render(
@@ -568,19 +600,10 @@ impl ImGuiRenderer {
             std::slice::from_raw_parts(projection.as_ptr() as *const u8, 64),
         );
 
-        // Bind descriptor set
-        device.cmd_bind_descriptor_sets(
-            command_buffer,
-            vk::PipelineBindPoint::GRAPHICS,
-            self.pipeline_layout,
-            0,
-            &[self.descriptor_set],
-            &[],
-        );
-
-        // Render command lists
+        // Render command lists (descriptor set is rebound whenever the texture changes)
         let mut vtx_offset = 0;
         let mut idx_offset = 0;
+        let mut bound_set = vk::DescriptorSet::null();
 
         for draw_list in draw_data.draw_lists() {
             for cmd in draw_list.commands() {
@@ -599,6 +622,20 @@ impl ImGuiRenderer {
                         };
 
                         device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+                        let descriptor_set = self.descriptor_set_for(cmd_params.texture_id);
+                        if descriptor_set != bound_set {
+                            device.cmd_bind_descriptor_sets(
+                                command_buffer,
+                                vk::PipelineBindPoint::GRAPHICS,
+                                self.pipeline_layout,
+                                0,
+                                &[descriptor_set],
+                                &[],
+                            );
+                            bound_set = descriptor_set;
+                        }
+
                         device.cmd_draw_indexed(
                             command_buffer,
                             count as u32,
@@ -810,6 +847,7 @@ impl ImGuiRenderer {
     pub unsafe fn recreate_pipeline(
         &mut self,
         device: &ash::Device,
+        cache: &crate::core::PipelineCache,
         render_pass: vk::RenderPass,
         swapchain_extent: vk::Extent2D,
     ) -> anyhow::Result<()> {
@@ -820,6 +858,7 @@ impl ImGuiRenderer {
         // Create new pipeline with updated extent
         let (pipeline_layout, pipeline) = Self::create_pipeline(
             device,
+            cache,
             render_pass,
             self.descriptor_set_layout,
             swapchain_extent,
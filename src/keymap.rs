@@ -0,0 +1,471 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use winit::keyboard::KeyCode;
+
+/// What a shortcut is grouped under in the help overlay and the Keymap panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionCategory {
+    General,
+    Editing,
+    Camera,
+    Play,
+    Skybox,
+}
+
+impl ActionCategory {
+    pub const ALL: [ActionCategory; 5] = [Self::General, Self::Editing, Self::Camera, Self::Play, Self::Skybox];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::General => "General",
+            Self::Editing => "Editing",
+            Self::Camera => "Camera",
+            Self::Play => "Play Mode",
+            Self::Skybox => "Skybox Tweaks (hold)",
+        }
+    }
+}
+
+/// Named input actions the engine looks up in the `Keymap` instead of matching keys directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    ShowHelp,
+    ToggleStats,
+    ToggleMinimap,
+    ReloadShaders,
+    Screenshot,
+    Poster,
+    ToggleConsole,
+    CommandPalette,
+    Cancel,
+    GizmoTranslate,
+    GizmoRotate,
+    GizmoScale,
+    FrameSelection,
+    ToggleRuler,
+    Copy,
+    Paste,
+    CopyMaterial,
+    PasteMaterial,
+    NudgeLeft,
+    NudgeRight,
+    NudgeUp,
+    NudgeDown,
+    NudgeRaise,
+    NudgeLower,
+    ToggleCameraMode,
+    ToggleProjection,
+    Bookmark1,
+    Bookmark2,
+    Bookmark3,
+    Bookmark4,
+    Bookmark5,
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    EndTurn,
+    StarDensityDown,
+    StarDensityUp,
+    StarBrightnessDown,
+    StarBrightnessUp,
+    NebulaIntensityDown,
+    NebulaIntensityUp,
+    BackgroundDown,
+    BackgroundUp,
+}
+
+/// Per-action metadata: config id, label, category and default binding
+struct ActionInfo {
+    action: Action,
+    id: &'static str,
+    label: &'static str,
+    category: ActionCategory,
+    default: Option<KeyBinding>,
+}
+
+const fn info(action: Action, id: &'static str, label: &'static str, category: ActionCategory, default: Option<KeyBinding>) -> ActionInfo {
+    ActionInfo { action, id, label, category, default }
+}
+
+use ActionCategory::{Camera, Editing, General, Play, Skybox};
+
+/// Every action in display order. Skybox tweaks are unbound by default: on the
+/// digit keys they used to share, they fought the gizmo and bookmark shortcuts.
+const ACTIONS: &[ActionInfo] = &[
+    info(Action::ShowHelp, "show_help", "Keyboard Shortcuts", General, Some(KeyBinding::key(KeyCode::F1))),
+    info(Action::ToggleStats, "toggle_stats", "Frame Stats Overlay", General, Some(KeyBinding::key(KeyCode::F3))),
+    info(Action::ToggleMinimap, "toggle_minimap", "Tactical Minimap", General, Some(KeyBinding::key(KeyCode::KeyN))),
+    info(Action::ReloadShaders, "reload_shaders", "Reload Shaders", General, Some(KeyBinding::key(KeyCode::F5))),
+    info(Action::Screenshot, "screenshot", "Screenshot", General, Some(KeyBinding::key(KeyCode::F12))),
+    info(Action::Poster, "poster", "Render Poster (4K)", General, Some(KeyBinding::shift(KeyCode::F12))),
+    info(Action::ToggleConsole, "toggle_console", "Log Console", General, Some(KeyBinding::key(KeyCode::Backquote))),
+    info(Action::CommandPalette, "command_palette", "Command Palette", General, Some(KeyBinding::ctrl(KeyCode::KeyP))),
+    info(Action::Cancel, "cancel", "Cancel / Stop Following", General, Some(KeyBinding::key(KeyCode::Escape))),
+    info(Action::GizmoTranslate, "gizmo_translate", "Translate Gizmo", Editing, Some(KeyBinding::key(KeyCode::Digit1))),
+    info(Action::GizmoRotate, "gizmo_rotate", "Rotate Gizmo", Editing, Some(KeyBinding::key(KeyCode::Digit2))),
+    info(Action::GizmoScale, "gizmo_scale", "Scale Gizmo", Editing, Some(KeyBinding::key(KeyCode::Digit3))),
+    info(Action::FrameSelection, "frame_selection", "Frame Selection", Editing, Some(KeyBinding::key(KeyCode::KeyF))),
+    info(Action::ToggleRuler, "toggle_ruler", "Measurement Ruler", Editing, Some(KeyBinding::key(KeyCode::KeyM))),
+    info(Action::Copy, "copy", "Copy Objects", Editing, Some(KeyBinding::ctrl(KeyCode::KeyC))),
+    info(Action::Paste, "paste", "Paste Objects", Editing, Some(KeyBinding::ctrl(KeyCode::KeyV))),
+    info(Action::CopyMaterial, "copy_material", "Copy Material", Editing, Some(KeyBinding::ctrl_shift(KeyCode::KeyC))),
+    info(Action::PasteMaterial, "paste_material", "Paste Material", Editing, Some(KeyBinding::ctrl_shift(KeyCode::KeyV))),
+    info(Action::NudgeLeft, "nudge_left", "Nudge -X / Yaw Left", Editing, Some(KeyBinding::key(KeyCode::ArrowLeft))),
+    info(Action::NudgeRight, "nudge_right", "Nudge +X / Yaw Right", Editing, Some(KeyBinding::key(KeyCode::ArrowRight))),
+    info(Action::NudgeUp, "nudge_up", "Nudge -Z / Pitch Up", Editing, Some(KeyBinding::key(KeyCode::ArrowUp))),
+    info(Action::NudgeDown, "nudge_down", "Nudge +Z / Pitch Down", Editing, Some(KeyBinding::key(KeyCode::ArrowDown))),
+    info(Action::NudgeRaise, "nudge_raise", "Nudge +Y / Roll Left", Editing, Some(KeyBinding::key(KeyCode::PageUp))),
+    info(Action::NudgeLower, "nudge_lower", "Nudge -Y / Roll Right", Editing, Some(KeyBinding::key(KeyCode::PageDown))),
+    info(Action::ToggleCameraMode, "toggle_camera_mode", "Orbit / Fly Camera", Camera, Some(KeyBinding::key(KeyCode::KeyC))),
+    info(Action::ToggleProjection, "toggle_projection", "Perspective / Ortho", Camera, Some(KeyBinding::key(KeyCode::KeyO))),
+    info(Action::Bookmark1, "bookmark_1", "Camera Bookmark 1", Camera, Some(KeyBinding::key(KeyCode::Digit5))),
+    info(Action::Bookmark2, "bookmark_2", "Camera Bookmark 2", Camera, Some(KeyBinding::key(KeyCode::Digit6))),
+    info(Action::Bookmark3, "bookmark_3", "Camera Bookmark 3", Camera, Some(KeyBinding::key(KeyCode::Digit7))),
+    info(Action::Bookmark4, "bookmark_4", "Camera Bookmark 4", Camera, Some(KeyBinding::key(KeyCode::Digit8))),
+    info(Action::Bookmark5, "bookmark_5", "Camera Bookmark 5", Camera, Some(KeyBinding::key(KeyCode::Digit9))),
+    info(Action::MoveForward, "move_forward", "Fly Forward", Camera, Some(KeyBinding::key(KeyCode::KeyW))),
+    info(Action::MoveBack, "move_back", "Fly Back", Camera, Some(KeyBinding::key(KeyCode::KeyS))),
+    info(Action::MoveLeft, "move_left", "Fly Left", Camera, Some(KeyBinding::key(KeyCode::KeyA))),
+    info(Action::MoveRight, "move_right", "Fly Right", Camera, Some(KeyBinding::key(KeyCode::KeyD))),
+    info(Action::MoveUp, "move_up", "Fly Up / Orbit Roll Right", Camera, Some(KeyBinding::key(KeyCode::KeyE))),
+    info(Action::MoveDown, "move_down", "Fly Down / Orbit Roll Left", Camera, Some(KeyBinding::key(KeyCode::KeyQ))),
+    info(Action::EndTurn, "end_turn", "End Turn", Play, Some(KeyBinding::key(KeyCode::Enter))),
+    info(Action::StarDensityDown, "star_density_down", "Star Density -", Skybox, None),
+    info(Action::StarDensityUp, "star_density_up", "Star Density +", Skybox, None),
+    info(Action::StarBrightnessDown, "star_brightness_down", "Star Brightness -", Skybox, None),
+    info(Action::StarBrightnessUp, "star_brightness_up", "Star Brightness +", Skybox, None),
+    info(Action::NebulaIntensityDown, "nebula_intensity_down", "Nebula Intensity -", Skybox, None),
+    info(Action::NebulaIntensityUp, "nebula_intensity_up", "Nebula Intensity +", Skybox, None),
+    info(Action::BackgroundDown, "background_down", "Background Brightness -", Skybox, None),
+    info(Action::BackgroundUp, "background_up", "Background Brightness +", Skybox, None),
+];
+
+impl Action {
+    /// All actions in display order
+    pub fn all() -> impl Iterator<Item = Action> {
+        ACTIONS.iter().map(|info| info.action)
+    }
+
+    fn info(&self) -> &'static ActionInfo {
+        ACTIONS
+            .iter()
+            .find(|info| info.action == *self)
+            .expect("every action has an entry in ACTIONS")
+    }
+
+    /// Stable name used in config files
+    pub fn id(&self) -> &'static str {
+        self.info().id
+    }
+
+    pub fn from_id(id: &str) -> Option<Action> {
+        ACTIONS.iter().find(|info| info.id == id).map(|info| info.action)
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.info().label
+    }
+
+    pub fn category(&self) -> ActionCategory {
+        self.info().category
+    }
+
+    pub fn default_binding(&self) -> Option<KeyBinding> {
+        self.info().default
+    }
+
+    /// Actions that still fire while an ImGui text field has keyboard focus
+    pub fn works_while_typing(&self) -> bool {
+        matches!(
+            self,
+            Action::ShowHelp
+                | Action::ToggleStats
+                | Action::ReloadShaders
+                | Action::Screenshot
+                | Action::Poster
+                | Action::CommandPalette
+        )
+    }
+
+    /// Held actions polled every frame (flying, skybox tweaks); pressing them once does nothing
+    pub fn is_continuous(&self) -> bool {
+        matches!(
+            self,
+            Action::MoveForward
+                | Action::MoveBack
+                | Action::MoveLeft
+                | Action::MoveRight
+                | Action::MoveUp
+                | Action::MoveDown
+                | Action::StarDensityDown
+                | Action::StarDensityUp
+                | Action::StarBrightnessDown
+                | Action::StarBrightnessUp
+                | Action::NebulaIntensityDown
+                | Action::NebulaIntensityUp
+                | Action::BackgroundDown
+                | Action::BackgroundUp
+        )
+    }
+}
+
+/// Keys that can be bound, with the names used in configs and the UI
+const KEY_NAMES: &[(KeyCode, &str)] = &[
+    (KeyCode::KeyA, "A"), (KeyCode::KeyB, "B"), (KeyCode::KeyC, "C"), (KeyCode::KeyD, "D"),
+    (KeyCode::KeyE, "E"), (KeyCode::KeyF, "F"), (KeyCode::KeyG, "G"), (KeyCode::KeyH, "H"),
+    (KeyCode::KeyI, "I"), (KeyCode::KeyJ, "J"), (KeyCode::KeyK, "K"), (KeyCode::KeyL, "L"),
+    (KeyCode::KeyM, "M"), (KeyCode::KeyN, "N"), (KeyCode::KeyO, "O"), (KeyCode::KeyP, "P"),
+    (KeyCode::KeyQ, "Q"), (KeyCode::KeyR, "R"), (KeyCode::KeyS, "S"), (KeyCode::KeyT, "T"),
+    (KeyCode::KeyU, "U"), (KeyCode::KeyV, "V"), (KeyCode::KeyW, "W"), (KeyCode::KeyX, "X"),
+    (KeyCode::KeyY, "Y"), (KeyCode::KeyZ, "Z"),
+    (KeyCode::Digit0, "0"), (KeyCode::Digit1, "1"), (KeyCode::Digit2, "2"), (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"), (KeyCode::Digit5, "5"), (KeyCode::Digit6, "6"), (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"), (KeyCode::Digit9, "9"),
+    (KeyCode::F1, "F1"), (KeyCode::F2, "F2"), (KeyCode::F3, "F3"), (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"), (KeyCode::F6, "F6"), (KeyCode::F7, "F7"), (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"), (KeyCode::F10, "F10"), (KeyCode::F11, "F11"), (KeyCode::F12, "F12"),
+    (KeyCode::ArrowLeft, "Left"), (KeyCode::ArrowRight, "Right"), (KeyCode::ArrowUp, "Up"), (KeyCode::ArrowDown, "Down"),
+    (KeyCode::PageUp, "PageUp"), (KeyCode::PageDown, "PageDown"), (KeyCode::Home, "Home"), (KeyCode::End, "End"),
+    (KeyCode::Insert, "Insert"), (KeyCode::Delete, "Delete"), (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Enter, "Enter"), (KeyCode::Escape, "Escape"), (KeyCode::Space, "Space"), (KeyCode::Tab, "Tab"),
+    (KeyCode::Backquote, "`"), (KeyCode::Minus, "-"), (KeyCode::Equal, "="),
+    (KeyCode::BracketLeft, "["), (KeyCode::BracketRight, "]"), (KeyCode::Backslash, "\\"),
+    (KeyCode::Semicolon, ";"), (KeyCode::Quote, "'"), (KeyCode::Comma, ","), (KeyCode::Period, "."), (KeyCode::Slash, "/"),
+    (KeyCode::Numpad0, "Num0"), (KeyCode::Numpad1, "Num1"), (KeyCode::Numpad2, "Num2"), (KeyCode::Numpad3, "Num3"),
+    (KeyCode::Numpad4, "Num4"), (KeyCode::Numpad5, "Num5"), (KeyCode::Numpad6, "Num6"), (KeyCode::Numpad7, "Num7"),
+    (KeyCode::Numpad8, "Num8"), (KeyCode::Numpad9, "Num9"),
+    (KeyCode::NumpadAdd, "NumAdd"), (KeyCode::NumpadSubtract, "NumSub"), (KeyCode::NumpadEnter, "NumEnter"),
+];
+
+/// Name of a bindable key (None for modifiers and keys outside `KEY_NAMES`)
+pub fn key_name(key: KeyCode) -> Option<&'static str> {
+    KEY_NAMES.iter().find(|(code, _)| *code == key).map(|(_, name)| *name)
+}
+
+/// A key plus the modifiers that must be held with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+}
+
+impl KeyBinding {
+    pub const fn key(key: KeyCode) -> Self {
+        Self { key, ctrl: false, shift: false }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self { key, ctrl: true, shift: false }
+    }
+
+    pub const fn shift(key: KeyCode) -> Self {
+        Self { key, ctrl: false, shift: true }
+    }
+
+    pub const fn ctrl_shift(key: KeyCode) -> Self {
+        Self { key, ctrl: true, shift: true }
+    }
+
+    /// Parse the `Display` form, e.g. "Ctrl+Shift+C" (modifiers are case-insensitive)
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut binding = Self::key(KeyCode::Escape);
+        let parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let (key, modifiers) = parts.split_last()?;
+        for modifier in modifiers {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => binding.ctrl = true,
+                "shift" => binding.shift = true,
+                _ => return None,
+            }
+        }
+        binding.key = KEY_NAMES.iter().find(|(_, name)| name.eq_ignore_ascii_case(key))?.0;
+        Some(binding)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", key_name(self.key).unwrap_or("?"))
+    }
+}
+
+/// Whether `key` is a modifier (held with a binding, never bound on its own)
+pub fn is_modifier(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::ShiftLeft | KeyCode::ShiftRight | KeyCode::ControlLeft | KeyCode::ControlRight
+            | KeyCode::AltLeft | KeyCode::AltRight | KeyCode::SuperLeft | KeyCode::SuperRight
+    )
+}
+
+/// Key bindings for every `Action`, loaded from config. Unbound actions can't be triggered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: HashMap<Action, KeyBinding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: ACTIONS
+                .iter()
+                .filter_map(|info| info.default.map(|binding| (info.action, binding)))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn binding(&self, action: Action) -> Option<KeyBinding> {
+        self.bindings.get(&action).copied()
+    }
+
+    /// Bind (or with None, unbind) an action. Any other action on the same binding is
+    /// unbound so a key never triggers two actions; those actions are returned.
+    pub fn bind(&mut self, action: Action, binding: Option<KeyBinding>) -> Vec<Action> {
+        let displaced = binding.map(|binding| self.conflicts_with(action, binding)).unwrap_or_default();
+        for other in &displaced {
+            self.bindings.remove(other);
+        }
+        match binding {
+            Some(binding) => self.bindings.insert(action, binding),
+            None => self.bindings.remove(&action),
+        };
+        displaced
+    }
+
+    /// Other actions already using `binding`
+    pub fn conflicts_with(&self, action: Action, binding: KeyBinding) -> Vec<Action> {
+        Action::all()
+            .filter(|other| *other != action && self.binding(*other) == Some(binding))
+            .collect()
+    }
+
+    /// Bindings shared by more than one action (only possible through a hand-edited config)
+    pub fn conflicts(&self) -> Vec<(KeyBinding, Vec<Action>)> {
+        let mut seen = HashSet::new();
+        Action::all()
+            .filter_map(|action| {
+                let binding = self.binding(action)?;
+                if !seen.insert(binding) {
+                    return None;
+                }
+                let mut actions = self.conflicts_with(action, binding);
+                if actions.is_empty() {
+                    return None;
+                }
+                actions.insert(0, action);
+                Some((binding, actions))
+            })
+            .collect()
+    }
+
+    /// Action triggered by pressing `key` with the given modifiers. An exact match wins;
+    /// otherwise held modifiers are ignored, so e.g. Shift+Arrow still nudges (Shift picks
+    /// the larger step) while Ctrl+C and C stay separate actions.
+    pub fn action_for(&self, key: KeyCode, ctrl: bool, shift: bool) -> Option<Action> {
+        let candidates = [(ctrl, shift), (ctrl, false), (false, shift), (false, false)];
+        candidates.iter().find_map(|&(ctrl, shift)| {
+            let wanted = KeyBinding { key, ctrl, shift };
+            Action::all().find(|action| self.binding(*action) == Some(wanted))
+        })
+    }
+
+    /// Whether a continuous action's key is held (modifiers don't matter, Shift boosts fly speed)
+    pub fn is_held(&self, action: Action, pressed_keys: &HashSet<KeyCode>) -> bool {
+        self.binding(action).is_some_and(|binding| pressed_keys.contains(&binding.key))
+    }
+
+    /// Binding shown in menus and tooltips, e.g. "Ctrl+C" or "unbound"
+    pub fn describe(&self, action: Action) -> String {
+        self.binding(action).map_or_else(|| "unbound".to_string(), |binding| binding.to_string())
+    }
+}
+
+// Keymap config conversions
+impl From<crate::config::KeymapConfigData> for Keymap {
+    fn from(data: crate::config::KeymapConfigData) -> Self {
+        // Start from the defaults so actions added after the config was saved get their keys
+        let mut keymap = Keymap::default();
+        for (id, text) in &data.bindings {
+            let Some(action) = Action::from_id(id) else {
+                crate::console::warn(format!("Keymap: ignoring unknown action '{}'", id));
+                continue;
+            };
+            if text.is_empty() {
+                keymap.bindings.remove(&action);
+                continue;
+            }
+            match KeyBinding::parse(text) {
+                Some(binding) => {
+                    keymap.bindings.insert(action, binding);
+                }
+                None => crate::console::warn(format!("Keymap: can't parse '{}' for '{}', keeping the default", text, id)),
+            }
+        }
+        keymap
+    }
+}
+
+impl From<&Keymap> for crate::config::KeymapConfigData {
+    fn from(keymap: &Keymap) -> Self {
+        Self {
+            bindings: Action::all()
+                .map(|action| (action.id().to_string(), keymap.binding(action).map(|b| b.to_string()).unwrap_or_default()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_conflict_free_and_round_trip() {
+        let keymap = Keymap::default();
+        assert!(keymap.conflicts().is_empty());
+
+        for action in Action::all() {
+            assert_eq!(Action::from_id(action.id()), Some(action));
+            if let Some(binding) = keymap.binding(action) {
+                assert_eq!(KeyBinding::parse(&binding.to_string()), Some(binding));
+            }
+        }
+        let data: crate::config::KeymapConfigData = (&keymap).into();
+        assert_eq!(Keymap::from(data), keymap);
+    }
+
+    #[test]
+    fn test_binding_a_used_key_moves_it() {
+        let mut keymap = Keymap::default();
+        let displaced = keymap.bind(Action::ToggleRuler, Some(KeyBinding::key(KeyCode::KeyF)));
+        assert_eq!(displaced, vec![Action::FrameSelection]);
+        assert_eq!(keymap.binding(Action::FrameSelection), None);
+        assert_eq!(keymap.action_for(KeyCode::KeyF, false, false), Some(Action::ToggleRuler));
+        assert!(keymap.conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_modifiers_prefer_exact_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action_for(KeyCode::KeyC, false, false), Some(Action::ToggleCameraMode));
+        assert_eq!(keymap.action_for(KeyCode::KeyC, true, false), Some(Action::Copy));
+        assert_eq!(keymap.action_for(KeyCode::KeyC, true, true), Some(Action::CopyMaterial));
+        assert_eq!(keymap.action_for(KeyCode::F12, false, true), Some(Action::Poster));
+        // No Shift+Left binding, so Shift falls through to the plain nudge
+        assert_eq!(keymap.action_for(KeyCode::ArrowLeft, false, true), Some(Action::NudgeLeft));
+        assert_eq!(KeyBinding::parse("ctrl+shift+v"), Some(KeyBinding::ctrl_shift(KeyCode::KeyV)));
+        assert_eq!(KeyBinding::parse("Hyper+V"), None);
+    }
+}
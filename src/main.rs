@@ -10,14 +10,33 @@ mod background;
 mod ui;
 mod nebula;
 mod config;
+mod migration;
+mod autosave;
 mod scene;
+mod idle_motion;
+mod prefab;
 mod gizmo;
+mod view_cube;
+mod ruler;
+mod minimap;
+mod raycast;
+mod particles;
+mod gltf_export;
+mod console;
+mod keymap;
+mod commands;
 mod ecs;      // ECS system with 64-bit coordinates
 mod movement; // Turn-based movement system
+mod headless; // Windowless rendering for tests and tools
 
 use engine::Engine;
 
 fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(headless_args) = headless::HeadlessArgs::parse(&args) {
+        return headless::run(&headless_args?);
+    }
+
     println!("=== Tribal Engine Starting ===");
     println!("Initializing Vulkan renderer...");
     let engine = Engine::new()?;
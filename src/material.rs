@@ -2,7 +2,7 @@ use glam::Vec3;
 use serde::{Deserialize, Serialize};
 
 /// Material properties for PBR rendering
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MaterialProperties {
     /// Base color (albedo) of the material
     pub albedo: Vec3,
@@ -14,6 +14,97 @@ pub struct MaterialProperties {
     pub ambient_strength: f32,
     /// Global illumination strength (0.0 = no GI, 1.0 = full GI)
     pub gi_strength: f32,
+    /// Optional albedo texture path (multiplied with the albedo color)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub albedo_texture: Option<String>,
+    /// Optional tangent-space normal map path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normal_texture: Option<String>,
+    /// Optional metallic/roughness texture path (glTF layout: G = roughness, B = metallic)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metallic_roughness_texture: Option<String>,
+    /// Emitted light color (added on top of the lit color, unaffected by lighting)
+    #[serde(default)]
+    pub emissive: Vec3,
+    /// Emission multiplier (0.0 = no glow, values above 1.0 feed bloom)
+    #[serde(default)]
+    pub emissive_strength: f32,
+    /// Opacity used when `transparent` is set (0.0 = invisible, 1.0 = solid)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Draw with alpha blending after opaque objects, sorted back to front
+    #[serde(default)]
+    pub transparent: bool,
+    /// Multiply albedo by the mesh's vertex colors (baked AO, team colors)
+    #[serde(default)]
+    pub vertex_colors: bool,
+    /// Optional weathering layer blended over this material
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<LayeredMaterial>,
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+/// What decides where the overlay of a `LayeredMaterial` shows through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WeatheringMask {
+    /// Object-space noise alone
+    #[default]
+    Noise,
+    /// Noise pulled toward crevices by screen-space ambient occlusion (needs SSAO enabled)
+    AmbientOcclusion,
+    /// Noise pulled toward occlusion baked into the mesh's vertex colors (darker = more wear)
+    VertexOcclusion,
+}
+
+impl WeatheringMask {
+    pub const ALL: [WeatheringMask; 3] = [
+        WeatheringMask::Noise,
+        WeatheringMask::AmbientOcclusion,
+        WeatheringMask::VertexOcclusion,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeatheringMask::Noise => "Noise",
+            WeatheringMask::AmbientOcclusion => "Ambient Occlusion (SSAO)",
+            WeatheringMask::VertexOcclusion => "Vertex Occlusion",
+        }
+    }
+}
+
+/// Weathering: an overlay material blended over the base (the material that owns the layer)
+/// where a mask computed in mesh.frag exceeds the coverage threshold, e.g. rust on worn paint.
+/// Only the overlay's albedo, metallic and roughness are used; its textures and layer are not
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayeredMaterial {
+    pub overlay: Box<MaterialProperties>,
+    pub mask: WeatheringMask,
+    /// Share of the surface the overlay covers (0.0 = none, 1.0 = all)
+    pub coverage: f32,
+    /// Softness of the edge between the layers in mask units (0.0 = hard edge, 1.0 = very soft)
+    pub blend_width: f32,
+    /// Frequency of the mask noise in cycles per object-space unit
+    pub noise_scale: f32,
+}
+
+impl Default for LayeredMaterial {
+    fn default() -> Self {
+        Self {
+            overlay: Box::new(MaterialProperties::rust()),
+            mask: WeatheringMask::Noise,
+            coverage: 0.3,
+            blend_width: 0.1,
+            noise_scale: 2.0,
+        }
+    }
+}
+
+impl LayeredMaterial {
+    /// Upper end of `noise_scale` (the shader receives it as a fraction of this)
+    pub const MAX_NOISE_SCALE: f32 = 64.0;
 }
 
 impl Default for MaterialProperties {
@@ -24,6 +115,15 @@ impl Default for MaterialProperties {
             roughness: 0.6,
             ambient_strength: 1.0,
             gi_strength: 0.5,
+            albedo_texture: None,
+            normal_texture: None,
+            metallic_roughness_texture: None,
+            emissive: Vec3::ZERO,
+            emissive_strength: 0.0,
+            opacity: 1.0,
+            transparent: false,
+            vertex_colors: false,
+            layer: None,
         }
     }
 }
@@ -36,6 +136,7 @@ impl MaterialProperties {
             roughness,
             ambient_strength,
             gi_strength: 0.5,
+            ..Default::default()
         }
     }
 
@@ -47,6 +148,7 @@ impl MaterialProperties {
             roughness: 0.9,
             ambient_strength: 1.0,
             gi_strength: 0.5,
+            ..Default::default()
         }
     }
 
@@ -58,6 +160,7 @@ impl MaterialProperties {
             roughness,
             ambient_strength: 1.0,
             gi_strength: 0.3,
+            ..Default::default()
         }
     }
 
@@ -69,6 +172,127 @@ impl MaterialProperties {
             roughness: 0.3,
             ambient_strength: 1.0,
             gi_strength: 0.5,
+            ..Default::default()
+        }
+    }
+
+    /// Create a glowing material (dark base, strong emission)
+    pub fn emissive(albedo: Vec3, emissive: Vec3, emissive_strength: f32) -> Self {
+        Self {
+            albedo,
+            metallic: 0.0,
+            roughness: 0.5,
+            ambient_strength: 1.0,
+            gi_strength: 0.2,
+            emissive,
+            emissive_strength,
+            ..Default::default()
+        }
+    }
+
+    /// Bright blue-white engine exhaust glow
+    pub fn engine_glow() -> Self {
+        Self::emissive(Vec3::new(0.1, 0.1, 0.12), Vec3::new(0.4, 0.7, 1.0), 6.0)
+    }
+
+    /// Rough orange-brown oxide, the default weathering overlay
+    pub fn rust() -> Self {
+        Self::matte(Vec3::new(0.45, 0.2, 0.08))
+    }
+
+    /// Clear, slightly blue-tinted glass (alpha blended)
+    pub fn glass() -> Self {
+        Self {
+            albedo: Vec3::new(0.85, 0.92, 1.0),
+            metallic: 0.0,
+            roughness: 0.05,
+            ambient_strength: 1.0,
+            gi_strength: 0.5,
+            opacity: 0.3,
+            transparent: true,
+            ..Default::default()
         }
     }
+
+    /// Keep the texture paths, vertex color setting and weathering layer of another material
+    /// (used when applying presets, which only change the constant values)
+    pub fn with_textures_of(mut self, other: &MaterialProperties) -> Self {
+        self.albedo_texture = other.albedo_texture.clone();
+        self.normal_texture = other.normal_texture.clone();
+        self.metallic_roughness_texture = other.metallic_roughness_texture.clone();
+        self.vertex_colors = other.vertex_colors;
+        self.layer = other.layer.clone();
+        self
+    }
+
+    /// Whether any texture slot is set
+    pub fn has_textures(&self) -> bool {
+        self.albedo_texture.is_some()
+            || self.normal_texture.is_some()
+            || self.metallic_roughness_texture.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_emissive_defaults_to_none() {
+        // Materials saved before emission existed
+        let json = r#"{
+            "albedo": [0.5, 0.5, 0.5],
+            "metallic": 0.1,
+            "roughness": 0.7,
+            "ambient_strength": 1.0,
+            "gi_strength": 0.5
+        }"#;
+
+        let material: MaterialProperties = serde_json::from_str(json).unwrap();
+        assert_eq!(material.emissive, Vec3::ZERO);
+        assert_eq!(material.emissive_strength, 0.0);
+        // ...and before transparency
+        assert_eq!(material.opacity, 1.0);
+        assert!(!material.transparent);
+        // ...and before vertex colors
+        assert!(!material.vertex_colors);
+        // ...and before weathering layers
+        assert!(material.layer.is_none());
+    }
+
+    #[test]
+    fn test_emissive_round_trips() {
+        let material = MaterialProperties::engine_glow();
+        let json = serde_json::to_string(&material).unwrap();
+        let loaded: MaterialProperties = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.emissive, material.emissive);
+        assert_eq!(loaded.emissive_strength, material.emissive_strength);
+    }
+
+    #[test]
+    fn test_layer_round_trips() {
+        let material = MaterialProperties {
+            layer: Some(LayeredMaterial {
+                mask: WeatheringMask::AmbientOcclusion,
+                coverage: 0.6,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&material).unwrap();
+        let loaded: MaterialProperties = serde_json::from_str(&json).unwrap();
+
+        let layer = loaded.layer.unwrap();
+        assert_eq!(layer.mask, WeatheringMask::AmbientOcclusion);
+        assert_eq!(layer.coverage, 0.6);
+        assert_eq!(layer.overlay.albedo, MaterialProperties::rust().albedo);
+        assert!(layer.overlay.layer.is_none());
+    }
+
+    #[test]
+    fn test_presets_keep_the_layer() {
+        let worn = MaterialProperties { layer: Some(LayeredMaterial::default()), ..Default::default() };
+        assert!(MaterialProperties::metallic(Vec3::ONE, 0.3).with_textures_of(&worn).layer.is_some());
+    }
 }
@@ -1,5 +1,7 @@
 use ash::vk;
-use glam::{Vec2, Vec3};
+use glam::{DVec3, Mat3, Mat4, Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+use std::collections::{BinaryHeap, HashMap};
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -7,6 +9,10 @@ pub struct Vertex {
     pub position: Vec3,
     pub normal: Vec3,
     pub uv: Vec2,
+    /// Direction of increasing U in object space, filled in by `Mesh::compute_tangents`
+    pub tangent: Vec3,
+    /// Linear RGB vertex color (white when the source has none), used by materials with `vertex_colors`
+    pub color: Vec3,
 }
 
 impl Vertex {
@@ -17,7 +23,9 @@ impl Vertex {
             .input_rate(vk::VertexInputRate::VERTEX)
     }
 
-    pub fn get_attribute_descriptions() -> [vk::VertexInputAttributeDescription; 3] {
+    /// Per-vertex attributes. The tangent and color sit at locations 11 and 12, after the
+    /// per-instance attributes of the instanced mesh pipeline (locations 3-10).
+    pub fn get_attribute_descriptions() -> [vk::VertexInputAttributeDescription; 5] {
         [
             vk::VertexInputAttributeDescription::default()
                 .binding(0)
@@ -34,6 +42,16 @@ impl Vertex {
                 .location(2)
                 .format(vk::Format::R32G32_SFLOAT)
                 .offset((std::mem::size_of::<Vec3>() * 2) as u32),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(11)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset((std::mem::size_of::<Vec3>() * 2 + std::mem::size_of::<Vec2>()) as u32),
+            vk::VertexInputAttributeDescription::default()
+                .binding(0)
+                .location(12)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset((std::mem::size_of::<Vec3>() * 3 + std::mem::size_of::<Vec2>()) as u32),
         ]
     }
 }
@@ -44,7 +62,150 @@ pub struct Mesh {
     pub indices: Vec<u32>,
 }
 
+/// How heavy a mesh is: geometry counts and local-space extent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshStats {
+    pub vertices: usize,
+    pub triangles: usize,
+    /// Local-space AABB (min, max)
+    pub bounds: (Vec3, Vec3),
+}
+
+impl MeshStats {
+    /// AABB dimensions
+    pub fn size(&self) -> Vec3 {
+        self.bounds.1 - self.bounds.0
+    }
+}
+
+/// Built-in procedural shapes (sized to fit the unit cube like `create_cube`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PrimitiveShape {
+    Sphere,
+    Plane,
+    Cylinder,
+    Torus,
+}
+
+impl PrimitiveShape {
+    pub const ALL: [PrimitiveShape; 4] = [
+        PrimitiveShape::Sphere,
+        PrimitiveShape::Plane,
+        PrimitiveShape::Cylinder,
+        PrimitiveShape::Torus,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrimitiveShape::Sphere => "Sphere",
+            PrimitiveShape::Plane => "Plane",
+            PrimitiveShape::Cylinder => "Cylinder",
+            PrimitiveShape::Torus => "Torus",
+        }
+    }
+}
+
+/// Marks the custom mesh entries that hold generated LOD levels
+pub const LOD_KEY_SEPARATOR: &str = "#lod";
+
+/// Key a generated LOD level of the mesh at `path` is stored under, next to the
+/// base mesh (level 0 is the base mesh itself)
+pub fn lod_key(path: &str, level: usize) -> String {
+    if level == 0 {
+        path.to_string()
+    } else {
+        format!("{}{}{}", path, LOD_KEY_SEPARATOR, level)
+    }
+}
+
+/// Weight of the planes that hold open borders in place during `Mesh::simplify`
+const SIMPLIFY_BORDER_WEIGHT: f64 = 10.0;
+/// Collapses that tilt a surviving triangle's normal below this cosine count as fold-overs
+const SIMPLIFY_MIN_NORMAL_DOT: f64 = 0.2;
+
+/// Symmetric 4x4 error quadric (sum of squared plane distances), upper triangle row by row
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Squared distance to the plane with unit `normal` through `point`, scaled by `weight`
+    fn from_plane(normal: DVec3, point: DVec3, weight: f64) -> Self {
+        let (a, b, c, d) = (normal.x, normal.y, normal.z, -normal.dot(point));
+        Self([a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d].map(|value| value * weight))
+    }
+
+    /// Error of placing a vertex at `p`
+    fn error(&self, p: DVec3) -> f64 {
+        let q = &self.0;
+        q[0] * p.x * p.x + 2.0 * q[1] * p.x * p.y + 2.0 * q[2] * p.x * p.z + 2.0 * q[3] * p.x
+            + q[4] * p.y * p.y + 2.0 * q[5] * p.y * p.z + 2.0 * q[6] * p.y
+            + q[7] * p.z * p.z + 2.0 * q[8] * p.z
+            + q[9]
+    }
+}
+
+impl std::ops::AddAssign for Quadric {
+    fn add_assign(&mut self, other: Self) {
+        for (value, other) in self.0.iter_mut().zip(other.0) {
+            *value += other;
+        }
+    }
+}
+
+impl std::ops::Add for Quadric {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+/// Pending collapse of vertex group `from` onto `to`; the heap pops the cheapest first
+struct Collapse {
+    cost: f64,
+    from: usize,
+    to: usize,
+    /// Change counters of both groups when queued (stale if either moved on)
+    stamps: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reversed so BinaryHeap (a max-heap) yields the lowest cost
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
 impl Mesh {
+    /// Vertex and triangle counts plus the bounding box
+    pub fn stats(&self) -> MeshStats {
+        MeshStats {
+            vertices: self.vertices.len(),
+            triangles: self.indices.len() / 3,
+            bounds: self.calculate_bounds(),
+        }
+    }
+
+    /// Size in bytes of the mesh's vertex and index buffers once uploaded
+    pub fn buffer_bytes(&self) -> u64 {
+        (self.vertices.len() * std::mem::size_of::<Vertex>() + self.indices.len() * std::mem::size_of::<u32>()) as u64
+    }
+
     /// Calculate axis-aligned bounding box from mesh vertices
     pub fn calculate_bounds(&self) -> (Vec3, Vec3) {
         if self.vertices.is_empty() {
@@ -62,6 +223,376 @@ impl Mesh {
         (min, max)
     }
 
+    /// Fill in per-vertex tangents from the UV layout: each triangle's dPosition/dU is
+    /// accumulated onto its vertices, then made perpendicular to the vertex normal.
+    /// Vertices whose triangles all have degenerate UVs get an arbitrary tangent
+    /// perpendicular to the normal, so the TBN basis is always valid.
+    pub fn compute_tangents(&mut self) {
+        let mut accumulated = vec![Vec3::ZERO; self.vertices.len()];
+
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| i as usize);
+            if a.max(b).max(c) >= self.vertices.len() {
+                continue;
+            }
+            let (v0, v1, v2) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+
+            let edge1 = v1.position - v0.position;
+            let edge2 = v2.position - v0.position;
+            let duv1 = v1.uv - v0.uv;
+            let duv2 = v2.uv - v0.uv;
+
+            let det = duv1.x * duv2.y - duv2.x * duv1.y;
+            if det.abs() < 1e-10 {
+                continue;
+            }
+            let tangent = (edge1 * duv2.y - edge2 * duv1.y) / det;
+
+            accumulated[a] += tangent;
+            accumulated[b] += tangent;
+            accumulated[c] += tangent;
+        }
+
+        for (vertex, tangent) in self.vertices.iter_mut().zip(accumulated) {
+            let normal = vertex.normal.try_normalize().unwrap_or(Vec3::Y);
+            // Gram-Schmidt against the normal
+            vertex.tangent = (tangent - normal * normal.dot(tangent))
+                .try_normalize()
+                .unwrap_or_else(|| normal.any_orthonormal_vector());
+        }
+    }
+
+    /// Replace the normals with ones computed from the triangles, for meshes that shipped
+    /// without normals or with bad ones. The triangles meeting at a position (across UV and
+    /// normal seams) are split into smoothing groups: faces at most `smoothing_angle` radians
+    /// apart, directly or through neighbors in the group, share one normal, the average of
+    /// their face normals weighted by their angle at that corner. Faceted (`smooth` false)
+    /// only groups coplanar faces, so every flat face gets its face normal.
+    ///
+    /// Each vertex is emitted once per smoothing group it's used in, so sharper edges stay
+    /// hard while UV seams keep their own vertices; UVs and colors are kept. Tangents are
+    /// recomputed afterwards since they depend on the normals.
+    pub fn recompute_normals(&mut self, smooth: bool, smoothing_angle: f32) {
+        // Triangles of one flat face (e.g. a quad's halves) are always grouped, even though
+        // rounding makes their face normals differ slightly
+        const COPLANAR_COS: f32 = 0.9999;
+        let cos_threshold = if smooth {
+            smoothing_angle.clamp(0.0, std::f32::consts::PI).cos().min(COPLANAR_COS)
+        } else {
+            COPLANAR_COS
+        };
+
+        // Weld corners by position, within rounding (a UV sphere's pole vertices differ by ~1e-8)
+        let position_key = |vertex: &Vertex| (vertex.position * 1e5).round().as_ivec3().to_array();
+        let mut corners_at: HashMap<[i32; 3], Vec<usize>> = HashMap::new();
+        let mut face_normals = Vec::with_capacity(self.indices.len() / 3);
+        let mut corner_angles = Vec::with_capacity(self.indices.len());
+        let mut triangles = Vec::with_capacity(self.indices.len() / 3);
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| i as usize);
+            if a.max(b).max(c) >= self.vertices.len() {
+                continue;
+            }
+            let [p0, p1, p2] = [a, b, c].map(|i| self.vertices[i].position);
+            // Slivers (e.g. the collapsed triangles at a sphere's poles) have no usable normal
+            let cross = (p1 - p0).cross(p2 - p0);
+            let longest_edge_sq = [p1 - p0, p2 - p1, p0 - p2].map(Vec3::length_squared).into_iter().fold(0.0, f32::max);
+            let degenerate = cross.length() <= longest_edge_sq * 1e-4;
+            face_normals.push(if degenerate { Vec3::ZERO } else { cross.normalize() });
+            // Angle weights don't depend on how a face was split into triangles
+            for (corner, previous, next) in [(p0, p2, p1), (p1, p0, p2), (p2, p1, p0)] {
+                let angle = match ((previous - corner).try_normalize(), (next - corner).try_normalize()) {
+                    (Some(to_previous), Some(to_next)) => to_previous.dot(to_next).clamp(-1.0, 1.0).acos(),
+                    _ => 0.0,
+                };
+                corner_angles.push(angle);
+            }
+            for (corner, vertex) in [a, b, c].into_iter().enumerate() {
+                corners_at.entry(position_key(&self.vertices[vertex])).or_default().push(triangles.len() * 3 + corner);
+            }
+            triangles.push([a, b, c]);
+        }
+
+        // Smoothing groups per position (union-find over the corners there) and their normals
+        let mut group_of_corner = vec![0; triangles.len() * 3];
+        let mut group_normals: Vec<Vec3> = Vec::new();
+        for corners in corners_at.values() {
+            fn root(parent: &mut [usize], mut i: usize) -> usize {
+                while parent[i] != i {
+                    parent[i] = parent[parent[i]];
+                    i = parent[i];
+                }
+                i
+            }
+
+            let mut parent: Vec<usize> = (0..corners.len()).collect();
+            for i in 0..corners.len() {
+                for j in i + 1..corners.len() {
+                    if face_normals[corners[i] / 3].dot(face_normals[corners[j] / 3]) >= cos_threshold {
+                        let (root_i, root_j) = (root(&mut parent, i), root(&mut parent, j));
+                        parent[root_i] = root_j;
+                    }
+                }
+            }
+
+            let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+            for (i, &corner) in corners.iter().enumerate() {
+                let group = *group_of_root.entry(root(&mut parent, i)).or_insert_with(|| {
+                    group_normals.push(Vec3::ZERO);
+                    group_normals.len() - 1
+                });
+                group_of_corner[corner] = group;
+                group_normals[group] += face_normals[corner / 3] * corner_angles[corner];
+            }
+        }
+
+        let mut vertices = Vec::with_capacity(self.vertices.len());
+        let mut indices = Vec::with_capacity(triangles.len() * 3);
+        let mut lookup: HashMap<(usize, usize), u32> = HashMap::new();
+        for (face, triangle) in triangles.iter().enumerate() {
+            for (corner, &vertex_index) in triangle.iter().enumerate() {
+                let group = group_of_corner[face * 3 + corner];
+                let index = *lookup.entry((vertex_index, group)).or_insert_with(|| {
+                    let vertex = self.vertices[vertex_index];
+                    // Groups of degenerate faces keep the normal the vertex had
+                    let normal = group_normals[group].try_normalize().unwrap_or(vertex.normal);
+                    vertices.push(Vertex { normal, ..vertex });
+                    (vertices.len() - 1) as u32
+                });
+                indices.push(index);
+            }
+        }
+
+        self.vertices = vertices;
+        self.indices = indices;
+        self.compute_tangents();
+    }
+
+    /// Reduce the triangle count to about `target_ratio` of the original with quadric
+    /// edge-collapse decimation (Garland-Heckbert), used to generate LOD levels.
+    ///
+    /// Vertices are welded by position so UV and hard-normal seams don't read as holes.
+    /// Each collapse moves a vertex onto a neighbor (no new positions), and only goes
+    /// through if every attribute variant of the removed vertex has a partner across the
+    /// collapsed edge, so seams stay intact. Open borders are weighted to stay in place and
+    /// collapses that fold triangles over are rejected, which means meshes with little
+    /// left to remove can stop above the target.
+    pub fn simplify(&self, target_ratio: f32) -> Mesh {
+        let triangle_count = self.indices.len() / 3;
+        let target = ((triangle_count as f32 * target_ratio.clamp(0.0, 1.0)).ceil() as usize).max(1);
+        if target >= triangle_count {
+            return self.clone();
+        }
+
+        // Weld vertices (attribute variants, "wedges") that share a position into groups
+        let mut group_of = Vec::with_capacity(self.vertices.len());
+        let mut positions: Vec<DVec3> = Vec::new();
+        let mut group_lookup: HashMap<[u32; 3], usize> = HashMap::new();
+        for vertex in &self.vertices {
+            // Adding zero folds -0.0 into 0.0 so both weld together
+            let key = (vertex.position + Vec3::ZERO).to_array().map(f32::to_bits);
+            let group = *group_lookup.entry(key).or_insert_with(|| {
+                positions.push(vertex.position.as_dvec3());
+                positions.len() - 1
+            });
+            group_of.push(group);
+        }
+
+        // Triangles that are degenerate once welded (e.g. sphere poles) cover nothing and are dropped
+        let mut triangles: Vec<[usize; 3]> = self
+            .indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]].map(|i| i as usize))
+            .filter(|triangle| triangle.iter().all(|&wedge| wedge < self.vertices.len()))
+            .filter(|triangle| {
+                let [a, b, c] = triangle.map(|wedge| group_of[wedge]);
+                a != b && b != c && a != c
+            })
+            .collect();
+        let mut alive = vec![true; triangles.len()];
+        let mut alive_count = triangles.len();
+
+        // Per-group error quadrics (area-weighted face planes) and triangle adjacency
+        let group_count = positions.len();
+        let mut quadrics = vec![Quadric::default(); group_count];
+        let mut group_triangles: Vec<Vec<usize>> = vec![Vec::new(); group_count];
+        // Undirected edge -> (a triangle using it, use count)
+        let mut edges: HashMap<(usize, usize), (usize, u32)> = HashMap::new();
+        for (index, triangle) in triangles.iter().enumerate() {
+            let groups = triangle.map(|wedge| group_of[wedge]);
+            let [p0, p1, p2] = groups.map(|group| positions[group]);
+            let cross = (p1 - p0).cross(p2 - p0);
+            if let Some(normal) = cross.try_normalize() {
+                let plane = Quadric::from_plane(normal, p0, cross.length() * 0.5);
+                for group in groups {
+                    quadrics[group] += plane;
+                }
+            }
+
+            for i in 0..3 {
+                group_triangles[groups[i]].push(index);
+                let (a, b) = (groups[i], groups[(i + 1) % 3]);
+                edges.entry((a.min(b), a.max(b))).or_insert((index, 0)).1 += 1;
+            }
+        }
+
+        // Open borders get perpendicular planes that resist moving them; non-manifold edges are locked
+        let mut locked = vec![false; group_count];
+        for (&(a, b), &(triangle, uses)) in &edges {
+            match uses {
+                1 => {
+                    let [p0, p1, p2] = triangles[triangle].map(|wedge| positions[group_of[wedge]]);
+                    let edge = positions[b] - positions[a];
+                    if let Some(normal) = edge.cross((p1 - p0).cross(p2 - p0)).try_normalize() {
+                        let plane = Quadric::from_plane(normal, positions[a], edge.length_squared() * SIMPLIFY_BORDER_WEIGHT);
+                        quadrics[a] += plane;
+                        quadrics[b] += plane;
+                    }
+                }
+                2 => {}
+                _ => {
+                    locked[a] = true;
+                    locked[b] = true;
+                }
+            }
+        }
+
+        // Candidates are invalidated lazily: a collapse is stale once either end has changed
+        let mut removed = vec![false; group_count];
+        let mut stamps = vec![0u32; group_count];
+        let mut heap = BinaryHeap::new();
+        let queue = |heap: &mut BinaryHeap<Collapse>, quadrics: &[Quadric], stamps: &[u32], from: usize, to: usize| {
+            if !locked[from] {
+                let cost = (quadrics[from] + quadrics[to]).error(positions[to]);
+                heap.push(Collapse { cost, from, to, stamps: (stamps[from], stamps[to]) });
+            }
+        };
+        for &(a, b) in edges.keys() {
+            queue(&mut heap, &quadrics, &stamps, a, b);
+            queue(&mut heap, &quadrics, &stamps, b, a);
+        }
+
+        let live_triangles = |group: usize, group_triangles: &[Vec<usize>], alive: &[bool]| -> Vec<usize> {
+            group_triangles[group].iter().copied().filter(|&t| alive[t]).collect()
+        };
+        let ring = |group: usize, around: &[usize], triangles: &[[usize; 3]]| -> Vec<usize> {
+            let mut ring: Vec<usize> = around
+                .iter()
+                .flat_map(|&t| triangles[t].map(|wedge| group_of[wedge]))
+                .filter(|&other| other != group)
+                .collect();
+            ring.sort_unstable();
+            ring.dedup();
+            ring
+        };
+
+        while alive_count > target {
+            let Some(Collapse { from: u, to: v, stamps: collapse_stamps, .. }) = heap.pop() else {
+                break;
+            };
+            if removed[u] || removed[v] || collapse_stamps != (stamps[u], stamps[v]) {
+                continue;
+            }
+
+            let around_u = live_triangles(u, &group_triangles, &alive);
+            let (shared, moved): (Vec<usize>, Vec<usize>) = around_u
+                .iter()
+                .partition(|&&t| triangles[t].iter().any(|&wedge| group_of[wedge] == v));
+            if shared.is_empty() {
+                continue;
+            }
+
+            // Each wedge of u continues as the v wedge it shares a triangle with;
+            // a wedge without one sits across a seam that the collapse would tear
+            let mut wedge_map: Vec<(usize, usize)> = Vec::new();
+            for &t in &shared {
+                let find = |group: usize| triangles[t].iter().copied().find(|&wedge| group_of[wedge] == group);
+                if let (Some(from), Some(to)) = (find(u), find(v)) {
+                    if !wedge_map.iter().any(|&(mapped, _)| mapped == from) {
+                        wedge_map.push((from, to));
+                    }
+                }
+            }
+            let map_wedge = |wedge: usize| wedge_map.iter().find(|&&(from, _)| from == wedge).map(|&(_, to)| to);
+            let tears_seam = moved.iter().any(|&t| {
+                triangles[t].iter().any(|&wedge| group_of[wedge] == u && map_wedge(wedge).is_none())
+            });
+            if tears_seam {
+                continue;
+            }
+
+            // Link condition: u and v may only share the neighbors opposite the collapsed edge,
+            // otherwise the result is non-manifold
+            let around_v = live_triangles(v, &group_triangles, &alive);
+            let ring_v = ring(v, &around_v, &triangles);
+            let common = ring(u, &around_u, &triangles)
+                .iter()
+                .filter(|group| ring_v.binary_search(group).is_ok())
+                .count();
+            if common > shared.len() {
+                continue;
+            }
+
+            // Reject collapses that flip or squash the triangles that move with u
+            let folds = moved.iter().any(|&t| {
+                let before = triangles[t].map(|wedge| positions[group_of[wedge]]);
+                let after = triangles[t].map(|wedge| if group_of[wedge] == u { positions[v] } else { positions[group_of[wedge]] });
+                let normal_before = (before[1] - before[0]).cross(before[2] - before[0]);
+                let normal_after = (after[1] - after[0]).cross(after[2] - after[0]);
+                match normal_after.try_normalize() {
+                    Some(normal_after) => normal_before
+                        .try_normalize()
+                        .is_some_and(|normal_before| normal_before.dot(normal_after) < SIMPLIFY_MIN_NORMAL_DOT),
+                    None => true,
+                }
+            });
+            if folds {
+                continue;
+            }
+
+            for &t in &shared {
+                alive[t] = false;
+                alive_count -= 1;
+            }
+            for &t in &moved {
+                for wedge in triangles[t].iter_mut() {
+                    if group_of[*wedge] == u {
+                        *wedge = map_wedge(*wedge).unwrap_or(*wedge);
+                    }
+                }
+                group_triangles[v].push(t);
+            }
+            group_triangles[u].clear();
+            removed[u] = true;
+            let merged = quadrics[u];
+            quadrics[v] += merged;
+            stamps[v] += 1;
+
+            // v's quadric changed, so every candidate touching it is re-queued
+            let around_v = live_triangles(v, &group_triangles, &alive);
+            for neighbor in ring(v, &around_v, &triangles) {
+                queue(&mut heap, &quadrics, &stamps, v, neighbor);
+                queue(&mut heap, &quadrics, &stamps, neighbor, v);
+            }
+        }
+
+        // Compact the surviving wedges
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(alive_count * 3);
+        for (triangle, _) in triangles.iter().zip(&alive).filter(|(_, &alive)| alive) {
+            for &wedge in triangle {
+                if remap[wedge] == u32::MAX {
+                    remap[wedge] = vertices.len() as u32;
+                    vertices.push(self.vertices[wedge]);
+                }
+                indices.push(remap[wedge]);
+            }
+        }
+
+        Mesh { vertices, indices }
+    }
+
     pub fn create_cube() -> Self {
         let vertices = vec![
             // Front face (Z+)
@@ -69,126 +600,174 @@ impl Mesh {
                 position: Vec3::new(-0.5, -0.5, 0.5),
                 normal: Vec3::new(0.0, 0.0, 1.0),
                 uv: Vec2::new(0.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, -0.5, 0.5),
                 normal: Vec3::new(0.0, 0.0, 1.0),
                 uv: Vec2::new(1.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, 0.5, 0.5),
                 normal: Vec3::new(0.0, 0.0, 1.0),
                 uv: Vec2::new(1.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, 0.5, 0.5),
                 normal: Vec3::new(0.0, 0.0, 1.0),
                 uv: Vec2::new(0.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             // Back face (Z-)
             Vertex {
                 position: Vec3::new(0.5, -0.5, -0.5),
                 normal: Vec3::new(0.0, 0.0, -1.0),
                 uv: Vec2::new(0.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, -0.5, -0.5),
                 normal: Vec3::new(0.0, 0.0, -1.0),
                 uv: Vec2::new(1.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, 0.5, -0.5),
                 normal: Vec3::new(0.0, 0.0, -1.0),
                 uv: Vec2::new(1.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, 0.5, -0.5),
                 normal: Vec3::new(0.0, 0.0, -1.0),
                 uv: Vec2::new(0.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             // Top face (Y+)
             Vertex {
                 position: Vec3::new(-0.5, 0.5, 0.5),
                 normal: Vec3::new(0.0, 1.0, 0.0),
                 uv: Vec2::new(0.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, 0.5, 0.5),
                 normal: Vec3::new(0.0, 1.0, 0.0),
                 uv: Vec2::new(1.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, 0.5, -0.5),
                 normal: Vec3::new(0.0, 1.0, 0.0),
                 uv: Vec2::new(1.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, 0.5, -0.5),
                 normal: Vec3::new(0.0, 1.0, 0.0),
                 uv: Vec2::new(0.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             // Bottom face (Y-)
             Vertex {
                 position: Vec3::new(-0.5, -0.5, -0.5),
                 normal: Vec3::new(0.0, -1.0, 0.0),
                 uv: Vec2::new(0.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, -0.5, -0.5),
                 normal: Vec3::new(0.0, -1.0, 0.0),
                 uv: Vec2::new(1.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, -0.5, 0.5),
                 normal: Vec3::new(0.0, -1.0, 0.0),
                 uv: Vec2::new(1.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, -0.5, 0.5),
                 normal: Vec3::new(0.0, -1.0, 0.0),
                 uv: Vec2::new(0.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             // Right face (X+)
             Vertex {
                 position: Vec3::new(0.5, -0.5, 0.5),
                 normal: Vec3::new(1.0, 0.0, 0.0),
                 uv: Vec2::new(0.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, -0.5, -0.5),
                 normal: Vec3::new(1.0, 0.0, 0.0),
                 uv: Vec2::new(1.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, 0.5, -0.5),
                 normal: Vec3::new(1.0, 0.0, 0.0),
                 uv: Vec2::new(1.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(0.5, 0.5, 0.5),
                 normal: Vec3::new(1.0, 0.0, 0.0),
                 uv: Vec2::new(0.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             // Left face (X-)
             Vertex {
                 position: Vec3::new(-0.5, -0.5, -0.5),
                 normal: Vec3::new(-1.0, 0.0, 0.0),
                 uv: Vec2::new(0.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, -0.5, 0.5),
                 normal: Vec3::new(-1.0, 0.0, 0.0),
                 uv: Vec2::new(1.0, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, 0.5, 0.5),
                 normal: Vec3::new(-1.0, 0.0, 0.0),
                 uv: Vec2::new(1.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
             Vertex {
                 position: Vec3::new(-0.5, 0.5, -0.5),
                 normal: Vec3::new(-1.0, 0.0, 0.0),
                 uv: Vec2::new(0.0, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             },
         ];
 
@@ -202,10 +781,12 @@ impl Mesh {
             20, 21, 22, 22, 23, 20, // Left
         ];
 
-        Self { vertices, indices }
+        let mut mesh = Self { vertices, indices };
+        mesh.compute_tangents();
+        mesh
     }
 
-    pub fn create_sphere(radius: f32, segments: u32, rings: u32) -> Self {
+    pub fn create_inverted_sphere(radius: f32, segments: u32, rings: u32) -> Self {
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
 
@@ -225,25 +806,27 @@ impl Mesh {
                 let z = sin_phi * sin_theta;
 
                 let position = Vec3::new(x * radius, y * radius, z * radius);
-                // Normal points outward
-                let normal = Vec3::new(x, y, z);
+                // Inverted normals point inward
+                let normal = Vec3::new(-x, -y, -z);
                 let uv = Vec2::new(segment as f32 / segments as f32, ring as f32 / rings as f32);
 
                 vertices.push(Vertex {
                     position,
                     normal,
                     uv,
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
                 });
             }
         }
 
-        // Generate indices (inverted winding so normals face outward from inside)
+        // Generate indices (reverse winding for inverted sphere)
         for ring in 0..rings {
             for segment in 0..segments {
                 let current = ring * (segments + 1) + segment;
                 let next = current + segments + 1;
 
-                // Inverted triangle winding
+                // Reversed triangle winding for inverted sphere
                 indices.push(current);
                 indices.push(current + 1);
                 indices.push(next);
@@ -257,56 +840,211 @@ impl Mesh {
         Self { vertices, indices }
     }
 
-    pub fn create_inverted_sphere(radius: f32, segments: u32, rings: u32) -> Self {
+    /// Create the default mesh for a built-in primitive shape
+    pub fn create_primitive(shape: PrimitiveShape) -> Self {
+        match shape {
+            PrimitiveShape::Sphere => Self::create_sphere(0.5, 32, 16),
+            PrimitiveShape::Plane => Self::create_plane(1.0, 4),
+            PrimitiveShape::Cylinder => Self::create_cylinder(0.5, 1.0, 32),
+            PrimitiveShape::Torus => Self::create_torus(0.4, 0.1, 32),
+        }
+    }
+
+    /// UV sphere centered at the origin with outward normals
+    pub fn create_sphere(radius: f32, segments: u32, rings: u32) -> Self {
+        let segments = segments.max(3);
+        let rings = rings.max(2);
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
 
-        // Generate vertices
         for ring in 0..=rings {
             let phi = std::f32::consts::PI * ring as f32 / rings as f32;
-            let sin_phi = phi.sin();
-            let cos_phi = phi.cos();
 
             for segment in 0..=segments {
-                let theta = 2.0 * std::f32::consts::PI * segment as f32 / segments as f32;
-                let sin_theta = theta.sin();
-                let cos_theta = theta.cos();
-
-                let x = sin_phi * cos_theta;
-                let y = cos_phi;
-                let z = sin_phi * sin_theta;
-
-                let position = Vec3::new(x * radius, y * radius, z * radius);
-                // Inverted normals point inward
-                let normal = Vec3::new(-x, -y, -z);
-                let uv = Vec2::new(segment as f32 / segments as f32, ring as f32 / rings as f32);
+                let theta = std::f32::consts::TAU * segment as f32 / segments as f32;
+                let normal = Vec3::new(phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin());
 
                 vertices.push(Vertex {
-                    position,
+                    position: normal * radius,
                     normal,
-                    uv,
+                    uv: Vec2::new(segment as f32 / segments as f32, ring as f32 / rings as f32),
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
                 });
             }
         }
 
-        // Generate indices (reverse winding for inverted sphere)
         for ring in 0..rings {
             for segment in 0..segments {
                 let current = ring * (segments + 1) + segment;
                 let next = current + segments + 1;
 
-                // Reversed triangle winding for inverted sphere
-                indices.push(current);
-                indices.push(current + 1);
-                indices.push(next);
+                indices.extend_from_slice(&[current, current + 1, next]);
+                indices.extend_from_slice(&[next, current + 1, next + 1]);
+            }
+        }
 
-                indices.push(next);
-                indices.push(current + 1);
-                indices.push(next + 1);
+        let mut mesh = Self { vertices, indices };
+        mesh.compute_tangents();
+        mesh
+    }
+
+    /// Flat XZ plane facing +Y, split into `subdivisions` cells per side
+    pub fn create_plane(size: f32, subdivisions: u32) -> Self {
+        let cells = subdivisions.max(1);
+        let half = size * 0.5;
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for z in 0..=cells {
+            for x in 0..=cells {
+                let u = x as f32 / cells as f32;
+                let v = z as f32 / cells as f32;
+                vertices.push(Vertex {
+                    position: Vec3::new(-half + size * u, 0.0, -half + size * v),
+                    normal: Vec3::Y,
+                    uv: Vec2::new(u, v),
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
+                });
             }
         }
 
-        Self { vertices, indices }
+        for z in 0..cells {
+            for x in 0..cells {
+                let a = z * (cells + 1) + x;
+                let b = a + 1;
+                let c = a + cells + 1;
+                let d = c + 1;
+
+                indices.extend_from_slice(&[a, c, b]);
+                indices.extend_from_slice(&[b, c, d]);
+            }
+        }
+
+        let mut mesh = Self { vertices, indices };
+        mesh.compute_tangents();
+        mesh
+    }
+
+    /// Capped cylinder along Y, centered at the origin
+    pub fn create_cylinder(radius: f32, height: f32, segments: u32) -> Self {
+        let segments = segments.max(3);
+        let half = height * 0.5;
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        // Side: bottom/top vertex pair per segment (seam duplicated for UVs)
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let theta = std::f32::consts::TAU * u;
+            let normal = Vec3::new(theta.cos(), 0.0, theta.sin());
+
+            vertices.push(Vertex {
+                position: Vec3::new(normal.x * radius, -half, normal.z * radius),
+                normal,
+                uv: Vec2::new(u, 0.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
+            });
+            vertices.push(Vertex {
+                position: Vec3::new(normal.x * radius, half, normal.z * radius),
+                normal,
+                uv: Vec2::new(u, 1.0),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
+            });
+        }
+
+        for segment in 0..segments {
+            let bottom = segment * 2;
+            let top = bottom + 1;
+            let next_bottom = bottom + 2;
+            let next_top = bottom + 3;
+
+            indices.extend_from_slice(&[bottom, top, next_bottom]);
+            indices.extend_from_slice(&[next_bottom, top, next_top]);
+        }
+
+        // Caps: center vertex plus a ring with flat normals
+        for (y, normal) in [(half, Vec3::Y), (-half, Vec3::NEG_Y)] {
+            let center = vertices.len() as u32;
+            vertices.push(Vertex {
+                position: Vec3::new(0.0, y, 0.0),
+                normal,
+                uv: Vec2::new(0.5, 0.5),
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
+            });
+
+            for segment in 0..=segments {
+                let theta = std::f32::consts::TAU * segment as f32 / segments as f32;
+                vertices.push(Vertex {
+                    position: Vec3::new(theta.cos() * radius, y, theta.sin() * radius),
+                    normal,
+                    uv: Vec2::new(0.5 + 0.5 * theta.cos(), 0.5 + 0.5 * theta.sin()),
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
+                });
+            }
+
+            for segment in 0..segments {
+                let current = center + 1 + segment;
+                if normal.y > 0.0 {
+                    indices.extend_from_slice(&[center, current + 1, current]);
+                } else {
+                    indices.extend_from_slice(&[center, current, current + 1]);
+                }
+            }
+        }
+
+        let mut mesh = Self { vertices, indices };
+        mesh.compute_tangents();
+        mesh
+    }
+
+    /// Torus lying in the XZ plane; the tube uses half as many segments as the ring
+    pub fn create_torus(major_radius: f32, minor_radius: f32, segments: u32) -> Self {
+        let segments = segments.max(3);
+        let sides = (segments / 2).max(3);
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for i in 0..=segments {
+            let u = i as f32 / segments as f32;
+            let around = std::f32::consts::TAU * u;
+            let ring_center = Vec3::new(around.cos(), 0.0, around.sin()) * major_radius;
+
+            for j in 0..=sides {
+                let v = j as f32 / sides as f32;
+                let tube = std::f32::consts::TAU * v;
+                let normal = Vec3::new(tube.cos() * around.cos(), tube.sin(), tube.cos() * around.sin());
+
+                vertices.push(Vertex {
+                    position: ring_center + normal * minor_radius,
+                    normal,
+                    uv: Vec2::new(u, v),
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
+                });
+            }
+        }
+
+        for i in 0..segments {
+            for j in 0..sides {
+                let a = i * (sides + 1) + j;
+                let b = a + sides + 1;
+                let c = a + 1;
+                let d = b + 1;
+
+                indices.extend_from_slice(&[a, c, b]);
+                indices.extend_from_slice(&[b, c, d]);
+            }
+        }
+
+        let mut mesh = Self { vertices, indices };
+        mesh.compute_tangents();
+        mesh
     }
 
     pub fn from_obj(path: &str) -> anyhow::Result<Self> {
@@ -348,10 +1086,23 @@ impl Mesh {
                     Vec2::ZERO
                 };
 
+                // Extended `v x y z r g b` lines
+                let color = if mesh.vertex_color.len() >= (i + 1) * 3 {
+                    Vec3::new(
+                        mesh.vertex_color[i * 3],
+                        mesh.vertex_color[i * 3 + 1],
+                        mesh.vertex_color[i * 3 + 2],
+                    )
+                } else {
+                    Vec3::ONE
+                };
+
                 vertices.push(Vertex {
                     position,
                     normal,
                     uv,
+                    tangent: Vec3::ZERO,
+                    color,
                 });
             }
 
@@ -361,7 +1112,124 @@ impl Mesh {
             }
         }
 
-        Ok(Self { vertices, indices })
+        let mut mesh = Self { vertices, indices };
+        mesh.compute_tangents();
+        Ok(mesh)
+    }
+
+    /// Load a glTF 2.0 file (.gltf or .glb), merging all triangle primitives into one mesh.
+    /// Node transforms of the default scene are baked into the vertices.
+    pub fn from_gltf(path: &str) -> anyhow::Result<Self> {
+        let (document, buffers, _images) = gltf::import(path)?;
+
+        let mut mesh = Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        };
+
+        match document.default_scene().or_else(|| document.scenes().next()) {
+            Some(scene) => {
+                for node in scene.nodes() {
+                    mesh.append_gltf_node(path, &node, Mat4::IDENTITY, &buffers);
+                }
+            }
+            None => {
+                // No scene graph - take meshes as-is
+                for gltf_mesh in document.meshes() {
+                    mesh.append_gltf_mesh(path, &gltf_mesh, Mat4::IDENTITY, &buffers);
+                }
+            }
+        }
+
+        if mesh.vertices.is_empty() {
+            anyhow::bail!("glTF file '{}' contains no triangle geometry", path);
+        }
+
+        mesh.compute_tangents();
+        Ok(mesh)
+    }
+
+    fn append_gltf_node(&mut self, path: &str, node: &gltf::Node, parent: Mat4, buffers: &[gltf::buffer::Data]) {
+        let transform = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+
+        if let Some(gltf_mesh) = node.mesh() {
+            self.append_gltf_mesh(path, &gltf_mesh, transform, buffers);
+        }
+
+        for child in node.children() {
+            self.append_gltf_node(path, &child, transform, buffers);
+        }
+    }
+
+    fn append_gltf_mesh(&mut self, path: &str, gltf_mesh: &gltf::Mesh, transform: Mat4, buffers: &[gltf::buffer::Data]) {
+        let normal_matrix = Mat3::from_mat4(transform).inverse().transpose();
+
+        for primitive in gltf_mesh.primitives() {
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                crate::console::warn(format!("glTF '{}': skipping primitive with unsupported mode {:?}", path, primitive.mode()));
+                continue;
+            }
+
+            for (semantic, _) in primitive.attributes() {
+                match semantic {
+                    gltf::Semantic::Positions
+                    | gltf::Semantic::Normals
+                    | gltf::Semantic::TexCoords(0)
+                    | gltf::Semantic::Colors(0) => {}
+                    other => crate::console::warn(format!("glTF '{}': ignoring unsupported vertex attribute {:?}", path, other)),
+                }
+            }
+
+            let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+
+            let positions: Vec<[f32; 3]> = match reader.read_positions() {
+                Some(positions) => positions.collect(),
+                None => {
+                    crate::console::warn(format!("glTF '{}': skipping primitive without positions", path));
+                    continue;
+                }
+            };
+            let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(|n| n.collect());
+            let uvs: Option<Vec<[f32; 2]>> = reader.read_tex_coords(0).map(|t| t.into_f32().collect());
+            // COLOR_0 is linear RGB(A); alpha is dropped
+            let colors: Option<Vec<[f32; 3]>> = reader.read_colors(0).map(|c| c.into_rgb_f32().collect());
+
+            let base_index = self.vertices.len() as u32;
+
+            for (i, position) in positions.iter().enumerate() {
+                let normal = normals
+                    .as_ref()
+                    .and_then(|n| n.get(i))
+                    .map(|n| (normal_matrix * Vec3::from_array(*n)).normalize_or_zero())
+                    .unwrap_or(Vec3::Y);
+
+                // glTF UVs already use a top-left origin, no flip needed
+                let uv = uvs
+                    .as_ref()
+                    .and_then(|t| t.get(i))
+                    .map(|t| Vec2::from_array(*t))
+                    .unwrap_or(Vec2::ZERO);
+
+                let color = colors
+                    .as_ref()
+                    .and_then(|c| c.get(i))
+                    .map(|c| Vec3::from_array(*c))
+                    .unwrap_or(Vec3::ONE);
+
+                self.vertices.push(Vertex {
+                    position: transform.transform_point3(Vec3::from_array(*position)),
+                    normal,
+                    uv,
+                    tangent: Vec3::ZERO,
+                    color,
+                });
+            }
+
+            match reader.read_indices() {
+                Some(indices) => self.indices.extend(indices.into_u32().map(|i| base_index + i)),
+                None => self.indices.extend(base_index..base_index + positions.len() as u32),
+            }
+        }
     }
 
     /// Create a directional light visualization (arrow pointing in light direction)
@@ -386,6 +1254,8 @@ impl Mesh {
                 position: Vec3::new(x, 0.0, z),
                 normal: Vec3::new(x, 0.0, z).normalize(),
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
 
             // Bottom of shaft
@@ -393,6 +1263,8 @@ impl Mesh {
                 position: Vec3::new(x, -shaft_length, z),
                 normal: Vec3::new(x, 0.0, z).normalize(),
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
         }
 
@@ -429,6 +1301,8 @@ impl Mesh {
                 position: Vec3::new(x, cone_base_y, z),
                 normal: Vec3::new(x, 0.5, z).normalize(),
                 uv: Vec2::ZERO,
+                tangent: Vec3::ZERO,
+                color: Vec3::ONE,
             });
         }
 
@@ -438,6 +1312,8 @@ impl Mesh {
             position: Vec3::new(0.0, cone_base_y - cone_height, 0.0),
             normal: Vec3::new(0.0, -1.0, 0.0),
             uv: Vec2::ZERO,
+            tangent: Vec3::ZERO,
+            color: Vec3::ONE,
         });
 
         // Create cone indices
@@ -453,3 +1329,161 @@ impl Mesh {
         Self { vertices, indices }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cube_stats() {
+        let stats = Mesh::create_cube().stats();
+        assert_eq!(stats.vertices, 24); // 4 per face so each face gets its own normal
+        assert_eq!(stats.triangles, 12);
+        assert_eq!(stats.size(), Vec3::ONE);
+    }
+
+    #[test]
+    fn test_simplify_plane_keeps_outline() {
+        let mesh = Mesh::create_plane(2.0, 16); // 512 triangles
+        let simplified = mesh.simplify(0.25);
+        let stats = simplified.stats();
+        assert!(stats.triangles <= 128, "{} triangles left", stats.triangles);
+        assert!(stats.triangles >= 2);
+        // Border planes keep the corners in place
+        assert_eq!(stats.bounds, mesh.calculate_bounds());
+        assert!(simplified.indices.iter().all(|&i| (i as usize) < simplified.vertices.len()));
+    }
+
+    #[test]
+    fn test_simplify_sphere_keeps_shape_and_winding() {
+        let mesh = Mesh::create_sphere(0.5, 32, 16);
+        let simplified = mesh.simplify(0.3);
+        let original = mesh.stats().triangles;
+        let triangles = simplified.stats().triangles;
+        assert!(triangles < original / 2, "{} of {} triangles left", triangles, original);
+        assert!(triangles > 0);
+
+        for triangle in simplified.indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| simplified.vertices[i as usize].position);
+            // Surviving vertices stay on the sphere and faces still point outward
+            assert!((a.length() - 0.5).abs() < 1e-4);
+            assert!((b - a).cross(c - a).dot(a + b + c) >= 0.0);
+        }
+    }
+
+    #[test]
+    fn primitives_wind_counter_clockwise_along_normals() {
+        for shape in PrimitiveShape::ALL {
+            let mesh = Mesh::create_primitive(shape);
+            assert!(!mesh.indices.is_empty(), "{} has no triangles", shape.name());
+
+            for triangle in mesh.indices.chunks_exact(3) {
+                let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| mesh.vertices[i as usize]);
+                let face_normal = (b.position - a.position).cross(c.position - a.position);
+                // Skip degenerate triangles at sphere poles
+                if face_normal.length_squared() < 1e-12 {
+                    continue;
+                }
+                let vertex_normal = a.normal + b.normal + c.normal;
+                assert!(face_normal.dot(vertex_normal) > 0.0, "{} has a back-facing triangle", shape.name());
+            }
+        }
+    }
+
+    #[test]
+    fn tangents_follow_u_and_stay_perpendicular_to_normals() {
+        // The plane's U runs along +X
+        let plane = Mesh::create_plane(1.0, 2);
+        for vertex in &plane.vertices {
+            assert!(vertex.tangent.abs_diff_eq(Vec3::X, 1e-5));
+        }
+
+        for shape in PrimitiveShape::ALL {
+            let mesh = Mesh::create_primitive(shape);
+            for vertex in &mesh.vertices {
+                assert!((vertex.tangent.length() - 1.0).abs() < 1e-4, "{} has a non-unit tangent", shape.name());
+                assert!(vertex.tangent.dot(vertex.normal).abs() < 1e-4, "{} has a tangent along the normal", shape.name());
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_uvs_still_get_a_valid_tangent() {
+        let mut mesh = Mesh::create_plane(1.0, 1);
+        for vertex in &mut mesh.vertices {
+            vertex.uv = Vec2::ZERO;
+        }
+        mesh.compute_tangents();
+
+        for vertex in &mesh.vertices {
+            assert!((vertex.tangent.length() - 1.0).abs() < 1e-4);
+            assert!(vertex.tangent.dot(vertex.normal).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn recomputed_cube_normals_are_faceted_below_the_smoothing_angle() {
+        let mut cube = Mesh::create_cube();
+        for vertex in &mut cube.vertices {
+            vertex.normal = Vec3::Y;
+        }
+
+        cube.recompute_normals(false, 0.0);
+        assert_eq!(cube.vertices.len(), 24);
+        for vertex in &cube.vertices {
+            // Face normals are axis-aligned and point outward
+            assert_eq!(vertex.normal.abs().max_element(), 1.0);
+            assert!(vertex.normal.dot(vertex.position) > 0.0);
+        }
+
+        // 90 degree edges are sharper than the threshold, so they stay hard
+        let mut smoothed = cube.clone();
+        smoothed.recompute_normals(true, 60f32.to_radians());
+        for (vertex, faceted) in smoothed.vertices.iter().zip(&cube.vertices) {
+            assert!(vertex.normal.abs_diff_eq(faceted.normal, 1e-6));
+        }
+    }
+
+    #[test]
+    fn smoothing_averages_across_seams_within_the_angle() {
+        let mut cube = Mesh::create_cube();
+        cube.recompute_normals(true, 100f32.to_radians());
+        // Every corner averages its three faces, while UV seams keep their own vertices
+        assert_eq!(cube.vertices.len(), 24);
+        for vertex in &cube.vertices {
+            assert!(vertex.normal.abs_diff_eq(vertex.position.normalize(), 1e-5));
+        }
+
+        let mut sphere = Mesh::create_sphere(0.5, 32, 16);
+        sphere.recompute_normals(false, 0.0);
+        assert!(sphere.vertices.len() > Mesh::create_sphere(0.5, 32, 16).vertices.len());
+        sphere.recompute_normals(true, 45f32.to_radians());
+        for vertex in &sphere.vertices {
+            assert!(vertex.normal.dot(vertex.position.normalize()) > 0.99);
+            assert!(vertex.tangent.dot(vertex.normal).abs() < 1e-4);
+        }
+        assert!(sphere.indices.iter().all(|&i| (i as usize) < sphere.vertices.len()));
+    }
+
+    #[test]
+    fn obj_vertex_colors_are_read_and_default_to_white() {
+        let dir = std::env::temp_dir().join(format!("tribal_obj_colors_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let colored = dir.join("colored.obj");
+        let plain = dir.join("plain.obj");
+        std::fs::write(&colored, "v 0 0 0 1 0 0\nv 1 0 0 0 1 0\nv 0 1 0 0 0 1\nf 1 2 3\n").unwrap();
+        std::fs::write(&plain, "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+
+        let mesh = Mesh::from_obj(colored.to_str().unwrap()).unwrap();
+        assert_eq!(mesh.vertices.len(), 3);
+        let color_at = |position: Vec3| mesh.vertices.iter().find(|vertex| vertex.position == position).map(|vertex| vertex.color);
+        assert_eq!(color_at(Vec3::ZERO), Some(Vec3::X));
+        assert_eq!(color_at(Vec3::X), Some(Vec3::Y));
+        assert_eq!(color_at(Vec3::Y), Some(Vec3::Z));
+
+        let mesh = Mesh::from_obj(plain.to_str().unwrap()).unwrap();
+        assert!(mesh.vertices.iter().all(|vertex| vertex.color == Vec3::ONE));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::{anyhow, bail, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// One upgrade step, rewriting a document from version N to N + 1 in place
+pub type Migration = fn(&mut Value);
+
+/// Current version of a document type: version 1 is the unversioned layout and each
+/// migration moves it up one
+pub const fn current_version(migrations: &[Migration]) -> u32 {
+    migrations.len() as u32 + 1
+}
+
+/// Parse a versioned JSON document, upgrading older layouts first.
+/// `migrations[i]` upgrades version i + 1 to i + 2; documents without a `version` field
+/// are version 1. Returns the document and the version it was stored as.
+/// Errors name the offending field (e.g. `camera.fov: invalid type ...`).
+pub fn load_versioned<T: DeserializeOwned>(content: &str, migrations: &[Migration]) -> Result<(T, u32)> {
+    let mut document: Value = serde_json::from_str(content).map_err(|e| located_error(content, &e))?;
+    let current = current_version(migrations);
+
+    let version = match document.get("version") {
+        None => 1,
+        Some(version) => match version.as_u64() {
+            Some(version) if version >= 1 => version.min(u32::MAX as u64) as u32,
+            _ => bail!("version: expected a positive integer, found {}", version),
+        },
+    };
+    if version > current {
+        bail!("saved by a newer version of the engine (format v{}, this build reads up to v{})", version, current);
+    }
+
+    if version == current {
+        // Parse the original text so error lines match the file
+        let parsed = serde_json::from_str(content).map_err(|e| located_error(content, &e))?;
+        return Ok((parsed, version));
+    }
+
+    for migration in &migrations[version as usize - 1..] {
+        migration(&mut document);
+    }
+    if let Some(fields) = document.as_object_mut() {
+        fields.insert("version".to_string(), Value::from(current));
+    }
+    let upgraded = serde_json::to_string_pretty(&document)?;
+    let parsed = serde_json::from_str(&upgraded)
+        .map_err(|e| anyhow!("after upgrading from v{}: {}", version, located_error(&upgraded, &e)))?;
+    Ok((parsed, version))
+}
+
+/// Add every field of `defaults` that `document` lacks, recursing into objects present
+/// in both. Existing values (including arrays) are never changed.
+pub fn fill_missing_fields(document: &mut Value, defaults: &Value) {
+    let (Some(fields), Some(default_fields)) = (document.as_object_mut(), defaults.as_object()) else {
+        return;
+    };
+    for (key, default) in default_fields {
+        match fields.get_mut(key) {
+            Some(value) => fill_missing_fields(value, default),
+            None => {
+                fields.insert(key.clone(), default.clone());
+            }
+        }
+    }
+}
+
+/// Prefix a serde error with the path of the field it occurred in
+fn located_error(content: &str, error: &serde_json::Error) -> anyhow::Error {
+    match field_path(content, error.line(), error.column()) {
+        Some(path) => anyhow!("{}: {}", path, error),
+        None => anyhow!("{}", error),
+    }
+}
+
+enum Container {
+    Object { key: Option<String> },
+    Array { index: usize },
+}
+
+/// Path of the value being read at `line`/`column` (1-based, as reported by serde_json),
+/// e.g. `objects[2].transform.position`. None at the top level.
+fn field_path(content: &str, line: usize, column: usize) -> Option<String> {
+    let mut stack: Vec<Container> = Vec::new();
+    let mut expecting_key = false;
+    let mut chars = content
+        .lines()
+        .take(line)
+        .enumerate()
+        .flat_map(|(i, text)| {
+            let end = if i + 1 == line { column.min(text.chars().count()) } else { usize::MAX };
+            text.chars().take(end).chain(std::iter::once('\n'))
+        });
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => {
+                            chars.next();
+                        }
+                        _ => text.push(c),
+                    }
+                }
+                if expecting_key {
+                    if let Some(Container::Object { key }) = stack.last_mut() {
+                        *key = Some(text);
+                    }
+                    expecting_key = false;
+                }
+            }
+            '{' => {
+                stack.push(Container::Object { key: None });
+                expecting_key = true;
+            }
+            '[' => stack.push(Container::Array { index: 0 }),
+            '}' | ']' => {
+                stack.pop();
+                expecting_key = false;
+            }
+            ',' => match stack.last_mut() {
+                Some(Container::Object { key }) => {
+                    *key = None;
+                    expecting_key = true;
+                }
+                Some(Container::Array { index }) => *index += 1,
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    let mut path = String::new();
+    for container in &stack {
+        match container {
+            Container::Object { key: Some(key) } => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+            }
+            Container::Object { key: None } => {}
+            Container::Array { index } => path.push_str(&format!("[{}]", index)),
+        }
+    }
+    (!path.is_empty()).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Doc {
+        version: u32,
+        name: String,
+        #[serde(default)]
+        sizes: Vec<f32>,
+    }
+
+    fn rename_title(document: &mut Value) {
+        if let Some(title) = document.as_object_mut().and_then(|fields| fields.remove("title")) {
+            document["name"] = title;
+        }
+    }
+
+    #[test]
+    fn test_older_documents_are_upgraded_step_by_step() {
+        let migrations: &[Migration] = &[rename_title];
+        let (doc, version) = load_versioned::<Doc>(r#"{"title": "Hull"}"#, migrations).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(doc, Doc { version: 2, name: "Hull".to_string(), sizes: Vec::new() });
+
+        // Current documents are read as they are; newer ones are refused
+        let (doc, version) = load_versioned::<Doc>(r#"{"version": 2, "name": "Hull"}"#, migrations).unwrap();
+        assert_eq!((doc.name.as_str(), version), ("Hull", 2));
+        let error = load_versioned::<Doc>(r#"{"version": 3, "name": "Hull"}"#, migrations).unwrap_err();
+        assert!(error.to_string().contains("newer version"));
+    }
+
+    #[test]
+    fn test_errors_name_the_offending_field() {
+        let content = "{\n  \"version\": 1,\n  \"name\": \"Hull\",\n  \"sizes\": [1.0, \"big\"]\n}";
+        let error = load_versioned::<Doc>(content, &[]).unwrap_err().to_string();
+        assert!(error.starts_with("sizes[1]: invalid type"), "{}", error);
+        assert!(error.contains("line 4"), "{}", error);
+
+        let error = load_versioned::<Doc>(r#"{"version": 1, "name": 7}"#, &[]).unwrap_err().to_string();
+        assert!(error.starts_with("name: invalid type"), "{}", error);
+    }
+
+    #[test]
+    fn test_fill_missing_fields_keeps_existing_values() {
+        let mut document = serde_json::json!({"camera": {"fov": 60.0}, "bookmarks": [1]});
+        let defaults = serde_json::json!({"camera": {"fov": 45.0, "near": 0.1}, "bookmarks": [], "fog": {"on": false}});
+        fill_missing_fields(&mut document, &defaults);
+        assert_eq!(
+            document,
+            serde_json::json!({"camera": {"fov": 60.0, "near": 0.1}, "bookmarks": [1], "fog": {"on": false}})
+        );
+    }
+}
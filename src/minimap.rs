@@ -0,0 +1,203 @@
+use glam::{DVec3, Mat4, Vec2, Vec3};
+use crate::ecs::components::{Position, Unit};
+use crate::game::Game;
+
+/// Width and height of the minimap texture in pixels
+pub const MINIMAP_SIZE: u32 = 256;
+/// Most markers drawn in one frame (the rest are left off the map)
+pub const MAX_MARKERS: usize = 1024;
+/// Empty border around the fitted content, as a share of its extent
+const FIT_PADDING: f64 = 0.1;
+/// Smallest half-width of the mapped area, so a lone unit isn't blown up to fill the map
+const MIN_HALF_EXTENT: f64 = 25.0;
+
+/// Marker diameters in minimap pixels
+const UNIT_MARKER_SIZE: f32 = 10.0;
+const SCENERY_MARKER_SIZE: f32 = 5.0;
+const CAMERA_MARKER_SIZE: f32 = 8.0;
+
+const PLAYER_COLOR: Vec3 = Vec3::new(0.2, 0.9, 0.3);
+/// AI factions by their place in the game manager's list
+const AI_FACTION_COLORS: [Vec3; 4] = [
+    Vec3::new(0.95, 0.25, 0.2),
+    Vec3::new(0.25, 0.5, 1.0),
+    Vec3::new(1.0, 0.75, 0.1),
+    Vec3::new(0.8, 0.3, 0.9),
+];
+/// Units whose faction is neither the player's nor an AI's
+const NEUTRAL_COLOR: Vec3 = Vec3::new(0.6, 0.6, 0.6);
+const SCENERY_COLOR: Vec3 = Vec3::new(0.35, 0.38, 0.42);
+const CAMERA_COLOR: Vec3 = Vec3::ONE;
+
+/// The minimap's camera: a square, top-down orthographic view of the XZ plane with -Z at
+/// the top. It's centered on the content in 64-bit world space, and markers are uploaded
+/// relative to `center` so far-out scenes keep their precision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapView {
+    pub center: DVec3,
+    /// Half the side length of the mapped square, in world units
+    pub half_extent: f64,
+}
+
+impl MinimapView {
+    /// The square that fits every point's XZ position, padded, and at least `MIN_HALF_EXTENT`
+    /// wide. Centered on the origin when there are no points
+    pub fn fit(points: impl IntoIterator<Item = DVec3>) -> Self {
+        let bounds = points
+            .into_iter()
+            .map(|point| (point, point))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)));
+        let Some((min, max)) = bounds else {
+            return Self { center: DVec3::ZERO, half_extent: MIN_HALF_EXTENT };
+        };
+
+        let size = max - min;
+        Self {
+            center: (min + max) * 0.5,
+            half_extent: (size.x.max(size.z) * 0.5 * (1.0 + FIT_PADDING)).max(MIN_HALF_EXTENT),
+        }
+    }
+
+    /// The minimap's view (looking down -Y, -Z up) and orthographic projection, for
+    /// positions relative to `center`. Heights only affect depth, which the marker
+    /// shader doesn't use
+    pub fn view_projection(&self) -> Mat4 {
+        let half = self.half_extent as f32;
+        let view = Mat4::look_at_rh(Vec3::Y, Vec3::ZERO, Vec3::NEG_Z);
+        let mut proj = Mat4::orthographic_rh(-half, half, -half, half, -1.0, 1.0);
+        // Flip Y for Vulkan coordinate system
+        proj.y_axis.y *= -1.0;
+        proj * view
+    }
+
+    /// World position under a map position (0..1 from the top-left corner) at `height`
+    pub fn map_to_world(&self, map: Vec2, height: f64) -> DVec3 {
+        let relative = (map.as_dvec2() * 2.0 - 1.0) * self.half_extent;
+        DVec3::new(self.center.x + relative.x, height, self.center.z + relative.y)
+    }
+
+    /// The view over the shown scene meshes and every unit
+    pub fn for_game(game: &Game) -> Self {
+        let scenery = game.shown_object_bounds().flat_map(|(min, max)| [min.as_dvec3(), max.as_dvec3()]);
+        let units: Vec<DVec3> = game
+            .ecs_world
+            .world
+            .query::<(&Position, &Unit)>()
+            .iter()
+            .map(|(_, (position, _))| position.0)
+            .collect();
+        Self::fit(scenery.chain(units))
+    }
+}
+
+/// One dot on the minimap (per-instance vertex data of the minimap pass)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MinimapMarker {
+    /// World position relative to `MinimapView::center`
+    pub position: Vec3,
+    /// Diameter in minimap pixels
+    pub size: f32,
+    pub color: Vec3,
+}
+
+/// Marker color of a unit's faction
+pub fn faction_color(faction: &str, player_faction: &str, ai_factions: &[String]) -> Vec3 {
+    if faction == player_faction {
+        return PLAYER_COLOR;
+    }
+    ai_factions
+        .iter()
+        .position(|ai| ai == faction)
+        .map_or(NEUTRAL_COLOR, |index| AI_FACTION_COLORS[index % AI_FACTION_COLORS.len()])
+}
+
+/// Markers for the scene meshes (dim), units (by faction) and the main camera (white),
+/// in draw order, at most `MAX_MARKERS`
+pub fn markers(game: &Game, view: &MinimapView) -> Vec<MinimapMarker> {
+    let marker = |position: DVec3, size: f32, color: Vec3| MinimapMarker {
+        position: (position - view.center).as_vec3(),
+        size,
+        color,
+    };
+
+    let mut markers: Vec<MinimapMarker> = game
+        .shown_object_bounds()
+        .map(|(min, max)| marker(((min + max) * 0.5).as_dvec3(), SCENERY_MARKER_SIZE, SCENERY_COLOR))
+        .collect();
+
+    let manager = &game.game_manager;
+    for (_, (position, unit)) in game.ecs_world.world.query::<(&Position, &Unit)>().iter() {
+        let color = faction_color(&unit.faction, &manager.player_faction, &manager.ai_factions);
+        markers.push(marker(position.0, UNIT_MARKER_SIZE, color));
+    }
+
+    // The camera goes on top, so keep room for it
+    markers.truncate(MAX_MARKERS - 1);
+    markers.push(marker(game.camera.world_position(), CAMERA_MARKER_SIZE, CAMERA_COLOR));
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_covers_the_points_with_a_minimum_size() {
+        let view = MinimapView::fit([DVec3::new(-100.0, 5.0, 20.0), DVec3::new(300.0, -5.0, 60.0)]);
+        assert_eq!(view.center, DVec3::new(100.0, 0.0, 40.0));
+        assert!((view.half_extent - 200.0 * (1.0 + FIT_PADDING)).abs() < 1e-9);
+
+        let lone = MinimapView::fit([DVec3::new(1e9, 0.0, -1e9)]);
+        assert_eq!(lone.center, DVec3::new(1e9, 0.0, -1e9));
+        assert_eq!(lone.half_extent, MIN_HALF_EXTENT);
+
+        assert_eq!(MinimapView::fit([]).center, DVec3::ZERO);
+    }
+
+    #[test]
+    fn map_positions_resolve_far_from_the_origin() {
+        let view = MinimapView { center: DVec3::new(5e8, 0.0, -5e8), half_extent: 1000.0 };
+        let point = DVec3::new(5e8 + 250.0, 12.0, -5e8 - 500.0);
+        assert!((view.map_to_world(Vec2::new(0.625, 0.25), 12.0) - point).length() < 1e-3);
+
+        // -Z is the top of the map
+        assert!(view.map_to_world(Vec2::new(0.5, 0.0), 0.0).z < view.center.z);
+    }
+
+    #[test]
+    fn projection_matches_map_positions() {
+        let view = MinimapView { center: DVec3::new(-40.0, 0.0, 70.0), half_extent: 80.0 };
+        let map = Vec2::new(0.375, 0.8);
+        let point = view.map_to_world(map, 30.0);
+
+        let clip = view.view_projection() * (point - view.center).as_vec3().extend(1.0);
+        let from_clip = (clip.truncate().truncate() / clip.w + 1.0) * 0.5;
+        assert!(from_clip.abs_diff_eq(map, 1e-5));
+    }
+
+    #[test]
+    fn panning_centers_the_view_on_the_clicked_point() {
+        let mut game = Game::new();
+        game.camera = crate::core::Camera::new(Vec3::new(0.0, 10.0, 0.0));
+        game.camera.set_rotation(-std::f32::consts::FRAC_PI_4, 0.0, 0.0);
+
+        let point = DVec3::new(100.0, 0.0, 50.0);
+        game.pan_camera_to(point);
+
+        let position = game.camera.world_position();
+        let forward = (game.camera.rotation() * Vec3::NEG_Z).as_dvec3();
+        let hit = position + forward * (-position.y / forward.y);
+        assert!((position.y - 10.0).abs() < 1e-6);
+        assert!((hit - point).length() < 1e-3);
+    }
+
+    #[test]
+    fn factions_get_distinct_colors() {
+        let ai = vec!["Red Team".to_string(), "Blue Team".to_string()];
+        assert_eq!(faction_color("Player", "Player", &ai), PLAYER_COLOR);
+        assert_eq!(faction_color("Red Team", "Player", &ai), AI_FACTION_COLORS[0]);
+        assert_eq!(faction_color("Blue Team", "Player", &ai), AI_FACTION_COLORS[1]);
+        assert_eq!(faction_color("Pirates", "Player", &ai), NEUTRAL_COLOR);
+    }
+}
@@ -0,0 +1,142 @@
+//! Collision checks for planned ship moves
+//!
+//! Samples the Bezier path and tests the moving ship's bounds against other units
+
+use glam::{DMat3, DQuat, DVec3, Vec3};
+
+use crate::ecs::components::MovementCurve;
+
+/// Number of path segments checked (matches the drawn path)
+pub const PATH_SEGMENTS: usize = 32;
+
+/// World-space axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: DVec3,
+    pub max: DVec3,
+}
+
+impl Aabb {
+    /// World box enclosing local bounds placed by a position, rotation and scale
+    pub fn from_local(bounds_min: Vec3, bounds_max: Vec3, position: DVec3, rotation: DQuat, scale: DVec3) -> Self {
+        let center = (bounds_min + bounds_max).as_dvec3() * 0.5 * scale;
+        let half_extents = (bounds_max - bounds_min).as_dvec3() * 0.5 * scale;
+
+        // Extents of the rotated box along each world axis
+        let rotation = DMat3::from_quat(rotation);
+        let abs_rotation = DMat3::from_cols(rotation.x_axis.abs(), rotation.y_axis.abs(), rotation.z_axis.abs());
+        let world_center = position + rotation * center;
+        let world_half = abs_rotation * half_extents;
+
+        Self {
+            min: world_center - world_half,
+            max: world_center + world_half,
+        }
+    }
+
+    /// Boxes overlap (touching faces don't count)
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.cmplt(other.max).all() && other.min.cmplt(self.max).all()
+    }
+}
+
+/// Result of checking a planned path against obstacles
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathCollision {
+    /// Per segment (`PATH_SEGMENTS` of them): does the ship hit an obstacle along it?
+    pub blocked_segments: Vec<bool>,
+    /// Curve parameter of the last sample before the first hit (1.0 when the path is clear)
+    pub last_safe_t: f64,
+}
+
+impl PathCollision {
+    pub fn is_blocked(&self) -> bool {
+        self.last_safe_t < 1.0
+    }
+}
+
+/// Ship being moved: local bounds, scale and the rotations at either end of the move
+#[derive(Debug, Clone, Copy)]
+pub struct MovingBody {
+    pub bounds_min: Vec3,
+    pub bounds_max: Vec3,
+    pub scale: DVec3,
+    pub start_rotation: DQuat,
+    pub end_rotation: DQuat,
+}
+
+impl MovingBody {
+    /// World bounds at curve parameter `t` (rotation eases from start to end)
+    pub fn bounds_at(&self, curve: &MovementCurve, t: f64) -> Aabb {
+        let rotation = self.start_rotation.slerp(self.end_rotation, t);
+        Aabb::from_local(self.bounds_min, self.bounds_max, curve.evaluate(t), rotation, self.scale)
+    }
+}
+
+/// Sample the path and flag the segments where the ship overlaps an obstacle
+/// Obstacles the ship already overlaps at the start are ignored, so touching units can still move apart
+pub fn check_path(curve: &MovementCurve, body: &MovingBody, obstacles: &[Aabb]) -> PathCollision {
+    let start = body.bounds_at(curve, 0.0);
+    let obstacles: Vec<&Aabb> = obstacles.iter().filter(|obstacle| !obstacle.overlaps(&start)).collect();
+
+    let mut blocked_segments = Vec::with_capacity(PATH_SEGMENTS);
+    let mut last_safe_t = 1.0;
+    for segment in 0..PATH_SEGMENTS {
+        let t = (segment + 1) as f64 / PATH_SEGMENTS as f64;
+        let bounds = body.bounds_at(curve, t);
+        let blocked = obstacles.iter().any(|obstacle| obstacle.overlaps(&bounds));
+        if blocked && last_safe_t == 1.0 {
+            last_safe_t = segment as f64 / PATH_SEGMENTS as f64;
+        }
+        blocked_segments.push(blocked);
+    }
+
+    PathCollision { blocked_segments, last_safe_t }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_body() -> MovingBody {
+        MovingBody {
+            bounds_min: Vec3::splat(-1.0),
+            bounds_max: Vec3::splat(1.0),
+            scale: DVec3::ONE,
+            start_rotation: DQuat::IDENTITY,
+            end_rotation: DQuat::IDENTITY,
+        }
+    }
+
+    #[test]
+    fn test_rotated_bounds_grow() {
+        let rotation = DQuat::from_rotation_y(std::f64::consts::FRAC_PI_4);
+        let aabb = Aabb::from_local(Vec3::splat(-1.0), Vec3::splat(1.0), DVec3::ZERO, rotation, DVec3::ONE);
+        assert!((aabb.max.x - std::f64::consts::SQRT_2).abs() < 1e-9);
+        assert!((aabb.max.y - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_path_blocked_by_unit() {
+        let curve = MovementCurve::new(DVec3::ZERO, DVec3::new(0.0, 0.0, -20.0), DVec3::new(0.0, 0.0, -10.0));
+        let blocker = Aabb::from_local(Vec3::splat(-1.0), Vec3::splat(1.0), DVec3::new(0.0, 0.0, -10.0), DQuat::IDENTITY, DVec3::ONE);
+
+        let result = check_path(&curve, &unit_body(), &[blocker]);
+        assert!(result.is_blocked());
+        assert_eq!(result.blocked_segments.len(), PATH_SEGMENTS);
+        // The ship stops short of the blocker's near face
+        let safe = unit_body().bounds_at(&curve, result.last_safe_t);
+        assert!(!safe.overlaps(&blocker));
+        assert!(result.last_safe_t > 0.0);
+
+        // Off to the side the path is clear
+        let aside = Aabb::from_local(Vec3::splat(-1.0), Vec3::splat(1.0), DVec3::new(10.0, 0.0, -10.0), DQuat::IDENTITY, DVec3::ONE);
+        let clear = check_path(&curve, &unit_body(), &[aside]);
+        assert!(!clear.is_blocked());
+        assert!(clear.blocked_segments.iter().all(|blocked| !blocked));
+
+        // A unit already touching the ship at the start doesn't block it
+        let touching = Aabb::from_local(Vec3::splat(-1.0), Vec3::splat(1.0), DVec3::new(0.5, 0.0, 0.0), DQuat::IDENTITY, DVec3::ONE);
+        assert!(!check_path(&curve, &unit_body(), &[touching]).is_blocked());
+    }
+}
@@ -52,6 +52,15 @@ pub enum MovementEvent {
         ship_id: u64,
         timestamp: f64,
     },
+
+    /// Planned path ran into another unit
+    MovementBlocked {
+        turn: u32,
+        ship_id: u64,
+        planned_position: DVec3,
+        safe_position: Option<DVec3>, // Where the move was clamped to, None if it was refused
+        timestamp: f64,
+    },
 }
 
 impl MovementEvent {
@@ -63,6 +72,7 @@ impl MovementEvent {
             MovementEvent::WidgetRotationChanged { turn, .. } => *turn,
             MovementEvent::MovementConfirmed { turn, .. } => *turn,
             MovementEvent::MovementCancelled { turn, .. } => *turn,
+            MovementEvent::MovementBlocked { turn, .. } => *turn,
         }
     }
 
@@ -74,6 +84,7 @@ impl MovementEvent {
             MovementEvent::WidgetRotationChanged { timestamp, .. } => *timestamp,
             MovementEvent::MovementConfirmed { timestamp, .. } => *timestamp,
             MovementEvent::MovementCancelled { timestamp, .. } => *timestamp,
+            MovementEvent::MovementBlocked { timestamp, .. } => *timestamp,
         }
     }
 
@@ -85,6 +96,7 @@ impl MovementEvent {
             MovementEvent::WidgetRotationChanged { ship_id, .. } => *ship_id,
             MovementEvent::MovementConfirmed { ship_id, .. } => *ship_id,
             MovementEvent::MovementCancelled { ship_id, .. } => *ship_id,
+            MovementEvent::MovementBlocked { ship_id, .. } => *ship_id,
         }
     }
 }
@@ -215,6 +227,19 @@ impl MovementEventRecorder {
         self.events.push(event);
     }
 
+    /// Record a planned move that was blocked by another unit (and where it stopped, if clamped)
+    pub fn record_movement_blocked(&mut self, ship_id: Entity, planned_position: DVec3, safe_position: Option<DVec3>) {
+        let event = MovementEvent::MovementBlocked {
+            turn: self.current_turn,
+            ship_id: Self::entity_to_u64(ship_id),
+            planned_position,
+            safe_position,
+            timestamp: Self::get_timestamp(),
+        };
+
+        self.events.push(event);
+    }
+
     /// Get all events for current turn
     pub fn get_turn_events(&self) -> Vec<&MovementEvent> {
         self.events
@@ -228,6 +253,14 @@ impl MovementEventRecorder {
         &self.events
     }
 
+    /// Follow the game's turn counter
+    pub fn set_turn(&mut self, turn: u32) {
+        if turn != self.current_turn {
+            self.current_turn = turn;
+            self.last_record_time = 0.0;
+        }
+    }
+
     /// Advance to next turn
     pub fn next_turn(&mut self) {
         self.current_turn += 1;
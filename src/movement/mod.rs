@@ -2,6 +2,10 @@
 ///
 /// Handles ship movement planning and Bezier curve-based motion
 
+pub mod collision;
 pub mod events;
+pub mod replay;
 
+pub use collision::{Aabb, MovingBody, PathCollision};
 pub use events::{MovementEvent, MovementEventRecorder};
+pub use replay::{MovementReplay, ReplayPlayer};
@@ -0,0 +1,265 @@
+//! Turn-by-turn replay of recorded movement
+//!
+//! Rebuilds ship poses at every turn boundary from confirmed moves, and in between
+//! follows each move's Bezier curve, so a battle can be scrubbed without touching the ECS
+
+use glam::{DQuat, DVec3};
+use std::collections::{BTreeMap, HashMap};
+
+use super::MovementEvent;
+use crate::ecs::components::MovementCurve;
+
+/// Where a ship is at one moment of the replay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipPose {
+    pub position: DVec3,
+    pub rotation: DQuat,
+}
+
+/// One ship's confirmed move within a turn
+#[derive(Debug, Clone, Copy)]
+struct ReplayMove {
+    curve: MovementCurve,
+    start_rotation: DQuat,
+    end_rotation: DQuat,
+}
+
+impl ReplayMove {
+    fn pose_at(&self, t: f64) -> ShipPose {
+        ShipPose {
+            position: self.curve.evaluate(t),
+            rotation: self.start_rotation.slerp(self.end_rotation, t),
+        }
+    }
+}
+
+/// Recorded turns in order, with a snapshot of every ship at each turn boundary
+pub struct MovementReplay {
+    /// Recorded turn number and the moves confirmed in it
+    turns: Vec<(u32, HashMap<u64, ReplayMove>)>,
+    /// Ship poses before the first turn, then after each turn (`turns.len() + 1` entries)
+    snapshots: Vec<HashMap<u64, ShipPose>>,
+}
+
+impl MovementReplay {
+    /// Replay the confirmed moves in `events` (a later confirmation in the same turn replaces an earlier one)
+    pub fn from_events(events: &[MovementEvent]) -> Self {
+        let mut by_turn: BTreeMap<u32, HashMap<u64, ReplayMove>> = BTreeMap::new();
+        for event in events {
+            if let MovementEvent::MovementConfirmed {
+                turn,
+                ship_id,
+                start_position,
+                end_position,
+                control_point,
+                start_rotation,
+                end_rotation,
+                ..
+            } = event
+            {
+                by_turn.entry(*turn).or_default().insert(
+                    *ship_id,
+                    ReplayMove {
+                        curve: MovementCurve::new(*start_position, *end_position, *control_point),
+                        start_rotation: *start_rotation,
+                        end_rotation: *end_rotation,
+                    },
+                );
+            }
+        }
+        let turns: Vec<(u32, HashMap<u64, ReplayMove>)> = by_turn.into_iter().collect();
+
+        // Every ship starts where its first recorded move starts, and holds still in turns it didn't move
+        let mut initial = HashMap::new();
+        for (_, moves) in &turns {
+            for (ship_id, replay_move) in moves {
+                initial.entry(*ship_id).or_insert_with(|| replay_move.pose_at(0.0));
+            }
+        }
+        let mut snapshots = vec![initial];
+        for (_, moves) in &turns {
+            let mut snapshot = snapshots[snapshots.len() - 1].clone();
+            for (ship_id, replay_move) in moves {
+                snapshot.insert(*ship_id, replay_move.pose_at(1.0));
+            }
+            snapshots.push(snapshot);
+        }
+
+        Self { turns, snapshots }
+    }
+
+    /// Number of recorded turns
+    pub fn turn_count(&self) -> usize {
+        self.turns.len()
+    }
+
+    /// Game turn number of the recorded turn at `index`
+    pub fn turn_number(&self, index: usize) -> Option<u32> {
+        self.turns.get(index).map(|(turn, _)| *turn)
+    }
+
+    /// Every ship that moved at some point
+    pub fn ship_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.snapshots[0].keys().copied()
+    }
+
+    /// Ship poses `time` turns into the replay (0 = before the first turn, `turn_count()` = after the last)
+    /// Fractional times are partway along that turn's moves
+    pub fn poses_at(&self, time: f64) -> HashMap<u64, ShipPose> {
+        let time = time.clamp(0.0, self.turn_count() as f64);
+        let boundary = time.floor() as usize;
+        let mut poses = self.snapshots[boundary].clone();
+        if let Some((_, moves)) = self.turns.get(boundary) {
+            let t = time - boundary as f64;
+            for (ship_id, replay_move) in moves {
+                poses.insert(*ship_id, replay_move.pose_at(t));
+            }
+        }
+        poses
+    }
+}
+
+/// Playback controller for a `MovementReplay` (play/pause, stepping and scrubbing)
+pub struct ReplayPlayer {
+    pub replay: MovementReplay,
+    /// Playback position in turns (0 = before the first recorded turn)
+    pub time: f64,
+    pub playing: bool,
+    /// Real seconds each turn takes to play
+    pub seconds_per_turn: f32,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: MovementReplay) -> Self {
+        Self {
+            replay,
+            time: 0.0,
+            playing: false,
+            seconds_per_turn: 2.0,
+        }
+    }
+
+    /// Last playback position
+    pub fn end_time(&self) -> f64 {
+        self.replay.turn_count() as f64
+    }
+
+    /// Advance playback, stopping at the end
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.playing {
+            return;
+        }
+        self.time += (delta_time / self.seconds_per_turn.max(0.01)) as f64;
+        if self.time >= self.end_time() {
+            self.time = self.end_time();
+            self.playing = false;
+        }
+    }
+
+    /// Start or pause playback (playing from the end starts over)
+    pub fn toggle_playing(&mut self) {
+        if !self.playing && self.time >= self.end_time() {
+            self.time = 0.0;
+        }
+        self.playing = !self.playing;
+    }
+
+    /// Pause and jump to the next (positive) or previous (negative) turn boundary
+    pub fn step(&mut self, turns: i32) {
+        self.playing = false;
+        let boundary = if turns > 0 {
+            self.time.floor() + turns as f64
+        } else {
+            self.time.ceil() + turns as f64
+        };
+        self.time = boundary.clamp(0.0, self.end_time());
+    }
+
+    /// Pause and move to `time` turns into the replay
+    pub fn seek(&mut self, time: f64) {
+        self.playing = false;
+        self.time = time.clamp(0.0, self.end_time());
+    }
+
+    /// Ship poses at the playback position
+    pub fn poses(&self) -> HashMap<u64, ShipPose> {
+        self.replay.poses_at(self.time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmed(turn: u32, ship_id: u64, start: DVec3, end: DVec3) -> MovementEvent {
+        MovementEvent::MovementConfirmed {
+            turn,
+            ship_id,
+            start_position: start,
+            end_position: end,
+            control_point: (start + end) * 0.5,
+            last_velocity: DVec3::ZERO,
+            start_rotation: DQuat::IDENTITY,
+            end_rotation: DQuat::from_rotation_y(1.0),
+            timestamp: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_replay_rebuilds_turn_boundaries() {
+        let events = vec![
+            MovementEvent::MovementPlanningStarted { turn: 1, ship_id: 1, timestamp: 0.0 },
+            confirmed(1, 1, DVec3::ZERO, DVec3::new(10.0, 0.0, 0.0)),
+            // Ship 2 sits out turn 1 and first moves in turn 2
+            confirmed(2, 2, DVec3::new(0.0, 0.0, 50.0), DVec3::new(0.0, 0.0, 40.0)),
+        ];
+        let replay = MovementReplay::from_events(&events);
+        assert_eq!(replay.turn_count(), 2);
+        assert_eq!(replay.turn_number(1), Some(2));
+
+        let start = replay.poses_at(0.0);
+        assert_eq!(start[&1].position, DVec3::ZERO);
+        assert_eq!(start[&2].position, DVec3::new(0.0, 0.0, 50.0));
+
+        // Halfway through turn 1 only ship 1 is moving, along its curve
+        let mid = replay.poses_at(0.5);
+        assert!((mid[&1].position - DVec3::new(5.0, 0.0, 0.0)).length() < 1e-9);
+        assert_eq!(mid[&2].position, DVec3::new(0.0, 0.0, 50.0));
+
+        // Ship 1 holds its end pose through turn 2, and times past the end clamp
+        let end = replay.poses_at(99.0);
+        assert_eq!(end[&1].position, DVec3::new(10.0, 0.0, 0.0));
+        assert_eq!(end[&2].position, DVec3::new(0.0, 0.0, 40.0));
+        assert!(end[&1].rotation.abs_diff_eq(DQuat::from_rotation_y(1.0), 1e-9));
+    }
+
+    #[test]
+    fn test_player_steps_and_plays_to_the_end() {
+        let events = vec![
+            confirmed(1, 1, DVec3::ZERO, DVec3::X),
+            confirmed(2, 1, DVec3::X, DVec3::Y),
+            confirmed(3, 1, DVec3::Y, DVec3::Z),
+        ];
+        let mut player = ReplayPlayer::new(MovementReplay::from_events(&events));
+
+        player.seek(1.5);
+        player.step(1);
+        assert_eq!(player.time, 2.0);
+        player.seek(1.5);
+        player.step(-1);
+        assert_eq!(player.time, 1.0);
+        player.step(-5);
+        assert_eq!(player.time, 0.0);
+
+        player.seconds_per_turn = 1.0;
+        player.toggle_playing();
+        player.update(10.0);
+        assert_eq!(player.time, 3.0);
+        assert!(!player.playing);
+
+        // Playing again from the end starts over
+        player.toggle_playing();
+        assert_eq!(player.time, 0.0);
+        assert!(player.playing);
+    }
+}
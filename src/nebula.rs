@@ -1,8 +1,54 @@
 use ash::vk;
 use glam::{Mat4, Vec2, Vec3};
+use serde::{Deserialize, Serialize};
 
-/// Nebula configuration
-#[derive(Clone, Copy)]
+/// Fewest raymarch steps allowed (below this the nebula breaks up into slices)
+pub const MIN_NEBULA_STEPS: u32 = 8;
+/// Most raymarch steps allowed; the loop runs per pixel, so more risks a GPU timeout
+pub const MAX_NEBULA_STEPS: u32 = 256;
+/// Step count the shader was tuned for
+pub const DEFAULT_NEBULA_STEPS: u32 = 56;
+/// Most nebula volumes drawn per frame; the nearest ones win when a scene has more
+pub const MAX_NEBULAE: usize = 16;
+
+/// Raymarch quality presets shown in the nebula panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NebulaQuality {
+    Low,
+    Medium,
+    High,
+}
+
+impl NebulaQuality {
+    /// All presets, in the order shown in the UI
+    pub const ALL: [NebulaQuality; 3] = [Self::Low, Self::Medium, Self::High];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        }
+    }
+
+    /// Raymarch steps per pixel
+    pub fn steps(&self) -> u32 {
+        match self {
+            Self::Low => 24,
+            Self::Medium => DEFAULT_NEBULA_STEPS,
+            Self::High => 128,
+        }
+    }
+
+    /// The preset with exactly this step count, if any
+    pub fn from_steps(steps: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|quality| quality.steps() == steps)
+    }
+}
+
+/// Nebula configuration (stored per nebula object; the engine config holds the default for new ones)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NebulaConfig {
     // Basic parameters
     pub zoom: f32,
@@ -22,6 +68,9 @@ pub struct NebulaConfig {
 
     // Raymarch distance
     pub max_distance: f32,
+
+    // Raymarch steps per pixel (quality vs. cost)
+    pub steps: u32,
 }
 
 impl From<crate::config::NebulaConfigData> for NebulaConfig {
@@ -38,6 +87,7 @@ impl From<crate::config::NebulaConfigData> for NebulaConfig {
             light_color: data.light_color,
             light_intensity: data.light_intensity,
             max_distance: data.max_distance,
+            steps: data.steps.clamp(MIN_NEBULA_STEPS, MAX_NEBULA_STEPS),
         }
     }
 }
@@ -56,6 +106,7 @@ impl From<&NebulaConfig> for crate::config::NebulaConfigData {
             light_color: config.light_color,
             light_intensity: config.light_intensity,
             max_distance: config.max_distance,
+            steps: config.steps,
         }
     }
 }
@@ -81,6 +132,8 @@ impl Default for NebulaConfig {
 
             // Raymarch distance - 1000x larger
             max_distance: 10000.0,  // Was 10.0
+
+            steps: DEFAULT_NEBULA_STEPS,
         }
     }
 }
@@ -115,9 +168,12 @@ pub struct NebulaUniformBufferObject {
     pub light_color: Vec3,
     pub light_intensity: f32,
 
-    // Raymarch distance
+    // Raymarch distance and loop bound
     pub max_distance: f32,
-    pub _padding5: [f32; 3],
+    pub steps: u32,
+    /// `Camera::log_depth_coefficient`, so the depth test against the scene matches its depth mode
+    pub log_depth: f32,
+    pub _padding5: f32,
 }
 
 /// Nebula renderer managing all nebula-related Vulkan resources
@@ -129,6 +185,8 @@ pub struct NebulaRenderer {
     pub uniform_buffers_memory: Vec<vk::DeviceMemory>,
     pub descriptor_pool: vk::DescriptorPool,
     pub descriptor_sets: Vec<vk::DescriptorSet>,
+    /// Bytes between per-nebula UBO slots (UBO size rounded up to the dynamic offset alignment)
+    pub ubo_stride: vk::DeviceSize,
 }
 
 impl NebulaRenderer {
@@ -170,19 +228,22 @@ impl NebulaRenderer {
             light_color: config.light_color,
             light_intensity: config.light_intensity,
 
-            // Raymarch distance
+            // Raymarch distance and loop bound
             max_distance: config.max_distance,
-            _padding5: [0.0; 3],
+            steps: config.steps.clamp(MIN_NEBULA_STEPS, MAX_NEBULA_STEPS),
+            log_depth: 0.0,
+            _padding5: 0.0,
         }
     }
     
     /// Cleanup Vulkan resources
     pub unsafe fn cleanup(&self, device: &ash::Device) {
-        device.destroy_buffer(self.uniform_buffers[0], None);
-        device.free_memory(self.uniform_buffers_memory[0], None);
-        device.destroy_buffer(self.uniform_buffers[1], None);
-        device.free_memory(self.uniform_buffers_memory[1], None);
-        
+        // One UBO per frame in flight
+        for (buffer, memory) in self.uniform_buffers.iter().zip(&self.uniform_buffers_memory) {
+            device.destroy_buffer(*buffer, None);
+            device.free_memory(*memory, None);
+        }
+
         device.destroy_descriptor_pool(self.descriptor_pool, None);
         device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
         device.destroy_pipeline(self.pipeline, None);
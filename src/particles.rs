@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::scene::ObjectId;
+
+/// Most emitters simulated at once (size of the emitter array in the particle shaders)
+pub const MAX_EMITTERS: usize = 32;
+
+/// Emitters slower than this (world units per second) count as standing still
+pub const MOVING_SPEED: f32 = 0.05;
+
+/// Particle emitter settings stored on emitter objects
+/// Particles leave along the emitter's local -Z (out the back of a ship facing +Z)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ParticleEmitterSettings {
+    /// Particles spawned per second
+    pub rate: f32,
+    /// Seconds each particle lives
+    pub lifetime: f32,
+    /// Initial speed along the emission direction
+    pub speed: f32,
+    /// Half-angle of the emission cone in degrees
+    pub spread: f32,
+    /// Fraction of the emitter's own velocity new particles inherit
+    pub inherit_velocity: f32,
+    pub start_color: Vec3,
+    pub start_alpha: f32,
+    pub end_color: Vec3,
+    pub end_alpha: f32,
+    pub start_size: f32,
+    pub end_size: f32,
+    /// Only emit while the emitter (or the ship it's attached to) is moving
+    pub only_while_moving: bool,
+}
+
+impl Default for ParticleEmitterSettings {
+    /// Engine exhaust: a hot blue-white core cooling to a faint orange trail
+    fn default() -> Self {
+        Self {
+            rate: 200.0,
+            lifetime: 1.2,
+            speed: 4.0,
+            spread: 8.0,
+            inherit_velocity: 0.2,
+            start_color: Vec3::new(0.6, 0.8, 2.0),
+            start_alpha: 1.0,
+            end_color: Vec3::new(1.0, 0.35, 0.05),
+            end_alpha: 0.0,
+            start_size: 0.25,
+            end_size: 0.6,
+            only_while_moving: true,
+        }
+    }
+}
+
+/// An emitter as seen this frame
+#[derive(Debug, Clone, Copy)]
+pub struct EmitterInstance {
+    pub id: ObjectId,
+    /// World position
+    pub position: Vec3,
+    /// Unit emission direction in world space
+    pub direction: Vec3,
+    pub settings: ParticleEmitterSettings,
+}
+
+/// Particles one emitter spawns this frame: `count` ring slots starting at `first`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnBatch {
+    pub first: u32,
+    pub count: u32,
+    /// World-space velocity of the emitter over the last frame
+    pub velocity: Vec3,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EmitterState {
+    /// Fractional particles carried over to the next frame
+    accumulator: f32,
+    last_position: Vec3,
+}
+
+/// CPU side of the GPU particle system
+///
+/// Particles live in a fixed-size ring on the GPU; each frame the spawner hands every
+/// emitter a run of slots to (re)initialize, and the compute shader ages the rest.
+/// When the ring is full the oldest slots are reused, so the particle budget is a hard cap.
+pub struct ParticleSpawner {
+    emitters: HashMap<ObjectId, EmitterState>,
+    /// Next ring slot to hand out
+    cursor: u32,
+}
+
+impl ParticleSpawner {
+    pub fn new() -> Self {
+        Self {
+            emitters: HashMap::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Work out this frame's spawns, one batch per emitter (at most `MAX_EMITTERS`)
+    /// `delta_time` follows the simulation clock, so a paused game spawns nothing
+    pub fn plan(&mut self, emitters: &[EmitterInstance], delta_time: f32, capacity: u32) -> Vec<SpawnBatch> {
+        let emitters = &emitters[..emitters.len().min(MAX_EMITTERS)];
+        self.emitters.retain(|id, _| emitters.iter().any(|emitter| emitter.id == *id));
+        if capacity == 0 {
+            self.cursor = 0;
+            return emitters.iter().map(|_| SpawnBatch { first: 0, count: 0, velocity: Vec3::ZERO }).collect();
+        }
+        self.cursor %= capacity;
+
+        emitters
+            .iter()
+            .map(|emitter| {
+                let state = self.emitters.entry(emitter.id).or_insert(EmitterState {
+                    accumulator: 0.0,
+                    last_position: emitter.position,
+                });
+
+                let velocity = if delta_time > 0.0 {
+                    (emitter.position - state.last_position) / delta_time
+                } else {
+                    Vec3::ZERO
+                };
+                state.last_position = emitter.position;
+
+                let settings = &emitter.settings;
+                let emitting = delta_time > 0.0
+                    && (!settings.only_while_moving || velocity.length() > MOVING_SPEED);
+                let count = if emitting {
+                    state.accumulator += settings.rate.max(0.0) * delta_time;
+                    let count = state.accumulator.floor();
+                    state.accumulator -= count;
+                    (count as u32).min(capacity)
+                } else {
+                    state.accumulator = 0.0;
+                    0
+                };
+
+                let first = self.cursor;
+                self.cursor = (self.cursor + count) % capacity;
+                SpawnBatch { first, count, velocity }
+            })
+            .collect()
+    }
+}
+
+impl Default for ParticleSpawner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitter(id: ObjectId, position: Vec3, settings: ParticleEmitterSettings) -> EmitterInstance {
+        EmitterInstance { id, position, direction: Vec3::NEG_Z, settings }
+    }
+
+    #[test]
+    fn test_exhaust_only_emits_while_moving() {
+        let mut spawner = ParticleSpawner::new();
+        let settings = ParticleEmitterSettings { rate: 100.0, ..Default::default() };
+
+        // Standing still: nothing
+        let batches = spawner.plan(&[emitter(1, Vec3::ZERO, settings)], 0.1, 1000);
+        assert_eq!(batches[0].count, 0);
+        let batches = spawner.plan(&[emitter(1, Vec3::ZERO, settings)], 0.1, 1000);
+        assert_eq!(batches[0].count, 0);
+
+        // Moving 1 unit in 0.1s: 10 particles and the emitter's velocity
+        let batches = spawner.plan(&[emitter(1, Vec3::Z, settings)], 0.1, 1000);
+        assert_eq!(batches[0].count, 10);
+        assert!((batches[0].velocity - Vec3::new(0.0, 0.0, 10.0)).length() < 1e-4);
+
+        // Paused (no simulated time): nothing, even for always-on emitters
+        let always_on = ParticleEmitterSettings { only_while_moving: false, ..settings };
+        assert_eq!(spawner.plan(&[emitter(1, Vec3::Z, always_on)], 0.0, 1000)[0].count, 0);
+        assert_eq!(spawner.plan(&[emitter(1, Vec3::Z, always_on)], 0.1, 1000)[0].count, 10);
+    }
+
+    #[test]
+    fn test_spawns_share_a_capped_ring() {
+        let mut spawner = ParticleSpawner::new();
+        let settings = ParticleEmitterSettings { rate: 30.0, only_while_moving: false, ..Default::default() };
+        let emitters = [emitter(1, Vec3::ZERO, settings), emitter(2, Vec3::X, settings)];
+
+        // Fractional spawns carry over between frames
+        assert_eq!(spawner.plan(&emitters, 0.05, 64)[0].count, 1);
+        let batches = spawner.plan(&emitters, 0.05, 64);
+        assert_eq!(batches.iter().map(|batch| batch.count).collect::<Vec<_>>(), vec![2, 2]);
+
+        // Batches are consecutive runs of the ring and wrap at the cap
+        assert_eq!(batches[1].first, (batches[0].first + batches[0].count) % 64);
+        let batches = spawner.plan(&emitters, 2.0, 64);
+        assert_eq!(batches[0].count, 60);
+        assert_eq!(batches[1].count, 60);
+        assert!(batches[1].first < 64);
+
+        // One emitter can never take more than the whole budget
+        assert_eq!(spawner.plan(&emitters, 10.0, 64)[0].count, 64);
+    }
+}
@@ -0,0 +1,178 @@
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::scene::{ObjectId, SceneGraph, SceneObject, Transform};
+
+/// Directory prefab files are saved to and listed from
+pub const PREFAB_DIR: &str = "config/prefabs";
+
+/// A saved object subtree that can be spawned into the scene again
+///
+/// Objects are stored like `SceneData`: the root first, then its descendants
+/// with their local transforms and saved IDs for the parent links. The root
+/// keeps its world rotation and scale but not its position, which is chosen
+/// when the prefab is instantiated. Materials are stored by library name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefabData {
+    pub name: String,
+    pub objects: Vec<SceneObject>,
+}
+
+impl PrefabData {
+    /// Capture an object and its children (singletons can't be part of a prefab)
+    pub fn from_subtree(scene: &SceneGraph, root: ObjectId, name: &str) -> anyhow::Result<Self> {
+        let ids = scene.subtree(root);
+        let mut objects = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let Some(obj) = scene.get_object(id) else {
+                anyhow::bail!("Object {} doesn't exist", id);
+            };
+            if obj.is_singleton() {
+                anyhow::bail!("'{}' is a singleton and can't be saved in a prefab", obj.name);
+            }
+
+            let mut obj = obj.clone();
+            if id == root {
+                let world = scene.world_transform(id);
+                obj.parent = None;
+                obj.transform = Transform::new(Vec3::ZERO, world.rotation, world.scale);
+            }
+            objects.push(obj);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            objects,
+        })
+    }
+
+    /// Add the prefab's objects to the scene with fresh IDs, the root placed at `position`
+    /// Returns the new root's ID (None for an empty prefab)
+    pub fn instantiate(&self, scene: &mut SceneGraph, position: Vec3) -> Option<ObjectId> {
+        let root_saved_id = self.objects.first()?.id;
+
+        // Parents come before children, so every mapped parent already exists
+        let mut id_map: HashMap<ObjectId, ObjectId> = HashMap::new();
+        for obj in &self.objects {
+            let id = scene.add_object(obj.name.clone(), obj.object_type.clone());
+            scene.add_layer(&obj.layer); // Layers missing from this scene are recreated
+            let parent = obj.parent.and_then(|parent| id_map.get(&parent).copied());
+
+            if let Some(new_obj) = scene.get_object_mut(id) {
+                new_obj.transform = obj.transform;
+                new_obj.visible = obj.visible;
+                new_obj.material = obj.material.clone();
+                new_obj.editor_only = obj.editor_only;
+                new_obj.wireframe = obj.wireframe;
+                new_obj.layer = obj.layer.clone();
+                if obj.point_light.is_some() {
+                    new_obj.point_light = obj.point_light;
+                }
+                if obj.directional_light.is_some() {
+                    new_obj.directional_light = obj.directional_light;
+                }
+                if obj.particle_emitter.is_some() {
+                    new_obj.particle_emitter = obj.particle_emitter;
+                }
+
+                if obj.id == root_saved_id {
+                    new_obj.transform.position = position;
+                } else {
+                    new_obj.parent = parent;
+                }
+            }
+            id_map.insert(obj.id, id);
+        }
+
+        id_map.get(&root_saved_id).copied()
+    }
+
+    /// File path of a prefab by name (characters unsafe in file names become '_')
+    pub fn path_for(name: &str) -> String {
+        let file_name: String = name
+            .trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+            .collect();
+        format!("{}/{}.json", PREFAB_DIR, file_name)
+    }
+
+    /// Names of the prefabs in `PREFAB_DIR`, sorted (empty if the directory doesn't exist)
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(PREFAB_DIR) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|path| path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Load from JSON file
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let data: PrefabData = serde_json::from_str(&content)?;
+        Ok(data)
+    }
+
+    /// Save to JSON file with pretty formatting
+    pub fn save(&self, path: &str) -> anyhow::Result<()> {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::ObjectType;
+
+    #[test]
+    fn test_instantiate_assigns_fresh_ids_and_keeps_hierarchy() {
+        let mut scene = SceneGraph::new();
+        let ship = scene.add_object_with_transform("Ship".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(20.0, 0.0, 0.0)));
+        let turret = scene.add_object_with_transform("Turret".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(20.0, 1.0, 0.0)));
+        scene.set_parent(turret, Some(ship)).unwrap();
+        scene.get_object_mut(turret).unwrap().material = Some("Gunmetal".to_string());
+
+        // Round-trip through JSON like a saved prefab file
+        let prefab = PrefabData::from_subtree(&scene, ship, "Gunship").unwrap();
+        let json = serde_json::to_string(&prefab).unwrap();
+        let prefab: PrefabData = serde_json::from_str(&json).unwrap();
+
+        let root = prefab.instantiate(&mut scene, Vec3::new(0.0, 0.0, -5.0)).unwrap();
+        assert!(root != ship && root != turret);
+        assert_eq!(scene.get_object(root).unwrap().parent, None);
+        assert!((scene.world_transform(root).position - Vec3::new(0.0, 0.0, -5.0)).length() < 1e-5);
+
+        let children = scene.children(root);
+        assert_eq!(children.len(), 1);
+        let new_turret = scene.get_object(children[0]).unwrap();
+        assert_eq!(new_turret.name, "Turret");
+        assert_eq!(new_turret.material.as_deref(), Some("Gunmetal"));
+        assert!((scene.world_transform(new_turret.id).position - Vec3::new(0.0, 1.0, -5.0)).length() < 1e-5);
+
+        // The original subtree is untouched
+        assert_eq!(scene.children(ship), vec![turret]);
+    }
+
+    #[test]
+    fn test_singletons_and_file_names() {
+        let mut scene = SceneGraph::new();
+        let skybox = scene.add_object("Skybox".to_string(), ObjectType::Skybox);
+        assert!(PrefabData::from_subtree(&scene, skybox, "Sky").is_err());
+
+        assert_eq!(PrefabData::path_for("Ship/Turret: v2"), format!("{}/Ship_Turret_ v2.json", PREFAB_DIR));
+    }
+}
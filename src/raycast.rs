@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use glam::{DVec3, Mat4, Vec3};
+
+use crate::gizmo::Ray;
+use crate::mesh::{Mesh, PrimitiveShape};
+use crate::scene::{ObjectId, ObjectType};
+
+/// Closest surface hit by a ray cast into the scene
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    pub object_id: ObjectId,
+    /// World-space hit point
+    pub point: DVec3,
+    /// World-space triangle normal, facing back toward the ray origin
+    pub normal: Vec3,
+    /// Distance from the ray origin along the (normalized) ray direction
+    pub distance: f64,
+}
+
+/// CPU copy of a mesh's triangles for ray tests
+pub struct PickMesh {
+    positions: Vec<Vec3>,
+    indices: Vec<u32>,
+    bounds: (Vec3, Vec3),
+}
+
+impl PickMesh {
+    /// Local-space bounding box (broad phase)
+    pub fn bounds(&self) -> (Vec3, Vec3) {
+        self.bounds
+    }
+
+    /// Closest hit of a ray against the mesh placed by `model`
+    /// The ray and `model` share one space (camera-relative, so everything is small f32).
+    /// Returns the distance along the ray and the triangle normal.
+    pub fn intersect(&self, ray: &Ray, model: Mat4, max_distance: f32) -> Option<(f32, Vec3)> {
+        let mut closest: Option<(f32, Vec3)> = None;
+        let mut max_distance = max_distance;
+
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]]
+                .map(|i| model.transform_point3(self.positions[i as usize]));
+            if let Some(t) = ray_triangle(ray, a, b, c) {
+                if t < max_distance {
+                    max_distance = t;
+                    closest = Some((t, (b - a).cross(c - a)));
+                }
+            }
+        }
+
+        closest.map(|(t, normal)| {
+            let normal = normal.normalize_or_zero();
+            // Face the ray so back faces of open meshes still give a usable normal
+            (t, if normal.dot(ray.direction) > 0.0 { -normal } else { normal })
+        })
+    }
+}
+
+impl From<&Mesh> for PickMesh {
+    fn from(mesh: &Mesh) -> Self {
+        Self {
+            positions: mesh.vertices.iter().map(|vertex| vertex.position).collect(),
+            indices: mesh.indices.clone(),
+            bounds: mesh.calculate_bounds(),
+        }
+    }
+}
+
+/// Pickable geometry for every object type that draws triangles
+///
+/// Built-in shapes are generated up front; custom meshes are added by the renderer
+/// as it loads them, so an object becomes pickable once it has been drawn.
+pub struct PickMeshes {
+    cube: PickMesh,
+    primitives: HashMap<PrimitiveShape, PickMesh>,
+    custom: HashMap<String, PickMesh>,
+}
+
+impl PickMeshes {
+    pub fn new() -> Self {
+        Self {
+            cube: PickMesh::from(&Mesh::create_cube()),
+            primitives: PrimitiveShape::ALL
+                .iter()
+                .map(|&shape| (shape, PickMesh::from(&Mesh::create_primitive(shape))))
+                .collect(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Register a loaded custom mesh (`.obj`/`.gltf`/`.glb` path)
+    pub fn insert_custom(&mut self, path: &str, mesh: &Mesh) {
+        self.custom.insert(path.to_string(), PickMesh::from(mesh));
+    }
+
+    pub fn contains_custom(&self, path: &str) -> bool {
+        self.custom.contains_key(path)
+    }
+
+    /// Geometry drawn for an object type, if any has been loaded
+    pub fn get(&self, object_type: &ObjectType) -> Option<&PickMesh> {
+        match object_type {
+            ObjectType::Cube => Some(&self.cube),
+            ObjectType::Primitive(shape) => self.primitives.get(shape),
+            ObjectType::Mesh(path) | ObjectType::Unlit(path) => self.custom.get(path),
+            _ => None,
+        }
+    }
+}
+
+impl Default for PickMeshes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Möller-Trumbore ray/triangle test (both faces); distance along the ray
+pub fn ray_triangle(ray: &Ray, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+    const EPSILON: f32 = 1e-7;
+
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = ray.direction.cross(edge2);
+    let det = edge1.dot(p);
+    if det.abs() < EPSILON {
+        return None; // Parallel to the triangle
+    }
+
+    let inv_det = 1.0 / det;
+    let s = ray.origin - a;
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = s.cross(edge1);
+    let v = ray.direction.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = edge2.dot(q) * inv_det;
+    (t > EPSILON).then_some(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{SceneGraph, Transform};
+
+    #[test]
+    fn test_ray_triangle() {
+        let ray = Ray { origin: Vec3::new(0.2, 0.2, 5.0), direction: Vec3::NEG_Z };
+        let (a, b, c) = (Vec3::ZERO, Vec3::X, Vec3::Y);
+        assert!((ray_triangle(&ray, a, b, c).unwrap() - 5.0).abs() < 1e-5);
+
+        // Outside the triangle, and behind the ray
+        let miss = Ray { origin: Vec3::new(0.8, 0.8, 5.0), direction: Vec3::NEG_Z };
+        assert!(ray_triangle(&miss, a, b, c).is_none());
+        let behind = Ray { origin: Vec3::new(0.2, 0.2, -5.0), direction: Vec3::NEG_Z };
+        assert!(ray_triangle(&behind, a, b, c).is_none());
+    }
+
+    #[test]
+    fn test_scene_raycast_hits_nearest_face() {
+        let meshes = PickMeshes::new();
+        let mut scene = SceneGraph::new();
+        let near = scene.add_object_with_transform("Near".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(0.0, 0.0, -5.0)));
+        scene.add_object_with_transform("Far".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(0.0, 0.0, -10.0)));
+
+        let hit = scene.raycast(DVec3::ZERO, DVec3::NEG_Z, &meshes).unwrap();
+        assert_eq!(hit.object_id, near);
+        // Unit cube: the front face is 0.5 in front of its center
+        assert!((hit.distance - 4.5).abs() < 1e-4);
+        assert!((hit.point - DVec3::new(0.0, 0.0, -4.5)).length() < 1e-4);
+        assert!((hit.normal - Vec3::Z).length() < 1e-4);
+
+        // Far from the origin the camera-relative test stays precise
+        let origin = DVec3::new(1.0e9, 0.0, 0.0);
+        let remote = scene.add_object_with_transform("Remote".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(1.0e9, 0.0, -3.0)));
+        let hit = scene.raycast(origin, DVec3::NEG_Z, &meshes).unwrap();
+        assert_eq!(hit.object_id, remote);
+        assert!((hit.distance - 2.5).abs() < 1e-3);
+
+        assert!(scene.raycast(DVec3::ZERO, DVec3::Y, &meshes).is_none());
+    }
+
+    #[test]
+    fn test_raycast_excluding_skips_objects() {
+        let meshes = PickMeshes::new();
+        let mut scene = SceneGraph::new();
+        let lifted = scene.add_object_with_transform("Lifted".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(0.0, 3.0, 0.0)));
+        let floor = scene.add_object_with_transform("Floor".to_string(), ObjectType::Cube, Transform::from_position(Vec3::ZERO));
+
+        // Casting down from inside the lifted cube finds its own face unless it is excluded
+        let origin = DVec3::new(0.0, 3.0, 0.0);
+        assert_eq!(scene.raycast(origin, DVec3::NEG_Y, &meshes).unwrap().object_id, lifted);
+        let hit = scene.raycast_excluding(origin, DVec3::NEG_Y, &meshes, &[lifted]).unwrap();
+        assert_eq!(hit.object_id, floor);
+        assert!((hit.point.y - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_sweep_point_stops_short_of_surface() {
+        let meshes = PickMeshes::new();
+        let mut scene = SceneGraph::new();
+        scene.add_object_with_transform("Hull".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(0.0, 0.0, -5.0)));
+
+        // Moving into the cube stops `radius` in front of its near face (z = -4.5)
+        let stopped = scene.sweep_point(DVec3::ZERO, DVec3::new(0.0, 0.0, -10.0), 0.5, &meshes, &[]);
+        assert!((stopped.z + 4.0).abs() < 1e-4);
+
+        // Ending just inside the margin also pulls back
+        let near = scene.sweep_point(DVec3::ZERO, DVec3::new(0.0, 0.0, -4.2), 0.5, &meshes, &[]);
+        assert!((near.z + 4.0).abs() < 1e-4);
+
+        // Nothing in the way, or moving away, keeps the target
+        let clear = DVec3::new(0.0, 0.0, 3.0);
+        assert_eq!(scene.sweep_point(DVec3::ZERO, clear, 0.5, &meshes, &[]), clear);
+    }
+}
@@ -0,0 +1,165 @@
+use glam::{DVec3, Mat4, Vec2, Vec3};
+use crate::core::Camera;
+use crate::gizmo::{ObjectPicker, Ray};
+use crate::scene::SceneGraph;
+
+/// Measurement tool: the distance between two points picked in the viewport
+/// Points are kept in 64-bit world coordinates so long spans stay accurate
+pub struct Ruler {
+    /// Clicks in the viewport place measurement points instead of selecting
+    pub active: bool,
+    start: Option<DVec3>,
+    end: Option<DVec3>,
+    /// Snapped point under the cursor, previewed while waiting for the second click
+    pub hover_point: Option<DVec3>,
+}
+
+impl Ruler {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            start: None,
+            end: None,
+            hover_point: None,
+        }
+    }
+
+    /// Turn the tool on or off (turning it off clears the measurement)
+    pub fn toggle(&mut self) {
+        self.active = !self.active;
+        if !self.active {
+            self.clear();
+        }
+    }
+
+    /// Forget the current measurement
+    pub fn clear(&mut self) {
+        self.start = None;
+        self.end = None;
+        self.hover_point = None;
+    }
+
+    /// Add a picked point: the first click starts a measurement, the second
+    /// finishes it, and the next click starts a new one
+    pub fn add_point(&mut self, point: DVec3) {
+        match (self.start, self.end) {
+            (Some(_), None) => self.end = Some(point),
+            _ => {
+                self.start = Some(point);
+                self.end = None;
+            }
+        }
+    }
+
+    /// Measure between two points directly (e.g. two selected objects)
+    pub fn measure(&mut self, start: DVec3, end: DVec3) {
+        self.start = Some(start);
+        self.end = Some(end);
+    }
+
+    /// First point of the measurement, if one has been placed
+    pub fn start(&self) -> Option<DVec3> {
+        self.start
+    }
+
+    /// The segment being measured: both points once finished, or the start
+    /// and the point under the cursor while placing the second point
+    pub fn segment(&self) -> Option<(DVec3, DVec3)> {
+        let start = self.start?;
+        let end = self.end.or(if self.active { self.hover_point } else { None })?;
+        Some((start, end))
+    }
+
+    /// True once both points are placed
+    pub fn is_finished(&self) -> bool {
+        self.start.is_some() && self.end.is_some()
+    }
+
+    /// Length of the measured (or previewed) segment in world units
+    pub fn distance(&self) -> Option<f64> {
+        self.segment().map(|(start, end)| start.distance(end))
+    }
+
+    /// Point under the cursor: the center of the object hit, otherwise the Y=0 ground plane
+    pub fn pick_point(
+        mouse_x: f32,
+        mouse_y: f32,
+        viewport_width: f32,
+        viewport_height: f32,
+        scene: &SceneGraph,
+        camera: &Camera,
+    ) -> Option<DVec3> {
+        if let Some(id) = ObjectPicker::new().pick_object(mouse_x, mouse_y, viewport_width, viewport_height, scene, camera) {
+            return Some(scene.world_transform(id).position.as_dvec3());
+        }
+
+        let view = camera.view_matrix();
+        let proj = camera.projection_matrix(viewport_width / viewport_height);
+        let ray = Ray::from_screen(mouse_x, mouse_y, viewport_width, viewport_height, view, proj);
+        ray.project_onto_plane(Vec3::ZERO, Vec3::Y).map(|point| point.as_dvec3())
+    }
+
+    /// Screen position (pixels from the top-left) of a world point, None if it's behind the camera
+    pub fn project_to_screen(point: Vec3, view_proj: Mat4, viewport_width: f32, viewport_height: f32) -> Option<Vec2> {
+        let clip = view_proj * point.extend(1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        let ndc = clip.truncate() / clip.w;
+        // The Vulkan projection already flips Y, so NDC -1 is the top of the screen
+        Some(Vec2::new(
+            (ndc.x + 1.0) * 0.5 * viewport_width,
+            (ndc.y + 1.0) * 0.5 * viewport_height,
+        ))
+    }
+}
+
+impl Default for Ruler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clicks_alternate_between_start_and_end() {
+        let mut ruler = Ruler::new();
+        ruler.active = true;
+
+        ruler.add_point(DVec3::ZERO);
+        assert!(!ruler.is_finished());
+        ruler.hover_point = Some(DVec3::new(0.0, 0.0, 2.0));
+        assert_eq!(ruler.distance(), Some(2.0));
+
+        ruler.add_point(DVec3::new(3.0, 4.0, 0.0));
+        assert!(ruler.is_finished());
+        assert_eq!(ruler.distance(), Some(5.0));
+
+        // A third click starts over
+        ruler.add_point(DVec3::ONE);
+        assert_eq!(ruler.start(), Some(DVec3::ONE));
+        assert!(!ruler.is_finished());
+
+        ruler.toggle();
+        assert_eq!(ruler.distance(), None);
+    }
+
+    #[test]
+    fn test_long_spans_keep_precision() {
+        let mut ruler = Ruler::new();
+        ruler.measure(DVec3::new(1.0e12, 0.0, 0.0), DVec3::new(1.0e12 + 0.25, 0.0, 0.0));
+        assert_eq!(ruler.distance(), Some(0.25));
+    }
+
+    #[test]
+    fn test_project_to_screen() {
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 0.1, 100.0);
+        let center = Ruler::project_to_screen(Vec3::ZERO, proj * view, 800.0, 800.0).unwrap();
+        assert!((center - Vec2::new(400.0, 400.0)).length() < 1e-3);
+        assert!(Ruler::project_to_screen(Vec3::new(0.0, 0.0, 10.0), proj * view, 800.0, 800.0).is_none());
+    }
+}
@@ -1,12 +1,54 @@
-use glam::{Mat4, Quat, Vec3};
+use glam::{DVec3, Mat4, Quat, Vec3};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::core::camera_track::CameraTrack;
+use crate::core::lighting::{DirectionalLightSettings, PointLightSettings};
+use crate::idle_motion::{Bob, Spin};
+use crate::mesh::PrimitiveShape;
+use crate::nebula::NebulaConfig;
+use crate::particles::ParticleEmitterSettings;
+use crate::raycast::{PickMeshes, RayHit};
 
 /// Unique identifier for scene objects
-pub type ObjectId = usize;
+pub type ObjectId = u64;
+
+/// Low bits of an object ID that are random; the bits above hold the creation time in milliseconds
+const OBJECT_ID_RANDOM_BITS: u32 = 22;
+
+/// Create a stable object ID: creation time in the high bits, random bits below
+/// Sorting by ID keeps creation order, and objects added on different machines or
+/// branches practically never collide, so saved scenes diff and merge cleanly
+pub fn generate_object_id() -> ObjectId {
+    static LAST_ID: AtomicU64 = AtomicU64::new(0);
+
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0);
+    let random = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    let candidate = (millis << OBJECT_ID_RANDOM_BITS) | (random & ((1 << OBJECT_ID_RANDOM_BITS) - 1));
+
+    // Strictly increasing within a run, even if the clock stalls or steps backwards
+    let mut last = LAST_ID.load(Ordering::Relaxed);
+    loop {
+        let id = candidate.max(last + 1);
+        match LAST_ID.compare_exchange_weak(last, id, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return id,
+            Err(current) => last = current,
+        }
+    }
+}
+
+/// Sequential IDs from scenes saved before stable IDs (no timestamp bits)
+pub fn is_legacy_object_id(id: ObjectId) -> bool {
+    id >> OBJECT_ID_RANDOM_BITS == 0
+}
 
 /// Transform component for positioning objects in 3D space
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Transform {
     pub position: Vec3,
     pub rotation: Quat,
@@ -66,16 +108,100 @@ impl Default for Transform {
 pub enum ObjectType {
     Cube,
     Sphere,  // Procedural sphere
+    Primitive(PrimitiveShape), // Built-in procedural mesh drawn like the cube
     Nebula,
     Skybox,
     DirectionalLight,
+    PointLight, // Point light positioned by its transform
+    ParticleEmitter, // GPU particle emitter (engine exhaust), usually parented to a ship
     SSAO, // SSAO settings singleton
     GameManager, // Game Manager singleton (play/pause, scenario params)
-    Mesh(String), // Custom mesh with path to .obj file
-    Unlit(String), // Unlit mesh with path to .obj file
+    Mesh(String), // Custom mesh with path to .obj/.gltf/.glb file
+    Unlit(String), // Unlit mesh with path to .obj/.gltf/.glb file
     Custom(u32), // For future custom mesh support
 }
 
+impl ObjectType {
+    /// Display name of the type, grouping every primitive shape and every mesh file together
+    pub fn category(&self) -> &'static str {
+        match self {
+            ObjectType::Cube => "Cube",
+            ObjectType::Sphere => "Star",
+            ObjectType::Primitive(_) => "Primitive",
+            ObjectType::Nebula => "Nebula",
+            ObjectType::Skybox => "Skybox",
+            ObjectType::DirectionalLight => "Directional Light",
+            ObjectType::PointLight => "Point Light",
+            ObjectType::ParticleEmitter => "Particle Emitter",
+            ObjectType::SSAO => "SSAO",
+            ObjectType::GameManager => "Game Manager",
+            ObjectType::Mesh(_) => "Mesh",
+            ObjectType::Unlit(_) => "Unlit Mesh",
+            ObjectType::Custom(_) => "Custom",
+        }
+    }
+
+    /// Whether objects of this type are drawn with a library material (cubes, primitives and lit meshes)
+    pub fn takes_material(&self) -> bool {
+        matches!(self, ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_))
+    }
+}
+
+/// Layer every object starts on; it can't be removed
+pub const DEFAULT_LAYER: &str = "Default";
+
+fn default_layer() -> String {
+    DEFAULT_LAYER.to_string()
+}
+
+fn is_default_layer(layer: &str) -> bool {
+    layer == DEFAULT_LAYER
+}
+
+/// Named group of objects that can be hidden or locked together in the editor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Layer {
+    pub name: String,
+    /// Hidden layers don't render and can't be picked
+    pub visible: bool,
+    /// Locked layers render but can't be selected in the viewport
+    pub locked: bool,
+}
+
+impl Layer {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), visible: true, locked: false }
+    }
+}
+
+/// Most copies a single array duplication may create
+pub const MAX_ARRAY_COPIES: u32 = 1000;
+
+/// Grid of copies made by `SceneGraph::duplicate_array` (a row is a grid with one count above 1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuplicateArray {
+    /// Cells along X, Y and Z, including the original (minimum 1)
+    pub counts: [u32; 3],
+    /// Distance between neighbouring cells on each axis
+    pub spacing: Vec3,
+}
+
+impl DuplicateArray {
+    /// Copies the grid adds (every cell except the original's)
+    pub fn copy_count(&self) -> u32 {
+        self.counts.iter().map(|&count| count.max(1)).product::<u32>() - 1
+    }
+}
+
+impl Default for DuplicateArray {
+    fn default() -> Self {
+        Self {
+            counts: [5, 1, 1],
+            spacing: Vec3::splat(5.0),
+        }
+    }
+}
+
 /// Scene object with transform and type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SceneObject {
@@ -88,10 +214,34 @@ pub struct SceneObject {
     pub material: Option<String>, // Name of material from material library
     #[serde(default)]
     pub editor_only: bool, // Don't render during gameplay
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub point_light: Option<PointLightSettings>, // Only set for point light objects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directional_light: Option<DirectionalLightSettings>, // Only set for directional light objects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nebula: Option<NebulaConfig>, // Nebula settings; None uses the engine config default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub particle_emitter: Option<ParticleEmitterSettings>, // Only set for particle emitter objects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<ObjectId>, // Transform is relative to this object
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub wireframe: bool, // Draw a wireframe overlay on top of the shaded mesh
+    #[serde(default = "default_layer", skip_serializing_if = "is_default_layer")]
+    pub layer: String, // Name of the layer this object belongs to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>, // Free-form labels for gameplay queries (e.g. "enemy", "spawn")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spin: Option<Spin>, // Idle rotation applied in play mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bob: Option<Bob>, // Idle bobbing applied in play mode
 }
 
 impl SceneObject {
     pub fn new(id: ObjectId, name: String, object_type: ObjectType) -> Self {
+        let point_light = (object_type == ObjectType::PointLight).then(PointLightSettings::default);
+        let directional_light = (object_type == ObjectType::DirectionalLight).then(DirectionalLightSettings::default);
+        let particle_emitter = (object_type == ObjectType::ParticleEmitter).then(ParticleEmitterSettings::default);
+
         Self {
             id,
             name,
@@ -100,6 +250,16 @@ impl SceneObject {
             visible: true,
             material: None,
             editor_only: false,
+            point_light,
+            directional_light,
+            nebula: None,
+            particle_emitter,
+            parent: None,
+            wireframe: false,
+            layer: default_layer(),
+            tags: Vec::new(),
+            spin: None,
+            bob: None,
         }
     }
 
@@ -108,6 +268,29 @@ impl SceneObject {
         self
     }
 
+    /// Whether the object carries `tag` (exact, case-sensitive match)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own| own == tag)
+    }
+
+    /// Add a tag (surrounding whitespace is trimmed)
+    /// Returns false for empty or already present tags
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.has_tag(tag) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    /// Remove a tag, returning whether it was present
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let count = self.tags.len();
+        self.tags.retain(|own| own != tag);
+        self.tags.len() != count
+    }
+
     /// Mark object as editor-only (won't render during gameplay)
     pub fn editor_only(mut self) -> Self {
         self.editor_only = true;
@@ -119,9 +302,12 @@ impl SceneObject {
         match self.object_type {
             ObjectType::Cube => 2.0, // Cube is 2x2x2
             ObjectType::Sphere => 2.0, // Sphere is diameter 2.0
+            ObjectType::Primitive(_) => 2.0, // Primitives fit the unit cube like Cube
             ObjectType::Nebula => 10.0, // Nebula is larger
             ObjectType::Skybox => 50.0, // Skybox is very large
             ObjectType::DirectionalLight => 1.5, // Light visualization arrow
+            ObjectType::PointLight => 1.0, // Small pickable area around the light
+            ObjectType::ParticleEmitter => 1.0, // Small pickable area around the nozzle
             ObjectType::SSAO => 0.0, // SSAO is a settings singleton, no visual representation
             ObjectType::GameManager => 0.0, // GameManager is a settings singleton, no visual representation
             ObjectType::Mesh(_) => 5.0, // Default size for mesh objects
@@ -130,6 +316,34 @@ impl SceneObject {
         }
     }
 
+    /// Singleton objects (settings holders and scene-wide effects) can't join a multi-selection
+    pub fn is_singleton(&self) -> bool {
+        matches!(
+            self.object_type,
+            ObjectType::Skybox
+                | ObjectType::SSAO
+                | ObjectType::GameManager
+        )
+    }
+
+    /// Lights and scene-wide backdrops, which stay shown while other objects are soloed
+    pub fn is_environment(&self) -> bool {
+        matches!(
+            self.object_type,
+            ObjectType::Skybox
+                | ObjectType::Nebula
+                | ObjectType::DirectionalLight
+                | ObjectType::PointLight
+                | ObjectType::SSAO
+                | ObjectType::GameManager
+        )
+    }
+
+    /// Objects shaded with a material from the library (cubes, primitives and lit meshes)
+    pub fn uses_material(&self) -> bool {
+        matches!(self.object_type, ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_))
+    }
+
     /// Get the actual bounding box size accounting for scale
     pub fn bounding_box_size(&self) -> f32 {
         let base_size = self.base_bounding_box_size();
@@ -141,24 +355,40 @@ impl SceneObject {
 /// Scene graph managing all objects in the scene
 pub struct SceneGraph {
     objects: HashMap<ObjectId, SceneObject>,
-    next_id: ObjectId,
     selected_object: Option<ObjectId>,
+    selection: Vec<ObjectId>, // All selected objects; the primary selection is the last entry
+    layers: Vec<Layer>, // Always starts with the default layer
+    soloed: HashSet<ObjectId>, // While non-empty only these (and their children) are shown; never saved
+    solo_shows_environment: bool, // Keep lights, skybox and nebulae while soloing
+    animation_time: Option<f32>, // Seconds into play mode; spin/bob only apply while set
 }
 
 impl SceneGraph {
     pub fn new() -> Self {
         Self {
             objects: HashMap::new(),
-            next_id: 0,
             selected_object: None,
+            selection: Vec::new(),
+            layers: vec![Layer::new(DEFAULT_LAYER)],
+            soloed: HashSet::new(),
+            solo_shows_environment: true,
+            animation_time: None,
+        }
+    }
+
+    /// A fresh stable ID not used by any object in the scene
+    fn unused_id(&self) -> ObjectId {
+        loop {
+            let id = generate_object_id();
+            if !self.objects.contains_key(&id) {
+                return id;
+            }
         }
     }
 
     /// Add an object to the scene
     pub fn add_object(&mut self, name: String, object_type: ObjectType) -> ObjectId {
-        let id = self.next_id;
-        self.next_id += 1;
-
+        let id = self.unused_id();
         let object = SceneObject::new(id, name, object_type);
         self.objects.insert(id, object);
         id
@@ -178,47 +408,329 @@ impl SceneGraph {
         id
     }
 
-    /// Duplicate an object (returns new object ID if successful)
+    /// Duplicate an object and its children (returns the new root's ID if successful)
     pub fn duplicate_object(&mut self, id: ObjectId) -> Option<ObjectId> {
-        // Get the object to duplicate
-        let obj = self.objects.get(&id)?;
+        // Offset the position slightly so it's visible
+        self.copy_subtree(id, " Copy", Vec3::splat(0.5))
+    }
 
-        // Don't allow duplicating skybox or nebula
-        if matches!(obj.object_type, ObjectType::Skybox | ObjectType::Nebula) {
+    /// Copy an object and its children, appending `suffix` to every name and moving the root by `offset`
+    fn copy_subtree(&mut self, id: ObjectId, suffix: &str, offset: Vec3) -> Option<ObjectId> {
+        // Don't allow duplicating the skybox
+        let obj = self.objects.get(&id)?;
+        if obj.object_type == ObjectType::Skybox {
             return None;
         }
 
-        // Clone the object data
-        let object_type = obj.object_type.clone();
-        let transform = obj.transform;
-        let visible = obj.visible;
-
-        // Create a new name with " Copy" suffix
-        let new_name = format!("{} Copy", obj.name);
+        // Copy the subtree parents-first so every copied parent exists before its children
+        let mut id_map: HashMap<ObjectId, ObjectId> = HashMap::new();
+        for original_id in self.subtree(id) {
+            let original = self.objects[&original_id].clone();
+
+            let new_id = self.unused_id();
+
+            let mut new_object = SceneObject::new(new_id, format!("{}{}", original.name, suffix), original.object_type);
+            new_object.transform = original.transform;
+            new_object.visible = original.visible;
+            new_object.point_light = original.point_light;
+            new_object.directional_light = original.directional_light;
+            new_object.nebula = original.nebula;
+            new_object.particle_emitter = original.particle_emitter;
+            new_object.wireframe = original.wireframe;
+            new_object.layer = original.layer;
+            new_object.tags = original.tags;
+            new_object.spin = original.spin;
+            new_object.bob = original.bob;
+
+            if original_id == id {
+                // The copy sits next to the original under the same parent
+                new_object.parent = original.parent;
+                new_object.transform.position += offset;
+            } else {
+                new_object.parent = original.parent.and_then(|parent| id_map.get(&parent).copied());
+            }
 
-        // Create the new object
-        let new_id = self.next_id;
-        self.next_id += 1;
+            id_map.insert(original_id, new_id);
+            self.objects.insert(new_id, new_object);
+        }
 
-        let mut new_object = SceneObject::new(new_id, new_name, object_type);
-        new_object.transform = transform;
-        new_object.visible = visible;
+        id_map.get(&id).copied()
+    }
 
-        // Offset the position slightly so it's visible
-        new_object.transform.position += glam::Vec3::new(0.5, 0.5, 0.5);
+    /// Fill a grid with copies of an object (and its children), the original taking the first cell
+    /// Offsets are along the parent's axes; copies are numbered after the highest "<name> N" already in the scene
+    pub fn duplicate_array(&mut self, id: ObjectId, array: &DuplicateArray) -> anyhow::Result<Vec<ObjectId>> {
+        let Some(obj) = self.objects.get(&id) else {
+            anyhow::bail!("object {} not found", id);
+        };
+        let copies = array.copy_count();
+        if copies > MAX_ARRAY_COPIES {
+            anyhow::bail!("{} copies is more than the limit of {}", copies, MAX_ARRAY_COPIES);
+        }
 
-        self.objects.insert(new_id, new_object);
-        Some(new_id)
+        let prefix = format!("{} ", obj.name);
+        let mut number = self
+            .objects
+            .values()
+            .filter_map(|other| other.name.strip_prefix(&prefix)?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+
+        let [count_x, count_y, count_z] = array.counts.map(|count| count.max(1));
+        let mut new_ids = Vec::with_capacity(copies as usize);
+        for z in 0..count_z {
+            for y in 0..count_y {
+                for x in 0..count_x {
+                    if (x, y, z) == (0, 0, 0) {
+                        continue;
+                    }
+                    number += 1;
+                    let offset = Vec3::new(x as f32, y as f32, z as f32) * array.spacing;
+                    match self.copy_subtree(id, &format!(" {}", number), offset) {
+                        Some(new_id) => new_ids.push(new_id),
+                        None => anyhow::bail!("{} can't be duplicated", self.objects[&id].name),
+                    }
+                }
+            }
+        }
+        Ok(new_ids)
     }
 
     /// Remove an object from the scene
+    /// Its children move up to its parent and keep their world transforms
     pub fn remove_object(&mut self, id: ObjectId) -> Option<SceneObject> {
-        if self.selected_object == Some(id) {
-            self.selected_object = None;
+        let grandparent = self.objects.get(&id)?.parent;
+        for child in self.children(id) {
+            let world = self.world_transform(child);
+            if let Some(obj) = self.objects.get_mut(&child) {
+                obj.parent = grandparent;
+            }
+            self.set_world_transform(child, world);
+        }
+
+        if self.selection.contains(&id) {
+            self.selection.retain(|&selected| selected != id);
+            self.selected_object = self.selection.last().copied();
         }
+        self.soloed.remove(&id);
         self.objects.remove(&id)
     }
 
+    /// Drive spin/bob idle motion (Some(seconds into play mode)), or stop it (None)
+    /// Only the drawn transforms change; `SceneObject::transform` is never written.
+    pub fn set_animation_time(&mut self, time: Option<f32>) {
+        self.animation_time = time;
+    }
+
+    /// An object's transform relative to its parent, with any idle motion layered on
+    pub fn local_transform(&self, obj: &SceneObject) -> Transform {
+        match self.animation_time {
+            Some(time) if obj.spin.is_some() || obj.bob.is_some() => {
+                crate::idle_motion::animate(obj.transform, obj.spin, obj.bob, time)
+            }
+            _ => obj.transform,
+        }
+    }
+
+    /// World matrix of an object: its local transform composed with every parent's
+    pub fn world_matrix(&self, id: ObjectId) -> Mat4 {
+        let mut matrix = Mat4::IDENTITY;
+        let mut current = Some(id);
+        // Depth is bounded by the object count in case a broken file slipped a cycle through
+        for _ in 0..=self.objects.len() {
+            let Some(obj) = current.and_then(|current| self.objects.get(&current)) else {
+                break;
+            };
+            matrix = self.local_transform(obj).model_matrix() * matrix;
+            current = obj.parent;
+        }
+        matrix
+    }
+
+    /// Closest triangle hit along a ray (direction need not be normalized)
+    ///
+    /// Each visible object with pickable geometry is first tested by its world AABB,
+    /// then triangle by triangle. Matrices are made relative to the ray origin in
+    /// 64-bit before the f32 tests, so hits stay precise far from the world origin.
+    pub fn raycast(&self, origin: DVec3, direction: DVec3, meshes: &PickMeshes) -> Option<RayHit> {
+        self.raycast_excluding(origin, direction, meshes, &[])
+    }
+
+    /// Like `raycast`, but objects in `excluded` are ignored (e.g. the object being placed)
+    pub fn raycast_excluding(&self, origin: DVec3, direction: DVec3, meshes: &PickMeshes, excluded: &[ObjectId]) -> Option<RayHit> {
+        let direction = direction.normalize_or_zero();
+        if direction == DVec3::ZERO {
+            return None;
+        }
+        let ray = crate::gizmo::Ray { origin: Vec3::ZERO, direction: direction.as_vec3() };
+
+        let mut closest: Option<(ObjectId, f32, Vec3)> = None;
+        for obj in self.objects.values().filter(|obj| self.is_shown(obj) && !excluded.contains(&obj.id)) {
+            let Some(mesh) = meshes.get(&obj.object_type) else {
+                continue;
+            };
+
+            let mut model = self.world_matrix(obj.id);
+            let relative = model.w_axis.truncate().as_dvec3() - origin;
+            model.w_axis = relative.as_vec3().extend(1.0);
+
+            // Broad phase: skip objects whose box is missed or starts beyond the current hit
+            let max_distance = closest.map_or(f32::MAX, |(_, distance, _)| distance);
+            let (bounds_min, bounds_max) = mesh.bounds();
+            let (box_min, box_max) = crate::core::frustum::transform_aabb(model, bounds_min, bounds_max);
+            match ray.aabb_distance(box_min, box_max) {
+                Some(entry) if entry < max_distance => {}
+                _ => continue,
+            }
+
+            if let Some((distance, normal)) = mesh.intersect(&ray, model, max_distance) {
+                closest = Some((obj.id, distance, normal));
+            }
+        }
+
+        closest.map(|(object_id, distance, normal)| RayHit {
+            object_id,
+            point: origin + direction * distance as f64,
+            normal,
+            distance: distance as f64,
+        })
+    }
+
+    /// Furthest point on the way from `from` to `to` that stays `radius` short of the
+    /// first surface in between (`to` itself when nothing is in the way)
+    pub fn sweep_point(&self, from: DVec3, to: DVec3, radius: f64, meshes: &PickMeshes, excluded: &[ObjectId]) -> DVec3 {
+        let offset = to - from;
+        let length = offset.length();
+        if length <= f64::EPSILON {
+            return to;
+        }
+
+        let direction = offset / length;
+        match self.raycast_excluding(from, direction, meshes, excluded) {
+            Some(hit) if hit.distance < length + radius => from + direction * (hit.distance - radius).max(0.0),
+            _ => to,
+        }
+    }
+
+    /// World-space transform of an object (decomposed from its world matrix)
+    pub fn world_transform(&self, id: ObjectId) -> Transform {
+        match self.objects.get(&id) {
+            Some(obj) if obj.parent.is_none() => self.local_transform(obj),
+            _ => {
+                let (scale, rotation, position) = self.world_matrix(id).to_scale_rotation_translation();
+                Transform::new(position, rotation, scale)
+            }
+        }
+    }
+
+    /// Place an object in world space, converting to its parent's local space
+    pub fn set_world_transform(&mut self, id: ObjectId, world: Transform) {
+        let Some(parent) = self.objects.get(&id).map(|obj| obj.parent) else {
+            return;
+        };
+
+        let local = match parent {
+            Some(parent) => {
+                let local_matrix = self.world_matrix(parent).inverse() * world.model_matrix();
+                let (scale, rotation, position) = local_matrix.to_scale_rotation_translation();
+                Transform::new(position, rotation.normalize(), scale)
+            }
+            None => world,
+        };
+
+        if let Some(obj) = self.objects.get_mut(&id) {
+            obj.transform = local;
+        }
+    }
+
+    /// Check that `child` may be attached to `parent` (both exist, neither is a singleton, no cycle)
+    pub fn validate_parent(&self, child: ObjectId, parent: ObjectId) -> anyhow::Result<()> {
+        let (Some(child_obj), Some(parent_obj)) = (self.objects.get(&child), self.objects.get(&parent)) else {
+            anyhow::bail!("Object {} or {} doesn't exist", child, parent);
+        };
+        if child_obj.is_singleton() || parent_obj.is_singleton() {
+            anyhow::bail!("Singletons can't be parented");
+        }
+        if self.is_ancestor(child, parent) || child == parent {
+            anyhow::bail!("'{}' can't be parented to its own descendant '{}'", child_obj.name, parent_obj.name);
+        }
+        Ok(())
+    }
+
+    /// Attach an object to a new parent (None = scene root), keeping its world transform
+    pub fn set_parent(&mut self, child: ObjectId, parent: Option<ObjectId>) -> anyhow::Result<()> {
+        if let Some(parent) = parent {
+            self.validate_parent(child, parent)?;
+        } else if !self.objects.contains_key(&child) {
+            anyhow::bail!("Object {} doesn't exist", child);
+        }
+
+        let world = self.world_transform(child);
+        if let Some(obj) = self.objects.get_mut(&child) {
+            obj.parent = parent;
+        }
+        self.set_world_transform(child, world);
+        Ok(())
+    }
+
+    /// True if `ancestor` is somewhere above `id` in the hierarchy
+    pub fn is_ancestor(&self, ancestor: ObjectId, id: ObjectId) -> bool {
+        let mut current = self.objects.get(&id).and_then(|obj| obj.parent);
+        for _ in 0..self.objects.len() {
+            match current {
+                Some(parent) if parent == ancestor => return true,
+                Some(parent) => current = self.objects.get(&parent).and_then(|obj| obj.parent),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Direct children of an object, sorted by ID
+    pub fn children(&self, id: ObjectId) -> Vec<ObjectId> {
+        let mut children: Vec<ObjectId> = self
+            .objects
+            .values()
+            .filter(|obj| obj.parent == Some(id))
+            .map(|obj| obj.id)
+            .collect();
+        children.sort();
+        children
+    }
+
+    /// Every object in display order: roots by ID, each followed by its descendants, with its depth
+    pub fn hierarchy_order(&self) -> Vec<(ObjectId, usize)> {
+        let mut roots: Vec<ObjectId> = self
+            .objects
+            .values()
+            .filter(|obj| obj.parent.is_none_or(|parent| !self.objects.contains_key(&parent)))
+            .map(|obj| obj.id)
+            .collect();
+        roots.sort();
+
+        let mut order = Vec::new();
+        let mut stack: Vec<(ObjectId, usize)> = roots.into_iter().rev().map(|id| (id, 0)).collect();
+        while let Some((id, depth)) = stack.pop() {
+            order.push((id, depth));
+            stack.extend(self.children(id).into_iter().rev().map(|child| (child, depth + 1)));
+        }
+        order
+    }
+
+    /// An object followed by all of its descendants (depth-first, parents before children)
+    pub fn subtree(&self, id: ObjectId) -> Vec<ObjectId> {
+        let mut ids = Vec::new();
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            if ids.contains(&current) {
+                continue;
+            }
+            ids.push(current);
+            stack.extend(self.children(current).into_iter().rev());
+        }
+        ids
+    }
+
     /// Get a reference to an object
     pub fn get_object(&self, id: ObjectId) -> Option<&SceneObject> {
         self.objects.get(&id)
@@ -234,6 +746,20 @@ impl SceneGraph {
         &self.objects
     }
 
+    /// Number of objects of each type category, most common first (ties by name)
+    pub fn count_by_category(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = Vec::new();
+        for obj in self.objects.values() {
+            let category = obj.object_type.category();
+            match counts.iter_mut().find(|(name, _)| *name == category) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((category, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        counts
+    }
+
     /// Get all objects as a sorted vector
     pub fn objects_sorted(&self) -> Vec<&SceneObject> {
         let mut objects: Vec<&SceneObject> = self.objects.values().collect();
@@ -241,16 +767,229 @@ impl SceneGraph {
         objects
     }
 
-    /// Select an object
+    /// All layers, the default layer first
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut Layer> {
+        self.layers.iter_mut().find(|layer| layer.name == name)
+    }
+
+    /// Add a layer (no-op for an empty name or one that already exists); returns whether it was added
+    pub fn add_layer(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.layer(name).is_some() {
+            return false;
+        }
+        self.layers.push(Layer::new(name));
+        true
+    }
+
+    /// Remove a layer, moving its objects to the default layer
+    pub fn remove_layer(&mut self, name: &str) -> bool {
+        if name == DEFAULT_LAYER || self.layer(name).is_none() {
+            return false;
+        }
+        self.layers.retain(|layer| layer.name != name);
+        for obj in self.objects.values_mut().filter(|obj| obj.layer == name) {
+            obj.layer = default_layer();
+        }
+        true
+    }
+
+    /// Layers worth saving (nothing while only the untouched default layer exists)
+    fn saved_layers(&self) -> Vec<Layer> {
+        if self.layers == [Layer::new(DEFAULT_LAYER)] {
+            Vec::new()
+        } else {
+            self.layers.clone()
+        }
+    }
+
+    /// Number of objects on a layer
+    pub fn layer_object_count(&self, name: &str) -> usize {
+        self.objects.values().filter(|obj| obj.layer == name).count()
+    }
+
+    /// Make sure the default layer and every layer an object refers to exist
+    fn ensure_layers(&mut self) {
+        if self.layer(DEFAULT_LAYER).is_none() {
+            self.layers.insert(0, Layer::new(DEFAULT_LAYER));
+        }
+        let mut missing: Vec<String> = self
+            .objects
+            .values()
+            .filter(|obj| self.layer(&obj.layer).is_none())
+            .map(|obj| obj.layer.clone())
+            .collect();
+        missing.sort();
+        missing.dedup();
+        self.layers.extend(missing.into_iter().map(Layer::new));
+    }
+
+    /// Object is visible, its layer isn't hidden and solo doesn't filter it out (drawn and pickable)
+    pub fn is_shown(&self, obj: &SceneObject) -> bool {
+        obj.visible && self.layer(&obj.layer).is_none_or(|layer| layer.visible) && self.passes_solo(obj)
+    }
+
+    /// Solo only filters what is shown; `visible` flags are never touched, so exiting
+    /// solo brings back exactly what was shown before
+    fn passes_solo(&self, obj: &SceneObject) -> bool {
+        self.soloed.is_empty()
+            || self.soloed.contains(&obj.id)
+            || self.soloed.iter().any(|&soloed| self.is_ancestor(soloed, obj.id))
+            || (self.solo_shows_environment && obj.is_environment())
+    }
+
+    /// Whether any object is soloed
+    pub fn is_solo_active(&self) -> bool {
+        !self.soloed.is_empty()
+    }
+
+    /// Number of soloed objects (their children are shown too but not counted)
+    pub fn solo_count(&self) -> usize {
+        self.soloed.len()
+    }
+
+    pub fn is_soloed(&self, id: ObjectId) -> bool {
+        self.soloed.contains(&id)
+    }
+
+    /// Add an object to (or remove it from) the solo set
+    pub fn set_soloed(&mut self, id: ObjectId, soloed: bool) {
+        if !soloed {
+            self.soloed.remove(&id);
+        } else if self.objects.contains_key(&id) {
+            self.soloed.insert(id);
+        }
+    }
+
+    /// Exit solo: everything that is visible is shown again
+    pub fn clear_solo(&mut self) {
+        self.soloed.clear();
+    }
+
+    pub fn solo_shows_environment(&self) -> bool {
+        self.solo_shows_environment
+    }
+
+    pub fn set_solo_shows_environment(&mut self, shows_environment: bool) {
+        self.solo_shows_environment = shows_environment;
+    }
+
+    /// Object can be selected in the viewport (shown, and its layer isn't locked)
+    pub fn is_selectable(&self, obj: &SceneObject) -> bool {
+        self.is_shown(obj) && self.layer(&obj.layer).is_none_or(|layer| !layer.locked)
+    }
+
+    /// Drop objects on hidden or locked layers from the selection
+    /// (objects hidden by their own Visible flag stay selected so they can be shown again)
+    pub fn deselect_unselectable(&mut self) {
+        let selection = std::mem::take(&mut self.selection);
+        self.selection = selection
+            .into_iter()
+            .filter(|id| {
+                self.objects
+                    .get(id)
+                    .and_then(|obj| self.layer(&obj.layer))
+                    .is_none_or(|layer| layer.visible && !layer.locked)
+            })
+            .collect();
+        self.selected_object = self.selection.last().copied();
+    }
+
+    /// Select an object, replacing the current selection
     pub fn select_object(&mut self, id: ObjectId) {
         if self.objects.contains_key(&id) {
             self.selected_object = Some(id);
+            self.selection = vec![id];
         }
     }
 
-    /// Deselect current object
+    /// Add or remove an object from the selection (Ctrl+click).
+    /// Singletons can't be combined with other objects, so they replace the selection instead.
+    pub fn toggle_selection(&mut self, id: ObjectId) {
+        let Some(obj) = self.objects.get(&id) else {
+            return;
+        };
+
+        let current_is_singleton = self.selected_object().is_some_and(|selected| selected.is_singleton());
+        if obj.is_singleton() || current_is_singleton {
+            self.select_object(id);
+            return;
+        }
+
+        if self.selection.contains(&id) {
+            self.selection.retain(|&selected| selected != id);
+        } else {
+            self.selection.push(id);
+        }
+        self.selected_object = self.selection.last().copied();
+    }
+
+    /// Deselect all objects
     pub fn deselect(&mut self) {
         self.selected_object = None;
+        self.selection.clear();
+    }
+
+    /// Get all selected object IDs (the primary selection is last)
+    pub fn selected_ids(&self) -> &[ObjectId] {
+        &self.selection
+    }
+
+    /// Check whether an object is part of the selection
+    pub fn is_selected(&self, id: ObjectId) -> bool {
+        self.selection.contains(&id)
+    }
+
+    /// Number of selected objects
+    pub fn selection_count(&self) -> usize {
+        self.selection.len()
+    }
+
+    /// Average world position of all selected objects
+    pub fn selection_centroid(&self) -> Option<Vec3> {
+        let positions: Vec<Vec3> = self
+            .selection
+            .iter()
+            .filter(|id| self.objects.contains_key(id))
+            .map(|&id| self.world_transform(id).position)
+            .collect();
+
+        if positions.is_empty() {
+            return None;
+        }
+        Some(positions.iter().sum::<Vec3>() / positions.len() as f32)
+    }
+
+    /// Selected objects that can take a library material
+    pub fn selected_material_targets(&self) -> Vec<ObjectId> {
+        self.selection
+            .iter()
+            .copied()
+            .filter(|id| self.objects.get(id).is_some_and(|obj| obj.object_type.takes_material()))
+            .collect()
+    }
+
+    /// Assign a library material to every selected object that can take one, skipping the rest.
+    /// Returns how many objects changed (already using `material` doesn't count).
+    pub fn assign_material_to_selection(&mut self, material: &str) -> usize {
+        let mut changed = 0;
+        for id in self.selected_material_targets() {
+            if let Some(obj) = self.objects.get_mut(&id) {
+                if obj.material.as_deref() != Some(material) {
+                    obj.material = Some(material.to_string());
+                    changed += 1;
+                }
+            }
+        }
+        changed
     }
 
     /// Get currently selected object ID
@@ -268,12 +1007,13 @@ impl SceneGraph {
         self.selected_object.and_then(|id| self.objects.get_mut(&id))
     }
 
-    /// Find object by type (returns first match)
+    /// Find object by type (returns the oldest match, so the pick is stable)
     pub fn find_by_type(&self, object_type: ObjectType) -> Option<ObjectId> {
         self.objects
             .iter()
-            .find(|(_, obj)| obj.object_type == object_type)
+            .filter(|(_, obj)| obj.object_type == object_type)
             .map(|(id, _)| *id)
+            .min()
     }
 
     /// Get all objects of a specific type
@@ -284,6 +1024,31 @@ impl SceneGraph {
             .map(|(id, _)| *id)
             .collect()
     }
+
+    /// All objects carrying `tag`, oldest first
+    pub fn find_by_tag(&self, tag: &str) -> Vec<ObjectId> {
+        self.objects_with_tags(&[tag])
+    }
+
+    /// All objects carrying every one of `tags`, oldest first (every object if `tags` is empty)
+    pub fn objects_with_tags(&self, tags: &[&str]) -> Vec<ObjectId> {
+        let mut ids: Vec<ObjectId> = self
+            .objects
+            .values()
+            .filter(|obj| tags.iter().all(|tag| obj.has_tag(tag)))
+            .map(|obj| obj.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Every tag used in the scene, sorted and without duplicates
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.objects.values().flat_map(|obj| obj.tags.iter().cloned()).collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
 }
 
 impl Default for SceneGraph {
@@ -292,39 +1057,147 @@ impl Default for SceneGraph {
     }
 }
 
+/// Engine config shared by every scene saved before scenes carried their own config path
+pub const LEGACY_CONFIG_PATH: &str = "config/default.json";
+
+/// Upgrades for older scene layouts, oldest first (see `migration::load_versioned`)
+/// Sequential IDs from before stable IDs are handled by `migrate_legacy_ids` instead.
+const SCENE_MIGRATIONS: &[crate::migration::Migration] = &[];
+
+/// Layout version written to new scene files
+pub const SCENE_VERSION: u32 = crate::migration::current_version(SCENE_MIGRATIONS);
+
+fn scene_version() -> u32 {
+    SCENE_VERSION
+}
+
 /// Serializable scene data (just transforms and metadata)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SceneData {
+    /// Layout version, upgraded on load (see `SCENE_MIGRATIONS`)
+    #[serde(default = "scene_version")]
+    pub version: u32,
     pub objects: Vec<SceneObject>,
+    /// Engine config (skybox, nebula, camera, ...) paired with this scene
+    /// Missing in older scene files, which keep using `LEGACY_CONFIG_PATH`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_path: Option<String>,
+    /// Recorded camera flythroughs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub camera_tracks: Vec<CameraTrack>,
+    /// Editor layers with their visibility/lock state (older scenes only have the default layer)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<Layer>,
+    /// Set by `load` when sequential IDs were replaced in memory; the file is left as it
+    /// was until the scene is saved
+    #[serde(skip)]
+    pub ids_migrated: bool,
 }
 
 impl SceneData {
     pub fn from_scene_graph(scene: &SceneGraph) -> Self {
-        let mut objects = scene.objects_sorted().into_iter().cloned().collect();
-        Self { objects }
+        // Sorted by ID so re-saving an unchanged scene writes the same file
+        let objects = scene.objects_sorted().into_iter().cloned().collect();
+        Self {
+            version: SCENE_VERSION,
+            objects,
+            config_path: None,
+            camera_tracks: Vec::new(),
+            layers: scene.saved_layers(),
+            ids_migrated: false,
+        }
+    }
+
+    /// Config file paired with a scene file (`config/scene.json` -> `config/scene.config.json`)
+    pub fn config_path_for(scene_path: &str) -> String {
+        let path = std::path::Path::new(scene_path);
+        let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        path.with_file_name(format!("{}.config.json", stem)).to_string_lossy().into_owned()
+    }
+
+    /// Config file this scene's visual settings load from
+    pub fn effective_config_path(&self) -> &str {
+        self.config_path.as_deref().unwrap_or(LEGACY_CONFIG_PATH)
     }
 
     pub fn to_scene_graph(&self) -> SceneGraph {
         let mut scene = SceneGraph::new();
+        scene.layers = self.layers.clone();
 
+        // Objects keep their saved IDs; a duplicate ID from a hand-edited file gets a fresh one
+        let mut id_map: HashMap<ObjectId, ObjectId> = HashMap::new();
         for obj in &self.objects {
-            let id = scene.add_object(obj.name.clone(), obj.object_type.clone());
-            if let Some(scene_obj) = scene.get_object_mut(id) {
-                scene_obj.transform = obj.transform;
-                scene_obj.visible = obj.visible;
+            let id = if scene.objects.contains_key(&obj.id) {
+                crate::console::warn(format!("Scene object '{}' has a duplicate ID, assigning a new one", obj.name));
+                scene.unused_id()
+            } else {
+                obj.id
+            };
+            let mut scene_obj = obj.clone();
+            scene_obj.id = id;
+            scene_obj.parent = None;
+            scene.objects.insert(id, scene_obj);
+            id_map.entry(obj.id).or_insert(id);
+        }
+
+        // Reconnect parents once every object exists (invalid links from a hand-edited file are dropped)
+        for obj in &self.objects {
+            let (Some(&child), Some(parent)) = (id_map.get(&obj.id), obj.parent) else {
+                continue;
+            };
+            let Some(&parent) = id_map.get(&parent) else {
+                crate::console::warn(format!("Scene object '{}' has a missing parent, attaching to root", obj.name));
+                continue;
+            };
+
+            match scene.validate_parent(child, parent) {
+                Ok(()) => {
+                    if let Some(scene_obj) = scene.get_object_mut(child) {
+                        scene_obj.parent = Some(parent);
+                    }
+                }
+                Err(e) => crate::console::warn(format!("Scene object '{}' can't keep its parent: {}", obj.name, e)),
             }
         }
 
+        scene.ensure_layers();
         scene
     }
 
-    /// Load from JSON file
+    /// Load from JSON file, upgrading older layouts
+    /// Scenes saved with sequential IDs get stable IDs in memory (see `ids_migrated`)
     pub fn load(path: &str) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        let data: SceneData = serde_json::from_str(&content)?;
+        let (mut data, version) = crate::migration::load_versioned::<SceneData>(&content, SCENE_MIGRATIONS)?;
+        if version < SCENE_VERSION {
+            crate::console::info(format!("Upgraded scene {} from v{} to v{}", path, version, SCENE_VERSION));
+        }
+        data.ids_migrated = data.migrate_legacy_ids();
+        if data.ids_migrated {
+            println!("Assigned stable IDs to {} objects in {}", data.objects.len(), path);
+        }
         Ok(data)
     }
 
+    /// Replace sequential IDs from older scene files with stable ones, in file order,
+    /// pointing parents at the new IDs. Returns true if anything changed
+    pub fn migrate_legacy_ids(&mut self) -> bool {
+        if !self.objects.iter().any(|obj| is_legacy_object_id(obj.id)) {
+            return false;
+        }
+
+        let mut id_map: HashMap<ObjectId, ObjectId> = HashMap::new();
+        for obj in &mut self.objects {
+            let id = generate_object_id();
+            id_map.entry(obj.id).or_insert(id);
+            obj.id = id;
+        }
+        for obj in &mut self.objects {
+            obj.parent = obj.parent.and_then(|parent| id_map.get(&parent).copied());
+        }
+        true
+    }
+
     /// Save to JSON file with pretty formatting
     pub fn save(&self, path: &str) -> anyhow::Result<()> {
         // Ensure parent directory exists
@@ -337,13 +1210,31 @@ impl SceneData {
         Ok(())
     }
 
+    /// Export visible meshes and lights to a standalone binary glTF (`.glb`) file
+    /// `material_for` resolves the PBR material each mesh object is drawn with
+    pub fn export_gltf(
+        &self,
+        path: &str,
+        material_for: impl Fn(&SceneObject) -> crate::material::MaterialProperties,
+    ) -> anyhow::Result<crate::gltf_export::GltfExportSummary> {
+        crate::gltf_export::export_glb(self, path, material_for)
+    }
+
     /// Load or create default scene
+    /// Only a missing file is replaced; an unreadable one is left for the user to fix
     pub fn load_or_default(path: &str) -> Self {
-        Self::load(path).unwrap_or_else(|_| {
-            let scene_data = Self::default();
-            let _ = scene_data.save(path);
-            scene_data
-        })
+        match Self::load(path) {
+            Ok(scene_data) => scene_data,
+            Err(_) if !std::path::Path::new(path).exists() => {
+                let scene_data = Self::default();
+                let _ = scene_data.save(path);
+                scene_data
+            }
+            Err(e) => {
+                crate::console::error(format!("Scene {} not loaded, using the default scene: {}", path, e));
+                Self::default()
+            }
+        }
     }
 
     /// Detect discrepancies between saved config and default scene
@@ -368,6 +1259,23 @@ impl SceneData {
     pub fn merge_with_default(self, default_scene: Self) -> Self {
         use std::collections::HashMap;
 
+        let config_path = self.config_path.clone();
+        let camera_tracks = self.camera_tracks.clone();
+        let layers = self.layers.clone();
+        let ids_migrated = self.ids_migrated;
+
+        // Parents are stored by ID, which differs between the saved and default scenes - remember them by name
+        let parent_names: HashMap<String, String> = [&default_scene.objects, &self.objects]
+            .into_iter()
+            .flat_map(|objects| {
+                let names: HashMap<ObjectId, &String> = objects.iter().map(|o| (o.id, &o.name)).collect();
+                objects
+                    .iter()
+                    .filter_map(|o| Some((o.name.clone(), (*names.get(&o.parent?)?).clone())))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
         // Build map of saved objects by name (config takes precedence)
         let mut objects_by_name: HashMap<String, SceneObject> = HashMap::new();
 
@@ -381,28 +1289,17 @@ impl SceneData {
             objects_by_name.insert(obj.name.clone(), obj);
         }
 
-        // Convert back to vec and reassign IDs sequentially
+        // Objects keep their stable IDs; sort by ID like a saved scene
         let mut objects: Vec<SceneObject> = objects_by_name.into_values().collect();
+        objects.sort_by_key(|obj| obj.id);
 
-        // Sort to maintain consistent order (Skybox/Nebula at end, Cubes/Meshes first)
-        objects.sort_by(|a, b| {
-            match (&a.object_type, &b.object_type) {
-                (ObjectType::Skybox, ObjectType::Skybox) => a.name.cmp(&b.name),
-                (ObjectType::Skybox, _) => std::cmp::Ordering::Greater,
-                (_, ObjectType::Skybox) => std::cmp::Ordering::Less,
-                (ObjectType::Nebula, ObjectType::Nebula) => a.name.cmp(&b.name),
-                (ObjectType::Nebula, _) => std::cmp::Ordering::Greater,
-                (_, ObjectType::Nebula) => std::cmp::Ordering::Less,
-                _ => a.name.cmp(&b.name),
-            }
-        });
-
-        // Reassign IDs sequentially
-        for (new_id, obj) in objects.iter_mut().enumerate() {
-            obj.id = new_id;
+        // Point parents at the IDs of the merged objects
+        let ids_by_name: HashMap<String, ObjectId> = objects.iter().map(|o| (o.name.clone(), o.id)).collect();
+        for obj in &mut objects {
+            obj.parent = parent_names.get(&obj.name).and_then(|parent| ids_by_name.get(parent).copied());
         }
 
-        Self { objects }
+        Self { version: SCENE_VERSION, objects, config_path, camera_tracks, layers, ids_migrated }
     }
 
     /// Load and merge with default scene
@@ -432,19 +1329,24 @@ impl SceneData {
                     saved_config
                 }
             }
-            Err(_) => {
+            Err(_) if !std::path::Path::new(path).exists() => {
                 // No saved config, use default and save it
                 println!("No saved scene found, creating default at {}", path);
                 let _ = default_scene.save(path);
                 default_scene
             }
+            Err(e) => {
+                // Never overwrite a scene that failed to load: the user may want to fix it
+                crate::console::error(format!("Scene {} not loaded, using the default scene: {}", path, e));
+                default_scene
+            }
         }
     }
 }
 
 impl Default for SceneData {
     fn default() -> Self {
-        Self {
+        let mut data = Self {
             objects: vec![
                 SceneObject::new(0, "Cube 1".to_string(), ObjectType::Cube)
                     .with_transform(Transform::identity())
@@ -467,6 +1369,369 @@ impl Default for SceneData {
                 SceneObject::new(4, "Skybox".to_string(), ObjectType::Skybox)
                     .with_transform(Transform::identity()),
             ],
+            version: SCENE_VERSION,
+            config_path: None,
+            camera_tracks: Vec::new(),
+            layers: Vec::new(),
+            ids_migrated: false,
+        };
+        data.migrate_legacy_ids();
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_count_by_category_groups_shapes_and_files() {
+        let mut scene = SceneGraph::new();
+        scene.add_object("A".to_string(), ObjectType::Mesh("models/a.glb".to_string()));
+        scene.add_object("B".to_string(), ObjectType::Mesh("models/b.obj".to_string()));
+        scene.add_object("Sphere".to_string(), ObjectType::Primitive(PrimitiveShape::Sphere));
+        scene.add_object("Ring".to_string(), ObjectType::Primitive(PrimitiveShape::Torus));
+        scene.add_object("Lamp".to_string(), ObjectType::PointLight);
+
+        let counts = scene.count_by_category();
+        assert_eq!(counts.iter().map(|(_, count)| count).sum::<usize>(), scene.objects().len());
+        assert_eq!(counts[..2], [("Mesh", 2), ("Primitive", 2)]);
+        assert!(counts.contains(&("Point Light", 1)));
+    }
+
+    #[test]
+    fn test_child_follows_parent() {
+        let mut scene = SceneGraph::new();
+        let hull = scene.add_object_with_transform(
+            "Hull".to_string(),
+            ObjectType::Cube,
+            Transform::new(Vec3::new(10.0, 0.0, 0.0), Quat::from_rotation_y(std::f32::consts::FRAC_PI_2), Vec3::ONE),
+        );
+        let turret = scene.add_object_with_transform("Turret".to_string(), ObjectType::Cube, Transform::from_position(Vec3::new(1.0, 0.0, 0.0)));
+
+        // Reparenting keeps the turret where it was in the world
+        scene.set_parent(turret, Some(hull)).unwrap();
+        assert_vec3_eq(scene.world_transform(turret).position, Vec3::new(1.0, 0.0, 0.0));
+        assert_vec3_eq(scene.get_object(turret).unwrap().transform.position, Vec3::new(0.0, 0.0, -9.0));
+
+        // Moving the hull moves the turret
+        scene.get_object_mut(hull).unwrap().transform.position.y = 5.0;
+        assert_vec3_eq(scene.world_transform(turret).position, Vec3::new(1.0, 5.0, 0.0));
+    }
+
+    #[test]
+    fn test_material_assignment_skips_objects_without_materials() {
+        let mut scene = SceneGraph::new();
+        let hull = scene.add_object("Hull".to_string(), ObjectType::Mesh("ship.obj".to_string()));
+        let armor = scene.add_object("Armor".to_string(), ObjectType::Cube);
+        let light = scene.add_object("Light".to_string(), ObjectType::PointLight);
+        scene.select_object(hull);
+        scene.toggle_selection(armor);
+        scene.toggle_selection(light);
+
+        assert_eq!(scene.selected_material_targets(), vec![hull, armor]);
+        assert_eq!(scene.assign_material_to_selection("Metal"), 2);
+        assert_eq!(scene.get_object(armor).unwrap().material.as_deref(), Some("Metal"));
+        assert_eq!(scene.get_object(light).unwrap().material, None);
+
+        // Re-applying the same material changes nothing
+        assert_eq!(scene.assign_material_to_selection("Metal"), 0);
+    }
+
+    #[test]
+    fn test_cycles_are_rejected() {
+        let mut scene = SceneGraph::new();
+        let a = scene.add_object("A".to_string(), ObjectType::Cube);
+        let b = scene.add_object("B".to_string(), ObjectType::Cube);
+        let c = scene.add_object("C".to_string(), ObjectType::Cube);
+        scene.set_parent(b, Some(a)).unwrap();
+        scene.set_parent(c, Some(b)).unwrap();
+
+        assert!(scene.set_parent(a, Some(c)).is_err());
+        assert!(scene.set_parent(a, Some(a)).is_err());
+        assert_eq!(scene.get_object(a).unwrap().parent, None);
+        assert_eq!(scene.subtree(a), vec![a, b, c]);
+    }
+
+    #[test]
+    fn test_duplicate_and_reload_keep_hierarchy() {
+        let mut scene = SceneGraph::new();
+        let hull = scene.add_object("Hull".to_string(), ObjectType::Cube);
+        let turret = scene.add_object("Turret".to_string(), ObjectType::Cube);
+        scene.set_parent(turret, Some(hull)).unwrap();
+        let gap = scene.add_object("Gap".to_string(), ObjectType::Cube);
+        scene.remove_object(gap);
+
+        let copy = scene.duplicate_object(hull).unwrap();
+        let copied_children = scene.children(copy);
+        assert_eq!(copied_children.len(), 1);
+        assert_eq!(scene.get_object(copied_children[0]).unwrap().name, "Turret Copy");
+        assert_eq!(scene.children(hull), vec![turret]);
+
+        // Saved IDs survive a reload, and parents with them
+        let reloaded = SceneData::from_scene_graph(&scene).to_scene_graph();
+        for obj in scene.objects().values() {
+            let reloaded_obj = reloaded.objects().values().find(|o| o.name == obj.name).unwrap();
+            let parent_name = |graph: &SceneGraph, parent: Option<ObjectId>| {
+                parent.and_then(|id| graph.get_object(id)).map(|p| p.name.clone())
+            };
+            assert_eq!(parent_name(&reloaded, reloaded_obj.parent), parent_name(&scene, obj.parent));
         }
     }
+
+    #[test]
+    fn test_duplicate_array_fills_grid() {
+        let mut scene = SceneGraph::new();
+        let hull = scene.add_object("Frigate".to_string(), ObjectType::Cube);
+        let turret = scene.add_object("Turret".to_string(), ObjectType::Cube);
+        scene.set_parent(turret, Some(hull)).unwrap();
+        scene.get_object_mut(hull).unwrap().transform.position = Vec3::new(1.0, 0.0, 0.0);
+
+        let array = DuplicateArray { counts: [3, 2, 1], spacing: Vec3::new(10.0, 5.0, 0.0) };
+        let copies = scene.duplicate_array(hull, &array).unwrap();
+        assert_eq!(copies.len(), 5);
+        assert_eq!(array.copy_count(), 5);
+
+        // Every cell but the original's is filled once, children come along
+        let mut positions: Vec<Vec3> = copies.iter().map(|&id| scene.get_object(id).unwrap().transform.position).collect();
+        positions.sort_by(|a, b| (a.y, a.x).partial_cmp(&(b.y, b.x)).unwrap());
+        assert_eq!(
+            positions,
+            vec![Vec3::new(11.0, 0.0, 0.0), Vec3::new(21.0, 0.0, 0.0), Vec3::new(1.0, 5.0, 0.0), Vec3::new(11.0, 5.0, 0.0), Vec3::new(21.0, 5.0, 0.0)]
+        );
+        assert!(copies.iter().all(|&id| scene.children(id).len() == 1));
+
+        // Names are unique, and a second array continues the numbering
+        let more = scene.duplicate_array(hull, &DuplicateArray { counts: [2, 1, 1], spacing: Vec3::ONE }).unwrap();
+        assert_eq!(scene.get_object(more[0]).unwrap().name, "Frigate 6");
+        assert_eq!(scene.get_object(scene.children(more[0])[0]).unwrap().name, "Turret 6");
+
+        let too_many = DuplicateArray { counts: [11, 10, 10], spacing: Vec3::ONE };
+        assert!(scene.duplicate_array(hull, &too_many).is_err());
+    }
+
+    #[test]
+    fn test_directional_lights_are_regular_objects() {
+        let mut scene = SceneGraph::new();
+        let key = scene.add_object("Directional Light".to_string(), ObjectType::DirectionalLight);
+        assert!(!scene.get_object(key).unwrap().is_singleton());
+
+        scene.get_object_mut(key).unwrap().directional_light.as_mut().unwrap().intensity = 0.25;
+        let fill = scene.duplicate_object(key).unwrap();
+        assert_eq!(scene.get_by_type(ObjectType::DirectionalLight).len(), 2);
+
+        let reloaded = SceneData::from_scene_graph(&scene).to_scene_graph();
+        let settings = reloaded.get_object(fill).unwrap().directional_light.unwrap();
+        assert_eq!(settings.intensity, 0.25);
+    }
+
+    #[test]
+    fn test_nebulae_keep_their_own_settings() {
+        let mut scene = SceneGraph::new();
+        let first = scene.add_object("Nebula".to_string(), ObjectType::Nebula);
+        assert!(!scene.get_object(first).unwrap().is_singleton());
+        // Legacy nebulae have no settings of their own and use the engine default
+        assert!(scene.get_object(first).unwrap().nebula.is_none());
+
+        scene.get_object_mut(first).unwrap().nebula = Some(NebulaConfig { density: 0.5, ..Default::default() });
+        let second = scene.duplicate_object(first).unwrap();
+        scene.get_object_mut(second).unwrap().nebula.as_mut().unwrap().color_center = Vec3::X;
+        assert_eq!(scene.get_by_type(ObjectType::Nebula).len(), 2);
+
+        let reloaded = SceneData::from_scene_graph(&scene).to_scene_graph();
+        let first_settings = reloaded.get_object(first).unwrap().nebula.unwrap();
+        let second_settings = reloaded.get_object(second).unwrap().nebula.unwrap();
+        assert_eq!(first_settings.density, 0.5);
+        assert_eq!(first_settings.color_center, NebulaConfig::default().color_center);
+        assert_eq!(second_settings.density, 0.5);
+        assert_eq!(second_settings.color_center, Vec3::X);
+
+        // The oldest nebula stays the primary one
+        assert_eq!(reloaded.find_by_type(ObjectType::Nebula), Some(first.min(second)));
+    }
+
+    #[test]
+    fn test_resave_is_byte_identical() {
+        let mut scene = SceneGraph::new();
+        let hull = scene.add_object("Hull".to_string(), ObjectType::Cube);
+        let turret = scene.add_object("Turret".to_string(), ObjectType::Cube);
+        scene.set_parent(turret, Some(hull)).unwrap();
+        let light = scene.add_object("Lamp".to_string(), ObjectType::PointLight);
+        scene.get_object_mut(light).unwrap().material = Some("Brass".to_string());
+
+        let saved = serde_json::to_string_pretty(&SceneData::from_scene_graph(&scene)).unwrap();
+        let reloaded: SceneData = serde_json::from_str(&saved).unwrap();
+        let resaved = serde_json::to_string_pretty(&SceneData::from_scene_graph(&reloaded.to_scene_graph())).unwrap();
+        assert_eq!(saved, resaved);
+
+        // IDs increase with creation order, so the file lists objects oldest first
+        assert!(hull < turret);
+        assert!(!is_legacy_object_id(hull));
+    }
+
+    #[test]
+    fn test_layers_hide_lock_and_persist() {
+        let mut scene = SceneGraph::new();
+        let ship = scene.add_object("Ship".to_string(), ObjectType::Cube);
+        let fx = scene.add_object("Glow".to_string(), ObjectType::Cube);
+        assert!(scene.add_layer("Ships"));
+        assert!(scene.add_layer("FX"));
+        assert!(!scene.add_layer("FX"));
+        scene.get_object_mut(ship).unwrap().layer = "Ships".to_string();
+        scene.get_object_mut(fx).unwrap().layer = "FX".to_string();
+
+        // Hidden layers aren't shown (or hit by rays), locked ones are shown but not selectable
+        scene.layer_mut("FX").unwrap().visible = false;
+        scene.layer_mut("Ships").unwrap().locked = true;
+        let (ship_obj, fx_obj) = (scene.get_object(ship).unwrap(), scene.get_object(fx).unwrap());
+        assert!(!scene.is_shown(fx_obj));
+        assert!(scene.is_shown(ship_obj) && !scene.is_selectable(ship_obj));
+        let meshes = crate::raycast::PickMeshes::new();
+        assert!(scene.raycast(DVec3::new(0.0, 0.0, 5.0), DVec3::NEG_Z, &meshes).is_some_and(|hit| hit.object_id == ship));
+
+        scene.select_object(ship);
+        scene.deselect_unselectable();
+        assert_eq!(scene.selection_count(), 0);
+
+        // Layer state survives a save/load round trip
+        let reloaded = SceneData::from_scene_graph(&scene).to_scene_graph();
+        assert_eq!(reloaded.layers(), scene.layers());
+        assert_eq!(reloaded.get_object(fx).unwrap().layer, "FX");
+
+        // Removing a layer moves its objects back to the default layer
+        assert!(scene.remove_layer("Ships"));
+        assert!(!scene.remove_layer(DEFAULT_LAYER));
+        assert_eq!(scene.get_object(ship).unwrap().layer, DEFAULT_LAYER);
+        assert_eq!(scene.layer_object_count(DEFAULT_LAYER), 1);
+
+        // Untouched scenes don't write a layer list
+        assert!(SceneData::from_scene_graph(&SceneGraph::new()).layers.is_empty());
+    }
+
+    #[test]
+    fn test_tags_query_and_round_trip() {
+        let mut scene = SceneGraph::new();
+        let fighter = scene.add_object("Fighter".to_string(), ObjectType::Cube);
+        let cruiser = scene.add_object("Cruiser".to_string(), ObjectType::Cube);
+        let beacon = scene.add_object("Beacon".to_string(), ObjectType::Cube);
+        for (id, tags) in [(fighter, ["enemy", "fighter"]), (cruiser, ["enemy", " objective "])] {
+            let obj = scene.get_object_mut(id).unwrap();
+            for tag in tags {
+                assert!(obj.add_tag(tag));
+            }
+        }
+        let obj = scene.get_object_mut(beacon).unwrap();
+        assert!(obj.add_tag("spawn"));
+        assert!(!obj.add_tag("spawn") && !obj.add_tag("  "));
+
+        assert_eq!(scene.find_by_tag("enemy"), vec![fighter, cruiser]);
+        assert_eq!(scene.find_by_tag("objective"), vec![cruiser]);
+        assert!(scene.find_by_tag("Enemy").is_empty());
+        assert_eq!(scene.objects_with_tags(&["enemy", "fighter"]), vec![fighter]);
+        assert_eq!(scene.all_tags(), vec!["enemy", "fighter", "objective", "spawn"]);
+
+        // Tags survive save/load and are copied by Duplicate
+        let reloaded = SceneData::from_scene_graph(&scene).to_scene_graph();
+        assert_eq!(reloaded.get_object(cruiser).unwrap().tags, vec!["enemy", "objective"]);
+        let copy = scene.duplicate_object(fighter).unwrap();
+        assert_eq!(scene.find_by_tag("fighter"), vec![fighter, copy]);
+
+        assert!(scene.get_object_mut(fighter).unwrap().remove_tag("enemy"));
+        assert_eq!(scene.find_by_tag("enemy"), vec![cruiser, copy]);
+    }
+
+    #[test]
+    fn test_solo_filters_without_touching_visibility() {
+        let mut scene = SceneGraph::new();
+        let ship = scene.add_object("Ship".to_string(), ObjectType::Cube);
+        let turret = scene.add_object("Turret".to_string(), ObjectType::Cube);
+        let rock = scene.add_object("Rock".to_string(), ObjectType::Cube);
+        let hidden = scene.add_object("Hidden".to_string(), ObjectType::Cube);
+        let light = scene.add_object("Light".to_string(), ObjectType::PointLight);
+        scene.set_parent(turret, Some(ship)).unwrap();
+        scene.get_object_mut(hidden).unwrap().visible = false;
+        let shown = |scene: &SceneGraph| -> Vec<ObjectId> {
+            scene.objects_sorted().into_iter().filter(|obj| scene.is_shown(obj)).map(|obj| obj.id).collect()
+        };
+        let before = shown(&scene);
+
+        // Soloed objects bring their children; lights stay unless the environment is hidden too
+        scene.set_soloed(ship, true);
+        scene.set_soloed(hidden, true);
+        assert_eq!(shown(&scene), vec![ship, turret, light]);
+        scene.set_solo_shows_environment(false);
+        assert_eq!(shown(&scene), vec![ship, turret]);
+        assert!(scene.get_object(rock).unwrap().visible && !scene.get_object(hidden).unwrap().visible);
+
+        // Exiting solo shows exactly what was shown before
+        scene.clear_solo();
+        assert!(!scene.is_solo_active());
+        assert_eq!(shown(&scene), before);
+
+        // Deleting the last soloed object ends solo
+        scene.set_soloed(rock, true);
+        scene.remove_object(rock);
+        assert!(!scene.is_solo_active());
+    }
+
+    #[test]
+    fn test_legacy_ids_are_migrated() {
+        let mut legacy = SceneData {
+            version: SCENE_VERSION,
+            objects: vec![
+                SceneObject::new(0, "Hull".to_string(), ObjectType::Cube),
+                SceneObject::new(1, "Turret".to_string(), ObjectType::Cube),
+            ],
+            config_path: None,
+            camera_tracks: Vec::new(),
+            layers: Vec::new(),
+            ids_migrated: false,
+        };
+        legacy.objects[1].parent = Some(0);
+
+        assert!(legacy.migrate_legacy_ids());
+        assert!(legacy.objects.iter().all(|obj| !is_legacy_object_id(obj.id)));
+        assert!(legacy.objects[0].id < legacy.objects[1].id);
+        assert_eq!(legacy.objects[1].parent, Some(legacy.objects[0].id));
+
+        // Already migrated scenes are left alone
+        assert!(!legacy.migrate_legacy_ids());
+    }
+
+    #[test]
+    fn test_loading_a_legacy_scene_leaves_the_file_alone() {
+        let path = std::env::temp_dir().join(format!("tribal_legacy_scene_{}.json", std::process::id()));
+        let legacy = SceneData {
+            objects: vec![SceneObject::new(0, "Hull".to_string(), ObjectType::Cube)],
+            ..SceneData::default()
+        };
+        let content = serde_json::to_string_pretty(&legacy).unwrap();
+        std::fs::write(&path, &content).unwrap();
+
+        let loaded = SceneData::load(path.to_str().unwrap()).unwrap();
+        assert!(loaded.ids_migrated);
+        assert!(!is_legacy_object_id(loaded.objects[0].id));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_scene_config_path() {
+        assert_eq!(SceneData::config_path_for("config/scene.json"), "config/scene.config.json");
+        assert_eq!(SceneData::config_path_for("missions/alpha.json"), "missions/alpha.config.json");
+
+        // Scenes saved before per-scene configs keep loading the shared file
+        let legacy: SceneData = serde_json::from_str(r#"{"objects": []}"#).unwrap();
+        assert_eq!(legacy.effective_config_path(), LEGACY_CONFIG_PATH);
+
+        let mut scene = SceneData::from_scene_graph(&SceneGraph::new());
+        scene.config_path = Some("config/scene.config.json".to_string());
+        let reloaded: SceneData = serde_json::from_str(&serde_json::to_string(&scene).unwrap()).unwrap();
+        assert_eq!(reloaded.effective_config_path(), "config/scene.config.json");
+        assert_eq!(reloaded.merge_with_default(SceneData::default()).effective_config_path(), "config/scene.config.json");
+    }
 }
@@ -1,5 +1,32 @@
 use imgui::Ui;
 use glam::Vec3;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by "Reset Layout"; picked up at the start of the next UI frame
+static LAYOUT_RESET_REQUESTED: AtomicBool = AtomicBool::new(false);
+/// True for the one frame in which every window goes back to its default rect
+static LAYOUT_RESETTING: AtomicBool = AtomicBool::new(false);
+
+/// Put every window back at its default position, size and collapsed state next frame
+pub fn request_layout_reset() {
+    LAYOUT_RESET_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Start a UI frame; a pending layout reset applies to the whole frame
+pub fn begin_layout_frame() {
+    let resetting = LAYOUT_RESET_REQUESTED.swap(false, Ordering::Relaxed);
+    LAYOUT_RESETTING.store(resetting, Ordering::Relaxed);
+}
+
+/// Condition for a window's default rect: its first use (saved layouts win
+/// afterwards), or always during a layout reset
+pub fn layout_condition() -> imgui::Condition {
+    if LAYOUT_RESETTING.load(Ordering::Relaxed) {
+        imgui::Condition::Always
+    } else {
+        imgui::Condition::FirstUseEver
+    }
+}
 
 /// Builder for creating GUI panels with common controls
 pub struct GuiPanelBuilder<'a> {
@@ -35,9 +62,9 @@ impl<'a> GuiPanelBuilder<'a> {
     {
         self.ui
             .window(self.title)
-            .size(self.size, imgui::Condition::FirstUseEver)
-            .position(self.position, imgui::Condition::FirstUseEver)
-            .collapsed(true, imgui::Condition::FirstUseEver)
+            .size(self.size, layout_condition())
+            .position(self.position, layout_condition())
+            .collapsed(true, layout_condition())
             .build(|| {
                 let mut content = GuiContentBuilder::new(self.ui);
                 f(&mut content);
@@ -162,6 +189,44 @@ impl<'a> GuiContentBuilder<'a> {
         self
     }
 
+    /// Input fields for Vec3 values with a lock toggle before each component.
+    /// Locked components are greyed out and can't be edited.
+    pub fn input_vec3_locked(&mut self, label: &str, value: &mut Vec3, locks: &mut [bool; 3]) -> &mut Self {
+        let mut arr = value.to_array();
+        let field_width = (self.ui.content_region_avail()[0] * 0.65 / 3.0 - 24.0).max(30.0);
+
+        for (i, axis) in ["X", "Y", "Z"].iter().enumerate() {
+            if i > 0 {
+                self.ui.same_line();
+            }
+
+            // Locked axes show as a highlighted button
+            let button_color = self.ui.push_style_color(
+                imgui::StyleColor::Button,
+                if locks[i] { [0.8, 0.5, 0.1, 1.0] } else { [0.25, 0.25, 0.3, 1.0] },
+            );
+            if self.ui.small_button(format!("{}##lock_{}_{}", axis, label, axis)) {
+                locks[i] = !locks[i];
+            }
+            button_color.pop();
+            if self.ui.is_item_hovered() {
+                let action = if locks[i] { "Unlock" } else { "Lock" };
+                self.ui.tooltip_text(format!("{} {} (gizmo and input edits keep it fixed)", action, axis));
+            }
+
+            self.ui.same_line();
+            self.ui.set_next_item_width(field_width);
+            let disabled = self.ui.begin_disabled(locks[i]);
+            self.ui.input_float(format!("##{}_{}", label, axis), &mut arr[i]).build();
+            disabled.end();
+        }
+        self.ui.same_line();
+        self.ui.text(label);
+
+        *value = Vec3::from_array(arr);
+        self
+    }
+
     /// Input for angles with wrap-around (in degrees)
     pub fn input_angle(&mut self, label: &str, value_deg: &mut f32) -> &mut Self {
         self.ui.input_float(label, value_deg).build();
@@ -2,16 +2,30 @@ mod gui_builder;
 
 pub use gui_builder::{GuiPanelBuilder, GuiContentBuilder, SkyboxFxBuilder};
 
-use imgui::{Context, Ui};
-use crate::game::{Game, SkyboxConfig, SSAOConfig, StarConfig};
-use crate::nebula::NebulaConfig;
-use crate::config::EngineConfig;
-use crate::scene::{SceneData, ObjectType};
-use crate::gizmo::GizmoMode;
+use imgui::{Context, TextureId, Ui};
+use crate::game::{AntiAliasingConfig, AntiAliasingMode, AutosaveConfig, BackgroundMode, BloomConfig, DebugView, FogMode, Game, GizmoConfig, GraphicsConfig, LodConfig, OutlineConfig, ParticleConfig, PresentMode, SceneBudgetConfig, SkyboxConfig, SkyboxMode, SSAOConfig, ShadowConfig, SsrConfig, StarConfig, TonemapOperator};
+use crate::game::{MAX_STAR_EXPOSURE, MIN_STAR_EXPOSURE};
+use crate::nebula::{NebulaConfig, NebulaQuality, MAX_NEBULA_STEPS, MIN_NEBULA_STEPS};
+use crate::autosave::AUTOSAVE_SCENE_PATH;
+use crate::config::{EngineConfig, WindowConfigData};
+use crate::ecs::EcsWorld;
+use crate::scene::{SceneData, ObjectId, ObjectType, LEGACY_CONFIG_PATH, MAX_ARRAY_COPIES};
+use crate::idle_motion::{Bob, Spin};
+use crate::game_manager::TIME_SCALES;
+use crate::gizmo::{GizmoMode, GizmoSpace};
+use crate::keymap::{Action, ActionCategory, KeyBinding, Keymap};
+use crate::commands::{CommandRegistry, EditorAction};
+use crate::mesh::PrimitiveShape;
+use crate::core::{CameraMode, DepthMode, FrameStats, ProjectionMode};
+use crate::core::passes::thumbnail::{ThumbnailAtlas, ThumbnailKey};
+use crate::core::camera::{MAX_COLLISION_RADIUS, MAX_FAR_PLANE, MAX_MOVE_SPEED, MAX_ORBIT_DISTANCE, MIN_COLLISION_RADIUS, MIN_MOVE_SPEED, MIN_NEAR_PLANE, MIN_ORBIT_DISTANCE};
 use glam::Quat;
 
-const CONFIG_PATH: &str = "config/default.json";
 const SCENE_PATH: &str = "config/scene.json";
+/// ImGui window layout (positions, sizes, collapsed state), saved by ImGui itself
+pub const IMGUI_INI_PATH: &str = "config/imgui.ini";
+const SCENE_OBJECT_PAYLOAD: &str = "SCENE_OBJECT"; // Drag-and-drop payload carrying an ObjectId
+const TAG_CHIPS_PER_ROW: usize = 3; // Tag chips shown side by side in the Transform panel
 
 /// Manages all UI rendering and interactions
 pub struct UiManager;
@@ -27,17 +41,38 @@ impl UiManager {
         let mut load_clicked = false;
         let mut reset_clicked = false;
 
-        // Store original config to detect changes
+        // Store original configs to detect changes
         let orig_config = game.skybox_config.clone();
+        let orig_star = game.star_config.clone();
 
         GuiPanelBuilder::new(ui, "Skybox Settings")
-            .size(350.0, 500.0)
+            .size(350.0, 560.0)
             .position(270.0, 10.0)
             .build(|content| {
                 content.text("Adjust skybox appearance in real-time");
 
                 let config = &mut game.skybox_config;
 
+                let mut mode_index = SkyboxMode::ALL
+                    .iter()
+                    .position(|mode| *mode == config.mode)
+                    .unwrap_or(0);
+                if ui.combo("Mode", &mut mode_index, &SkyboxMode::ALL, |mode| mode.name().into()) {
+                    config.mode = SkyboxMode::ALL[mode_index];
+                }
+
+                if config.mode == SkyboxMode::Cubemap {
+                    let mut path_buf = config.cubemap_path.clone();
+                    if ui.input_text("Cubemap##skybox_cubemap", &mut path_buf).enter_returns_true(true).build() {
+                        config.cubemap_path = path_buf.trim().to_string();
+                    }
+                    ui.text_disabled("Folder with px/nx/py/ny/pz/nz images");
+                    ui.text_disabled("or an equirectangular .hdr/.png (Enter)");
+                    ui.text_disabled("Falls back to stars if it fails to load");
+                }
+
+                // The cubemap replaces the generated sky, so its controls do nothing
+                let procedural_token = ui.begin_disabled(config.mode == SkyboxMode::Cubemap);
                 content
                     .header("Stars")
                     .slider_f32("Star Density", &mut config.star_density, 0.1, 10.0)
@@ -48,6 +83,11 @@ impl UiManager {
                     .color_picker("Secondary Color", &mut config.nebula_secondary_color)
                     .header("Background")
                     .slider_f32("Brightness", &mut config.background_brightness, 0.0, 0.5);
+                procedural_token.end();
+
+                // Tune the star's brightness and twinkle against the current background
+                content.header("Star Shader");
+                Self::build_star_shader_controls(ui, content, &mut game.star_config);
 
                 let (s, l, r) = content.config_buttons();
                 save_clicked = s;
@@ -62,6 +102,9 @@ impl UiManager {
             || orig_config.nebula_primary_color != game.skybox_config.nebula_primary_color
             || orig_config.nebula_secondary_color != game.skybox_config.nebula_secondary_color
             || orig_config.background_brightness != game.skybox_config.background_brightness
+            || orig_config.mode != game.skybox_config.mode
+            || orig_config.cubemap_path != game.skybox_config.cubemap_path
+            || Self::star_shader_changed(&orig_star, &game.star_config)
         {
             game.mark_config_dirty();
         }
@@ -74,27 +117,38 @@ impl UiManager {
         }
         if reset_clicked {
             game.skybox_config = SkyboxConfig::default();
+            let star_defaults = StarConfig::default();
+            let star = &mut game.star_config;
+            star.exposure = star_defaults.exposure;
+            star.gamma = star_defaults.gamma;
+            star.speed_hi = star_defaults.speed_hi;
+            star.speed_low = star_defaults.speed_low;
             game.mark_config_dirty();
         }
     }
 
-    /// Build the nebula settings UI
+    /// Build the nebula settings UI (edits the selected nebula object)
     pub fn build_nebula_settings(ui: &Ui, game: &mut Game) {
         let mut save_clicked = false;
         let mut load_clicked = false;
         let mut reset_clicked = false;
 
-        // Store original config to detect changes
-        let orig_config = game.nebula_config.clone();
+        // Nebulae saved before per-object settings existed start from the engine config
+        let fallback = game.nebula_config;
+        let Some(obj) = game.scene.selected_object_mut() else {
+            return;
+        };
+        let settings = obj.nebula.get_or_insert(fallback);
+        let orig_settings = *settings;
 
         GuiPanelBuilder::new(ui, "Nebula Settings")
-            .size(380.0, 450.0)
+            .size(380.0, 540.0)
             .position(270.0, 10.0)
             .build(|content| {
                 content.text("Volumetric nebula raymarch shader");
                 content.text("Use transform gizmo to move/rotate nebula");
 
-                let config = &mut game.nebula_config;
+                let config = &mut *settings;
 
                 content
                     .header("Basic Controls")
@@ -116,39 +170,54 @@ impl UiManager {
                     .slider_f32("Light Intensity", &mut config.light_intensity, 0.0, 0.1)
 
                     .header("Distance")
-                    .slider_f32("Max Distance", &mut config.max_distance, 1.0, 50.0);
+                    .slider_f32("Max Distance", &mut config.max_distance, 1.0, 50.0)
+
+                    .header("Quality");
+
+                // Presets set the step count; other counts show as "Custom"
+                let preview = NebulaQuality::from_steps(config.steps).map_or("Custom", |quality| quality.name());
+                if let Some(_combo) = ui.begin_combo("Preset", preview) {
+                    for quality in NebulaQuality::ALL {
+                        if ui.selectable_config(quality.name())
+                            .selected(config.steps == quality.steps())
+                            .build()
+                        {
+                            config.steps = quality.steps();
+                        }
+                    }
+                }
+                content.slider_u32("Raymarch Steps", &mut config.steps, MIN_NEBULA_STEPS, MAX_NEBULA_STEPS);
 
+                // Save stores these settings as the default for new nebulae
+                content.text_disabled("Save/Load use the config's default nebula");
                 let (s, l, r) = content.config_buttons();
                 save_clicked = s;
                 load_clicked = l;
                 reset_clicked = r;
             });
 
-        // Check if config changed
-        if orig_config.scale != game.nebula_config.scale
-            || orig_config.zoom != game.nebula_config.zoom
-            || orig_config.density != game.nebula_config.density
-            || orig_config.brightness != game.nebula_config.brightness
-            || orig_config.color_center != game.nebula_config.color_center
-            || orig_config.color_edge != game.nebula_config.color_edge
-            || orig_config.color_density_low != game.nebula_config.color_density_low
-            || orig_config.color_density_high != game.nebula_config.color_density_high
-            || orig_config.light_color != game.nebula_config.light_color
-            || orig_config.light_intensity != game.nebula_config.light_intensity
-            || orig_config.max_distance != game.nebula_config.max_distance
-        {
-            game.mark_config_dirty();
+        let settings = *settings;
+        if settings != orig_settings {
+            game.mark_scene_dirty();
         }
 
         if save_clicked {
+            game.nebula_config = settings;
             Self::save_nebula_config(game);
         }
         if load_clicked {
             Self::load_nebula_config(game);
         }
         if reset_clicked {
-            game.nebula_config = NebulaConfig::default();
-            game.mark_config_dirty();
+            Self::set_selected_nebula(game, NebulaConfig::default());
+        }
+    }
+
+    /// Replace the selected nebula's settings
+    fn set_selected_nebula(game: &mut Game, config: NebulaConfig) {
+        if let Some(obj) = game.scene.selected_object_mut() {
+            obj.nebula = Some(config);
+            game.mark_scene_dirty();
         }
     }
 
@@ -172,14 +241,9 @@ impl UiManager {
 
                 content
                     .header("Appearance")
-                    .color_picker("Star Color Tint", &mut config.color)
-                    .slider_f32("Gamma", &mut config.gamma, 1.0, 3.0)
-                    .slider_f32("Exposure", &mut config.exposure, 0.1, 30.0)
-
-                    .header("Animation")
-                    .slider_f32("High Speed", &mut config.speed_hi, 0.0, 10.0)
-                    .slider_f32("Low Speed", &mut config.speed_low, 0.0, 5.0)
-
+                    .color_picker("Star Color Tint", &mut config.color);
+                Self::build_star_shader_controls(ui, content, config);
+                content
                     .header("Detail")
                     .slider_f32("Zoom", &mut config.zoom, 0.1, 2.0);
 
@@ -191,10 +255,7 @@ impl UiManager {
 
         // Check if config changed
         if orig_config.color != game.star_config.color
-            || orig_config.gamma != game.star_config.gamma
-            || orig_config.exposure != game.star_config.exposure
-            || orig_config.speed_hi != game.star_config.speed_hi
-            || orig_config.speed_low != game.star_config.speed_low
+            || Self::star_shader_changed(&orig_config, &game.star_config)
             || orig_config.zoom != game.star_config.zoom
         {
             game.mark_config_dirty();
@@ -212,45 +273,263 @@ impl UiManager {
         }
     }
 
-    /// Build directional light settings panel
+    /// Star exposure, gamma and animation speeds (shared by the Skybox and Star panels)
+    fn build_star_shader_controls(ui: &Ui, content: &mut GuiContentBuilder, config: &mut StarConfig) {
+        // Logarithmic: useful exposures run from dim background stars to a blinding sun
+        ui.slider_config("Exposure", MIN_STAR_EXPOSURE, MAX_STAR_EXPOSURE)
+            .flags(imgui::SliderFlags::LOGARITHMIC)
+            .display_format("%.1f")
+            .build(&mut config.exposure);
+        config.exposure = config.exposure.clamp(MIN_STAR_EXPOSURE, MAX_STAR_EXPOSURE);
+
+        content
+            .slider_f32("Gamma", &mut config.gamma, 1.0, 3.0)
+            .slider_f32("Twinkle Speed (High)", &mut config.speed_hi, 0.0, 10.0)
+            .slider_f32("Twinkle Speed (Low)", &mut config.speed_low, 0.0, 5.0);
+        config.gamma = config.gamma.max(0.1);
+    }
+
+    /// Whether any of the values edited by `build_star_shader_controls` changed
+    fn star_shader_changed(orig: &StarConfig, config: &StarConfig) -> bool {
+        orig.exposure != config.exposure
+            || orig.gamma != config.gamma
+            || orig.speed_hi != config.speed_hi
+            || orig.speed_low != config.speed_low
+    }
+
+    /// Build directional light settings panel (edits the selected light object)
     pub fn build_directional_light_settings(ui: &Ui, game: &mut Game) {
+        let Some(id) = game.scene.selected_object_id() else {
+            return;
+        };
+        // Only the first light casts shadows; lights past the limit aren't shaded at all
+        let light_index = game.directional_light_index(id);
+        let direction = (game.scene.world_transform(id).rotation * glam::Vec3::NEG_Y).normalize();
+        let looking_through = game.is_looking_through(id);
+        let mut toggle_look_through = false;
+        let mut align_to_view = false;
+
+        // Store original shadow config to detect changes
+        let orig_shadow = game.shadow_config.clone();
+
+        // Lights saved before per-light settings existed start from the scene's light settings
+        let fallback = crate::core::lighting::DirectionalLightSettings {
+            color: game.directional_light.color,
+            intensity: game.directional_light.intensity,
+        };
+        let Some(obj) = game.scene.get_object_mut(id) else {
+            return;
+        };
+        let settings = obj.directional_light.get_or_insert(fallback);
+        let orig_settings = *settings;
+
+        let shadow_color = &mut game.directional_light.shadow_color;
+        let shadow = &mut game.shadow_config;
+
         GuiPanelBuilder::new(ui, "Directional Light Settings")
-            .size(350.0, 300.0)
+            .size(350.0, 540.0)
             .position(270.0, 10.0)
             .build(|content| {
-                content.text("Main directional light source");
+                match light_index {
+                    Some(0) => content.text("Key light (casts shadows)"),
+                    Some(_) => content.text("Fill/rim light (no shadows)"),
+                    None => content.text(&format!(
+                        "Inactive: only {} directional lights are shaded",
+                        crate::core::lighting::MAX_DIRECTIONAL_LIGHTS
+                    )),
+                };
                 content.separator();
 
-                let light = &mut game.directional_light;
-
                 content.header("Light Color & Intensity");
 
                 // Color picker for light color
-                let mut color = [light.color.x, light.color.y, light.color.z];
+                let mut color = [settings.color.x, settings.color.y, settings.color.z];
                 content.text("Light Color");
                 if ui.color_edit3("##light_color", &mut color) {
-                    light.color = glam::Vec3::new(color[0], color[1], color[2]);
+                    settings.color = glam::Vec3::new(color[0], color[1], color[2]);
                 }
 
                 // Intensity slider
                 content.text("Brightness");
-                ui.slider("##light_intensity", 0.0, 3.0, &mut light.intensity);
+                ui.slider("##light_intensity", 0.0, 3.0, &mut settings.intensity);
 
                 content.separator();
                 content.header("Shadow/Ambient Color");
 
-                // Shadow color picker
-                let mut shadow_color = [light.shadow_color.x, light.shadow_color.y, light.shadow_color.z];
+                // Shadow color picker (shared by all directional lights)
+                let mut shadow_rgb = [shadow_color.x, shadow_color.y, shadow_color.z];
                 content.text("Shadow Color");
-                if ui.color_edit3("##shadow_color", &mut shadow_color) {
-                    light.shadow_color = glam::Vec3::new(shadow_color[0], shadow_color[1], shadow_color[2]);
+                if ui.color_edit3("##shadow_color", &mut shadow_rgb) {
+                    *shadow_color = glam::Vec3::new(shadow_rgb[0], shadow_rgb[1], shadow_rgb[2]);
+                }
+
+                content.separator();
+                content.header("Direction");
+                content.text(&format!("({:.3}, {:.3}, {:.3})", direction.x, direction.y, direction.z));
+                let look_label = if looking_through { "Stop Looking Through" } else { "Look Through Light" };
+                if ui.button(look_label) {
+                    toggle_look_through = true;
+                }
+                ui.same_line();
+                if ui.button("Align Light to View") {
+                    align_to_view = true;
+                }
+                if looking_through {
+                    content.text("Turn the camera to aim the light");
+                } else {
+                    content.text("Or rotate the light object");
+                }
+
+                if light_index != Some(0) {
+                    return;
+                }
+
+                content.separator();
+                content.header("Shadows");
+
+                content.checkbox("Enable Shadows", &mut shadow.enabled);
+
+                // Resolution picker (only the supported sizes)
+                content.text("Shadow Map Resolution");
+                for (i, resolution) in ShadowConfig::RESOLUTIONS.iter().enumerate() {
+                    if i > 0 {
+                        ui.same_line();
+                    }
+                    if ui.radio_button_bool(format!("{}", resolution), shadow.resolution == *resolution) {
+                        shadow.resolution = *resolution;
+                    }
+                }
+
+                content.text("Range (half-size of shadow area)");
+                ui.slider("##shadow_range", 5.0, 500.0, &mut shadow.range);
+
+                content.text("Depth Bias (reduces acne)");
+                ui.slider("##shadow_bias", 0.0, 0.05, &mut shadow.depth_bias);
+            });
+
+        // Detect changes
+        let settings_changed = *settings != orig_settings;
+        if orig_shadow.enabled != game.shadow_config.enabled
+            || orig_shadow.resolution != game.shadow_config.resolution
+            || orig_shadow.range != game.shadow_config.range
+            || orig_shadow.depth_bias != game.shadow_config.depth_bias
+        {
+            game.mark_config_dirty();
+        }
+        if settings_changed {
+            game.mark_scene_dirty();
+        }
+
+        if toggle_look_through {
+            if looking_through {
+                game.stop_looking_through_light();
+            } else {
+                game.look_through_light(id);
+            }
+        }
+        if align_to_view {
+            game.align_light_to_view(id);
+        }
+    }
+
+    /// Build point light settings UI (edits the selected light object)
+    pub fn build_point_light_settings(ui: &Ui, game: &mut Game) {
+        let Some(obj) = game.scene.selected_object_mut() else {
+            return;
+        };
+        let settings = obj.point_light.get_or_insert_with(Default::default);
+        let orig_settings = *settings;
+
+        GuiPanelBuilder::new(ui, "Point Light Settings")
+            .size(350.0, 260.0)
+            .position(270.0, 10.0)
+            .build(|content| {
+                content.text("Local light source");
+                content.separator();
+
+                content
+                    .header("Light Color & Intensity")
+                    .color_picker("Light Color", &mut settings.color)
+                    .slider_f32("Brightness", &mut settings.intensity, 0.0, 50.0)
+                    .slider_f32("Radius", &mut settings.radius, 0.5, 100.0);
+
+                content.separator();
+                content.header("Position (via Transform)");
+                content.text("Move the light object to");
+                content.text("change light position");
+            });
+
+        if *settings != orig_settings {
+            game.mark_scene_dirty();
+        }
+    }
+
+    /// Build particle emitter settings UI (edits the selected emitter and the global particle budget)
+    pub fn build_particle_emitter_settings(ui: &Ui, game: &mut Game) {
+        let orig_particles = game.particle_config.clone();
+
+        let Some(obj) = game.scene.selected_object_mut() else {
+            return;
+        };
+        let attached = obj.parent.is_some();
+        let settings = obj.particle_emitter.get_or_insert_with(Default::default);
+        let orig_settings = *settings;
+        let particles = &mut game.particle_config;
+
+        GuiPanelBuilder::new(ui, "Particle Emitter Settings")
+            .size(350.0, 560.0)
+            .position(270.0, 10.0)
+            .build(|content| {
+                content.text("Engine exhaust (GPU particles)");
+                if !attached {
+                    content.text_disabled("Parent it to a ship to follow it");
                 }
+                content.separator();
+
+                content
+                    .header("Emission")
+                    .slider_f32("Rate (per second)", &mut settings.rate, 0.0, 2000.0)
+                    .slider_f32("Lifetime (s)", &mut settings.lifetime, 0.05, 10.0)
+                    .slider_f32("Speed", &mut settings.speed, 0.0, 50.0)
+                    .slider_f32("Spread (deg)", &mut settings.spread, 0.0, 180.0)
+                    .slider_f32("Inherit Velocity", &mut settings.inherit_velocity, 0.0, 1.0)
+                    .checkbox("Only While Moving", &mut settings.only_while_moving);
+
+                content
+                    .header("Color Over Life")
+                    .color_picker("Start Color", &mut settings.start_color)
+                    .slider_f32("Start Alpha", &mut settings.start_alpha, 0.0, 1.0)
+                    .color_picker("End Color", &mut settings.end_color)
+                    .slider_f32("End Alpha", &mut settings.end_alpha, 0.0, 1.0);
+
+                content
+                    .header("Size Over Life")
+                    .slider_f32("Start Size", &mut settings.start_size, 0.01, 10.0)
+                    .slider_f32("End Size", &mut settings.end_size, 0.01, 10.0);
 
                 content.separator();
-                content.header("Direction (via Transform)");
-                content.text("Rotate the light object to");
-                content.text("change light direction");
+                content.header("Particle Budget (all emitters)");
+                for (i, budget) in ParticleConfig::BUDGETS.iter().enumerate() {
+                    if i > 0 {
+                        ui.same_line();
+                    }
+                    if ui.radio_button_bool(format!("{}", budget), particles.max_particles == *budget) {
+                        particles.max_particles = *budget;
+                    }
+                }
+                content.slider_f32("Drag", &mut particles.drag, 0.0, 1.0);
+                content.text("Particles leave along the");
+                content.text("emitter's local -Z axis");
             });
+
+        if *settings != orig_settings {
+            game.mark_scene_dirty();
+        }
+        if orig_particles.max_particles != game.particle_config.max_particles
+            || orig_particles.drag != game.particle_config.drag
+        {
+            game.mark_config_dirty();
+        }
     }
 
     pub fn build_ssao_settings(ui: &Ui, game: &mut Game) {
@@ -258,7 +537,7 @@ impl UiManager {
         let orig_config = game.ssao_config.clone();
 
         GuiPanelBuilder::new(ui, "SSAO Settings")
-            .size(350.0, 300.0)
+            .size(350.0, 380.0)
             .position(270.0, 10.0)
             .build(|content| {
                 content.text("Screen-Space Ambient Occlusion");
@@ -291,6 +570,12 @@ impl UiManager {
                     ssao.kernel_size = kernel_f32 as u32;
                 }
 
+                // Intensity blends the AO term toward 1 (no occlusion) in mesh.frag
+                content.text("Intensity");
+                ui.slider("##ssao_intensity", 0.0, 1.0, &mut ssao.ao_intensity);
+
+                content.checkbox("Show AO Only (debug)", &mut ssao.show_ao_only);
+
                 content.separator();
                 content.text("Quality vs Performance:");
                 content.text("Lower samples = faster");
@@ -303,6 +588,7 @@ impl UiManager {
             || orig_config.bias != game.ssao_config.bias
             || orig_config.power != game.ssao_config.power
             || orig_config.kernel_size != game.ssao_config.kernel_size
+            || orig_config.ao_intensity != game.ssao_config.ao_intensity
         {
             game.mark_config_dirty();
         }
@@ -315,13 +601,17 @@ impl UiManager {
         let is_paused = game.game_manager.is_paused();
 
         // Calculate center position (window width / 2 - toolbar width / 2)
-        let toolbar_width = 280.0;
+        let toolbar_width = 300.0;
         let screen_width = ui.io().display_size[0];
         let center_x = (screen_width - toolbar_width) * 0.5;
 
+        // An extra row shows while solo is active
+        let solo_active = game.scene.is_solo_active();
+        let toolbar_height = if solo_active { 95.0 } else { 70.0 };
+
         ui.window("Game Mode")
             .position([center_x, 5.0], imgui::Condition::Always)
-            .size([toolbar_width, 70.0], imgui::Condition::Always)
+            .size([toolbar_width, toolbar_height], imgui::Condition::Always)
             .collapsible(false)
             .title_bar(false)
             .build(|| {
@@ -373,6 +663,37 @@ impl UiManager {
                         game.game_manager.toggle_pause();
                     }
                 }
+
+                // Speed presets (the current one is marked); pause stays separate
+                ui.text("Speed");
+                for time_scale in TIME_SCALES {
+                    ui.same_line();
+                    let label = if game.game_manager.time_scale == time_scale {
+                        format!("[{}x]", time_scale)
+                    } else {
+                        format!("{}x", time_scale)
+                    };
+                    if ui.small_button(&label) {
+                        game.game_manager.set_time_scale(time_scale);
+                    }
+                }
+            }
+
+            // Solo indicator: everything else is hidden until this is exited
+            if solo_active {
+                ui.text_colored([1.0, 0.6, 0.2, 1.0], format!("SOLO ({})", game.scene.solo_count()));
+                ui.same_line();
+                if ui.small_button("Exit Solo") {
+                    game.scene.clear_solo();
+                }
+                ui.same_line();
+                let mut shows_environment = game.scene.solo_shows_environment();
+                if ui.checkbox("Lights & Sky", &mut shows_environment) {
+                    game.scene.set_solo_shows_environment(shows_environment);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Keep lights, the skybox and nebulae while soloing");
+                }
             }
         });
     }
@@ -407,7 +728,7 @@ impl UiManager {
             ui.text(format!("Scenario: {}", game.game_manager.scenario_name));
             ui.text(format!("Turn: {}", game.game_manager.current_turn));
 
-            let elapsed = game.game_manager.get_elapsed_time(game.time());
+            let elapsed = game.game_manager.get_elapsed_time();
             let minutes = (elapsed / 60.0) as u32;
             let seconds = (elapsed % 60.0) as u32;
             ui.text(format!("Elapsed Time: {}:{:02}", minutes, seconds));
@@ -440,8 +761,8 @@ impl UiManager {
     fn build_play_mode_ui(ui: &Ui, game: &mut Game) {
         // Turn info panel
         ui.window("Turn Information")
-            .position([10.0, 80.0], imgui::Condition::FirstUseEver)
-            .size([300.0, 200.0], imgui::Condition::FirstUseEver)
+            .position([10.0, 80.0], gui_builder::layout_condition())
+            .size([300.0, 300.0], gui_builder::layout_condition())
             .build(|| {
                 ui.text_colored([0.3, 0.8, 1.0, 1.0], "Current Turn");
                 ui.separator();
@@ -462,13 +783,107 @@ impl UiManager {
                 ui.text("Turn Duration: 10 seconds");
                 ui.text("Phase: Planning");
 
+                // Action points of the unit being commanded
+                if let Some(unit) = game.active_unit() {
+                    ui.spacing();
+                    ui.separator();
+                    ui.spacing();
+
+                    ui.text_colored([0.3, 0.8, 1.0, 1.0], "Selected Unit");
+                    ui.text(format!("Faction: {}", unit.faction));
+                    ui.text(format!("Action Points: {} / {}", unit.action_points, unit.max_action_points));
+
+                    if let Some(cost) = game.planned_move_cost() {
+                        let color = if unit.can_afford(cost) {
+                            [0.0, 1.0, 0.4, 1.0]
+                        } else {
+                            [1.0, 0.3, 0.1, 1.0]
+                        };
+                        ui.text_colored(color, format!("Planned Move: {} AP", cost));
+                    }
+                    if game.planned_path_collision().is_some_and(|collision| collision.is_blocked()) {
+                        ui.text_colored([1.0, 0.0, 0.0, 1.0], "Path blocked");
+                    }
+                }
+
+                ui.checkbox("Stop Blocked Moves Short", &mut game.clamp_blocked_moves);
+
                 ui.spacing();
 
                 if ui.button_with_size("End Turn", [280.0, 30.0]) {
-                    game.add_notification("Turn ended (not implemented)".to_string(), 2.0);
-                    // TODO: Execute turn and advance to next turn
+                    game.end_turn();
+                }
+            });
+
+        Self::build_replay_panel(ui, game);
+    }
+
+    /// Turn replay: scrub, step and play back the recorded moves
+    fn build_replay_panel(ui: &Ui, game: &mut Game) {
+        let mut start_clicked = false;
+        let mut stop_clicked = false;
+
+        ui.window("Turn Replay")
+            .position([10.0, 390.0], gui_builder::layout_condition())
+            .size([300.0, 170.0], gui_builder::layout_condition())
+            .build(|| {
+                let Some(player) = game.replay.as_mut() else {
+                    ui.text(format!("{} recorded events", game.movement_events.event_count()));
+                    if ui.button_with_size("Review Turns", [280.0, 25.0]) {
+                        start_clicked = true;
+                    }
+                    return;
+                };
+
+                // Counter shows the turn being played (or the last one, at the end)
+                let turn_count = player.replay.turn_count();
+                let index = (player.time.floor() as usize).min(turn_count.saturating_sub(1));
+                let turn = player.replay.turn_number(index).unwrap_or(0);
+                ui.text(format!("Turn {} ({} of {})", turn, index + 1, turn_count));
+
+                let mut time = player.time as f32;
+                ui.set_next_item_width(280.0);
+                if ui.slider_config("##replay_time", 0.0, player.end_time() as f32)
+                    .display_format("%.2f")
+                    .build(&mut time)
+                {
+                    player.seek(time as f64);
+                }
+
+                if ui.button("|<") {
+                    player.seek(0.0);
+                }
+                ui.same_line();
+                if ui.button("<") {
+                    player.step(-1);
+                }
+                ui.same_line();
+                if ui.button(if player.playing { "Pause" } else { "Play" }) {
+                    player.toggle_playing();
+                }
+                ui.same_line();
+                if ui.button(">") {
+                    player.step(1);
+                }
+                ui.same_line();
+                if ui.button(">|") {
+                    player.seek(player.end_time());
+                }
+
+                ui.set_next_item_width(150.0);
+                ui.slider("Seconds / Turn", 0.25, 10.0, &mut player.seconds_per_turn);
+
+                if ui.button_with_size("Stop Replay", [280.0, 25.0]) {
+                    stop_clicked = true;
                 }
             });
+
+        if start_clicked {
+            game.start_replay();
+        }
+        if stop_clicked {
+            game.stop_replay();
+        }
     }
 
     /// Build Game Manager settings panel
@@ -507,14 +922,82 @@ impl UiManager {
             });
     }
 
+    /// Spaces that make room for a hierarchy icon (a square one text line high)
+    fn hierarchy_icon_padding(ui: &Ui) -> String {
+        let icon_width = ui.text_line_height() + 4.0;
+        let space_width = ui.calc_text_size(" ")[0].max(1.0);
+        " ".repeat((icon_width / space_width).ceil() as usize)
+    }
+
+    /// Abbreviation and color of the icon for types without a mesh thumbnail
+    fn hierarchy_type_icon(object_type: &ObjectType) -> (&'static str, [f32; 4]) {
+        match object_type {
+            ObjectType::Cube => ("C", [0.55, 0.55, 0.6, 1.0]),
+            ObjectType::Primitive(_) => ("P", [0.55, 0.55, 0.6, 1.0]),
+            ObjectType::Mesh(_) => ("M", [0.3, 0.55, 0.6, 1.0]),
+            ObjectType::Unlit(_) => ("U", [0.3, 0.55, 0.6, 1.0]),
+            ObjectType::Sphere => ("O", [0.85, 0.6, 0.2, 1.0]),
+            ObjectType::Nebula => ("N", [0.55, 0.3, 0.7, 1.0]),
+            ObjectType::Skybox => ("S", [0.25, 0.4, 0.75, 1.0]),
+            ObjectType::DirectionalLight => ("D", [0.85, 0.75, 0.2, 1.0]),
+            ObjectType::PointLight => ("L", [0.85, 0.55, 0.2, 1.0]),
+            ObjectType::ParticleEmitter => ("E", [0.8, 0.35, 0.25, 1.0]),
+            ObjectType::SSAO => ("A", [0.4, 0.4, 0.45, 1.0]),
+            ObjectType::GameManager => ("G", [0.3, 0.6, 0.35, 1.0]),
+            ObjectType::Custom(_) => ("?", [0.4, 0.4, 0.45, 1.0]),
+        }
+    }
+
+    /// Draw an object's thumbnail (or type icon when it has none yet) over the padding
+    /// after `marker` in the hierarchy row just submitted
+    fn draw_hierarchy_icon(ui: &Ui, thumbnails: &ThumbnailAtlas, object_type: &ObjectType, marker: &str) {
+        let size = ui.text_line_height();
+        let row_min = ui.item_rect_min();
+        let min = [row_min[0] + ui.calc_text_size(marker)[0], row_min[1]];
+        let max = [min[0] + size, min[1] + size];
+        let draw_list = ui.get_window_draw_list();
+
+        let thumbnail = ThumbnailKey::for_object(object_type).and_then(|key| thumbnails.image(&key));
+        match thumbnail {
+            Some((texture, uv_min, uv_max)) => {
+                draw_list.add_image(texture, min, max).uv_min(uv_min).uv_max(uv_max).build();
+            }
+            None => {
+                let (letter, color) = Self::hierarchy_type_icon(object_type);
+                draw_list.add_rect(min, max, color).filled(true).rounding(3.0).build();
+                let text_size = ui.calc_text_size(letter);
+                let text_pos = [
+                    min[0] + (size - text_size[0]) * 0.5,
+                    min[1] + (size - text_size[1]) * 0.5,
+                ];
+                draw_list.add_text(text_pos, [1.0, 1.0, 1.0, 1.0], letter);
+            }
+        }
+    }
+
     /// Build the scene hierarchy UI
-    pub fn build_scene_hierarchy(ui: &Ui, game: &mut Game) {
+    pub fn build_scene_hierarchy(ui: &Ui, game: &mut Game, thumbnails: &ThumbnailAtlas) {
         let mut save_scene_clicked = false;
         let mut load_scene_clicked = false;
-        let mut clicked_obj_id: Option<usize> = None;
-        let mut double_clicked_obj_id: Option<usize> = None;
-        let mut duplicate_object_id: Option<usize> = None;
+        let mut export_gltf_clicked = false;
+        let mut clicked_obj_id: Option<ObjectId> = None;
+        let mut toggled_obj_id: Option<ObjectId> = None;
+        let mut double_clicked_obj_id: Option<ObjectId> = None;
+        let mut duplicate_clicked = false;
+        let mut duplicate_array_clicked = false;
+        let mut delete_clicked = false;
+        let mut copy_clicked = false;
+        let mut paste_clicked = false;
+        let mut copy_material_clicked = false;
+        let mut paste_material_clicked = false;
+        let mut add_point_light_clicked = false;
+        let mut add_particle_emitter_clicked = false;
+        let mut add_directional_light_clicked = false;
+        let mut add_nebula_clicked = false;
+        let mut drop_to_ground_clicked = false;
+        let mut add_primitive: Option<PrimitiveShape> = None;
         let mut clicked_material: Option<String> = None;
+        let mut reparent: Option<(ObjectId, Option<ObjectId>)> = None;
 
         GuiPanelBuilder::new(ui, "Scene Hierarchy")
             .size(250.0, 550.0)
@@ -522,48 +1005,81 @@ impl UiManager {
             .build(|content| {
                 content.text("Select objects to edit");
                 content.text_disabled("Click selected to focus");
+                content.text_disabled("Ctrl+click to multi-select");
                 content.separator();
 
+                // Search box (filters regular objects by name; singletons always stay listed)
+                ui.set_next_item_width(-60.0);
+                ui.input_text("##hierarchy_filter", &mut game.hierarchy_filter)
+                    .hint("Search objects")
+                    .build();
+                ui.same_line();
+                if ui.small_button("Clear##hierarchy_filter") {
+                    game.hierarchy_filter.clear();
+                }
+                let filtering = !game.hierarchy_filter.trim().is_empty();
+
+                // Select every object carrying a tag (objects on hidden or locked layers are skipped)
+                let tags = game.scene.all_tags();
+                if !tags.is_empty() {
+                    ui.set_next_item_width(-1.0);
+                    if let Some(_combo) = ui.begin_combo("##select_by_tag", "Select by tag") {
+                        for tag in &tags {
+                            if ui.selectable(tag) {
+                                let tagged = game.scene.find_by_tag(tag);
+                                if let Some((&first, rest)) = tagged.split_first() {
+                                    game.scene.select_object(first);
+                                    for &id in rest {
+                                        game.scene.toggle_selection(id);
+                                    }
+                                    game.scene.deselect_unselectable();
+                                }
+                            }
+                        }
+                    }
+                }
+
                 // Collect objects and categorize them
-                let all_objects: Vec<(usize, String, crate::scene::ObjectType)> = game
+                let all_objects: Vec<(ObjectId, String, crate::scene::ObjectType)> = game
                     .scene
                     .objects_sorted()
                     .iter()
                     .map(|obj| (obj.id, obj.name.clone(), obj.object_type.clone()))
                     .collect();
 
-                let selected_id = game.scene.selected_object_id();
+                let selected_ids = game.scene.selected_ids().to_vec();
+                let ctrl_held = ui.io().key_ctrl;
 
                 // Split into singletons and regular objects
                 let singletons: Vec<_> = all_objects.iter()
                     .filter(|(_, _, obj_type)| matches!(obj_type,
                         crate::scene::ObjectType::Skybox |
-                        crate::scene::ObjectType::Nebula |
-                        crate::scene::ObjectType::DirectionalLight |
                         crate::scene::ObjectType::SSAO))
                     .collect();
 
                 let objects: Vec<_> = all_objects.iter()
                     .filter(|(_, _, obj_type)| !matches!(obj_type,
                         crate::scene::ObjectType::Skybox |
-                        crate::scene::ObjectType::Nebula |
-                        crate::scene::ObjectType::DirectionalLight |
                         crate::scene::ObjectType::SSAO))
                     .collect();
 
+                // Room left in each label for its thumbnail or type icon
+                let icon_padding = Self::hierarchy_icon_padding(ui);
+
                 // Render Singletons section
                 if !singletons.is_empty() {
                     content.header("Singletons");
-                    for (id, name, _obj_type) in singletons {
-                        let is_selected = selected_id == Some(*id);
-                        let label = if is_selected {
-                            format!("> {}", name)
-                        } else {
-                            format!("  {}", name)
-                        };
-
-                        if ui.selectable(&label) {
-                            if is_selected {
+                    for (id, name, obj_type) in singletons {
+                        let is_selected = selected_ids.contains(id);
+                        let marker = if is_selected { "> " } else { "  " };
+                        let label = format!("{}{}{}", marker, icon_padding, name);
+
+                        let clicked = ui.selectable(&label);
+                        Self::draw_hierarchy_icon(ui, thumbnails, obj_type, marker);
+                        if clicked {
+                            if ctrl_held {
+                                toggled_obj_id = Some(*id);
+                            } else if is_selected {
                                 double_clicked_obj_id = Some(*id);
                             } else {
                                 clicked_obj_id = Some(*id);
@@ -577,28 +1093,75 @@ impl UiManager {
                     content.separator();
                 }
 
-                // Render Objects section
+                // Render Objects section as a tree (children indented under their parent)
                 if !objects.is_empty() {
                     content.header("Objects");
-                    for (id, name, _obj_type) in objects {
-                        let is_selected = selected_id == Some(*id);
-                        let label = if is_selected {
-                            format!("> {}", name)
-                        } else {
-                            format!("  {}", name)
+                    let object_ids: Vec<ObjectId> = objects.iter()
+                        .filter(|(_, name, _)| game.matches_hierarchy_filter(name))
+                        .map(|(id, _, _)| *id)
+                        .collect();
+
+                    if filtering {
+                        ui.text_disabled(format!("{} of {} objects match", object_ids.len(), objects.len()));
+                        // Filtered-out objects stay selected, so say why the highlight is missing
+                        let hidden_selected = selected_ids.iter()
+                            .any(|id| objects.iter().any(|(obj_id, _, _)| obj_id == id) && !object_ids.contains(id));
+                        if hidden_selected {
+                            ui.text_disabled("(selection hidden by filter)");
+                        }
+                    }
+
+                    for (id, depth) in game.scene.hierarchy_order() {
+                        if !object_ids.contains(&id) {
+                            continue;
+                        }
+                        let Some((name, obj_type)) = game.scene.get_object(id).map(|obj| (obj.name.clone(), obj.object_type.clone())) else {
+                            continue;
                         };
 
-                        if ui.selectable(&label) {
-                            if is_selected {
-                                double_clicked_obj_id = Some(*id);
+                        let is_selected = selected_ids.contains(&id);
+                        // Parents may be filtered out, so matches are listed flat while searching
+                        let indent = if filtering { String::new() } else { "    ".repeat(depth) };
+                        let marker = format!("{}{}", indent, if is_selected { "> " } else { "  " });
+                        let solo_tag = if game.scene.is_soloed(id) { " [S]" } else { "" };
+                        let label = format!("{}{}{}{}##object_{}", marker, icon_padding, name, solo_tag, id);
+
+                        let clicked = ui.selectable(&label);
+                        Self::draw_hierarchy_icon(ui, thumbnails, &obj_type, &marker);
+                        if clicked {
+                            if ctrl_held {
+                                toggled_obj_id = Some(id);
+                            } else if is_selected {
+                                double_clicked_obj_id = Some(id);
                             } else {
-                                clicked_obj_id = Some(*id);
+                                clicked_obj_id = Some(id);
                             }
                         }
 
                         if ui.is_item_hovered() && ui.is_mouse_double_clicked(imgui::MouseButton::Left) {
-                            double_clicked_obj_id = Some(*id);
+                            double_clicked_obj_id = Some(id);
+                        }
+
+                        // Drag an object onto another to make it a child
+                        if let Some(tooltip) = ui.drag_drop_source_config(SCENE_OBJECT_PAYLOAD).begin_payload(id) {
+                            ui.text(&name);
+                            tooltip.end();
                         }
+                        if let Some(target) = ui.drag_drop_target() {
+                            if let Some(Ok(payload)) = target.accept_payload::<ObjectId, _>(SCENE_OBJECT_PAYLOAD, imgui::DragDropFlags::empty()) {
+                                reparent = Some((payload.data, Some(id)));
+                            }
+                            target.pop();
+                        }
+                    }
+
+                    // Dropping here detaches an object from its parent
+                    ui.text_disabled("(drop here to unparent)");
+                    if let Some(target) = ui.drag_drop_target() {
+                        if let Some(Ok(payload)) = target.accept_payload::<ObjectId, _>(SCENE_OBJECT_PAYLOAD, imgui::DragDropFlags::empty()) {
+                            reparent = Some((payload.data, None));
+                        }
+                        target.pop();
                     }
                 }
 
@@ -606,28 +1169,145 @@ impl UiManager {
                 content.separator();
                 content.header("Object Actions");
 
-                // Duplicate button - only enabled if an object is selected
-                let selected_id = game.scene.selected_object_id();
-                if let Some(id) = selected_id {
-                    // Check if the selected object is duplicatable
-                    let can_duplicate = game.scene.get_object(id)
-                        .map(|obj| !matches!(obj.object_type,
+                // Duplicate/Delete act on the whole selection - only enabled if an object is selected
+                let selected_objects: Vec<_> = game.scene.selected_ids()
+                    .iter()
+                    .filter_map(|&id| game.scene.get_object(id))
+                    .collect();
+                if !selected_objects.is_empty() {
+                    if selected_objects.len() > 1 {
+                        content.text(&format!("{} objects selected", selected_objects.len()));
+                    }
+
+                    // Check if the selected objects are duplicatable
+                    let can_duplicate = selected_objects.iter()
+                        .any(|obj| !matches!(obj.object_type,
                             crate::scene::ObjectType::Skybox |
-                            crate::scene::ObjectType::Nebula |
-                            crate::scene::ObjectType::SSAO))
-                        .unwrap_or(false);
+                            crate::scene::ObjectType::SSAO));
 
                     if can_duplicate {
                         if ui.button("Duplicate") {
-                            duplicate_object_id = Some(id);
+                            duplicate_clicked = true;
+                        }
+
+                        // Rows and grids of copies of the last selected object
+                        ui.same_line();
+                        if ui.button("Array...") {
+                            ui.open_popup("duplicate_array_popup");
                         }
+                        ui.popup("duplicate_array_popup", || {
+                            let array = &mut game.duplicate_array;
+                            ui.text("Copies of the selected object");
+
+                            let mut counts = array.counts.map(|count| count as i32);
+                            if ui.input_int3("Count X/Y/Z", &mut counts).build() {
+                                array.counts = counts.map(|count| count.max(1) as u32);
+                            }
+                            let mut spacing = array.spacing.to_array();
+                            if ui.input_float3("Spacing", &mut spacing).build() {
+                                array.spacing = glam::Vec3::from_array(spacing);
+                            }
+
+                            let copies = array.copy_count();
+                            if copies > MAX_ARRAY_COPIES {
+                                ui.text_disabled(format!("{} copies (limit {})", copies, MAX_ARRAY_COPIES));
+                            } else {
+                                ui.text_disabled(format!("{} copies", copies));
+                            }
+                            ui.disabled(copies == 0 || copies > MAX_ARRAY_COPIES, || {
+                                if ui.button("Create") {
+                                    duplicate_array_clicked = true;
+                                    ui.close_current_popup();
+                                }
+                            });
+                        });
                     } else {
                         ui.text_disabled("Cannot duplicate");
                     }
+
+                    // Singletons are always present, so they can't be deleted
+                    let can_delete = selected_objects.iter().any(|obj| !obj.is_singleton());
+
+                    if can_delete {
+                        ui.same_line();
+                        if ui.button("Delete") {
+                            delete_clicked = true;
+                        }
+                    }
+
+                    if can_delete {
+                        ui.same_line();
+                        if ui.button("Drop to Ground") {
+                            drop_to_ground_clicked = true;
+                        }
+                        if ui.is_item_hovered() {
+                            ui.tooltip_text("Lower onto the mesh below, or the Y=0 plane");
+                        }
+                    }
+
+                    // Clipboard (Ctrl+C / Ctrl+V; Ctrl+Shift for materials)
+                    if ui.button("Copy") {
+                        copy_clicked = true;
+                    }
+                    if selected_objects.iter().any(|obj| obj.uses_material()) {
+                        ui.same_line();
+                        if ui.button("Copy Material") {
+                            copy_material_clicked = true;
+                        }
+                        if game.material_clipboard.is_some() {
+                            ui.same_line();
+                            if ui.button("Paste Material") {
+                                paste_material_clicked = true;
+                            }
+                        }
+                    }
                 } else {
                     ui.text_disabled("Select object first");
                 }
 
+                if let Some(clipboard) = &game.clipboard {
+                    let label = format!("Paste '{}'", clipboard.name);
+                    if ui.button(&label) {
+                        paste_clicked = true;
+                    }
+                }
+
+                if ui.button("Add Point Light") {
+                    add_point_light_clicked = true;
+                }
+                ui.same_line();
+                if ui.button("Add Directional Light") {
+                    add_directional_light_clicked = true;
+                }
+                ui.same_line();
+                if ui.button("Add Nebula") {
+                    add_nebula_clicked = true;
+                }
+                if ui.button("Add Engine Exhaust") {
+                    add_particle_emitter_clicked = true;
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Attaches to the selected ship");
+                }
+
+                if ui.button("Add Primitive") {
+                    ui.open_popup("add_primitive_popup");
+                }
+                ui.popup("add_primitive_popup", || {
+                    for shape in PrimitiveShape::ALL {
+                        if ui.selectable(shape.name()) {
+                            add_primitive = Some(shape);
+                        }
+                    }
+                });
+
+                // New lights, primitives and prefabs land on the surface last under the cursor
+                ui.checkbox("Snap New to Surface", &mut game.placement.snap_to_surface);
+                ui.same_line();
+                ui.disabled(!game.placement.snap_to_surface, || {
+                    ui.checkbox("Align to Normal", &mut game.placement.align_to_normal);
+                });
+
                 // Gizmo controls integrated here
                 content.separator();
                 content.header("Transform Tools");
@@ -663,6 +1343,38 @@ impl UiManager {
                 }
 
                 content.checkbox("Show Gizmo", &mut game.gizmo_state.enabled);
+                Self::build_space_toggle(ui, content, game);
+                Self::build_snap_controls(content, game);
+
+                // Measurement ruler (M toggles, Escape clears)
+                content.separator();
+                content.header("Ruler");
+
+                let ruler_label = if game.ruler.active { "Stop Measuring (M)" } else { "Measure (M)" };
+                if ui.button(ruler_label) {
+                    game.toggle_ruler();
+                }
+                ui.same_line();
+                if ui.button("Clear##ruler") {
+                    game.ruler.clear();
+                }
+
+                if game.scene.selection_count() == 2 {
+                    if ui.button("Measure Selection") {
+                        game.measure_selection();
+                    }
+                } else {
+                    ui.text_disabled("Select two objects to measure between them");
+                }
+
+                if let Some(distance) = game.ruler.distance() {
+                    // Until the second point is placed the distance follows the cursor
+                    let suffix = if game.ruler.is_finished() { "" } else { " (preview)" };
+                    content.text(&format!("Distance: {:.3}{}", distance, suffix));
+                } else if game.ruler.active {
+                    let hint = if game.ruler.start().is_some() { "Click the second point" } else { "Click the first point" };
+                    ui.text_disabled(hint);
+                }
 
                 // Camera up vector controls
                 content.separator();
@@ -674,7 +1386,42 @@ impl UiManager {
                     game.reset_camera_up();
                 }
 
-                // Materials section
+                content.checkbox("Show View Cube", &mut game.view_cube.enabled);
+
+                // Camera bookmarks (keys 5-9 jump to the first five)
+                content.separator();
+                content.header("Camera Bookmarks");
+
+                if ui.button("Save View") {
+                    game.save_camera_bookmark();
+                }
+
+                let mut goto_bookmark: Option<usize> = None;
+                let mut remove_bookmark: Option<String> = None;
+                for (i, bookmark) in game.camera.bookmarks().iter().enumerate() {
+                    let label = if i < 5 {
+                        format!("{} ({})##bookmark_{}", bookmark.name, i + 5, i)
+                    } else {
+                        format!("{}##bookmark_{}", bookmark.name, i)
+                    };
+                    if ui.button(&label) {
+                        goto_bookmark = Some(i);
+                    }
+                    ui.same_line();
+                    if ui.small_button(format!("X##remove_bookmark_{}", i)) {
+                        remove_bookmark = Some(bookmark.name.clone());
+                    }
+                }
+
+                if let Some(index) = goto_bookmark {
+                    game.goto_camera_bookmark(index);
+                }
+                if let Some(name) = remove_bookmark {
+                    game.camera.remove_bookmark(&name);
+                    game.mark_config_dirty();
+                }
+
+                // Materials section
                 content.separator();
                 content.header("Materials");
 
@@ -690,31 +1437,149 @@ impl UiManager {
                 let (s, l, _) = content.config_buttons();
                 save_scene_clicked = s;
                 load_scene_clicked = l;
+                if ui.button("Export glTF") {
+                    export_gltf_clicked = true;
+                }
+
+                // Auto-save writes a separate file; the Save button's files are never touched
+                let autosave = &mut game.autosave_config;
+                let orig_autosave = autosave.clone();
+                ui.checkbox("Auto-save", &mut autosave.enabled);
+                ui.same_line();
+                ui.disabled(!autosave.enabled, || {
+                    ui.set_next_item_width(90.0);
+                    ui.slider_config("##autosave_interval", AutosaveConfig::MIN_INTERVAL_SECS, AutosaveConfig::MAX_INTERVAL_SECS)
+                        .flags(imgui::SliderFlags::LOGARITHMIC)
+                        .display_format("%d s")
+                        .build(&mut autosave.interval_secs);
+                });
+                if orig_autosave.enabled != autosave.enabled || orig_autosave.interval_secs != autosave.interval_secs {
+                    game.mark_config_dirty();
+                }
+                ui.text_disabled(format!("Last saved {} s ago", game.last_save_time.elapsed().as_secs()));
             });
 
         if let Some(id) = clicked_obj_id {
             game.scene.select_object(id);
         }
 
-        // Handle double-click to focus on object
+        if let Some(id) = toggled_obj_id {
+            game.scene.toggle_selection(id);
+        }
+
+        // Handle double-click to focus on object (and keep orbiting it in orbit mode)
         if let Some(id) = double_clicked_obj_id {
             game.scene.select_object(id);
             game.focus_on_object(id);
+            if game.camera.mode() == CameraMode::Orbit {
+                game.follow_object(id);
+            }
+        }
+
+        // Handle drag-to-reparent (cycles and singletons are rejected)
+        if let Some((child, parent)) = reparent {
+            match game.scene.set_parent(child, parent) {
+                Ok(()) => game.mark_scene_dirty(),
+                Err(e) => game.add_notification(format!("Can't reparent: {}", e), 3.0),
+            }
+        }
+
+        // Handle array duplicate (the copies become the new selection)
+        if duplicate_array_clicked {
+            if let Some(id) = game.scene.selected_object_id() {
+                let array = game.duplicate_array;
+                match game.scene.duplicate_array(id, &array) {
+                    Ok(new_ids) => {
+                        if let Some((&first, rest)) = new_ids.split_first() {
+                            game.scene.select_object(first);
+                            for &id in rest {
+                                game.scene.toggle_selection(id);
+                            }
+                            game.mark_scene_dirty();
+                        }
+                        game.add_notification(format!("Created {} copies", new_ids.len()), 2.0);
+                    }
+                    Err(e) => game.add_notification(format!("Can't duplicate: {}", e), 3.0),
+                }
+            }
         }
 
-        // Handle duplicate
-        if let Some(id) = duplicate_object_id {
-            if let Some(new_id) = game.scene.duplicate_object(id) {
-                game.scene.select_object(new_id);
+        // Handle duplicate (the copies become the new selection); children are copied
+        // with their parent, so selected descendants of selected objects are skipped
+        if duplicate_clicked {
+            let selected_ids = game.scene.selected_ids().to_vec();
+            let roots: Vec<ObjectId> = selected_ids
+                .iter()
+                .copied()
+                .filter(|&id| !selected_ids.iter().any(|&other| game.scene.is_ancestor(other, id)))
+                .collect();
+            let mut new_ids = Vec::new();
+            for id in roots {
+                new_ids.extend(game.scene.duplicate_object(id));
+            }
+
+            if let Some((&first, rest)) = new_ids.split_first() {
+                game.scene.select_object(first);
+                for &id in rest {
+                    game.scene.toggle_selection(id);
+                }
                 game.mark_scene_dirty();
             }
         }
 
+        // Handle delete (singletons are skipped)
+        if delete_clicked {
+            let deletable: Vec<ObjectId> = game.scene.selected_ids()
+                .iter()
+                .copied()
+                .filter(|&id| game.scene.get_object(id).is_some_and(|obj| !obj.is_singleton()))
+                .collect();
+
+            for id in deletable {
+                if game.scene.remove_object(id).is_some() {
+                    game.mark_scene_dirty();
+                }
+            }
+        }
+
+        if copy_clicked {
+            game.copy_selected();
+        }
+        if paste_clicked {
+            game.paste();
+        }
+        if copy_material_clicked {
+            game.copy_material();
+        }
+        if paste_material_clicked {
+            game.paste_material();
+        }
+
+        if add_point_light_clicked {
+            game.add_point_light();
+        }
+        if add_directional_light_clicked {
+            game.add_directional_light();
+        }
+        if add_nebula_clicked {
+            game.add_nebula();
+        }
+        if add_particle_emitter_clicked {
+            game.add_particle_emitter();
+        }
+        if drop_to_ground_clicked {
+            game.drop_selection_to_ground();
+        }
+
+        if let Some(shape) = add_primitive {
+            game.add_primitive(shape);
+        }
+
         // Handle material click - open material editor
         if let Some(mat_name) = clicked_material {
             game.current_material_name = mat_name.clone();
             if let Some(mat) = game.material_library.get(&mat_name) {
-                game.material = *mat;
+                game.material = mat.clone();
             }
             game.material_editor_open = true;
         }
@@ -725,6 +1590,9 @@ impl UiManager {
         if load_scene_clicked {
             Self::load_scene(game);
         }
+        if export_gltf_clicked {
+            game.export_scene_gltf();
+        }
     }
 
     /// Build the transform editor UI for selected object (top-right corner)
@@ -733,41 +1601,116 @@ impl UiManager {
         let panel_width = 350.0;
         let mut transform_changed = false;
 
+        // Children show (and edit) their transform relative to the parent
+        let parent_name = game
+            .scene
+            .selected_object()
+            .and_then(|obj| obj.parent)
+            .and_then(|parent| game.scene.get_object(parent))
+            .map(|parent| parent.name.clone());
+        let layer_names: Vec<String> = game.scene.layers().iter().map(|layer| layer.name.clone()).collect();
+        let mesh_stats = game.selected_mesh_stats;
+        // Solo is a render filter, not a scene edit, so it's applied after the panel without marking dirty
+        let selected_id = game.scene.selected_object().map(|obj| obj.id);
+        let orig_soloed = selected_id.is_some_and(|id| game.scene.is_soloed(id));
+        let mut soloed = orig_soloed;
+
         GuiPanelBuilder::new(ui, "Transform")
-            .size(panel_width, 320.0)
+            .size(panel_width, 560.0)
             .position(window_width - panel_width - 10.0, 10.0)
             .build(|content| {
-                if let Some(obj) = game.scene.selected_object_mut() {
+                if game.scene.selection_count() > 1 {
+                    transform_changed = Self::build_group_transform_editor(ui, content, game);
+                } else if let Some(obj) = game.scene.selected_object_mut() {
                     // Show selected object name prominently
                     content.text_colored([0.2, 1.0, 0.2, 1.0], "Selected:");
                     ui.same_line();
                     content.text(&obj.name);
+                    if let Some(parent_name) = &parent_name {
+                        content.text_disabled(&format!("Local to parent '{}'", parent_name));
+                    }
                     content.separator();
 
                     // Store original values to detect changes
                     let orig_visible = obj.visible;
+                    let orig_layer = obj.layer.clone();
+                    let orig_wireframe = obj.wireframe;
+                    let orig_tags = obj.tags.clone();
+                    let (orig_spin, orig_bob) = (obj.spin, obj.bob);
                     let orig_position = obj.transform.position;
                     let orig_scale = obj.transform.scale;
                     let (orig_pitch, orig_yaw, orig_roll) = obj.transform.euler_angles();
 
-                    // Visibility
+                    // Visibility, and Solo to temporarily hide everything else
                     content.checkbox("Visible", &mut obj.visible);
+                    ui.same_line();
+                    content.checkbox("Solo", &mut soloed);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Render only soloed objects (and their children) until solo is exited");
+                    }
+
+                    // Layer (hide/lock in the Layers panel)
+                    let mut layer_index = layer_names.iter().position(|name| *name == obj.layer).unwrap_or(0);
+                    if ui.combo("Layer", &mut layer_index, &layer_names, |name| name.as_str().into()) {
+                        obj.layer = layer_names[layer_index].clone();
+                    }
+
+                    // Wireframe overlay (mesh objects only)
+                    if matches!(
+                        obj.object_type,
+                        ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_) | ObjectType::Unlit(_)
+                    ) {
+                        content.checkbox("Wireframe", &mut obj.wireframe);
+                    }
+
+                    // Tags for gameplay queries: click a chip to remove it
+                    content.text("Tags");
+                    let mut removed_tag = None;
+                    for (index, tag) in obj.tags.iter().enumerate() {
+                        if index % TAG_CHIPS_PER_ROW != 0 {
+                            ui.same_line();
+                        }
+                        if ui.small_button(format!("{} x##tag_{}", tag, index)) {
+                            removed_tag = Some(tag.clone());
+                        }
+                    }
+                    if let Some(tag) = removed_tag {
+                        obj.remove_tag(&tag);
+                    }
+                    ui.set_next_item_width(-50.0);
+                    let entered = ui
+                        .input_text("##tag_input", &mut game.tag_input)
+                        .hint("New tag")
+                        .enter_returns_true(true)
+                        .build();
+                    ui.same_line();
+                    if (ui.small_button("Add##tag") || entered) && obj.add_tag(&game.tag_input) {
+                        game.tag_input.clear();
+                    }
                     content.separator();
 
                     // Position - using input fields (unbounded)
+                    // (the X/Y/Z buttons lock an axis for these inputs and the gizmo)
                     content.header("Position");
-                    content.input_vec3("Position", &mut obj.transform.position);
+                    let axis_locks = &mut game.gizmo_state.axis_locks;
+                    content.input_vec3_locked("Position", &mut obj.transform.position, axis_locks);
 
-                    // Rotation - using input fields with wrap-around
+                    // Rotation - using input fields with wrap-around; pitch/yaw/roll follow the X/Y/Z locks
                     content.header("Rotation (degrees)");
                     let (pitch, yaw, roll) = obj.transform.euler_angles();
                     let mut pitch_deg = pitch.to_degrees();
                     let mut yaw_deg = yaw.to_degrees();
                     let mut roll_deg = roll.to_degrees();
 
-                    content.input_angle("Pitch", &mut pitch_deg);
-                    content.input_angle("Yaw", &mut yaw_deg);
-                    content.input_angle("Roll", &mut roll_deg);
+                    for (angle_label, angle, locked) in [
+                        ("Pitch", &mut pitch_deg, axis_locks[0]),
+                        ("Yaw", &mut yaw_deg, axis_locks[1]),
+                        ("Roll", &mut roll_deg, axis_locks[2]),
+                    ] {
+                        let disabled = ui.begin_disabled(locked);
+                        content.input_angle(angle_label, angle);
+                        disabled.end();
+                    }
 
                     // Update rotation quaternion
                     obj.transform.set_euler_rotation(
@@ -778,10 +1721,35 @@ impl UiManager {
 
                     // Scale - using input fields (unbounded)
                     content.header("Scale");
-                    content.input_vec3("Scale", &mut obj.transform.scale);
+                    content.input_vec3_locked("Scale", &mut obj.transform.scale, axis_locks);
+
+                    // Procedural idle motion, layered over the transform above in play mode only
+                    content.header("Idle Motion (play mode)");
+                    let mut spin_enabled = obj.spin.is_some();
+                    if ui.checkbox("Spin", &mut spin_enabled) {
+                        obj.spin = spin_enabled.then(Spin::default);
+                    }
+                    if let Some(spin) = &mut obj.spin {
+                        content.input_vec3("Spin Axis", &mut spin.axis);
+                        content.slider_f32("Speed (deg/s)", &mut spin.speed, -180.0, 180.0);
+                    }
+                    let mut bob_enabled = obj.bob.is_some();
+                    if ui.checkbox("Bob", &mut bob_enabled) {
+                        obj.bob = bob_enabled.then(Bob::default);
+                    }
+                    if let Some(bob) = &mut obj.bob {
+                        content.input_vec3("Bob Axis", &mut bob.axis);
+                        content.slider_f32("Amplitude", &mut bob.amplitude, 0.0, 5.0);
+                        content.slider_f32("Frequency (Hz)", &mut bob.frequency, 0.0, 2.0);
+                    }
 
                     // Check if anything changed
                     if orig_visible != obj.visible
+                        || orig_layer != obj.layer
+                        || orig_wireframe != obj.wireframe
+                        || orig_tags != obj.tags
+                        || orig_spin != obj.spin
+                        || orig_bob != obj.bob
                         || orig_position != obj.transform.position
                         || orig_scale != obj.transform.scale
                         || orig_pitch != pitch_deg.to_radians()
@@ -791,6 +1759,44 @@ impl UiManager {
                         transform_changed = true;
                     }
 
+                    // Mesh weight, to judge whether an imported asset needs decimating
+                    if matches!(
+                        obj.object_type,
+                        ObjectType::Cube | ObjectType::Primitive(_) | ObjectType::Mesh(_) | ObjectType::Unlit(_)
+                    ) {
+                        content.header("Mesh");
+                        match mesh_stats {
+                            Some(stats) => {
+                                let size = stats.size();
+                                let scaled = size * obj.transform.scale;
+                                content.text(&format!("Vertices: {}  Triangles: {}", stats.vertices, stats.triangles));
+                                content.text(&format!("Size: {:.2} x {:.2} x {:.2}", size.x, size.y, size.z));
+                                content.text_disabled(&format!("Scaled: {:.2} x {:.2} x {:.2}", scaled.x, scaled.y, scaled.z));
+                            }
+                            None => {
+                                content.text_disabled("Mesh not loaded yet");
+                            }
+                        }
+
+                        // Rebuild normals of imported meshes (shared by every object using the file)
+                        if let ObjectType::Mesh(path) | ObjectType::Unlit(path) = &obj.object_type {
+                            let options = &mut game.normal_recompute_options;
+                            content.checkbox("Smooth Normals", &mut options.smooth);
+                            ui.disabled(!options.smooth, || {
+                                ui.text("Smoothing Angle");
+                                ui.slider_config("##normal_smoothing_angle", 0.0, 180.0)
+                                    .display_format("%.0f deg")
+                                    .build(&mut options.smoothing_angle);
+                            });
+                            if ui.button("Recompute Normals") && mesh_stats.is_some() {
+                                game.pending_normal_recompute = Some((path.clone(), *options));
+                            }
+                            if ui.is_item_hovered() {
+                                ui.tooltip_text("Rebuild this mesh's normals from its faces (not saved to the file)");
+                            }
+                        }
+                    }
+
                     // Show object-specific settings hint
                     content.separator();
                     match obj.object_type {
@@ -806,6 +1812,14 @@ impl UiManager {
                             content.text("Select this object to see");
                             content.text("SSAO Settings panel");
                         }
+                        ObjectType::PointLight => {
+                            content.text("Select this object to see");
+                            content.text("Point Light Settings panel");
+                        }
+                        ObjectType::ParticleEmitter => {
+                            content.text("Select this object to see");
+                            content.text("Particle Emitter Settings panel");
+                        }
                         _ => {}
                     }
                 } else {
@@ -816,28 +1830,285 @@ impl UiManager {
                 }
             });
 
+        if let Some(id) = selected_id.filter(|_| soloed != orig_soloed) {
+            game.scene.set_soloed(id, soloed);
+        }
+
         // Mark scene as dirty if transform changed
         if transform_changed {
             game.mark_scene_dirty();
         }
     }
 
+    /// Edit the fields shared by a multi-selection. Position edits move the group by the
+    /// change in its centroid; rotation and scale edits write only the changed component
+    /// to every object. Returns true if anything changed.
+    fn build_group_transform_editor(ui: &Ui, content: &mut GuiContentBuilder, game: &mut Game) -> bool {
+        let selected_ids = game.scene.selected_ids().to_vec();
+        let (Some(centroid), Some(primary)) = (game.scene.selection_centroid(), game.scene.selected_object()) else {
+            return false;
+        };
+        let primary_transform = primary.transform;
+        let all_visible = selected_ids
+            .iter()
+            .filter_map(|&id| game.scene.get_object(id))
+            .all(|obj| obj.visible);
+
+        content.text_colored([0.2, 1.0, 0.2, 1.0], &format!("{} objects selected", selected_ids.len()));
+        content.text_disabled("Rotation/scale show the last selected");
+        content.separator();
+
+        // Visibility, and Solo for the whole selection (not a scene edit, so it's applied here)
+        let mut visible = all_visible;
+        content.checkbox("Visible", &mut visible);
+        let all_soloed = selected_ids.iter().all(|&id| game.scene.is_soloed(id));
+        let mut soloed = all_soloed;
+        ui.same_line();
+        content.checkbox("Solo", &mut soloed);
+        if soloed != all_soloed {
+            for &id in &selected_ids {
+                game.scene.set_soloed(id, soloed);
+            }
+        }
+        content.separator();
+
+        // Position of the selection centroid (X/Y/Z buttons lock an axis here and for the gizmo)
+        content.header("Position (center)");
+        let axis_locks = &mut game.gizmo_state.axis_locks;
+        let mut position = centroid;
+        content.input_vec3_locked("Position", &mut position, axis_locks);
+
+        // Rotation
+        content.header("Rotation (degrees)");
+        let (orig_pitch, orig_yaw, orig_roll) = primary_transform.euler_angles();
+        let mut pitch_deg = orig_pitch.to_degrees();
+        let mut yaw_deg = orig_yaw.to_degrees();
+        let mut roll_deg = orig_roll.to_degrees();
+        for (angle_label, angle, locked) in [
+            ("Pitch", &mut pitch_deg, axis_locks[0]),
+            ("Yaw", &mut yaw_deg, axis_locks[1]),
+            ("Roll", &mut roll_deg, axis_locks[2]),
+        ] {
+            let disabled = ui.begin_disabled(locked);
+            content.input_angle(angle_label, angle);
+            disabled.end();
+        }
+
+        // Scale
+        content.header("Scale");
+        let mut scale = primary_transform.scale;
+        content.input_vec3_locked("Scale", &mut scale, axis_locks);
+
+        let pitch_changed = pitch_deg != orig_pitch.to_degrees();
+        let yaw_changed = yaw_deg != orig_yaw.to_degrees();
+        let roll_changed = roll_deg != orig_roll.to_degrees();
+        let scale_changed = scale.cmpne(primary_transform.scale);
+        let position_delta = position - centroid;
+
+        let changed = visible != all_visible
+            || position_delta != glam::Vec3::ZERO
+            || pitch_changed
+            || yaw_changed
+            || roll_changed
+            || scale_changed.any();
+        if !changed {
+            return false;
+        }
+
+        for &id in &selected_ids {
+            // The centroid moves in world space; convert to each object's parent space.
+            // Children of selected objects already follow their parent.
+            let follows_parent = selected_ids.iter().any(|&other| game.scene.is_ancestor(other, id));
+            let local_delta = match game.scene.get_object(id).and_then(|obj| obj.parent) {
+                Some(parent) => game.scene.world_matrix(parent).inverse().transform_vector3(position_delta),
+                None => position_delta,
+            };
+
+            let Some(obj) = game.scene.get_object_mut(id) else {
+                continue;
+            };
+
+            if visible != all_visible {
+                obj.visible = visible;
+            }
+            if !follows_parent {
+                obj.transform.position += local_delta;
+            }
+
+            if pitch_changed || yaw_changed || roll_changed {
+                let (pitch, yaw, roll) = obj.transform.euler_angles();
+                obj.transform.set_euler_rotation(
+                    if pitch_changed { pitch_deg.to_radians() } else { pitch },
+                    if yaw_changed { yaw_deg.to_radians() } else { yaw },
+                    if roll_changed { roll_deg.to_radians() } else { roll },
+                );
+            }
+
+            obj.transform.scale = glam::Vec3::select(scale_changed, scale, obj.transform.scale);
+        }
+
+        true
+    }
+
+    /// Build prefab browser panel: save the selected subtree and spawn saved prefabs
+    pub fn build_prefab_browser(ui: &Ui, game: &mut Game) {
+        let mut save_clicked = false;
+        let mut spawn_prefab: Option<String> = None;
+
+        GuiPanelBuilder::new(ui, "Prefabs")
+            .size(250.0, 240.0)
+            .position(10.0, 570.0)
+            .build(|content| {
+                let selected_name = game.scene.selected_object().map(|obj| obj.name.clone());
+
+                content.text("Prefab Name");
+                ui.input_text("##prefab_name", &mut game.prefab_name)
+                    .hint(selected_name.as_deref().unwrap_or("Select an object"))
+                    .build();
+
+                if selected_name.is_some() {
+                    if ui.button("Save Selected as Prefab") {
+                        save_clicked = true;
+                    }
+                } else {
+                    ui.text_disabled("Select an object to save it");
+                }
+
+                content.separator();
+                content.header("Saved Prefabs");
+
+                if game.prefab_names.is_empty() {
+                    content.text_disabled("No prefabs in config/prefabs/");
+                }
+                for name in &game.prefab_names {
+                    if ui.small_button(format!("Spawn##prefab_{}", name)) {
+                        spawn_prefab = Some(name.clone());
+                    }
+                    ui.same_line();
+                    ui.text(name);
+                }
+
+                if ui.button("Refresh") {
+                    game.prefab_names = crate::prefab::PrefabData::list();
+                }
+            });
+
+        if save_clicked {
+            game.save_selected_as_prefab();
+        }
+
+        if let Some(name) = spawn_prefab {
+            game.instantiate_prefab(&name);
+        }
+    }
+
+    /// Build layers panel: hide or lock groups of objects and manage the layer list
+    pub fn build_layers_panel(ui: &Ui, game: &mut Game) {
+        let orig_layers = game.scene.layers().to_vec();
+        let mut add_clicked = false;
+        let mut remove_layer: Option<String> = None;
+
+        GuiPanelBuilder::new(ui, "Layers")
+            .size(250.0, 220.0)
+            .position(10.0, 820.0)
+            .build(|content| {
+                content.text_disabled("Hidden: not drawn or pickable");
+                content.text_disabled("Locked: drawn, not selectable");
+                content.separator();
+
+                for layer in orig_layers.iter() {
+                    let count = game.scene.layer_object_count(&layer.name);
+                    let Some(state) = game.scene.layer_mut(&layer.name) else {
+                        continue;
+                    };
+                    ui.checkbox(format!("##layer_visible_{}", layer.name), &mut state.visible);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Visible");
+                    }
+                    ui.same_line();
+                    ui.checkbox(format!("##layer_locked_{}", layer.name), &mut state.locked);
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Locked");
+                    }
+                    ui.same_line();
+                    ui.text(format!("{} ({})", layer.name, count));
+                    if layer.name != crate::scene::DEFAULT_LAYER {
+                        ui.same_line();
+                        if ui.small_button(format!("X##remove_layer_{}", layer.name)) {
+                            remove_layer = Some(layer.name.clone());
+                        }
+                    }
+                }
+
+                content.separator();
+                ui.input_text("##new_layer_name", &mut game.new_layer_name)
+                    .hint("New layer name")
+                    .build();
+                ui.same_line();
+                add_clicked = ui.button("Add");
+            });
+
+        let mut changed = game.scene.layers() != orig_layers.as_slice();
+
+        if add_clicked {
+            let name = game.new_layer_name.trim().to_string();
+            if game.scene.add_layer(&name) {
+                game.new_layer_name.clear();
+                changed = true;
+            } else if !name.is_empty() {
+                game.add_notification(format!("Layer '{}' already exists", name), 2.0);
+            }
+        }
+
+        if let Some(name) = remove_layer {
+            changed |= game.scene.remove_layer(&name);
+        }
+
+        if changed {
+            // Objects that just became hidden or locked can't stay selected
+            game.scene.deselect_unselectable();
+            game.mark_scene_dirty();
+        }
+    }
+
     /// Build material editor panel
-    pub fn build_material_editor(ui: &Ui, game: &mut Game) {
+    /// `preview_texture` is the offscreen sphere rendered with `game.material` by the renderer
+    pub fn build_material_editor(ui: &Ui, game: &mut Game, preview_texture: TextureId) {
         // Material Editor Panel - only show when open
         if !game.material_editor_open {
             return;
         }
 
+        let mut open = game.material_editor_open;
+        let mut apply_requested = false;
         ui.window("Material Editor")
-            .position([990.0, 10.0], imgui::Condition::FirstUseEver)
-            .size([280.0, 500.0], imgui::Condition::FirstUseEver)
-            .opened(&mut game.material_editor_open)
+            .position([990.0, 10.0], gui_builder::layout_condition())
+            .size([280.0, 600.0], gui_builder::layout_condition())
+            .opened(&mut open)
             .build(|| {
                 let content = ui;
                 content.text("PBR Material Properties");
                 content.separator();
 
+                // Live preview sphere (drag to orbit, slowly turns on its own otherwise)
+                let preview_size = crate::core::passes::material_preview::PREVIEW_SIZE as f32;
+                let preview_min = ui.cursor_screen_pos();
+                let preview_max = [preview_min[0] + preview_size, preview_min[1] + preview_size];
+                ui.invisible_button("##material_preview", [preview_size, preview_size]);
+                ui.get_window_draw_list().add_image(preview_texture, preview_min, preview_max).build();
+                if ui.is_item_active() {
+                    let delta = ui.io().mouse_delta;
+                    game.material_preview_yaw -= delta[0] * 0.01;
+                    game.material_preview_pitch = (game.material_preview_pitch + delta[1] * 0.01).clamp(-1.4, 1.4);
+                } else {
+                    game.material_preview_yaw += ui.io().delta_time * 0.3;
+                }
+                game.material_preview_yaw %= std::f32::consts::TAU;
+                ui.same_line();
+                ui.text_disabled("(drag to orbit)");
+
+                content.separator();
+
                 // Material name input
                 ui.text("Material Name:");
                 let mut name_buf = game.current_material_name.clone();
@@ -857,7 +2128,7 @@ impl UiManager {
                         let is_selected = name == &game.current_material_name;
                         if ui.selectable_config(name).selected(is_selected).build() {
                             if let Some(mat) = game.material_library.get(name) {
-                                game.material = *mat;
+                                game.material = mat.clone();
                                 game.current_material_name = name.clone();
                             }
                         }
@@ -875,146 +2146,1557 @@ impl UiManager {
 
                 content.separator();
 
-                // Metallic slider
-                ui.text("Metallic");
-                ui.slider("##metallic", 0.0, 1.0, &mut game.material.metallic);
-                ui.same_line();
-                ui.text_disabled("(0=plastic, 1=metal)");
+                // Metallic slider
+                ui.text("Metallic");
+                ui.slider("##metallic", 0.0, 1.0, &mut game.material.metallic);
+                ui.same_line();
+                ui.text_disabled("(0=plastic, 1=metal)");
+
+                // Roughness slider
+                ui.text("Roughness");
+                ui.slider("##roughness", 0.0, 1.0, &mut game.material.roughness);
+                ui.same_line();
+                ui.text_disabled("(0=smooth, 1=rough)");
+
+                // Ambient lighting slider
+                ui.text("Ambient Strength");
+                ui.slider("##ambient_strength", 0.0, 2.0, &mut game.material.ambient_strength);
+                ui.same_line();
+                ui.text_disabled("(constant ambient light)");
+
+                // GI strength slider
+                ui.text("GI Strength");
+                ui.slider("##gi_strength", 0.0, 1.0, &mut game.material.gi_strength);
+                ui.same_line();
+                ui.text_disabled("(environmental lighting)");
+
+                content.separator();
+
+                // Emission (unlit glow, strong values feed bloom)
+                content.text("Emissive Color");
+                let mut emissive = game.material.emissive.to_array();
+                if ui.color_edit3("##emissive", &mut emissive) {
+                    game.material.emissive = glam::Vec3::from_array(emissive);
+                }
+
+                ui.text("Emissive Strength");
+                ui.slider("##emissive_strength", 0.0, 20.0, &mut game.material.emissive_strength);
+                ui.same_line();
+                ui.text_disabled("(>1 glows with bloom)");
+
+                content.separator();
+
+                // Transparency (blended after opaque objects, sorted back to front)
+                ui.checkbox("Transparent", &mut game.material.transparent);
+                ui.disabled(!game.material.transparent, || {
+                    ui.text("Opacity");
+                    ui.slider("##opacity", 0.0, 1.0, &mut game.material.opacity);
+                    ui.same_line();
+                    ui.text_disabled("(0=invisible, 1=solid)");
+                });
+
+                content.separator();
+
+                // Texture paths (empty = use the constant values above)
+                content.text("Textures:");
+                let texture_slots = [
+                    ("Albedo##albedo_texture", &mut game.material.albedo_texture),
+                    ("Normal##normal_texture", &mut game.material.normal_texture),
+                    ("Metal/Rough##mr_texture", &mut game.material.metallic_roughness_texture),
+                ];
+                for (label, slot) in texture_slots {
+                    let mut path_buf = slot.clone().unwrap_or_default();
+                    if ui.input_text(label, &mut path_buf).enter_returns_true(true).build() {
+                        let path = path_buf.trim();
+                        *slot = if path.is_empty() { None } else { Some(path.to_string()) };
+                    }
+                }
+                ui.text_disabled("(press Enter to apply, missing files use the color)");
+
+                // Baked per-vertex colors from the mesh file (white when it has none)
+                ui.checkbox("Vertex Colors", &mut game.material.vertex_colors);
+                ui.same_line();
+                ui.text_disabled("(multiply albedo by mesh colors)");
+
+                content.separator();
+
+                // Weathering layer (overlay blended over this material by a mask)
+                let mut weathered = game.material.layer.is_some();
+                if ui.checkbox("Weathering", &mut weathered) {
+                    game.material.layer = weathered.then(crate::material::LayeredMaterial::default);
+                }
+                ui.same_line();
+                ui.text_disabled("(overlay layer, e.g. rust)");
+                if let Some(layer) = game.material.layer.as_mut() {
+                    ui.text("Overlay Albedo");
+                    let mut overlay_albedo = layer.overlay.albedo.to_array();
+                    if ui.color_edit3("##layer_albedo", &mut overlay_albedo) {
+                        layer.overlay.albedo = glam::Vec3::from_array(overlay_albedo);
+                    }
+                    ui.text("Overlay Metallic");
+                    ui.slider("##layer_metallic", 0.0, 1.0, &mut layer.overlay.metallic);
+                    ui.text("Overlay Roughness");
+                    ui.slider("##layer_roughness", 0.0, 1.0, &mut layer.overlay.roughness);
+
+                    let mut mask_index = crate::material::WeatheringMask::ALL
+                        .iter()
+                        .position(|mask| *mask == layer.mask)
+                        .unwrap_or(0);
+                    if ui.combo("Mask##layer_mask", &mut mask_index, &crate::material::WeatheringMask::ALL, |mask| {
+                        mask.name().into()
+                    }) {
+                        layer.mask = crate::material::WeatheringMask::ALL[mask_index];
+                    }
+
+                    ui.text("Coverage");
+                    ui.slider("##layer_coverage", 0.0, 1.0, &mut layer.coverage);
+                    ui.same_line();
+                    ui.text_disabled("(0=base only, 1=overlay only)");
+                    ui.text("Blend Width");
+                    ui.slider("##layer_blend_width", 0.0, 1.0, &mut layer.blend_width);
+                    ui.same_line();
+                    ui.text_disabled("(edge softness)");
+                    ui.text("Noise Scale");
+                    ui.slider(
+                        "##layer_noise_scale",
+                        0.1,
+                        crate::material::LayeredMaterial::MAX_NOISE_SCALE,
+                        &mut layer.noise_scale,
+                    );
+                }
+
+                content.separator();
+
+                // Preset buttons
+                content.text("Presets:");
+                if ui.button("Plastic") {
+                    game.material = crate::material::MaterialProperties::plastic(game.material.albedo)
+                        .with_textures_of(&game.material);
+                }
+                ui.same_line();
+                if ui.button("Metal") {
+                    game.material = crate::material::MaterialProperties::metallic(game.material.albedo, 0.3)
+                        .with_textures_of(&game.material);
+                }
+                ui.same_line();
+                if ui.button("Matte") {
+                    game.material = crate::material::MaterialProperties::matte(game.material.albedo)
+                        .with_textures_of(&game.material);
+                }
+                if ui.button("Engine Glow") {
+                    game.material = crate::material::MaterialProperties::engine_glow()
+                        .with_textures_of(&game.material);
+                }
+                ui.same_line();
+                if ui.button("Glass") {
+                    game.material = crate::material::MaterialProperties::glass()
+                        .with_textures_of(&game.material);
+                }
+
+                content.separator();
+
+                // Save/Delete buttons
+                ui.text("Material Library:");
+                if ui.button("Save Material") {
+                    game.material_library.set(game.current_material_name.clone(), game.material.clone());
+                    if let Err(e) = game.material_library.save("config/materials.json") {
+                        crate::console::error(format!("Failed to save material library: {}", e));
+                    } else {
+                        println!("Material '{}' saved to library", game.current_material_name);
+                    }
+                }
+
+                ui.same_line();
+
+                // Can't delete default materials
+                let can_delete = game.current_material_name != "Default"
+                    && game.current_material_name != "Metal"
+                    && game.current_material_name != "Plastic"
+                    && game.current_material_name != "Matte"
+                    && game.material_library.contains(&game.current_material_name);
+
+                ui.disabled(!can_delete, || {
+                    if ui.button("Delete") {
+                        if game.material_library.remove(&game.current_material_name).is_some() {
+                            if let Err(e) = game.material_library.save("config/materials.json") {
+                                crate::console::error(format!("Failed to save material library: {}", e));
+                            } else {
+                                println!("Material '{}' deleted from library", game.current_material_name);
+                            }
+                            // Switch to default material after deleting
+                            game.current_material_name = "Default".to_string();
+                            if let Some(mat) = game.material_library.get("Default") {
+                                game.material = mat.clone();
+                            }
+                        }
+                    }
+                });
+
+                content.separator();
+
+                // Apply to the selection (objects that can't take materials are skipped)
+                let selection_count = game.scene.selection_count();
+                let targets = game.scene.selected_material_targets().len();
+                if selection_count == 0 {
+                    ui.text_disabled("No object selected");
+                } else {
+                    if let Some(selected_obj) = game.scene.selected_object() {
+                        if selection_count == 1 {
+                            ui.text(format!("Selected: {}", selected_obj.name));
+                        } else {
+                            ui.text(format!("Selected: {} objects", selection_count));
+                        }
+                    }
+
+                    let label = if selection_count == 1 { "Apply to Selected Object" } else { "Apply to All Selected" };
+                    ui.disabled(targets == 0, || {
+                        if ui.button(label) {
+                            apply_requested = true;
+                        }
+                    });
+                    if targets < selection_count {
+                        ui.text_disabled(format!("{} of {} can take materials", targets, selection_count));
+                    } else if selection_count > 1 {
+                        ui.text_disabled(format!("Affects {} objects", targets));
+                    }
+                }
+            });
+        game.material_editor_open = open;
+
+        if apply_requested {
+            let changed = game.apply_material_to_selection();
+            crate::console::info(format!("Applied material '{}' to {} object(s)", game.current_material_name, changed));
+        }
+    }
+
+    /// Build gizmo toolbar
+    pub fn build_gizmo_toolbar(ui: &Ui, game: &mut Game) {
+        GuiPanelBuilder::new(ui, "Gizmo")
+            .size(260.0, 240.0)
+            .position(630.0, 520.0)
+            .build(|content| {
+                content.text("Transform Tools");
+                content.separator();
+
+                // Gizmo mode buttons (using regular buttons as radio)
+                if ui.button("Translate (1)") {
+                    game.gizmo_state.mode = GizmoMode::Translate;
+                }
+                ui.same_line();
+                if game.gizmo_state.mode == GizmoMode::Translate {
+                    ui.text("[X]");
+                } else {
+                    ui.text("[ ]");
+                }
+
+                if ui.button("Rotate (2)") {
+                    game.gizmo_state.mode = GizmoMode::Rotate;
+                }
+                ui.same_line();
+                if game.gizmo_state.mode == GizmoMode::Rotate {
+                    ui.text("[X]");
+                } else {
+                    ui.text("[ ]");
+                }
+
+                if ui.button("Scale (3)") {
+                    game.gizmo_state.mode = GizmoMode::Scale;
+                }
+                ui.same_line();
+                if game.gizmo_state.mode == GizmoMode::Scale {
+                    ui.text("[X]");
+                } else {
+                    ui.text("[ ]");
+                }
+
+                content.separator();
+                content.checkbox("Show Gizmo", &mut game.gizmo_state.enabled);
+                Self::build_space_toggle(ui, content, game);
+                Self::build_snap_controls(content, game);
+            });
+    }
+
+    /// Local/World space toggle shared by the gizmo toolbars
+    fn build_space_toggle(ui: &Ui, content: &mut GuiContentBuilder, game: &mut Game) {
+        let next = match game.gizmo_state.space {
+            GizmoSpace::World => GizmoSpace::Local,
+            GizmoSpace::Local => GizmoSpace::World,
+        };
+        if ui.button(format!("Space: {}", game.gizmo_state.space.name())) {
+            game.gizmo_state.space = next;
+        }
+        if game.gizmo_state.mode == GizmoMode::Scale {
+            ui.same_line();
+            content.text_disabled("(scale is always local)");
+        }
+    }
+
+    /// Snap toggle and increments shared by the gizmo toolbars
+    fn build_snap_controls(content: &mut GuiContentBuilder, game: &mut Game) {
+        content.checkbox("Snap (hold Ctrl to toggle)", &mut game.gizmo_state.snap_enabled);
+        content
+            .slider_f32("Move Step", &mut game.gizmo_state.translate_snap, 0.05, 10.0)
+            .slider_f32("Rotate Step (deg)", &mut game.gizmo_state.rotate_snap, 1.0, 90.0)
+            .slider_f32("Scale Step", &mut game.gizmo_state.scale_snap, 0.01, 1.0);
+        if game.gizmo_state.snapping_active() {
+            content.text_disabled("Snapping active");
+        }
+    }
+
+    /// Build camera projection settings panel
+    pub fn build_camera_settings(ui: &Ui, game: &mut Game) {
+        let orig_mode = game.camera.projection_mode();
+        let orig_ortho_size = game.camera.ortho_size();
+        let orig_fov = game.camera.fov();
+        let orig_control_mode = game.camera.mode();
+        let orig_move_speed = game.camera.move_speed();
+        let orig_collision = game.camera.collision_enabled();
+        let orig_collision_radius = game.camera.collision_radius();
+        let orig_orbit_limits = game.camera.orbit_distance_limits();
+        let orig_dolly_at_min = game.camera.orbit_dolly_at_min();
+        let orig_clip_planes = (game.camera.near_plane(), game.camera.far_plane());
+        let orig_depth_mode = game.camera.depth_mode();
+
+        GuiPanelBuilder::new(ui, "Camera")
+            .size(240.0, 450.0)
+            .position(860.0, 650.0)
+            .build(|content| {
+                let mut mode_index = ProjectionMode::ALL
+                    .iter()
+                    .position(|mode| *mode == orig_mode)
+                    .unwrap_or(0);
+                if ui.combo("Projection", &mut mode_index, &ProjectionMode::ALL, |mode| {
+                    std::borrow::Cow::Borrowed(mode.name())
+                }) {
+                    game.camera.set_projection_mode(ProjectionMode::ALL[mode_index]);
+                }
+
+                if ui.button("Toggle (O)") {
+                    game.toggle_camera_projection();
+                }
+
+                if game.camera.projection_mode() == ProjectionMode::Orthographic {
+                    let mut ortho_size = game.camera.ortho_size();
+                    content.slider_f32("Ortho Size", &mut ortho_size, 1.0, 500.0);
+                    game.camera.set_ortho_size(ortho_size);
+                } else {
+                    let mut fov_deg = game.camera.fov().to_degrees();
+                    content.slider_f32("FOV", &mut fov_deg, 20.0, 120.0);
+                    game.camera.set_fov(fov_deg.to_radians());
+                }
+
+                // Clip planes span docking range to system scale, so both sliders are logarithmic
+                let (mut near, mut far) = (game.camera.near_plane(), game.camera.far_plane());
+                ui.slider_config("Near Plane", MIN_NEAR_PLANE, 100.0)
+                    .flags(imgui::SliderFlags::LOGARITHMIC)
+                    .display_format("%.3f")
+                    .build(&mut near);
+                ui.slider_config("Far Plane", 10.0, MAX_FAR_PLANE)
+                    .flags(imgui::SliderFlags::LOGARITHMIC)
+                    .display_format("%.0f")
+                    .build(&mut far);
+                game.camera.set_clip_planes(near, far);
+
+                let mut depth_index = DepthMode::ALL
+                    .iter()
+                    .position(|mode| *mode == orig_depth_mode)
+                    .unwrap_or(0);
+                if ui.combo("Depth", &mut depth_index, &DepthMode::ALL, |mode| {
+                    std::borrow::Cow::Borrowed(mode.name())
+                }) {
+                    game.camera.set_depth_mode(DepthMode::ALL[depth_index]);
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Logarithmic stops z-fighting far away; huge triangles near the camera may sort slightly wrong");
+                }
+
+                ui.separator();
+
+                let mut control_index = CameraMode::ALL
+                    .iter()
+                    .position(|mode| *mode == orig_control_mode)
+                    .unwrap_or(0);
+                if ui.combo("Controls", &mut control_index, &CameraMode::ALL, |mode| {
+                    std::borrow::Cow::Borrowed(mode.name())
+                }) {
+                    game.camera.set_mode(CameraMode::ALL[control_index]);
+                }
+
+                if ui.button("Toggle (C)") {
+                    game.toggle_camera_mode();
+                }
+
+                if game.camera.mode() == CameraMode::Fly {
+                    // Logarithmic so both docking speeds and system-scale travel are reachable
+                    let mut move_speed = game.camera.move_speed();
+                    ui.slider_config("Fly Speed", MIN_MOVE_SPEED, MAX_MOVE_SPEED)
+                        .flags(imgui::SliderFlags::LOGARITHMIC)
+                        .display_format("%.1f")
+                        .build(&mut move_speed);
+                    game.camera.set_move_speed(move_speed);
+                    ui.text_disabled("WASD/QE move, Shift boost, wheel speed");
+                } else {
+                    // Wheel zoom range around the orbit target (logarithmic, like the zoom itself)
+                    let (mut min_distance, mut max_distance) = game.camera.orbit_distance_limits();
+                    ui.slider_config("Min Distance", MIN_ORBIT_DISTANCE, MAX_ORBIT_DISTANCE)
+                        .flags(imgui::SliderFlags::LOGARITHMIC)
+                        .display_format("%.2f")
+                        .build(&mut min_distance);
+                    ui.slider_config("Max Distance", MIN_ORBIT_DISTANCE, MAX_ORBIT_DISTANCE)
+                        .flags(imgui::SliderFlags::LOGARITHMIC)
+                        .display_format("%.0f")
+                        .build(&mut max_distance);
+                    game.camera.set_orbit_distance_limits(min_distance, max_distance);
+
+                    let mut dolly_at_min = game.camera.orbit_dolly_at_min();
+                    if ui.checkbox("Dolly Past Min", &mut dolly_at_min) {
+                        game.camera.set_orbit_dolly_at_min(dolly_at_min);
+                    }
+                    if ui.is_item_hovered() {
+                        ui.tooltip_text("Zooming in at the minimum distance keeps moving forward");
+                    }
+
+                    // Pin the pivot to an object and track it as it moves
+                    if let Some(id) = game.followed_object() {
+                        let name = game.scene.get_object(id).map(|obj| obj.name.clone()).unwrap_or_default();
+                        ui.text(format!("Following: {}", name));
+                        if ui.button("Free Orbit (Esc)") {
+                            game.stop_following();
+                        }
+                    } else {
+                        let no_selection = ui.begin_disabled(game.scene.selected_object_id().is_none());
+                        if ui.button("Orbit Selected") {
+                            game.follow_selected();
+                        }
+                        no_selection.end();
+                    }
+                }
+
+                ui.separator();
+
+                // Stop at mesh surfaces (orbit pulls in toward the target, fly stops at hulls)
+                let mut collision = game.camera.collision_enabled();
+                if ui.checkbox("Collide With Meshes", &mut collision) {
+                    game.camera.set_collision_enabled(collision);
+                }
+                if collision {
+                    let mut radius = game.camera.collision_radius();
+                    content.slider_f32("Collision Radius", &mut radius, MIN_COLLISION_RADIUS, MAX_COLLISION_RADIUS);
+                    game.camera.set_collision_radius(radius);
+                }
+            });
+
+        if game.camera.projection_mode() != orig_mode
+            || game.camera.ortho_size() != orig_ortho_size
+            || game.camera.fov() != orig_fov
+            || game.camera.mode() != orig_control_mode
+            || game.camera.move_speed() != orig_move_speed
+            || game.camera.collision_enabled() != orig_collision
+            || game.camera.collision_radius() != orig_collision_radius
+            || game.camera.orbit_distance_limits() != orig_orbit_limits
+            || game.camera.orbit_dolly_at_min() != orig_dolly_at_min
+            || (game.camera.near_plane(), game.camera.far_plane()) != orig_clip_planes
+            || game.camera.depth_mode() != orig_depth_mode
+        {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Camera flythrough tracks: record keyframes from the current view and play them back
+    pub fn build_camera_tracks(ui: &Ui, game: &mut Game) {
+        let mut changed = false;
+        let mut play_clicked = false;
+        let mut stop_clicked = false;
+        let mut new_track_clicked = false;
+        let mut delete_track_clicked = false;
+        let mut add_keyframe_clicked = false;
+        let mut goto_keyframe: Option<usize> = None;
+        let mut swap_keyframes: Option<(usize, usize)> = None;
+        let mut remove_keyframe: Option<usize> = None;
+        let mut keyframe_time: Option<(usize, f32)> = None;
+
+        GuiPanelBuilder::new(ui, "Camera Tracks")
+            .size(300.0, 360.0)
+            .position(1110.0, 650.0)
+            .build(|content| {
+                if let Some(progress) = game.camera_track_progress() {
+                    imgui::ProgressBar::new(progress)
+                        .overlay_text(format!("{:.0}%", progress * 100.0))
+                        .build(ui);
+                    if ui.button("Stop (Esc)") {
+                        stop_clicked = true;
+                    }
+                    return;
+                }
+
+                if !game.camera_tracks.is_empty() {
+                    let mut track_index = game.selected_camera_track.min(game.camera_tracks.len() - 1);
+                    ui.combo("Track", &mut track_index, &game.camera_tracks, |track| {
+                        std::borrow::Cow::Borrowed(track.name.as_str())
+                    });
+                    game.selected_camera_track = track_index;
+                }
+
+                if ui.button("New Track") {
+                    new_track_clicked = true;
+                }
+                if !game.camera_tracks.is_empty() {
+                    ui.same_line();
+                    if ui.button("Delete Track") {
+                        delete_track_clicked = true;
+                    }
+                }
+
+                let Some(track) = game.camera_tracks.get_mut(game.selected_camera_track) else {
+                    ui.text_disabled("No tracks - add a keyframe to start one");
+                    if ui.button("Add Keyframe (current view)") {
+                        add_keyframe_clicked = true;
+                    }
+                    return;
+                };
+
+                changed |= ui.input_text("Name", &mut track.name).build();
+
+                content.separator();
+                content.header("Keyframes");
+                if ui.button("Add Keyframe (current view)") {
+                    add_keyframe_clicked = true;
+                }
+
+                let count = track.keyframes().len();
+                for (i, keyframe) in track.keyframes().iter().enumerate() {
+                    let mut time = keyframe.time;
+                    ui.set_next_item_width(70.0);
+                    if ui.input_float(format!("s##keyframe_time_{}", i), &mut time).build() {
+                        keyframe_time = Some((i, time));
+                    }
+                    ui.same_line();
+                    if ui.small_button(format!("Go##keyframe_go_{}", i)) {
+                        goto_keyframe = Some(i);
+                    }
+                    ui.same_line();
+                    if ui.small_button(format!("Up##keyframe_up_{}", i)) && i > 0 {
+                        swap_keyframes = Some((i - 1, i));
+                    }
+                    ui.same_line();
+                    if ui.small_button(format!("Down##keyframe_down_{}", i)) && i + 1 < count {
+                        swap_keyframes = Some((i, i + 1));
+                    }
+                    ui.same_line();
+                    if ui.small_button(format!("X##keyframe_remove_{}", i)) {
+                        remove_keyframe = Some(i);
+                    }
+                }
+
+                content.separator();
+                ui.text(format!("Duration: {:.1} s", track.duration()));
+                if ui.button("Play") {
+                    play_clicked = true;
+                }
+            });
+
+        if let Some(track) = game.camera_tracks.get_mut(game.selected_camera_track) {
+            if let Some((index, time)) = keyframe_time {
+                track.set_keyframe_time(index, time);
+                changed = true;
+            }
+            if let Some((a, b)) = swap_keyframes {
+                track.swap_keyframes(a, b);
+                changed = true;
+            }
+            if let Some(index) = remove_keyframe {
+                track.remove_keyframe(index);
+                changed = true;
+            }
+            if let Some(keyframe) = goto_keyframe.and_then(|index| track.keyframes().get(index).copied()) {
+                game.camera.set_pose(keyframe.position, keyframe.rotation, keyframe.fov.to_radians());
+            }
+        }
+        if changed {
+            game.mark_scene_dirty();
+        }
+
+        if new_track_clicked {
+            game.add_camera_track();
+        }
+        if delete_track_clicked {
+            game.remove_camera_track();
+        }
+        if add_keyframe_clicked {
+            game.add_camera_keyframe();
+        }
+        if play_clicked {
+            game.play_camera_track();
+        }
+        if stop_clicked {
+            game.stop_camera_track();
+        }
+    }
+
+    /// Distance fog settings (applies to meshes and stars)
+    pub fn build_fog_settings(ui: &Ui, game: &mut Game) {
+        let orig_config = game.fog_config.clone();
+
+        GuiPanelBuilder::new(ui, "Fog")
+            .size(240.0, 250.0)
+            .position(860.0, 820.0)
+            .build(|content| {
+                let fog = &mut game.fog_config;
+                content.checkbox("Enable Fog", &mut fog.enabled);
+
+                let mut mode_index = FogMode::ALL
+                    .iter()
+                    .position(|mode| *mode == fog.mode)
+                    .unwrap_or(0);
+                if ui.combo("Mode", &mut mode_index, &FogMode::ALL, |mode| mode.name().into()) {
+                    fog.mode = FogMode::ALL[mode_index];
+                }
+
+                let mut color = fog.color.to_array();
+                content.text("Color");
+                if ui.color_edit3("##fog_color", &mut color) {
+                    fog.color = glam::Vec3::from_array(color);
+                }
+
+                match fog.mode {
+                    FogMode::Exponential => {
+                        content.text("Density");
+                        ui.slider("##fog_density", 0.0, 0.1, &mut fog.density);
+                    }
+                    FogMode::Linear => {
+                        content.text("Start Distance");
+                        ui.slider("##fog_start", 0.0, 1000.0, &mut fog.start);
+                        content.text("End Distance");
+                        ui.slider("##fog_end", 0.0, 2000.0, &mut fog.end);
+                        fog.end = fog.end.max(fog.start);
+                    }
+                }
+            });
+
+        let fog = &game.fog_config;
+        if orig_config.enabled != fog.enabled
+            || orig_config.mode != fog.mode
+            || orig_config.color != fog.color
+            || orig_config.density != fog.density
+            || orig_config.start != fog.start
+            || orig_config.end != fog.end
+        {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Background settings: solid clear color, gradient, or the skybox
+    pub fn build_background_settings(ui: &Ui, game: &mut Game) {
+        let orig_config = game.background_config.clone();
+
+        GuiPanelBuilder::new(ui, "Background")
+            .size(240.0, 200.0)
+            .position(1610.0, 820.0)
+            .build(|content| {
+                let background = &mut game.background_config;
+
+                let mut mode_index = BackgroundMode::ALL
+                    .iter()
+                    .position(|mode| *mode == background.mode)
+                    .unwrap_or(0);
+                if ui.combo("Mode", &mut mode_index, &BackgroundMode::ALL, |mode| mode.name().into()) {
+                    background.mode = BackgroundMode::ALL[mode_index];
+                }
+
+                match background.mode {
+                    BackgroundMode::Gradient => {
+                        let mut top = background.top_color.to_array();
+                        if ui.color_edit3("Top", &mut top) {
+                            background.top_color = glam::Vec3::from_array(top);
+                        }
+                        let mut bottom = background.bottom_color.to_array();
+                        if ui.color_edit3("Bottom", &mut bottom) {
+                            background.bottom_color = glam::Vec3::from_array(bottom);
+                        }
+                    }
+                    BackgroundMode::SolidColor | BackgroundMode::Skybox => {
+                        let mut color = background.color.to_array();
+                        if ui.color_edit3("Color", &mut color) {
+                            background.color = glam::Vec3::from_array(color);
+                        }
+                        if background.mode == BackgroundMode::Skybox {
+                            content.text_disabled("Shown while the skybox is hidden");
+                        }
+                    }
+                }
+            });
+
+        let background = &game.background_config;
+        if orig_config.mode != background.mode
+            || orig_config.color != background.color
+            || orig_config.top_color != background.top_color
+            || orig_config.bottom_color != background.bottom_color
+        {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Display settings: swapchain present mode, frame limiter and depth prepass
+    pub fn build_display_settings(ui: &Ui, game: &mut Game) {
+        let orig_config = game.graphics_config.clone();
+
+        GuiPanelBuilder::new(ui, "Display")
+            .size(240.0, 250.0)
+            .position(1360.0, 820.0)
+            .build(|content| {
+                let graphics = &mut game.graphics_config;
+
+                // Unsupported modes fall back to FIFO with a notification
+                content.text("Present Mode");
+                let mut mode_index = PresentMode::ALL
+                    .iter()
+                    .position(|mode| *mode == graphics.present_mode)
+                    .unwrap_or(0);
+                if ui.combo("##present_mode", &mut mode_index, &PresentMode::ALL, |mode| mode.name().into()) {
+                    graphics.present_mode = PresentMode::ALL[mode_index];
+                }
+
+                content.text("FPS Cap");
+                let format = if graphics.fps_cap == 0 { "Uncapped" } else { "%d FPS" };
+                ui.slider_config("##fps_cap", 0, GraphicsConfig::MAX_FPS_CAP)
+                    .display_format(format)
+                    .build(&mut graphics.fps_cap);
+                content.text_disabled("0 = uncapped");
+
+                // Compare with the Overdraw debug view on and off
+                content.separator();
+                ui.checkbox("Depth Prepass", &mut graphics.depth_prepass);
+                content.text_disabled("Shade each mesh pixel once");
+            });
+
+        // Typed values can go out of range (Ctrl+click on a slider)
+        let graphics = &mut game.graphics_config;
+        graphics.fps_cap = graphics.fps_cap.min(GraphicsConfig::MAX_FPS_CAP);
+
+        if orig_config.present_mode != graphics.present_mode
+            || orig_config.fps_cap != graphics.fps_cap
+            || orig_config.depth_prepass != graphics.depth_prepass
+        {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Scene content summary: object counts, triangles, mesh memory and lights,
+    /// with soft budgets that turn a line orange when exceeded
+    pub fn build_scene_statistics(ui: &Ui, game: &mut Game, frame_stats: &FrameStats) {
+        const OVER_BUDGET_COLOR: [f32; 4] = [1.0, 0.6, 0.2, 1.0];
+        const OVER_CAPACITY_COLOR: [f32; 4] = [1.0, 0.3, 0.3, 1.0];
+        let orig_config = game.scene_budget_config.clone();
+
+        let counts = game.scene.count_by_category();
+        let object_count = game.scene.objects().len();
+        let point_lights = game.scene.objects().values()
+            .filter(|obj| obj.object_type == ObjectType::PointLight && game.scene.is_shown(obj))
+            .count();
+        let directional_lights = game.get_directional_light_transforms().len();
+        let memory = frame_stats.mesh_memory;
+        let megabytes = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+        GuiPanelBuilder::new(ui, "Scene Statistics")
+            .size(260.0, 420.0)
+            .position(1610.0, 380.0)
+            .build(|content| {
+                let budget = &mut game.scene_budget_config;
+                let line = |text: String, over: bool| {
+                    if over {
+                        ui.text_colored(OVER_BUDGET_COLOR, text);
+                    } else {
+                        ui.text(text);
+                    }
+                };
+
+                content.header("Content");
+                line(
+                    format!("Objects: {}", object_count),
+                    SceneBudgetConfig::exceeds(object_count as u64, budget.max_objects),
+                );
+                for (category, count) in &counts {
+                    ui.text_disabled(format!("  {}: {}", category, count));
+                }
+                line(
+                    format!("Triangles: {}", frame_stats.scene_triangles),
+                    SceneBudgetConfig::exceeds(frame_stats.scene_triangles, budget.max_triangles),
+                );
+                content.text_disabled("All shown meshes, before culling");
+
+                content.header("GPU Mesh Memory");
+                line(
+                    format!("Imported: {:.1} MB ({} buffers)", megabytes(memory.custom_bytes), memory.custom_meshes),
+                    SceneBudgetConfig::exceeds(memory.custom_bytes.div_ceil(1024 * 1024), budget.max_mesh_memory_mb),
+                );
+                content.text_disabled("Vertex + index data, LOD levels included");
+                ui.text(format!("Built-in: {:.2} MB", megabytes(memory.builtin_bytes)));
+
+                content.header("Lights");
+                let capacity = crate::core::lighting::MAX_POINT_LIGHTS;
+                let point_text = format!("Point lights: {} / {}", point_lights, capacity);
+                if point_lights > capacity {
+                    ui.text_colored(OVER_CAPACITY_COLOR, point_text);
+                    content.text_disabled(&format!("Only the first {} are shaded", capacity));
+                } else if point_lights * 10 >= capacity * 9 {
+                    ui.text_colored(OVER_BUDGET_COLOR, point_text);
+                    content.text_disabled("Close to the shader's limit");
+                } else {
+                    line(point_text, SceneBudgetConfig::exceeds(point_lights as u64, budget.max_point_lights));
+                }
+                ui.text(format!(
+                    "Directional lights: {} / {}",
+                    directional_lights,
+                    crate::core::lighting::MAX_DIRECTIONAL_LIGHTS
+                ));
+
+                content.header("Budgets (0 = none)");
+                let budget_input = |label: &str, value: &mut u32| {
+                    let mut value_i32 = (*value).min(i32::MAX as u32) as i32;
+                    if ui.input_int(label, &mut value_i32).build() {
+                        *value = value_i32.max(0) as u32;
+                    }
+                };
+                budget_input("Objects##budget", &mut budget.max_objects);
+                budget_input("Triangles##budget", &mut budget.max_triangles);
+                budget_input("Mesh MB##budget", &mut budget.max_mesh_memory_mb);
+                budget_input("Point Lights##budget", &mut budget.max_point_lights);
+            });
+
+        // Budgets past the point light capacity would never warn before lights are dropped
+        let budget = &mut game.scene_budget_config;
+        budget.max_point_lights = budget.max_point_lights.min(crate::core::lighting::MAX_POINT_LIGHTS as u32);
+
+        if orig_config != game.scene_budget_config {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Viewport settings: the editor ground grid
+    pub fn build_viewport_settings(ui: &Ui, game: &mut Game) {
+        let orig_config = game.grid_config.clone();
+        let orig_outline = game.outline_config.clone();
+        let orig_gizmo = game.gizmo_config.clone();
+        let mut reset_layout_clicked = false;
+
+        GuiPanelBuilder::new(ui, "Viewport")
+            .size(240.0, 400.0)
+            .position(1110.0, 820.0)
+            .build(|content| {
+                let grid = &mut game.grid_config;
+                content.checkbox("Show Grid", &mut grid.enabled);
+
+                // Logarithmic so both hangar-scale and system-scale layouts are reachable
+                content.text("Spacing");
+                ui.slider_config("##grid_spacing", 0.01, 10000.0)
+                    .flags(imgui::SliderFlags::LOGARITHMIC)
+                    .display_format("%.2f")
+                    .build(&mut grid.spacing);
+
+                content.text("Major Line Every");
+                ui.slider("##grid_major_every", 1, 20, &mut grid.major_every);
+
+                content.text("Fade Distance");
+                ui.slider_config("##grid_fade_distance", 10.0, 100000.0)
+                    .flags(imgui::SliderFlags::LOGARITHMIC)
+                    .display_format("%.0f")
+                    .build(&mut grid.fade_distance);
+
+                let mut color = grid.color.to_array();
+                content.text("Color");
+                if ui.color_edit3("##grid_color", &mut color) {
+                    grid.color = glam::Vec3::from_array(color);
+                }
+
+                content.separator();
+                content.header("Selection Outline");
+
+                let outline = &mut game.outline_config;
+                let mut selected_color = outline.selected_color.to_array();
+                content.text("Selected");
+                if ui.color_edit3("##outline_selected_color", &mut selected_color) {
+                    outline.selected_color = glam::Vec3::from_array(selected_color);
+                }
+
+                let mut hovered_color = outline.hovered_color.to_array();
+                content.text("Hovered");
+                if ui.color_edit3("##outline_hovered_color", &mut hovered_color) {
+                    outline.hovered_color = glam::Vec3::from_array(hovered_color);
+                }
+
+                content.text("Width (pixels)");
+                ui.slider_config("##outline_width", OutlineConfig::MIN_WIDTH, OutlineConfig::MAX_WIDTH)
+                    .display_format("%.1f")
+                    .build(&mut outline.width);
+                content.text_disabled("Hover outlines are half as wide");
+
+                content.separator();
+                content.header("Gizmo");
+
+                let gizmo = &mut game.gizmo_config;
+                content.text("Size");
+                ui.slider_config("##gizmo_screen_size", GizmoConfig::MIN_SCREEN_SIZE, GizmoConfig::MAX_SCREEN_SIZE)
+                    .display_format("%.2fx")
+                    .build(&mut gizmo.screen_size);
+                content.checkbox("Constant Pixel Size", &mut gizmo.constant_pixel_size);
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(format!(
+                        "Arrows stay {:.0} pixels long (times Size) whatever the FOV or window size",
+                        GizmoConfig::REFERENCE_PIXELS,
+                    ));
+                }
+
+                content.separator();
+                reset_layout_clicked = ui.button("Reset Layout");
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Move every panel back to its default place and size");
+                }
+            });
+
+        if reset_layout_clicked {
+            Self::reset_layout(game);
+        }
+
+        // Typed values can go out of range (Ctrl+click on a slider)
+        let grid = &mut game.grid_config;
+        grid.spacing = grid.spacing.max(0.001);
+        grid.major_every = grid.major_every.max(1);
+        grid.fade_distance = grid.fade_distance.max(1.0);
+        let outline = &mut game.outline_config;
+        outline.width = outline.width.clamp(OutlineConfig::MIN_WIDTH, OutlineConfig::MAX_WIDTH);
+        let gizmo = &mut game.gizmo_config;
+        gizmo.screen_size = gizmo.screen_size.clamp(GizmoConfig::MIN_SCREEN_SIZE, GizmoConfig::MAX_SCREEN_SIZE);
+
+        if orig_config.enabled != grid.enabled
+            || orig_config.spacing != grid.spacing
+            || orig_config.major_every != grid.major_every
+            || orig_config.fade_distance != grid.fade_distance
+            || orig_config.color != grid.color
+            || orig_outline.selected_color != game.outline_config.selected_color
+            || orig_outline.hovered_color != game.outline_config.hovered_color
+            || orig_outline.width != game.outline_config.width
+            || orig_gizmo.screen_size != game.gizmo_config.screen_size
+            || orig_gizmo.constant_pixel_size != game.gizmo_config.constant_pixel_size
+        {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Build render pass toggles panel
+    /// Build the frame-time/draw stats overlay (toggled with F3)
+    /// Scrolling log window for shader reload output and other errors (toggled with `)
+    fn build_console(ui: &Ui, game: &mut Game) {
+        // Pick up messages logged outside the game (renderer, loaders, Vulkan validation)
+        game.console.collect_pending();
+        if !game.console.open {
+            return;
+        }
+
+        let mut open = true;
+        ui.window("Console")
+            .position([10.0, 520.0], gui_builder::layout_condition())
+            .size([600.0, 220.0], gui_builder::layout_condition())
+            .opened(&mut open)
+            .build(|| {
+                if ui.button("Clear") {
+                    game.console.clear();
+                }
+                ui.same_line();
+                ui.checkbox("Auto-scroll", &mut game.console.auto_scroll);
+                ui.same_line();
+                ui.set_next_item_width(200.0);
+                ui.input_text("Filter", &mut game.console.filter).build();
+                ui.same_line();
+                let shown = game.console.filtered_entries().count();
+                ui.text_disabled(format!("{} / {}", shown, game.console.entries().count()));
+                ui.separator();
+
+                ui.child_window("##console_log").build(|| {
+                    for entry in game.console.filtered_entries() {
+                        ui.text_disabled(entry.time_label());
+                        ui.same_line();
+                        ui.text_colored(entry.level.color(), &entry.message);
+                    }
+                    // Follow new entries unless the user scrolled up
+                    if game.console.auto_scroll && ui.scroll_y() >= ui.scroll_max_y() {
+                        ui.set_scroll_here_y_with_ratio(1.0);
+                    }
+                });
+            });
+        game.console.open = open;
+    }
+
+    /// Keyboard shortcuts overlay (F1), listing every bound action by category
+    fn build_keymap_help(ui: &Ui, game: &mut Game) {
+        let display_size = ui.io().display_size;
+        let mut open = true;
+        ui.window("Keyboard Shortcuts")
+            .position([display_size[0] * 0.5, display_size[1] * 0.5], imgui::Condition::Appearing)
+            .position_pivot([0.5, 0.5])
+            .size([420.0, 560.0], imgui::Condition::Appearing)
+            .bg_alpha(0.9)
+            .opened(&mut open)
+            .build(|| {
+                for category in ActionCategory::ALL {
+                    let bound: Vec<(Action, KeyBinding)> = Action::all()
+                        .filter(|action| action.category() == category)
+                        .filter_map(|action| game.keymap.binding(action).map(|binding| (action, binding)))
+                        .collect();
+                    if bound.is_empty() {
+                        continue;
+                    }
+                    ui.text_colored([0.5, 0.8, 1.0, 1.0], category.name());
+                    for (action, binding) in bound {
+                        // KeyBinding's Display ignores width, so pad the rendered name
+                        let keys = binding.to_string();
+                        ui.text(format!("  {:<16} {}", keys, action.label()));
+                    }
+                    ui.spacing();
+                }
+                ui.separator();
+                ui.text_disabled("Unbound actions are hidden");
+                if ui.button("Remap...") {
+                    game.show_keymap_editor = true;
+                }
+                ui.same_line();
+                ui.text_disabled(format!("{} to close", game.keymap.describe(Action::ShowHelp)));
+            });
+        game.show_keymap_help = open;
+    }
+
+    /// Keymap panel: rebind, clear or reset each action's shortcut
+    fn build_keymap_editor(ui: &Ui, game: &mut Game) {
+        let orig_keymap = game.keymap.clone();
+        let mut open = true;
+        ui.window("Keymap")
+            .position([630.0, 10.0], gui_builder::layout_condition())
+            .size([460.0, 600.0], gui_builder::layout_condition())
+            .opened(&mut open)
+            .build(|| {
+                match game.keymap_capture {
+                    Some(action) => ui.text_colored(
+                        [1.0, 1.0, 0.0, 1.0],
+                        format!("Press a key for '{}' (Esc cancels)", action.label()),
+                    ),
+                    None => ui.text_disabled("Hold Ctrl/Shift while pressing to add modifiers"),
+                }
+
+                // Only a hand-edited config can bind one key twice; the first action listed wins
+                let conflicts = game.keymap.conflicts();
+                if !conflicts.is_empty() {
+                    ui.separator();
+                    ui.text_colored([1.0, 0.4, 0.2, 1.0], "Conflicting bindings:");
+                    for (binding, actions) in &conflicts {
+                        let labels: Vec<&str> = actions.iter().map(|action| action.label()).collect();
+                        ui.text_colored([1.0, 0.4, 0.2, 1.0], format!("  {}: {}", binding, labels.join(", ")));
+                    }
+                }
+
+                for category in ActionCategory::ALL {
+                    if !ui.collapsing_header(category.name(), imgui::TreeNodeFlags::DEFAULT_OPEN) {
+                        continue;
+                    }
+                    for action in Action::all().filter(|action| action.category() == category) {
+                        let _id = ui.push_id(action.id());
+                        ui.text(action.label());
+                        ui.same_line_with_pos(210.0);
+                        if game.keymap_capture == Some(action) {
+                            ui.text_colored([1.0, 1.0, 0.0, 1.0], "...");
+                        } else if game.keymap.binding(action).is_some() {
+                            ui.text(game.keymap.describe(action));
+                        } else {
+                            ui.text_disabled("unbound");
+                        }
+
+                        ui.same_line_with_pos(300.0);
+                        if ui.small_button("Rebind") {
+                            game.keymap_capture = Some(action);
+                        }
+                        ui.same_line();
+                        if ui.small_button("Clear") {
+                            game.keymap.bind(action, None);
+                        }
+                        ui.same_line();
+                        let is_default = game.keymap.binding(action) == action.default_binding();
+                        ui.disabled(is_default, || {
+                            if ui.small_button("Reset") {
+                                let displaced = game.keymap.bind(action, action.default_binding());
+                                for other in displaced {
+                                    game.add_notification(format!("{} is now unbound", other.label()), 3.0);
+                                }
+                            }
+                        });
+                    }
+                }
+
+                ui.separator();
+                if ui.button("Reset All to Defaults") {
+                    game.keymap = Keymap::default();
+                    game.keymap_capture = None;
+                }
+            });
+
+        if !open {
+            game.show_keymap_editor = false;
+            game.keymap_capture = None;
+        }
+        if game.keymap != orig_keymap {
+            game.mark_config_dirty();
+        }
+    }
+
+    /// Commands listed in the command palette. Rebuilt while the palette is open, so
+    /// mode-dependent commands and render pass toggles always match the current state.
+    fn editor_commands(game: &Game) -> CommandRegistry {
+        let mut commands = CommandRegistry::new();
+
+        commands.register("Save Scene", Self::save_scene);
+        commands.register("Load Scene", Self::load_scene);
+        commands.register("Reset Panel Layout", Self::reset_layout);
+        commands.register("Open Keymap Panel", |game| game.show_keymap_editor = true);
+        commands.register("Toggle Minimap", |game| game.show_minimap = !game.show_minimap);
+
+        if game.game_manager.is_editing() {
+            commands.register("Enter Play Mode", Game::enter_play_mode);
+            commands.register("Open Material Editor", |game| game.material_editor_open = true);
+            commands.register("Add Point Light", |game| {
+                game.add_point_light();
+            });
+            commands.register("Add Directional Light", |game| {
+                game.add_directional_light();
+            });
+            commands.register("Add Nebula", |game| {
+                game.add_nebula();
+            });
+            commands.register("Add Engine Exhaust", |game| {
+                game.add_particle_emitter();
+            });
+            for shape in PrimitiveShape::ALL {
+                commands.register(format!("Add Primitive: {}", shape.name()), move |game| {
+                    game.add_primitive(shape);
+                });
+            }
+            commands.register("Drop Selection to Ground", Game::drop_selection_to_ground);
+        } else {
+            commands.register("Return to Edit Mode", Game::exit_play_mode);
+            commands.register("Pause / Resume", |game| game.game_manager.toggle_pause());
+        }
+
+        for (name, _) in &game.render_pass_states {
+            let pass = name.clone();
+            commands.register(format!("Toggle Pass: {}", name), move |game| {
+                if let Some((_, enabled)) = game.render_pass_states.iter_mut().find(|(name, _)| *name == pass) {
+                    *enabled = !*enabled;
+                }
+            });
+        }
+
+        // Keymap actions last (held movement/tweak keys do nothing when run once)
+        for action in Action::all().filter(|action| !action.is_continuous() && *action != Action::CommandPalette) {
+            commands.register_key_action(action);
+        }
+
+        commands
+    }
+
+    /// Command palette (Ctrl+P): fuzzy-search every editor command by name, then run it
+    /// with Enter or a click. Up/Down move the highlight, Escape closes.
+    fn build_command_palette(ui: &Ui, game: &mut Game) {
+        if !game.command_palette.open {
+            return;
+        }
+
+        let commands = Self::editor_commands(game);
+        let mut chosen: Option<&EditorAction> = None;
+        let mut open = true;
+        let display_size = ui.io().display_size;
+        ui.window("Command Palette")
+            .position([display_size[0] * 0.5, 80.0], imgui::Condition::Appearing)
+            .position_pivot([0.5, 0.0])
+            .size([440.0, 360.0], imgui::Condition::Appearing)
+            .bg_alpha(0.95)
+            .collapsible(false)
+            .opened(&mut open)
+            .build(|| {
+                let palette = &mut game.command_palette;
+                if palette.focus_query {
+                    ui.set_keyboard_focus_here();
+                    palette.focus_query = false;
+                }
+                ui.set_next_item_width(-1.0);
+                if ui.input_text("##command_query", &mut palette.query).hint("Type a command").build() {
+                    palette.selected = 0;
+                }
+
+                let matches = commands.search(&palette.query);
+                let mut moved = false;
+                if !matches.is_empty() {
+                    if ui.is_key_pressed(imgui::Key::DownArrow) {
+                        palette.selected = (palette.selected + 1) % matches.len();
+                        moved = true;
+                    }
+                    if ui.is_key_pressed(imgui::Key::UpArrow) {
+                        palette.selected = (palette.selected + matches.len() - 1) % matches.len();
+                        moved = true;
+                    }
+                    palette.selected = palette.selected.min(matches.len() - 1);
+                    if ui.is_key_pressed(imgui::Key::Enter) {
+                        chosen = Some(matches[palette.selected]);
+                    }
+                }
+                if ui.is_key_pressed(imgui::Key::Escape) {
+                    palette.open = false;
+                }
+
+                ui.separator();
+                if matches.is_empty() {
+                    ui.text_disabled("No matching commands");
+                }
+                ui.child_window("##command_list").build(|| {
+                    for (i, command) in matches.iter().enumerate() {
+                        let is_selected = i == palette.selected;
+                        if ui.selectable_config(format!("{}##command{}", command.name, i)).selected(is_selected).build() {
+                            chosen = Some(*command);
+                        }
+                        if is_selected && moved {
+                            ui.set_scroll_here_y();
+                        }
+                        if let Some(binding) = command.shortcut.and_then(|action| game.keymap.binding(action)) {
+                            ui.same_line();
+                            ui.text_disabled(binding.to_string());
+                        }
+                    }
+                });
+            });
+
+        if !open {
+            game.command_palette.open = false;
+        }
+        if let Some(command) = chosen {
+            game.command_palette.open = false;
+            command.run(game);
+        }
+    }
+
+    fn build_stats_overlay(ui: &Ui, frame_stats: &FrameStats, toggle_key: &str) {
+        let frame_times = frame_stats.frame_times();
+        let average = frame_stats.average_frame_time();
+        let peak = frame_times.iter().copied().fold(0.0_f32, f32::max);
+
+        ui.window("##stats_overlay")
+            .position([ui.io().display_size[0] - 310.0, 340.0], imgui::Condition::Always)
+            .size([300.0, 0.0], imgui::Condition::Always)
+            .no_decoration()
+            .bg_alpha(0.8)
+            .build(|| {
+                ui.text_colored(
+                    [0.0, 1.0, 0.0, 1.0],
+                    format!("FPS: {:.0}  ({:.2} ms)", frame_stats.fps(), frame_stats.latest_frame_time()),
+                );
+
+                // Scale to at least 33ms so a steady frame rate doesn't look jittery
+                let overlay = format!("avg {:.2} ms / max {:.2} ms", average, peak);
+                ui.plot_lines("##frame_times", &frame_times)
+                    .graph_size([284.0, 60.0])
+                    .scale_min(0.0)
+                    .scale_max(peak.max(33.3))
+                    .overlay_text(&overlay)
+                    .build();
+
+                let stats = frame_stats.draw_stats;
+                ui.text(format!("Draw calls: {}", stats.draw_calls));
+                ui.text(format!("Triangles: {}", stats.triangles));
+                ui.text_disabled("Counts cover mesh-drawing passes");
+                ui.text(format!("Scene triangles: {}", frame_stats.scene_triangles));
+                ui.text_disabled("All shown meshes, including culled ones");
+
+                ui.separator();
+                ui.text("Pass CPU time (ms)");
+                for (name, time_ms) in &frame_stats.pass_timings {
+                    ui.text(format!("  {:<16} {:.3}", name, time_ms));
+                }
+                ui.text_disabled(format!("{} to hide", toggle_key));
+            });
+    }
+
+    /// Tactical minimap (N): top-down view of units and scene content, click or drag to
+    /// move the camera over that spot
+    fn build_minimap(ui: &Ui, game: &mut Game, minimap: TextureId) {
+        let display_size = ui.io().display_size;
+        let map_size = crate::minimap::MINIMAP_SIZE as f32;
+        let mut open = true;
+        let mut clicked = None;
+        ui.window("Minimap")
+            .position([display_size[0] - map_size - 30.0, 40.0], imgui::Condition::FirstUseEver)
+            .always_auto_resize(true)
+            .opened(&mut open)
+            .build(|| {
+                let map_min = ui.cursor_screen_pos();
+                let map_max = [map_min[0] + map_size, map_min[1] + map_size];
+                ui.invisible_button("##minimap", [map_size, map_size]);
+                ui.get_window_draw_list().add_image(minimap, map_min, map_max).build();
+                if ui.is_item_active() {
+                    let mouse = ui.io().mouse_pos;
+                    clicked = Some(glam::Vec2::new(
+                        ((mouse[0] - map_min[0]) / map_size).clamp(0.0, 1.0),
+                        ((mouse[1] - map_min[1]) / map_size).clamp(0.0, 1.0),
+                    ));
+                }
+                ui.text_disabled(format!("Click to move the camera, {} to hide", game.keymap.describe(Action::ToggleMinimap)));
+            });
+        game.show_minimap = open;
+
+        if let Some(map) = clicked {
+            let view = crate::minimap::MinimapView::for_game(game);
+            game.pan_camera_to(view.map_to_world(map, view.center.y));
+        }
+    }
+
+    pub fn build_render_pass_panel(ui: &Ui, game: &mut Game) {
+        // Store original post-process configs to detect changes
+        let orig_bloom = game.bloom_config.clone();
+        let orig_tonemap = game.tonemap_config.clone();
+        let orig_aa_mode = game.anti_aliasing_config.mode;
+        let orig_samples = game.anti_aliasing_config.samples;
+        let orig_fxaa_quality = game.anti_aliasing_config.fxaa_quality;
+        let orig_anisotropy = game.anti_aliasing_config.anisotropy;
+        let orig_wireframe = game.wireframe_config.clone();
+        let orig_lod = game.lod_config.clone();
+        let orig_ssr = game.ssr_config.clone();
+        let mut add_color_chart = false;
+
+        GuiPanelBuilder::new(ui, "Render Passes")
+            .size(220.0, 910.0)
+            .position(630.0, 650.0)
+            .build(|content| {
+                content.text("Toggle passes at runtime");
+                content.separator();
+
+                if game.render_pass_states.is_empty() {
+                    content.text_disabled("No render passes registered");
+                }
+
+                for (name, enabled) in game.render_pass_states.iter_mut() {
+                    content.checkbox(name, enabled);
+                }
+
+                content.separator();
+                let stats = game.mesh_draw_stats;
+                content.text(&format!("Meshes: {} drawn, {} culled", stats.drawn, stats.culled));
+                content.text_disabled("Disabled Skybox shows the clear color");
+                content.checkbox("Show Console", &mut game.console.open);
+                content.text_disabled("F5 recompiles and reloads shaders");
+
+                content.separator();
+                content.header("Bloom");
 
-                // Roughness slider
-                ui.text("Roughness");
-                ui.slider("##roughness", 0.0, 1.0, &mut game.material.roughness);
-                ui.same_line();
-                ui.text_disabled("(0=smooth, 1=rough)");
+                let bloom = &mut game.bloom_config;
+                content.checkbox("Enable Bloom", &mut bloom.enabled);
 
-                // Ambient lighting slider
-                ui.text("Ambient Strength");
-                ui.slider("##ambient_strength", 0.0, 2.0, &mut game.material.ambient_strength);
-                ui.same_line();
-                ui.text_disabled("(constant ambient light)");
+                content.text("Threshold");
+                ui.slider("##bloom_threshold", 0.0, 1.0, &mut bloom.threshold);
 
-                // GI strength slider
-                ui.text("GI Strength");
-                ui.slider("##gi_strength", 0.0, 1.0, &mut game.material.gi_strength);
-                ui.same_line();
-                ui.text_disabled("(environmental lighting)");
+                content.text("Intensity");
+                ui.slider("##bloom_intensity", 0.0, 3.0, &mut bloom.intensity);
+
+                content.text("Blur Passes");
+                ui.slider("##bloom_blur_passes", 1, BloomConfig::MAX_BLUR_PASSES, &mut bloom.blur_passes);
 
                 content.separator();
+                content.header("Tonemapping");
 
-                // Preset buttons
-                content.text("Presets:");
-                if ui.button("Plastic") {
-                    game.material = crate::material::MaterialProperties::plastic(game.material.albedo);
+                let tonemap = &mut game.tonemap_config;
+                content.text("Operator");
+                let mut operator_index = TonemapOperator::ALL
+                    .iter()
+                    .position(|op| *op == tonemap.operator)
+                    .unwrap_or(0);
+                if ui.combo("##tonemap_operator", &mut operator_index, &TonemapOperator::ALL, |op| op.name().into()) {
+                    tonemap.operator = TonemapOperator::ALL[operator_index];
                 }
-                ui.same_line();
-                if ui.button("Metal") {
-                    game.material = crate::material::MaterialProperties::metallic(game.material.albedo, 0.3);
+
+                content.text("Exposure");
+                ui.slider("##tonemap_exposure", 0.1, 8.0, &mut tonemap.exposure);
+
+                content.checkbox("sRGB Colors", &mut tonemap.srgb_colors);
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Picked colors are sRGB and linearized before lighting.\nOff feeds them to shaders raw (the old, washed-out look).");
                 }
-                ui.same_line();
-                if ui.button("Matte") {
-                    game.material = crate::material::MaterialProperties::matte(game.material.albedo);
+                if ui.button("Add Color Chart") {
+                    add_color_chart = true;
+                }
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("Emissive swatches of known sRGB values in front of the camera.\nWith None (Clamp), exposure 1 and bloom off they match their pickers.");
                 }
 
                 content.separator();
-
-                // Save/Delete buttons
-                ui.text("Material Library:");
-                if ui.button("Save Material") {
-                    game.material_library.set(game.current_material_name.clone(), game.material);
-                    if let Err(e) = game.material_library.save("config/materials.json") {
-                        eprintln!("Failed to save material library: {}", e);
-                    } else {
-                        println!("Material '{}' saved to library", game.current_material_name);
-                    }
+                content.header("Screen-Space Reflections");
+
+                let ssr = &mut game.ssr_config;
+                content.checkbox("Enable SSR", &mut ssr.enabled);
+                {
+                    let _disabled = ui.begin_disabled(!ssr.enabled);
+                    content.text("Max Steps");
+                    ui.slider("##ssr_max_steps", SsrConfig::MIN_STEPS, SsrConfig::MAX_STEPS, &mut ssr.max_steps);
+                    content.text("Thickness");
+                    ui.slider_config("##ssr_thickness", SsrConfig::MIN_THICKNESS, SsrConfig::MAX_THICKNESS)
+                        .flags(imgui::SliderFlags::LOGARITHMIC)
+                        .display_format("%.2f")
+                        .build(&mut ssr.thickness);
                 }
+                content.text_disabled("Metals reflect the last frame;");
+                content.text_disabled("off-screen rays use the skybox");
 
-                ui.same_line();
+                content.separator();
+                content.header("Anti-Aliasing");
 
-                // Can't delete default materials
-                let can_delete = game.current_material_name != "Default"
-                    && game.current_material_name != "Metal"
-                    && game.current_material_name != "Plastic"
-                    && game.current_material_name != "Matte"
-                    && game.material_library.contains(&game.current_material_name);
+                let anti_aliasing = &mut game.anti_aliasing_config;
+                content.text("Mode");
+                let mut mode_index = AntiAliasingMode::ALL
+                    .iter()
+                    .position(|mode| *mode == anti_aliasing.mode)
+                    .unwrap_or(0);
+                if ui.combo("##aa_mode", &mut mode_index, &AntiAliasingMode::ALL, |mode| mode.name().into()) {
+                    anti_aliasing.mode = AntiAliasingMode::ALL[mode_index];
+                }
 
-                ui.disabled(!can_delete, || {
-                    if ui.button("Delete") {
-                        if game.material_library.remove(&game.current_material_name).is_some() {
-                            if let Err(e) = game.material_library.save("config/materials.json") {
-                                eprintln!("Failed to save material library: {}", e);
-                            } else {
-                                println!("Material '{}' deleted from library", game.current_material_name);
+                // Flip between the two techniques with everything else unchanged
+                if matches!(anti_aliasing.mode, AntiAliasingMode::Fxaa | AntiAliasingMode::Msaa) {
+                    ui.same_line();
+                    if ui.small_button("Swap FXAA/MSAA") {
+                        anti_aliasing.mode = if anti_aliasing.fxaa_enabled() {
+                            AntiAliasingMode::Msaa
+                        } else {
+                            AntiAliasingMode::Fxaa
+                        };
+                    }
+                }
+
+                match anti_aliasing.mode {
+                    AntiAliasingMode::Msaa => {
+                        // MSAA level picker (clamped to what the GPU supports)
+                        content.text("MSAA Samples");
+                        for (i, samples) in AntiAliasingConfig::SAMPLE_COUNTS.iter().enumerate() {
+                            if i > 0 {
+                                ui.same_line();
                             }
-                            // Switch to default material after deleting
-                            game.current_material_name = "Default".to_string();
-                            if let Some(mat) = game.material_library.get("Default") {
-                                game.material = *mat;
+                            let label = if *samples == 1 { "Off".to_string() } else { format!("{}x", samples) };
+                            if ui.radio_button_bool(label, anti_aliasing.samples == *samples) {
+                                anti_aliasing.samples = *samples;
                             }
                         }
                     }
-                });
-
-                content.separator();
-
-                // Apply to selected object
-                if let Some(selected_obj) = game.scene.selected_object() {
-                    ui.text(format!("Selected: {}", selected_obj.name));
-                    if ui.button("Apply to Selected Object") {
-                        if let Some(obj) = game.scene.selected_object_mut() {
-                            obj.material = Some(game.current_material_name.clone());
-                            game.scene_dirty = true;
-                            println!("Applied material '{}' to '{}'", game.current_material_name, obj.name);
+                    AntiAliasingMode::Fxaa => {
+                        content.text("FXAA Quality");
+                        ui.slider_config("##fxaa_quality", 0.0, 1.0)
+                            .display_format("%.2f")
+                            .build(&mut anti_aliasing.fxaa_quality);
+                        content.checkbox("Split Compare", &mut anti_aliasing.fxaa_split);
+                        if anti_aliasing.fxaa_split {
+                            content.text_disabled("Left: FXAA, right: unfiltered");
                         }
                     }
-                } else {
-                    ui.text_disabled("No object selected");
+                    AntiAliasingMode::None => {}
                 }
-            });
-    }
-
-    /// Build gizmo toolbar
-    pub fn build_gizmo_toolbar(ui: &Ui, game: &mut Game) {
-        GuiPanelBuilder::new(ui, "Gizmo")
-            .size(200.0, 120.0)
-            .position(630.0, 520.0)
-            .build(|content| {
-                content.text("Transform Tools");
-                content.separator();
 
-                // Gizmo mode buttons (using regular buttons as radio)
-                if ui.button("Translate (1)") {
-                    game.gizmo_state.mode = GizmoMode::Translate;
+                // Anisotropic filtering for material textures (levels above the GPU limit are disabled)
+                content.text("Anisotropic Filtering");
+                for (i, level) in AntiAliasingConfig::ANISOTROPY_LEVELS.iter().enumerate() {
+                    if i > 0 {
+                        ui.same_line();
+                    }
+                    let label = if *level == 1 { "Off##aniso".to_string() } else { format!("{}x##aniso", level) };
+                    let _disabled = ui.begin_disabled(*level > game.max_anisotropy.max(1));
+                    if ui.radio_button_bool(label, anti_aliasing.anisotropy == *level) {
+                        anti_aliasing.anisotropy = *level;
+                    }
                 }
-                ui.same_line();
-                if game.gizmo_state.mode == GizmoMode::Translate {
-                    ui.text("[X]");
-                } else {
-                    ui.text("[ ]");
+                if game.max_anisotropy <= 1 {
+                    content.text_disabled("Not supported by this GPU");
                 }
 
-                if ui.button("Rotate (2)") {
-                    game.gizmo_state.mode = GizmoMode::Rotate;
-                }
-                ui.same_line();
-                if game.gizmo_state.mode == GizmoMode::Rotate {
-                    ui.text("[X]");
-                } else {
-                    ui.text("[ ]");
-                }
+                content.separator();
+                content.header("Wireframe");
 
-                if ui.button("Scale (3)") {
-                    game.gizmo_state.mode = GizmoMode::Scale;
+                let wireframe = &mut game.wireframe_config;
+                content.checkbox("Wireframe All", &mut wireframe.show_all);
+                content.text_disabled("Per-object toggle in Transform panel");
+
+                let mut color = wireframe.color.to_array();
+                content.text("Line Color");
+                if ui.color_edit3("##wireframe_color", &mut color) {
+                    wireframe.color = glam::Vec3::from_array(color);
                 }
-                ui.same_line();
-                if game.gizmo_state.mode == GizmoMode::Scale {
-                    ui.text("[X]");
-                } else {
-                    ui.text("[ ]");
+
+                content.checkbox("Show Bounds", &mut wireframe.show_bounds);
+                content.text_disabled("Mesh AABBs, selected in orange");
+
+                content.separator();
+                content.header("Level of Detail");
+
+                let lod = &mut game.lod_config;
+                content.checkbox("Enable LOD", &mut lod.enabled);
+                {
+                    let _disabled = ui.begin_disabled(!lod.enabled);
+                    // Logarithmic: useful switch distances go from close fly-bys to system scale
+                    for (i, distance) in lod.distances.iter_mut().enumerate() {
+                        content.text(&format!("LOD {} Distance", i + 1));
+                        ui.slider_config(format!("##lod_distance_{}", i + 1), 1.0, 100_000.0)
+                            .flags(imgui::SliderFlags::LOGARITHMIC)
+                            .display_format("%.0f")
+                            .build(distance);
+                    }
                 }
+                // Keep the levels in order whichever slider moved
+                lod.distances[1] = lod.distances[1].max(lod.distances[0]);
+                content.text_disabled(&format!("Generated for meshes over {} tris", LodConfig::MIN_TRIANGLES));
 
                 content.separator();
-                content.checkbox("Show Gizmo", &mut game.gizmo_state.enabled);
+                content.header("Debug View");
+
+                // Replaces mesh shading; not saved, so scenes always load with normal shading
+                let mut view_index = DebugView::ALL
+                    .iter()
+                    .position(|view| *view == game.debug_view)
+                    .unwrap_or(0);
+                if ui.combo("##debug_view", &mut view_index, &DebugView::ALL, |view| view.name().into()) {
+                    game.debug_view = DebugView::ALL[view_index];
+                }
+                if game.debug_view == DebugView::Overdraw {
+                    content.text_disabled("Brighter = more layers drawn");
+                }
+                if game.debug_view == DebugView::Lod {
+                    content.text_disabled("White full, yellow LOD 1, red LOD 2");
+                }
             });
+
+        // Detect changes
+        if orig_bloom.enabled != game.bloom_config.enabled
+            || orig_bloom.threshold != game.bloom_config.threshold
+            || orig_bloom.intensity != game.bloom_config.intensity
+            || orig_bloom.blur_passes != game.bloom_config.blur_passes
+            || orig_tonemap.operator != game.tonemap_config.operator
+            || orig_tonemap.exposure != game.tonemap_config.exposure
+            || orig_tonemap.srgb_colors != game.tonemap_config.srgb_colors
+            || orig_aa_mode != game.anti_aliasing_config.mode
+            || orig_samples != game.anti_aliasing_config.samples
+            || orig_fxaa_quality != game.anti_aliasing_config.fxaa_quality
+            || orig_anisotropy != game.anti_aliasing_config.anisotropy
+            || orig_wireframe.show_all != game.wireframe_config.show_all
+            || orig_wireframe.color != game.wireframe_config.color
+            || orig_wireframe.show_bounds != game.wireframe_config.show_bounds
+            || orig_lod.enabled != game.lod_config.enabled
+            || orig_lod.distances != game.lod_config.distances
+            || orig_ssr.enabled != game.ssr_config.enabled
+            || orig_ssr.max_steps != game.ssr_config.max_steps
+            || orig_ssr.thickness != game.ssr_config.thickness
+        {
+            game.mark_config_dirty();
+        }
+
+        if add_color_chart {
+            game.add_color_chart();
+        }
     }
 
     /// Render object hover info overlay (works in both edit and play mode)
@@ -1039,16 +3721,20 @@ impl UiManager {
         if game.game_manager.is_editing() {
             if let Some(hovered_id) = game.object_picker.hovered_object {
                 if let Some(obj) = game.scene.get_object(hovered_id) {
-                    let is_selected = game.scene.selected_object_id() == Some(hovered_id);
+                    let is_selected = game.scene.is_selected(hovered_id);
                     let label = if is_selected {
                         format!("Selected: {}", obj.name)
                     } else {
                         format!("Hovering: {}", obj.name)
                     };
 
+                    // Exact surface point when the cursor is over this object's triangles
+                    let hit = game.hover_hit.filter(|hit| hit.object_id == hovered_id);
+                    let height = if hit.is_some() { 110.0 } else { 60.0 };
+
                     ui.window("##hover_overlay")
-                        .position([10.0, ui.io().display_size[1] - 80.0], imgui::Condition::Always)
-                        .size([250.0, 60.0], imgui::Condition::Always)
+                        .position([10.0, ui.io().display_size[1] - height - 20.0], imgui::Condition::Always)
+                        .size([300.0, height], imgui::Condition::Always)
                         .no_decoration()
                         .bg_alpha(0.9)
                         .build(|| {
@@ -1058,6 +3744,11 @@ impl UiManager {
                                 ui.text_colored([1.0, 1.0, 0.0, 1.0], &label);
                                 ui.text_disabled("Click to select");
                             }
+                            if let Some(hit) = hit {
+                                ui.text(format!("Hit: ({:.2}, {:.2}, {:.2})", hit.point.x, hit.point.y, hit.point.z));
+                                ui.text(format!("Normal: ({:.2}, {:.2}, {:.2})", hit.normal.x, hit.normal.y, hit.normal.z));
+                                ui.text(format!("Distance: {:.2}", hit.distance));
+                            }
                         });
                 }
             }
@@ -1065,6 +3756,33 @@ impl UiManager {
         // Selected object info is now shown in the Transform panel (top-right)
     }
 
+    /// Label the ruler's segment with its length, at the segment's midpoint on screen
+    fn render_ruler_overlay(ui: &Ui, game: &Game, viewport_width: f32, viewport_height: f32) {
+        if !game.is_ruler_active() {
+            return;
+        }
+        let Some((start, end)) = game.ruler.segment() else {
+            return;
+        };
+
+        // Measured in 64-bit coordinates; only the label position goes through f32
+        let distance = start.distance(end);
+        let midpoint = ((start + end) * 0.5).as_vec3();
+        let view_proj = game.camera.projection_matrix(viewport_width / viewport_height) * game.camera.view_matrix();
+        let [display_width, display_height] = ui.io().display_size;
+        let Some(screen) = crate::ruler::Ruler::project_to_screen(midpoint, view_proj, display_width, display_height) else {
+            return;
+        };
+
+        let label = format!("{:.3} u", distance);
+        let size = ui.calc_text_size(&label);
+        let min = [screen.x - size[0] * 0.5 - 4.0, screen.y - size[1] - 10.0];
+        let max = [screen.x + size[0] * 0.5 + 4.0, screen.y - 6.0];
+        let draw_list = ui.get_background_draw_list();
+        draw_list.add_rect(min, max, [0.0, 0.0, 0.0, 0.75]).filled(true).rounding(3.0).build();
+        draw_list.add_text([min[0] + 4.0, min[1] + 2.0], [0.2, 0.9, 1.0, 1.0], &label);
+    }
+
     /// Render notifications in the lower right corner
     pub fn render_notifications(ui: &Ui, game: &Game) {
         let screen_width = ui.io().display_size[0];
@@ -1086,15 +3804,56 @@ impl UiManager {
     }
 
     /// Build all UI panels
-    pub fn build_ui(context: &mut Context, game: &mut Game, viewport_width: f32, viewport_height: f32) {
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_ui(
+        context: &mut Context,
+        game: &mut Game,
+        frame_stats: &FrameStats,
+        material_preview: TextureId,
+        minimap: TextureId,
+        thumbnails: &ThumbnailAtlas,
+        viewport_width: f32,
+        viewport_height: f32,
+    ) {
         let ui = context.frame();
+        gui_builder::begin_layout_frame();
+
+        // Frame-time/draw stats overlay (F3), shown in every mode
+        if game.show_stats_overlay {
+            Self::build_stats_overlay(&ui, frame_stats, &game.keymap.describe(Action::ToggleStats));
+        }
+
+        // Keyboard shortcuts overlay (F1) and remapping panel, shown in every mode
+        if game.show_keymap_help {
+            Self::build_keymap_help(&ui, game);
+        }
+        if game.show_keymap_editor {
+            Self::build_keymap_editor(&ui, game);
+        }
+
+        // Tactical minimap (N), shown in every mode
+        if game.show_minimap {
+            Self::build_minimap(&ui, game, minimap);
+        }
+
+        // Command palette (Ctrl+P), available in every mode
+        Self::build_command_palette(&ui, game);
+
+        // Restore prompt for an auto-save left by a crash
+        Self::build_autosave_recovery(&ui, game);
 
         // Show object hover/selection info overlay (edit mode and play mode)
         Self::render_object_info(&ui, game);
 
+        // Ruler distance label (edit mode)
+        Self::render_ruler_overlay(&ui, game, viewport_width, viewport_height);
+
         // Show notifications in lower right
         Self::render_notifications(&ui, game);
 
+        // Log console (opens itself when an error such as a shader compile failure is logged)
+        Self::build_console(&ui, game);
+
         // Show Play/Pause/Edit mode controls at top
         Self::build_game_mode_toolbar(&ui, game);
 
@@ -1111,14 +3870,40 @@ impl UiManager {
         // Only show edit UI when in edit mode
         if game.game_manager.is_editing() {
             // Always show scene hierarchy and transform editor in edit mode
-            Self::build_scene_hierarchy(&ui, game);
+            Self::build_scene_hierarchy(&ui, game, thumbnails);
             Self::build_transform_editor(&ui, game);
+            Self::build_prefab_browser(&ui, game);
+            Self::build_layers_panel(&ui, game);
         }
 
         // Show edit-mode-only panels
         if game.game_manager.is_editing() {
             // Show material editor if open
-            Self::build_material_editor(&ui, game);
+            Self::build_material_editor(&ui, game, material_preview);
+
+            // Render pass toggles
+            Self::build_render_pass_panel(&ui, game);
+
+            // Camera projection settings
+            Self::build_camera_settings(&ui, game);
+
+            // Camera flythrough keyframes and playback
+            Self::build_camera_tracks(&ui, game);
+
+            // Distance fog settings
+            Self::build_fog_settings(&ui, game);
+
+            // Clear color, gradient or skybox behind the scene
+            Self::build_background_settings(&ui, game);
+
+            // Ground grid settings
+            Self::build_viewport_settings(&ui, game);
+
+            // Present mode and FPS cap
+            Self::build_display_settings(&ui, game);
+
+            // Object counts, triangles, mesh memory and lights against the budgets
+            Self::build_scene_statistics(&ui, game, frame_stats);
 
             // Show object-specific panels ONLY when that object is selected
             let selected_type = game.scene.selected_object().map(|obj| obj.object_type.clone());
@@ -1128,10 +3913,12 @@ impl UiManager {
                 Some(ObjectType::Nebula) => Self::build_nebula_settings(&ui, game),
                 Some(ObjectType::Sphere) => Self::build_star_settings(&ui, game),
                 Some(ObjectType::DirectionalLight) => Self::build_directional_light_settings(&ui, game),
+                Some(ObjectType::PointLight) => Self::build_point_light_settings(&ui, game),
+                Some(ObjectType::ParticleEmitter) => Self::build_particle_emitter_settings(&ui, game),
                 Some(ObjectType::SSAO) => Self::build_ssao_settings(&ui, game),
                 Some(ObjectType::GameManager) => Self::build_game_manager_settings(&ui, game),
-                Some(ObjectType::Cube) | Some(ObjectType::Mesh(_)) => {
-                    // Mesh/Cube objects can use materials but have no extra settings panel
+                Some(ObjectType::Cube) | Some(ObjectType::Primitive(_)) | Some(ObjectType::Mesh(_)) => {
+                    // Mesh/Cube/Primitive objects can use materials but have no extra settings panel
                     // Material editor is accessed via Materials section in hierarchy
                 }
                 None => {
@@ -1145,209 +3932,269 @@ impl UiManager {
     // Config save/load helper functions
 
     fn save_skybox_config(game: &mut Game) {
-        let mut engine_config = EngineConfig::load_or_default(CONFIG_PATH);
+        // The Skybox panel also edits the star shader, so its section is saved alongside
+        let mut engine_config = EngineConfig::load_or_fallback(&game.config_path, LEGACY_CONFIG_PATH);
         engine_config.skybox = (&game.skybox_config).into();
-        if let Err(e) = engine_config.save(CONFIG_PATH) {
-            eprintln!("Failed to save skybox config: {}", e);
+        engine_config.star = (&game.star_config).into();
+        if let Err(e) = engine_config.save(&game.config_path) {
+            crate::console::error(format!("Failed to save skybox config: {}", e));
             game.add_notification("Failed to save skybox config".to_string(), 3.0);
         } else {
-            println!("Skybox config saved to {}", CONFIG_PATH);
+            println!("Skybox config saved to {}", game.config_path);
             game.config_dirty = false;
             game.add_notification("Skybox config saved".to_string(), 2.0);
         }
     }
 
     fn load_skybox_config(game: &mut Game) {
-        match EngineConfig::load(CONFIG_PATH) {
+        match EngineConfig::load(&game.config_path) {
             Ok(config) => {
                 game.skybox_config = config.skybox.into();
-                println!("Skybox config loaded from {}", CONFIG_PATH);
+                game.star_config = config.star.into();
+                println!("Skybox config loaded from {}", game.config_path);
                 game.config_dirty = false;
                 game.add_notification("Skybox config loaded".to_string(), 2.0);
             }
             Err(e) => {
-                eprintln!("Failed to load skybox config: {}", e);
+                crate::console::error(format!("Failed to load skybox config: {}", e));
                 game.add_notification("Failed to load skybox config".to_string(), 3.0);
             }
         }
     }
 
     fn save_ssao_config(game: &mut Game) {
-        let mut engine_config = EngineConfig::load_or_default(CONFIG_PATH);
+        let mut engine_config = EngineConfig::load_or_fallback(&game.config_path, LEGACY_CONFIG_PATH);
         engine_config.ssao = (&game.ssao_config).into();
-        if let Err(e) = engine_config.save(CONFIG_PATH) {
-            eprintln!("Failed to save SSAO config: {}", e);
+        if let Err(e) = engine_config.save(&game.config_path) {
+            crate::console::error(format!("Failed to save SSAO config: {}", e));
             game.add_notification("Failed to save SSAO config".to_string(), 3.0);
         } else {
-            println!("SSAO config saved to {}", CONFIG_PATH);
+            println!("SSAO config saved to {}", game.config_path);
             game.config_dirty = false;
             game.add_notification("SSAO config saved".to_string(), 2.0);
         }
     }
 
     fn load_ssao_config(game: &mut Game) {
-        match EngineConfig::load(CONFIG_PATH) {
+        match EngineConfig::load(&game.config_path) {
             Ok(config) => {
                 game.ssao_config = config.ssao.into();
-                println!("SSAO config loaded from {}", CONFIG_PATH);
+                println!("SSAO config loaded from {}", game.config_path);
                 game.config_dirty = false;
                 game.add_notification("SSAO config loaded".to_string(), 2.0);
             }
             Err(e) => {
-                eprintln!("Failed to load SSAO config: {}", e);
+                crate::console::error(format!("Failed to load SSAO config: {}", e));
                 game.add_notification("Failed to load SSAO config".to_string(), 3.0);
             }
         }
     }
 
     fn save_nebula_config(game: &mut Game) {
-        let mut engine_config = EngineConfig::load_or_default(CONFIG_PATH);
+        let mut engine_config = EngineConfig::load_or_fallback(&game.config_path, LEGACY_CONFIG_PATH);
         engine_config.nebula = (&game.nebula_config).into();
-        if let Err(e) = engine_config.save(CONFIG_PATH) {
-            eprintln!("Failed to save nebula config: {}", e);
+        if let Err(e) = engine_config.save(&game.config_path) {
+            crate::console::error(format!("Failed to save nebula config: {}", e));
             game.add_notification("Failed to save nebula config".to_string(), 3.0);
         } else {
-            println!("Nebula config saved to {}", CONFIG_PATH);
+            println!("Nebula config saved to {}", game.config_path);
             game.config_dirty = false;
             game.add_notification("Nebula config saved".to_string(), 2.0);
         }
     }
 
     fn load_nebula_config(game: &mut Game) {
-        match EngineConfig::load(CONFIG_PATH) {
+        match EngineConfig::load(&game.config_path) {
             Ok(config) => {
-                game.nebula_config = config.nebula.into();
-                game.sync_nebula_transform(); // Sync position/rotation to ECS
-                game.sync_star_to_nebula(); // Ensure star stays at nebula center
-                println!("Nebula config loaded from {}", CONFIG_PATH);
+                let nebula: NebulaConfig = config.nebula.into();
+                game.nebula_config = nebula;
+                Self::set_selected_nebula(game, nebula);
+                println!("Nebula config loaded from {}", game.config_path);
                 game.config_dirty = false;
                 game.add_notification("Nebula config loaded".to_string(), 2.0);
             }
             Err(e) => {
-                eprintln!("Failed to load nebula config: {}", e);
+                crate::console::error(format!("Failed to load nebula config: {}", e));
                 game.add_notification("Failed to load nebula config".to_string(), 3.0);
             }
         }
     }
 
     fn save_star_config(game: &mut Game) {
-        let mut engine_config = EngineConfig::load_or_default(CONFIG_PATH);
+        let mut engine_config = EngineConfig::load_or_fallback(&game.config_path, LEGACY_CONFIG_PATH);
         engine_config.star = (&game.star_config).into();
-        if let Err(e) = engine_config.save(CONFIG_PATH) {
-            eprintln!("Failed to save star config: {}", e);
+        if let Err(e) = engine_config.save(&game.config_path) {
+            crate::console::error(format!("Failed to save star config: {}", e));
             game.add_notification("Failed to save star config".to_string(), 3.0);
         } else {
-            println!("Star config saved to {}", CONFIG_PATH);
+            println!("Star config saved to {}", game.config_path);
             game.config_dirty = false;
             game.add_notification("Star config saved".to_string(), 2.0);
         }
     }
 
     fn load_star_config(game: &mut Game) {
-        match EngineConfig::load(CONFIG_PATH) {
+        match EngineConfig::load(&game.config_path) {
             Ok(config) => {
                 game.star_config = config.star.into();
-                println!("Star config loaded from {}", CONFIG_PATH);
+                println!("Star config loaded from {}", game.config_path);
                 game.config_dirty = false;
                 game.add_notification("Star config loaded".to_string(), 2.0);
             }
             Err(e) => {
-                eprintln!("Failed to load star config: {}", e);
+                crate::console::error(format!("Failed to load star config: {}", e));
                 game.add_notification("Failed to load star config".to_string(), 3.0);
             }
         }
     }
 
-    /// Load all configs from file into the game state
+    /// Load all configs of the current scene into the game state
+    /// A scene without its own config file yet starts from the shared `default.json`
     pub fn load_all_configs(game: &mut Game) {
-        match EngineConfig::load(CONFIG_PATH) {
-            Ok(config) => {
-                game.skybox_config = config.skybox.into();
-                game.nebula_config = config.nebula.into();
-                game.camera = config.camera.into();
-                game.ssao_config = config.ssao.into();
-                game.star_config = config.star.into();
-                println!("All configs loaded from {}", CONFIG_PATH);
-            }
-            Err(e) => {
-                eprintln!("Failed to load config file: {}, using defaults", e);
-            }
-        }
+        let config = EngineConfig::load_or_fallback(&game.config_path, LEGACY_CONFIG_PATH);
+        Self::apply_engine_config(game, config);
+        println!("All configs loaded for {}", game.config_path);
 
         // Load material library
         game.material_library = crate::material_library::MaterialLibrary::load_or_default("config/materials.json");
         println!("Material library loaded");
     }
 
-    /// Save all current configs to file
-    pub fn save_all_configs(game: &Game) {
-        let engine_config = EngineConfig {
+    /// Forget the saved ImGui layout and put every panel back at its default rect
+    fn reset_layout(game: &mut Game) {
+        if let Err(e) = std::fs::remove_file(IMGUI_INI_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                crate::console::warn(format!("Failed to delete {}: {}", IMGUI_INI_PATH, e));
+            }
+        }
+        gui_builder::request_layout_reset();
+        game.add_notification("Panel layout reset".to_string(), 2.0);
+    }
+
+    /// Window rect already in the config file (the engine records it on exit)
+    fn saved_window_rect(game: &Game) -> Option<WindowConfigData> {
+        EngineConfig::load(&game.config_path).ok().and_then(|config| config.window)
+    }
+
+    /// Every config section as currently edited, ready to save
+    fn current_engine_config(game: &Game) -> EngineConfig {
+        EngineConfig {
+            version: crate::config::CONFIG_VERSION,
             nebula: (&game.nebula_config).into(),
             skybox: (&game.skybox_config).into(),
             camera: (&game.camera).into(),
             ssao: (&game.ssao_config).into(),
             star: (&game.star_config).into(),
-        };
+            shadow: (&game.shadow_config).into(),
+            bloom: (&game.bloom_config).into(),
+            tonemap: (&game.tonemap_config).into(),
+            anti_aliasing: (&game.anti_aliasing_config).into(),
+            graphics: (&game.graphics_config).into(),
+            wireframe: (&game.wireframe_config).into(),
+            fog: (&game.fog_config).into(),
+            background: (&game.background_config).into(),
+            grid: (&game.grid_config).into(),
+            particles: (&game.particle_config).into(),
+            lod: (&game.lod_config).into(),
+            budget: (&game.scene_budget_config).into(),
+            outline: (&game.outline_config).into(),
+            gizmo: (&game.gizmo_config).into(),
+            ssr: (&game.ssr_config).into(),
+            keymap: (&game.keymap).into(),
+            autosave: (&game.autosave_config).into(),
+            window: Self::saved_window_rect(game),
+        }
+    }
 
-        if let Err(e) = engine_config.save(CONFIG_PATH) {
-            eprintln!("Failed to save all configs: {}", e);
+    /// Replace every config section of the game with the loaded ones
+    fn apply_engine_config(game: &mut Game, config: EngineConfig) {
+        game.skybox_config = config.skybox.into();
+        game.nebula_config = config.nebula.into();
+        game.camera = config.camera.into();
+        game.ssao_config = config.ssao.into();
+        game.star_config = config.star.into();
+        game.shadow_config = config.shadow.into();
+        game.bloom_config = config.bloom.into();
+        game.tonemap_config = config.tonemap.into();
+        game.anti_aliasing_config = config.anti_aliasing.into();
+        game.graphics_config = config.graphics.into();
+        game.wireframe_config = config.wireframe.into();
+        game.fog_config = config.fog.into();
+        game.background_config = config.background.into();
+        game.grid_config = config.grid.into();
+        game.particle_config = config.particles.into();
+        game.lod_config = config.lod.into();
+        game.scene_budget_config = config.budget.into();
+        game.outline_config = config.outline.into();
+        game.gizmo_config = config.gizmo.into();
+        game.ssr_config = config.ssr.into();
+        game.keymap = config.keymap.into();
+        game.autosave_config = config.autosave.into();
+    }
+
+    /// Save all current configs to file
+    pub fn save_all_configs(game: &Game) {
+        let engine_config = Self::current_engine_config(game);
+
+        if let Err(e) = engine_config.save(&game.config_path) {
+            crate::console::error(format!("Failed to save all configs: {}", e));
         } else {
-            println!("All configs saved to {}", CONFIG_PATH);
+            println!("All configs saved to {}", game.config_path);
         }
     }
 
     /// Save scene and configs (returns error for game to handle)
     pub fn save_scene_and_configs(game: &mut Game) -> anyhow::Result<()> {
-        // Save scene
-        let scene_data = SceneData::from_scene_graph(&game.scene);
+        // Save scene (paired with its own config file, which migrates scenes still on default.json)
+        game.config_path = SceneData::config_path_for(SCENE_PATH);
+        let mut scene_data = SceneData::from_scene_graph(&game.scene);
+        scene_data.config_path = Some(game.config_path.clone());
+        scene_data.camera_tracks = game.camera_tracks.clone();
         scene_data.save(SCENE_PATH)?;
+        game.ecs_world.save(&EcsWorld::path_for_scene(SCENE_PATH))?;
 
         // Save all configs
-        let engine_config = EngineConfig {
-            nebula: (&game.nebula_config).into(),
-            skybox: (&game.skybox_config).into(),
-            camera: (&game.camera).into(),
-            ssao: (&game.ssao_config).into(),
-            star: (&game.star_config).into(),
-        };
-        engine_config.save(CONFIG_PATH)?;
+        let engine_config = Self::current_engine_config(game);
+        engine_config.save(&game.config_path)?;
 
         // Clear dirty flags
         game.scene_dirty = false;
         game.config_dirty = false;
+        game.last_save_time = std::time::Instant::now();
 
         Ok(())
     }
 
     /// Save EVERYTHING (scene + all configs) to files
     fn save_scene(game: &mut Game) {
-        // Save scene (object transforms and hierarchy)
-        let scene_data = SceneData::from_scene_graph(&game.scene);
-        let scene_result = scene_data.save(SCENE_PATH);
+        // Save scene (object transforms and hierarchy), paired with its own config file
+        // (this migrates scenes that still share default.json)
+        game.config_path = SceneData::config_path_for(SCENE_PATH);
+        let mut scene_data = SceneData::from_scene_graph(&game.scene);
+        scene_data.config_path = Some(game.config_path.clone());
+        scene_data.camera_tracks = game.camera_tracks.clone();
+        let scene_result = scene_data
+            .save(SCENE_PATH)
+            .and_then(|_| game.ecs_world.save(&EcsWorld::path_for_scene(SCENE_PATH)));
 
         // Save all configs (skybox, nebula, camera, SSAO, star)
-        let engine_config = EngineConfig {
-            nebula: (&game.nebula_config).into(),
-            skybox: (&game.skybox_config).into(),
-            camera: (&game.camera).into(),
-            ssao: (&game.ssao_config).into(),
-            star: (&game.star_config).into(),
-        };
-        let config_result = engine_config.save(CONFIG_PATH);
+        let engine_config = Self::current_engine_config(game);
+        let config_result = engine_config.save(&game.config_path);
 
         // Report results
         if scene_result.is_err() || config_result.is_err() {
             if let Err(e) = scene_result {
-                eprintln!("Failed to save scene: {}", e);
+                crate::console::error(format!("Failed to save scene: {}", e));
             }
             if let Err(e) = config_result {
-                eprintln!("Failed to save configs: {}", e);
+                crate::console::error(format!("Failed to save configs: {}", e));
             }
             game.add_notification("Failed to save".to_string(), 3.0);
         } else {
             println!("Scene and configs saved");
             game.scene_dirty = false;
             game.config_dirty = false;
+            game.last_save_time = std::time::Instant::now();
             game.add_notification("Everything saved!".to_string(), 2.0);
         }
     }
@@ -1355,70 +4202,173 @@ impl UiManager {
     /// Load EVERYTHING (scene + all configs) from files
     fn load_scene(game: &mut Game) {
         let mut success = true;
+        let mut ids_migrated = false;
 
         // Load scene
         match SceneData::load(SCENE_PATH) {
             Ok(scene_data) => {
                 game.scene = scene_data.to_scene_graph();
+                game.config_path = scene_data.effective_config_path().to_string();
+                ids_migrated = scene_data.ids_migrated;
+                if let Err(e) = Self::load_ecs_world(game, SCENE_PATH) {
+                    eprintln!("Failed to load ECS world: {}", e);
+                    success = false;
+                }
+                game.stop_camera_track();
+                game.camera_tracks = scene_data.camera_tracks;
+                game.selected_camera_track = 0;
                 game.sync_nebula_transform(); // Sync nebula transform to ECS
                 game.sync_star_to_nebula(); // Ensure star stays at nebula center
                 println!("Scene loaded from {}", SCENE_PATH);
             }
             Err(e) => {
-                eprintln!("Failed to load scene: {}", e);
+                crate::console::error(format!("Failed to load scene: {}", e));
                 success = false;
             }
         }
 
-        // Load all configs
-        match EngineConfig::load(CONFIG_PATH) {
-            Ok(config) => {
-                game.skybox_config = config.skybox.into();
-                game.nebula_config = config.nebula.into();
-                game.camera = config.camera.into();
-                game.ssao_config = config.ssao.into();
-                println!("All configs loaded from {}", CONFIG_PATH);
-            }
-            Err(e) => {
-                eprintln!("Failed to load configs: {}", e);
-                success = false;
-            }
-        }
+        // Load the scene's configs (missing file falls back to default.json, then built-in defaults)
+        let config = EngineConfig::load_or_fallback(&game.config_path, LEGACY_CONFIG_PATH);
+        Self::apply_engine_config(game, config);
+        println!("All configs loaded for {}", game.config_path);
 
         if success {
-            game.scene_dirty = false;
+            // Migrated IDs only reach the file with the next save
+            game.scene_dirty = ids_migrated;
             game.config_dirty = false;
+            game.last_save_time = std::time::Instant::now();
             game.add_notification("Everything loaded!".to_string(), 2.0);
         } else {
             game.add_notification("Failed to load".to_string(), 3.0);
         }
     }
 
+    /// Write unsaved edits to the auto-save files in the background. The scene and configs
+    /// are serialized here (cheap); only the file writes leave the frame.
+    pub fn autosave(game: &mut Game) {
+        let config_path = SceneData::config_path_for(AUTOSAVE_SCENE_PATH);
+        let mut scene_data = SceneData::from_scene_graph(&game.scene);
+        scene_data.config_path = Some(config_path.clone());
+        scene_data.camera_tracks = game.camera_tracks.clone();
+
+        let serialized = serde_json::to_string_pretty(&scene_data)
+            .and_then(|scene| Ok((scene, serde_json::to_string_pretty(&Self::current_engine_config(game))?)));
+        match serialized {
+            Ok((scene, config)) => {
+                let files = vec![(AUTOSAVE_SCENE_PATH.to_string(), scene), (config_path, config)];
+                if crate::autosave::write_in_background(files) {
+                    game.last_save_time = std::time::Instant::now();
+                    crate::console::info(format!("Auto-saved to {}", AUTOSAVE_SCENE_PATH));
+                }
+            }
+            Err(e) => {
+                crate::console::error(format!("Auto-save failed: {}", e));
+                // Don't retry every frame
+                game.last_save_time = std::time::Instant::now();
+            }
+        }
+    }
+
+    /// Offer to restore an auto-save left newer than the saved scene (e.g. by a crash)
+    pub fn check_for_autosave(game: &mut Game) {
+        game.autosave_recovery = crate::autosave::newer_autosave(SCENE_PATH);
+        if game.autosave_recovery.is_some() {
+            crate::console::warn(format!("{} is newer than {}", AUTOSAVE_SCENE_PATH, SCENE_PATH));
+        }
+    }
+
+    /// Load the auto-save over the current scene. The scene keeps its own file paths and
+    /// stays dirty, so nothing reaches `SCENE_PATH` until the user saves.
+    fn restore_autosave(game: &mut Game) {
+        let scene_data = match SceneData::load(AUTOSAVE_SCENE_PATH) {
+            Ok(scene_data) => scene_data,
+            Err(e) => {
+                crate::console::error(format!("Failed to load auto-save: {}", e));
+                game.add_notification("Failed to restore auto-save".to_string(), 3.0);
+                return;
+            }
+        };
+        game.scene = scene_data.to_scene_graph();
+        game.stop_camera_track();
+        game.camera_tracks = scene_data.camera_tracks;
+        game.selected_camera_track = 0;
+        game.sync_nebula_transform();
+        game.sync_star_to_nebula();
+
+        let config = EngineConfig::load_or_fallback(&SceneData::config_path_for(AUTOSAVE_SCENE_PATH), &game.config_path);
+        Self::apply_engine_config(game, config);
+
+        game.scene_dirty = true;
+        game.config_dirty = true;
+        game.last_save_time = std::time::Instant::now();
+        game.add_notification("Auto-save restored - save to keep it".to_string(), 4.0);
+    }
+
+    /// Startup dialog offering to restore a newer auto-save
+    fn build_autosave_recovery(ui: &Ui, game: &mut Game) {
+        let Some(saved_at) = game.autosave_recovery else {
+            return;
+        };
+        let minutes_ago = saved_at.elapsed().map_or(0, |age| age.as_secs() / 60);
+        let display_size = ui.io().display_size;
+        let mut choice = None;
+        ui.window("Recover Auto-Save")
+            .position([display_size[0] * 0.5, display_size[1] * 0.4], imgui::Condition::Appearing)
+            .position_pivot([0.5, 0.5])
+            .always_auto_resize(true)
+            .collapsible(false)
+            .build(|| {
+                ui.text(format!("An auto-save from {} min ago is newer than {}.", minutes_ago, SCENE_PATH));
+                ui.text_disabled("The editor may not have closed cleanly.");
+                if ui.button("Restore") {
+                    choice = Some(true);
+                }
+                ui.same_line();
+                if ui.button("Discard") {
+                    choice = Some(false);
+                }
+            });
+
+        if let Some(restore) = choice {
+            game.autosave_recovery = None;
+            if restore {
+                Self::restore_autosave(game);
+            } else {
+                crate::autosave::remove();
+                game.add_notification("Auto-save discarded".to_string(), 2.0);
+            }
+        }
+    }
+
     /// Load scene on startup with intelligent merging
     pub fn load_scene_on_startup(game: &mut Game) {
         let scene_data = SceneData::load_and_merge_with_default(SCENE_PATH);
         game.scene = scene_data.to_scene_graph();
+        // Migrated IDs only reach the file with the next save
+        game.scene_dirty = scene_data.ids_migrated;
+        game.config_path = scene_data.effective_config_path().to_string();
+        game.camera_tracks = scene_data.camera_tracks;
 
         // Ensure SSAO singleton always exists (add if missing)
         if game.scene.find_by_type(crate::scene::ObjectType::SSAO).is_none() {
             game.scene.add_object("SSAO".to_string(), crate::scene::ObjectType::SSAO);
         }
 
-        // Ensure DirectionalLight singleton always exists (add if missing, mark as editor-only)
+        // Ensure at least one directional light exists (the shadow-casting key light)
         if game.scene.find_by_type(crate::scene::ObjectType::DirectionalLight).is_none() {
-            let light_id = game.scene.add_object("Directional Light".to_string(), crate::scene::ObjectType::DirectionalLight);
+            game.scene.add_object("Directional Light".to_string(), crate::scene::ObjectType::DirectionalLight);
+        }
+        // Light visualizations are editor-only
+        for light_id in game.scene.get_by_type(crate::scene::ObjectType::DirectionalLight) {
             if let Some(light_obj) = game.scene.get_object_mut(light_id) {
-                light_obj.editor_only = true; // Light visualization is editor-only
-            }
-        } else {
-            // If it already exists, make sure it's marked editor-only
-            if let Some(light_id) = game.scene.find_by_type(crate::scene::ObjectType::DirectionalLight) {
-                if let Some(light_obj) = game.scene.get_object_mut(light_id) {
-                    light_obj.editor_only = true;
-                }
+                light_obj.editor_only = true;
             }
         }
 
+        if let Err(e) = Self::load_ecs_world(game, SCENE_PATH) {
+            eprintln!("Failed to load ECS world: {}", e);
+        }
+
         // Sync nebula transform from loaded scene to ECS
         game.sync_nebula_transform();
         // Ensure star is always at nebula center
@@ -1426,4 +4376,14 @@ impl UiManager {
 
         println!("Scene initialized from {}", SCENE_PATH);
     }
+
+    /// Replace the game's ECS world with the one saved alongside `scene_path`, if any
+    /// (scenes saved before the world was persisted keep the default entities)
+    fn load_ecs_world(game: &mut Game, scene_path: &str) -> anyhow::Result<()> {
+        let path = EcsWorld::path_for_scene(scene_path);
+        if std::path::Path::new(&path).exists() {
+            game.replace_ecs_world(EcsWorld::load(&path)?);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,278 @@
+use glam::{Mat4, Quat, Vec2, Vec3};
+use crate::gizmo::Ray;
+use crate::mesh::Vertex;
+
+/// Side length of the view cube's screen region in pixels
+pub const VIEW_CUBE_SIZE: f32 = 110.0;
+/// Gap between the view cube region and the bottom-right screen corner in pixels
+const VIEW_CUBE_MARGIN: f32 = 10.0;
+/// Half-extent of the orthographic box the unit cube is drawn in (fits the cube's diagonal)
+const VIEW_CUBE_ORTHO_EXTENT: f32 = 0.9;
+/// Distance from the cube center to the view cube's eye
+const VIEW_CUBE_EYE_DISTANCE: f32 = 2.0;
+/// Normal length (and so gizmo color brightness) of the negative faces
+const NEGATIVE_FACE_SHADE: f32 = 0.55;
+
+/// A face of the view cube, named by its outward axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewCubeFace {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl ViewCubeFace {
+    /// All faces in mesh order (face `i` uses indices `i * 6 .. i * 6 + 6`)
+    pub const ALL: [ViewCubeFace; 6] = [
+        ViewCubeFace::PosX,
+        ViewCubeFace::NegX,
+        ViewCubeFace::PosY,
+        ViewCubeFace::NegY,
+        ViewCubeFace::PosZ,
+        ViewCubeFace::NegZ,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ViewCubeFace::PosX => "Right (+X)",
+            ViewCubeFace::NegX => "Left (-X)",
+            ViewCubeFace::PosY => "Top (+Y)",
+            ViewCubeFace::NegY => "Bottom (-Y)",
+            ViewCubeFace::PosZ => "Front (+Z)",
+            ViewCubeFace::NegZ => "Back (-Z)",
+        }
+    }
+
+    /// Outward unit normal
+    pub fn normal(&self) -> Vec3 {
+        match self {
+            ViewCubeFace::PosX => Vec3::X,
+            ViewCubeFace::NegX => Vec3::NEG_X,
+            ViewCubeFace::PosY => Vec3::Y,
+            ViewCubeFace::NegY => Vec3::NEG_Y,
+            ViewCubeFace::PosZ => Vec3::Z,
+            ViewCubeFace::NegZ => Vec3::NEG_Z,
+        }
+    }
+
+    /// Index of the face in `ALL` (and in the mesh)
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|face| face == self).unwrap_or(0)
+    }
+
+    /// Hovered-axis code for gizmo.vert (1..3 = +X/+Y/+Z, -1..-3 = -X/-Y/-Z)
+    pub fn hover_code(&self) -> i32 {
+        match self {
+            ViewCubeFace::PosX => 1,
+            ViewCubeFace::PosY => 2,
+            ViewCubeFace::PosZ => 3,
+            ViewCubeFace::NegX => -1,
+            ViewCubeFace::NegY => -2,
+            ViewCubeFace::NegZ => -3,
+        }
+    }
+
+    /// Camera (pitch, yaw) looking at this face, i.e. down the opposite axis
+    pub fn camera_angles(&self) -> (f32, f32) {
+        use std::f32::consts::{FRAC_PI_2, PI};
+
+        match self {
+            ViewCubeFace::PosX => (0.0, FRAC_PI_2),
+            ViewCubeFace::NegX => (0.0, -FRAC_PI_2),
+            ViewCubeFace::PosY => (-FRAC_PI_2, 0.0),
+            ViewCubeFace::NegY => (FRAC_PI_2, 0.0),
+            ViewCubeFace::PosZ => (0.0, 0.0),
+            ViewCubeFace::NegZ => (0.0, PI),
+        }
+    }
+
+    /// Face whose axis dominates a point on the cube surface
+    fn from_point(point: Vec3) -> Self {
+        let abs = point.abs();
+        if abs.x >= abs.y && abs.x >= abs.z {
+            if point.x >= 0.0 { ViewCubeFace::PosX } else { ViewCubeFace::NegX }
+        } else if abs.y >= abs.z {
+            if point.y >= 0.0 { ViewCubeFace::PosY } else { ViewCubeFace::NegY }
+        } else if point.z >= 0.0 {
+            ViewCubeFace::PosZ
+        } else {
+            ViewCubeFace::NegZ
+        }
+    }
+}
+
+/// Camera orientation widget in the bottom-right corner of the viewport
+///
+/// A unit cube drawn with the gizmo pipeline, rotated with the camera and
+/// projected into a fixed-size screen region. Clicking a face snaps the
+/// camera to look down that axis.
+pub struct ViewCube {
+    pub enabled: bool,
+    pub hovered_face: Option<ViewCubeFace>,
+}
+
+impl ViewCube {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            hovered_face: None,
+        }
+    }
+
+    /// Top-left corner of the view cube's screen region (the region is `VIEW_CUBE_SIZE` square)
+    pub fn region_origin(viewport_width: f32, viewport_height: f32) -> Vec2 {
+        Vec2::new(
+            viewport_width - VIEW_CUBE_SIZE - VIEW_CUBE_MARGIN,
+            viewport_height - VIEW_CUBE_SIZE - VIEW_CUBE_MARGIN,
+        )
+    }
+
+    /// Whether a screen position is inside the view cube region
+    pub fn contains(mouse_x: f32, mouse_y: f32, viewport_width: f32, viewport_height: f32) -> bool {
+        let origin = Self::region_origin(viewport_width, viewport_height);
+        mouse_x >= origin.x
+            && mouse_y >= origin.y
+            && mouse_x <= origin.x + VIEW_CUBE_SIZE
+            && mouse_y <= origin.y + VIEW_CUBE_SIZE
+    }
+
+    /// View matrix: the camera's orientation, looking at the cube from a fixed distance
+    pub fn view_matrix(camera_rotation: Quat) -> Mat4 {
+        let eye = camera_rotation * Vec3::Z * VIEW_CUBE_EYE_DISTANCE;
+        Mat4::look_at_rh(eye, Vec3::ZERO, camera_rotation * Vec3::Y)
+    }
+
+    /// Orthographic projection squeezed into the view cube's screen region,
+    /// so the full-screen gizmo pipeline can draw it without a viewport change
+    pub fn projection_matrix(viewport_width: f32, viewport_height: f32) -> Mat4 {
+        let extent = VIEW_CUBE_ORTHO_EXTENT;
+        let mut ortho = Mat4::orthographic_rh(-extent, extent, -extent, extent, 0.1, VIEW_CUBE_EYE_DISTANCE * 2.0);
+        // Flip Y for Vulkan coordinate system
+        ortho.y_axis.y *= -1.0;
+
+        // Map the [-1, 1] square onto the region (Vulkan NDC: y points down like screen space)
+        let center = Self::region_origin(viewport_width, viewport_height) + Vec2::splat(VIEW_CUBE_SIZE * 0.5);
+        let ndc_center = Vec3::new(
+            center.x / viewport_width * 2.0 - 1.0,
+            center.y / viewport_height * 2.0 - 1.0,
+            0.0,
+        );
+        let ndc_scale = Vec3::new(VIEW_CUBE_SIZE / viewport_width, VIEW_CUBE_SIZE / viewport_height, 1.0);
+
+        Mat4::from_translation(ndc_center) * Mat4::from_scale(ndc_scale) * ortho
+    }
+
+    /// Face under the mouse, if any
+    pub fn pick_face(
+        mouse_x: f32,
+        mouse_y: f32,
+        viewport_width: f32,
+        viewport_height: f32,
+        camera_rotation: Quat,
+    ) -> Option<ViewCubeFace> {
+        if !Self::contains(mouse_x, mouse_y, viewport_width, viewport_height) {
+            return None;
+        }
+
+        let ray = Ray::from_screen(
+            mouse_x,
+            mouse_y,
+            viewport_width,
+            viewport_height,
+            Self::view_matrix(camera_rotation),
+            Self::projection_matrix(viewport_width, viewport_height),
+        );
+        let distance = ray.aabb_distance(Vec3::splat(-0.5), Vec3::splat(0.5))?;
+
+        Some(ViewCubeFace::from_point(ray.origin + ray.direction * distance))
+    }
+
+    /// Faces pointing toward the camera. The gizmo pipeline ignores depth, so only
+    /// these are drawn (front faces of a cube never overlap).
+    pub fn visible_faces(camera_rotation: Quat) -> Vec<ViewCubeFace> {
+        let to_camera = camera_rotation * Vec3::Z;
+        ViewCubeFace::ALL
+            .into_iter()
+            .filter(|face| face.normal().dot(to_camera) > 1e-4)
+            .collect()
+    }
+
+    /// Unit cube with 4 vertices and 6 indices per face, in `ViewCubeFace::ALL` order.
+    /// Normals double as gizmo colors: positive faces are full-bright, negative faces darker.
+    pub fn generate_mesh() -> (Vec<Vertex>, Vec<u32>) {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for face in ViewCubeFace::ALL {
+            let normal = face.normal();
+            let (u, v) = normal.any_orthonormal_pair();
+            let shade = if normal.max_element() > 0.0 { 1.0 } else { NEGATIVE_FACE_SHADE };
+
+            let base = vertices.len() as u32;
+            for (su, sv) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                vertices.push(Vertex {
+                    position: (normal + u * su + v * sv) * 0.5,
+                    normal: normal * shade,
+                    uv: Vec2::new((su + 1.0) * 0.5, (sv + 1.0) * 0.5),
+                    tangent: Vec3::ZERO,
+                    color: Vec3::ONE,
+                });
+            }
+            indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+
+        (vertices, indices)
+    }
+}
+
+impl Default for ViewCube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: f32 = 1280.0;
+    const HEIGHT: f32 = 720.0;
+
+    fn camera_rotation(face: ViewCubeFace) -> Quat {
+        let (pitch, yaw) = face.camera_angles();
+        Quat::from_euler(glam::EulerRot::YXZ, yaw, pitch, 0.0)
+    }
+
+    #[test]
+    fn test_camera_angles_look_down_the_face_axis() {
+        for face in ViewCubeFace::ALL {
+            let forward = camera_rotation(face) * Vec3::NEG_Z;
+            assert!(forward.abs_diff_eq(-face.normal(), 1e-5), "{:?} looks along {:?}", face, forward);
+        }
+    }
+
+    #[test]
+    fn test_pick_center_hits_facing_face() {
+        let center = ViewCube::region_origin(WIDTH, HEIGHT) + Vec2::splat(VIEW_CUBE_SIZE * 0.5);
+
+        for face in ViewCubeFace::ALL {
+            let picked = ViewCube::pick_face(center.x, center.y, WIDTH, HEIGHT, camera_rotation(face));
+            assert_eq!(picked, Some(face));
+        }
+
+        // Outside the region nothing is picked
+        assert_eq!(ViewCube::pick_face(10.0, 10.0, WIDTH, HEIGHT, Quat::IDENTITY), None);
+    }
+
+    #[test]
+    fn test_visible_faces() {
+        assert_eq!(ViewCube::visible_faces(Quat::IDENTITY), vec![ViewCubeFace::PosZ]);
+
+        // Looking down a corner diagonal shows three faces
+        let corner = Quat::from_euler(glam::EulerRot::YXZ, 45f32.to_radians(), -35f32.to_radians(), 0.0);
+        assert_eq!(ViewCube::visible_faces(corner).len(), 3);
+    }
+}
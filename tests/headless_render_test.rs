@@ -0,0 +1,80 @@
+/// Golden-image test of the headless renderer
+/// Runs `tribal-engine --headless` on the built-in cube scene and compares the frame against
+/// tests/golden/headless_cube.png. Skips when the machine has no Vulkan device.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Exit code of `--headless` when no Vulkan device is available (headless::NO_DEVICE_EXIT_CODE)
+const NO_DEVICE_EXIT_CODE: i32 = 77;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+
+/// A channel may drift this much between drivers before the pixel counts as different
+const CHANNEL_TOLERANCE: u8 = 8;
+
+/// Fraction of pixels allowed to differ (edge rasterization varies slightly across GPUs)
+const MAX_DIFFERING_FRACTION: f64 = 0.01;
+
+fn golden_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/headless_cube.png")
+}
+
+/// Fraction of pixels whose channels differ by more than `CHANNEL_TOLERANCE`
+fn differing_fraction(actual: &image::RgbaImage, expected: &image::RgbaImage) -> f64 {
+    let differing = actual
+        .pixels()
+        .zip(expected.pixels())
+        .filter(|(a, e)| a.0.iter().zip(e.0.iter()).any(|(a, e)| a.abs_diff(*e) > CHANNEL_TOLERANCE))
+        .count();
+    differing as f64 / (actual.width() * actual.height()) as f64
+}
+
+#[test]
+fn test_headless_cube_matches_golden_image() {
+    let output = Path::new(env!("CARGO_TARGET_TMPDIR")).join("headless_cube.png");
+    let status = Command::new(env!("CARGO_BIN_EXE_tribal-engine"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .arg("--headless")
+        .arg(format!("{}x{}", WIDTH, HEIGHT))
+        .arg(&output)
+        .status()
+        .expect("failed to launch the engine");
+
+    if status.code() == Some(NO_DEVICE_EXIT_CODE) {
+        eprintln!("Skipping golden-image test: no Vulkan device available");
+        return;
+    }
+    assert!(status.success(), "headless render failed: {}", status);
+
+    let actual = image::open(&output).expect("headless frame wasn't written").to_rgba8();
+    assert_eq!(actual.dimensions(), (WIDTH, HEIGHT));
+
+    // Bless a new golden image only when asked to
+    let golden = golden_path();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(golden.parent().unwrap()).unwrap();
+        std::fs::copy(&output, &golden).unwrap();
+        eprintln!("Wrote golden image {}", golden.display());
+        return;
+    }
+    assert!(
+        golden.exists(),
+        "golden image {} is missing; run with UPDATE_GOLDEN=1 to create it from {}",
+        golden.display(),
+        output.display(),
+    );
+
+    let expected = image::open(&golden).unwrap().to_rgba8();
+    assert_eq!(actual.dimensions(), expected.dimensions(), "golden image size changed");
+
+    let fraction = differing_fraction(&actual, &expected);
+    assert!(
+        fraction <= MAX_DIFFERING_FRACTION,
+        "{:.2}% of pixels differ from {} (actual frame: {})",
+        fraction * 100.0,
+        golden.display(),
+        output.display(),
+    );
+}